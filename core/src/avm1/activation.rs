@@ -1202,7 +1202,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                             self.context.player.clone(),
                             level,
                             Request::get(url),
-                            None,
+                            Some(self.base_clip().movie().url().to_string()),
                             None,
                             None,
                         );
@@ -1331,7 +1331,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                         self.context.player.clone(),
                         clip_target,
                         request,
-                        None,
+                        Some(self.base_clip().movie().url().to_string()),
                         None,
                         None,
                     );
@@ -1352,7 +1352,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                         self.context.player.clone(),
                         clip_target,
                         Request::get(url.to_utf8_lossy().into_owned()),
-                        None,
+                        Some(self.base_clip().movie().url().to_string()),
                         None,
                         None,
                     );
@@ -0,0 +1,328 @@
+//! Conversion of AVM1 filter objects into the filters of the render backend.
+
+use crate::avm1::object::NativeObject;
+use crate::avm1::{Activation, Error, Object, TObject, Value};
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+use swf::{
+    BevelFilter, BevelFilterFlags, BlurFilter, BlurFilterFlags, Color, ColorMatrixFilter,
+    ConvolutionFilter, ConvolutionFilterFlags, DropShadowFilter, DropShadowFilterFlags, Fixed16,
+    Fixed8, GlowFilter, GlowFilterFlags, GradientFilter, GradientFilterFlags, GradientRecord,
+};
+
+/// Converts an AVM1 filter object, returning `None` if the object isn't a filter.
+pub fn avm1_to_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Option<Filter>, Error<'gc>> {
+    let filter = match object.native() {
+        NativeObject::BevelFilter(_) => avm1_to_bevel_filter(activation, object)?,
+        NativeObject::BlurFilter(_) => avm1_to_blur_filter(activation, object)?,
+        _ if object.as_color_matrix_filter_object().is_some() => {
+            avm1_to_color_matrix_filter(activation, object)?
+        }
+        _ if object.as_convolution_filter_object().is_some() => {
+            avm1_to_convolution_filter(activation, object)?
+        }
+        _ if object.as_displacement_map_filter_object().is_some() => {
+            avm1_to_displacement_map_filter(activation, object)?
+        }
+        _ if object.as_drop_shadow_filter_object().is_some() => {
+            avm1_to_drop_shadow_filter(activation, object)?
+        }
+        _ if object.as_glow_filter_object().is_some() => avm1_to_glow_filter(activation, object)?,
+        _ if object.as_gradient_bevel_filter_object().is_some() => {
+            Filter::GradientBevelFilter(avm1_to_gradient_filter(activation, object)?)
+        }
+        _ if object.as_gradient_glow_filter_object().is_some() => {
+            Filter::GradientGlowFilter(avm1_to_gradient_filter(activation, object)?)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(filter))
+}
+
+fn get_f64<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<f64, Error<'gc>> {
+    object.get(name, activation)?.coerce_to_f64(activation)
+}
+
+fn get_u32<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<u32, Error<'gc>> {
+    object.get(name, activation)?.coerce_to_u32(activation)
+}
+
+fn get_bool<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<bool, Error<'gc>> {
+    Ok(object
+        .get(name, activation)?
+        .as_bool(activation.swf_version()))
+}
+
+/// Reads a color from its `color` and `alpha` properties.
+fn get_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    color: &'static str,
+    alpha: &'static str,
+) -> Result<Color, Error<'gc>> {
+    let rgb = get_u32(activation, object, color)?;
+    let alpha = get_f64(activation, object, alpha)?;
+    Ok(Color::from_rgb(rgb, (alpha.clamp(0.0, 1.0) * 255.0) as u8))
+}
+
+fn get_numbers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<Vec<f64>, Error<'gc>> {
+    let mut numbers = vec![];
+    if let Value::Object(array) = object.get(name, activation)? {
+        let length = array.length(activation)?;
+        for i in 0..length {
+            numbers.push(array.get_element(activation, i).coerce_to_f64(activation)?);
+        }
+    }
+    Ok(numbers)
+}
+
+fn get_passes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<u8, Error<'gc>> {
+    Ok(get_u32(activation, object, "quality")?.clamp(1, 15) as u8)
+}
+
+/// Returns whether a bevel `type` is `"inner"` and `"full"` (on top), respectively.
+fn get_bevel_type<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<(bool, bool), Error<'gc>> {
+    let bevel_type = object
+        .get("type", activation)?
+        .coerce_to_string(activation)?;
+    Ok((&bevel_type == b"inner", &bevel_type == b"full"))
+}
+
+fn avm1_to_bevel_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let (inner, on_top) = get_bevel_type(activation, object)?;
+    let mut flags = BevelFilterFlags::COMPOSITE_SOURCE;
+    flags.set(BevelFilterFlags::INNER_SHADOW, inner);
+    flags.set(BevelFilterFlags::ON_TOP, on_top);
+    flags.set(
+        BevelFilterFlags::KNOCKOUT,
+        get_bool(activation, object, "knockout")?,
+    );
+    flags |= BevelFilterFlags::from_passes(get_passes(activation, object)?);
+    Ok(Filter::BevelFilter(BevelFilter {
+        shadow_color: get_color(activation, object, "shadowColor", "shadowAlpha")?,
+        highlight_color: get_color(activation, object, "highlightColor", "highlightAlpha")?,
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?),
+        angle: Fixed16::from_f64(get_f64(activation, object, "angle")?.to_radians()),
+        distance: Fixed16::from_f64(get_f64(activation, object, "distance")?),
+        strength: Fixed8::from_f64(get_f64(activation, object, "strength")?),
+        flags,
+    }))
+}
+
+fn avm1_to_blur_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    Ok(Filter::BlurFilter(BlurFilter {
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?),
+        flags: BlurFilterFlags::from_passes(get_passes(activation, object)?),
+    }))
+}
+
+fn avm1_to_color_matrix_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let mut matrix = [0.0; 20];
+    for (value, number) in matrix
+        .iter_mut()
+        .zip(get_numbers(activation, object, "matrix")?)
+    {
+        *value = number as f32;
+    }
+    Ok(Filter::ColorMatrixFilter(ColorMatrixFilter { matrix }))
+}
+
+fn avm1_to_convolution_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let matrix_x = get_u32(activation, object, "matrixX")?.min(255);
+    let matrix_y = get_u32(activation, object, "matrixY")?.min(255);
+    let mut matrix: Vec<_> = get_numbers(activation, object, "matrix")?
+        .into_iter()
+        .map(Fixed16::from_f64)
+        .collect();
+    matrix.resize((matrix_x * matrix_y) as usize, Fixed16::ZERO);
+    let mut flags = ConvolutionFilterFlags::empty();
+    flags.set(
+        ConvolutionFilterFlags::CLAMP,
+        get_bool(activation, object, "clamp")?,
+    );
+    flags.set(
+        ConvolutionFilterFlags::PRESERVE_ALPHA,
+        get_bool(activation, object, "preserveAlpha")?,
+    );
+    Ok(Filter::ConvolutionFilter(ConvolutionFilter {
+        num_matrix_rows: matrix_y as u8,
+        num_matrix_cols: matrix_x as u8,
+        matrix,
+        divisor: Fixed16::from_f64(get_f64(activation, object, "divisor")?),
+        bias: Fixed16::from_f64(get_f64(activation, object, "bias")?),
+        default_color: get_color(activation, object, "color", "alpha")?,
+        flags,
+    }))
+}
+
+fn avm1_to_displacement_map_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let map_bitmap = match object.get("mapBitmap", activation)? {
+        Value::Object(map_bitmap) => map_bitmap.as_bitmap_data_object(),
+        _ => None,
+    };
+    let map_bitmap = map_bitmap
+        .filter(|map_bitmap| !map_bitmap.disposed())
+        .map(|map_bitmap| {
+            map_bitmap
+                .bitmap_data_wrapper()
+                .bitmap_handle(&mut activation.context)
+        });
+    let map_point = match object.get("mapPoint", activation)? {
+        Value::Object(point) => (
+            point.get("x", activation)?.coerce_to_i32(activation)?,
+            point.get("y", activation)?.coerce_to_i32(activation)?,
+        ),
+        _ => (0, 0),
+    };
+    let mode = object
+        .get("mode", activation)?
+        .coerce_to_string(activation)?;
+    let mode = if &mode == b"clamp" {
+        DisplacementMapFilterMode::Clamp
+    } else if &mode == b"ignore" {
+        DisplacementMapFilterMode::Ignore
+    } else if &mode == b"color" {
+        DisplacementMapFilterMode::Color
+    } else {
+        DisplacementMapFilterMode::Wrap
+    };
+    Ok(Filter::DisplacementMapFilter(DisplacementMapFilter {
+        color: get_color(activation, object, "color", "alpha")?,
+        component_x: get_u32(activation, object, "componentX")? as u8,
+        component_y: get_u32(activation, object, "componentY")? as u8,
+        map_bitmap,
+        map_point,
+        mode,
+        scale_x: get_f64(activation, object, "scaleX")? as f32,
+        scale_y: get_f64(activation, object, "scaleY")? as f32,
+    }))
+}
+
+fn avm1_to_drop_shadow_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let mut flags = DropShadowFilterFlags::empty();
+    flags.set(
+        DropShadowFilterFlags::COMPOSITE_SOURCE,
+        !get_bool(activation, object, "hideObject")?,
+    );
+    flags.set(
+        DropShadowFilterFlags::INNER_SHADOW,
+        get_bool(activation, object, "inner")?,
+    );
+    flags.set(
+        DropShadowFilterFlags::KNOCKOUT,
+        get_bool(activation, object, "knockout")?,
+    );
+    flags |= DropShadowFilterFlags::from_passes(get_passes(activation, object)?);
+    Ok(Filter::DropShadowFilter(DropShadowFilter {
+        color: get_color(activation, object, "color", "alpha")?,
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?),
+        angle: Fixed16::from_f64(get_f64(activation, object, "angle")?.to_radians()),
+        distance: Fixed16::from_f64(get_f64(activation, object, "distance")?),
+        strength: Fixed8::from_f64(get_f64(activation, object, "strength")?),
+        flags,
+    }))
+}
+
+fn avm1_to_glow_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let mut flags = GlowFilterFlags::COMPOSITE_SOURCE;
+    flags.set(
+        GlowFilterFlags::INNER_GLOW,
+        get_bool(activation, object, "inner")?,
+    );
+    flags.set(
+        GlowFilterFlags::KNOCKOUT,
+        get_bool(activation, object, "knockout")?,
+    );
+    flags |= GlowFilterFlags::from_passes(get_passes(activation, object)?);
+    Ok(Filter::GlowFilter(GlowFilter {
+        color: get_color(activation, object, "color", "alpha")?,
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?),
+        strength: Fixed8::from_f64(get_f64(activation, object, "strength")?),
+        flags,
+    }))
+}
+
+fn avm1_to_gradient_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<GradientFilter, Error<'gc>> {
+    let colors = get_numbers(activation, object, "colors")?;
+    let alphas = get_numbers(activation, object, "alphas")?;
+    let ratios = get_numbers(activation, object, "ratios")?;
+    let colors = colors
+        .into_iter()
+        .zip(alphas)
+        .zip(ratios)
+        .map(|((color, alpha), ratio)| GradientRecord {
+            ratio: ratio.clamp(0.0, 255.0) as u8,
+            color: Color::from_rgb(color as u32, (alpha.clamp(0.0, 1.0) * 255.0) as u8),
+        })
+        .collect();
+    let (inner, on_top) = get_bevel_type(activation, object)?;
+    let mut flags = GradientFilterFlags::COMPOSITE_SOURCE;
+    flags.set(GradientFilterFlags::INNER_SHADOW, inner);
+    flags.set(GradientFilterFlags::ON_TOP, on_top);
+    flags.set(
+        GradientFilterFlags::KNOCKOUT,
+        get_bool(activation, object, "knockout")?,
+    );
+    flags |= GradientFilterFlags::from_passes(get_passes(activation, object)?);
+    Ok(GradientFilter {
+        colors,
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?),
+        angle: Fixed16::from_f64(get_f64(activation, object, "angle")?.to_radians()),
+        distance: Fixed16::from_f64(get_f64(activation, object, "distance")?),
+        strength: Fixed8::from_f64(get_f64(activation, object, "strength")?),
+        flags,
+    })
+}
@@ -19,6 +19,7 @@ mod bitmap_filter;
 pub(crate) mod blur_filter;
 pub(crate) mod boolean;
 pub(crate) mod button;
+mod camera;
 mod color;
 pub mod color_matrix_filter;
 pub(crate) mod color_transform;
@@ -42,6 +43,7 @@ mod matrix;
 pub(crate) mod mouse;
 pub(crate) mod movie_clip;
 mod movie_clip_loader;
+mod netconnection;
 pub(crate) mod netstream;
 pub(crate) mod number;
 mod object;
@@ -62,6 +64,7 @@ mod transform;
 mod video;
 mod xml;
 mod xml_node;
+mod xml_socket;
 
 const GLOBAL_DECLS: &[Declaration] = declare_properties! {
     "trace" => method(trace; DONT_ENUM);
@@ -491,6 +494,8 @@ pub struct SystemPrototypes<'gc> {
     pub transform_constructor: Object<'gc>,
     pub shared_object: Object<'gc>,
     pub shared_object_constructor: Object<'gc>,
+    pub camera: Object<'gc>,
+    pub camera_constructor: Object<'gc>,
     pub color_transform: Object<'gc>,
     pub color_transform_constructor: Object<'gc>,
     pub context_menu: Object<'gc>,
@@ -588,6 +593,9 @@ pub fn create_globals<'gc>(
 
     let video_proto = video::create_proto(gc_context, object_proto, function_proto);
     let netstream_proto = netstream::create_proto(gc_context, object_proto, function_proto);
+    let netconnection_proto = netconnection::create_proto(gc_context, object_proto, function_proto);
+    let xml_socket_proto = xml_socket::create_proto(gc_context, object_proto, function_proto);
+    let camera_proto = camera::create_proto(gc_context, object_proto, function_proto);
 
     //TODO: These need to be constructors and should also set `.prototype` on each one
     let object = object::create_object_object(gc_context, object_proto, function_proto);
@@ -687,6 +695,10 @@ pub fn create_globals<'gc>(
     let boolean = boolean::create_boolean_object(gc_context, boolean_proto, function_proto);
     let date = date::create_constructor(gc_context, object_proto, function_proto);
     let netstream = netstream::create_class(gc_context, netstream_proto, function_proto);
+    let netconnection =
+        netconnection::create_class(gc_context, netconnection_proto, function_proto);
+    let xml_socket = xml_socket::create_class(gc_context, xml_socket_proto, function_proto);
+    let camera = camera::create_class(gc_context, camera_proto, function_proto);
 
     let flash = ScriptObject::new(gc_context, Some(object_proto));
 
@@ -1110,6 +1122,19 @@ pub fn create_globals<'gc>(
         netstream.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "NetConnection",
+        netconnection.into(),
+        Attribute::DONT_ENUM,
+    );
+    globals.define_value(gc_context, "Camera", camera.into(), Attribute::DONT_ENUM);
+    globals.define_value(
+        gc_context,
+        "XMLSocket",
+        xml_socket.into(),
+        Attribute::DONT_ENUM,
+    );
 
     define_properties_on(GLOBAL_DECLS, gc_context, globals, function_proto);
 
@@ -1140,6 +1165,8 @@ pub fn create_globals<'gc>(
             transform_constructor: transform,
             shared_object: shared_object_proto,
             shared_object_constructor: shared_obj,
+            camera: camera_proto,
+            camera_constructor: camera,
             color_transform: color_transform_proto,
             color_transform_constructor: color_transform,
             context_menu: context_menu_proto,
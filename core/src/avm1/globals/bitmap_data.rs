@@ -1,6 +1,7 @@
 //! flash.display.BitmapData object
 
 use super::matrix::object_to_matrix;
+use crate::avm1::filters::avm1_to_filter;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::globals::color_transform::ColorTransformObject;
 use crate::avm1::object::bitmap_data::BitmapDataObject;
@@ -8,12 +9,14 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, TObject, Value};
 use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, Color, ThresholdOperation};
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
+use crate::bitmap::filters;
 use crate::bitmap::is_size_valid;
 use crate::character::Character;
 use crate::display_object::TDisplayObject;
 use crate::swf::BlendMode;
 use crate::{avm1_stub, avm_error};
 use gc_arena::{GcCell, MutationContext};
+use ruffle_render::filters::Filter;
 use ruffle_render::transform::Transform;
 use std::str::FromStr;
 
@@ -534,6 +537,19 @@ pub fn draw<'gc>(
                 .unwrap_or(&Value::Undefined)
                 .coerce_to_object(activation);
             let source = if let Some(source_object) = source.as_display_object() {
+                let accessor = activation.base_clip().movie();
+                if let Some(content_url) = activation.context.security.find_unreadable_content(
+                    &activation.context.system.sandbox_type,
+                    accessor.url(),
+                    source_object,
+                ) {
+                    tracing::warn!(
+                        "BitmapData.draw: {} cannot access {}",
+                        accessor.url(),
+                        content_url
+                    );
+                    return Ok(Value::Undefined);
+                }
                 IBitmapDrawable::DisplayObject(source_object)
             } else if let Some(source_bitmap) = source.as_bitmap_data_object() {
                 IBitmapDrawable::BitmapData(source_bitmap.bitmap_data_wrapper())
@@ -582,22 +598,124 @@ pub fn draw<'gc>(
 
 pub fn apply_filter<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "BitmapData", "applyFilter");
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        if !bitmap_data.disposed() {
+            let source = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let source = match source.as_bitmap_data_object() {
+                Some(source) if !source.disposed() => source,
+                _ => return Ok((-1).into()),
+            };
+
+            let source_rect = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let src_min_x = source_rect
+                .get("x", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let src_min_y = source_rect
+                .get("y", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let src_width = source_rect
+                .get("width", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let src_height = source_rect
+                .get("height", activation)?
+                .coerce_to_f64(activation)? as i32;
+
+            let dest_point = args
+                .get(2)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let dest_x = dest_point.get("x", activation)?.coerce_to_f64(activation)? as i32;
+            let dest_y = dest_point.get("y", activation)?.coerce_to_f64(activation)? as i32;
+
+            let filter_object = args
+                .get(3)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let Some(filter) = avm1_to_filter(activation, filter_object)? else {
+                return Ok((-1).into());
+            };
+            let displacement_map = if let Filter::DisplacementMapFilter(_) = filter {
+                match filter_object.get("mapBitmap", activation)? {
+                    Value::Object(map) => map
+                        .as_bitmap_data_object()
+                        .filter(|map| !map.disposed())
+                        .map(|map| map.bitmap_data_wrapper()),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            // Areas that start outside of either bitmap are moved inside of them.
+            let offset_x = (-src_min_x).max(-dest_x).max(0);
+            let offset_y = (-src_min_y).max(-dest_y).max(0);
+            let src_width = src_width - offset_x;
+            let src_height = src_height - offset_y;
+            if src_width <= 0 || src_height <= 0 {
+                return Ok(0.into());
+            }
+
+            bitmap_data.bitmap_data_wrapper().apply_filter(
+                &mut activation.context,
+                source.bitmap_data_wrapper(),
+                ((src_min_x + offset_x) as u32, (src_min_y + offset_y) as u32),
+                (src_width as u32, src_height as u32),
+                ((dest_x + offset_x) as u32, (dest_y + offset_y) as u32),
+                filter,
+                displacement_map,
+            );
+            return Ok(0.into());
+        }
+    }
+
     Ok((-1).into())
 }
 
 pub fn generate_filter_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            avm1_stub!(activation, "BitmapData", "generateFilterRect");
-            return Ok(Value::Undefined);
+            let source_rect = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let x = source_rect
+                .get("x", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let y = source_rect
+                .get("y", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let width = source_rect
+                .get("width", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let height = source_rect
+                .get("height", activation)?
+                .coerce_to_f64(activation)? as i32;
+
+            let filter = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let Some(filter) = avm1_to_filter(activation, filter)? else {
+                return Ok((-1).into());
+            };
+
+            let (x, y, w, h) = filters::calculate_dest_rect(&filter, (x, y, width, height));
+            let proto = activation.context.avm1.prototypes().rectangle_constructor;
+            let rect = proto.construct(activation, &[x.into(), y.into(), w.into(), h.into()])?;
+            return Ok(rect);
         }
     }
 
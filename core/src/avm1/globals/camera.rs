@@ -0,0 +1,402 @@
+//! Camera class
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::{NativeObject, Object, TObject};
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, ArrayObject, Error, ScriptObject, Value};
+use crate::camera::{Camera, Cameras};
+use crate::string::AvmString;
+use crate::vminterface::AvmObject;
+use gc_arena::MutationContext;
+
+/// Implements `Camera`
+///
+/// Cameras can only be obtained with `Camera.get`; constructed objects are
+/// not connected to anything.
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "activityLevel" => property(get_activity_level);
+    "bandwidth" => property(get_bandwidth);
+    "currentFps" => property(get_current_fps);
+    "fps" => property(get_fps);
+    "height" => property(get_height);
+    "index" => property(get_index);
+    "keyFrameInterval" => property(get_key_frame_interval);
+    "loopback" => property(get_loopback);
+    "motionLevel" => property(get_motion_level);
+    "motionTimeout" => property(get_motion_timeout);
+    "muted" => property(get_muted);
+    "name" => property(get_name);
+    "quality" => property(get_quality);
+    "width" => property(get_width);
+    "setKeyFrameInterval" => method(set_key_frame_interval; DONT_ENUM | DONT_DELETE);
+    "setLoopback" => method(set_loopback; DONT_ENUM | DONT_DELETE);
+    "setMode" => method(set_mode; DONT_ENUM | DONT_DELETE);
+    "setMotionLevel" => method(set_motion_level; DONT_ENUM | DONT_DELETE);
+    "setQuality" => method(set_quality; DONT_ENUM | DONT_DELETE);
+};
+
+const OBJECT_DECLS: &[Declaration] = declare_properties! {
+    "get" => method(get; DONT_ENUM | DONT_DELETE);
+    "names" => property(get_names);
+};
+
+/// Implements `Camera.get`
+fn get<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Leaving out the index asks for the default camera.
+    let index = match args.get(0) {
+        None | Some(Value::Undefined) => 0,
+        Some(index) => index.coerce_to_i32(activation)?.max(0) as usize,
+    };
+    let names = activation.context.camera.names();
+    let Some(name) = names.get(index) else {
+        return Ok(Value::Null);
+    };
+
+    if let Some(camera) = activation.context.cameras.get(index) {
+        if let Some(AvmObject::Avm1(object)) = camera.avm_object() {
+            return Ok(object.into());
+        }
+    }
+
+    let object: Object<'gc> = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().camera),
+    )
+    .into();
+    let camera = Camera::new(activation.context.gc_context, Some(object.into()));
+    object.set_native(activation.context.gc_context, NativeObject::Camera(camera));
+    camera.set_device(activation.context.gc_context, index, name.clone());
+    Cameras::open(&mut activation.context, camera);
+
+    Ok(object.into())
+}
+
+/// Implements `Camera.names`
+fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation.context.camera.names();
+    let names: Vec<Value<'gc>> = names
+        .iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name).into())
+        .collect();
+    Ok(ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        names,
+    )
+    .into())
+}
+
+fn get_activity_level<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.activity_level().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_bandwidth<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.bandwidth().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_current_fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.current_fps().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.fps().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.height().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.index().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_key_frame_interval<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.key_frame_interval().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_loopback<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.loopback().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_motion_level<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.motion_level().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_motion_timeout<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.motion_timeout().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_muted<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.muted().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(AvmString::new_utf8(activation.context.gc_context, camera.name()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_quality<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.quality().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        return Ok(camera.width().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_key_frame_interval<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        let interval = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        camera.set_key_frame_interval(activation.context.gc_context, interval);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_loopback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        let compress = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .as_bool(activation.swf_version());
+
+        camera.set_loopback(activation.context.gc_context, compress);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        // Anything that is left out keeps its current value.
+        let width = match args.get(0) {
+            Some(width) => width.coerce_to_i32(activation)?,
+            None => camera.width() as i32,
+        };
+        let height = match args.get(1) {
+            Some(height) => height.coerce_to_i32(activation)?,
+            None => camera.height() as i32,
+        };
+        let fps = match args.get(2) {
+            Some(fps) => fps.coerce_to_f64(activation)?,
+            None => camera.fps(),
+        };
+
+        camera.set_mode(&mut activation.context, width, height, fps);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_motion_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        let motion_level = match args.get(0) {
+            Some(motion_level) => motion_level.coerce_to_i32(activation)?,
+            None => camera.motion_level(),
+        };
+        let timeout = match args.get(1) {
+            Some(timeout) => timeout.coerce_to_i32(activation)?,
+            None => camera.motion_timeout(),
+        };
+
+        camera.set_motion_level(activation.context.gc_context, motion_level, timeout);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_quality<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::Camera(camera) = this.native() {
+        let bandwidth = match args.get(0) {
+            Some(bandwidth) => bandwidth.coerce_to_i32(activation)?,
+            None => camera.bandwidth(),
+        };
+        let quality = match args.get(1) {
+            Some(quality) => quality.coerce_to_i32(activation)?,
+            None => camera.quality(),
+        };
+
+        camera.set_quality(activation.context.gc_context, bandwidth, quality);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    camera_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let camera = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        camera_proto,
+    );
+    let object = camera.raw_script_object();
+    define_properties_on(OBJECT_DECLS, gc_context, object, fn_proto);
+    camera
+}
@@ -2,14 +2,21 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::globals::shared_object::serialize_value;
+use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{Object, ScriptObject, Value};
+use crate::avm1::{Object, ScriptObject, TObject, Value};
 use crate::display_object::TDisplayObject;
+use crate::local_connection::{is_reserved_method, movie_domain, LocalConnection};
 use crate::string::AvmString;
+use flash_lso::types::Value as AmfValue;
 use gc_arena::MutationContext;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "domain" => method(domain; DONT_DELETE | READ_ONLY);
+    "connect" => method(connect; DONT_ENUM | DONT_DELETE);
+    "send" => method(send; DONT_ENUM | DONT_DELETE);
+    "close" => method(close; DONT_ENUM | DONT_DELETE);
 };
 
 pub fn domain<'gc>(
@@ -19,28 +26,95 @@ pub fn domain<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let movie = activation.base_clip().movie();
 
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
+    let Some(domain) = movie_domain(movie.url()) else {
         tracing::error!("LocalConnection::domain: Unable to parse movie URL");
         return Ok(Value::Null);
     };
 
-    Ok(Value::String(domain))
+    Ok(AvmString::new_utf8(activation.context.gc_context, domain).into())
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let NativeObject::LocalConnection(connection) = this.native() else {
+        return Ok(Value::Undefined);
+    };
+
+    let name = match args.get(0) {
+        Some(Value::String(name)) => *name,
+        _ => return Ok(false.into()),
+    };
+    let movie = activation.base_clip().movie();
+    let connected = connection.connect(&mut activation.context, movie.url(), &name.to_utf8_lossy());
+    Ok(connected.into())
+}
+
+/// Implements `LocalConnection.send`.
+///
+/// The message is sent on the next tick, and `onStatus` is called once it
+/// is known whether anyone received it.
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let NativeObject::LocalConnection(connection) = this.native() else {
+        return Ok(Value::Undefined);
+    };
+
+    let (connection_name, method_name) = match (args.get(0), args.get(1)) {
+        (Some(Value::String(connection_name)), Some(Value::String(method_name))) => {
+            (*connection_name, method_name.to_string())
+        }
+        _ => return Ok(false.into()),
+    };
+    if method_name.is_empty() || is_reserved_method(&method_name) {
+        return Ok(false.into());
+    }
+
+    let arguments = args
+        .get(2..)
+        .unwrap_or_default()
+        .iter()
+        .map(|argument| serialize_value(activation, *argument).unwrap_or(AmfValue::Undefined))
+        .collect();
+    let movie = activation.base_clip().movie();
+    connection.send(
+        &mut activation.context,
+        movie.url(),
+        &connection_name.to_utf8_lossy(),
+        method_name,
+        arguments,
+    );
+    Ok(true.into())
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::LocalConnection(connection) = this.native() {
+        connection.close(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn constructor<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = LocalConnection::new(activation.context.gc_context, Some(this.into()));
+    this.set_native(
+        activation.context.gc_context,
+        NativeObject::LocalConnection(connection),
+    );
+
     Ok(this.into())
 }
 
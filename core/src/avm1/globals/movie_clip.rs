@@ -112,6 +112,8 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "useHandCursor" => property(mc_getter!(use_hand_cursor), mc_setter!(set_use_hand_cursor); DONT_DELETE | DONT_ENUM);
     "blendMode" => property(mc_getter!(blend_mode), mc_setter!(set_blend_mode); DONT_DELETE | DONT_ENUM);
     "scrollRect" => property(mc_getter!(scroll_rect), mc_setter!(set_scroll_rect); DONT_DELETE | DONT_ENUM | VERSION_8);
+    "scale9Grid" => property(mc_getter!(scale9_grid), mc_setter!(set_scale9_grid); DONT_DELETE | DONT_ENUM | VERSION_8);
+    "cacheAsBitmap" => property(mc_getter!(cache_as_bitmap), mc_setter!(set_cache_as_bitmap); DONT_DELETE | DONT_ENUM | VERSION_8);
 };
 
 /// Implements `MovieClip`
@@ -184,6 +186,50 @@ fn set_scroll_rect<'gc>(
     Ok(())
 }
 
+fn scale9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = this.scaling_grid();
+    if rect.is_valid() {
+        new_rectangle(activation, rect)
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+fn set_scale9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    if let Value::Object(object) = value {
+        if let Some(rect) = object_to_rectangle(activation, object)? {
+            this.set_scaling_grid(activation.context.gc_context, rect);
+        }
+    } else {
+        this.set_scaling_grid(activation.context.gc_context, Default::default());
+    };
+    Ok(())
+}
+
+fn cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.is_bitmap_cached().into())
+}
+
+fn set_cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let value = value.as_bool(activation.swf_version());
+    this.set_is_bitmap_cached(activation.context.gc_context, value);
+    Ok(())
+}
+
 #[allow(clippy::comparison_chain)]
 pub fn hit_test<'gc>(
     movie_clip: MovieClip<'gc>,
@@ -1335,7 +1381,7 @@ fn load_movie<'gc>(
         activation.context.player.clone(),
         DisplayObject::MovieClip(target),
         request,
-        None,
+        Some(activation.base_clip().movie().url().to_string()),
         None,
         None,
     );
@@ -64,7 +64,7 @@ fn load_clip<'gc>(
                     activation.context.player.clone(),
                     target,
                     Request::get(url.to_utf8_lossy().into_owned()),
-                    None,
+                    Some(activation.base_clip().movie().url().to_string()),
                     Some(MovieLoaderEventHandler::Avm1Broadcast(this)),
                     None,
                 );
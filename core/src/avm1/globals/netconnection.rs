@@ -0,0 +1,185 @@
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::shared_object::serialize_value;
+use crate::avm1::object::{NativeObject, Object, TObject};
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, Error, ScriptObject, Value};
+use crate::net_connection::NetConnection;
+use crate::string::AvmString;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+use gc_arena::MutationContext;
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = NetConnection::new(activation.context.gc_context, Some(this.into()));
+    this.set_native(
+        activation.context.gc_context,
+        NativeObject::NetConnection(connection),
+    );
+
+    Ok(Value::Undefined)
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "isConnected" => property(get_is_connected);
+    "uri" => property(get_uri);
+    "addHeader" => method(add_header; DONT_ENUM | DONT_DELETE);
+    "call" => method(call; DONT_ENUM | DONT_DELETE);
+    "close" => method(close; DONT_ENUM | DONT_DELETE);
+    "connect" => method(connect; DONT_ENUM | DONT_DELETE);
+};
+
+/// Serialize values to send to a server. AVM1 always uses AMF0.
+fn serialize_arguments<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    arguments: &[Value<'gc>],
+) -> Vec<AmfValue> {
+    arguments
+        .iter()
+        .map(|argument| serialize_value(activation, *argument).unwrap_or(AmfValue::Undefined))
+        .collect()
+}
+
+fn get_is_connected<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetConnection(connection) = this.native() {
+        return Ok(connection.is_connected().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_uri<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetConnection(connection) = this.native() {
+        if let Some(uri) = connection.uri() {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, uri).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn add_header<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetConnection(connection) = this.native() {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let must_understand = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .as_bool(activation.swf_version());
+        let value = serialize_arguments(activation, args.get(2..).unwrap_or_default())
+            .into_iter()
+            .next()
+            .unwrap_or(AmfValue::Undefined);
+
+        connection.add_header(
+            activation.context.gc_context,
+            name.to_string(),
+            must_understand,
+            value,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetConnection(connection) = this.native() {
+        let command = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let responder = match args.get(1) {
+            Some(Value::Object(responder)) => Some((*responder).into()),
+            _ => None,
+        };
+        let arguments = serialize_arguments(activation, args.get(2..).unwrap_or_default());
+
+        connection.call(
+            &mut activation.context,
+            command.to_string(),
+            responder,
+            arguments,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetConnection(connection) = this.native() {
+        connection.close(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetConnection(connection) = this.native() {
+        let uri = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            uri => Some(uri.coerce_to_string(activation)?.to_string()),
+        };
+        let arguments = serialize_arguments(activation, args.get(1..).unwrap_or_default());
+
+        return Ok(connection
+            .connect(&mut activation.context, uri, AMFVersion::AMF0, arguments)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    netconnection_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        netconnection_proto,
+    )
+}
@@ -8,6 +8,7 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, ScriptObject, TObject, Value};
 use crate::avm1_stub;
 use crate::display_object::TDisplayObject;
+use crate::remote_shared_object::RemoteSharedObject;
 use crate::string::AvmString;
 use flash_lso::types::Value as AmfValue;
 use flash_lso::types::{AMFVersion, Element, Lso};
@@ -55,7 +56,7 @@ pub fn get_disk_usage<'gc>(
 }
 
 /// Serialize a Value to an AmfValue
-fn serialize_value<'gc>(
+pub fn serialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     elem: Value<'gc>,
 ) -> Option<AmfValue> {
@@ -95,7 +96,7 @@ fn serialize_value<'gc>(
 }
 
 /// Serialize an Object and any children to a JSON object
-fn recursive_serialize<'gc>(
+pub fn recursive_serialize<'gc>(
     activation: &mut Activation<'_, 'gc>,
     obj: Object<'gc>,
     elements: &mut Vec<Element>,
@@ -111,7 +112,7 @@ fn recursive_serialize<'gc>(
 }
 
 /// Deserialize a AmfValue to a Value
-fn deserialize_value<'gc>(activation: &mut Activation<'_, 'gc>, val: &AmfValue) -> Value<'gc> {
+pub fn deserialize_value<'gc>(activation: &mut Activation<'_, 'gc>, val: &AmfValue) -> Value<'gc> {
     match val {
         AmfValue::Null => Value::Null,
         AmfValue::Undefined => Value::Undefined,
@@ -381,10 +382,74 @@ pub fn get_local<'gc>(
 pub fn get_remote<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "getRemote");
-    Ok(Value::Undefined)
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let name = name.to_utf8_lossy();
+
+    const INVALID_CHARS: &str = "~%&\\;:\"',<>?# ";
+    if name.is_empty() || name.contains(|c| INVALID_CHARS.contains(c)) {
+        tracing::error!("SharedObject::get_remote: Invalid name");
+        return Ok(Value::Null);
+    }
+
+    let uri = match args.get(1) {
+        Some(Value::String(uri)) => uri.to_string(),
+        _ => return Ok(Value::Null),
+    };
+
+    // A local path also asks for a copy of the data to be kept on disk,
+    // which isn't supported, but makes the object persistent on the server.
+    let persistent = match args.get(2) {
+        Some(Value::String(local_path)) => !local_path.is_empty(),
+        Some(value) => value.as_bool(activation.swf_version()),
+        None => false,
+    };
+
+    // Check if this is referencing an existing shared object
+    let key = RemoteSharedObject::cache_key(&name, &uri, persistent);
+    if let Some(so) = activation.context.avm1_shared_objects.get(&key) {
+        return Ok((*so).into());
+    }
+
+    let constructor = activation
+        .context
+        .avm1
+        .prototypes()
+        .shared_object_constructor;
+    let this = constructor
+        .construct(activation, &[])?
+        .coerce_to_object(activation);
+    let Some(obj_so) = this.as_shared_object() else {
+        return Ok(Value::Undefined);
+    };
+
+    let remote = RemoteSharedObject::new(
+        activation.context.gc_context,
+        name.into_owned(),
+        uri,
+        persistent,
+        Some(this.into()),
+    );
+    obj_so.set_remote(activation.context.gc_context, remote);
+
+    let data = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    this.define_value(
+        activation.context.gc_context,
+        "data",
+        data.into(),
+        Attribute::DONT_DELETE,
+    );
+
+    activation.context.avm1_shared_objects.insert(key, this);
+
+    Ok(this.into())
 }
 
 pub fn get_max_size<'gc>(
@@ -452,20 +517,36 @@ pub fn clear<'gc>(
 
 pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "close");
+    if let Some(remote) = this.as_shared_object().and_then(|so| so.remote()) {
+        remote.close(&mut activation.context);
+    } else {
+        avm1_stub!(activation, "SharedObject", "close");
+    }
     Ok(Value::Undefined)
 }
 
+/// Implements `SharedObject.connect`, which starts syncing a remote shared
+/// object over a `NetConnection`.
 pub fn connect<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "connect");
-    Ok(Value::Undefined)
+    let Some(remote) = this.as_shared_object().and_then(|so| so.remote()) else {
+        return Ok(false.into());
+    };
+    let connection = match args.get(0) {
+        Some(Value::Object(connection)) => match connection.native() {
+            NativeObject::NetConnection(connection) => connection,
+            _ => return Ok(false.into()),
+        },
+        _ => return Ok(false.into()),
+    };
+
+    Ok(remote.connect(&mut activation.context, connection).into())
 }
 
 pub fn flush<'gc>(
@@ -476,6 +557,10 @@ pub fn flush<'gc>(
     let data = this.get("data", activation)?.coerce_to_object(activation);
 
     let this_obj = this.as_shared_object().unwrap();
+    if this_obj.remote().is_some() {
+        // Remote shared objects are never kept on disk.
+        return Ok(false.into());
+    }
     let name = this_obj.get_name();
 
     let mut elements = Vec::new();
@@ -503,39 +588,61 @@ pub fn get_size<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `SharedObject.send`, which calls a method on this shared
+/// object in every client that is connected to it.
 pub fn send<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "send");
+    let Some(remote) = this.as_shared_object().and_then(|so| so.remote()) else {
+        return Ok(Value::Undefined);
+    };
+    let Some(Value::String(handler_name)) = args.get(0) else {
+        return Ok(Value::Undefined);
+    };
+
+    let mut arguments = vec![AmfValue::String(handler_name.to_string())];
+    for argument in &args[1..] {
+        arguments.push(serialize_value(activation, *argument).unwrap_or(AmfValue::Undefined));
+    }
+    remote.send(&mut activation.context, arguments);
     Ok(Value::Undefined)
 }
 
 pub fn set_fps<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "setFps");
-    Ok(Value::Undefined)
+    let Some(remote) = this.as_shared_object().and_then(|so| so.remote()) else {
+        return Ok(false.into());
+    };
+    let fps = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    remote.set_fps(&mut activation.context, fps);
+    Ok(true.into())
 }
 
+/// The default `onStatus` handler, which movies replace to hear about
+/// connection and sync errors.
 pub fn on_status<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "onStatus");
     Ok(Value::Undefined)
 }
 
+/// The default `onSync` handler, which movies replace to hear about changes
+/// to a remote shared object.
 pub fn on_sync<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "onSync");
     Ok(Value::Undefined)
 }
 
@@ -20,27 +20,52 @@ const OBJECT_DECLS: &[Declaration] = declare_properties! {
 fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "allowDomain");
-    Ok(Value::Undefined)
+    allow_domains(activation, args, false)
 }
 
 fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "allowInsecureDomain");
+    allow_domains(activation, args, true)
+}
+
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    insecure: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut domains = Vec::with_capacity(args.len());
+    for domain in args {
+        domains.push(domain.coerce_to_string(activation)?.to_string());
+    }
+
+    let movie = activation.base_clip().movie();
+    activation
+        .context
+        .security
+        .allow_domains(movie.url(), &domains, insecure);
     Ok(Value::Undefined)
 }
 
 fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "loadPolicyFile");
+    let url = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    activation.context.security.load_policy_file(
+        activation.context.player.clone(),
+        activation.context.navigator,
+        &url.to_string(),
+    );
     Ok(Value::Undefined)
 }
 
@@ -0,0 +1,140 @@
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::{NativeObject, Object, TObject};
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, Error, ExecutionReason, ScriptObject, Value};
+use crate::socket::{Socket, SocketKind};
+use gc_arena::MutationContext;
+use std::time::Duration;
+
+/// How long to wait for a connection before giving up, as AVM1 has no way to
+/// change it.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let socket = Socket::new(
+        activation.context.gc_context,
+        Some(this.into()),
+        SocketKind::Xml,
+    );
+    this.set_native(
+        activation.context.gc_context,
+        NativeObject::XmlSocket(socket),
+    );
+
+    Ok(Value::Undefined)
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "connect" => method(connect; DONT_ENUM | DONT_DELETE);
+    "send" => method(send; DONT_ENUM | DONT_DELETE);
+    "close" => method(close; DONT_ENUM | DONT_DELETE);
+    "onData" => method(on_data; DONT_ENUM | DONT_DELETE);
+};
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::XmlSocket(socket) = this.native() {
+        let host = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            host => Some(host.coerce_to_string(activation)?.to_string()),
+        };
+        let port = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let Ok(port) = u16::try_from(port) else {
+            return Ok(false.into());
+        };
+        if port == 0 {
+            return Ok(false.into());
+        }
+
+        let movie = activation.base_clip().movie();
+        let connected = socket.connect(
+            &mut activation.context,
+            movie.url(),
+            host,
+            port,
+            CONNECT_TIMEOUT,
+        );
+        return Ok(connected.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::XmlSocket(socket) = this.native() {
+        let data = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        socket.send(data.to_utf8_lossy().into_owned().into_bytes());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::XmlSocket(socket) = this.native() {
+        socket.close(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// The default `onData` handler, which parses the message and passes it on
+/// to `onXML`.
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let src = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let xml_constructor = activation.context.avm1.prototypes().xml_constructor;
+    let xml = xml_constructor.construct(activation, &[src])?;
+    this.call_method("onXML".into(), &[xml], activation, ExecutionReason::Special)?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    xml_socket_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        xml_socket_proto,
+    )
+}
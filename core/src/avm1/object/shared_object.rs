@@ -2,6 +2,7 @@ use crate::impl_custom_object;
 use gc_arena::{Collect, GcCell, MutationContext};
 
 use crate::avm1::{Object, ScriptObject, TObject};
+use crate::remote_shared_object::RemoteSharedObject;
 use std::fmt;
 
 /// A SharedObject
@@ -17,7 +18,10 @@ pub struct SharedObjectData<'gc> {
 
     /// The local name of this shared object
     name: Option<String>,
-    // In future this will also handle remote SharedObjects
+
+    /// The server side of this shared object, if it was made with
+    /// `getRemote`.
+    remote: Option<RemoteSharedObject<'gc>>,
 }
 
 impl fmt::Debug for SharedObject<'_> {
@@ -36,6 +40,7 @@ impl<'gc> SharedObject<'gc> {
             SharedObjectData {
                 base: ScriptObject::new(gc_context, Some(proto)),
                 name: None,
+                remote: None,
             },
         ))
     }
@@ -47,6 +52,18 @@ impl<'gc> SharedObject<'gc> {
     pub fn get_name(&self) -> String {
         self.0.read().name.as_ref().cloned().unwrap_or_default()
     }
+
+    pub fn set_remote(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        remote: RemoteSharedObject<'gc>,
+    ) {
+        self.0.write(gc_context).remote = Some(remote);
+    }
+
+    pub fn remote(&self) -> Option<RemoteSharedObject<'gc>> {
+        self.0.read().remote
+    }
 }
 
 impl<'gc> TObject<'gc> for SharedObject<'gc> {
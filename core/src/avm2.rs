@@ -8,8 +8,10 @@ use crate::avm2::script::{Script, TranslationUnit};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, DisplayObjectWeak, TDisplayObject};
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use fnv::FnvHashMap;
 use gc_arena::{Collect, GcCell, MutationContext};
+use std::sync::Arc;
 use swf::avm2::read::Reader;
 use swf::DoAbc2Flag;
 
@@ -23,7 +25,7 @@ macro_rules! avm_debug {
 }
 
 pub mod activation;
-mod amf;
+pub mod amf;
 mod array;
 pub mod bytearray;
 mod call_stack;
@@ -31,7 +33,7 @@ mod class;
 mod domain;
 mod e4x;
 pub mod error;
-mod events;
+pub mod events;
 mod filters;
 mod function;
 pub mod globals;
@@ -101,6 +103,7 @@ pub struct Avm2<'gc> {
     pub flash_utils_internal: Namespace<'gc>,
     pub flash_geom_internal: Namespace<'gc>,
     pub flash_events_internal: Namespace<'gc>,
+    pub flash_text_engine_internal: Namespace<'gc>,
 
     #[collect(require_static)]
     native_method_table: &'static [Option<(&'static str, NativeMethodImpl)>],
@@ -164,6 +167,7 @@ impl<'gc> Avm2<'gc> {
             flash_utils_internal: Namespace::internal("flash.utils", mc),
             flash_geom_internal: Namespace::internal("flash.geom", mc),
             flash_events_internal: Namespace::internal("flash.events", mc),
+            flash_text_engine_internal: Namespace::internal("flash.text.engine", mc),
 
             native_method_table: Default::default(),
             native_instance_allocator_table: Default::default(),
@@ -418,6 +422,7 @@ impl<'gc> Avm2<'gc> {
         name: Option<AvmString<'gc>>,
         flags: DoAbc2Flag,
         domain: Domain<'gc>,
+        movie: Arc<SwfMovie>,
     ) -> Result<(), Error<'gc>> {
         let mut reader = Reader::new(data);
         let abc = match reader.read() {
@@ -433,7 +438,7 @@ impl<'gc> Avm2<'gc> {
         };
 
         let num_scripts = abc.scripts.len();
-        let tunit = TranslationUnit::from_abc(abc, domain, name, context.gc_context);
+        let tunit = TranslationUnit::from_abc(abc, domain, name, movie, context.gc_context);
         for i in 0..num_scripts {
             tunit.load_script(i as u32, context)?;
         }
@@ -23,10 +23,12 @@ use crate::avm2::{value, Avm2, Error};
 use crate::context::UpdateContext;
 use crate::string::AvmString;
 use crate::swf::extensions::ReadSwfExt;
+use crate::tag_utils::SwfMovie;
 use gc_arena::{Gc, GcCell};
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cmp::{min, Ordering};
+use std::sync::Arc;
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
     Class as AbcClass, Exception, Index, Method as AbcMethod, MethodFlags as AbcMethodFlags,
@@ -119,6 +121,15 @@ pub struct Activation<'a, 'gc: 'a> {
     /// current domain instead.
     caller_domain: Option<Domain<'gc>>,
 
+    /// The movie of the original AS3 caller.
+    ///
+    /// Like `caller_domain`, this lets builtin methods act on behalf of the
+    /// movie whose bytecode called them, even when that bytecode went
+    /// through our own `playerglobal` classes first.
+    ///
+    /// This is `None` if this activation was not made to run any code.
+    caller_movie: Option<Arc<SwfMovie>>,
+
     /// The class that yielded the currently executing method.
     ///
     /// This is used to maintain continuity when multiple methods supercall
@@ -177,6 +188,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             return_value: None,
             outer: ScopeChain::new(context.avm2.globals),
             caller_domain: None,
+            caller_movie: None,
             subclass_object: None,
             activation_class: None,
             stack_depth: context.avm2.stack.len(),
@@ -208,6 +220,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             return_value: None,
             outer: ScopeChain::new(context.avm2.globals),
             caller_domain: Some(domain),
+            caller_movie: None,
             subclass_object: None,
             activation_class: None,
             stack_depth: context.avm2.stack.len(),
@@ -252,6 +265,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             return_value: None,
             outer: ScopeChain::new(domain),
             caller_domain: Some(domain),
+            caller_movie: script.translation_unit().map(|tunit| tunit.movie()),
             subclass_object: None,
             activation_class: None,
             stack_depth: context.avm2.stack.len(),
@@ -432,6 +446,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     /// Construct an activation for the execution of a particular bytecode
     /// method.
+    #[allow(clippy::too_many_arguments)]
     pub fn from_method(
         mut context: UpdateContext<'a, 'gc>,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -440,6 +455,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         user_arguments: &[Value<'gc>],
         subclass_object: Option<ClassObject<'gc>>,
         callee: Object<'gc>,
+        caller_movie: Arc<SwfMovie>,
     ) -> Result<Self, Error<'gc>> {
         let body: Result<_, Error<'gc>> = method
             .body()
@@ -496,6 +512,14 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             None
         };
 
+        // Methods of `playerglobal` are called on behalf of their caller.
+        let translation_unit = method.translation_unit();
+        let caller_movie = if translation_unit.domain() == context.avm2.global_domain() {
+            caller_movie
+        } else {
+            translation_unit.movie()
+        };
+
         let mut activation = Self {
             this,
             arguments: None,
@@ -505,6 +529,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             return_value: None,
             outer,
             caller_domain: Some(outer.domain()),
+            caller_movie: Some(caller_movie),
             subclass_object,
             activation_class,
             stack_depth: context.avm2.stack.len(),
@@ -576,6 +601,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         subclass_object: Option<ClassObject<'gc>>,
         outer: ScopeChain<'gc>,
         caller_domain: Domain<'gc>,
+        caller_movie: Arc<SwfMovie>,
     ) -> Result<Self, Error<'gc>> {
         let local_registers = RegisterSet::new(0);
 
@@ -588,6 +614,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             return_value: None,
             outer,
             caller_domain: Some(caller_domain),
+            caller_movie: Some(caller_movie),
             subclass_object,
             activation_class: None,
             stack_depth: context.avm2.stack.len(),
@@ -676,6 +703,15 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.caller_domain.expect("No caller domain available - use Activation::from_domain when constructing your domain")
     }
 
+    /// Returns the movie of the original AS3 caller.
+    ///
+    /// Falls back to the root movie if the caller is unknown.
+    pub fn caller_movie(&self) -> Arc<SwfMovie> {
+        self.caller_movie
+            .clone()
+            .unwrap_or_else(|| self.context.swf.clone())
+    }
+
     /// Returns the global scope of this activation.
     ///
     /// The global scope refers to scope at the bottom of the
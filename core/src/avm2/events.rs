@@ -491,3 +491,59 @@ pub fn dispatch_event<'gc>(
 
     Ok(was_not_cancelled)
 }
+
+/// Dispatch a `NetStatusEvent` to `target`.
+///
+/// `info` is the event's info object, which can be built from a code and
+/// level with `net_status_info`.
+pub fn dispatch_net_status_event<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Object<'gc>,
+    info: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let event = activation.avm2().classes().netstatusevent.construct(
+        activation,
+        &["netStatus".into(), false.into(), false.into(), info],
+    )?;
+    dispatch_event(activation, target, event)?;
+    Ok(())
+}
+
+/// Build the info object of a `NetStatusEvent` with the given code and level.
+pub fn net_status_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    code: AvmString<'gc>,
+    level: AvmString<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut info = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+    info.set_public_property("code", code.into(), activation)?;
+    info.set_public_property("level", level.into(), activation)?;
+    Ok(info.into())
+}
+
+/// Dispatch a `StatusEvent` of type `status` to `target`.
+///
+/// Some senders, such as `LocalConnection`, leave the code as `null`.
+pub fn dispatch_status_event<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Object<'gc>,
+    code: Value<'gc>,
+    level: &'static str,
+) -> Result<(), Error<'gc>> {
+    let event = activation.avm2().classes().statusevent.construct(
+        activation,
+        &[
+            "status".into(),
+            false.into(),
+            false.into(),
+            code,
+            level.into(),
+        ],
+    )?;
+    dispatch_event(activation, target, event)?;
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+use ruffle_render::filters::{
+    DisplacementMapFilter, DisplacementMapFilterMode, Filter, ShaderFilter,
+};
 use swf::{
     BevelFilter, BevelFilterFlags, BlurFilter, BlurFilterFlags, Color, ColorMatrixFilter,
     ConvolutionFilter, ConvolutionFilterFlags, DropShadowFilter, DropShadowFilterFlags, Fixed16,
@@ -6,6 +8,7 @@ use swf::{
 };
 
 use crate::avm2::error::{argument_error, type_error};
+use crate::avm2::globals::flash::display::shader_data::{shader_arguments, shader_to_avm2};
 use crate::avm2::{Activation, ArrayObject, ClassObject, Error, Object, TObject, Value};
 
 pub trait FilterAvm2Ext {
@@ -74,6 +77,11 @@ impl FilterAvm2Ext for Filter {
             )?));
         }
 
+        let shader_filter = activation.avm2().classes().shaderfilter;
+        if object.is_of_type(shader_filter, activation) {
+            return avm2_to_shader_filter(activation, object);
+        }
+
         Err(Error::AvmError(type_error(
             activation,
             &format!(
@@ -105,6 +113,7 @@ impl FilterAvm2Ext for Filter {
                 let gradientglowfilter = activation.avm2().classes().gradientglowfilter;
                 gradient_filter_to_avm2(activation, filter, gradientglowfilter)
             }
+            Filter::ShaderFilter(filter) => shader_filter_to_avm2(activation, filter),
         }
     }
 }
@@ -707,6 +716,61 @@ fn gradient_filter_to_avm2<'gc>(
     )
 }
 
+fn avm2_to_shader_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let shader_data = match object
+        .get_public_property("shader", activation)?
+        .as_object()
+    {
+        Some(shader) => shader.get_public_property("data", activation)?.as_object(),
+        None => None,
+    };
+    let Some((shader, arguments)) = (match shader_data {
+        Some(shader_data) => shader_arguments(activation, shader_data)?,
+        None => None,
+    }) else {
+        // A filter without a shader leaves the pixels as they are.
+        return Ok(Filter::default());
+    };
+
+    let mut extension = |name: &'static str| -> Result<i32, Error<'gc>> {
+        object
+            .get_public_property(name, activation)?
+            .coerce_to_i32(activation)
+    };
+    Ok(Filter::ShaderFilter(ShaderFilter {
+        bottom_extension: extension("bottomExtension")?,
+        left_extension: extension("leftExtension")?,
+        right_extension: extension("rightExtension")?,
+        top_extension: extension("topExtension")?,
+        shader,
+        arguments,
+    }))
+}
+
+fn shader_filter_to_avm2<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    filter: &ShaderFilter,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let shader = shader_to_avm2(activation, &filter.shader, &filter.arguments)?;
+    let mut object = activation
+        .avm2()
+        .classes()
+        .shaderfilter
+        .construct(activation, &[shader.into()])?;
+    object.set_public_property(
+        "bottomExtension",
+        filter.bottom_extension.into(),
+        activation,
+    )?;
+    object.set_public_property("leftExtension", filter.left_extension.into(), activation)?;
+    object.set_public_property("rightExtension", filter.right_extension.into(), activation)?;
+    object.set_public_property("topExtension", filter.top_extension.into(), activation)?;
+    Ok(object)
+}
+
 fn get_gradient_colors<'gc>(
     activation: &mut Activation<'_, 'gc>,
     object: Object<'gc>,
@@ -737,12 +801,12 @@ fn get_gradient_colors<'gc>(
                                     .get(i)
                                     .expect("Length was already checked at this point")
                                     .coerce_to_u32(activation)?;
-                                let alpha = colors_array
+                                let alpha = alphas_array
                                     .get(i)
                                     .expect("Length was already checked at this point")
                                     .coerce_to_number(activation)?
                                     as f32;
-                                let ratio = colors_array
+                                let ratio = ratios_array
                                     .get(i)
                                     .expect("Length was already checked at this point")
                                     .coerce_to_u32(activation)?;
@@ -114,6 +114,7 @@ impl<'gc> Executable<'gc> {
                 let method = bm.method.method;
                 let receiver = bm.bound_receiver.or(unbound_receiver);
                 let caller_domain = activation.caller_domain();
+                let caller_movie = activation.caller_movie();
                 let subclass_object = bm.bound_superclass;
                 let mut activation = Activation::from_builtin(
                     activation.context.reborrow(),
@@ -121,6 +122,7 @@ impl<'gc> Executable<'gc> {
                     subclass_object,
                     bm.scope,
                     caller_domain,
+                    caller_movie,
                 )?;
 
                 if arguments.len() > bm.method.signature.len() && !bm.method.is_variadic {
@@ -154,6 +156,7 @@ impl<'gc> Executable<'gc> {
 
                 let receiver = bm.receiver.or(unbound_receiver);
                 let subclass_object = bm.bound_superclass;
+                let caller_movie = activation.caller_movie();
 
                 let mut activation = Activation::from_method(
                     activation.context.reborrow(),
@@ -163,6 +166,7 @@ impl<'gc> Executable<'gc> {
                     arguments,
                     subclass_object,
                     callee,
+                    caller_movie,
                 )?;
                 activation
                     .context
@@ -71,12 +71,17 @@ pub struct SystemClasses<'gc> {
     pub graphics: ClassObject<'gc>,
     pub igraphicsdata: ClassObject<'gc>,
     pub graphicsbitmapfill: ClassObject<'gc>,
+    pub graphicsshaderfill: ClassObject<'gc>,
     pub graphicsendfill: ClassObject<'gc>,
     pub graphicsgradientfill: ClassObject<'gc>,
     pub graphicspath: ClassObject<'gc>,
     pub graphicstrianglepath: ClassObject<'gc>,
     pub graphicssolidfill: ClassObject<'gc>,
     pub graphicsstroke: ClassObject<'gc>,
+    pub shader: ClassObject<'gc>,
+    pub shaderdata: ClassObject<'gc>,
+    pub shaderinput: ClassObject<'gc>,
+    pub shaderparameter: ClassObject<'gc>,
     pub loaderinfo: ClassObject<'gc>,
     pub bytearray: ClassObject<'gc>,
     pub stage: ClassObject<'gc>,
@@ -92,10 +97,17 @@ pub struct SystemClasses<'gc> {
     pub qname: ClassObject<'gc>,
     pub mouseevent: ClassObject<'gc>,
     pub progressevent: ClassObject<'gc>,
+    pub sampledataevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
+    pub dataevent: ClassObject<'gc>,
+    pub statusevent: ClassObject<'gc>,
+    pub activityevent: ClassObject<'gc>,
+    pub syncevent: ClassObject<'gc>,
+    pub netstatusevent: ClassObject<'gc>,
+    pub asyncerrorevent: ClassObject<'gc>,
     pub transform: ClassObject<'gc>,
     pub colortransform: ClassObject<'gc>,
     pub matrix: ClassObject<'gc>,
@@ -104,16 +116,25 @@ pub struct SystemClasses<'gc> {
     pub rectangle: ClassObject<'gc>,
     pub keyboardevent: ClassObject<'gc>,
     pub point: ClassObject<'gc>,
+    pub matrix3d: ClassObject<'gc>,
+    pub vector3d: ClassObject<'gc>,
+    pub perspectiveprojection: ClassObject<'gc>,
     pub rangeerror: ClassObject<'gc>,
     pub referenceerror: ClassObject<'gc>,
     pub argumenterror: ClassObject<'gc>,
     pub typeerror: ClassObject<'gc>,
     pub verifyerror: ClassObject<'gc>,
+    pub securityerror: ClassObject<'gc>,
     pub ioerror: ClassObject<'gc>,
     pub eoferror: ClassObject<'gc>,
     pub error: ClassObject<'gc>,
     pub uncaughterrorevents: ClassObject<'gc>,
     pub statictext: ClassObject<'gc>,
+    pub fontmetrics: ClassObject<'gc>,
+    pub textline: ClassObject<'gc>,
+    pub textelement: ClassObject<'gc>,
+    pub groupelement: ClassObject<'gc>,
+    pub graphicelement: ClassObject<'gc>,
     pub textlinemetrics: ClassObject<'gc>,
     pub stage3d: ClassObject<'gc>,
     pub context3d: ClassObject<'gc>,
@@ -121,6 +142,10 @@ pub struct SystemClasses<'gc> {
     pub vertexbuffer3d: ClassObject<'gc>,
     pub program3d: ClassObject<'gc>,
     pub urlvariables: ClassObject<'gc>,
+    pub filereference: ClassObject<'gc>,
+    pub id3info: ClassObject<'gc>,
+    pub microphone: ClassObject<'gc>,
+    pub camera: ClassObject<'gc>,
     pub bevelfilter: ClassObject<'gc>,
     pub bitmapfilter: ClassObject<'gc>,
     pub blurfilter: ClassObject<'gc>,
@@ -131,6 +156,7 @@ pub struct SystemClasses<'gc> {
     pub glowfilter: ClassObject<'gc>,
     pub gradientbevelfilter: ClassObject<'gc>,
     pub gradientglowfilter: ClassObject<'gc>,
+    pub shaderfilter: ClassObject<'gc>,
     pub texture: ClassObject<'gc>,
     pub cubetexture: ClassObject<'gc>,
     pub rectangletexture: ClassObject<'gc>,
@@ -179,12 +205,17 @@ impl<'gc> SystemClasses<'gc> {
             graphics: object,
             igraphicsdata: object,
             graphicsbitmapfill: object,
+            graphicsshaderfill: object,
             graphicsendfill: object,
             graphicsgradientfill: object,
             graphicspath: object,
             graphicstrianglepath: object,
             graphicssolidfill: object,
             graphicsstroke: object,
+            shader: object,
+            shaderdata: object,
+            shaderinput: object,
+            shaderparameter: object,
             loaderinfo: object,
             bytearray: object,
             stage: object,
@@ -200,10 +231,17 @@ impl<'gc> SystemClasses<'gc> {
             qname: object,
             mouseevent: object,
             progressevent: object,
+            sampledataevent: object,
             textevent: object,
             errorevent: object,
             ioerrorevent: object,
             securityerrorevent: object,
+            dataevent: object,
+            statusevent: object,
+            activityevent: object,
+            syncevent: object,
+            netstatusevent: object,
+            asyncerrorevent: object,
             transform: object,
             colortransform: object,
             matrix: object,
@@ -212,16 +250,25 @@ impl<'gc> SystemClasses<'gc> {
             rectangle: object,
             keyboardevent: object,
             point: object,
+            matrix3d: object,
+            vector3d: object,
+            perspectiveprojection: object,
             rangeerror: object,
             referenceerror: object,
             argumenterror: object,
             typeerror: object,
             verifyerror: object,
+            securityerror: object,
             ioerror: object,
             eoferror: object,
             error: object,
             uncaughterrorevents: object,
             statictext: object,
+            fontmetrics: object,
+            textline: object,
+            textelement: object,
+            groupelement: object,
+            graphicelement: object,
             textlinemetrics: object,
             stage3d: object,
             context3d: object,
@@ -229,6 +276,10 @@ impl<'gc> SystemClasses<'gc> {
             vertexbuffer3d: object,
             program3d: object,
             urlvariables: object,
+            filereference: object,
+            id3info: object,
+            microphone: object,
+            camera: object,
             bevelfilter: object,
             bitmapfilter: object,
             blurfilter: object,
@@ -239,6 +290,7 @@ impl<'gc> SystemClasses<'gc> {
             glowfilter: object,
             gradientbevelfilter: object,
             gradientglowfilter: object,
+            shaderfilter: object,
             texture: object,
             cubetexture: object,
             rectangletexture: object,
@@ -559,7 +611,8 @@ fn load_playerglobal<'gc>(
     let movie = SwfMovie::from_data(PLAYERGLOBAL, "file:///".into(), None)
         .expect("playerglobal.swf should be valid");
 
-    let slice = SwfSlice::from(Arc::new(movie));
+    let movie = Arc::new(movie);
+    let slice = SwfSlice::from(movie.clone());
 
     let mut reader = slice.read_from(0);
 
@@ -574,6 +627,7 @@ fn load_playerglobal<'gc>(
                 None,
                 do_abc.flags,
                 domain,
+                movie.clone(),
             )
             .expect("playerglobal.swf should be valid");
         } else if tag_code != TagCode::End {
@@ -607,6 +661,7 @@ fn load_playerglobal<'gc>(
             ("", "ReferenceError", referenceerror),
             ("", "TypeError", typeerror),
             ("", "VerifyError", verifyerror),
+            ("", "SecurityError", securityerror),
             ("", "XML", xml),
             ("", "XMLList", xml_list),
             ("flash.display", "Bitmap", bitmap),
@@ -615,6 +670,7 @@ fn load_playerglobal<'gc>(
             ("flash.display", "FrameLabel", framelabel),
             ("flash.display", "IGraphicsData", igraphicsdata),
             ("flash.display", "GraphicsBitmapFill", graphicsbitmapfill),
+            ("flash.display", "GraphicsShaderFill", graphicsshaderfill),
             ("flash.display", "GraphicsEndFill", graphicsendfill),
             (
                 "flash.display",
@@ -632,6 +688,10 @@ fn load_playerglobal<'gc>(
             ("flash.display", "Graphics", graphics),
             ("flash.display", "LoaderInfo", loaderinfo),
             ("flash.display", "MovieClip", movieclip),
+            ("flash.display", "Shader", shader),
+            ("flash.display", "ShaderData", shaderdata),
+            ("flash.display", "ShaderInput", shaderinput),
+            ("flash.display", "ShaderParameter", shaderparameter),
             ("flash.display", "Shape", shape),
             ("flash.display", "SimpleButton", simplebutton),
             ("flash.display", "Sprite", sprite),
@@ -661,25 +721,44 @@ fn load_playerglobal<'gc>(
             ("flash.events", "ErrorEvent", errorevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
             ("flash.events", "ProgressEvent", progressevent),
+            ("flash.events", "SampleDataEvent", sampledataevent),
             ("flash.events", "SecurityErrorEvent", securityerrorevent),
             ("flash.events", "IOErrorEvent", ioerrorevent),
+            ("flash.events", "DataEvent", dataevent),
+            ("flash.events", "StatusEvent", statusevent),
+            ("flash.events", "ActivityEvent", activityevent),
+            ("flash.events", "SyncEvent", syncevent),
+            ("flash.events", "NetStatusEvent", netstatusevent),
+            ("flash.events", "AsyncErrorEvent", asyncerrorevent),
             ("flash.events", "MouseEvent", mouseevent),
             ("flash.events", "FullScreenEvent", fullscreenevent),
             ("flash.events", "UncaughtErrorEvents", uncaughterrorevents),
             ("flash.geom", "Matrix", matrix),
             ("flash.geom", "Point", point),
+            ("flash.geom", "Matrix3D", matrix3d),
+            ("flash.geom", "Vector3D", vector3d),
+            ("flash.geom", "PerspectiveProjection", perspectiveprojection),
             ("flash.geom", "Rectangle", rectangle),
             ("flash.geom", "Transform", transform),
             ("flash.geom", "ColorTransform", colortransform),
+            ("flash.media", "ID3Info", id3info),
+            ("flash.media", "Microphone", microphone),
+            ("flash.media", "Camera", camera),
             ("flash.media", "SoundChannel", soundchannel),
             ("flash.media", "SoundTransform", soundtransform),
             ("flash.net", "URLVariables", urlvariables),
+            ("flash.net", "FileReference", filereference),
             ("flash.utils", "ByteArray", bytearray),
             ("flash.system", "ApplicationDomain", application_domain),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextFormat", textformat),
             ("flash.text", "TextField", textfield),
             ("flash.text", "TextLineMetrics", textlinemetrics),
+            ("flash.text.engine", "FontMetrics", fontmetrics),
+            ("flash.text.engine", "TextLine", textline),
+            ("flash.text.engine", "TextElement", textelement),
+            ("flash.text.engine", "GroupElement", groupelement),
+            ("flash.text.engine", "GraphicElement", graphicelement),
             ("flash.filters", "BevelFilter", bevelfilter),
             ("flash.filters", "BitmapFilter", bitmapfilter),
             ("flash.filters", "BlurFilter", blurfilter),
@@ -694,6 +773,7 @@ fn load_playerglobal<'gc>(
             ("flash.filters", "GlowFilter", glowfilter),
             ("flash.filters", "GradientBevelFilter", gradientbevelfilter),
             ("flash.filters", "GradientGlowFilter", gradientglowfilter),
+            ("flash.filters", "ShaderFilter", shaderfilter),
         ]
     );
 
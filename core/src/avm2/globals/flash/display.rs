@@ -9,6 +9,8 @@ pub mod interactive_object;
 pub mod loader;
 pub mod loader_info;
 pub mod movie_clip;
+pub mod shader_data;
+pub mod shader_job;
 pub mod shape;
 pub mod simple_button;
 pub mod sprite;
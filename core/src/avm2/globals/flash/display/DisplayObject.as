@@ -1,6 +1,7 @@
 package flash.display {
     
     import flash.accessibility.AccessibilityProperties;
+    import flash.geom.Matrix;
     import flash.geom.Rectangle;
     import flash.geom.Transform;
     import flash.display.DisplayObject;
@@ -8,6 +9,7 @@ package flash.display {
     import flash.display.LoaderInfo;
     import flash.display.Stage;
     import flash.geom.Point;
+    import flash.geom.Vector3D;
     import flash.events.EventDispatcher;
     
     import __ruffle__.stub_getter;
@@ -35,6 +37,8 @@ package flash.display {
         public native function get blendMode():String;
         public native function set blendMode(value:String):void;
 
+        public native function set blendShader(value:Shader):void;
+
         public native function get height():Number;
         public native function set height(value:Number):void;
 
@@ -71,13 +75,8 @@ package flash.display {
         public native function get scaleZ():Number;
         public native function set scaleZ(value:Number):void;
         
-        public function get scale9Grid():Rectangle {
-            stub_getter("flash.display.DisplayObject", "scale9Grid");
-            return null;
-        }
-        public function set scale9Grid(value:Rectangle):void {
-            stub_setter("flash.display.DisplayObject", "scale9Grid");
-        }
+        public native function get scale9Grid():Rectangle;
+        public native function set scale9Grid(value:Rectangle):void;
 
         public native function get name():String;
         public native function set name(value:String):void;
@@ -115,6 +114,9 @@ package flash.display {
         public native function get cacheAsBitmap():Boolean;
         public native function set cacheAsBitmap(value:Boolean):void;
 
+        public native function get cacheAsBitmapMatrix():Matrix;
+        public native function set cacheAsBitmapMatrix(value:Matrix):void;
+
         public native function hitTestPoint(x:Number, y:Number, shapeFlag:Boolean = false):Boolean;
 
         public native function hitTestObject(obj:DisplayObject):Boolean;
@@ -123,6 +125,10 @@ package flash.display {
 
         public native function globalToLocal(point:Point):Point;
 
+        public native function local3DToGlobal(point3d:Vector3D):Point;
+
+        public native function globalToLocal3D(point:Point):Vector3D;
+
         public native function getBounds(targetCoordinateSpace:DisplayObject):Rectangle;
 
         public native function getRect(targetCoordinateSpace:DisplayObject):Rectangle;
@@ -0,0 +1,13 @@
+package flash.display {
+    import flash.geom.Matrix;
+
+    public final class GraphicsShaderFill implements IGraphicsFill, IGraphicsData {
+        public var matrix : Matrix;
+        public var shader : Shader;
+
+        public function GraphicsShaderFill(shader:Shader = null, matrix:Matrix = null) {
+            this.shader = shader;
+            this.matrix = matrix;
+        }
+    }
+}
@@ -24,9 +24,12 @@ package flash.display {
 			if (this.numChildren == 0) {
 				return null;
 			}
+			this.checkContentAccess();
 			return this.getChildAt(0)
 		}
 
+		private native function checkContentAccess():void;
+
 		public native function load(request: URLRequest, context: LoaderContext = null):void;
 
 		public native function loadBytes(data: ByteArray, context: LoaderContext = null):void;
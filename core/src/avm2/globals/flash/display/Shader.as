@@ -0,0 +1,35 @@
+package flash.display {
+    import flash.utils.ByteArray;
+
+    public class Shader {
+        private var _data:ShaderData;
+        private var _precisionHint:String = ShaderPrecision.FULL;
+
+        public function Shader(code:ByteArray = null) {
+            if (code) {
+                this.byteCode = code;
+            }
+        }
+
+        public function set byteCode(code:ByteArray):void {
+            this._data = new ShaderData(code);
+        }
+
+        public function get data():ShaderData {
+            return this._data;
+        }
+
+        public function set data(value:ShaderData):void {
+            this._data = value;
+        }
+
+        public function get precisionHint():String {
+            return this._precisionHint;
+        }
+
+        public function set precisionHint(value:String):void {
+            // Every shader runs at full precision, so the hint is only remembered.
+            this._precisionHint = value;
+        }
+    }
+}
@@ -0,0 +1,14 @@
+package flash.display {
+    import flash.utils.ByteArray;
+
+    // Holds a `ShaderParameter` or `ShaderInput` for every parameter of the shader, under
+    // the name of that parameter, along with the metadata of the shader itself.
+    [Ruffle(InstanceAllocator)]
+    public final dynamic class ShaderData {
+        public function ShaderData(byteCode:ByteArray) {
+            this.init(byteCode);
+        }
+
+        private native function init(byteCode:ByteArray):void;
+    }
+}
@@ -0,0 +1,18 @@
+package flash.display {
+    public final dynamic class ShaderInput {
+        internal var _channels:int;
+        internal var _index:int;
+
+        public var height:int = 0;
+        public var input:Object;
+        public var width:int = 0;
+
+        public function get channels():int {
+            return this._channels;
+        }
+
+        public function get index():int {
+            return this._index;
+        }
+    }
+}
@@ -0,0 +1,48 @@
+package flash.display {
+    import flash.events.EventDispatcher;
+    import flash.events.ShaderEvent;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
+
+    public class ShaderJob extends EventDispatcher {
+        public var height:int;
+        public var shader:Shader;
+        public var target:Object;
+        public var width:int;
+
+        private var _progress:Number = 0;
+
+        public function ShaderJob(shader:Shader = null, target:Object = null, width:int = 0, height:int = 0) {
+            this.shader = shader;
+            this.target = target;
+            this.width = width;
+            this.height = height;
+        }
+
+        public function get progress():Number {
+            return this._progress;
+        }
+
+        public function start(waitForCompletion:Boolean = false):void {
+            this.runShader();
+            this._progress = 1;
+
+            // The shader always runs right away, but only asynchronous jobs announce that
+            // they are done. Like in Flash Player, that happens after `start` returns.
+            if (!waitForCompletion) {
+                var job:ShaderJob = this;
+                var target:Object = this.target;
+                setTimeout(function():void {
+                    job.dispatchEvent(new ShaderEvent(ShaderEvent.COMPLETE, false, false,
+                        target as BitmapData, target as ByteArray, target as Vector.<Number>));
+                }, 0);
+            }
+        }
+
+        public function cancel():void {
+            // Jobs finish within `start`, so there's never anything to cancel.
+        }
+
+        private native function runShader():void;
+    }
+}
@@ -0,0 +1,16 @@
+package flash.display {
+    public final dynamic class ShaderParameter {
+        internal var _index:int;
+        internal var _type:String;
+
+        public var value:Array;
+
+        public function get index():int {
+            return this._index;
+        }
+
+        public function get type():String {
+            return this._type;
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! `flash.display.BitmapData` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::error::argument_error;
+use crate::avm2::error::{argument_error, security_error};
 use crate::avm2::filters::FilterAvm2Ext;
 use crate::avm2::object::{BitmapDataObject, ByteArrayObject, Object, TObject, VectorObject};
 use crate::avm2::value::Value;
@@ -10,9 +10,10 @@ use crate::avm2::Error;
 use crate::avm2_stub_method;
 use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, Color, ThresholdOperation};
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
+use crate::bitmap::filters;
 use crate::bitmap::is_size_valid;
 use crate::character::Character;
-use crate::display_object::Bitmap;
+use crate::display_object::{Bitmap, DisplayObject};
 use crate::swf::BlendMode;
 use gc_arena::GcCell;
 use ruffle_render::filters::Filter;
@@ -795,6 +796,32 @@ pub fn hit_test<'gc>(
     Ok(false.into())
 }
 
+/// Throws a `SecurityError` if the caller may not read pixels from any
+/// content being drawn.
+fn check_draw_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    source: DisplayObject<'gc>,
+) -> Result<(), Error<'gc>> {
+    let accessor = activation.caller_movie();
+    if let Some(content_url) = activation.context.security.find_unreadable_content(
+        &activation.context.system.sandbox_type,
+        accessor.url(),
+        source,
+    ) {
+        return Err(Error::AvmError(security_error(
+            activation,
+            &format!(
+                "Error #2122: Security sandbox violation: BitmapData.draw: {} cannot access {}. A policy file is required, but the checkPolicyFile flag was not set when this media was loaded.",
+                accessor.url(),
+                content_url
+            ),
+            2122,
+        )?));
+    }
+
+    Ok(())
+}
+
 /// Implements `BitmapData.draw`
 pub fn draw<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -841,6 +868,7 @@ pub fn draw<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, source_object)?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data_wrapper() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -923,6 +951,7 @@ pub fn draw_with_quality<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, source_object)?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data_wrapper() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -1041,23 +1070,16 @@ pub fn apply_filter<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dest_bitmap) = this.and_then(|this| this.as_bitmap_data_wrapper()) {
-        let dest_bitmap_data = dest_bitmap.overwrite_cpu_pixels_from_gpu(&mut activation.context);
-        dest_bitmap_data.read().check_valid(activation)?;
+        dest_bitmap.check_valid(activation)?;
         let source_bitmap = args.get_object(activation, 0, "sourceBitmapData")?
-            .as_bitmap_data()
+            .as_bitmap_data_wrapper()
             .ok_or_else(|| {
                 Error::from(format!("TypeError: Error #1034: Type Coercion failed: cannot convert {} to flash.display.BitmapData.", args[0].coerce_to_string(activation).unwrap_or_default()))
             })?;
-        let source_handle = match source_bitmap
-            .write(activation.context.gc_context)
-            .bitmap_handle(activation.context.renderer)
-        {
-            Some(handle) => handle,
-            None => {
-                tracing::warn!("Ignoring BitmapData.apply_filter() with an undrawable source");
-                return Ok(Value::Undefined);
-            }
-        };
+        if source_bitmap.disposed() {
+            tracing::warn!("Ignoring BitmapData.apply_filter() with an undrawable source");
+            return Ok(Value::Undefined);
+        }
         let source_rect = args.get_object(activation, 1, "sourceRect")?;
         let source_rect = super::display_object::object_to_rectangle(activation, source_rect)?;
         let source_point = (
@@ -1077,17 +1099,59 @@ pub fn apply_filter<'gc>(
                 .get_public_property("y", activation)?
                 .coerce_to_u32(activation)?,
         );
-        let filter = args.get_object(activation, 3, "filter")?;
-        let filter = Filter::from_avm2_object(activation, filter)?;
-        let mut dest_bitmap_data = dest_bitmap_data.write(activation.context.gc_context);
-        dest_bitmap_data.apply_filter(
+        let filter_object = args.get_object(activation, 3, "filter")?;
+        let filter = Filter::from_avm2_object(activation, filter_object)?;
+        let displacement_map = if let Filter::DisplacementMapFilter(_) = filter {
+            filter_object
+                .get_public_property("mapBitmap", activation)?
+                .as_object()
+                .and_then(|map| map.as_bitmap_data_wrapper())
+        } else {
+            None
+        };
+        dest_bitmap.apply_filter(
             &mut activation.context,
-            source_handle,
+            source_bitmap,
             source_point,
             source_size,
             dest_point,
             filter,
-        )
+            displacement_map,
+        );
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implement `BitmapData.generateFilterRect`
+pub fn generate_filter_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data_wrapper()) {
+        bitmap_data.check_valid(activation)?;
+        let source_rect = args.get_object(activation, 0, "sourceRect")?;
+        let source_rect = super::display_object::object_to_rectangle(activation, source_rect)?;
+        let filter = args.get_object(activation, 1, "filter")?;
+        let filter = Filter::from_avm2_object(activation, filter)?;
+        let (x, y, width, height) = filters::calculate_dest_rect(
+            &filter,
+            (
+                source_rect.x_min.to_pixels() as i32,
+                source_rect.y_min.to_pixels() as i32,
+                source_rect.width().to_pixels() as i32,
+                source_rect.height().to_pixels() as i32,
+            ),
+        );
+        return Ok(activation
+            .avm2()
+            .classes()
+            .rectangle
+            .construct(
+                activation,
+                &[x.into(), y.into(), width.into(), height.into()],
+            )?
+            .into());
     }
     Ok(Value::Undefined)
 }
@@ -2,20 +2,24 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::filters::FilterAvm2Ext;
+use crate::avm2::globals::flash::display::shader_data::shader_arguments;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::{ArrayObject, ArrayStorage};
-use crate::display_object::{DisplayObject, HitTestOptions, TDisplayObject};
+use crate::display_object::{
+    global_to_local_3d, local_3d_to_global, DisplayObject, HitTestOptions, TDisplayObject,
+    Transform3D,
+};
 use crate::ecma_conversions::round_to_even;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::prelude::*;
 use crate::string::AvmString;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::Instantiator;
-use crate::{avm2_stub_getter, avm2_stub_setter};
+use ruffle_render::commands::BlendShader;
 use ruffle_render::filters::Filter;
 use std::str::FromStr;
 use swf::BlendMode;
@@ -352,96 +356,169 @@ pub fn set_y<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `z`'s getter.
 pub fn get_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "z");
-    Ok(0.into())
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.transform_3d().unwrap_or_default().z.into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `z`'s setter.
 pub fn set_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "z");
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let z = args.get_f64(activation, 0)?;
+        let transform_3d = Transform3D {
+            z,
+            ..dobj.transform_3d().unwrap_or_default()
+        };
+        dobj.set_transform_3d(activation.context.gc_context, Some(transform_3d));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s getter.
 pub fn get_rotation_x<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationX");
-    Ok(0.into())
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rotation: f64 = dobj.transform_3d().unwrap_or_default().rotation_x.into();
+        return Ok(normalize_rotation(rotation).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s setter.
 pub fn set_rotation_x<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationX");
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rotation_x = args.get_f64(activation, 0)?;
+        let transform_3d = Transform3D {
+            rotation_x: Degrees::from(rotation_x),
+            ..dobj.transform_3d().unwrap_or_default()
+        };
+        dobj.set_transform_3d(activation.context.gc_context, Some(transform_3d));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s getter.
 pub fn get_rotation_y<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationY");
-    Ok(0.into())
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rotation: f64 = dobj.transform_3d().unwrap_or_default().rotation_y.into();
+        return Ok(normalize_rotation(rotation).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s setter.
 pub fn set_rotation_y<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationY");
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rotation_y = args.get_f64(activation, 0)?;
+        let transform_3d = Transform3D {
+            rotation_y: Degrees::from(rotation_y),
+            ..dobj.transform_3d().unwrap_or_default()
+        };
+        dobj.set_transform_3d(activation.context.gc_context, Some(transform_3d));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationZ`'s getter.
+///
+/// This is the same as `rotation`.
 pub fn get_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(0.into())
+    get_rotation(activation, this, args)
 }
 
+/// Implements `rotationZ`'s setter.
+///
+/// This is the same as `rotation`, except that it also turns the object into a 3D object.
 pub fn set_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(Value::Undefined)
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let transform_3d = dobj.transform_3d().unwrap_or_default();
+        dobj.set_transform_3d(activation.context.gc_context, Some(transform_3d));
+    }
+
+    set_rotation(activation, this, args)
 }
 
+/// Implements `scaleZ`'s getter.
 pub fn get_scale_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "scaleZ");
-    Ok(1.into())
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.transform_3d().unwrap_or_default().scale_z.into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `scaleZ`'s setter.
 pub fn set_scale_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "scaleZ");
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let scale_z = args.get_f64(activation, 0)?;
+        let transform_3d = Transform3D {
+            scale_z,
+            ..dobj.transform_3d().unwrap_or_default()
+        };
+        dobj.set_transform_3d(activation.context.gc_context, Some(transform_3d));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Wraps a rotation in degrees into the range `-180..=180`, as the rotation getters do.
+fn normalize_rotation(rotation: f64) -> f64 {
+    let rem = rotation % 360.0;
+    if rem <= 180.0 {
+        rem
+    } else {
+        rem - 360.0
+    }
+}
+
 /// Implements `rotation`'s getter.
 pub fn get_rotation<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -450,13 +527,7 @@ pub fn get_rotation<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let rot: f64 = dobj.rotation(activation.context.gc_context).into();
-        let rem = rot % 360.0;
-
-        if rem <= 180.0 {
-            return Ok(rem.into());
-        } else {
-            return Ok((rem - 360.0).into());
-        }
+        return Ok(normalize_rotation(rot).into());
     }
 
     Ok(Value::Undefined)
@@ -728,9 +799,8 @@ pub fn set_transform<'gc>(
             )?;
 
         let dobj = this.as_display_object().unwrap();
-        let mut write = dobj.base_mut(activation.context.gc_context);
-        write.set_matrix(matrix);
-        write.set_color_transform(color_transform);
+        dobj.set_matrix(activation.context.gc_context, matrix);
+        dobj.set_color_transform(activation.context.gc_context, color_transform);
     }
     Ok(Value::Undefined)
 }
@@ -742,6 +812,10 @@ pub fn get_blend_mode<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        // Setting `blendShader` switches the blend mode to "shader".
+        if dobj.blend_shader().is_some() {
+            return Ok("shader".into());
+        }
         let mode =
             AvmString::new_utf8(activation.context.gc_context, dobj.blend_mode().to_string());
         return Ok(mode.into());
@@ -759,6 +833,8 @@ pub fn set_blend_mode<'gc>(
         let mode = args.get_string(activation, 0)?;
 
         if let Ok(mode) = BlendMode::from_str(&mode.to_string()) {
+            // A blend mode replaces any blend shader.
+            dobj.set_blend_shader(activation.context.gc_context, None);
             dobj.set_blend_mode(activation.context.gc_context, mode);
         } else {
             tracing::error!("Unknown blend mode {}", mode);
@@ -768,6 +844,30 @@ pub fn set_blend_mode<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `DisplayObject.blendShader`'s setter.
+///
+/// The parameters and inputs of the shader are copied, so changing them later
+/// has no effect until the shader is set again.
+pub fn set_blend_shader<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let shader_data = match args.try_get_object(activation, 0) {
+            Some(shader) => shader.get_public_property("data", activation)?.as_object(),
+            None => None,
+        };
+        let blend_shader = match shader_data {
+            Some(shader_data) => shader_arguments(activation, shader_data)?
+                .map(|(shader, arguments)| BlendShader { shader, arguments }),
+            None => None,
+        };
+        dobj.set_blend_shader(activation.context.gc_context, blend_shader);
+    }
+    Ok(Value::Undefined)
+}
+
 fn new_rectangle<'gc>(
     activation: &mut Activation<'_, 'gc>,
     rectangle: Rectangle<Twips>,
@@ -799,6 +899,55 @@ pub fn get_scroll_rect<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_scale9grid<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rect = dobj.scaling_grid();
+        return if rect.is_valid() {
+            Ok(new_rectangle(activation, rect)?.into())
+        } else {
+            Ok(Value::Null)
+        };
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_scale9grid<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rect = match args.try_get_object(activation, 0) {
+            Some(rect) => {
+                let rect = object_to_rectangle(activation, rect)?;
+
+                // The grid has to lie within the content of the object.
+                let bounds = dobj.scaling_grid_bounds();
+                if !bounds.is_valid()
+                    || rect.x_min < bounds.x_min
+                    || rect.y_min < bounds.y_min
+                    || rect.x_max > bounds.x_max
+                    || rect.y_max > bounds.y_max
+                {
+                    return Err(Error::AvmError(crate::avm2::error::argument_error(
+                        activation,
+                        "Error #2004: One of the parameters is invalid.",
+                        2004,
+                    )?));
+                }
+                rect
+            }
+            None => Default::default(),
+        };
+        dobj.set_scaling_grid(activation.context.gc_context, rect);
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn object_to_rectangle<'gc>(
     activation: &mut Activation<'_, 'gc>,
     object: Object<'gc>,
@@ -908,6 +1057,74 @@ pub fn global_to_local<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn local3d_to_global<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let point = args.get_object(activation, 0, "point3d")?;
+        let x = point
+            .get_public_property("x", activation)?
+            .coerce_to_number(activation)?;
+        let y = point
+            .get_public_property("y", activation)?
+            .coerce_to_number(activation)?;
+        let z = point
+            .get_public_property("z", activation)?
+            .coerce_to_number(activation)?;
+
+        // Points behind the viewer end up infinitely far away.
+        let (out_x, out_y) = local_3d_to_global(
+            dobj,
+            activation.context.gc_context,
+            activation.context.stage,
+            (x, y, z),
+        )
+        .unwrap_or((f64::INFINITY, f64::INFINITY));
+        return Ok(activation
+            .avm2()
+            .classes()
+            .point
+            .construct(activation, &[out_x.into(), out_y.into()])?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn global_to_local3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let point = args.get_object(activation, 0, "point")?;
+        let x = point
+            .get_public_property("x", activation)?
+            .coerce_to_number(activation)?;
+        let y = point
+            .get_public_property("y", activation)?
+            .coerce_to_number(activation)?;
+
+        let (out_x, out_y) = global_to_local_3d(
+            dobj,
+            activation.context.gc_context,
+            activation.context.stage,
+            (x, y),
+        )
+        .unwrap_or((f64::NAN, f64::NAN));
+        return Ok(activation
+            .avm2()
+            .classes()
+            .vector3d
+            .construct(activation, &[out_x.into(), out_y.into(), 0.into()])?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bounds<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
@@ -1003,6 +1220,41 @@ pub fn set_cache_as_bitmap<'gc>(
     Ok(Value::Undefined)
 }
 
+/// `cacheAsBitmapMatrix`'s getter.
+pub fn get_cache_as_bitmap_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_display_object()) {
+        return match this.cache_as_bitmap_matrix() {
+            Some(matrix) => {
+                crate::avm2::globals::flash::geom::transform::matrix_to_object(matrix, activation)
+            }
+            None => Ok(Value::Null),
+        };
+    }
+    Ok(Value::Undefined)
+}
+
+/// `cacheAsBitmapMatrix`'s setter.
+pub fn set_cache_as_bitmap_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_display_object()) {
+        let matrix = match args.try_get_object(activation, 0) {
+            Some(matrix) => Some(
+                crate::avm2::globals::flash::geom::transform::object_to_matrix(matrix, activation)?,
+            ),
+            None => None,
+        };
+        this.set_cache_as_bitmap_matrix(activation.context.gc_context, matrix);
+    }
+    Ok(Value::Undefined)
+}
+
 /// `opaqueBackground`'s getter.
 pub fn get_opaque_background<'gc>(
     _activation: &mut Activation<'_, 'gc>,
@@ -2,17 +2,21 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::argument_error;
-use crate::avm2::globals::flash::geom::transform::object_to_matrix;
-use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::globals::flash::display::shader_data::shader_arguments;
+use crate::avm2::globals::flash::geom::transform::{matrix_to_object, object_to_matrix};
+use crate::avm2::object::{ArrayObject, Object, TObject, VectorObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::{ArrayStorage, Error};
 use crate::avm2_stub_method;
-use crate::display_object::TDisplayObject;
-use crate::drawing::Drawing;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::drawing::{Drawing, Triangle};
 use crate::string::{AvmString, WStr};
-use ruffle_render::shape_utils::{DrawCommand, GradientType};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapInfo};
+use ruffle_render::pixel_bender::interpreter::run_shader;
+use ruffle_render::pixel_bender::PixelBenderShaderArgument;
+use ruffle_render::shape_utils::{DrawCommand, DrawPath, FillRule, GradientType};
 use std::f64::consts::FRAC_1_SQRT_2;
 use swf::{
     Color, FillStyle, Fixed16, Fixed8, Gradient, GradientInterpolation, GradientRecord,
@@ -96,6 +100,96 @@ pub fn begin_bitmap_fill<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Runs a `Shader` once into a tile, and describes a repeating bitmap fill of that tile.
+///
+/// Shaders are run on the CPU, so the fill doesn't follow later changes to the shader.
+/// The tile is as large as the largest input image, or the stage if there are no inputs.
+fn shader_fill_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: DisplayObject<'gc>,
+    shader: Object<'gc>,
+    matrix: Matrix,
+) -> Result<Option<FillStyle>, Error<'gc>> {
+    let Some(shader_data) = shader.get_public_property("data", activation)?.as_object() else {
+        return Ok(None);
+    };
+    let Some((shader, arguments)) = shader_arguments(activation, shader_data)? else {
+        return Ok(None);
+    };
+
+    let (width, height) = arguments
+        .iter()
+        .filter_map(|argument| match argument {
+            PixelBenderShaderArgument::ImageInput {
+                image: Some(image), ..
+            } => Some((image.width, image.height)),
+            _ => None,
+        })
+        .max_by_key(|(width, height)| width * height)
+        .unwrap_or_else(|| activation.context.stage.stage_size());
+    if width == 0 || height == 0 {
+        return Ok(None);
+    }
+
+    let output = run_shader(&shader, &arguments, width, height);
+    let pixels = output
+        .to_premultiplied()
+        .into_iter()
+        .flat_map(|pixel| pixel.map(|channel| (channel * 255.0).round() as u8))
+        .collect();
+    let bitmap = Bitmap::new(width, height, BitmapFormat::Rgba, pixels);
+    let handle = match activation.context.renderer.register_bitmap(bitmap) {
+        Ok(handle) => handle,
+        Err(e) => {
+            tracing::warn!("Failed to register shader fill: {:?}", e);
+            return Ok(None);
+        }
+    };
+
+    let Some(mut draw) = this.as_drawing(activation.context.gc_context) else {
+        return Ok(None);
+    };
+    let id = draw.add_bitmap(BitmapInfo {
+        handle,
+        width: width as u16,
+        height: height as u16,
+    });
+    let scale_matrix = Matrix::scale(
+        Fixed16::from_f64(width as f64),
+        Fixed16::from_f64(height as f64),
+    );
+    Ok(Some(FillStyle::Bitmap {
+        id,
+        matrix: matrix * scale_matrix,
+        is_smoothed: false,
+        is_repeating: true,
+    }))
+}
+
+/// Implements `Graphics.beginShaderFill`.
+pub fn begin_shader_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let shader = args.get_object(activation, 0, "shader")?;
+        let matrix = if let Some(matrix) = args.try_get_object(activation, 1) {
+            Matrix::from(object_to_matrix(matrix, activation)?)
+        } else {
+            // Users can explicitly pass in `null` to mean identity matrix
+            Matrix::IDENTITY
+        };
+
+        let fill_style = shader_fill_style(activation, this, shader, matrix)?;
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_fill_style(fill_style);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `Graphics.beginGradientFill`.
 pub fn begin_gradient_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -830,60 +924,545 @@ pub fn line_gradient_style<'gc>(
 /// Implements `Graphics.cubicCurveTo`
 pub fn cubic_curve_to<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "cubicCurveTo");
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let control_x1 = Twips::from_pixels(args.get_f64(activation, 0)?);
+        let control_y1 = Twips::from_pixels(args.get_f64(activation, 1)?);
+        let control_x2 = Twips::from_pixels(args.get_f64(activation, 2)?);
+        let control_y2 = Twips::from_pixels(args.get_f64(activation, 3)?);
+        let anchor_x = Twips::from_pixels(args.get_f64(activation, 4)?);
+        let anchor_y = Twips::from_pixels(args.get_f64(activation, 5)?);
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.cubic_curve_to(
+                (control_x1, control_y1),
+                (control_x2, control_y2),
+                (anchor_x, anchor_y),
+            );
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
 /// Implements `Graphics.copyFrom`
 pub fn copy_from<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "copyFrom");
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let source = args
+            .get_object(activation, 0, "sourceGraphics")?
+            .as_display_object()
+            .expect("Source graphics is ensured to be a Graphics from actionscript");
+
+        // Copy first, as the source may be this object.
+        let drawing = source
+            .as_drawing(activation.context.gc_context)
+            .map(|drawing| (*drawing).clone());
+
+        if let Some(drawing) = drawing {
+            if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+                *draw = drawing;
+            }
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Reads the values of a `Vector.<Number>`.
+fn vector_to_f64s<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    vector: Object<'gc>,
+) -> Result<Vec<f64>, Error<'gc>> {
+    let values: Vec<_> = vector
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect())
+        .unwrap_or_default();
+    values
+        .into_iter()
+        .map(|value| value.coerce_to_number(activation))
+        .collect()
+}
+
+/// Reads the values of a `Vector.<int>`.
+fn vector_to_i32s<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    vector: Object<'gc>,
+) -> Result<Vec<i32>, Error<'gc>> {
+    let values: Vec<_> = vector
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect())
+        .unwrap_or_default();
+    values
+        .into_iter()
+        .map(|value| value.coerce_to_i32(activation))
+        .collect()
+}
+
+fn parse_winding<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    winding: AvmString<'gc>,
+) -> Result<FillRule, Error<'gc>> {
+    if &winding == b"evenOdd" {
+        Ok(FillRule::EvenOdd)
+    } else if &winding == b"nonZero" {
+        Ok(FillRule::NonZero)
+    } else {
+        Err(Error::AvmError(argument_error(
+            activation,
+            "Parameter winding must be one of the accepted values.",
+            2008,
+        )?))
+    }
+}
+
+/// Draws the commands of a path, as described by `GraphicsPathCommand`.
+///
+/// Drawing stops at the first command that doesn't have enough data.
+fn draw_path_internal(draw: &mut Drawing, commands: &[i32], data: &[f64]) -> Option<()> {
+    let mut data = data.iter().map(|value| Twips::from_pixels(*value));
+    let mut next_point = move || Some((data.next()?, data.next()?));
+
+    for command in commands {
+        match command {
+            // MOVE_TO
+            1 => {
+                let (x, y) = next_point()?;
+                draw.draw_command(DrawCommand::MoveTo { x, y });
+            }
+            // LINE_TO
+            2 => {
+                let (x, y) = next_point()?;
+                draw.draw_command(DrawCommand::LineTo { x, y });
+            }
+            // CURVE_TO
+            3 => {
+                let (x1, y1) = next_point()?;
+                let (x2, y2) = next_point()?;
+                draw.draw_command(DrawCommand::CurveTo { x1, y1, x2, y2 });
+            }
+            // WIDE_MOVE_TO, where the first point is unused
+            4 => {
+                next_point()?;
+                let (x, y) = next_point()?;
+                draw.draw_command(DrawCommand::MoveTo { x, y });
+            }
+            // WIDE_LINE_TO, where the first point is unused
+            5 => {
+                next_point()?;
+                let (x, y) = next_point()?;
+                draw.draw_command(DrawCommand::LineTo { x, y });
+            }
+            // CUBIC_CURVE_TO
+            6 => {
+                let control_a = next_point()?;
+                let control_b = next_point()?;
+                let anchor = next_point()?;
+                draw.cubic_curve_to(control_a, control_b, anchor);
+            }
+            // NO_OP, and any unknown command
+            _ => {}
+        }
+    }
+
+    Some(())
+}
+
 /// Implements `Graphics.drawPath`
 pub fn draw_path<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "drawPath");
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let commands = args.get_object(activation, 0, "commands")?;
+        let commands = vector_to_i32s(activation, commands)?;
+        let data = args.get_object(activation, 1, "data")?;
+        let data = vector_to_f64s(activation, data)?;
+        let winding = args.get_string(activation, 2)?;
+        let winding_rule = parse_winding(activation, winding)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_winding_rule(winding_rule);
+            draw_path_internal(&mut draw, &commands, &data);
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Draws a quarter of an ellipse, as two of the curves in `UNIT_CIRCLE_POINTS`.
+///
+/// `radius_x` and `radius_y` are negative to mirror the quarter into the other
+/// quadrants. When `reversed` is set, the quarter is drawn from 90 degrees to 0 degrees.
+fn draw_quarter_ellipse(
+    draw: &mut Drawing,
+    (center_x, center_y): (f64, f64),
+    radius_x: f64,
+    radius_y: f64,
+    reversed: bool,
+) {
+    if radius_x == 0.0 && radius_y == 0.0 {
+        return;
+    }
+
+    let ucp = UNIT_CIRCLE_POINTS;
+    let points = if reversed {
+        [ucp[3], ucp[2], ucp[1], ucp[0]]
+    } else {
+        [ucp[1], ucp[2], ucp[3], ucp[4]]
+    };
+    for curve in points.chunks_exact(2) {
+        draw.draw_command(DrawCommand::CurveTo {
+            x1: Twips::from_pixels(center_x + radius_x * curve[0].0),
+            y1: Twips::from_pixels(center_y + radius_y * curve[0].1),
+            x2: Twips::from_pixels(center_x + radius_x * curve[1].0),
+            y2: Twips::from_pixels(center_y + radius_y * curve[1].1),
+        });
+    }
+}
+
 /// Implements `Graphics.drawRoundRectComplex`
 pub fn draw_round_rect_complex<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "drawRoundRectComplex");
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let x = args.get_f64(activation, 0)?;
+        let y = args.get_f64(activation, 1)?;
+        let width = args.get_f64(activation, 2)?;
+        let height = args.get_f64(activation, 3)?;
+
+        // Corners can't be larger than half of the rectangle.
+        let max_radius = (width.min(height) / 2.0).max(0.0);
+        let top_left = args.get_f64(activation, 4)?.clamp(0.0, max_radius);
+        let top_right = args.get_f64(activation, 5)?.clamp(0.0, max_radius);
+        let bottom_left = args.get_f64(activation, 6)?.clamp(0.0, max_radius);
+        let bottom_right = args.get_f64(activation, 7)?.clamp(0.0, max_radius);
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            let (right, bottom) = (x + width, y + height);
+
+            // Like `drawRoundRect`, start from the bottom-right corner.
+            draw.draw_command(DrawCommand::MoveTo {
+                x: Twips::from_pixels(right),
+                y: Twips::from_pixels(bottom - bottom_right),
+            });
+            draw_quarter_ellipse(
+                &mut draw,
+                (right - bottom_right, bottom - bottom_right),
+                bottom_right,
+                bottom_right,
+                false,
+            );
+            draw.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(x + bottom_left),
+                y: Twips::from_pixels(bottom),
+            });
+            draw_quarter_ellipse(
+                &mut draw,
+                (x + bottom_left, bottom - bottom_left),
+                -bottom_left,
+                bottom_left,
+                true,
+            );
+            draw.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(x),
+                y: Twips::from_pixels(y + top_left),
+            });
+            draw_quarter_ellipse(
+                &mut draw,
+                (x + top_left, y + top_left),
+                -top_left,
+                -top_left,
+                false,
+            );
+            draw.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(right - top_right),
+                y: Twips::from_pixels(y),
+            });
+            draw_quarter_ellipse(
+                &mut draw,
+                (right - top_right, y + top_right),
+                top_right,
+                -top_right,
+                true,
+            );
+            draw.draw_command(DrawCommand::LineTo {
+                x: Twips::from_pixels(right),
+                y: Twips::from_pixels(bottom - bottom_right),
+            });
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Which triangles `Graphics.drawTriangles` skips, based on the direction they face.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TriangleCulling {
+    None,
+    Positive,
+    Negative,
+}
+
+fn parse_culling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    culling: AvmString<'gc>,
+) -> Result<TriangleCulling, Error<'gc>> {
+    if &culling == b"none" {
+        Ok(TriangleCulling::None)
+    } else if &culling == b"positive" {
+        Ok(TriangleCulling::Positive)
+    } else if &culling == b"negative" {
+        Ok(TriangleCulling::Negative)
+    } else {
+        Err(Error::AvmError(argument_error(
+            activation,
+            "Parameter culling must be one of the accepted values.",
+            2008,
+        )?))
+    }
+}
+
+/// Builds the triangles described by the arguments of `Graphics.drawTriangles`.
+///
+/// Triangles that refer to missing vertices are skipped.
+fn build_triangles(
+    vertices: &[f64],
+    indices: Option<&[i32]>,
+    uvt_data: Option<&[f64]>,
+    culling: TriangleCulling,
+) -> Vec<Triangle> {
+    let points: Vec<_> = vertices.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+
+    // There are either two (u, v) or three (u, v, t) coordinates for each vertex.
+    let uvt: Option<Vec<_>> = uvt_data.and_then(|data| {
+        if data.len() == points.len() * 3 {
+            Some(data.chunks_exact(3).map(|c| (c[0], c[1], c[2])).collect())
+        } else if data.len() >= points.len() * 2 {
+            Some(data.chunks_exact(2).map(|c| (c[0], c[1], 1.0)).collect())
+        } else {
+            None
+        }
+    });
+
+    let corners: Vec<[usize; 3]> = match indices {
+        Some(indices) => indices
+            .chunks_exact(3)
+            .filter_map(|c| {
+                Some([
+                    usize::try_from(c[0]).ok()?,
+                    usize::try_from(c[1]).ok()?,
+                    usize::try_from(c[2]).ok()?,
+                ])
+            })
+            .collect(),
+        None => (0..points.len() / 3)
+            .map(|i| [i * 3, i * 3 + 1, i * 3 + 2])
+            .collect(),
+    };
+
+    corners
+        .into_iter()
+        .filter_map(|[a, b, c]| {
+            let triangle_points = [*points.get(a)?, *points.get(b)?, *points.get(c)?];
+            let [(x0, y0), (x1, y1), (x2, y2)] = triangle_points;
+            // The Z component of the triangle's normal.
+            let normal = (x1 - x0) * (y2 - y0) - (y1 - y0) * (x2 - x0);
+            let is_culled = match culling {
+                TriangleCulling::None => false,
+                TriangleCulling::Positive => normal > 0.0,
+                TriangleCulling::Negative => normal < 0.0,
+            };
+            if is_culled {
+                return None;
+            }
+
+            let uvt = match &uvt {
+                Some(uvt) => Some([*uvt.get(a)?, *uvt.get(b)?, *uvt.get(c)?]),
+                None => None,
+            };
+            Some(Triangle {
+                points: triangle_points,
+                uvt,
+            })
+        })
+        .collect()
+}
+
 /// Implements `Graphics.drawTriangles`
 pub fn draw_triangles<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "drawTriangles");
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let vertices = args.get_object(activation, 0, "vertices")?;
+        let vertices = vector_to_f64s(activation, vertices)?;
+        let indices = match args.try_get_object(activation, 1) {
+            Some(indices) => Some(vector_to_i32s(activation, indices)?),
+            None => None,
+        };
+        let uvt_data = match args.try_get_object(activation, 2) {
+            Some(uvt_data) => Some(vector_to_f64s(activation, uvt_data)?),
+            None => None,
+        };
+        let culling = args.get_string(activation, 3)?;
+        let culling = parse_culling(activation, culling)?;
+
+        let triangles =
+            build_triangles(&vertices, indices.as_deref(), uvt_data.as_deref(), culling);
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.draw_triangles(&triangles);
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Reads the given public properties of an object, in order.
+fn get_properties<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    names: &[&'static str],
+) -> Result<Vec<Value<'gc>>, Error<'gc>> {
+    names
+        .iter()
+        .map(|name| object.get_public_property(*name, activation))
+        .collect()
+}
+
+const GRADIENT_FILL_PROPERTIES: [&str; 8] = [
+    "type",
+    "colors",
+    "alphas",
+    "ratios",
+    "matrix",
+    "spreadMethod",
+    "interpolationMethod",
+    "focalPointRatio",
+];
+
+const BITMAP_FILL_PROPERTIES: [&str; 4] = ["bitmapData", "matrix", "repeat", "smooth"];
+
+/// Draws a single `IGraphicsData` object with the matching `Graphics` method.
+fn draw_graphics_data_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    data: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let classes = activation.avm2().classes();
+    let (solid_fill, gradient_fill, bitmap_fill, shader_fill) = (
+        classes.graphicssolidfill,
+        classes.graphicsgradientfill,
+        classes.graphicsbitmapfill,
+        classes.graphicsshaderfill,
+    );
+    let (end_fill_class, stroke, path, triangle_path) = (
+        classes.graphicsendfill,
+        classes.graphicsstroke,
+        classes.graphicspath,
+        classes.graphicstrianglepath,
+    );
+
+    if data.is_of_type(solid_fill, activation) {
+        let args = get_properties(activation, data, &["color", "alpha"])?;
+        begin_fill(activation, this, &args)?;
+    } else if data.is_of_type(gradient_fill, activation) {
+        let args = get_properties(activation, data, &GRADIENT_FILL_PROPERTIES)?;
+        begin_gradient_fill(activation, this, &args)?;
+    } else if data.is_of_type(bitmap_fill, activation) {
+        let args = get_properties(activation, data, &BITMAP_FILL_PROPERTIES)?;
+        begin_bitmap_fill(activation, this, &args)?;
+    } else if data.is_of_type(shader_fill, activation) {
+        let args = get_properties(activation, data, &["shader", "matrix"])?;
+        if !matches!(args[0], Value::Null | Value::Undefined) {
+            begin_shader_fill(activation, this, &args)?;
+        }
+    } else if data.is_of_type(end_fill_class, activation) {
+        end_fill(activation, this, &[])?;
+    } else if data.is_of_type(stroke, activation) {
+        let fill = data.get_public_property("fill", activation)?.as_object();
+        let (color, alpha) = match fill {
+            Some(fill) if fill.is_of_type(solid_fill, activation) => (
+                fill.get_public_property("color", activation)?,
+                fill.get_public_property("alpha", activation)?,
+            ),
+            _ => (0.into(), 1.0.into()),
+        };
+        let mut args = get_properties(
+            activation,
+            data,
+            &[
+                "thickness",
+                "pixelHinting",
+                "scaleMode",
+                "caps",
+                "joints",
+                "miterLimit",
+            ],
+        )?;
+        args.splice(1..1, [color, alpha]);
+        line_style(activation, this, &args)?;
+
+        if let Some(fill) = fill {
+            if fill.is_of_type(gradient_fill, activation) {
+                let args = get_properties(activation, fill, &GRADIENT_FILL_PROPERTIES)?;
+                line_gradient_style(activation, this, &args)?;
+            } else if fill.is_of_type(bitmap_fill, activation) {
+                let args = get_properties(activation, fill, &BITMAP_FILL_PROPERTIES)?;
+                line_bitmap_style(activation, this, &args)?;
+            } else if fill.is_of_type(shader_fill, activation) {
+                let args = get_properties(activation, fill, &["shader", "matrix"])?;
+                if !matches!(args[0], Value::Null | Value::Undefined) {
+                    line_shader_style(activation, this, &args)?;
+                }
+            }
+        }
+    } else if data.is_of_type(path, activation) {
+        let args = get_properties(activation, data, &["commands", "data", "winding"])?;
+        if !matches!(args[0], Value::Null | Value::Undefined)
+            && !matches!(args[1], Value::Null | Value::Undefined)
+        {
+            draw_path(activation, this, &args)?;
+        }
+    } else if data.is_of_type(triangle_path, activation) {
+        let args = get_properties(
+            activation,
+            data,
+            &["vertices", "indices", "uvtData", "culling"],
+        )?;
+        if !matches!(args[0], Value::Null | Value::Undefined) {
+            draw_triangles(activation, this, &args)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Implements `Graphics.drawGraphicsData`
 pub fn draw_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "drawGraphicsData");
+    let graphics_data = args.get_object(activation, 0, "graphicsData")?;
+    let graphics_data: Vec<_> = graphics_data
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect())
+        .unwrap_or_default();
+
+    for data in graphics_data.iter().filter_map(|data| data.as_object()) {
+        draw_graphics_data_item(activation, this, data)?;
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -940,14 +1519,285 @@ pub fn line_bitmap_style<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Graphics.lineShaderStyle`
+pub fn line_shader_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let shader = args.get_object(activation, 0, "shader")?;
+        let matrix = if let Some(matrix) = args.try_get_object(activation, 1) {
+            Matrix::from(object_to_matrix(matrix, activation)?)
+        } else {
+            // Users can explicitly pass in `null` to mean identity matrix
+            Matrix::IDENTITY
+        };
+
+        if let Some(fill_style) = shader_fill_style(activation, this, shader, matrix)? {
+            if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+                draw.set_line_fill_style(fill_style);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Creates the `IGraphicsFill` that describes a fill style, transformed by `matrix`.
+///
+/// Returns `None` for fills that can't be described.
+fn fill_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &FillStyle,
+    matrix: Matrix,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    let (gradient_type, gradient, focal_point) = match style {
+        FillStyle::Color(color) => {
+            let class = activation.avm2().classes().graphicssolidfill;
+            let args = [color.to_rgb().into(), (f64::from(color.a) / 255.0).into()];
+            return Ok(Some(class.construct(activation, &args)?));
+        }
+        FillStyle::LinearGradient(gradient) => ("linear", gradient, 0.0),
+        FillStyle::RadialGradient(gradient) => ("radial", gradient, 0.0),
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => ("radial", gradient, focal_point.to_f64()),
+        FillStyle::Bitmap { .. } => {
+            avm2_stub_method!(
+                activation,
+                "flash.display.Graphics",
+                "readGraphicsData",
+                "with bitmap fills"
+            );
+            return Ok(None);
+        }
+    };
+
+    let values = |f: fn(&GradientRecord) -> Value<'gc>| {
+        ArrayStorage::from_args(&gradient.records.iter().map(f).collect::<Vec<_>>())
+    };
+    let colors = values(|record| record.color.to_rgb().into());
+    let alphas = values(|record| (f64::from(record.color.a) / 255.0).into());
+    let ratios = values(|record| record.ratio.into());
+    let colors = ArrayObject::from_storage(activation, colors)?;
+    let alphas = ArrayObject::from_storage(activation, alphas)?;
+    let ratios = ArrayObject::from_storage(activation, ratios)?;
+    let gradient_matrix = matrix_to_object((matrix * gradient.matrix).into(), activation)?;
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+
+    let class = activation.avm2().classes().graphicsgradientfill;
+    let args = [
+        gradient_type.into(),
+        colors.into(),
+        alphas.into(),
+        ratios.into(),
+        gradient_matrix,
+        spread.into(),
+        interpolation.into(),
+        focal_point.into(),
+    ];
+    Ok(Some(class.construct(activation, &args)?))
+}
+
+/// Creates the `GraphicsStroke` that describes a line style, transformed by `matrix`.
+fn line_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &LineStyle,
+    matrix: Matrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let scale_mode = match (style.allow_scale_x(), style.allow_scale_y()) {
+        (true, true) => "normal",
+        (true, false) => "horizontal",
+        (false, true) => "vertical",
+        (false, false) => "none",
+    };
+    let caps = match style.start_cap() {
+        LineCapStyle::None => "none",
+        LineCapStyle::Round => "round",
+        LineCapStyle::Square => "square",
+    };
+    let (joints, miter_limit) = match style.join_style() {
+        LineJoinStyle::Round => ("round", 3.0),
+        LineJoinStyle::Bevel => ("bevel", 3.0),
+        LineJoinStyle::Miter(miter_limit) => ("miter", miter_limit.to_f64()),
+    };
+    let fill = match fill_style_to_object(activation, style.fill_style(), matrix)? {
+        Some(fill) => fill.into(),
+        None => Value::Null,
+    };
+
+    let class = activation.avm2().classes().graphicsstroke;
+    let args = [
+        style.width().to_pixels().into(),
+        style.is_pixel_hinted().into(),
+        scale_mode.into(),
+        caps.into(),
+        joints.into(),
+        miter_limit.into(),
+        fill,
+    ];
+    class.construct(activation, &args)
+}
+
+/// Creates the `GraphicsPath` that describes a path, transformed by `matrix`.
+fn draw_commands_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    commands: &[DrawCommand],
+    winding_rule: FillRule,
+    matrix: Matrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let mut path_commands: Vec<Value<'gc>> = Vec::with_capacity(commands.len());
+    let mut data: Vec<Value<'gc>> = Vec::with_capacity(commands.len() * 2);
+    let mut push_point = |x: Twips, y: Twips| {
+        let (x, y) = matrix * (x, y);
+        data.push(x.to_pixels().into());
+        data.push(y.to_pixels().into());
+    };
+    for (i, command) in commands.iter().enumerate() {
+        match *command {
+            DrawCommand::MoveTo { x, y } => {
+                // Only the last of several moves in a row has an effect.
+                if matches!(commands.get(i + 1), Some(DrawCommand::MoveTo { .. }) | None) {
+                    continue;
+                }
+                path_commands.push(1.into());
+                push_point(x, y);
+            }
+            DrawCommand::LineTo { x, y } => {
+                path_commands.push(2.into());
+                push_point(x, y);
+            }
+            DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                path_commands.push(3.into());
+                push_point(x1, y1);
+                push_point(x2, y2);
+            }
+        }
+    }
+
+    let int = activation.avm2().classes().int;
+    let path_commands = VectorStorage::from_values(path_commands, false, int);
+    let path_commands = VectorObject::from_vector(path_commands, activation)?;
+    let number = activation.avm2().classes().number;
+    let data = VectorStorage::from_values(data, false, number);
+    let data = VectorObject::from_vector(data, activation)?;
+    let winding = match winding_rule {
+        FillRule::EvenOdd => "evenOdd",
+        FillRule::NonZero => "nonZero",
+    };
+
+    let class = activation.avm2().classes().graphicspath;
+    class.construct(
+        activation,
+        &[path_commands.into(), data.into(), winding.into()],
+    )
+}
+
+/// Describes the drawing of `display_object` as `IGraphicsData`, transformed by `matrix`.
+fn read_graphics_data_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    display_object: DisplayObject<'gc>,
+    matrix: Matrix,
+    recurse: bool,
+    graphics_data: &mut Vec<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    // Copy the drawing, as creating objects may run code that changes it.
+    let drawing = display_object
+        .as_drawing(activation.context.gc_context)
+        .map(|drawing| (*drawing).clone());
+
+    if let Some(drawing) = drawing {
+        for path in drawing.draw_paths() {
+            match path {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding_rule,
+                } => {
+                    if commands
+                        .iter()
+                        .all(|command| matches!(command, DrawCommand::MoveTo { .. }))
+                    {
+                        continue;
+                    }
+                    if let Some(fill) = fill_style_to_object(activation, style, matrix)? {
+                        let path =
+                            draw_commands_to_object(activation, &commands, winding_rule, matrix)?;
+                        let end_fill = activation.avm2().classes().graphicsendfill;
+                        graphics_data.push(fill.into());
+                        graphics_data.push(path.into());
+                        graphics_data.push(end_fill.construct(activation, &[])?.into());
+                    }
+                }
+                DrawPath::Stroke {
+                    style, commands, ..
+                } => {
+                    if commands
+                        .iter()
+                        .all(|command| matches!(command, DrawCommand::MoveTo { .. }))
+                    {
+                        continue;
+                    }
+                    let stroke = line_style_to_object(activation, style, matrix)?;
+                    let path =
+                        draw_commands_to_object(activation, &commands, FillRule::EvenOdd, matrix)?;
+                    // A stroke without a thickness ends the line.
+                    let end_stroke = activation.avm2().classes().graphicsstroke;
+                    graphics_data.push(stroke.into());
+                    graphics_data.push(path.into());
+                    graphics_data.push(end_stroke.construct(activation, &[])?.into());
+                }
+            }
+        }
+    }
+
+    if recurse {
+        if let Some(container) = display_object.as_container() {
+            for child in container.iter_render_list() {
+                let child_matrix = matrix * Matrix::from(*child.base().matrix());
+                read_graphics_data_internal(
+                    activation,
+                    child,
+                    child_matrix,
+                    recurse,
+                    graphics_data,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Implements `Graphics.readGraphicsData`
 pub fn read_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "readGraphicsData");
+    let mut graphics_data = Vec::new();
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let recurse = args.get_bool(0);
+        read_graphics_data_internal(
+            activation,
+            this,
+            Matrix::IDENTITY,
+            recurse,
+            &mut graphics_data,
+        )?;
+    }
+
     let value_type = activation.avm2().classes().igraphicsdata;
-    let new_storage = VectorStorage::new(0, false, value_type, activation);
+    let new_storage = VectorStorage::from_values(graphics_data, false, value_type);
     Ok(VectorObject::from_vector(new_storage, activation)?.into())
 }
@@ -1,7 +1,10 @@
 //! `flash.display.Loader` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::security_error;
+use crate::avm2::globals::flash::display::loader_info::child_allows_parent;
 use crate::avm2::object::LoaderInfoObject;
+use crate::avm2::object::LoaderStream;
 use crate::avm2::object::TObject;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
@@ -13,6 +16,7 @@ use crate::display_object::MovieClip;
 use crate::loader::{Avm2LoaderData, MovieLoaderEventHandler};
 use crate::tag_utils::SwfMovie;
 use std::sync::Arc;
+use url::Url;
 
 pub fn init<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -49,6 +53,48 @@ pub fn init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements the check in `Loader.content` that the movie that loaded the
+/// content may access it.
+pub fn check_content_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this else {
+        return Ok(Value::Undefined);
+    };
+    let loader_info = this.get_property(
+        &Multiname::new(
+            activation.avm2().flash_display_internal,
+            "_contentLoaderInfo",
+        ),
+        activation,
+    )?;
+
+    let denied = match loader_info
+        .as_object()
+        .as_ref()
+        .and_then(|o| o.as_loader_info_object())
+        .and_then(|o| o.as_loader_stream())
+        .as_deref()
+    {
+        Some(LoaderStream::Swf(movie, _)) if !child_allows_parent(activation, movie) => {
+            let loader_url = movie.loader_url().unwrap_or_else(|| movie.url());
+            Some(format!(
+                "Error #2121: Security sandbox violation: Loader.content: {} cannot access {}. This may be worked around by calling Security.allowDomain.",
+                loader_url,
+                movie.url()
+            ))
+        }
+        _ => None,
+    };
+
+    if let Some(message) = denied {
+        return Err(Error::AvmError(security_error(activation, &message, 2121)?));
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn load<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
@@ -61,6 +107,25 @@ pub fn load<'gc>(
         let url = url_request
             .get_public_property("url", activation)?
             .coerce_to_string(activation)?;
+        let loader_url = activation.caller_movie().url().to_string();
+
+        let mut policy_file = None;
+        if let Some(context) = context {
+            let check_policy_file = context
+                .get_public_property("checkPolicyFile", activation)?
+                .coerce_to_boolean();
+            if check_policy_file {
+                let content_url = Url::parse(&loader_url)
+                    .and_then(|base| base.join(&url.to_utf8_lossy()))
+                    .map(|url| url.to_string())
+                    .unwrap_or_else(|_| url.to_string());
+                policy_file = activation.context.security.check_policy_file(
+                    activation.context.player.clone(),
+                    activation.context.navigator,
+                    &content_url,
+                );
+            }
+        }
 
         // This is a dummy MovieClip, which will get overwritten in `Loader`
         let content = MovieClip::new(
@@ -79,18 +144,25 @@ pub fn load<'gc>(
             .as_object()
             .unwrap();
 
-        let future = activation.context.load_manager.load_movie_into_clip(
+        let mut future = activation.context.load_manager.load_movie_into_clip(
             activation.context.player.clone(),
             content.into(),
             // FIXME - set options from the `URLRequest`
             Request::get(url.to_string()),
-            Some(url.to_string()),
+            Some(loader_url),
             Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)),
             Some(Avm2LoaderData {
                 context,
                 default_domain: activation.caller_domain(),
             }),
         );
+        // The content is only loaded once its policy file is in place.
+        if let Some(policy_file) = policy_file {
+            future = Box::pin(async move {
+                policy_file.await?;
+                future.await
+            });
+        }
         activation.context.navigator.spawn_future(future);
     }
     Ok(Value::Undefined)
@@ -2,12 +2,14 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::Endian;
-use crate::avm2::error::error;
+use crate::avm2::error::{error, security_error};
 use crate::avm2::object::{DomainObject, LoaderStream, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::{AvmString, Error};
 use crate::avm2_stub_getter;
 use crate::display_object::TDisplayObject;
+use crate::security::SecurityDomain;
+use crate::tag_utils::SwfMovie;
 use swf::{write_swf, Compression};
 
 pub use crate::avm2::object::loader_info_allocator;
@@ -136,7 +138,7 @@ pub fn get_bytes_loaded<'gc>(
 
 /// `content` getter
 pub fn get_content<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -146,7 +148,18 @@ pub fn get_content<'gc>(
             .and_then(|o| o.as_loader_stream())
         {
             match &*loader_stream {
-                LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => {
+                LoaderStream::Swf(movie, root) => {
+                    if !child_allows_parent(activation, movie) {
+                        let message = format!(
+                            "Error #2123: Security sandbox violation: LoaderInfo.content: {} cannot access {}. No policy files granted access.",
+                            movie.loader_url().unwrap_or_else(|| movie.url()),
+                            movie.url()
+                        );
+                        return Err(Error::AvmError(security_error(activation, &message, 2123)?));
+                    }
+                    return Ok(root.object2());
+                }
+                LoaderStream::NotYetLoaded(_, Some(root), _) => {
                     return Ok(root.object2());
                 }
                 _ => {
@@ -257,9 +270,11 @@ pub fn get_same_domain<'gc>(
                 LoaderStream::NotYetLoaded(_, _, _) => {
                     return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
                 }
-                LoaderStream::Swf(_root, _) => {
-                    avm2_stub_getter!(activation, "flash.display.LoaderInfo", "sameDomain");
-                    return Ok(false.into());
+                LoaderStream::Swf(root, _) => {
+                    let loader_url = root.loader_url().unwrap_or_else(|| root.url());
+                    let same_domain = SecurityDomain::from_url(loader_url)
+                        == SecurityDomain::from_url(root.url());
+                    return Ok(same_domain.into());
                 }
             }
         }
@@ -283,9 +298,8 @@ pub fn get_child_allows_parent<'gc>(
                 LoaderStream::NotYetLoaded(_, _, _) => {
                     return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
                 }
-                LoaderStream::Swf(_root, _) => {
-                    avm2_stub_getter!(activation, "flash.display.LoaderInfo", "childAllowsParent");
-                    return Ok(false.into());
+                LoaderStream::Swf(root, _) => {
+                    return Ok(child_allows_parent(activation, root).into());
                 }
             }
         }
@@ -294,6 +308,18 @@ pub fn get_child_allows_parent<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Whether the movie that loaded some content may access it, either because
+/// the content called `Security.allowDomain`, or because a policy file on its
+/// server allows it.
+pub fn child_allows_parent<'gc>(activation: &mut Activation<'_, 'gc>, movie: &SwfMovie) -> bool {
+    let loader_url = movie.loader_url().unwrap_or_else(|| movie.url());
+    activation.context.security.can_read(
+        &activation.context.system.sandbox_type,
+        loader_url,
+        movie.url(),
+    )
+}
+
 /// `parentAllowsChild` getter
 pub fn get_parent_allows_child<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -309,9 +335,14 @@ pub fn get_parent_allows_child<'gc>(
                 LoaderStream::NotYetLoaded(_, _, _) => {
                     return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
                 }
-                LoaderStream::Swf(_root, _) => {
-                    avm2_stub_getter!(activation, "flash.display.LoaderInfo", "parentAllowsChild");
-                    return Ok(false.into());
+                LoaderStream::Swf(root, _) => {
+                    let loader_url = root.loader_url().unwrap_or_else(|| root.url());
+                    let allowed = activation.context.security.can_script(
+                        &activation.context.system.sandbox_type,
+                        root.url(),
+                        loader_url,
+                    );
+                    return Ok(allowed.into());
                 }
             }
         }
@@ -0,0 +1,357 @@
+//! `flash.display.ShaderData` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::{ByteArrayStorage, Endian};
+use crate::avm2::error::argument_error;
+use crate::avm2::object::{ArrayObject, Object, ShaderDataObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayStorage, Error, Multiname};
+use crate::string::AvmString;
+use ruffle_render::pixel_bender::{
+    parse_shader, PixelBenderImage, PixelBenderParam, PixelBenderParamQualifier, PixelBenderShader,
+    PixelBenderShaderArgument, PixelBenderType, PixelBenderValue,
+};
+use std::sync::Arc;
+
+pub use crate::avm2::object::shader_data_allocator;
+
+/// Implements `ShaderData`'s constructor.
+///
+/// Every parameter of the shader becomes a `ShaderParameter` property, and every
+/// texture a `ShaderInput` property, named after the parameter. The metadata of the
+/// shader itself becomes properties as well.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(mut this) = this else {
+        return Ok(Value::Undefined);
+    };
+    let bytecode = args.get_object(activation, 0, "byteCode")?;
+    let bytecode = bytecode
+        .as_bytearray()
+        .map(|bytes| bytes.bytes().to_vec())
+        .unwrap_or_default();
+    let shader = match parse_shader(&bytecode) {
+        Ok(shader) => shader,
+        Err(e) => {
+            tracing::warn!("Invalid Pixel Bender bytecode: {e}");
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2004: One of the parameters is invalid.",
+                2004,
+            )?));
+        }
+    };
+
+    let shader = Arc::new(shader);
+    populate(activation, this, &shader)?;
+    if let Some(shader_data) = this.as_shader_data() {
+        shader_data.set_shader(activation.context.gc_context, shader);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Adds the properties of a `ShaderData` object for the metadata and parameters of `shader`.
+fn populate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut this: Object<'gc>,
+    shader: &PixelBenderShader,
+) -> Result<(), Error<'gc>> {
+    let name = AvmString::new_utf8(activation.context.gc_context, &shader.name);
+    this.set_public_property("name", name.into(), activation)?;
+    for metadata in &shader.metadata {
+        let value = value_to_avm2(activation, metadata.value_type, &metadata.value)?;
+        let key = AvmString::new_utf8(activation.context.gc_context, &metadata.key);
+        this.set_public_property(key, value, activation)?;
+    }
+
+    let internal = activation.avm2().flash_display_internal;
+    let mut param_index: i32 = 0;
+    for param in &shader.params {
+        let (object, name) = match param {
+            PixelBenderParam::Normal {
+                qualifier,
+                param_type,
+                name,
+                metadata,
+                ..
+            } => {
+                // Outputs and hidden parameters such as `_OutCoord` can't be set from
+                // ActionScript, so they aren't listed.
+                if *qualifier == PixelBenderParamQualifier::Output || name.starts_with('_') {
+                    continue;
+                }
+                let mut object = activation
+                    .avm2()
+                    .classes()
+                    .shaderparameter
+                    .construct(activation, &[])?;
+                object.set_property(
+                    &Multiname::new(internal, "_index"),
+                    param_index.into(),
+                    activation,
+                )?;
+                let type_name = param_type.name().into();
+                object.set_property(&Multiname::new(internal, "_type"), type_name, activation)?;
+                for metadata in metadata {
+                    let value = value_to_avm2(activation, metadata.value_type, &metadata.value)?;
+                    if metadata.key == "defaultValue" {
+                        object.set_public_property("value", value, activation)?;
+                    }
+                    let key = AvmString::new_utf8(activation.context.gc_context, &metadata.key);
+                    object.set_public_property(key, value, activation)?;
+                }
+                param_index += 1;
+                (object, name)
+            }
+            PixelBenderParam::Texture {
+                index,
+                channels,
+                name,
+            } => {
+                let mut object = activation
+                    .avm2()
+                    .classes()
+                    .shaderinput
+                    .construct(activation, &[])?;
+                object.set_property(
+                    &Multiname::new(internal, "_index"),
+                    (*index).into(),
+                    activation,
+                )?;
+                object.set_property(
+                    &Multiname::new(internal, "_channels"),
+                    (*channels).into(),
+                    activation,
+                )?;
+                (object, name)
+            }
+        };
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+        this.set_public_property(name, object.into(), activation)?;
+    }
+
+    Ok(())
+}
+
+/// Creates a `flash.display.Shader` object for a parsed shader, with its parameters set
+/// to the given arguments. This is how filters that were applied are handed back
+/// to ActionScript.
+///
+/// Input images are only held as pixels, so the inputs of the new shader are left empty.
+pub fn shader_to_avm2<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    shader: &Arc<PixelBenderShader>,
+    arguments: &[PixelBenderShaderArgument],
+) -> Result<Object<'gc>, Error<'gc>> {
+    let data = ShaderDataObject::from_shader(activation, shader.clone())?;
+    populate(activation, data, shader)?;
+
+    for argument in arguments {
+        let PixelBenderShaderArgument::ValueInput { index, value } = argument else {
+            continue;
+        };
+        let Some(PixelBenderParam::Normal {
+            param_type, name, ..
+        }) = shader.params.get(*index)
+        else {
+            continue;
+        };
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+        if let Some(mut param) = data.get_public_property(name, activation)?.as_object() {
+            let value = value_to_avm2(activation, *param_type, value)?;
+            param.set_public_property("value", value, activation)?;
+        }
+    }
+
+    let mut object = activation
+        .avm2()
+        .classes()
+        .shader
+        .construct(activation, &[])?;
+    object.set_public_property("data", data.into(), activation)?;
+    Ok(object)
+}
+
+/// Converts a parameter or metadata value to a string, or to an array of its numbers
+/// or booleans.
+fn value_to_avm2<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value_type: PixelBenderType,
+    value: &PixelBenderValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let is_bool = matches!(
+        value_type,
+        PixelBenderType::Bool
+            | PixelBenderType::Bool2
+            | PixelBenderType::Bool3
+            | PixelBenderType::Bool4
+    );
+    let values: Vec<Value<'gc>> = match value {
+        PixelBenderValue::String(string) => {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, string).into())
+        }
+        PixelBenderValue::Float(floats) => floats.iter().map(|f| f64::from(*f).into()).collect(),
+        PixelBenderValue::Int(ints) if is_bool => ints.iter().map(|i| (*i != 0).into()).collect(),
+        PixelBenderValue::Int(ints) => ints.iter().map(|i| (*i).into()).collect(),
+    };
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&values))?.into())
+}
+
+/// Collects the parsed shader of a `ShaderData` object, along with the current values
+/// of its parameters and the images of its inputs.
+///
+/// Returns `None` if the object doesn't hold a shader.
+pub fn shader_arguments<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    shader_data: Object<'gc>,
+) -> Result<Option<(Arc<PixelBenderShader>, Vec<PixelBenderShaderArgument>)>, Error<'gc>> {
+    let Some(shader) = shader_data.as_shader_data().and_then(|data| data.shader()) else {
+        return Ok(None);
+    };
+
+    let mut arguments = vec![];
+    for (index, param) in shader.params.iter().enumerate() {
+        match param {
+            PixelBenderParam::Normal {
+                qualifier: PixelBenderParamQualifier::Input,
+                param_type,
+                name,
+                ..
+            } if !name.starts_with('_') => {
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+                let Some(param) = shader_data
+                    .get_public_property(name, activation)?
+                    .as_object()
+                else {
+                    continue;
+                };
+                let Some(values) = param.get_public_property("value", activation)?.as_object()
+                else {
+                    continue;
+                };
+                let values: Vec<Value<'gc>> = match values.as_array_storage() {
+                    Some(storage) => storage
+                        .iter()
+                        .map(|v| v.unwrap_or(Value::Undefined))
+                        .collect(),
+                    None => continue,
+                };
+                let value = if param_type.is_float() {
+                    let mut floats = Vec::with_capacity(values.len());
+                    for value in values {
+                        floats.push(value.coerce_to_number(activation)? as f32);
+                    }
+                    PixelBenderValue::Float(floats)
+                } else {
+                    let mut ints = Vec::with_capacity(values.len());
+                    for value in values {
+                        ints.push(match value {
+                            Value::Bool(value) => value as i32,
+                            value => value.coerce_to_i32(activation)?,
+                        });
+                    }
+                    PixelBenderValue::Int(ints)
+                };
+                arguments.push(PixelBenderShaderArgument::ValueInput { index, value });
+            }
+            PixelBenderParam::Texture { channels, name, .. } => {
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+                let image = match shader_data
+                    .get_public_property(name, activation)?
+                    .as_object()
+                {
+                    Some(input) => input_image(activation, input, *channels)?,
+                    None => None,
+                };
+                arguments.push(PixelBenderShaderArgument::ImageInput {
+                    index,
+                    image: image.map(Arc::new),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(Some((shader, arguments)))
+}
+
+/// Reads the image of a `ShaderInput`, which can be a `BitmapData`, or a `ByteArray`
+/// or `Vector.<Number>` holding `width * height * channels` floats.
+fn input_image<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    shader_input: Object<'gc>,
+    channels: u8,
+) -> Result<Option<PixelBenderImage>, Error<'gc>> {
+    let Some(input) = shader_input
+        .get_public_property("input", activation)?
+        .as_object()
+    else {
+        return Ok(None);
+    };
+
+    if let Some(bitmap) = input.as_bitmap_data_wrapper() {
+        let bitmap = bitmap.sync();
+        let bitmap = bitmap.read();
+        let pixels = bitmap.pixels().iter().map(|color| {
+            [color.red(), color.green(), color.blue(), color.alpha()]
+                .map(|channel| f32::from(channel) / 255.0)
+        });
+        return Ok(Some(PixelBenderImage::from_premultiplied(
+            bitmap.width(),
+            bitmap.height(),
+            channels,
+            pixels,
+        )));
+    }
+
+    let width = shader_input
+        .get_public_property("width", activation)?
+        .coerce_to_u32(activation)?;
+    let height = shader_input
+        .get_public_property("height", activation)?
+        .coerce_to_u32(activation)?;
+    let len = width as usize * height as usize * usize::from(channels);
+    let mut data = vec![0.0; len];
+    if let Some(bytes) = input.as_bytearray() {
+        for (i, value) in data.iter_mut().enumerate() {
+            if let Ok(float) = bytes.read_float_at(i * 4) {
+                *value = float;
+            }
+        }
+    } else if input.as_vector_storage().is_some() {
+        let values: Vec<_> = input
+            .as_vector_storage()
+            .map(|vector| vector.iter().take(len).collect())
+            .unwrap_or_default();
+        for (value, number) in data.iter_mut().zip(values) {
+            *value = number.coerce_to_number(activation)? as f32;
+        }
+    } else {
+        return Ok(None);
+    }
+    Ok(Some(PixelBenderImage {
+        width,
+        height,
+        channels,
+        data,
+    }))
+}
+
+/// Writes floats to a `ByteArray` from its start, in its byte order.
+pub fn write_floats(bytes: &mut ByteArrayStorage, floats: &[f32]) {
+    let endian = bytes.endian();
+    let data: Vec<u8> = floats
+        .iter()
+        .flat_map(|float| match endian {
+            Endian::Big => float.to_be_bytes(),
+            Endian::Little => float.to_le_bytes(),
+        })
+        .collect();
+    if bytes.len() < data.len() {
+        bytes.set_length(data.len());
+    }
+    bytes.bytes_mut()[..data.len()].copy_from_slice(&data);
+}
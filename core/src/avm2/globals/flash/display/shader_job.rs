@@ -0,0 +1,81 @@
+//! `flash.display.ShaderJob` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::argument_error;
+use crate::avm2::globals::flash::display::shader_data::{shader_arguments, write_floats};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::bitmap::bitmap_data::Color;
+use ruffle_render::pixel_bender::interpreter;
+
+/// Implements `ShaderJob.runShader`, which runs the shader of the job on the CPU
+/// and writes its output to the target.
+pub fn run_shader<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this else {
+        return Ok(Value::Undefined);
+    };
+    let shader = this.get_public_property("shader", activation)?.as_object();
+    let target = this.get_public_property("target", activation)?.as_object();
+    let (Some(shader), Some(target)) = (shader, target) else {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2007: Parameter shader and target must be non-null.",
+            2007,
+        )?));
+    };
+    let Some(data) = shader.get_public_property("data", activation)?.as_object() else {
+        return Ok(Value::Undefined);
+    };
+    let Some((shader, arguments)) = shader_arguments(activation, data)? else {
+        return Ok(Value::Undefined);
+    };
+
+    let mut width = this
+        .get_public_property("width", activation)?
+        .coerce_to_u32(activation)?;
+    let mut height = this
+        .get_public_property("height", activation)?
+        .coerce_to_u32(activation)?;
+    let bitmap = target.as_bitmap_data_wrapper();
+    if let Some(bitmap) = bitmap {
+        // Jobs without a size fill the whole bitmap.
+        if width == 0 || height == 0 {
+            width = bitmap.width();
+            height = bitmap.height();
+        }
+    }
+
+    let output = interpreter::run_shader(&shader, &arguments, width, height);
+
+    if let Some(bitmap) = bitmap {
+        let pixels: Vec<Color> = output
+            .to_premultiplied()
+            .into_iter()
+            .map(|pixel| {
+                let [r, g, b, a] = pixel.map(|channel| (channel * 255.0).round() as u8);
+                Color::argb(a, r, g, b)
+            })
+            .collect();
+        bitmap
+            .sync()
+            .write(activation.context.gc_context)
+            .draw_filter_result(&pixels, (width, height), (0, 0));
+    } else if let Some(mut bytes) = target.as_bytearray_mut(activation.context.gc_context) {
+        write_floats(&mut bytes, &output.data);
+    } else if let Some(mut vector) = target.as_vector_storage_mut(activation.context.gc_context) {
+        vector.replace_storage(output.data.iter().map(|v| f64::from(*v).into()).collect());
+    } else {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2005: Parameter 0 is of the incorrect type. Should be type BitmapData, ByteArray or Vector.<Number>.",
+            2005,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
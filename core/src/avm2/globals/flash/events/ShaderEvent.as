@@ -28,7 +28,7 @@ package flash.events
         //  Creates a copy of the ShaderEvent object and sets the value of each property to match that of the original.
         override public function clone():Event
         {
-            return new ShaderEvent(this.type, this.bubbles, this.cancelable, this.vector);
+            return new ShaderEvent(this.type, this.bubbles, this.cancelable, this.bitmapData, this.byteArray, this.vector);
         }
 
         //  Returns a string that contains all the properties of the ShaderEvent object.
@@ -0,0 +1,24 @@
+package flash.filters {
+	import flash.display.Shader;
+
+	public class ShaderFilter extends BitmapFilter {
+		public var bottomExtension: int = 0;
+		public var leftExtension: int = 0;
+		public var rightExtension: int = 0;
+		public var topExtension: int = 0;
+		public var shader: Shader;
+
+		public function ShaderFilter(shader:Shader = null) {
+			this.shader = shader;
+		}
+
+		override public function clone(): BitmapFilter {
+			var filter: ShaderFilter = new ShaderFilter(this.shader);
+			filter.bottomExtension = this.bottomExtension;
+			filter.leftExtension = this.leftExtension;
+			filter.rightExtension = this.rightExtension;
+			filter.topExtension = this.topExtension;
+			return filter;
+		}
+	}
+}
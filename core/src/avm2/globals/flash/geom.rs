@@ -1,3 +1,4 @@
 //! `flash.geom` namespace
 
+pub mod matrix_3d;
 pub mod transform;
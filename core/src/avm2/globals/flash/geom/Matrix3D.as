@@ -277,5 +277,53 @@ package flash.geom {
 		public function copyToMatrix3D(other:Matrix3D):void {
 			other.rawData = rawData
 		}
+
+		public function prependScale(xScale:Number, yScale:Number, zScale:Number):void {
+			this.prepend(new Matrix3D(Vector.<Number>([
+					xScale, 0.0, 0.0, 0.0, 0.0, yScale, 0.0, 0.0, 0.0, 0.0, zScale, 0.0, 0.0, 0.0, 0.0, 1.0
+				])));
+		}
+
+		public function prependRotation(degrees:Number, axis:Vector3D, pivotPoint:Vector3D = null):void {
+			var m:Matrix3D = new Matrix3D();
+			m.appendRotation(degrees, axis, pivotPoint);
+			this.prepend(m);
+		}
+
+		public function transformVector(v:Vector3D):Vector3D {
+			var x:Number = v.x, y:Number = v.y, z:Number = v.z;
+			return new Vector3D(
+				x * _rawData[0] + y * _rawData[4] + z * _rawData[8] + _rawData[12],
+				x * _rawData[1] + y * _rawData[5] + z * _rawData[9] + _rawData[13],
+				x * _rawData[2] + y * _rawData[6] + z * _rawData[10] + _rawData[14],
+				x * _rawData[3] + y * _rawData[7] + z * _rawData[11] + _rawData[15]
+			);
+		}
+
+		public function deltaTransformVector(v:Vector3D):Vector3D {
+			var x:Number = v.x, y:Number = v.y, z:Number = v.z;
+			return new Vector3D(
+				x * _rawData[0] + y * _rawData[4] + z * _rawData[8],
+				x * _rawData[1] + y * _rawData[5] + z * _rawData[9],
+				x * _rawData[2] + y * _rawData[6] + z * _rawData[10],
+				0
+			);
+		}
+
+		public function transformVectors(vin:Vector.<Number>, vout:Vector.<Number>):void {
+			for (var i:int = 0; i + 2 < vin.length; i += 3) {
+				var x:Number = vin[i], y:Number = vin[i + 1], z:Number = vin[i + 2];
+				vout[i] = x * _rawData[0] + y * _rawData[4] + z * _rawData[8] + _rawData[12];
+				vout[i + 1] = x * _rawData[1] + y * _rawData[5] + z * _rawData[9] + _rawData[13];
+				vout[i + 2] = x * _rawData[2] + y * _rawData[6] + z * _rawData[10] + _rawData[14];
+			}
+		}
+
+		public native function get determinant():Number;
+		public native function invert():Boolean;
+
+		// Only `Orientation3D.EULER_ANGLES` is supported.
+		public native function decompose(orientationStyle:String = "eulerAngles"):Vector.<Vector3D>;
+		public native function recompose(components:Vector.<Vector3D>, orientationStyle:String = "eulerAngles"):Boolean;
 	}
 }
\ No newline at end of file
@@ -0,0 +1,52 @@
+package flash.geom {
+	public class PerspectiveProjection {
+		// The width of the stage, which the field of view spans.
+		internal var _width:Number = 500;
+
+		private var _fieldOfView:Number = 55;
+		private var _projectionCenter:Point = new Point(250, 200);
+
+		public function PerspectiveProjection() {
+		}
+
+		public function get fieldOfView():Number {
+			return this._fieldOfView;
+		}
+
+		public function set fieldOfView(value:Number):void {
+			if (!(value > 0 && value < 180)) {
+				throw new ArgumentError("Error #2182: Invalid fieldOfView value.  The value must be greater than 0 and less than 180.", 2182);
+			}
+			this._fieldOfView = value;
+		}
+
+		public function get focalLength():Number {
+			return this._width / 2 / Math.tan(this._fieldOfView * Math.PI / 360);
+		}
+
+		public function set focalLength(value:Number):void {
+			if (!(value > 0)) {
+				throw new ArgumentError("Error #2186: Invalid focalLength " + value + ".", 2186);
+			}
+			this._fieldOfView = Math.atan(this._width / 2 / value) * 360 / Math.PI;
+		}
+
+		public function get projectionCenter():Point {
+			return this._projectionCenter;
+		}
+
+		public function set projectionCenter(value:Point):void {
+			this._projectionCenter = value;
+		}
+
+		public function toMatrix3D():Matrix3D {
+			var focalLength:Number = this.focalLength;
+			return new Matrix3D(Vector.<Number>([
+				focalLength, 0, 0, 0,
+				0, focalLength, 0, 0,
+				0, 0, 1, 1,
+				0, 0, 0, 0
+			]));
+		}
+	}
+}
@@ -15,5 +15,10 @@ package flash.geom {
 
 		public native function get concatenatedColorTransform():ColorTransform;
 		public native function get concatenatedMatrix():Matrix;
+
+		public native function get matrix3D():Matrix3D;
+		public native function set matrix3D(value:Matrix3D):void;
+		public native function get perspectiveProjection():PerspectiveProjection;
+		public native function set perspectiveProjection(value:PerspectiveProjection):void;
 	}
 }
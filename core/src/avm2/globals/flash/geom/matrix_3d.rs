@@ -0,0 +1,120 @@
+//! `flash.geom.Matrix3D` native methods
+
+use crate::avm2::globals::flash::geom::transform::{matrix_3d_to_raw_data, object_to_matrix_3d};
+use crate::avm2::object::VectorObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::avm2_stub_method;
+use ruffle_render::matrix3d::{Decomposition, Matrix3D};
+
+/// Implements `Matrix3D.decompose`.
+pub fn decompose<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.unwrap();
+    let orientation_style = args.get_string(activation, 0)?;
+    if &orientation_style != b"eulerAngles" {
+        avm2_stub_method!(
+            activation,
+            "flash.geom.Matrix3D",
+            "decompose",
+            "with orientation styles other than eulerAngles"
+        );
+    }
+
+    let matrix = object_to_matrix_3d(this, activation)?;
+    let Some(decomposition) = matrix.decompose() else {
+        return Ok(Value::Null);
+    };
+
+    let vector3d = activation.avm2().classes().vector3d;
+    let mut components = Vec::with_capacity(3);
+    for [x, y, z] in [
+        decomposition.translation,
+        decomposition.rotation,
+        decomposition.scale,
+    ] {
+        let component = vector3d.construct(activation, &[x.into(), y.into(), z.into()])?;
+        components.push(component.into());
+    }
+    let components = VectorStorage::from_values(components, false, vector3d);
+    Ok(VectorObject::from_vector(components, activation)?.into())
+}
+
+/// Implements `Matrix3D.recompose`.
+pub fn recompose<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut this = this.unwrap();
+    let components = args.get_object(activation, 0, "components")?;
+    let orientation_style = args.get_string(activation, 1)?;
+    if &orientation_style != b"eulerAngles" {
+        avm2_stub_method!(
+            activation,
+            "flash.geom.Matrix3D",
+            "recompose",
+            "with orientation styles other than eulerAngles"
+        );
+    }
+
+    let components: Vec<_> = match components.as_vector_storage() {
+        Some(components) => components.iter().collect(),
+        None => return Ok(false.into()),
+    };
+    if components.len() < 3 {
+        return Ok(false.into());
+    }
+    let mut values = [[0.0; 3]; 3];
+    for (component, values) in components.into_iter().zip(values.iter_mut()) {
+        let component = component.coerce_to_object(activation)?;
+        for (name, value) in ["x", "y", "z"].into_iter().zip(values.iter_mut()) {
+            *value = component
+                .get_public_property(name, activation)?
+                .coerce_to_number(activation)?;
+        }
+    }
+    let [translation, rotation, scale] = values;
+    if scale.contains(&0.0) {
+        return Ok(false.into());
+    }
+
+    let matrix = Matrix3D::recompose(&Decomposition {
+        translation,
+        rotation,
+        scale,
+    });
+    let raw_data = matrix_3d_to_raw_data(&matrix, activation)?;
+    this.set_public_property("rawData", raw_data.into(), activation)?;
+    Ok(true.into())
+}
+
+/// Implements `Matrix3D.determinant`'s getter.
+pub fn get_determinant<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix = object_to_matrix_3d(this.unwrap(), activation)?;
+    Ok(matrix.determinant().into())
+}
+
+/// Implements `Matrix3D.invert`.
+pub fn invert<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut this = this.unwrap();
+    let matrix = object_to_matrix_3d(this, activation)?;
+    let Some(inverse) = matrix.inverse() else {
+        return Ok(false.into());
+    };
+    let raw_data = matrix_3d_to_raw_data(&inverse, activation)?;
+    this.set_public_property("rawData", raw_data.into(), activation)?;
+    Ok(true.into())
+}
@@ -1,9 +1,12 @@
+use crate::avm2::object::VectorObject;
 use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::avm2_stub_getter;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{PerspectiveProjection, TDisplayObject};
 use crate::prelude::{DisplayObject, Matrix, Twips};
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8};
 
@@ -53,9 +56,7 @@ pub fn set_color_transform<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.unwrap();
     let ct = object_to_color_transform(args.get_object(activation, 0, "value")?, activation)?;
-    get_display_object(this, activation)?
-        .base_mut(activation.context.gc_context)
-        .set_color_transform(ct);
+    get_display_object(this, activation)?.set_color_transform(activation.context.gc_context, ct);
     Ok(Value::Undefined)
 }
 
@@ -65,7 +66,12 @@ pub fn get_matrix<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.unwrap();
-    let matrix = *get_display_object(this, activation)?.base().matrix();
+    let display_object = get_display_object(this, activation)?;
+    // Objects with a 3D transform only have a `matrix3D`.
+    if display_object.transform_3d().is_some() {
+        return Ok(Value::Null);
+    }
+    let matrix = *display_object.base().matrix();
     matrix_to_object(matrix, activation)
 }
 
@@ -76,9 +82,77 @@ pub fn set_matrix<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.unwrap();
     let matrix = object_to_matrix(args.get_object(activation, 0, "value")?, activation)?;
+    let display_object = get_display_object(this, activation)?;
+    display_object.set_transform_3d(activation.context.gc_context, None);
+    display_object.set_matrix(activation.context.gc_context, matrix);
+    Ok(Value::Undefined)
+}
+
+pub fn get_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.unwrap();
+    let display_object = get_display_object(this, activation)?;
+    // Objects with a 2D transform only have a `matrix`.
+    if display_object.transform_3d().is_none() {
+        return Ok(Value::Null);
+    }
+    let matrix = display_object.matrix_3d(activation.context.gc_context);
+    matrix_3d_to_object(matrix, activation)
+}
+
+pub fn set_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.unwrap();
+    let display_object = get_display_object(this, activation)?;
+    match args.try_get_object(activation, 0) {
+        Some(object) => {
+            let matrix = object_to_matrix_3d(object, activation)?;
+            display_object.set_matrix_3d(activation.context.gc_context, &matrix);
+        }
+        // Setting `null` turns the object back into a 2D object.
+        None => display_object.set_transform_3d(activation.context.gc_context, None),
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.unwrap();
+    let display_object = get_display_object(this, activation)?;
+    // The root of a movie always has a projection, all other objects only have
+    // one when it was set.
+    let is_root = display_object
+        .parent()
+        .map_or(false, |parent| parent.as_stage().is_some());
+    let projection = match display_object.perspective_projection() {
+        Some(projection) => projection,
+        None if is_root => PerspectiveProjection::for_stage(activation.context.stage),
+        None => return Ok(Value::Null),
+    };
+    perspective_projection_to_object(&projection, activation)
+}
+
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.unwrap();
+    let projection = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_perspective_projection(object, activation)?),
+        None => None,
+    };
     get_display_object(this, activation)?
-        .base_mut(activation.context.gc_context)
-        .set_matrix(matrix);
+        .set_perspective_projection(activation.context.gc_context, projection);
     Ok(Value::Undefined)
 }
 
@@ -246,3 +320,95 @@ pub fn object_to_matrix<'gc>(
 
     Ok(Matrix { a, b, c, d, tx, ty })
 }
+
+pub fn matrix_3d_to_object<'gc>(
+    matrix: Matrix3D,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let raw_data = matrix_3d_to_raw_data(&matrix, activation)?;
+    let object = activation
+        .avm2()
+        .classes()
+        .matrix3d
+        .construct(activation, &[raw_data.into()])?;
+    Ok(object.into())
+}
+
+/// Creates the `Vector.<Number>` used as the `rawData` of a `Matrix3D`.
+pub fn matrix_3d_to_raw_data<'gc>(
+    matrix: &Matrix3D,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let raw_data = matrix
+        .raw_data
+        .iter()
+        .map(|value| (*value).into())
+        .collect();
+    let number = activation.avm2().classes().number;
+    let raw_data = VectorStorage::from_values(raw_data, false, number);
+    VectorObject::from_vector(raw_data, activation)
+}
+
+pub fn object_to_matrix_3d<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Matrix3D, Error<'gc>> {
+    let raw_data = object
+        .get_public_property("rawData", activation)?
+        .coerce_to_object(activation)?;
+    let raw_data: Vec<_> = match raw_data.as_vector_storage() {
+        Some(raw_data) => raw_data.iter().collect(),
+        None => return Err("TypeError: rawData is not a Vector".into()),
+    };
+    let mut matrix = Matrix3D::IDENTITY;
+    for (element, value) in matrix.raw_data.iter_mut().zip(raw_data) {
+        *element = value.coerce_to_number(activation)?;
+    }
+    Ok(matrix)
+}
+
+pub fn perspective_projection_to_object<'gc>(
+    projection: &PerspectiveProjection,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut object = activation
+        .avm2()
+        .classes()
+        .perspectiveprojection
+        .construct(activation, &[])?;
+    let stage_width = activation.context.stage.stage_size().0;
+    object.set_property(
+        &Multiname::new(activation.avm2().flash_geom_internal, "_width"),
+        stage_width.into(),
+        activation,
+    )?;
+    object.set_public_property("fieldOfView", projection.field_of_view.into(), activation)?;
+    let center = activation.avm2().classes().point.construct(
+        activation,
+        &[projection.center.0.into(), projection.center.1.into()],
+    )?;
+    object.set_public_property("projectionCenter", center.into(), activation)?;
+    Ok(object.into())
+}
+
+pub fn object_to_perspective_projection<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<PerspectiveProjection, Error<'gc>> {
+    let field_of_view = object
+        .get_public_property("fieldOfView", activation)?
+        .coerce_to_number(activation)?;
+    let center = object
+        .get_public_property("projectionCenter", activation)?
+        .coerce_to_object(activation)?;
+    let x = center
+        .get_public_property("x", activation)?
+        .coerce_to_number(activation)?;
+    let y = center
+        .get_public_property("y", activation)?
+        .coerce_to_number(activation)?;
+    Ok(PerspectiveProjection {
+        field_of_view,
+        center: (x, y),
+    })
+}
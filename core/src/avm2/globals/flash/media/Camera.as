@@ -4,123 +4,58 @@ package flash.media {
     import flash.geom.Rectangle;
     import flash.display.BitmapData;
 
+    [Ruffle(InstanceAllocator)]
     public final class Camera extends EventDispatcher {
-        public function copyToByteArray(rect:Rectangle, destination:ByteArray) {
-            __ruffle__.stub_method("flash.media.Camera", "copyToByteArray");
-        }
+        public native function copyToByteArray(rect:Rectangle, destination:ByteArray):void;
 
-        public function copyToVector(rect:Rectangle, destination:Vector.<uint>) {
-            __ruffle__.stub_method("flash.media.Camera", "copyToVector");
-        }
+        public native function copyToVector(rect:Rectangle, destination:Vector.<uint>):void;
 
-        public function drawToBitmapData(destination:BitmapData) {
-            __ruffle__.stub_method("flash.media.Camera", "drawToBitmapData");
-        }
+        public native function drawToBitmapData(destination:BitmapData):void;
 
-        public static function getCamera(name: String = null):Camera {
-            __ruffle__.stub_method("flash.media.Camera", "getCamera");
-            return null;
-        }
+        public static native function getCamera(name:String = null):Camera;
 
-        public function setKeyFrameInterval(keyFrameInterval:int) {
-            __ruffle__.stub_method("flash.media.Camera", "setKeyFrameInterval");
-        }
+        public native function setKeyFrameInterval(keyFrameInterval:int):void;
 
-        public function setLoopback(compress:Boolean = false) {
-            __ruffle__.stub_method("flash.media.Camera", "setLoopback");
-        }
+        public native function setLoopback(compress:Boolean = false):void;
 
-        public function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true) {
-            __ruffle__.stub_method("flash.media.Camera", "setMode");
-        }
+        public native function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true):void;
 
-        public function setMotionLevel(motionLevel:int, timeout:int = 2000) {
-            __ruffle__.stub_method("flash.media.Camera", "setMotionLevel");
-        }
+        public native function setMotionLevel(motionLevel:int, timeout:int = 2000):void;
 
-        public function setQuality(bandwidth:int, quality:int) {
-            __ruffle__.stub_method("flash.media.Camera", "setQuality");
-        }
+        public native function setQuality(bandwidth:int, quality:int):void;
 
-        public function get activityLevel(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "activityLevel");
-            return 0;
-        }
+        public native function get activityLevel():Number;
 
-        public function get bandwidth(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "bandwidth");
-            return 0;
-        }
+        public native function get bandwidth():int;
 
-        public function get currentFPS(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "currentFPS");
-            return 0;
-        }
+        public native function get currentFPS():Number;
 
-        public function get fps(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "fps");
-            return 0;
-        }
+        public native function get fps():Number;
 
-        public function get height(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "height");
-            return 0;
-        }
+        public native function get height():int;
 
-        public function get index(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "index");
-            return 0;
-        }
+        public native function get index():int;
 
-        public static function get isSupported(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "isSupported");
-            return false;
+        public static function get isSupported():Boolean {
+            return true;
         }
 
-        public function get keyFrameInterval(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "keyFrameInterval");
-            return 0;
-        }
+        public native function get keyFrameInterval():int;
 
-        public function get loopback(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "loopback");
-            return false;
-        }
+        public native function get loopback():Boolean;
 
-        public function get motionLevel(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "motionLevel");
-            return 0;
-        }
+        public native function get motionLevel():int;
 
-        public function get motionTimeout(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "motionTimeout");
-            return 0;
-        }
+        public native function get motionTimeout():int;
 
-        public function get muted(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "muted");
-            return true;
-        }
+        public native function get muted():Boolean;
 
-        public function get name(): String {
-            __ruffle__.stub_getter("flash.media.Camera", "name");
-            return "";
-        }
+        public native function get name():String;
 
-        public static function get names(): Array {
-            __ruffle__.stub_getter("flash.media.Camera", "names");
-            return [];
-        }
-        
-        public function get quality(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "quality");
-            return 0;
-        }
-        
-        public function get width(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "width");
-            return 0;
-        }
-    }
+        public static native function get names():Array;
+
+        public native function get quality():int;
 
-}
\ No newline at end of file
+        public native function get width():int;
+    }
+}
@@ -13,6 +13,7 @@ package flash.media {
 
         public native function get bytesLoaded():uint;
         public native function get bytesTotal():int;
+        public native function get id3():ID3Info;
         public native function get isBuffering():Boolean;
         public native function get isURLInaccessible():Boolean;
         public native function get url():String;
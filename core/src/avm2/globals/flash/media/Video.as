@@ -7,14 +7,13 @@ package flash.media
     {
         private var _deblocking: int;
         private var _smoothing: Boolean;
-        private var _videoWidth: int;
-        private var _videoHeight: int;
 
         public function Video(width: int = 320, height: int = 240) {
-            this._videoWidth = width;
-            this._videoHeight = height;
+            this.init(width, height);
         }
 
+        private native function init(width: int, height: int);
+
         public function get deblocking():int {
             return this._deblocking;
         }
@@ -31,14 +30,12 @@ package flash.media
             this._smoothing = value;
         }
 
-        public function get videoWidth():int {
-            return this._videoWidth;
-        }
+        public native function get videoWidth():int;
 
-        public function get videoHeight():int {
-            return this._videoHeight;
-        }
+        public native function get videoHeight():int;
 
         public native function attachNetStream(netStream: NetStream);
+
+        public native function attachCamera(camera: Camera);
     }
 }
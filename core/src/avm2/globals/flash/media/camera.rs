@@ -0,0 +1,419 @@
+//! `flash.media.Camera` native function definitions
+
+use crate::avm2::error::range_error;
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Object, Value};
+use crate::camera::{Camera, Cameras};
+use crate::string::AvmString;
+use crate::vminterface::AvmObject;
+
+pub use crate::avm2::object::camera_allocator;
+
+/// Read the rectangle argument of `copyToByteArray` and `copyToVector`, and
+/// get the camera's pixels inside it.
+fn pixels_in_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    camera: Camera<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Vec<u32>, Error<'gc>> {
+    let rectangle = args.get_object(activation, 0, "rect")?;
+    let x = rectangle
+        .get_public_property("x", activation)?
+        .coerce_to_i32(activation)?;
+    let y = rectangle
+        .get_public_property("y", activation)?
+        .coerce_to_i32(activation)?;
+    let width = rectangle
+        .get_public_property("width", activation)?
+        .coerce_to_i32(activation)?;
+    let height = rectangle
+        .get_public_property("height", activation)?
+        .coerce_to_i32(activation)?;
+
+    match camera.pixels_argb(x, y, width, height) {
+        Some(pixels) => Ok(pixels),
+        None => Err(Error::AvmError(range_error(
+            activation,
+            "Error #2006: The supplied index is out of bounds.",
+            2006,
+        )?)),
+    }
+}
+
+/// Implements `Camera.copyToByteArray`
+pub fn copy_to_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        let pixels = pixels_in_rect(activation, camera, args)?;
+        let destination = args.get_object(activation, 1, "destination")?;
+        if let Some(mut bytearray) = destination.as_bytearray_mut(activation.context.gc_context) {
+            for pixel in pixels {
+                bytearray.write_unsigned_int(pixel)?;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.copyToVector`
+pub fn copy_to_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        let pixels = pixels_in_rect(activation, camera, args)?;
+        let destination = args.get_object(activation, 1, "destination")?;
+        if let Some(mut vector) = destination.as_vector_storage_mut(activation.context.gc_context) {
+            vector.resize(pixels.len(), activation)?;
+            for (i, pixel) in pixels.into_iter().enumerate() {
+                vector.set(i, pixel.into(), activation)?;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.drawToBitmapData`
+///
+/// The last frame is drawn at the top left of the bitmap, without scaling.
+pub fn draw_to_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        let destination = args.get_object(activation, 0, "destination")?;
+        if let Some(bitmap_data) = destination.as_bitmap_data() {
+            bitmap_data.read().check_valid(activation)?;
+
+            let width = camera.width().min(bitmap_data.read().width());
+            let height = camera.height().min(bitmap_data.read().height());
+            let pixels = camera
+                .pixels_argb(0, 0, width as i32, height as i32)
+                .unwrap_or_default();
+
+            let mut bitmap_data = bitmap_data.write(activation.context.gc_context);
+            for (i, pixel) in pixels.into_iter().enumerate() {
+                let x = i as u32 % width;
+                let y = i as u32 / width;
+                bitmap_data.set_pixel32(x, y, (pixel as i32).into());
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.getCamera`
+pub fn get_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // The name is the position of the camera in `Camera.names`, as a string.
+    // Leaving it out asks for the default camera.
+    let index = match args.try_get_string(activation, 0)? {
+        Some(name) => match name.to_string().parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => return Ok(Value::Null),
+        },
+        None => 0,
+    };
+    let names = activation.context.camera.names();
+    let Some(name) = names.get(index) else {
+        return Ok(Value::Null);
+    };
+
+    if let Some(camera) = activation.context.cameras.get(index) {
+        if let Some(AvmObject::Avm2(object)) = camera.avm_object() {
+            return Ok(object.into());
+        }
+    }
+
+    let object = activation
+        .avm2()
+        .classes()
+        .camera
+        .construct(activation, &[])?;
+    let camera = object.as_camera().expect("Camera should be a CameraObject");
+    camera.set_device(activation.context.gc_context, index, name.clone());
+    Cameras::open(&mut activation.context, camera);
+
+    Ok(object.into())
+}
+
+/// Implements `Camera.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation.context.camera.names();
+    let storage: ArrayStorage<'gc> = names
+        .iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name))
+        .collect();
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements `Camera.setKeyFrameInterval`
+pub fn set_key_frame_interval<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        let interval = args.get_i32(activation, 0)?;
+        camera.set_key_frame_interval(activation.context.gc_context, interval);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.setLoopback`
+pub fn set_loopback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        camera.set_loopback(activation.context.gc_context, args.get_bool(0));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.setMode`
+pub fn set_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        let width = args.get_i32(activation, 0)?;
+        let height = args.get_i32(activation, 1)?;
+        let fps = args.get_f64(activation, 2)?;
+        camera.set_mode(&mut activation.context, width, height, fps);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.setMotionLevel`
+pub fn set_motion_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        let motion_level = args.get_i32(activation, 0)?;
+        let timeout = args.get_i32(activation, 1)?;
+        camera.set_motion_level(activation.context.gc_context, motion_level, timeout);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.setQuality`
+pub fn set_quality<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        let bandwidth = args.get_i32(activation, 0)?;
+        let quality = args.get_i32(activation, 1)?;
+        camera.set_quality(activation.context.gc_context, bandwidth, quality);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.activityLevel`'s getter
+pub fn get_activity_level<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.activity_level().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.bandwidth`'s getter
+pub fn get_bandwidth<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.bandwidth().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.currentFPS`'s getter
+pub fn get_current_fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.current_fps().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.fps`'s getter
+pub fn get_fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.fps().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.height`'s getter
+pub fn get_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.height().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.index`'s getter
+pub fn get_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok((camera.index() as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.keyFrameInterval`'s getter
+pub fn get_key_frame_interval<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.key_frame_interval().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.loopback`'s getter
+pub fn get_loopback<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.loopback().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.motionLevel`'s getter
+pub fn get_motion_level<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.motion_level().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.motionTimeout`'s getter
+pub fn get_motion_timeout<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.motion_timeout().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.muted`'s getter
+pub fn get_muted<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.muted().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.name`'s getter
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(AvmString::new_utf8(activation.context.gc_context, camera.name()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.quality`'s getter
+pub fn get_quality<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.quality().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.width`'s getter
+pub fn get_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.width().into());
+    }
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,254 @@
+//! `flash.media.Microphone` native function definitions
+
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Object, Value};
+use crate::backend::audio::SampleBuffer;
+use crate::microphone::Microphones;
+use crate::string::AvmString;
+
+pub use crate::avm2::object::microphone_allocator;
+
+/// Implements `Microphone.getMicrophone`
+pub fn get_microphone<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // A negative index asks for the default microphone.
+    let index = args.get_i32(activation, 0)?.max(0) as usize;
+    let names = activation.context.microphone.names();
+    let Some(name) = names.get(index) else {
+        return Ok(Value::Null);
+    };
+
+    if let Some(microphone) = activation.context.microphones.get(index) {
+        return Ok(Object::from(microphone).into());
+    }
+
+    let object = activation
+        .avm2()
+        .classes()
+        .microphone
+        .construct(activation, &[])?;
+    let microphone = object
+        .as_microphone()
+        .expect("Microphone should be a MicrophoneObject");
+    {
+        let mut settings = microphone.microphone_mut(activation.context.gc_context);
+        settings.index = index;
+        settings.name = name.clone();
+    }
+    Microphones::open(&mut activation.context, microphone);
+
+    Ok(object.into())
+}
+
+/// Implements `Microphone.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation.context.microphone.names();
+    let storage: ArrayStorage<'gc> = names
+        .iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name))
+        .collect();
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements `Microphone.setLoopBack`
+pub fn set_loop_back<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        let is_looped = args.get_bool(0);
+        let has_loopback = microphone.microphone().has_loopback();
+        if is_looped && !has_loopback {
+            let buffer = SampleBuffer::new();
+            let instance = activation
+                .context
+                .audio
+                .start_sample_stream(buffer.clone())
+                .ok();
+            microphone
+                .microphone_mut(activation.context.gc_context)
+                .start_loopback(buffer, instance);
+        } else if !is_looped && has_loopback {
+            let instance = microphone
+                .microphone_mut(activation.context.gc_context)
+                .stop_loopback();
+            if let Some(instance) = instance {
+                activation.context.audio.stop_sound(instance);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.setSilenceLevel`
+pub fn set_silence_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        let silence_level = args.get_f64(activation, 0)?;
+        let timeout = args.get_i32(activation, 1)?;
+
+        let mut settings = microphone.microphone_mut(activation.context.gc_context);
+        settings.silence_level = silence_level.clamp(0.0, 100.0);
+        // A negative timeout leaves the current one as it is.
+        if timeout >= 0 {
+            settings.silence_timeout = timeout;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.activityLevel`'s getter
+pub fn get_activity_level<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        return Ok(microphone.microphone().activity_level.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.gain`'s getter
+pub fn get_gain<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        return Ok(microphone.microphone().gain.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.gain`'s setter
+pub fn set_gain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        let gain = args.get_f64(activation, 0)?;
+        if !gain.is_nan() {
+            microphone
+                .microphone_mut(activation.context.gc_context)
+                .gain = gain.clamp(0.0, 100.0);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.index`'s getter
+pub fn get_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        return Ok((microphone.microphone().index as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.muted`'s getter
+pub fn get_muted<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        return Ok(microphone.microphone().muted.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.name`'s getter
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        return Ok(AvmString::new_utf8(
+            activation.context.gc_context,
+            &microphone.microphone().name,
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.rate`'s getter
+pub fn get_rate<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        return Ok(microphone.microphone().rate.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.rate`'s setter
+pub fn set_rate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        let rate = args.get_i32(activation, 0)?;
+        microphone
+            .microphone_mut(activation.context.gc_context)
+            .set_rate(rate);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.silenceLevel`'s getter
+pub fn get_silence_level<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        return Ok(microphone.microphone().silence_level.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.silenceTimeout`'s getter
+pub fn get_silence_timeout<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(microphone) = this.and_then(|this| this.as_microphone()) {
+        return Ok(microphone.microphone().silence_timeout.into());
+    }
+
+    Ok(Value::Undefined)
+}
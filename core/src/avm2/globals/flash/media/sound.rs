@@ -1,17 +1,25 @@
 //! `flash.media.Sound` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::{Object, QueuedPlay, SoundChannelObject, TObject};
+use crate::avm2::error::{argument_error, error, io_error};
+use crate::avm2::object::{Object, QueuedPlay, SoundChannelObject, SoundObject, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::avm2_stub_getter;
+use crate::backend::audio::AudioManager;
 use crate::backend::navigator::Request;
 use crate::character::Character;
 use crate::display_object::SoundTransform;
-use crate::{avm2_stub_constructor, avm2_stub_getter, avm2_stub_method};
+use crate::string::AvmString;
 use swf::{SoundEvent, SoundInfo};
 
 pub use crate::avm2::object::sound_allocator;
 
+/// The `bufferTime` of a `SoundLoaderContext`, in milliseconds, which is used
+/// when `Sound.load` isn't given one.
+const DEFAULT_BUFFER_TIME: f64 = 1000.0;
+
 /// Implements `flash.media.Sound`'s 'init' method. which is called from the constructor.
 pub fn init<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -21,10 +29,6 @@ pub fn init<'gc>(
     if let Some(this) = this {
         activation.super_init(this, &[])?;
 
-        if !args.is_empty() {
-            avm2_stub_constructor!(activation, "flash.media.Sound", "with arguments");
-        }
-
         if let Some(sound_object) = this.as_sound_object() {
             let class_object = this
                 .instance_of()
@@ -49,6 +53,10 @@ pub fn init<'gc>(
                 }
             }
         }
+
+        if let Some(Value::Object(_)) = args.get(0) {
+            load(activation, Some(this), args)?;
+        }
     }
 
     Ok(Value::Undefined)
@@ -61,6 +69,9 @@ pub fn get_bytes_total<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(sound) = this.and_then(|this| this.as_sound_object()) {
+        if let Some(bytes_total) = sound.bytes_total() {
+            return Ok(bytes_total.into());
+        }
         if let Some(sound_handle) = sound.sound_handle() {
             if let Some(length) = activation.context.audio.get_sound_size(sound_handle) {
                 return Ok((length).into());
@@ -72,25 +83,33 @@ pub fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Sound.bytesLoaded`
 pub fn get_bytes_loaded<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // This should have a different value from bytesTotal when the sound is loading.
-    avm2_stub_getter!(activation, "flash.media.Sound", "bytesLoaded");
-    get_bytes_total(activation, this, args)
+    if let Some(sound) = this.and_then(|this| this.as_sound_object()) {
+        if let Some(bytes_loaded) = sound.bytes_loaded() {
+            return Ok(bytes_loaded.into());
+        }
+        return get_bytes_total(activation, this, args);
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `Sound.isBuffering`
 pub fn get_is_buffering<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.media.Sound", "isBuffering");
-    //STUB: We do not yet support network-loaded sounds.
-    Ok(false.into())
+    if let Some(sound) = this.and_then(|this| this.as_sound_object()) {
+        return Ok(sound.is_buffering().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `Sound.isURLInaccessible`
@@ -100,18 +119,37 @@ pub fn get_is_url_inaccessible<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     avm2_stub_getter!(activation, "flash.media.Sound", "isURLInaccessible");
-    //STUB: We do not yet support network-loaded sounds.
     Ok(false.into())
 }
 
 /// Implements `Sound.url`
 pub fn get_url<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.media.Sound", "url");
-    //STUB: We do not yet support network-loaded sounds.
+    if let Some(sound) = this.and_then(|this| this.as_sound_object()) {
+        if let Some(url) = sound.url() {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, url).into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Sound.id3`
+pub fn get_id3<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(id3) = this
+        .and_then(|this| this.as_sound_object())
+        .and_then(|sound| sound.id3())
+    {
+        return Ok(id3.into());
+    }
+
     Ok(Value::Null)
 }
 
@@ -177,6 +215,39 @@ pub fn play<'gc>(
 
         let sound_channel = SoundChannelObject::empty(activation)?;
 
+        // A sound without any audio of its own generates it on demand, if
+        // something is listening for `sampleData` events.
+        if sound_object.sound_handle().is_none()
+            && sound_object.url().is_none()
+            && sound_object
+                .call_public_property("hasEventListener", &["sampleData".into()], activation)?
+                .coerce_to_boolean()
+        {
+            let start_position = in_sample.map_or(0, u64::from);
+            if let Some(instance) = activation
+                .context
+                .start_sample_data_sound(sound_object.into(), start_position)
+            {
+                if let Some(sound_transform) = sound_transform {
+                    activation
+                        .context
+                        .set_local_sound_transform(instance, sound_transform);
+                }
+
+                sound_channel
+                    .as_sound_channel()
+                    .unwrap()
+                    .set_sound_instance(activation, instance);
+
+                activation
+                    .context
+                    .attach_avm2_sound_channel(instance, sound_channel);
+
+                AudioManager::start_sample_data(&mut activation.context, instance)?;
+            }
+            return Ok(sound_channel.into());
+        }
+
         let queued_play = QueuedPlay {
             position,
             sound_info,
@@ -197,20 +268,70 @@ pub fn play<'gc>(
 /// `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
+    if let Some(sound_object) = this.and_then(|this| this.as_sound_object()) {
+        let target = args.get_object(activation, 0, "target")?;
+        let length = args.get_f64(activation, 1)?;
+        let start_position = args.get_f64(activation, 2)?;
+
+        let Some(sound) = sound_object.sound_handle() else {
+            return Ok(0.into());
+        };
+        let position = if start_position < 0.0 {
+            sound_object.extract_position()
+        } else {
+            start_position as u32
+        };
+        let samples = activation
+            .context
+            .audio
+            .get_sound_samples(sound, position, length.max(0.0) as u32)
+            .unwrap_or_default();
+
+        if let Some(mut bytearray) = target.as_bytearray_mut(activation.context.gc_context) {
+            for [left, right] in &samples {
+                bytearray.write_float(*left)?;
+                bytearray.write_float(*right)?;
+            }
+        }
+
+        let num_samples = samples.len() as u32;
+        sound_object.set_extract_position(
+            activation.context.gc_context,
+            position.saturating_add(num_samples),
+        );
+        return Ok(num_samples.into());
+    }
+
     Ok(Value::Undefined)
 }
 
 /// `Sound.close`
 pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "close");
+    if let Some(this) = this {
+        match activation.context.load_manager.sound_avm2_loader(this) {
+            Some(handle) => {
+                activation.context.load_manager.remove_loader(handle);
+                if let Some(sound) = this.as_sound_object() {
+                    sound.finish_load(activation.context.gc_context);
+                }
+            }
+            None => {
+                return Err(Error::AvmError(io_error(
+                    activation,
+                    "Error #2029: This URLStream object does not have a stream opened.",
+                    2029,
+                )?));
+            }
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -220,27 +341,31 @@ pub fn load<'gc>(
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this {
-        // FIXME - don't allow replacing an existing sound
+    if let Some(sound_object) = this.and_then(|this| this.as_sound_object()) {
         let url_request = match args.get(0) {
             Some(Value::Object(request)) => request,
             // This should never actually happen
             _ => return Ok(Value::Undefined),
         };
 
+        check_not_loaded(activation, sound_object)?;
+
         let url = url_request
             .get_public_property("url", activation)?
             .coerce_to_string(activation)?;
+        sound_object.set_url(activation.context.gc_context, url.to_string());
 
-        // TODO: context parameter currently unused.
-        let _sound_context = args.get(1);
-        if _sound_context.is_some() {
-            avm2_stub_method!(activation, "flash.media.Sound", "load", "with context");
-        }
+        let buffer_time = match args.get(1) {
+            Some(Value::Object(context)) => context
+                .get_public_property("bufferTime", activation)?
+                .coerce_to_number(activation)?,
+            _ => DEFAULT_BUFFER_TIME,
+        };
+        sound_object.start_load(activation.context.gc_context, buffer_time);
 
         let future = activation.context.load_manager.load_sound_avm2(
             activation.context.player.clone(),
-            this,
+            sound_object.into(),
             // FIXME: Set options from the `URLRequest`.
             Request::get(url.to_string()),
         );
@@ -249,26 +374,138 @@ pub fn load<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Throws if the given `Sound` already has, or is loading, audio data.
+fn check_not_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    sound_object: SoundObject<'gc>,
+) -> Result<(), Error<'gc>> {
+    if sound_object.url().is_some() || sound_object.sound_handle().is_some() {
+        return Err(Error::AvmError(error(
+            activation,
+            "Error #2037: Functions called in incorrect sequence, or earlier call was unsuccessful.",
+            2037,
+        )?));
+    }
+    Ok(())
+}
+
 /// `Sound.loadCompressedDataFromByteArray`
 pub fn load_compressed_data_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(
-        activation,
-        "flash.media.Sound",
-        "loadCompressedDataFromByteArray"
-    );
+    if let Some(sound_object) = this.and_then(|this| this.as_sound_object()) {
+        let bytes = args.get_object(activation, 0, "bytes")?;
+        let bytes_length = args.get_u32(activation, 1)? as usize;
+        check_not_loaded(activation, sound_object)?;
+
+        let data = match bytes.as_bytearray() {
+            Some(bytearray) => {
+                let length = bytes_length.min(bytearray.bytes_available());
+                let data = bytearray
+                    .read_bytes(length)
+                    .map_err(|e| e.to_avm(activation))?;
+                data.to_vec()
+            }
+            None => return Ok(Value::Undefined),
+        };
+
+        match activation.context.audio.register_mp3(&data) {
+            Ok(sound) => sound_object.set_sound(&mut activation.context, sound)?,
+            Err(e) => tracing::error!(
+                "Sound.loadCompressedDataFromByteArray: Couldn't decode MP3: {}",
+                e
+            ),
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
 /// `Sound.loadPCMFromByteArray`
 pub fn load_pcm_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "loadPCMFromByteArray");
+    if let Some(sound_object) = this.and_then(|this| this.as_sound_object()) {
+        let bytes = args.get_object(activation, 0, "bytes")?;
+        let num_samples = args.get_u32(activation, 1)? as usize;
+        let format = args.get_string(activation, 2)?;
+        let is_stereo = args.get_bool(3);
+        let sample_rate = args.get_f64(activation, 4)?;
+        check_not_loaded(activation, sound_object)?;
+
+        let is_float = if &format == b"float" {
+            true
+        } else if &format == b"short" {
+            false
+        } else {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2008: Parameter format must be one of the accepted values.",
+                2008,
+            )?));
+        };
+        let sample_rate = match sample_rate as u16 {
+            rate @ (5512 | 11025 | 22050 | 44100) if sample_rate.fract() == 0.0 => rate,
+            _ => {
+                return Err(Error::AvmError(argument_error(
+                    activation,
+                    "Error #2008: Parameter sampleRate must be one of the accepted values.",
+                    2008,
+                )?));
+            }
+        };
+
+        let num_channels = if is_stereo { 2 } else { 1 };
+        let sample_size = if is_float { 4 } else { 2 };
+        let Some(bytearray) = bytes.as_bytearray() else {
+            return Ok(Value::Undefined);
+        };
+        let available = bytearray.bytes_available() / (sample_size * num_channels);
+        let num_values = num_samples.min(available) * num_channels;
+
+        if is_float {
+            let samples = (0..num_values)
+                .map(|_| bytearray.read_float())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| e.to_avm(activation))?;
+            drop(bytearray);
+            let sound =
+                activation
+                    .context
+                    .audio
+                    .register_float_pcm(&samples, is_stereo, sample_rate);
+            sound_object.set_sound(&mut activation.context, sound)?;
+            return Ok(Value::Undefined);
+        }
+
+        // Short samples are already in the little-endian 16-bit PCM that SWF sounds use.
+        let mut data = Vec::with_capacity(num_values * 2);
+        for _ in 0..num_values {
+            let sample = bytearray.read_short().map_err(|e| e.to_avm(activation))?;
+            data.extend_from_slice(&sample.to_le_bytes());
+        }
+        drop(bytearray);
+
+        let swf_sound = swf::Sound {
+            id: 0,
+            format: swf::SoundFormat {
+                compression: swf::AudioCompression::Uncompressed,
+                sample_rate,
+                is_stereo,
+                is_16_bit: true,
+            },
+            num_samples: (num_values / num_channels) as u32,
+            data: &data,
+        };
+        match activation.context.audio.register_sound(&swf_sound) {
+            Ok(sound) => sound_object.set_sound(&mut activation.context, sound)?,
+            Err(e) => tracing::error!("Sound.loadPCMFromByteArray: Couldn't register sound: {}", e),
+        }
+    }
+
     Ok(Value::Undefined)
 }
@@ -1,5 +1,34 @@
 use crate::avm2::{Activation, Error, Object, TObject, Value};
-use crate::display_object::TDisplayObject;
+use crate::display_object::{TDisplayObject, Video};
+
+/// Implements `flash.media.Video`'s `init` method, which is called from the constructor.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let width = args
+                .get(0)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_i32(activation)?;
+            let height = args
+                .get(1)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_i32(activation)?;
+
+            let new_do = Video::new(&mut activation.context, width, height, Some(this.into()));
+            this.init_display_object(&mut activation.context, new_do.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
 
 pub fn attach_net_stream<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -28,3 +57,65 @@ pub fn attach_net_stream<'gc>(
 
     Ok(Value::Undefined)
 }
+
+pub fn attach_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        let source = args.get(0).cloned().and_then(|v| v.as_object());
+
+        if let Some(camera) = source.and_then(|o| o.as_camera()) {
+            video.attach_camera(&mut activation.context, camera);
+        } else if source.is_none() {
+            video.detach(&mut activation.context);
+        } else {
+            return Err(format!(
+                "Cannot use value of type {:?} as video source",
+                source
+                    .and_then(|o| o.instance_of_class_definition())
+                    .map(|c| c.read().name().local_name())
+                    .unwrap_or_else(|| "Object".into())
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `videoWidth`'s getter.
+pub fn get_video_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        return Ok(video.video_size().0.into());
+    }
+
+    Ok(0.into())
+}
+
+/// Implements `videoHeight`'s getter.
+pub fn get_video_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        return Ok(video.video_size().1.into());
+    }
+
+    Ok(0.into())
+}
@@ -3,11 +3,17 @@
 use crate::avm2::object::TObject;
 use crate::avm2::{Activation, Error, Object, Value};
 
+pub mod file_reference;
+pub mod file_reference_list;
 pub mod local_connection;
+pub mod net_connection;
 pub mod net_stream;
 pub mod object_encoding;
+pub mod responder;
 pub mod shared_object;
+pub mod socket;
 pub mod url_loader;
+pub mod xml_socket;
 
 /// Implements `flash.net.navigateToURL`
 pub fn navigate_to_url<'gc>(
@@ -1,25 +1,21 @@
 package flash.net
 {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_method;
 
     public class FileReferenceList extends EventDispatcher
     {
-        private var _fileList:Array;
+        // Set by the player to the chosen files when `select` is dispatched.
+        ruffle var _fileList:Array = null;
+
         public function FileReferenceList()
         {
-            _fileList = new Array();
         }
 
         public function get fileList():Array
         {
-            return this._fileList;
+            return this.ruffle::_fileList;
         }
 
-        public function browse(typeFilter:Array = null):Boolean
-        {
-            stub_method("flash.net.FileReferenceList", "browse");
-            return false;
-        }
+        public native function browse(typeFilter:Array = null):Boolean;
     }
-}
\ No newline at end of file
+}
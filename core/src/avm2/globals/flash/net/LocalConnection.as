@@ -1,38 +1,50 @@
 package flash.net {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
 
-    // NOTE: this entire class is a stub.
-    // Thankfully (hopefully) a lot of code like Mochicrypt doesn't actually require this to... well do anything.
+    [Ruffle(InstanceAllocator)]
     public class LocalConnection extends EventDispatcher {
 
         public var client: Object;
 
+        public var isPerUser: Boolean = false;
+
         public function LocalConnection() {
             this.client = this;
         }
 
-        public native function get domain():String;
-
-        public function close(): void {
-            stub_method("flash.net.LocalConnection", "close");
+        public static function get isSupported():Boolean {
+            return true;
         }
 
+        public native function get domain():String;
+
         public function connect(connectionName:String): void {
-            stub_method("flash.net.LocalConnection", "connect");
+            if (connectionName == null) {
+                throw new TypeError("Error #2007: Parameter connectionName must be non-null.", 2007);
+            }
+
+            this.connectToName(connectionName);
         }
 
+        private native function connectToName(connectionName:String):void;
+
+        public native function close(): void;
+
         public function send(connectionName: String, methodName: String, ... arguments): void {
-            stub_method("flash.net.LocalConnection", "send");
+            if (connectionName == null) {
+                throw new TypeError("Error #2007: Parameter connectionName must be non-null.", 2007);
+            }
+            if (methodName == null) {
+                throw new TypeError("Error #2007: Parameter methodName must be non-null.", 2007);
+            }
+
+            this.sendMessage(connectionName, methodName, arguments);
         }
 
-        public function allowDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowDomain");
-        }
+        private native function sendMessage(connectionName:String, methodName:String, arguments:Array):void;
 
-        public function allowInsecureDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowInsecureDomain");
-        }
+        public native function allowDomain(... domains): void;
+
+        public native function allowInsecureDomain(... domains): void;
     }
-}
\ No newline at end of file
+}
@@ -1,25 +1,52 @@
 package flash.net {
     import flash.events.EventDispatcher;
     import flash.errors.IOError;
-    import __ruffle__.stub_method;
+    import __ruffle__.stub_getter;
 
+    [Ruffle(InstanceAllocator)]
     public class NetConnection extends EventDispatcher {
 
         public static var defaultObjectEncoding:uint = 3;
 
         public var objectEncoding:uint = NetConnection.defaultObjectEncoding; // NOPMD WronglyNamedVariable
 
+        private var _client:Object;
 
-        public function connect(command:String, ... arguments):void {
-            stub_method("flash.net.NetConnection", "connect");
+        public function NetConnection() {
+            this._client = this;
         }
 
-        public function call(command:String, responder:Responder, ... arguments):void {
-            stub_method("flash.net.NetConnection", "call");
+        public native function addHeader(operation:String, mustUnderstand:Boolean = false, param:Object = null):void;
+
+        public native function connect(command:String, ... arguments):void;
+
+        public native function call(command:String, responder:Responder, ... arguments):void;
+
+        public native function close():void;
+
+        public function get client():Object {
+            return this._client;
+        }
+
+        public function set client(client:Object) {
+            if (client == null) {
+                throw new TypeError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            this._client = client;
+        }
+
+        public native function get connected():Boolean;
+
+        public function get connectedProxyType():String {
+            stub_getter("flash.net.NetConnection", "connectedProxyType");
+            return "none";
         }
-        
-        public function close():void {
-            stub_method("flash.net.NetConnection", "close");
+
+        public native function get uri():String;
+
+        public function get usingTLS():Boolean {
+            return false;
         }
     }
 }
@@ -17,17 +17,15 @@ package flash.net {
         public static const CONNECT_TO_FMS: String = "connectToFMS";
         public static const DIRECT_CONNECTIONS: String = "directConnections";
 
-        public function NetStream(connection:NetConnection, peer:String = CONNECT_TO_FMS) {
+        private var _client:Object;
 
+        public function NetStream(connection:NetConnection, peer:String = CONNECT_TO_FMS) {
+            this._client = this;
         }
 
-        public function appendBytes(bytes:ByteArray) {
-            stub_method("flash.net.NetStream", "appendBytes");
-        }
+        public native function appendBytes(bytes:ByteArray);
 
-        public function appendBytesAction(action:String) {
-            stub_method("flash.net.NetStream", "appendBytesAction");
-        }
+        public native function appendBytesAction(action:String);
 
         public function attach(connection:NetConnection) {
             stub_method("flash.net.NetStream", "attach");
@@ -41,9 +39,7 @@ package flash.net {
             stub_method("flash.net.NetStream", "attachCamera");
         }
 
-        public function close() {
-            stub_method("flash.net.NetStream", "close");
-        }
+        public native function close();
 
         public function dispose() {
             stub_method("flash.net.NetStream", "dispose");
@@ -83,9 +79,7 @@ package flash.net {
 
         public native function resume();
 
-        public function seek(offset:Number) {
-            stub_method("flash.net.NetStream", "seek");
-        }
+        public native function seek(offset:Number);
 
         public function send(handlerName:String, ...args) {
             stub_method("flash.net.NetStream", "send");
@@ -133,19 +127,11 @@ package flash.net {
             stub_setter("flash.net.NetStream", "backBufferTime");
         }
 
-        public function get bufferLength():Number {
-            stub_getter("flash.net.NetStream", "bufferLength");
-            return 0.0;
-        }
+        public native function get bufferLength():Number;
 
-        public function get bufferTime():Number {
-            stub_getter("flash.net.NetStream", "bufferTime");
-            return 0.0;
-        }
+        public native function get bufferTime():Number;
 
-        public function set bufferTime(time:Number) {
-            stub_setter("flash.net.NetStream", "bufferTime");
-        }
+        public native function set bufferTime(time:Number);
 
         public function get bufferTimeMax():Number {
             stub_getter("flash.net.NetStream", "bufferTimeMax");
@@ -170,12 +156,15 @@ package flash.net {
         }
 
         public function get client():Object {
-            stub_getter("flash.net.NetStream", "client");
-            return {};
+            return this._client;
         }
 
         public function set client(client:Object) {
-            stub_setter("flash.net.NetStream", "client");
+            if (client == null) {
+                throw new TypeError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            this._client = client;
         }
 
         public function get currentFPS():Number {
@@ -316,10 +305,7 @@ package flash.net {
             stub_setter("flash.net.NetStream", "soundTransform");
         };
 
-        public function get time(): Number {
-            stub_getter("flash.net.NetStream", "time");
-            return 0;
-        };
+        public native function get time():Number;
 
         public function get useHardwareDecoder(): Boolean {
             stub_getter("flash.net.NetStream", "useHardwareDecoder");
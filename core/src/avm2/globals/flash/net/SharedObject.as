@@ -5,11 +5,13 @@ package flash.net
 
    namespace ruffle = "__ruffle__";
    
+   [Ruffle(InstanceAllocator)]
    public class SharedObject extends EventDispatcher
    {
       public function SharedObject()
       {
          this.data = {};
+         this.client = this;
       }
 
       // NOTE: We currently always use AMF3 serialization.
@@ -18,11 +20,32 @@ package flash.net
       // to work with AMF0.
 
       native public static function getLocal(name:String, localPath:String = null, secure:Boolean = false): SharedObject;
+      native public static function getRemote(name:String, remotePath:String = null, persistence:Object = false, secure:Boolean = false): SharedObject;
 
       native public function flush(minDiskSpace:int = 0) : String;
       native public function close() : void;
       native public function clear() : void;
 
+      native public function connect(myConnection:NetConnection, params:String = null) : void;
+      native public function setDirty(propertyName:String) : void;
+      native public function set fps(updatesPerSecond:Number) : void;
+
+      public function setProperty(propertyName:String, value:Object = null) : void
+      {
+         this.data[propertyName] = value;
+         this.setDirty(propertyName);
+      }
+
+      public function send(... arguments) : void
+      {
+         this.sendMessage(arguments);
+      }
+
+      native private function sendMessage(arguments:Array) : void;
+
+      // The object that methods called with `send` are invoked on.
+      public var client: Object;
+
       // note: this is supposed to be a read-only property
       public var data: Object;
       
@@ -0,0 +1,214 @@
+package flash.net {
+    import flash.errors.IOError;
+    import flash.events.EventDispatcher;
+    import flash.utils.ByteArray;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+
+    [Ruffle(InstanceAllocator)]
+    public class Socket extends EventDispatcher implements IDataInput, IDataOutput {
+        // Data received from the server, which is appended by the player.
+        private var _input:ByteArray = new ByteArray();
+
+        // Data written to the socket, which is sent when the socket is flushed.
+        private var _output:ByteArray = new ByteArray();
+
+        private var _timeout:uint = 20000;
+
+        public function Socket(host:String = null, port:int = 0) {
+            this.init(this._input, this._output);
+
+            if (port != 0) {
+                this.connect(host, port);
+            }
+        }
+
+        private native function init(input:ByteArray, output:ByteArray):void;
+
+        public function connect(host:String, port:int):void {
+            if (port <= 0 || port > 65535) {
+                throw new SecurityError("Error #2003: Invalid socket port number specified.", 2003);
+            }
+
+            this.connectToHost(host, port, this._timeout);
+        }
+
+        private native function connectToHost(host:String, port:int, timeout:uint):void;
+
+        public native function close():void;
+
+        public native function flush():void;
+
+        public native function get connected():Boolean;
+
+        public function get timeout():uint {
+            return this._timeout;
+        }
+
+        public function set timeout(value:uint):void {
+            this._timeout = value;
+        }
+
+        public function get bytesAvailable():uint {
+            return this._input.bytesAvailable;
+        }
+
+        public function get bytesPending():uint {
+            return this._output.length;
+        }
+
+        public function get endian():String {
+            return this._input.endian;
+        }
+
+        public function set endian(value:String):void {
+            this._input.endian = value;
+            this._output.endian = value;
+        }
+
+        public function get objectEncoding():uint {
+            return this._input.objectEncoding;
+        }
+
+        public function set objectEncoding(value:uint):void {
+            this._input.objectEncoding = value;
+            this._output.objectEncoding = value;
+        }
+
+        private function checkConnected():void {
+            if (!this.connected) {
+                throw new IOError("Error #2002: Operation attempted on invalid socket.", 2002);
+            }
+        }
+
+        public function readBoolean():Boolean {
+            this.checkConnected();
+            return this._input.readBoolean();
+        }
+
+        public function readByte():int {
+            this.checkConnected();
+            return this._input.readByte();
+        }
+
+        public function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkConnected();
+            this._input.readBytes(bytes, offset, length);
+        }
+
+        public function readDouble():Number {
+            this.checkConnected();
+            return this._input.readDouble();
+        }
+
+        public function readFloat():Number {
+            this.checkConnected();
+            return this._input.readFloat();
+        }
+
+        public function readInt():int {
+            this.checkConnected();
+            return this._input.readInt();
+        }
+
+        public function readMultiByte(length:uint, charSet:String):String {
+            this.checkConnected();
+            return this._input.readMultiByte(length, charSet);
+        }
+
+        public function readObject():* {
+            this.checkConnected();
+            return this._input.readObject();
+        }
+
+        public function readShort():int {
+            this.checkConnected();
+            return this._input.readShort();
+        }
+
+        public function readUnsignedByte():uint {
+            this.checkConnected();
+            return this._input.readUnsignedByte();
+        }
+
+        public function readUnsignedInt():uint {
+            this.checkConnected();
+            return this._input.readUnsignedInt();
+        }
+
+        public function readUnsignedShort():uint {
+            this.checkConnected();
+            return this._input.readUnsignedShort();
+        }
+
+        public function readUTF():String {
+            this.checkConnected();
+            return this._input.readUTF();
+        }
+
+        public function readUTFBytes(length:uint):String {
+            this.checkConnected();
+            return this._input.readUTFBytes(length);
+        }
+
+        public function writeBoolean(value:Boolean):void {
+            this.checkConnected();
+            this._output.writeBoolean(value);
+        }
+
+        public function writeByte(value:int):void {
+            this.checkConnected();
+            this._output.writeByte(value);
+        }
+
+        public function writeBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkConnected();
+            this._output.writeBytes(bytes, offset, length);
+        }
+
+        public function writeDouble(value:Number):void {
+            this.checkConnected();
+            this._output.writeDouble(value);
+        }
+
+        public function writeFloat(value:Number):void {
+            this.checkConnected();
+            this._output.writeFloat(value);
+        }
+
+        public function writeInt(value:int):void {
+            this.checkConnected();
+            this._output.writeInt(value);
+        }
+
+        public function writeMultiByte(value:String, charSet:String):void {
+            this.checkConnected();
+            this._output.writeMultiByte(value, charSet);
+        }
+
+        public function writeObject(object:*):void { // NOPMD WronglyNamedVariable
+            this.checkConnected();
+            this._output.writeObject(object);
+        }
+
+        public function writeShort(value:int):void {
+            this.checkConnected();
+            this._output.writeShort(value);
+        }
+
+        public function writeUnsignedInt(value:uint):void {
+            this.checkConnected();
+            this._output.writeUnsignedInt(value);
+        }
+
+        public function writeUTF(value:String):void {
+            this.checkConnected();
+            this._output.writeUTF(value);
+        }
+
+        public function writeUTFBytes(value:String):void {
+            this.checkConnected();
+            this._output.writeUTFBytes(value);
+        }
+    }
+}
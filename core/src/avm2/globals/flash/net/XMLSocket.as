@@ -1,23 +1,38 @@
 package flash.net {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
 
+    [Ruffle(InstanceAllocator)]
     public class XMLSocket extends EventDispatcher {
-        public function XMLSocket(host: String = null, port: int = 0) {
-            stub_constructor("flash.net.XMLSocket");
+        private var _timeout:uint = 20000;
+
+        public function XMLSocket(host:String = null, port:int = 0) {
+            if (port != 0) {
+                this.connect(host, port);
+            }
         }
 
-        public function close(): void {
-            stub_method("flash.net.XMLSocket", "close");
+        public function connect(host:String, port:int):void {
+            if (port <= 0 || port > 65535) {
+                throw new SecurityError("Error #2003: Invalid socket port number specified.", 2003);
+            }
+
+            this.connectToHost(host, port, this._timeout);
         }
 
-        public function connect(host: String, port: int): void {
-            stub_method("flash.net.XMLSocket", "connect");
+        private native function connectToHost(host:String, port:int, timeout:uint):void;
+
+        public native function close():void;
+
+        public native function send(object:*):void; // NOPMD WronglyNamedVariable
+
+        public native function get connected():Boolean;
+
+        public function get timeout():int {
+            return this._timeout;
         }
 
-        public function send(object: *): void { // NOPMD WronglyNamedVariable
-            stub_method("flash.net.XMLSocket", "send");
+        public function set timeout(value:int):void {
+            this._timeout = value;
         }
     }
 }
@@ -0,0 +1,608 @@
+//! `flash.net.FileReference` native function definitions
+
+use crate::avm2::error::{argument_error, illegal_operation_error};
+use crate::avm2::object::{FileReferenceObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::backend::navigator::{NavigationMethod, Request};
+use crate::backend::ui::{FileDialogResult, FileFilter};
+use crate::loader::FileReferenceOperation;
+use crate::string::AvmString;
+use rand::Rng;
+use std::rc::Rc;
+use url::form_urlencoded;
+
+pub use crate::avm2::object::file_reference_allocator;
+
+/// Characters that Flash Player does not allow in the suggested name of a
+/// saved or downloaded file.
+const PROHIBITED_FILE_NAME_CHARACTERS: &[char] =
+    &['/', '\\', ':', '*', '?', '"', '<', '>', '|', '%'];
+
+/// Files larger than this are first tested with an empty upload when
+/// `FileReference.upload` is asked to.
+const TEST_UPLOAD_MIN_SIZE: usize = 10 * 1024;
+
+/// Returns the file chosen for a `FileReference`, or throws if there is none.
+fn chosen_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: FileReferenceObject<'gc>,
+) -> Result<Rc<dyn FileDialogResult>, Error<'gc>> {
+    match this.file() {
+        Some(file) => Ok(file),
+        None => Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #2037: Functions called in incorrect sequence, or earlier call was unsuccessful.",
+            2037,
+        )?)),
+    }
+}
+
+/// Throws if a dialog or transfer is already in progress for the given
+/// `FileReference` or `FileReferenceList`.
+fn check_not_busy<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    is_browse: bool,
+) -> Result<(), Error<'gc>> {
+    if activation
+        .context
+        .load_manager
+        .file_reference_loader(this)
+        .is_none()
+    {
+        return Ok(());
+    }
+
+    Err(Error::AvmError(if is_browse {
+        illegal_operation_error(
+            activation,
+            "Error #2041: Only one file browsing session may be performed at a time.",
+            2041,
+        )?
+    } else {
+        illegal_operation_error(
+            activation,
+            "Error #2174: Only one download, upload, load or save operation can be active at a time on each FileReference.",
+            2174,
+        )?
+    }))
+}
+
+/// Converts an array of `FileFilter` objects into the filters of a file
+/// dialog.
+fn file_filters<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    type_filter: Option<Object<'gc>>,
+) -> Result<Vec<FileFilter>, Error<'gc>> {
+    let Some(type_filter) = type_filter else {
+        return Ok(Vec::new());
+    };
+    let filter_objects: Vec<_> = type_filter
+        .as_array_storage()
+        .map(|array| array.iter().flatten().collect())
+        .unwrap_or_default();
+
+    let mut filters = Vec::with_capacity(filter_objects.len());
+    for filter_object in filter_objects {
+        let filter_object = filter_object.coerce_to_object(activation)?;
+        let description = filter_object
+            .get_public_property("description", activation)?
+            .coerce_to_string(activation)?;
+        let extension = filter_object
+            .get_public_property("extension", activation)?
+            .coerce_to_string(activation)?;
+        let mac_type = filter_object.get_public_property("macType", activation)?;
+
+        let extensions = extension
+            .to_string()
+            .split(';')
+            .map(|pattern| {
+                let pattern = pattern.trim();
+                let extension = pattern.strip_prefix("*.").unwrap_or(pattern);
+                extension.to_string()
+            })
+            .filter(|extension| !extension.is_empty())
+            .collect();
+        let mac_types = match mac_type {
+            Value::Null | Value::Undefined => Vec::new(),
+            mac_type => mac_type
+                .coerce_to_string(activation)?
+                .to_string()
+                .split(';')
+                .map(|mac_type| mac_type.trim().to_string())
+                .filter(|mac_type| !mac_type.is_empty())
+                .collect(),
+        };
+
+        filters.push(FileFilter {
+            description: description.to_string(),
+            extensions,
+            mac_types,
+        });
+    }
+    Ok(filters)
+}
+
+/// Shows a file open dialog for a `FileReference`, or for a
+/// `FileReferenceList` if `multiple` is set.
+pub fn browse_files<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    multiple: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_not_busy(activation, this, true)?;
+
+    let type_filter = args.try_get_object(activation, 0);
+    let filters = file_filters(activation, type_filter)?;
+    let Some(dialog) = activation
+        .context
+        .ui
+        .display_file_open_dialog(filters, multiple)
+    else {
+        return Ok(false.into());
+    };
+
+    let future = activation.context.load_manager.file_reference_operation(
+        activation.context.player.clone(),
+        this,
+        FileReferenceOperation::Browse(dialog),
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(true.into())
+}
+
+/// Validates the suggested name of a saved or downloaded file.
+fn check_file_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    file_name: &str,
+) -> Result<(), Error<'gc>> {
+    if file_name.contains(PROHIBITED_FILE_NAME_CHARACTERS) {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2087: The FileReference.download() file name contains prohibited characters.",
+            2087,
+        )?));
+    }
+    Ok(())
+}
+
+/// Builds a request from a `URLRequest`, sending any `URLVariables` in the
+/// URL or body depending on the method.
+fn url_request<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    request_object: Object<'gc>,
+) -> Result<Request, Error<'gc>> {
+    let mut url = request_object
+        .get_public_property("url", activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    let method = request_object
+        .get_public_property("method", activation)?
+        .coerce_to_string(activation)?;
+    let method = NavigationMethod::from_method_str(&method).unwrap_or(NavigationMethod::Get);
+
+    let data = match request_object.get_public_property("data", activation)? {
+        Value::Null | Value::Undefined => None,
+        data => Some(data.coerce_to_string(activation)?.to_string()),
+    };
+
+    Ok(match (method, data) {
+        (NavigationMethod::Get, Some(data)) => {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(&data);
+            Request::get(url)
+        }
+        (NavigationMethod::Post, Some(data)) => Request::post(
+            url,
+            Some((
+                data.into_bytes(),
+                "application/x-www-form-urlencoded".to_string(),
+            )),
+        ),
+        (method, None) => Request::request(method, url, None),
+    })
+}
+
+/// Implements `FileReference.creationDate`'s getter
+pub fn get_creation_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|o| o.as_file_reference()) {
+        let file = chosen_file(activation, this)?;
+        if let Some(time) = file.creation_time() {
+            let date_class = activation.avm2().classes().date;
+            let date =
+                date_class.construct(activation, &[(time.timestamp_millis() as f64).into()])?;
+            return Ok(date.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `FileReference.creator`'s getter
+pub fn get_creator<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|o| o.as_file_reference()) {
+        // The creator type only exists on classic Mac OS.
+        chosen_file(activation, this)?;
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `FileReference.data`'s getter
+pub fn get_data<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .and_then(|o| o.as_file_reference())
+        .and_then(|this| this.data())
+        .map_or(Value::Null, Value::Object))
+}
+
+/// Implements `FileReference.extension`'s getter
+pub fn get_extension<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|o| o.as_file_reference()) {
+        let name = chosen_file(activation, this)?.file_name();
+        if let Some((_, extension)) = name.rsplit_once('.') {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, extension).into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `FileReference.modificationDate`'s getter
+pub fn get_modification_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|o| o.as_file_reference()) {
+        let file = chosen_file(activation, this)?;
+        if let Some(time) = file.modification_time() {
+            let date_class = activation.avm2().classes().date;
+            let date =
+                date_class.construct(activation, &[(time.timestamp_millis() as f64).into()])?;
+            return Ok(date.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `FileReference.name`'s getter
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|o| o.as_file_reference()) {
+        let name = chosen_file(activation, this)?.file_name();
+        return Ok(AvmString::new_utf8(activation.context.gc_context, name).into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `FileReference.size`'s getter
+pub fn get_size<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|o| o.as_file_reference()) {
+        let size = chosen_file(activation, this)?.size().unwrap_or_default();
+        return Ok((size as f64).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.type`'s getter, which is the file extension
+/// with a leading dot, as reported on Windows.
+pub fn get_type<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|o| o.as_file_reference()) {
+        let name = chosen_file(activation, this)?.file_name();
+        if let Some(index) = name.rfind('.') {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, &name[index..]).into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `FileReference.browse`
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match this {
+        Some(this) => browse_files(activation, this, args, false),
+        None => Ok(false.into()),
+    }
+}
+
+/// Implements `FileReference.cancel`
+pub fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let load_manager = &mut activation.context.load_manager;
+        if let Some(handle) = load_manager.file_reference_loader(this) {
+            load_manager.remove_loader(handle);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.download`
+pub fn download<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this else {
+        return Ok(Value::Undefined);
+    };
+    check_not_busy(activation, this, false)?;
+
+    let request_object = args.get_object(activation, 0, "request")?;
+    let request = url_request(activation, request_object)?;
+    let file_name = match args.try_get_string(activation, 1)? {
+        Some(file_name) => file_name.to_string(),
+        None => {
+            // Suggest the last part of the URL's path.
+            let path = request.url().split(['?', '#']).next().unwrap_or_default();
+            path.rsplit('/').next().unwrap_or_default().to_string()
+        }
+    };
+    check_file_name(activation, &file_name)?;
+
+    let Some(dialog) = activation.context.ui.display_file_save_dialog(file_name) else {
+        tracing::warn!("FileReference.download: File dialogs are not supported");
+        return Ok(Value::Undefined);
+    };
+
+    let future = activation.context.load_manager.file_reference_operation(
+        activation.context.player.clone(),
+        this,
+        FileReferenceOperation::Download(dialog, request),
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.load`
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this else {
+        return Ok(Value::Undefined);
+    };
+    let Some(file_reference) = this.as_file_reference() else {
+        return Ok(Value::Undefined);
+    };
+    chosen_file(activation, file_reference)?;
+    check_not_busy(activation, this, false)?;
+
+    let future = activation.context.load_manager.file_reference_operation(
+        activation.context.player.clone(),
+        this,
+        FileReferenceOperation::Load,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Converts the data passed to `FileReference.save` into the contents of the
+/// file.
+fn save_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    data: Value<'gc>,
+) -> Result<Vec<u8>, Error<'gc>> {
+    let string = match data {
+        Value::Object(object) => {
+            if let Some(bytearray) = object.as_bytearray() {
+                return Ok(bytearray.bytes().to_vec());
+            }
+
+            if object.as_xml_object().is_some() || object.as_xml_list_object().is_some() {
+                object
+                    .call_public_property("toXMLString", &[], activation)?
+                    .coerce_to_string(activation)?
+            } else {
+                data.coerce_to_string(activation)?
+            }
+        }
+        data => data.coerce_to_string(activation)?,
+    };
+
+    Ok(string.to_utf8_lossy().into_owned().into_bytes())
+}
+
+/// Implements `FileReference.save`
+pub fn save<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this else {
+        return Ok(Value::Undefined);
+    };
+    check_not_busy(activation, this, false)?;
+
+    let data = args.get_value(0);
+    if matches!(data, Value::Null | Value::Undefined) {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2007: Parameter data must be non-null.",
+            2007,
+        )?));
+    }
+    let data = save_data(activation, data)?;
+
+    let file_name = args
+        .try_get_string(activation, 1)?
+        .map(|file_name| file_name.to_string())
+        .unwrap_or_default();
+    check_file_name(activation, &file_name)?;
+
+    let Some(dialog) = activation.context.ui.display_file_save_dialog(file_name) else {
+        tracing::warn!("FileReference.save: File dialogs are not supported");
+        return Ok(Value::Undefined);
+    };
+
+    let future = activation.context.load_manager.file_reference_operation(
+        activation.context.player.clone(),
+        this,
+        FileReferenceOperation::Save(dialog, data),
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.upload`, which sends the file in a
+/// `multipart/form-data` POST request like an HTML form would.
+pub fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this else {
+        return Ok(Value::Undefined);
+    };
+    let Some(file_reference) = this.as_file_reference() else {
+        return Ok(Value::Undefined);
+    };
+    let file = chosen_file(activation, file_reference)?;
+    check_not_busy(activation, this, false)?;
+
+    let request_object = args.get_object(activation, 0, "request")?;
+    let field_name = args.get_string(activation, 1)?.to_string();
+    let test_upload = args.get_bool(2);
+
+    let url = request_object
+        .get_public_property("url", activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    let variables = match request_object.get_public_property("data", activation)? {
+        Value::Null | Value::Undefined => String::new(),
+        data => data.coerce_to_string(activation)?.to_string(),
+    };
+
+    let file_name = file.file_name();
+    let contents = file.contents().unwrap_or_default();
+    let size = contents.len();
+
+    let boundary = format!("----------{:016x}", activation.context.rng.gen::<u64>());
+    let mut body = Vec::with_capacity(size + 512);
+    let mut add_field = |name: &str, file_name: Option<&str>, value: &[u8]| {
+        body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+        match file_name {
+            Some(file_name) => body.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{name}\"; filename=\"{file_name}\"\r\n\
+                     Content-Type: application/octet-stream\r\n\r\n"
+                )
+                .as_bytes(),
+            ),
+            None => body.extend_from_slice(
+                format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n").as_bytes(),
+            ),
+        }
+        body.extend_from_slice(value);
+        body.extend_from_slice(b"\r\n");
+    };
+    add_field("Filename", None, file_name.as_bytes());
+    for (name, value) in form_urlencoded::parse(variables.as_bytes()) {
+        add_field(&name, None, value.as_bytes());
+    }
+    add_field(&field_name, Some(&file_name), &contents);
+    add_field("Upload", None, b"Submit Query");
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+
+    // The test upload checks that the server accepts the upload, without
+    // sending the whole file.
+    let content_type = format!("multipart/form-data; boundary={boundary}");
+    let test_request = (test_upload && size > TEST_UPLOAD_MIN_SIZE)
+        .then(|| Request::post(url.clone(), Some((Vec::new(), content_type.clone()))));
+    let request = Request::post(url, Some((body, content_type)));
+    let future = activation.context.load_manager.file_reference_operation(
+        activation.context.player.clone(),
+        this,
+        FileReferenceOperation::Upload {
+            request,
+            test_request,
+            size,
+        },
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.uploadUnencoded`, which sends the file as the
+/// body of a POST request.
+pub fn upload_unencoded<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this else {
+        return Ok(Value::Undefined);
+    };
+    let Some(file_reference) = this.as_file_reference() else {
+        return Ok(Value::Undefined);
+    };
+    let file = chosen_file(activation, file_reference)?;
+    check_not_busy(activation, this, false)?;
+
+    let request_object = args.get_object(activation, 0, "request")?;
+    let url = request_object
+        .get_public_property("url", activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    let content_type = request_object
+        .get_public_property("contentType", activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+
+    let contents = file.contents().unwrap_or_default();
+    let size = contents.len();
+    let request = Request::post(url, Some((contents, content_type)));
+    let future = activation.context.load_manager.file_reference_operation(
+        activation.context.player.clone(),
+        this,
+        FileReferenceOperation::Upload {
+            request,
+            test_request: None,
+            size,
+        },
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,16 @@
+//! `flash.net.FileReferenceList` native function definitions
+
+use crate::avm2::globals::flash::net::file_reference::browse_files;
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `FileReferenceList.browse`
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match this {
+        Some(this) => browse_files(activation, this, args, true),
+        None => Ok(false.into()),
+    }
+}
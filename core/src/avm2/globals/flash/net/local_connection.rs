@@ -1,5 +1,12 @@
-use crate::avm2::{Activation, Error, Object, Value};
+use crate::avm2::amf::serialize_value;
+use crate::avm2::error::argument_error;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::local_connection::{is_reserved_method, movie_domain};
 use crate::string::AvmString;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+
+pub use crate::avm2::object::local_connection_allocator;
 
 /// Implements `domain` getter
 pub fn get_domain<'gc>(
@@ -9,19 +16,136 @@ pub fn get_domain<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let movie = activation.context.swf;
 
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
+    let Some(domain) = movie_domain(movie.url()) else {
         tracing::error!("LocalConnection::domain: Unable to parse movie URL");
         return Ok(Value::Null);
     };
 
-    Ok(Value::String(domain))
+    Ok(AvmString::new_utf8(activation.context.gc_context, domain).into())
+}
+
+/// Implements the private `connectToName` method, which is called by
+/// `connect` once the name is validated.
+pub fn connect_to_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(connection) = this.and_then(|o| o.as_local_connection()) {
+        let name = args.get_string(activation, 0)?;
+        let movie = activation.caller_movie();
+
+        if !connection.connection().connect(
+            &mut activation.context,
+            movie.url(),
+            &name.to_utf8_lossy(),
+        ) {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2082: Connect failed because the object is already connected.",
+                2082,
+            )?));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(connection) = this.and_then(|o| o.as_local_connection()) {
+        if !connection.connection().close(&mut activation.context) {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2083: Close failed because the object is not connected.",
+                2083,
+            )?));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `sendMessage` method, which is called by `send`
+/// with the message arguments in an array.
+pub fn send_message<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(connection) = this.and_then(|o| o.as_local_connection()) {
+        let connection_name = args.get_string(activation, 0)?;
+        let method_name = args.get_string(activation, 1)?.to_string();
+        if is_reserved_method(&method_name) {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2004: One of the parameters is invalid.",
+                2004,
+            )?));
+        }
+
+        let values: Vec<_> = args
+            .get_object(activation, 2, "arguments")?
+            .as_array_storage()
+            .map(|array| {
+                array
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let arguments = values
+            .into_iter()
+            .map(|value| {
+                serialize_value(activation, value, AMFVersion::AMF0).unwrap_or(AmfValue::Undefined)
+            })
+            .collect();
+
+        let movie = activation.caller_movie();
+        connection.connection().send(
+            &mut activation.context,
+            movie.url(),
+            &connection_name.to_utf8_lossy(),
+            method_name,
+            arguments,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.allowDomain`, which lets movies from other
+/// domains send messages to this connection.
+pub fn allow_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(connection) = this.and_then(|o| o.as_local_connection()) {
+        let mut domains = Vec::with_capacity(args.len());
+        for domain in args {
+            domains.push(domain.coerce_to_string(activation)?.to_string());
+        }
+
+        connection
+            .connection()
+            .allow_domains(activation.context.gc_context, &domains);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.allowInsecureDomain`.
+///
+/// Messages are not told apart by protocol, so this is the same as
+/// `allowDomain`.
+pub fn allow_insecure_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    allow_domain(activation, this, args)
 }
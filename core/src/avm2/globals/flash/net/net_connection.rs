@@ -0,0 +1,153 @@
+use crate::avm2::amf::serialize_value;
+use crate::avm2::error::error;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::string::AvmString;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+use std::rc::Rc;
+
+pub use crate::avm2::object::netconnection_allocator as net_connection_allocator;
+
+/// Serialize the arguments of a `connect` or `call`, or the value of a
+/// header, to send to a server.
+///
+/// With AMF3 encoding, each argument is wrapped in an AMF0 "switch to AMF3"
+/// marker, as Flash Player does.
+pub fn serialize_arguments<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    arguments: &[Value<'gc>],
+    amf_version: AMFVersion,
+) -> Vec<AmfValue> {
+    arguments
+        .iter()
+        .map(|argument| {
+            let value =
+                serialize_value(activation, *argument, amf_version).unwrap_or(AmfValue::Undefined);
+            match amf_version {
+                AMFVersion::AMF0 => value,
+                AMFVersion::AMF3 => AmfValue::AMF3(Rc::new(value)),
+            }
+        })
+        .collect()
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this else {
+        return Ok(Value::Undefined);
+    };
+    if let Some(connection) = this.as_net_connection() {
+        let uri = args.try_get_string(activation, 0)?;
+        let object_encoding = match this
+            .get_public_property("objectEncoding", activation)?
+            .coerce_to_u32(activation)?
+        {
+            0 => AMFVersion::AMF0,
+            _ => AMFVersion::AMF3,
+        };
+
+        // The arguments of `connect` itself are always sent as AMF0.
+        let arguments = serialize_arguments(activation, &args[1..], AMFVersion::AMF0);
+        connection.connect(
+            &mut activation.context,
+            uri.map(|uri| uri.to_string()),
+            object_encoding,
+            arguments,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(connection) = this.and_then(|o| o.as_net_connection()) {
+        let command = args.get_string(activation, 0)?;
+        let responder = args.try_get_object(activation, 1).map(|o| o.into());
+        let arguments = serialize_arguments(activation, &args[2..], connection.object_encoding());
+
+        if !connection.call(
+            &mut activation.context,
+            command.to_string(),
+            responder,
+            arguments,
+        ) {
+            return Err(Error::AvmError(error(
+                activation,
+                "Error #2126: NetConnection object must be connected.",
+                2126,
+            )?));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn add_header<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(connection) = this.and_then(|o| o.as_net_connection()) {
+        let operation = args.get_string(activation, 0)?;
+        let must_understand = args.get_bool(1);
+        let param = args.get(2).copied().unwrap_or(Value::Null);
+        let value = serialize_arguments(activation, &[param], connection.object_encoding())
+            .pop()
+            .unwrap_or(AmfValue::Undefined);
+
+        connection.add_header(
+            activation.context.gc_context,
+            operation.to_string(),
+            must_understand,
+            value,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(connection) = this.and_then(|o| o.as_net_connection()) {
+        connection.close(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_connected<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(connection) = this.and_then(|o| o.as_net_connection()) {
+        return Ok(connection.is_connected().into());
+    }
+
+    Ok(false.into())
+}
+
+pub fn get_uri<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(uri) = this
+        .and_then(|o| o.as_net_connection())
+        .and_then(|connection| connection.uri())
+    {
+        return Ok(AvmString::new_utf8(activation.context.gc_context, uri).into());
+    }
+
+    Ok(Value::Null)
+}
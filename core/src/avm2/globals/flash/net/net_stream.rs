@@ -1,7 +1,54 @@
+use crate::avm2::error::argument_error;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::streams::AppendBytesAction;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|o| o.as_netstream()) {
+        let bytes = args.get_object(activation, 0, "bytes")?;
+        if let Some(bytearray) = bytes.as_bytearray() {
+            let data = bytearray.bytes().to_vec();
+            drop(bytearray);
+            ns.append_bytes(&mut activation.context, &data);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|o| o.as_netstream()) {
+        let action = args.get_string(activation, 0)?;
+        let action = if &action == b"resetBegin" {
+            AppendBytesAction::ResetBegin
+        } else if &action == b"resetSeek" {
+            AppendBytesAction::ResetSeek
+        } else if &action == b"endSequence" {
+            AppendBytesAction::EndSequence
+        } else {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2008: Parameter action must be one of the accepted values.",
+                2008,
+            )?));
+        };
+
+        ns.append_bytes_action(&mut activation.context, action);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bytes_loaded<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
@@ -80,3 +127,87 @@ pub fn toggle_pause<'gc>(
 
     Ok(Value::Undefined)
 }
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|o| o.as_netstream()) {
+        ns.close(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|o| o.as_netstream()) {
+        let offset = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        ns.seek(&mut activation.context, offset);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|o| o.as_netstream()) {
+        return Ok(ns.buffer_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|o| o.as_netstream()) {
+        return Ok(ns.buffer_time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|o| o.as_netstream()) {
+        let buffer_time = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        ns.set_buffer_time(activation.context.gc_context, buffer_time);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|o| o.as_netstream()) {
+        return Ok(ns.time().into());
+    }
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,26 @@
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+
+pub use crate::avm2::object::responder_allocator;
+
+/// Implements `Responder`'s private `init` method, which is called from the
+/// constructor.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(responder) = this.and_then(|o| o.as_responder()) {
+        let function = |value: Option<&Value<'gc>>| {
+            value
+                .and_then(|value| value.as_object())
+                .and_then(|object| object.as_function_object())
+        };
+        responder.set_callbacks(
+            activation.context.gc_context,
+            function(args.get(0)),
+            function(args.get(1)),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
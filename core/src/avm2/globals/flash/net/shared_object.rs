@@ -1,15 +1,21 @@
 //! `flash.net.SharedObject` builtin/prototype
 
+use crate::avm2::error::error;
+use crate::avm2::globals::flash::net::net_connection::serialize_arguments;
 use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Namespace, Object, Value};
 use crate::avm2_stub_method;
 use crate::display_object::DisplayObject;
 use crate::display_object::TDisplayObject;
+use crate::remote_shared_object::RemoteSharedObject;
 use crate::string::AvmString;
-use flash_lso::types::{AMFVersion, Lso};
+use flash_lso::types::{AMFVersion, Lso, Value as AmfValue};
 use std::borrow::Cow;
 
+pub use crate::avm2::object::shared_object_allocator;
+
 pub fn get_local<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
@@ -175,12 +181,170 @@ pub fn get_local<'gc>(
     Ok(this.into())
 }
 
+pub fn get_remote<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_string();
+
+    const INVALID_CHARS: &str = "~%&\\;:\"',<>?# ";
+    if name.is_empty() || name.contains(|c| INVALID_CHARS.contains(c)) {
+        tracing::error!("SharedObject::get_remote: Invalid name");
+        return Ok(Value::Null);
+    }
+
+    let Some(uri) = args.try_get_string(activation, 1)? else {
+        return Ok(Value::Null);
+    };
+
+    // A local path also asks for a copy of the data to be kept on disk,
+    // which isn't supported, but makes the object persistent on the server.
+    let persistent = match args.get_value(2) {
+        Value::String(local_path) => !local_path.is_empty(),
+        value => value.coerce_to_boolean(),
+    };
+
+    // Check if this is referencing an existing shared object
+    let uri = uri.to_string();
+    let key = RemoteSharedObject::cache_key(&name, &uri, persistent);
+    if let Some(so) = activation.context.avm2_shared_objects.get(&key) {
+        return Ok((*so).into());
+    }
+
+    let sharedobject_cls = this.unwrap(); // `this` of a static method is the class
+    let this = sharedobject_cls.construct(activation, &[])?;
+    if let Some(shared_object) = this.as_shared_object() {
+        let remote = RemoteSharedObject::new(
+            activation.context.gc_context,
+            name,
+            uri,
+            persistent,
+            Some(this.into()),
+        );
+        shared_object.set_remote(activation.context.gc_context, remote);
+    }
+
+    activation.context.avm2_shared_objects.insert(key, this);
+
+    Ok(this.into())
+}
+
+/// Implements `SharedObject.connect`, which starts syncing a remote shared
+/// object over a `NetConnection`.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(remote) = this
+        .and_then(|o| o.as_shared_object())
+        .and_then(|so| so.remote())
+    else {
+        return Ok(Value::Undefined);
+    };
+    let connection = args
+        .try_get_object(activation, 0)
+        .and_then(|connection| connection.as_net_connection());
+
+    let connected = match connection {
+        Some(connection) => remote.connect(&mut activation.context, connection),
+        None => false,
+    };
+    if !connected {
+        return Err(Error::AvmError(error(
+            activation,
+            "Error #2126: NetConnection object must be connected.",
+            2126,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_dirty<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(remote) = this
+        .and_then(|o| o.as_shared_object())
+        .and_then(|so| so.remote())
+    {
+        let name = args.get_string(activation, 0)?;
+        remote.set_dirty(activation.context.gc_context, &name.to_utf8_lossy());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `fps` setter
+pub fn set_fps<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(remote) = this
+        .and_then(|o| o.as_shared_object())
+        .and_then(|so| so.remote())
+    {
+        let fps = args.get_f64(activation, 0)?;
+        remote.set_fps(&mut activation.context, fps);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `sendMessage` method, which is called by `send`
+/// with the handler name and its arguments in an array.
+pub fn send_message<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(remote) = this
+        .and_then(|o| o.as_shared_object())
+        .and_then(|so| so.remote())
+    else {
+        return Ok(Value::Undefined);
+    };
+    let values: Vec<_> = args
+        .get_object(activation, 0, "arguments")?
+        .as_array_storage()
+        .map(|array| {
+            array
+                .iter()
+                .map(|v| v.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default();
+    let Some((handler_name, values)) = values.split_first() else {
+        return Ok(Value::Undefined);
+    };
+
+    // The handler name is always sent as AMF0, like the name of a command.
+    let handler_name = handler_name.coerce_to_string(activation)?;
+    let mut arguments = vec![AmfValue::String(handler_name.to_string())];
+    let object_encoding = remote
+        .connection()
+        .map_or(AMFVersion::AMF3, |connection| connection.object_encoding());
+    arguments.extend(serialize_arguments(activation, values, object_encoding));
+    remote.send(&mut activation.context, arguments);
+
+    Ok(Value::Undefined)
+}
+
 pub fn flush<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
+        if this.as_shared_object().and_then(|so| so.remote()).is_some() {
+            // Remote shared objects are never kept on disk.
+            return Ok(Value::Undefined);
+        }
+
         let data = this
             .get_public_property("data", activation)?
             .coerce_to_object(activation)?;
@@ -214,10 +378,17 @@ pub fn flush<'gc>(
 
 pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.net.SharedObject", "close");
+    if let Some(remote) = this
+        .and_then(|o| o.as_shared_object())
+        .and_then(|so| so.remote())
+    {
+        remote.close(&mut activation.context);
+    } else {
+        avm2_stub_method!(activation, "flash.net.SharedObject", "close");
+    }
     Ok(Value::Undefined)
 }
 
@@ -0,0 +1,122 @@
+use crate::avm2::error::{io_error, security_error};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use std::time::Duration;
+
+pub use crate::avm2::object::socket_allocator;
+
+/// Implements `Socket`'s private `init` method, which is called from the
+/// constructor.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(socket) = this.and_then(|o| o.as_socket()) {
+        socket.set_buffers(
+            activation.context.gc_context,
+            args.try_get_object(activation, 0),
+            args.try_get_object(activation, 1),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `connectToHost` method of both `Socket` and
+/// `XMLSocket`, which is called by `connect` once the port is validated.
+pub fn connect_to_host<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(socket) = this.and_then(|o| o.as_socket()) {
+        let host = args.try_get_string(activation, 0)?;
+        let port = args.get_u32(activation, 1)?;
+        let timeout = args.get_u32(activation, 2)?;
+        let movie = activation.caller_movie();
+
+        if !socket.socket().connect(
+            &mut activation.context,
+            movie.url(),
+            host.map(|host| host.to_string()),
+            port as u16,
+            Duration::from_millis(timeout.into()),
+        ) {
+            return Err(Error::AvmError(security_error(
+                activation,
+                "Error #2010: Local-with-filesystem SWF files are not permitted to use sockets.",
+                2010,
+            )?));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_connected<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(socket) = this.and_then(|o| o.as_socket()) {
+        return Ok(socket.socket().is_connected().into());
+    }
+
+    Ok(false.into())
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(socket) = this.and_then(|o| o.as_socket()) {
+        if !socket.socket().close(&mut activation.context) {
+            return Err(invalid_socket_error(activation));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.flush`, which sends everything that was written since
+/// the last flush.
+pub fn flush<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(socket) = this.and_then(|o| o.as_socket()) {
+        if !socket.socket().is_connected() {
+            return Err(invalid_socket_error(activation));
+        }
+
+        if let Some(output) = socket.output() {
+            let data = match output.as_bytearray_mut(activation.context.gc_context) {
+                Some(mut bytes) => {
+                    let data = bytes.bytes().to_vec();
+                    bytes.clear();
+                    data
+                }
+                None => return Ok(Value::Undefined),
+            };
+            if !data.is_empty() {
+                socket.socket().send(data);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub(super) fn invalid_socket_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match io_error(
+        activation,
+        "Error #2002: Operation attempted on invalid socket.",
+        2002,
+    ) {
+        Ok(error) => Error::AvmError(error),
+        Err(error) => error,
+    }
+}
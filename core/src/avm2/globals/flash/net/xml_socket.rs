@@ -0,0 +1,41 @@
+use crate::avm2::globals::flash::net::socket::invalid_socket_error;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+
+pub use crate::avm2::globals::flash::net::socket::{connect_to_host, get_connected};
+pub use crate::avm2::object::xml_socket_allocator;
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(socket) = this.and_then(|o| o.as_socket()) {
+        socket.socket().close(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.send`, which sends the object as a string.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(socket) = this.and_then(|o| o.as_socket()) {
+        let data = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if !socket
+            .socket()
+            .send(data.to_utf8_lossy().into_owned().into_bytes())
+        {
+            return Err(invalid_socket_error(activation));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
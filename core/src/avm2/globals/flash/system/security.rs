@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
@@ -19,27 +20,48 @@ pub fn get_sandbox_type<'gc>(
 pub fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowDomain");
-    Ok(Value::Undefined)
+    allow_domains(activation, args, false)
 }
 
 pub fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowInsecureDomain");
+    allow_domains(activation, args, true)
+}
+
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    insecure: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut domains = Vec::with_capacity(args.len());
+    for domain in args {
+        domains.push(domain.coerce_to_string(activation)?.to_string());
+    }
+
+    let movie = activation.caller_movie();
+    activation
+        .context
+        .security
+        .allow_domains(movie.url(), &domains, insecure);
     Ok(Value::Undefined)
 }
 
 pub fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "loadPolicyFile");
+    let url = args.get_string(activation, 0)?;
+    activation.context.security.load_policy_file(
+        activation.context.player.clone(),
+        activation.context.navigator,
+        &url.to_string(),
+    );
     Ok(Value::Undefined)
 }
 
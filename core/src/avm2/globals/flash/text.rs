@@ -1,5 +1,6 @@
 //! `flash.text` namespace
 
+pub mod engine;
 pub mod font;
 pub mod static_text;
 pub mod text_field;
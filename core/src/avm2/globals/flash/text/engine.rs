@@ -0,0 +1,6 @@
+//! `flash.text.engine` namespace
+
+pub mod element_format;
+pub mod font_description;
+pub mod text_block;
+pub mod text_line;
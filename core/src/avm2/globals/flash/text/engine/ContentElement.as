@@ -0,0 +1,104 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+
+    public class ContentElement {
+        public static const GRAPHIC_ELEMENT:uint = 0xFDEF;
+
+        internal var _textBlock:TextBlock;
+        internal var _groupElement:GroupElement;
+
+        private var _elementFormat:ElementFormat;
+        private var _eventMirror:EventDispatcher;
+        private var _textRotation:String;
+
+        public var userData:*;
+
+        public function ContentElement(elementFormat:ElementFormat = null, eventMirror:EventDispatcher = null, textRotation:String = "rotate0") {
+            this._elementFormat = elementFormat;
+            this._eventMirror = eventMirror;
+            this.textRotation = textRotation;
+        }
+
+        public function get elementFormat():ElementFormat {
+            return this._elementFormat;
+        }
+
+        public function set elementFormat(value:ElementFormat):void {
+            this._elementFormat = value;
+            this.invalidateLines();
+        }
+
+        public function get eventMirror():EventDispatcher {
+            return this._eventMirror;
+        }
+
+        public function set eventMirror(value:EventDispatcher):void {
+            this._eventMirror = value;
+        }
+
+        public function get textRotation():String {
+            return this._textRotation;
+        }
+
+        public function set textRotation(value:String):void {
+            if (value != TextRotation.ROTATE_0 && value != TextRotation.ROTATE_90 && value != TextRotation.ROTATE_180
+                    && value != TextRotation.ROTATE_270 && value != TextRotation.AUTO) {
+                throw new ArgumentError("Error #2008: Parameter textRotation must be one of the accepted values.", 2008);
+            }
+            this._textRotation = value;
+            this.invalidateLines();
+        }
+
+        public function get groupElement():GroupElement {
+            return this._groupElement;
+        }
+
+        public function get textBlock():TextBlock {
+            var element:ContentElement = this;
+            while (element._groupElement != null) {
+                element = element._groupElement;
+            }
+            return element._textBlock;
+        }
+
+        public function get rawText():String {
+            return null;
+        }
+
+        public function get text():String {
+            var rawText:String = this.rawText;
+            if (rawText == null) {
+                return null;
+            }
+            return rawText.split(String.fromCharCode(GRAPHIC_ELEMENT)).join("");
+        }
+
+        public function get textBlockBeginIndex():int {
+            var group:GroupElement = this._groupElement;
+            if (group == null) {
+                return 0;
+            }
+
+            var index:int = group.textBlockBeginIndex;
+            for each (var sibling:ContentElement in group._elements) {
+                if (sibling === this) {
+                    break;
+                }
+                var siblingText:String = sibling.rawText;
+                if (siblingText != null) {
+                    index += siblingText.length;
+                }
+            }
+            return index;
+        }
+
+        // Marks every line of the text block containing this element as
+        // needing to be recreated.
+        internal function invalidateLines():void {
+            var block:TextBlock = this.textBlock;
+            if (block != null) {
+                block.invalidateLines();
+            }
+        }
+    }
+}
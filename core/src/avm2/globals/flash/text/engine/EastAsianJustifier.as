@@ -0,0 +1,30 @@
+package flash.text.engine {
+    public final class EastAsianJustifier extends TextJustifier {
+        private var _justificationStyle:String;
+
+        public var composeTrailingIdeographicSpaces:Boolean = false;
+
+        public function EastAsianJustifier(locale:String = "ja", lineJustification:String = "allButLast", justificationStyle:String = "pushInKinsoku") {
+            super(locale, lineJustification);
+            this.justificationStyle = justificationStyle;
+        }
+
+        public function get justificationStyle():String {
+            return this._justificationStyle;
+        }
+
+        public function set justificationStyle(value:String):void {
+            if (value != JustificationStyle.PUSH_IN_KINSOKU && value != JustificationStyle.PUSH_OUT_ONLY
+                    && value != JustificationStyle.PRIORITIZE_LEAST_ADJUSTMENT) {
+                throw new ArgumentError("Error #2008: Parameter justificationStyle must be one of the accepted values.", 2008);
+            }
+            this._justificationStyle = value;
+        }
+
+        override public function clone():TextJustifier {
+            var justifier:EastAsianJustifier = new EastAsianJustifier(this.locale, this.lineJustification, this._justificationStyle);
+            justifier.composeTrailingIdeographicSpaces = this.composeTrailingIdeographicSpaces;
+            return justifier;
+        }
+    }
+}
@@ -0,0 +1,147 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+
+    public final class GroupElement extends ContentElement {
+        internal var _elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+
+        public function GroupElement(elements:Vector.<ContentElement> = null, elementFormat:ElementFormat = null, eventMirror:EventDispatcher = null, textRotation:String = "rotate0") {
+            super(elementFormat, eventMirror, textRotation);
+            this.setElements(elements);
+        }
+
+        override public function get rawText():String {
+            var rawText:String = "";
+            for each (var element:ContentElement in this._elements) {
+                var elementText:String = element.rawText;
+                if (elementText != null) {
+                    rawText += elementText;
+                }
+            }
+            return rawText;
+        }
+
+        public function get elementCount():int {
+            return this._elements.length;
+        }
+
+        public function getElementAt(index:int):ContentElement {
+            if (index < 0 || index >= this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return this._elements[index];
+        }
+
+        public function getElementIndex(element:ContentElement):int {
+            return this._elements.indexOf(element);
+        }
+
+        public function getElementAtCharIndex(charIndex:int):ContentElement {
+            if (charIndex < 0) {
+                return null;
+            }
+            var index:int = 0;
+            for each (var element:ContentElement in this._elements) {
+                var elementText:String = element.rawText;
+                var length:int = elementText != null ? elementText.length : 0;
+                if (charIndex < index + length) {
+                    return element;
+                }
+                index += length;
+            }
+            return null;
+        }
+
+        public function setElements(value:Vector.<ContentElement>):void {
+            this.replaceElements(0, this._elements.length, value);
+        }
+
+        public function replaceElements(beginIndex:int, endIndex:int, newElements:Vector.<ContentElement>):Vector.<ContentElement> {
+            if (beginIndex < 0 || endIndex < beginIndex || endIndex > this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            if (newElements != null) {
+                for each (var element:ContentElement in newElements) {
+                    if (element._textBlock != null || (element._groupElement != null && element._groupElement !== this)) {
+                        throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+                    }
+                }
+            }
+
+            var removed:Vector.<ContentElement> = this._elements.splice(beginIndex, endIndex - beginIndex);
+            for each (var old:ContentElement in removed) {
+                old._groupElement = null;
+            }
+            if (newElements != null) {
+                for (var i:int = 0; i < newElements.length; i++) {
+                    newElements[i]._groupElement = this;
+                    this._elements.splice(beginIndex + i, 0, newElements[i]);
+                }
+            }
+
+            this.invalidateLines();
+            return removed;
+        }
+
+        public function groupElements(beginIndex:int, endIndex:int):GroupElement {
+            if (beginIndex < 0 || endIndex <= beginIndex || endIndex > this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var grouped:Vector.<ContentElement> = this.replaceElements(beginIndex, endIndex, null);
+            var group:GroupElement = new GroupElement(grouped);
+            var groups:Vector.<ContentElement> = new Vector.<ContentElement>();
+            groups.push(group);
+            this.replaceElements(beginIndex, beginIndex, groups);
+            return group;
+        }
+
+        public function ungroupElements(groupIndex:int):void {
+            var group:GroupElement = this.getElementAt(groupIndex) as GroupElement;
+            if (group == null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            var elements:Vector.<ContentElement> = group.replaceElements(0, group.elementCount, null);
+            this.replaceElements(groupIndex, groupIndex + 1, elements);
+        }
+
+        public function mergeTextElements(beginIndex:int, endIndex:int):TextElement {
+            if (beginIndex < 0 || endIndex <= beginIndex || endIndex > this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var first:TextElement = this._elements[beginIndex] as TextElement;
+            if (first == null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            var text:String = first.rawText != null ? first.rawText : "";
+            for (var i:int = beginIndex + 1; i < endIndex; i++) {
+                var element:TextElement = this._elements[i] as TextElement;
+                if (element == null) {
+                    throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+                }
+                if (element.rawText != null) {
+                    text += element.rawText;
+                }
+            }
+            this.replaceElements(beginIndex + 1, endIndex, null);
+            first.text = text;
+            return first;
+        }
+
+        public function splitTextElement(elementIndex:int, splitIndex:int):TextElement {
+            var element:TextElement = this.getElementAt(elementIndex) as TextElement;
+            if (element == null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            var text:String = element.rawText != null ? element.rawText : "";
+            if (splitIndex < 0 || splitIndex > text.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var format:ElementFormat = element.elementFormat;
+            var split:TextElement = new TextElement(text.substring(splitIndex), format != null ? format.clone() : null, element.eventMirror, element.textRotation);
+            element.text = text.substring(0, splitIndex);
+            var splits:Vector.<ContentElement> = new Vector.<ContentElement>();
+            splits.push(split);
+            this.replaceElements(elementIndex + 1, elementIndex + 1, splits);
+            return split;
+        }
+    }
+}
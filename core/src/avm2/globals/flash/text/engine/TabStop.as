@@ -0,0 +1,43 @@
+package flash.text.engine {
+    public final class TabStop {
+        private var _alignment:String;
+        private var _position:Number;
+        private var _decimalAlignmentToken:String;
+
+        public function TabStop(alignment:String = "start", position:Number = 0.0, decimalAlignmentToken:String = "") {
+            this.alignment = alignment;
+            this.position = position;
+            this.decimalAlignmentToken = decimalAlignmentToken;
+        }
+
+        public function get alignment():String {
+            return this._alignment;
+        }
+
+        public function set alignment(value:String):void {
+            if (value != TabAlignment.START && value != TabAlignment.CENTER && value != TabAlignment.END && value != TabAlignment.DECIMAL) {
+                throw new ArgumentError("Error #2008: Parameter alignment must be one of the accepted values.", 2008);
+            }
+            this._alignment = value;
+        }
+
+        public function get position():Number {
+            return this._position;
+        }
+
+        public function set position(value:Number):void {
+            if (value < 0 || value > TextLine.MAX_LINE_WIDTH) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._position = value;
+        }
+
+        public function get decimalAlignmentToken():String {
+            return this._decimalAlignmentToken;
+        }
+
+        public function set decimalAlignmentToken(value:String):void {
+            this._decimalAlignmentToken = value;
+        }
+    }
+}
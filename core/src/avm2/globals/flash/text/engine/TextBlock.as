@@ -0,0 +1,346 @@
+package flash.text.engine {
+    import flash.display.DisplayObject;
+    import flash.geom.Rectangle;
+
+    public final class TextBlock {
+        private var _content:ContentElement;
+        private var _tabStops:Vector.<TabStop>;
+        private var _textJustifier:TextJustifier;
+        private var _lineRotation:String;
+        private var _baselineZero:String;
+        private var _bidiLevel:int;
+        private var _applyNonLinearFontScaling:Boolean;
+        private var _baselineFontDescription:FontDescription;
+        private var _baselineFontSize:Number;
+        private var _firstLine:TextLine;
+        private var _lastLine:TextLine;
+
+        internal var _textLineCreationResult:String = null;
+
+        public var userData:*;
+
+        public function TextBlock(content:ContentElement = null, tabStops:Vector.<TabStop> = null, textJustifier:TextJustifier = null,
+                                  lineRotation:String = "rotate0", baselineZero:String = "roman", bidiLevel:int = 0,
+                                  applyNonLinearFontScaling:Boolean = true, baselineFontDescription:FontDescription = null,
+                                  baselineFontSize:Number = 12.0) {
+            if (content != null) {
+                this.content = content;
+            }
+            this.tabStops = tabStops;
+            this.textJustifier = textJustifier != null ? textJustifier : TextJustifier.getJustifierForLocale("en");
+            this.lineRotation = lineRotation;
+            this.baselineZero = baselineZero;
+            this.bidiLevel = bidiLevel;
+            this.applyNonLinearFontScaling = applyNonLinearFontScaling;
+            this.baselineFontDescription = baselineFontDescription;
+            this.baselineFontSize = baselineFontSize;
+        }
+
+        public function get content():ContentElement {
+            return this._content;
+        }
+
+        public function set content(value:ContentElement):void {
+            if (value != null && (value._groupElement != null || (value._textBlock != null && value._textBlock !== this))) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (this._content != null) {
+                this._content._textBlock = null;
+            }
+            this._content = value;
+            if (value != null) {
+                value._textBlock = this;
+            }
+            this.invalidateLines();
+        }
+
+        public function get tabStops():Vector.<TabStop> {
+            return this._tabStops;
+        }
+
+        public function set tabStops(value:Vector.<TabStop>):void {
+            this._tabStops = value;
+            this.invalidateLines();
+        }
+
+        public function get textJustifier():TextJustifier {
+            return this._textJustifier;
+        }
+
+        public function set textJustifier(value:TextJustifier):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter textJustifier must be non-null.", 2007);
+            }
+            this._textJustifier = value;
+            this.invalidateLines();
+        }
+
+        public function get lineRotation():String {
+            return this._lineRotation;
+        }
+
+        public function set lineRotation(value:String):void {
+            if (value != TextRotation.ROTATE_0 && value != TextRotation.ROTATE_90 && value != TextRotation.ROTATE_180
+                    && value != TextRotation.ROTATE_270) {
+                throw new ArgumentError("Error #2008: Parameter lineRotation must be one of the accepted values.", 2008);
+            }
+            this._lineRotation = value;
+            this.invalidateLines();
+        }
+
+        public function get baselineZero():String {
+            return this._baselineZero;
+        }
+
+        public function set baselineZero(value:String):void {
+            this._baselineZero = value;
+            this.invalidateLines();
+        }
+
+        public function get bidiLevel():int {
+            return this._bidiLevel;
+        }
+
+        public function set bidiLevel(value:int):void {
+            if (value < 0) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._bidiLevel = value;
+            this.invalidateLines();
+        }
+
+        public function get applyNonLinearFontScaling():Boolean {
+            return this._applyNonLinearFontScaling;
+        }
+
+        public function set applyNonLinearFontScaling(value:Boolean):void {
+            this._applyNonLinearFontScaling = value;
+        }
+
+        public function get baselineFontDescription():FontDescription {
+            return this._baselineFontDescription;
+        }
+
+        public function set baselineFontDescription(value:FontDescription):void {
+            this._baselineFontDescription = value;
+            this.invalidateLines();
+        }
+
+        public function get baselineFontSize():Number {
+            return this._baselineFontSize;
+        }
+
+        public function set baselineFontSize(value:Number):void {
+            if (value < 0) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._baselineFontSize = value;
+            this.invalidateLines();
+        }
+
+        public function get firstLine():TextLine {
+            return this._firstLine;
+        }
+
+        public function get lastLine():TextLine {
+            return this._lastLine;
+        }
+
+        public function get firstInvalidLine():TextLine {
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                if (line._validity != TextLineValidity.VALID) {
+                    return line;
+                }
+            }
+            return null;
+        }
+
+        public function get textLineCreationResult():String {
+            return this._textLineCreationResult;
+        }
+
+        public function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0.0, fitSomething:Boolean = false):TextLine {
+            return this.layoutLine(null, previousLine, width, fitSomething);
+        }
+
+        public function recreateTextLine(textLine:TextLine, previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0.0, fitSomething:Boolean = false):TextLine {
+            if (textLine == null) {
+                throw new TypeError("Error #2007: Parameter textLine must be non-null.", 2007);
+            }
+            if (textLine === previousLine || (textLine._textBlock != null && textLine._textBlock !== this)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            return this.layoutLine(textLine, previousLine, width, fitSomething);
+        }
+
+        private native function breakLine(textLine:TextLine, beginIndex:int, width:Number, fitSomething:Boolean):TextLine;
+
+        private function layoutLine(textLine:TextLine, previousLine:TextLine, width:Number, fitSomething:Boolean):TextLine {
+            if (!(width >= 0 && width <= TextLine.MAX_LINE_WIDTH)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (previousLine != null && previousLine._textBlock !== this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var beginIndex:int = 0;
+            if (previousLine != null) {
+                beginIndex = previousLine._textBlockBeginIndex + previousLine._rawTextLength;
+            }
+            var rawText:String = this._content != null ? this._content.rawText : null;
+            if (rawText == null || beginIndex >= rawText.length) {
+                this._textLineCreationResult = TextLineCreationResult.COMPLETE;
+                return null;
+            }
+
+            // Lines after the previous line are replaced by the new line.
+            var next:TextLine = previousLine != null ? previousLine._nextLine : this._firstLine;
+            if (next != null) {
+                this.releaseLines(next, this._lastLine);
+            }
+            if (textLine != null) {
+                textLine.removeChildren();
+            }
+
+            var line:TextLine = this.breakLine(textLine, beginIndex, width, fitSomething);
+            if (line == null) {
+                return null;
+            }
+
+            line._textBlock = this;
+            line._validity = TextLineValidity.VALID;
+            line._specifiedWidth = width;
+            line._previousLine = previousLine;
+            line._nextLine = null;
+            if (previousLine != null) {
+                previousLine._nextLine = line;
+            } else {
+                this._firstLine = line;
+            }
+            this._lastLine = line;
+
+            if (line._atomGraphics != null) {
+                for (var i:int = 0; i < line._atomGraphics.length; i++) {
+                    var graphic:DisplayObject = line._atomGraphics[i];
+                    if (graphic != null) {
+                        var bounds:Rectangle = line.getAtomBounds(i);
+                        graphic.x = bounds.x;
+                        graphic.y = bounds.y;
+                        line.addChild(graphic);
+                    }
+                }
+            }
+
+            return line;
+        }
+
+        public function releaseLines(firstLine:TextLine, lastLine:TextLine):void {
+            if (firstLine == null || lastLine == null || firstLine._textBlock !== this || lastLine._textBlock !== this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var before:TextLine = firstLine._previousLine;
+            var after:TextLine = lastLine._nextLine;
+            var line:TextLine = firstLine;
+            while (line != null) {
+                var next:TextLine = line._nextLine;
+                line._textBlock = null;
+                line._previousLine = null;
+                line._nextLine = null;
+                if (line._validity == TextLineValidity.VALID) {
+                    line._validity = TextLineValidity.INVALID;
+                }
+                if (line === lastLine) {
+                    break;
+                }
+                line = next;
+            }
+
+            if (before != null) {
+                before._nextLine = after;
+            } else {
+                this._firstLine = after;
+            }
+            if (after != null) {
+                after._previousLine = before;
+            } else {
+                this._lastLine = before;
+            }
+        }
+
+        public function releaseLineCreationData():void {
+            // We don't keep any data around between line creations.
+        }
+
+        public function getTextLineAtCharIndex(charIndex:int):TextLine {
+            this.checkCharIndex(charIndex);
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                if (charIndex >= line._textBlockBeginIndex && charIndex < line._textBlockBeginIndex + line._rawTextLength) {
+                    return line;
+                }
+            }
+            return null;
+        }
+
+        public function findNextAtomBoundary(afterCharIndex:int):int {
+            this.checkCharIndex(afterCharIndex);
+            return afterCharIndex + 1;
+        }
+
+        public function findPreviousAtomBoundary(beforeCharIndex:int):int {
+            this.checkCharIndex(beforeCharIndex);
+            return beforeCharIndex - 1;
+        }
+
+        public function findNextWordBoundary(afterCharIndex:int):int {
+            this.checkCharIndex(afterCharIndex);
+            var rawText:String = this._content.rawText;
+            var index:int = afterCharIndex + 1;
+            var separator:Boolean = isWordSeparator(rawText.charCodeAt(afterCharIndex));
+            while (index < rawText.length && isWordSeparator(rawText.charCodeAt(index)) == separator) {
+                index++;
+            }
+            return index;
+        }
+
+        public function findPreviousWordBoundary(beforeCharIndex:int):int {
+            this.checkCharIndex(beforeCharIndex);
+            var rawText:String = this._content.rawText;
+            var index:int = beforeCharIndex;
+            if (index == 0) {
+                return 0;
+            }
+            var separator:Boolean = isWordSeparator(rawText.charCodeAt(index - 1));
+            while (index > 0 && isWordSeparator(rawText.charCodeAt(index - 1)) == separator) {
+                index--;
+            }
+            return index;
+        }
+
+        public function dump():String {
+            return "";
+        }
+
+        private function checkCharIndex(charIndex:int):void {
+            var rawText:String = this._content != null ? this._content.rawText : null;
+            if (rawText == null || charIndex < 0 || charIndex >= rawText.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+        }
+
+        // Marks every line of this block as needing to be recreated.
+        internal function invalidateLines():void {
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                if (line._validity == TextLineValidity.VALID) {
+                    line._validity = TextLineValidity.INVALID;
+                }
+            }
+        }
+
+        internal static function isWordSeparator(charCode:Number):Boolean {
+            // Space, tab, the line and paragraph separators and graphic elements.
+            return charCode == 0x20 || charCode == 0x09 || charCode == 0x2028 || charCode == 0x2029
+                || charCode == ContentElement.GRAPHIC_ELEMENT;
+        }
+    }
+}
@@ -0,0 +1,42 @@
+package flash.text.engine {
+    public class TextJustifier {
+        private var _locale:String;
+        private var _lineJustification:String;
+
+        public function TextJustifier(locale:String, lineJustification:String) {
+            if (locale == null || locale.length == 0) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._locale = locale;
+            this.lineJustification = lineJustification;
+        }
+
+        public static function getJustifierForLocale(locale:String):TextJustifier {
+            var language:String = locale != null ? locale.substr(0, 2).toLowerCase() : "";
+            if (language == "ja" || language == "zh" || language == "ko") {
+                return new EastAsianJustifier(locale);
+            }
+            return new SpaceJustifier(locale);
+        }
+
+        public function get locale():String {
+            return this._locale;
+        }
+
+        public function get lineJustification():String {
+            return this._lineJustification;
+        }
+
+        public function set lineJustification(value:String):void {
+            if (value != LineJustification.UNJUSTIFIED && value != LineJustification.ALL_BUT_LAST
+                    && value != LineJustification.ALL_BUT_MANDATORY_BREAK && value != LineJustification.ALL_INCLUDING_LAST) {
+                throw new ArgumentError("Error #2008: Parameter lineJustification must be one of the accepted values.", 2008);
+            }
+            this._lineJustification = value;
+        }
+
+        public function clone():TextJustifier {
+            return null;
+        }
+    }
+}
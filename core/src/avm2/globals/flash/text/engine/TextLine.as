@@ -0,0 +1,229 @@
+package flash.text.engine {
+    import flash.display.DisplayObject;
+    import flash.display.DisplayObjectContainer;
+    import flash.events.EventDispatcher;
+    import flash.geom.Point;
+    import flash.geom.Rectangle;
+
+    [Ruffle(NativeInstanceInit)]
+    public final class TextLine extends DisplayObjectContainer {
+        public static const MAX_LINE_WIDTH:int = 1000000;
+
+        internal var _textBlock:TextBlock = null;
+        internal var _previousLine:TextLine = null;
+        internal var _nextLine:TextLine = null;
+        internal var _specifiedWidth:Number = 0;
+        internal var _validity:String = "valid";
+
+        // These are set by `TextBlock.breakLine` whenever the line is
+        // (re)created.
+        internal var _textBlockBeginIndex:int = 0;
+        internal var _rawTextLength:int = 0;
+        internal var _ascent:Number = 0;
+        internal var _descent:Number = 0;
+        internal var _textWidth:Number = 0;
+        internal var _atomBounds:Vector.<Number> = null;
+        internal var _atomIndices:Vector.<int> = null;
+        internal var _atomGraphics:Array = null;
+        internal var _hasGraphicElement:Boolean = false;
+
+        public var userData:*;
+
+        public function TextLine() {
+            throw new ArgumentError("Error #2012: TextLine$ class cannot be instantiated.", 2012);
+        }
+
+        public function get ascent():Number {
+            return this._ascent;
+        }
+
+        public function get descent():Number {
+            return this._descent;
+        }
+
+        public function get textWidth():Number {
+            return this._textWidth;
+        }
+
+        public function get textHeight():Number {
+            return this._ascent + this._descent;
+        }
+
+        public function get unjustifiedTextWidth():Number {
+            return this._textWidth;
+        }
+
+        public function get totalAscent():Number {
+            return this._ascent;
+        }
+
+        public function get totalDescent():Number {
+            return this._descent;
+        }
+
+        public function get totalHeight():Number {
+            return this.totalAscent + this.totalDescent;
+        }
+
+        public function get specifiedWidth():Number {
+            return this._specifiedWidth;
+        }
+
+        public function get rawTextLength():int {
+            return this._rawTextLength;
+        }
+
+        public function get textBlockBeginIndex():int {
+            return this._textBlockBeginIndex;
+        }
+
+        public function get textBlock():TextBlock {
+            return this._textBlock;
+        }
+
+        public function get nextLine():TextLine {
+            return this._nextLine;
+        }
+
+        public function get previousLine():TextLine {
+            return this._previousLine;
+        }
+
+        public function get atomCount():int {
+            return this._atomIndices != null ? this._atomIndices.length - 1 : 0;
+        }
+
+        public function get hasGraphicElement():Boolean {
+            return this._hasGraphicElement;
+        }
+
+        public function get hasTabs():Boolean {
+            return false;
+        }
+
+        public function get mirrorRegions():Vector.<TextLineMirrorRegion> {
+            return null;
+        }
+
+        public function get validity():String {
+            return this._validity;
+        }
+
+        public function set validity(value:String):void {
+            if (value != TextLineValidity.VALID && value != TextLineValidity.INVALID
+                    && value != TextLineValidity.POSSIBLY_INVALID && value != TextLineValidity.STATIC) {
+                throw new ArgumentError("Error #2008: Parameter validity must be one of the accepted values.", 2008);
+            }
+            if (value == TextLineValidity.VALID && this._validity != TextLineValidity.VALID) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (value == TextLineValidity.STATIC && this._textBlock != null) {
+                this._textBlock.releaseLines(this, this);
+            }
+            this._validity = value;
+        }
+
+        private function checkAtomIndex(atomIndex:int):void {
+            if (atomIndex < 0 || atomIndex >= this.atomCount) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+        }
+
+        public function getAtomBounds(atomIndex:int):Rectangle {
+            checkAtomIndex(atomIndex);
+            var i:int = atomIndex * 4;
+            return new Rectangle(this._atomBounds[i], this._atomBounds[i + 1], this._atomBounds[i + 2], this._atomBounds[i + 3]);
+        }
+
+        public function getAtomCenter(atomIndex:int):Number {
+            checkAtomIndex(atomIndex);
+            return this._atomBounds[atomIndex * 4] + this._atomBounds[atomIndex * 4 + 2] / 2;
+        }
+
+        public function getAtomIndexAtCharIndex(charIndex:int):int {
+            for (var i:int = 0; i < this.atomCount; i++) {
+                if (charIndex >= this._atomIndices[i] && charIndex < this._atomIndices[i + 1]) {
+                    return i;
+                }
+            }
+            return -1;
+        }
+
+        public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
+            var point:Point = this.globalToLocal(new Point(stageX, stageY));
+            for (var i:int = 0; i < this.atomCount; i++) {
+                var x:Number = this._atomBounds[i * 4];
+                var y:Number = this._atomBounds[i * 4 + 1];
+                if (point.x >= x && point.x < x + this._atomBounds[i * 4 + 2]
+                        && point.y >= y && point.y < y + this._atomBounds[i * 4 + 3]) {
+                    return i;
+                }
+            }
+            return -1;
+        }
+
+        public function getAtomTextBlockBeginIndex(atomIndex:int):int {
+            checkAtomIndex(atomIndex);
+            return this._atomIndices[atomIndex];
+        }
+
+        public function getAtomTextBlockEndIndex(atomIndex:int):int {
+            checkAtomIndex(atomIndex);
+            return this._atomIndices[atomIndex + 1];
+        }
+
+        public function getAtomBidiLevel(atomIndex:int):int {
+            checkAtomIndex(atomIndex);
+            return 0;
+        }
+
+        public function getAtomTextRotation(atomIndex:int):String {
+            checkAtomIndex(atomIndex);
+            return TextRotation.ROTATE_0;
+        }
+
+        public function getAtomWordBoundaryOnLeft(atomIndex:int):Boolean {
+            checkAtomIndex(atomIndex);
+            var index:int = this._atomIndices[atomIndex];
+            if (index == 0 || this._textBlock == null || this._textBlock.content == null) {
+                return true;
+            }
+            var rawText:String = this._textBlock.content.rawText;
+            return TextBlock.isWordSeparator(rawText.charCodeAt(index - 1)) != TextBlock.isWordSeparator(rawText.charCodeAt(index));
+        }
+
+        public function getAtomGraphic(atomIndex:int):DisplayObject {
+            checkAtomIndex(atomIndex);
+            return this._atomGraphics != null ? this._atomGraphics[atomIndex] : null;
+        }
+
+        public function getBaselinePosition(baseline:String):Number {
+            switch (baseline) {
+                case TextBaseline.ROMAN:
+                    return 0;
+                case TextBaseline.ASCENT:
+                case TextBaseline.IDEOGRAPHIC_TOP:
+                    return -this._ascent;
+                case TextBaseline.DESCENT:
+                case TextBaseline.IDEOGRAPHIC_BOTTOM:
+                    return this._descent;
+                case TextBaseline.IDEOGRAPHIC_CENTER:
+                    return (this._descent - this._ascent) / 2;
+                default:
+                    throw new ArgumentError("Error #2008: Parameter baseline must be one of the accepted values.", 2008);
+            }
+        }
+
+        public function getMirrorRegion(mirror:EventDispatcher):TextLineMirrorRegion {
+            return null;
+        }
+
+        public function flushAtomData():void {
+            // Atom data is always kept, as it's cheap to hold on to.
+        }
+
+        public function dump():String {
+            return "";
+        }
+    }
+}
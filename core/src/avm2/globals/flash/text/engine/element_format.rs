@@ -0,0 +1,201 @@
+//! `flash.text.engine.ElementFormat` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::font::{EvalParameters, Font};
+use crate::string::AvmString;
+use swf::{Color, Twips};
+
+/// The formatting of a `ContentElement`, resolved from its `ElementFormat`.
+#[derive(Clone, Copy)]
+pub struct ResolvedFormat<'gc> {
+    /// The font that the element is rendered with, if any font is available.
+    pub font: Option<Font<'gc>>,
+    pub size: f64,
+    pub color: Color,
+    pub kerning: bool,
+    /// The additional space around every character, combining the left and
+    /// right tracking of the format.
+    pub tracking: f64,
+    pub baseline_shift: f64,
+    pub break_opportunity: AvmString<'gc>,
+}
+
+impl<'gc> ResolvedFormat<'gc> {
+    /// The parameters to evaluate the font of the element with.
+    pub fn eval_parameters(&self) -> EvalParameters {
+        EvalParameters::from_parts(
+            Twips::from_pixels(self.size),
+            Twips::from_pixels(self.tracking),
+            self.kerning,
+        )
+    }
+
+    /// The distances that the font of the element reaches above and below the
+    /// baseline, in pixels.
+    pub fn ascent_and_descent(&self) -> (f64, f64) {
+        let height = Twips::from_pixels(self.size);
+        match self.font {
+            Some(font) => (
+                font.get_baseline_for_height(height).to_pixels(),
+                font.get_descent_for_height(height).to_pixels(),
+            ),
+            None => (self.size * 0.8, self.size * 0.2),
+        }
+    }
+}
+
+/// Finds the font described by a `FontDescription`.
+///
+/// Embedded fonts are looked up by name in the library of the movie. Device
+/// fonts, and embedded fonts that can't be found, use the device font.
+pub fn resolve_font<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    font_description: Option<Object<'gc>>,
+) -> Result<Option<Font<'gc>>, Error<'gc>> {
+    if let Some(font_description) = font_description {
+        let lookup = font_description
+            .get_public_property("fontLookup", activation)?
+            .coerce_to_string(activation)?;
+        if &lookup == b"embeddedCFF" {
+            let name = font_description
+                .get_public_property("fontName", activation)?
+                .coerce_to_string(activation)?;
+            let weight = font_description
+                .get_public_property("fontWeight", activation)?
+                .coerce_to_string(activation)?;
+            let posture = font_description
+                .get_public_property("fontPosture", activation)?
+                .coerce_to_string(activation)?;
+            if let Some(font) =
+                embedded_font(activation, name, &weight == b"bold", &posture == b"italic")
+            {
+                return Ok(Some(font));
+            }
+        }
+    }
+
+    Ok(activation.context.library.device_font())
+}
+
+/// Finds an embedded font with glyphs in the library of the movie.
+pub fn embedded_font<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: AvmString<'gc>,
+    is_bold: bool,
+    is_italic: bool,
+) -> Option<Font<'gc>> {
+    activation
+        .context
+        .library
+        .library_for_movie(activation.context.swf.clone())
+        .and_then(|library| library.get_font_by_name(&name.to_utf8_lossy(), is_bold, is_italic))
+        .filter(|font| font.has_glyphs())
+}
+
+/// Reads the formatting of an `ElementFormat`, or the default formatting if
+/// there is none.
+pub fn resolve_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    element_format: Option<Object<'gc>>,
+) -> Result<ResolvedFormat<'gc>, Error<'gc>> {
+    let Some(element_format) = element_format else {
+        return Ok(ResolvedFormat {
+            font: activation.context.library.device_font(),
+            size: 12.0,
+            color: Color::BLACK,
+            kerning: true,
+            tracking: 0.0,
+            baseline_shift: 0.0,
+            break_opportunity: "auto".into(),
+        });
+    };
+
+    let font_description = element_format
+        .get_public_property("fontDescription", activation)?
+        .as_object();
+    let font = resolve_font(activation, font_description)?;
+    let size = element_format
+        .get_public_property("fontSize", activation)?
+        .coerce_to_number(activation)?;
+    let rgb = element_format
+        .get_public_property("color", activation)?
+        .coerce_to_u32(activation)?;
+    let alpha = element_format
+        .get_public_property("alpha", activation)?
+        .coerce_to_number(activation)?;
+    let kerning = element_format
+        .get_public_property("kerning", activation)?
+        .coerce_to_string(activation)?;
+    let tracking_left = element_format
+        .get_public_property("trackingLeft", activation)?
+        .coerce_to_number(activation)?;
+    let tracking_right = element_format
+        .get_public_property("trackingRight", activation)?
+        .coerce_to_number(activation)?;
+    let baseline_shift = element_format
+        .get_public_property("baselineShift", activation)?
+        .coerce_to_number(activation)?;
+    let break_opportunity = element_format
+        .get_public_property("breakOpportunity", activation)?
+        .coerce_to_string(activation)?;
+
+    Ok(ResolvedFormat {
+        font,
+        size,
+        color: Color::from_rgb(rgb, (alpha.clamp(0.0, 1.0) * 255.0) as u8),
+        kerning: &kerning != b"off",
+        tracking: tracking_left + tracking_right,
+        baseline_shift,
+        break_opportunity,
+    })
+}
+
+/// Implements `ElementFormat.getFontMetrics`.
+pub fn get_font_metrics<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = resolve_format(activation, this)?;
+    let size = format.size;
+    let (ascent, descent) = format.ascent_and_descent();
+    let line_gap = match format.font {
+        Some(font) => font
+            .get_leading_for_height(Twips::from_pixels(size))
+            .to_pixels(),
+        None => 0.0,
+    };
+
+    // Only the ascent and descent are known for our fonts, so the positions of
+    // decorations use the usual proportions of the em box.
+    let em_ascent = if ascent + descent > 0.0 {
+        size * ascent / (ascent + descent)
+    } else {
+        size
+    };
+    let em_box = activation.avm2().classes().rectangle.construct(
+        activation,
+        &[0.into(), (-em_ascent).into(), size.into(), size.into()],
+    )?;
+    let args = [
+        em_box.into(),
+        (-size * 0.3).into(),
+        (size / 20.0).into(),
+        (size / 10.0).into(),
+        (size / 20.0).into(),
+        (size / 6.0).into(),
+        (2.0 / 3.0).into(),
+        (-size / 3.0).into(),
+        (2.0 / 3.0).into(),
+        line_gap.into(),
+    ];
+    Ok(activation
+        .avm2()
+        .classes()
+        .fontmetrics
+        .construct(activation, &args)?
+        .into())
+}
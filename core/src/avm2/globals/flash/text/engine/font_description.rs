@@ -0,0 +1,23 @@
+//! `flash.text.engine.FontDescription` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::text::engine::element_format::embedded_font;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Implements `FontDescription.isFontCompatible`, which checks whether a font
+/// with the given name and style is embedded in the movie.
+pub fn is_font_compatible<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?;
+    let weight = args.get_string(activation, 1)?;
+    let posture = args.get_string(activation, 2)?;
+
+    let font = embedded_font(activation, name, &weight == b"bold", &posture == b"italic");
+    Ok(font.is_some().into())
+}
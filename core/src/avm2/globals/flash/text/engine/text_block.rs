@@ -0,0 +1,401 @@
+//! `flash.text.engine.TextBlock` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::argument_error;
+use crate::avm2::globals::flash::text::engine::element_format::{resolve_format, ResolvedFormat};
+use crate::avm2::object::{ArrayObject, Object, StageObject, TObject, VectorObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{ArrayStorage, Error, Multiname};
+use crate::avm2_stub_method;
+use crate::display_object::{MovieClip, TDisplayObject};
+use crate::drawing::Drawing;
+use crate::font::{fit_line, Glyph, LineAtom, LineFit};
+use crate::string::{AvmString, WString};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use swf::{Color, FillStyle, Twips};
+
+/// The character that stands in for a `GraphicElement` in the raw text of a block.
+const GRAPHIC_ELEMENT: u16 = 0xFDEF;
+
+/// A part of the content of a text block that shares one format.
+struct Run<'gc> {
+    /// The raw text of the run. A graphic is a single `GRAPHIC_ELEMENT`.
+    text: AvmString<'gc>,
+    format: ResolvedFormat<'gc>,
+    graphic: Option<RunGraphic<'gc>>,
+}
+
+/// The graphic of a `GraphicElement`, with the space that it takes up.
+#[derive(Clone, Copy)]
+struct RunGraphic<'gc> {
+    graphic: Option<Object<'gc>>,
+    width: f64,
+    height: f64,
+}
+
+/// A character or graphic of a text block.
+struct Atom {
+    /// The index of the run that the atom is part of.
+    run: usize,
+    /// The index of the first character of the atom in the raw text of the block.
+    begin: usize,
+    /// The index after the last character of the atom in the raw text of the block.
+    end: usize,
+    character: char,
+    line_atom: LineAtom,
+}
+
+/// Implements `TextBlock.breakLine`, which lays out the atoms of the block
+/// from `beginIndex` onto a single line.
+///
+/// The line is drawn into `textLine` if given, or into a new `TextLine`
+/// otherwise. Returns `null` if nothing fit on the line. The outcome is
+/// stored as the `textLineCreationResult` of the block.
+pub fn break_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(mut this) = this else {
+        return Ok(Value::Null);
+    };
+    let text_line = args.try_get_object(activation, 0);
+    let begin_index = args.get_u32(activation, 1)? as usize;
+    let width = args.get_f64(activation, 2)?;
+    let fit_something = args.get_bool(3);
+
+    let Some(content) = this.get_public_property("content", activation)?.as_object() else {
+        return Ok(Value::Null);
+    };
+    let mut runs = vec![];
+    collect_runs(activation, content, None, &mut runs)?;
+    let atoms = atoms_from(activation, &runs, begin_index);
+
+    let line_atoms: Vec<LineAtom> = atoms.iter().map(|atom| atom.line_atom).collect();
+    let (count, result) = match fit_line(&line_atoms, Twips::from_pixels(width), fit_something) {
+        LineFit::Break(count) => (count, "success"),
+        LineFit::Emergency(count) => (count, "emergency"),
+        LineFit::InsufficientWidth => (0, "insufficientWidth"),
+    };
+    let internal = activation.avm2().flash_text_engine_internal;
+    this.set_property(
+        &Multiname::new(internal, "_textLineCreationResult"),
+        result.into(),
+        activation,
+    )?;
+    if count == 0 {
+        return Ok(Value::Null);
+    }
+    let atoms = &atoms[..count];
+
+    let mut line = match text_line {
+        Some(line) => line,
+        None => create_text_line(activation)?,
+    };
+    let Some(line_object) = line.as_display_object() else {
+        return Ok(Value::Null);
+    };
+
+    let mut ascent: f64 = 0.0;
+    let mut descent: f64 = 0.0;
+    let mut x = Twips::ZERO;
+    let mut atom_bounds: Vec<Value<'gc>> = Vec::with_capacity(atoms.len() * 4);
+    let mut atom_indices: Vec<Value<'gc>> = Vec::with_capacity(atoms.len() + 1);
+    let mut atom_graphics: Vec<Value<'gc>> = Vec::with_capacity(atoms.len());
+    let mut has_graphic_element = false;
+    {
+        let Some(mut drawing) = line_object.as_drawing(activation.context.gc_context) else {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2004: One of the parameters is invalid.",
+                2004,
+            )?));
+        };
+        drawing.clear();
+
+        for atom in atoms {
+            let run = &runs[atom.run];
+            let format = &run.format;
+            let (top, height) = if let Some(graphic) = run.graphic {
+                has_graphic_element = true;
+                atom_graphics.push(graphic.graphic.map_or(Value::Null, Value::from));
+                (format.baseline_shift - graphic.height, graphic.height)
+            } else {
+                atom_graphics.push(Value::Null);
+                let (atom_ascent, atom_descent) = format.ascent_and_descent();
+                if let Some(font) = format.font {
+                    if let Some(glyph) = font.get_glyph_for_char(atom.character) {
+                        let scale = Twips::from_pixels(format.size).get() as f32 / font.scale();
+                        let matrix = Matrix {
+                            a: scale,
+                            d: scale,
+                            tx: x,
+                            ty: Twips::from_pixels(format.baseline_shift),
+                            ..Default::default()
+                        };
+                        draw_glyph(&mut drawing, glyph, matrix, format.color);
+                    }
+                }
+                (
+                    format.baseline_shift - atom_ascent,
+                    atom_ascent + atom_descent,
+                )
+            };
+
+            ascent = ascent.max(-top);
+            descent = descent.max(top + height);
+            atom_bounds.extend([
+                x.to_pixels().into(),
+                top.into(),
+                atom.line_atom.advance.to_pixels().into(),
+                height.into(),
+            ]);
+            atom_indices.push((atom.begin as i32).into());
+            x += atom.line_atom.advance;
+        }
+    }
+    let begin = atoms.first().map_or(begin_index, |atom| atom.begin);
+    let end = atoms.last().map_or(begin_index, |atom| atom.end);
+    atom_indices.push((end as i32).into());
+
+    let number = activation.avm2().classes().number;
+    let atom_bounds = VectorStorage::from_values(atom_bounds, false, number);
+    let atom_bounds = VectorObject::from_vector(atom_bounds, activation)?;
+    let int = activation.avm2().classes().int;
+    let atom_indices = VectorStorage::from_values(atom_indices, false, int);
+    let atom_indices = VectorObject::from_vector(atom_indices, activation)?;
+    let atom_graphics = if has_graphic_element {
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&atom_graphics))?.into()
+    } else {
+        Value::Null
+    };
+
+    let properties: [(&'static str, Value<'gc>); 9] = [
+        ("_textBlockBeginIndex", (begin as i32).into()),
+        ("_rawTextLength", ((end - begin) as i32).into()),
+        ("_ascent", ascent.into()),
+        ("_descent", descent.into()),
+        ("_textWidth", x.to_pixels().into()),
+        ("_atomBounds", atom_bounds.into()),
+        ("_atomIndices", atom_indices.into()),
+        ("_atomGraphics", atom_graphics),
+        ("_hasGraphicElement", has_graphic_element.into()),
+    ];
+    for (name, value) in properties {
+        line.set_property(&Multiname::new(internal, name), value, activation)?;
+    }
+
+    Ok(line.into())
+}
+
+/// Creates an empty `TextLine`, which is backed by a movie clip that the
+/// glyphs of the line are drawn into.
+///
+/// The line belongs to the movie that called `createTextLine`.
+fn create_text_line<'gc>(activation: &mut Activation<'_, 'gc>) -> Result<Object<'gc>, Error<'gc>> {
+    let movie = activation.caller_movie();
+    let display_object = MovieClip::new(movie, activation.context.gc_context);
+    let class = activation.avm2().classes().textline;
+    let object: Object<'gc> =
+        StageObject::for_display_object_childless(activation, display_object.into(), class)?.into();
+    display_object.set_object2(&mut activation.context, object);
+    Ok(object)
+}
+
+/// Flattens a tree of `ContentElement`s into runs of text and graphics.
+///
+/// Elements without an `ElementFormat` use the format of their group.
+fn collect_runs<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    element: Object<'gc>,
+    group_format: Option<Object<'gc>>,
+    runs: &mut Vec<Run<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let element_format = element
+        .get_public_property("elementFormat", activation)?
+        .as_object()
+        .or(group_format);
+    let classes = activation.avm2().classes();
+    let (text_element, group_element, graphic_element) = (
+        classes.textelement,
+        classes.groupelement,
+        classes.graphicelement,
+    );
+
+    if element.is_of_type(group_element, activation) {
+        let internal = activation.avm2().flash_text_engine_internal;
+        let elements = element.get_property(&Multiname::new(internal, "_elements"), activation)?;
+        let elements: Vec<Value<'gc>> = elements
+            .as_object()
+            .and_then(|elements| elements.as_vector_storage().map(|v| v.iter().collect()))
+            .unwrap_or_default();
+        for child in elements.iter().filter_map(|element| element.as_object()) {
+            collect_runs(activation, child, element_format, runs)?;
+        }
+    } else if element.is_of_type(text_element, activation) {
+        let text = match element.get_public_property("text", activation)? {
+            Value::Undefined | Value::Null => return Ok(()),
+            text => text.coerce_to_string(activation)?,
+        };
+        if !text.is_empty() {
+            let format = resolve_format(activation, element_format)?;
+            runs.push(Run {
+                text,
+                format,
+                graphic: None,
+            });
+        }
+    } else if element.is_of_type(graphic_element, activation) {
+        let graphic = element
+            .get_public_property("graphic", activation)?
+            .as_object();
+        let width = element
+            .get_public_property("elementWidth", activation)?
+            .coerce_to_number(activation)?;
+        let height = element
+            .get_public_property("elementHeight", activation)?
+            .coerce_to_number(activation)?;
+        let format = resolve_format(activation, element_format)?;
+        runs.push(Run {
+            text: AvmString::new(
+                activation.context.gc_context,
+                WString::from_unit(GRAPHIC_ELEMENT),
+            ),
+            format,
+            graphic: Some(RunGraphic {
+                graphic,
+                width,
+                height,
+            }),
+        });
+    }
+
+    Ok(())
+}
+
+/// Splits the runs of a text block into atoms, starting from `begin_index`
+/// in the raw text of the block.
+fn atoms_from(
+    activation: &mut Activation<'_, '_>,
+    runs: &[Run<'_>],
+    begin_index: usize,
+) -> Vec<Atom> {
+    let mut atoms: Vec<Atom> = vec![];
+    let mut run_begin = 0;
+
+    for (run_index, run) in runs.iter().enumerate() {
+        let run_end = run_begin + run.text.len();
+        if run_end <= begin_index {
+            run_begin = run_end;
+            continue;
+        }
+
+        if let Some(graphic) = run.graphic {
+            // Lines may always be broken around graphics.
+            if let Some(previous) = atoms.last_mut() {
+                previous.line_atom.can_break_after = true;
+            }
+            atoms.push(Atom {
+                run: run_index,
+                begin: run_begin,
+                end: run_end,
+                character: char::REPLACEMENT_CHARACTER,
+                line_atom: LineAtom {
+                    advance: Twips::from_pixels(graphic.width),
+                    can_break_after: true,
+                    ..Default::default()
+                },
+            });
+            run_begin = run_end;
+            continue;
+        }
+
+        let break_opportunity = run.format.break_opportunity;
+        let break_anywhere = &break_opportunity == b"all" || &break_opportunity == b"any";
+        let break_nowhere = &break_opportunity == b"none";
+        let advances = match run.format.font {
+            Some(font) => font.char_advances(&run.text, run.format.eval_parameters()),
+            None => {
+                avm2_stub_method!(
+                    activation,
+                    "flash.text.engine.TextBlock",
+                    "createTextLine",
+                    "without a device font"
+                );
+                vec![Twips::ZERO; run.text.len()]
+            }
+        };
+
+        for ((pos, character), advance) in run.text.char_indices().zip(advances) {
+            let begin = run_begin + pos;
+            let (character, len) = match character {
+                Ok(character) => (character, character.len_utf16()),
+                Err(_) => (char::REPLACEMENT_CHARACTER, 1),
+            };
+            if begin < begin_index {
+                continue;
+            }
+
+            let is_whitespace = character == ' ' || character == '\t';
+            let can_break_after = !break_nowhere
+                && (break_anywhere || is_whitespace || character == '-' || character == '\u{200B}');
+            atoms.push(Atom {
+                run: run_index,
+                begin,
+                end: begin + len,
+                character,
+                line_atom: LineAtom {
+                    advance,
+                    is_whitespace,
+                    can_break_after,
+                    forces_break: character == '\u{2028}' || character == '\u{2029}',
+                },
+            });
+        }
+        run_begin = run_end;
+    }
+
+    atoms
+}
+
+/// Draws the outline of a glyph as a solid fill.
+fn draw_glyph(drawing: &mut Drawing, glyph: &Glyph, matrix: Matrix, color: Color) {
+    let shape = glyph.as_shape();
+    let shape = DistilledShape::from(&*shape);
+    for path in shape.paths {
+        if let DrawPath::Fill {
+            commands,
+            winding_rule,
+            ..
+        } = path
+        {
+            drawing.set_fill_style(Some(FillStyle::Color(color)));
+            drawing.set_winding_rule(winding_rule);
+            for command in commands {
+                drawing.draw_command(transform_command(matrix, command));
+            }
+            drawing.set_fill_style(None);
+        }
+    }
+}
+
+fn transform_command(matrix: Matrix, command: DrawCommand) -> DrawCommand {
+    match command {
+        DrawCommand::MoveTo { x, y } => {
+            let (x, y) = matrix * (x, y);
+            DrawCommand::MoveTo { x, y }
+        }
+        DrawCommand::LineTo { x, y } => {
+            let (x, y) = matrix * (x, y);
+            DrawCommand::LineTo { x, y }
+        }
+        DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+            let (x1, y1) = matrix * (x1, y1);
+            let (x2, y2) = matrix * (x2, y2);
+            DrawCommand::CurveTo { x1, y1, x2, y2 }
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! `flash.text.engine.TextLine` native methods
+
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements the native initializer of `TextLine`, which can only be created
+/// by a `TextBlock`.
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
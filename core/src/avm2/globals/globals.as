@@ -67,6 +67,7 @@ include "flash/display/GraphicsPathWinding.as"
 include "flash/display/GraphicsPath.as"
 include "flash/display/GraphicsTrianglePath.as"
 include "flash/display/GraphicsSolidFill.as"
+include "flash/display/GraphicsShaderFill.as"
 include "flash/display/GraphicsStroke.as"
 include "flash/display/InterpolationMethod.as"
 include "flash/display/JointStyle.as"
@@ -81,6 +82,11 @@ include "flash/display/PNGEncoderOptions.as"
 include "flash/display/Scene.as"
 include "flash/display/ShaderParameterType.as"
 include "flash/display/ShaderPrecision.as"
+include "flash/display/ShaderData.as"
+include "flash/display/ShaderInput.as"
+include "flash/display/ShaderParameter.as"
+include "flash/display/Shader.as"
+include "flash/display/ShaderJob.as"
 include "flash/display/Shape.as"
 include "flash/display/SimpleButton.as"
 include "flash/display/SpreadMethod.as"
@@ -187,11 +193,13 @@ include "flash/filters/DisplacementMapFilterMode.as"
 include "flash/filters/DropShadowFilter.as"
 include "flash/filters/GlowFilter.as"
 include "flash/filters/GradientGlowFilter.as"
+include "flash/filters/ShaderFilter.as"
 
 include "flash/geom/ColorTransform.as"
 include "flash/geom/Matrix.as"
 include "flash/geom/Matrix3D.as"
 include "flash/geom/Orientation3D.as"
+include "flash/geom/PerspectiveProjection.as"
 include "flash/geom/Point.as"
 include "flash/geom/Rectangle.as"
 include "flash/geom/Transform.as"
@@ -212,6 +220,7 @@ include "flash/media/AVTagData.as"
 include "flash/media/Camera.as"
 include "flash/media/H264Level.as"
 include "flash/media/H264Profile.as"
+include "flash/media/ID3Info.as"
 include "flash/media/Microphone.as"
 include "flash/media/MicrophoneEnhancedMode.as"
 include "flash/media/MicrophoneEnhancedOptions.as"
@@ -251,6 +260,7 @@ include "flash/net/ObjectEncoding.as"
 include "flash/net/Responder.as"
 include "flash/net/SharedObject.as"
 include "flash/net/SharedObjectFlushStatus.as"
+include "flash/net/Socket.as"
 include "flash/net/URLLoader.as"
 include "flash/net/URLLoaderDataFormat.as"
 include "flash/net/URLRequest.as"
@@ -301,21 +311,36 @@ include "flash/text/TextInteractionMode.as"
 include "flash/text/TextLineMetrics.as"
 include "flash/text/TextRenderer.as"
 
+include "flash/text/engine/ContentElement.as"
+include "flash/text/engine/TextJustifier.as"
+
 include "flash/text/engine/BreakOpportunity.as"
 include "flash/text/engine/CFFHinting.as"
 include "flash/text/engine/DigitCase.as"
 include "flash/text/engine/DigitWidth.as"
+include "flash/text/engine/EastAsianJustifier.as"
+include "flash/text/engine/ElementFormat.as"
+include "flash/text/engine/FontDescription.as"
 include "flash/text/engine/FontLookup.as"
+include "flash/text/engine/FontMetrics.as"
 include "flash/text/engine/FontPosture.as"
 include "flash/text/engine/FontWeight.as"
+include "flash/text/engine/GraphicElement.as"
+include "flash/text/engine/GroupElement.as"
 include "flash/text/engine/JustificationStyle.as"
 include "flash/text/engine/Kerning.as"
 include "flash/text/engine/LigatureLevel.as"
 include "flash/text/engine/LineJustification.as"
 include "flash/text/engine/RenderingMode.as"
+include "flash/text/engine/SpaceJustifier.as"
 include "flash/text/engine/TabAlignment.as"
+include "flash/text/engine/TabStop.as"
 include "flash/text/engine/TextBaseline.as"
+include "flash/text/engine/TextBlock.as"
+include "flash/text/engine/TextElement.as"
+include "flash/text/engine/TextLine.as"
 include "flash/text/engine/TextLineCreationResult.as"
+include "flash/text/engine/TextLineMirrorRegion.as"
 include "flash/text/engine/TextLineValidity.as"
 include "flash/text/engine/TextRotation.as"
 include "flash/text/engine/TypographicCase.as"
@@ -18,9 +18,11 @@ use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::QName;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
+use crate::camera::Camera;
 use crate::context::UpdateContext;
 use crate::display_object::DisplayObject;
 use crate::html::TextFormat;
+use crate::net_connection::NetConnection;
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use gc_arena::{Collect, GcCell, MutationContext};
@@ -32,6 +34,7 @@ use std::hash::{Hash, Hasher};
 mod array_object;
 mod bitmapdata_object;
 mod bytearray_object;
+mod camera_object;
 mod class_object;
 mod context3d_object;
 mod date_object;
@@ -40,17 +43,25 @@ mod dispatch_object;
 mod domain_object;
 mod error_object;
 mod event_object;
+mod file_reference_object;
 mod function_object;
 mod index_buffer_3d_object;
 mod loaderinfo_object;
+mod local_connection_object;
+mod microphone_object;
 mod namespace_object;
+mod netconnection_object;
 mod netstream_object;
 mod primitive_object;
 mod program_3d_object;
 mod proxy_object;
 mod qname_object;
 mod regexp_object;
+mod responder_object;
 mod script_object;
+mod shader_data_object;
+mod shared_object_object;
+mod socket_object;
 mod sound_object;
 mod soundchannel_object;
 mod stage3d_object;
@@ -65,6 +76,7 @@ mod xml_object;
 pub use crate::avm2::object::array_object::{array_allocator, ArrayObject};
 pub use crate::avm2::object::bitmapdata_object::{bitmap_data_allocator, BitmapDataObject};
 pub use crate::avm2::object::bytearray_object::{byte_array_allocator, ByteArrayObject};
+pub use crate::avm2::object::camera_object::{camera_allocator, CameraObject};
 pub use crate::avm2::object::class_object::ClassObject;
 pub use crate::avm2::object::context3d_object::Context3DObject;
 pub use crate::avm2::object::date_object::{date_allocator, DateObject};
@@ -73,19 +85,35 @@ pub use crate::avm2::object::dispatch_object::DispatchObject;
 pub use crate::avm2::object::domain_object::{application_domain_allocator, DomainObject};
 pub use crate::avm2::object::error_object::{error_allocator, ErrorObject};
 pub use crate::avm2::object::event_object::{event_allocator, EventObject};
+pub use crate::avm2::object::file_reference_object::{
+    file_reference_allocator, FileReferenceObject,
+};
 pub use crate::avm2::object::function_object::{function_allocator, FunctionObject};
 pub use crate::avm2::object::index_buffer_3d_object::IndexBuffer3DObject;
 pub use crate::avm2::object::loaderinfo_object::{
     loader_info_allocator, LoaderInfoObject, LoaderStream,
 };
+pub use crate::avm2::object::local_connection_object::{
+    local_connection_allocator, LocalConnectionObject,
+};
+pub use crate::avm2::object::microphone_object::{microphone_allocator, MicrophoneObject};
 pub use crate::avm2::object::namespace_object::{namespace_allocator, NamespaceObject};
+pub use crate::avm2::object::netconnection_object::{netconnection_allocator, NetConnectionObject};
 pub use crate::avm2::object::netstream_object::{netstream_allocator, NetStreamObject};
 pub use crate::avm2::object::primitive_object::{primitive_allocator, PrimitiveObject};
 pub use crate::avm2::object::program_3d_object::Program3DObject;
 pub use crate::avm2::object::proxy_object::{proxy_allocator, ProxyObject};
 pub use crate::avm2::object::qname_object::{qname_allocator, QNameObject};
 pub use crate::avm2::object::regexp_object::{regexp_allocator, RegExpObject};
+pub use crate::avm2::object::responder_object::{
+    responder_allocator, ResponderCallback, ResponderObject,
+};
 pub use crate::avm2::object::script_object::{ScriptObject, ScriptObjectData};
+pub use crate::avm2::object::shader_data_object::{shader_data_allocator, ShaderDataObject};
+pub use crate::avm2::object::shared_object_object::{shared_object_allocator, SharedObjectObject};
+pub use crate::avm2::object::socket_object::{
+    socket_allocator, xml_socket_allocator, SocketObject,
+};
 pub use crate::avm2::object::sound_object::{sound_allocator, QueuedPlay, SoundData, SoundObject};
 pub use crate::avm2::object::soundchannel_object::{sound_channel_allocator, SoundChannelObject};
 pub use crate::avm2::object::stage3d_object::{stage_3d_allocator, Stage3DObject};
@@ -136,6 +164,15 @@ pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject};
         TextureObject(TextureObject<'gc>),
         Program3DObject(Program3DObject<'gc>),
         NetStreamObject(NetStreamObject<'gc>),
+        NetConnectionObject(NetConnectionObject<'gc>),
+        ResponderObject(ResponderObject<'gc>),
+        SocketObject(SocketObject<'gc>),
+        LocalConnectionObject(LocalConnectionObject<'gc>),
+        SharedObjectObject(SharedObjectObject<'gc>),
+        FileReferenceObject(FileReferenceObject<'gc>),
+        MicrophoneObject(MicrophoneObject<'gc>),
+        CameraObject(CameraObject<'gc>),
+        ShaderDataObject(ShaderDataObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -1305,6 +1342,42 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_netstream(self) -> Option<NetStream<'gc>> {
         None
     }
+
+    fn as_net_connection(self) -> Option<NetConnection<'gc>> {
+        None
+    }
+
+    fn as_responder(self) -> Option<ResponderObject<'gc>> {
+        None
+    }
+
+    fn as_socket(self) -> Option<SocketObject<'gc>> {
+        None
+    }
+
+    fn as_local_connection(self) -> Option<LocalConnectionObject<'gc>> {
+        None
+    }
+
+    fn as_shared_object(self) -> Option<SharedObjectObject<'gc>> {
+        None
+    }
+
+    fn as_file_reference(self) -> Option<FileReferenceObject<'gc>> {
+        None
+    }
+
+    fn as_microphone(self) -> Option<MicrophoneObject<'gc>> {
+        None
+    }
+
+    fn as_camera(self) -> Option<Camera<'gc>> {
+        None
+    }
+
+    fn as_shader_data(&self) -> Option<ShaderDataObject<'gc>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
@@ -0,0 +1,77 @@
+//! Object representation for Cameras
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::camera::Camera;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+use std::fmt::Debug;
+
+pub fn camera_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class);
+    let camera = Camera::new(activation.context.gc_context, None);
+    let this: Object<'gc> = CameraObject(GcCell::allocate(
+        activation.context.gc_context,
+        CameraObjectData { base, camera },
+    ))
+    .into();
+    camera.set_avm_object(activation.context.gc_context, this.into());
+
+    Ok(this)
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct CameraObject<'gc>(GcCell<'gc, CameraObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct CameraObjectData<'gc> {
+    base: ScriptObjectData<'gc>,
+    camera: Camera<'gc>,
+}
+
+impl<'gc> TObject<'gc> for CameraObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object((*self).into()))
+    }
+
+    fn as_camera(self) -> Option<Camera<'gc>> {
+        Some(self.0.read().camera)
+    }
+}
+
+impl<'gc> Debug for CameraObject<'gc> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.0.try_read() {
+            Ok(obj) => f
+                .debug_struct("CameraObject")
+                .field("class", &obj.base.debug_class_name())
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+            Err(err) => f
+                .debug_struct("CameraObject")
+                .field("class", &err)
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Object representation for FileReference
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::FileDialogResult;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+use std::fmt::Debug;
+use std::rc::Rc;
+
+pub fn file_reference_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class);
+    Ok(FileReferenceObject(GcCell::allocate(
+        activation.context.gc_context,
+        FileReferenceObjectData {
+            base,
+            file: None,
+            data: None,
+        },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct FileReferenceObject<'gc>(GcCell<'gc, FileReferenceObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct FileReferenceObjectData<'gc> {
+    base: ScriptObjectData<'gc>,
+
+    /// The file chosen by the user, if any.
+    #[collect(require_static)]
+    file: Option<Rc<dyn FileDialogResult>>,
+
+    /// The contents of the file, once it has been loaded with `load`.
+    data: Option<Object<'gc>>,
+}
+
+impl<'gc> FileReferenceObject<'gc> {
+    pub fn file(self) -> Option<Rc<dyn FileDialogResult>> {
+        self.0.read().file.clone()
+    }
+
+    /// Replaces the chosen file, which discards any data loaded from the
+    /// previous one.
+    pub fn set_file(self, mc: MutationContext<'gc, '_>, file: Option<Rc<dyn FileDialogResult>>) {
+        let mut write = self.0.write(mc);
+        write.file = file;
+        write.data = None;
+    }
+
+    pub fn data(self) -> Option<Object<'gc>> {
+        self.0.read().data
+    }
+
+    pub fn set_data(self, mc: MutationContext<'gc, '_>, data: Option<Object<'gc>>) {
+        self.0.write(mc).data = data;
+    }
+}
+
+impl<'gc> TObject<'gc> for FileReferenceObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object((*self).into()))
+    }
+
+    fn as_file_reference(self) -> Option<FileReferenceObject<'gc>> {
+        Some(self)
+    }
+}
+
+impl<'gc> Debug for FileReferenceObject<'gc> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.0.try_read() {
+            Ok(obj) => f
+                .debug_struct("FileReferenceObject")
+                .field("class", &obj.base.debug_class_name())
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+            Err(err) => f
+                .debug_struct("FileReferenceObject")
+                .field("class", &err)
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Object representation for LocalConnections
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::local_connection::LocalConnection;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+use std::fmt::Debug;
+
+pub fn local_connection_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class);
+    let connection = LocalConnection::new(activation.context.gc_context, None);
+    let this: Object<'gc> = LocalConnectionObject(GcCell::allocate(
+        activation.context.gc_context,
+        LocalConnectionObjectData { base, connection },
+    ))
+    .into();
+    connection.set_avm_object(activation.context.gc_context, this.into());
+
+    Ok(this)
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct LocalConnectionObject<'gc>(GcCell<'gc, LocalConnectionObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct LocalConnectionObjectData<'gc> {
+    base: ScriptObjectData<'gc>,
+    connection: LocalConnection<'gc>,
+}
+
+impl<'gc> LocalConnectionObject<'gc> {
+    pub fn connection(self) -> LocalConnection<'gc> {
+        self.0.read().connection
+    }
+}
+
+impl<'gc> TObject<'gc> for LocalConnectionObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object((*self).into()))
+    }
+
+    fn as_local_connection(self) -> Option<LocalConnectionObject<'gc>> {
+        Some(self)
+    }
+}
+
+impl<'gc> Debug for LocalConnectionObject<'gc> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.0.try_read() {
+            Ok(obj) => f
+                .debug_struct("LocalConnectionObject")
+                .field("class", &obj.base.debug_class_name())
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+            Err(err) => f
+                .debug_struct("LocalConnectionObject")
+                .field("class", &err)
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Object representation for NetConnections
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::net_connection::NetConnection;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+use std::fmt::Debug;
+
+pub fn netconnection_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class);
+    let connection = NetConnection::new(activation.context.gc_context, None);
+    let this: Object<'gc> = NetConnectionObject(GcCell::allocate(
+        activation.context.gc_context,
+        NetConnectionObjectData { base, connection },
+    ))
+    .into();
+    connection.set_avm_object(activation.context.gc_context, this.into());
+
+    Ok(this)
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct NetConnectionObject<'gc>(GcCell<'gc, NetConnectionObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct NetConnectionObjectData<'gc> {
+    base: ScriptObjectData<'gc>,
+    connection: NetConnection<'gc>,
+}
+
+impl<'gc> TObject<'gc> for NetConnectionObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object((*self).into()))
+    }
+
+    fn as_net_connection(self) -> Option<NetConnection<'gc>> {
+        Some(self.0.read().connection)
+    }
+}
+
+impl<'gc> Debug for NetConnectionObject<'gc> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.0.try_read() {
+            Ok(obj) => f
+                .debug_struct("NetConnectionObject")
+                .field("class", &obj.base.debug_class_name())
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+            Err(err) => f
+                .debug_struct("NetConnectionObject")
+                .field("class", &err)
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+        }
+    }
+}
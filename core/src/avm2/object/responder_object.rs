@@ -0,0 +1,124 @@
+//! Object representation for Responders
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, FunctionObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+use std::fmt::Debug;
+
+pub fn responder_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class);
+
+    Ok(ResponderObject(GcCell::allocate(
+        activation.context.gc_context,
+        ResponderObjectData {
+            base,
+            result: None,
+            status: None,
+        },
+    ))
+    .into())
+}
+
+/// Which of a `Responder`'s functions to call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponderCallback {
+    /// The call succeeded.
+    Result,
+
+    /// The call failed.
+    Status,
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct ResponderObject<'gc>(GcCell<'gc, ResponderObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct ResponderObjectData<'gc> {
+    base: ScriptObjectData<'gc>,
+
+    /// The function called with the result of a successful call.
+    result: Option<FunctionObject<'gc>>,
+
+    /// The function called with the error of a failed call.
+    status: Option<FunctionObject<'gc>>,
+}
+
+impl<'gc> ResponderObject<'gc> {
+    pub fn set_callbacks(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        result: Option<FunctionObject<'gc>>,
+        status: Option<FunctionObject<'gc>>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        write.result = result;
+        write.status = status;
+    }
+
+    /// Call one of this responder's functions, if it has one.
+    pub fn send_callback(
+        self,
+        activation: &mut Activation<'_, 'gc>,
+        callback: ResponderCallback,
+        value: Value<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        let function = match callback {
+            ResponderCallback::Result => self.0.read().result,
+            ResponderCallback::Status => self.0.read().status,
+        };
+
+        if let Some(function) = function {
+            function.call(None, &[value], activation)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'gc> TObject<'gc> for ResponderObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object((*self).into()))
+    }
+
+    fn as_responder(self) -> Option<ResponderObject<'gc>> {
+        Some(self)
+    }
+}
+
+impl<'gc> Debug for ResponderObject<'gc> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.0.try_read() {
+            Ok(obj) => f
+                .debug_struct("ResponderObject")
+                .field("class", &obj.base.debug_class_name())
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+            Err(err) => f
+                .debug_struct("ResponderObject")
+                .field("class", &err)
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Object representation for `flash.display.ShaderData`
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use core::fmt;
+use gc_arena::{Collect, GcCell, MutationContext};
+use ruffle_render::pixel_bender::PixelBenderShader;
+use std::cell::{Ref, RefMut};
+use std::sync::Arc;
+
+/// A class instance allocator that allocates ShaderData objects.
+pub fn shader_data_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class);
+
+    Ok(ShaderDataObject(GcCell::allocate(
+        activation.context.gc_context,
+        ShaderDataObjectData { base, shader: None },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct ShaderDataObject<'gc>(GcCell<'gc, ShaderDataObjectData<'gc>>);
+
+impl fmt::Debug for ShaderDataObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShaderDataObject")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+impl<'gc> ShaderDataObject<'gc> {
+    /// Creates a `ShaderData` for a shader that was already parsed.
+    ///
+    /// Unlike the constructor, this doesn't add any properties for the parameters.
+    pub fn from_shader(
+        activation: &mut Activation<'_, 'gc>,
+        shader: Arc<PixelBenderShader>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let class = activation.avm2().classes().shaderdata;
+        let base = ScriptObjectData::new(class);
+
+        let mut this: Object<'gc> = ShaderDataObject(GcCell::allocate(
+            activation.context.gc_context,
+            ShaderDataObjectData {
+                base,
+                shader: Some(shader),
+            },
+        ))
+        .into();
+        this.install_instance_slots(activation);
+
+        Ok(this)
+    }
+
+    /// The parsed shader, if the bytecode that this object was constructed with was valid.
+    pub fn shader(self) -> Option<Arc<PixelBenderShader>> {
+        self.0.read().shader.clone()
+    }
+
+    pub fn set_shader(self, mc: MutationContext<'gc, '_>, shader: Arc<PixelBenderShader>) {
+        self.0.write(mc).shader = Some(shader);
+    }
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct ShaderDataObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    #[collect(require_static)]
+    shader: Option<Arc<PixelBenderShader>>,
+}
+
+impl<'gc> TObject<'gc> for ShaderDataObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_shader_data(&self) -> Option<ShaderDataObject<'gc>> {
+        Some(*self)
+    }
+}
@@ -0,0 +1,86 @@
+//! Object representation for SharedObjects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::remote_shared_object::RemoteSharedObject;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+use std::fmt::Debug;
+
+pub fn shared_object_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class);
+    Ok(SharedObjectObject(GcCell::allocate(
+        activation.context.gc_context,
+        SharedObjectObjectData { base, remote: None },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct SharedObjectObject<'gc>(GcCell<'gc, SharedObjectObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct SharedObjectObjectData<'gc> {
+    base: ScriptObjectData<'gc>,
+
+    /// The server side of this shared object, if it was made with
+    /// `getRemote`.
+    remote: Option<RemoteSharedObject<'gc>>,
+}
+
+impl<'gc> SharedObjectObject<'gc> {
+    pub fn remote(self) -> Option<RemoteSharedObject<'gc>> {
+        self.0.read().remote
+    }
+
+    pub fn set_remote(self, mc: MutationContext<'gc, '_>, remote: RemoteSharedObject<'gc>) {
+        self.0.write(mc).remote = Some(remote);
+    }
+}
+
+impl<'gc> TObject<'gc> for SharedObjectObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object((*self).into()))
+    }
+
+    fn as_shared_object(self) -> Option<SharedObjectObject<'gc>> {
+        Some(self)
+    }
+}
+
+impl<'gc> Debug for SharedObjectObject<'gc> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.0.try_read() {
+            Ok(obj) => f
+                .debug_struct("SharedObjectObject")
+                .field("class", &obj.base.debug_class_name())
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+            Err(err) => f
+                .debug_struct("SharedObjectObject")
+                .field("class", &err)
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Object representation for Sockets and XMLSockets
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::socket::{Socket, SocketKind};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+use std::fmt::Debug;
+
+pub fn socket_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    allocate(class, activation, SocketKind::Binary)
+}
+
+pub fn xml_socket_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    allocate(class, activation, SocketKind::Xml)
+}
+
+fn allocate<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    kind: SocketKind,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class);
+    let socket = Socket::new(activation.context.gc_context, None, kind);
+    let this: Object<'gc> = SocketObject(GcCell::allocate(
+        activation.context.gc_context,
+        SocketObjectData {
+            base,
+            socket,
+            input: None,
+            output: None,
+        },
+    ))
+    .into();
+    socket.set_avm_object(activation.context.gc_context, this.into());
+
+    Ok(this)
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct SocketObject<'gc>(GcCell<'gc, SocketObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct SocketObjectData<'gc> {
+    base: ScriptObjectData<'gc>,
+    socket: Socket<'gc>,
+
+    /// The `ByteArray` that received data is appended to, and read from.
+    input: Option<Object<'gc>>,
+
+    /// The `ByteArray` that data is written to until the socket is flushed.
+    output: Option<Object<'gc>>,
+}
+
+impl<'gc> SocketObject<'gc> {
+    pub fn socket(self) -> Socket<'gc> {
+        self.0.read().socket
+    }
+
+    pub fn input(self) -> Option<Object<'gc>> {
+        self.0.read().input
+    }
+
+    pub fn output(self) -> Option<Object<'gc>> {
+        self.0.read().output
+    }
+
+    pub fn set_buffers(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        input: Option<Object<'gc>>,
+        output: Option<Object<'gc>>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        write.input = input;
+        write.output = output;
+    }
+}
+
+impl<'gc> TObject<'gc> for SocketObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object((*self).into()))
+    }
+
+    fn as_socket(self) -> Option<SocketObject<'gc>> {
+        Some(self)
+    }
+}
+
+impl<'gc> Debug for SocketObject<'gc> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.0.try_read() {
+            Ok(obj) => f
+                .debug_struct("SocketObject")
+                .field("class", &obj.base.debug_class_name())
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+            Err(err) => f
+                .debug_struct("SocketObject")
+                .field("class", &err)
+                .field("ptr", &self.0.as_ptr())
+                .finish(),
+        }
+    }
+}
@@ -5,13 +5,14 @@ use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::backend::audio::SoundHandle;
+use crate::backend::audio::mp3_frames::Mp3FrameSplitter;
+use crate::backend::audio::{SoundHandle, Substream};
 use crate::context::UpdateContext;
 use crate::display_object::SoundTransform;
 use core::fmt;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::cell::{Ref, RefMut};
-use swf::SoundInfo;
+use swf::{AudioCompression, SoundFormat, SoundInfo};
 
 use super::SoundChannelObject;
 
@@ -29,6 +30,10 @@ pub fn sound_allocator<'gc>(
             sound_data: SoundData::NotLoaded {
                 queued_plays: Vec::new(),
             },
+            url: None,
+            id3: None,
+            extract_position: 0,
+            load: None,
         },
     ))
     .into())
@@ -54,6 +59,54 @@ pub struct SoundObjectData<'gc> {
 
     /// The sound this object holds.
     sound_data: SoundData<'gc>,
+
+    /// The URL this sound is being loaded from, if `Sound.load` was called.
+    #[collect(require_static)]
+    url: Option<String>,
+
+    /// The `ID3Info` read from the loaded MP3 file, if it has ID3 tags.
+    id3: Option<Object<'gc>>,
+
+    /// The sample frame after the last one read by `Sound.extract`, where the
+    /// next extraction starts if no position is given.
+    extract_position: u32,
+
+    /// The download of the MP3 file, while `Sound.load` is loading it.
+    #[collect(require_static)]
+    load: Option<SoundLoad>,
+}
+
+/// The download of an MP3 file that is being loaded by `Sound.load`.
+///
+/// The sound can be played before it has finished downloading. Each channel
+/// that plays it is given a substream of the MP3 frames received so far, and
+/// the frames that arrive later are appended to it.
+pub struct SoundLoad {
+    /// How much audio has to be received before the sound starts playing, in
+    /// milliseconds.
+    buffer_time: f64,
+
+    /// The length of the file in bytes, if it is known.
+    bytes_total: Option<u64>,
+
+    /// All of the data received so far.
+    data: Vec<u8>,
+
+    splitter: Mp3FrameSplitter,
+
+    /// The runs of whole MP3 frames received so far, along with the time at
+    /// which each of them starts in milliseconds.
+    packets: Vec<(f64, Vec<u8>)>,
+
+    /// The substreams of the channels that are playing the sound.
+    substreams: Vec<Substream>,
+}
+
+impl SoundLoad {
+    /// Whether less than `buffer_time` worth of audio has been received.
+    fn is_buffering(&self) -> bool {
+        self.splitter.sample_rate().is_none() || self.splitter.duration() < self.buffer_time
+    }
 }
 
 #[derive(Collect)]
@@ -88,12 +141,142 @@ impl<'gc> SoundObject<'gc> {
         }
     }
 
+    pub fn url(self) -> Option<String> {
+        self.0.read().url.clone()
+    }
+
+    pub fn set_url(self, mc: MutationContext<'gc, '_>, url: String) {
+        self.0.write(mc).url = Some(url);
+    }
+
+    pub fn id3(self) -> Option<Object<'gc>> {
+        self.0.read().id3
+    }
+
+    pub fn set_id3(self, mc: MutationContext<'gc, '_>, id3: Object<'gc>) {
+        self.0.write(mc).id3 = Some(id3);
+    }
+
+    pub fn extract_position(self) -> u32 {
+        self.0.read().extract_position
+    }
+
+    pub fn set_extract_position(self, mc: MutationContext<'gc, '_>, position: u32) {
+        self.0.write(mc).extract_position = position;
+    }
+
+    /// Start loading an MP3 file into this sound, which will start playing
+    /// once `buffer_time` milliseconds of it have been received.
+    pub fn start_load(self, mc: MutationContext<'gc, '_>, buffer_time: f64) {
+        self.0.write(mc).load = Some(SoundLoad {
+            buffer_time,
+            bytes_total: None,
+            data: Vec::new(),
+            splitter: Mp3FrameSplitter::new(),
+            packets: Vec::new(),
+            substreams: Vec::new(),
+        });
+    }
+
+    pub fn set_bytes_total(self, mc: MutationContext<'gc, '_>, bytes_total: Option<u64>) {
+        if let Some(load) = &mut self.0.write(mc).load {
+            load.bytes_total = bytes_total;
+        }
+    }
+
+    /// The number of bytes of the MP3 file received so far, while it is
+    /// being loaded.
+    pub fn bytes_loaded(self) -> Option<usize> {
+        self.0.read().load.as_ref().map(|load| load.data.len())
+    }
+
+    /// The length of the MP3 file being loaded, if it is known.
+    pub fn bytes_total(self) -> Option<usize> {
+        let this = self.0.read();
+        let load = this.load.as_ref()?;
+        load.bytes_total.map(|bytes_total| bytes_total as usize)
+    }
+
+    /// Whether the sound is being loaded, and not enough of it has been
+    /// received to start playing.
+    pub fn is_buffering(self) -> bool {
+        self.0
+            .read()
+            .load
+            .as_ref()
+            .map_or(false, |load| load.is_buffering())
+    }
+
+    /// Append data received for the MP3 file being loaded, feeding it to the
+    /// channels that are playing the sound.
+    ///
+    /// Plays that were waiting for the sound to buffer are started once
+    /// enough audio has been received.
+    pub fn append_load_data(
+        self,
+        data: &[u8],
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<(), Error<'gc>> {
+        let queued_plays = {
+            let mut this = self.0.write(activation.context.gc_context);
+            let this = &mut *this;
+            let Some(load) = &mut this.load else {
+                return Ok(());
+            };
+
+            load.data.extend_from_slice(data);
+            let start_time = load.splitter.duration();
+            if let Some(packet) = load.splitter.push(data) {
+                for substream in &load.substreams {
+                    substream.append(packet.clone());
+                }
+                load.packets.push((start_time, packet));
+            }
+
+            match &mut this.sound_data {
+                SoundData::NotLoaded { queued_plays } if !load.is_buffering() => {
+                    std::mem::take(queued_plays)
+                }
+                _ => Vec::new(),
+            }
+        };
+
+        for queued in queued_plays {
+            self.play_loading(queued, activation);
+        }
+        Ok(())
+    }
+
+    /// Stop loading the MP3 file, returning all of the data that was received.
+    ///
+    /// The channels that are playing the sound end once they have played the
+    /// data received so far.
+    pub fn finish_load(self, mc: MutationContext<'gc, '_>) -> Option<Vec<u8>> {
+        let load = self.0.write(mc).load.take()?;
+        for substream in &load.substreams {
+            substream.close();
+        }
+        Some(load.data)
+    }
+
     /// Returns `true` if a `SoundChannel` should be returned back to the AVM2 caller.
     pub fn play(
         self,
         queued: QueuedPlay<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<bool, Error<'gc>> {
+        // A sound that is still loading can play once it has buffered.
+        let is_buffered = self
+            .0
+            .read()
+            .load
+            .as_ref()
+            .map_or(false, |load| !load.is_buffering());
+        if is_buffered {
+            self.play_loading(queued, activation);
+            return Ok(true);
+        }
+
         let mut this = self.0.write(activation.context.gc_context);
         match &mut this.sound_data {
             SoundData::NotLoaded { queued_plays } => {
@@ -105,6 +288,61 @@ impl<'gc> SoundObject<'gc> {
         }
     }
 
+    /// Start playing the part of the sound that has been received so far,
+    /// while it is still being loaded.
+    ///
+    /// Playback starts at the first run of frames that contains the start
+    /// position. Loops are not supported until the sound has been loaded.
+    fn play_loading(self, queued: QueuedPlay<'gc>, activation: &mut Activation<'_, 'gc>) {
+        let mut this = self.0.write(activation.context.gc_context);
+        let Some(load) = &mut this.load else {
+            return;
+        };
+        let Some(sample_rate) = load.splitter.sample_rate() else {
+            return;
+        };
+
+        let substream = Substream::new();
+        let first_packet = load
+            .packets
+            .iter()
+            .rposition(|(start_time, _)| *start_time <= queued.position)
+            .unwrap_or_default();
+        for (_, packet) in &load.packets[first_packet..] {
+            substream.append(packet.clone());
+        }
+        load.substreams.push(substream.clone());
+        drop(this);
+
+        let format = SoundFormat {
+            compression: AudioCompression::Mp3,
+            sample_rate,
+            is_stereo: true,
+            is_16_bit: true,
+        };
+        if let Some(instance) = activation.context.audio_manager.start_substream(
+            activation.context.audio,
+            substream,
+            &format,
+        ) {
+            if let Some(sound_transform) = queued.sound_transform {
+                activation
+                    .context
+                    .set_local_sound_transform(instance, sound_transform);
+            }
+
+            queued
+                .sound_channel
+                .as_sound_channel()
+                .unwrap()
+                .set_sound_instance(activation, instance);
+
+            activation
+                .context
+                .attach_avm2_sound_channel(instance, queued.sound_channel);
+        }
+    }
+
     pub fn set_sound(
         self,
         context: &mut UpdateContext<'_, 'gc>,
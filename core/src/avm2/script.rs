@@ -14,10 +14,12 @@ use crate::avm2::Namespace;
 use crate::avm2::{Avm2, Error};
 use crate::context::UpdateContext;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use std::cell::Ref;
 use std::mem::drop;
 use std::rc::Rc;
+use std::sync::Arc;
 use swf::avm2::types::{
     AbcFile, Index, Method as AbcMethod, Multiname as AbcMultiname, Namespace as AbcNamespace,
     Script as AbcScript,
@@ -48,6 +50,10 @@ struct TranslationUnitData<'gc> {
     /// The name from the original `DoAbc2` tag, or `None` if this came from a `DoAbc` tag
     name: Option<AvmString<'gc>>,
 
+    /// The movie that contained the ABC file.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
+
     /// The ABC file that all of the following loaded data comes from.
     #[collect(require_static)]
     abc: Rc<AbcFile>,
@@ -81,6 +87,7 @@ impl<'gc> TranslationUnit<'gc> {
         abc: AbcFile,
         domain: Domain<'gc>,
         name: Option<AvmString<'gc>>,
+        movie: Arc<SwfMovie>,
         mc: MutationContext<'gc, '_>,
     ) -> Self {
         let classes = vec![None; abc.classes.len()];
@@ -95,6 +102,7 @@ impl<'gc> TranslationUnit<'gc> {
             TranslationUnitData {
                 domain,
                 name,
+                movie,
                 abc: Rc::new(abc),
                 classes,
                 methods,
@@ -111,6 +119,11 @@ impl<'gc> TranslationUnit<'gc> {
     }
 
     // Retrieve the name associated with the original `DoAbc2` tag
+    /// The movie that this ABC file was loaded from.
+    pub fn movie(self) -> Arc<SwfMovie> {
+        self.0.read().movie.clone()
+    }
+
     pub fn name(self) -> Option<AvmString<'gc>> {
         self.0.read().name
     }
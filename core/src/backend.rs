@@ -1,5 +1,8 @@
 pub mod audio;
+pub mod camera;
+pub mod local_connection;
 pub mod log;
+pub mod microphone;
 pub mod navigator;
 pub mod storage;
 pub mod ui;
@@ -1,6 +1,11 @@
 use crate::{
     avm1::SoundObject,
-    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
+    avm2::{
+        bytearray::{ByteArrayStorage, Endian},
+        object::{ByteArrayObject, TObject as _},
+        Activation as Avm2Activation, Avm2, Error as Avm2Error, EventObject as Avm2EventObject,
+        Object as Avm2Object, SoundChannelObject,
+    },
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
 };
@@ -10,6 +15,8 @@ use generational_arena::{Arena, Index};
 
 #[cfg(feature = "audio")]
 pub mod decoders;
+pub mod id3;
+pub mod mp3_frames;
 pub mod swf {
     pub use swf::{
         read, AudioCompression, CharacterId, Sound, SoundEnvelope, SoundEnvelopePoint, SoundEvent,
@@ -29,6 +36,8 @@ mod decoders {
 }
 
 use instant::Duration;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
 pub type SoundHandle = Index;
@@ -51,6 +60,17 @@ pub trait AudioBackend: Downcast {
     /// Registers MP3 audio from an external source.
     fn register_mp3(&mut self, data: &[u8]) -> Result<SoundHandle, DecodeError>;
 
+    /// Registers a sound made of 32-bit float PCM samples, such as those given
+    /// to `Sound.loadPCMFromByteArray`.
+    ///
+    /// The samples of stereo sounds are interleaved.
+    fn register_float_pcm(
+        &mut self,
+        samples: &[f32],
+        is_stereo: bool,
+        sample_rate: u16,
+    ) -> SoundHandle;
+
     /// Plays a sound.
     fn start_sound(
         &mut self,
@@ -70,6 +90,28 @@ pub trait AudioBackend: Downcast {
         handle: &swf::SoundStreamHead,
     ) -> Result<SoundInstanceHandle, DecodeError>;
 
+    /// Starts playing a "substream" sound, which is an audio stream whose data is
+    /// appended by the player as it becomes available, such as the audio track of
+    /// an FLV file playing in a `NetStream`.
+    ///
+    /// The sound will output silence while it waits for more data, and ends once
+    /// the substream is closed and all of its data has been played.
+    fn start_substream(
+        &mut self,
+        substream: Substream,
+        format: &swf::SoundFormat,
+    ) -> Result<SoundInstanceHandle, DecodeError>;
+
+    /// Starts playing a sound whose sample frames are generated by script, such
+    /// as an AVM2 `Sound` that is listening for `sampleData` events.
+    ///
+    /// The sound will output silence while it waits for more samples, and ends
+    /// once the buffer is closed and all of its samples have been played.
+    fn start_sample_stream(
+        &mut self,
+        buffer: SampleBuffer,
+    ) -> Result<SoundInstanceHandle, DecodeError>;
+
     /// Stops a playing sound instance.
     /// No-op if the sound is not playing.
     fn stop_sound(&mut self, sound: SoundInstanceHandle);
@@ -93,6 +135,17 @@ pub trait AudioBackend: Downcast {
     /// Get the sound format that a given sound was added with.
     fn get_sound_format(&self, sound: SoundHandle) -> Option<&swf::SoundFormat>;
 
+    /// Get up to `num_frames` sample frames of a sound, starting at sample frame
+    /// `position`, decoded to 44.1KHz stereo floats.
+    ///
+    /// Returns `None` if the sound is not registered or can't be decoded.
+    fn get_sound_samples(
+        &mut self,
+        sound: SoundHandle,
+        position: u32,
+        num_frames: u32,
+    ) -> Option<Vec<[f32; 2]>>;
+
     /// Set the volume transform for a sound instance.
     fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform);
 
@@ -198,6 +251,26 @@ impl AudioBackend for NullAudioBackend {
         }))
     }
 
+    fn register_float_pcm(
+        &mut self,
+        samples: &[f32],
+        is_stereo: bool,
+        sample_rate: u16,
+    ) -> SoundHandle {
+        let num_channels = if is_stereo { 2 } else { 1 };
+        let num_sample_frames = (samples.len() / num_channels) as f64;
+        self.sounds.insert(NullSound {
+            size: (samples.len() * 4) as u32,
+            duration: num_sample_frames * 1000.0 / f64::from(sample_rate),
+            format: swf::SoundFormat {
+                compression: swf::AudioCompression::Uncompressed,
+                sample_rate,
+                is_stereo,
+                is_16_bit: false,
+            },
+        })
+    }
+
     fn start_sound(
         &mut self,
         _sound: SoundHandle,
@@ -216,6 +289,21 @@ impl AudioBackend for NullAudioBackend {
         Ok(SoundInstanceHandle::from_raw_parts(0, 0))
     }
 
+    fn start_substream(
+        &mut self,
+        _substream: Substream,
+        _format: &swf::SoundFormat,
+    ) -> Result<SoundInstanceHandle, DecodeError> {
+        Ok(SoundInstanceHandle::from_raw_parts(0, 0))
+    }
+
+    fn start_sample_stream(
+        &mut self,
+        _buffer: SampleBuffer,
+    ) -> Result<SoundInstanceHandle, DecodeError> {
+        Ok(SoundInstanceHandle::from_raw_parts(0, 0))
+    }
+
     fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
 
     fn stop_all_sounds(&mut self) {}
@@ -241,6 +329,15 @@ impl AudioBackend for NullAudioBackend {
         self.sounds.get(sound).map(|s| &s.format)
     }
 
+    fn get_sound_samples(
+        &mut self,
+        _sound: SoundHandle,
+        _position: u32,
+        _num_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        None
+    }
+
     fn set_sound_transform(&mut self, _instance: SoundInstanceHandle, _transform: SoundTransform) {}
 
     fn get_sound_peak(&mut self, _instance: SoundInstanceHandle) -> Option<[f32; 2]> {
@@ -296,6 +393,17 @@ impl<'gc> AudioManager<'gc> {
     /// The threshold in seconds where an audio stream is considered too out-of-sync and will be stopped.
     pub const STREAM_RESTART_THRESHOLD: f64 = 1.0;
 
+    /// The number of sample frames that dynamic sounds keep buffered on top of
+    /// what will play before the next frame, to cover the audio backend's own
+    /// buffering and uneven frame timing.
+    pub const SAMPLE_DATA_LATENCY: usize = 2048;
+
+    /// The most samples that a single `sampleData` event may provide.
+    pub const SAMPLE_DATA_MAX_SAMPLES: usize = 8192;
+
+    /// A `sampleData` event providing fewer samples than this ends the sound.
+    pub const SAMPLE_DATA_MIN_SAMPLES: usize = 2048;
+
     /// The minimum audio sycning threshold in seconds.
     ///
     /// The player will adjust animation speed to stay within this many seconds of the audio track.
@@ -313,9 +421,11 @@ impl<'gc> AudioManager<'gc> {
     /// Update state of active sounds. Should be called once per frame.
     pub fn update_sounds(context: &mut UpdateContext<'_, 'gc>) {
         let Some(root) = context.stage.root_clip() else {
-            return
+            return;
         };
 
+        Self::request_sample_data(context);
+
         // We can't use 'context' to construct an event inside the
         // 'retain()' closure, so we queue the events up here, and fire
         // them after running 'retain()'
@@ -373,6 +483,89 @@ impl<'gc> AudioManager<'gc> {
         context.audio_manager.update_sound_transforms(context.audio);
     }
 
+    /// Dispatches `sampleData` events for playing dynamic sounds until each of
+    /// them has enough samples buffered to last until the next frame.
+    fn request_sample_data(context: &mut UpdateContext<'_, 'gc>) {
+        let sources: Vec<_> = context
+            .audio_manager
+            .sounds
+            .iter()
+            .filter_map(|sound| sound.sample_data.clone())
+            .collect();
+        if sources.is_empty() {
+            return;
+        }
+
+        let frame_samples = f64::from(SampleBuffer::SAMPLE_RATE) / context.frame_rate.max(1.0);
+        let target = frame_samples.ceil() as usize + Self::SAMPLE_DATA_LATENCY;
+
+        for source in sources {
+            while !source.buffer.is_closed() && source.buffer.buffered_frames() < target {
+                if let Err(e) = Self::dispatch_sample_data(context, &source) {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in sampleData event handler: {}",
+                        e
+                    );
+                    source.buffer.close();
+                }
+            }
+        }
+    }
+
+    /// Requests more samples for a dynamic sound with a `sampleData` event, and
+    /// appends whatever the event handlers wrote to the sound's buffer.
+    fn dispatch_sample_data(
+        context: &mut UpdateContext<'_, 'gc>,
+        source: &SampleDataSource<'gc>,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let data = ByteArrayObject::from_storage(&mut activation, ByteArrayStorage::new())?;
+        let event = activation.avm2().classes().sampledataevent.construct(
+            &mut activation,
+            &[
+                "sampleData".into(),
+                false.into(),
+                false.into(),
+                (source.buffer.position() as f64).into(),
+                data.into(),
+            ],
+        )?;
+        Avm2::dispatch_event(&mut activation.context, event, source.sound_object)?;
+
+        let storage = data
+            .as_bytearray()
+            .expect("SampleDataEvent data should be a ByteArray");
+        let (frames, is_last) = read_sample_data(storage.bytes(), storage.endian());
+        source.buffer.append(frames);
+        if is_last {
+            source.buffer.close();
+        }
+
+        Ok(())
+    }
+
+    /// Dispatches the first `sampleData` event of a dynamic sound that has just
+    /// started, so that it happens before `Sound.play` returns.
+    pub fn start_sample_data(
+        context: &mut UpdateContext<'_, 'gc>,
+        instance: SoundInstanceHandle,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let source = context
+            .audio_manager
+            .sounds
+            .iter()
+            .find(|sound| sound.instance == instance)
+            .and_then(|sound| sound.sample_data.clone());
+        if let Some(source) = source {
+            if let Err(e) = Self::dispatch_sample_data(context, &source) {
+                source.buffer.close();
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn start_sound(
         &mut self,
         audio: &mut dyn AudioBackend,
@@ -391,6 +584,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                sample_data: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -488,6 +682,68 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                sample_data: None,
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Starts a dynamic sound, whose samples are requested from the given AVM2
+    /// `Sound` object with `sampleData` events.
+    ///
+    /// `start_position` is the position in sample frames of the first requested samples.
+    pub fn start_sample_data_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        sound_object: Avm2Object<'gc>,
+        start_position: u64,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let buffer = SampleBuffer::with_position(start_position);
+            let handle = audio.start_sample_stream(buffer.clone()).ok()?;
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                sample_data: Some(SampleDataSource {
+                    sound_object,
+                    buffer,
+                }),
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Starts a substream sound, such as the audio track of a `NetStream`.
+    pub fn start_substream(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        substream: Substream,
+        format: &swf::SoundFormat,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = audio.start_substream(substream, format).ok()?;
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                sample_data: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -670,6 +926,22 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The source of samples for a dynamic sound, if this is one.
+    sample_data: Option<SampleDataSource<'gc>>,
+}
+
+/// The source of samples for a dynamic sound, which are generated by an AVM2
+/// `Sound` object in response to `sampleData` events.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct SampleDataSource<'gc> {
+    /// The `Sound` object that `sampleData` events are dispatched to.
+    sound_object: Avm2Object<'gc>,
+
+    /// The buffer that the generated samples are appended to.
+    #[collect(require_static)]
+    buffer: SampleBuffer,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
@@ -709,3 +981,250 @@ impl Default for SoundTransform {
         }
     }
 }
+
+/// A queue of encoded audio packets that is shared between the player and the
+/// audio backend.
+///
+/// The player appends packets as they are demuxed, and the backend decodes them
+/// as the sound plays. Packets must be complete units of audio data in the
+/// substream's format (for example, one ADPCM block or a whole number of MP3
+/// frames).
+#[derive(Clone, Debug, Default)]
+pub struct Substream(Arc<Mutex<SubstreamData>>);
+
+#[derive(Debug, Default)]
+struct SubstreamData {
+    /// Packets that have not been decoded yet.
+    packets: VecDeque<Vec<u8>>,
+
+    /// Whether or not more packets will be appended.
+    is_closed: bool,
+}
+
+impl Substream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a packet of encoded audio to the end of the substream.
+    pub fn append(&self, packet: Vec<u8>) {
+        let mut data = self.0.lock().expect("Substream lock poisoned");
+        data.packets.push_back(packet);
+        data.is_closed = false;
+    }
+
+    /// Mark the substream as complete; the sound ends once its remaining
+    /// packets have been played.
+    pub fn close(&self) {
+        self.0.lock().expect("Substream lock poisoned").is_closed = true;
+    }
+
+    /// Discard all packets that have not been decoded yet.
+    pub fn clear(&self) {
+        self.0
+            .lock()
+            .expect("Substream lock poisoned")
+            .packets
+            .clear();
+    }
+
+    /// Take the next packet from the front of the substream.
+    pub fn pop(&self) -> Option<Vec<u8>> {
+        self.0
+            .lock()
+            .expect("Substream lock poisoned")
+            .packets
+            .pop_front()
+    }
+
+    /// Returns true if there are no packets waiting to be decoded.
+    pub fn is_empty(&self) -> bool {
+        self.0
+            .lock()
+            .expect("Substream lock poisoned")
+            .packets
+            .is_empty()
+    }
+
+    /// Returns true if the substream has been closed and drained.
+    pub fn is_finished(&self) -> bool {
+        let data = self.0.lock().expect("Substream lock poisoned");
+        data.is_closed && data.packets.is_empty()
+    }
+}
+
+/// A queue of sample frames that is shared between the player and the audio
+/// backend, for sounds whose samples are generated by script.
+///
+/// The player appends the samples that `sampleData` event handlers write, and
+/// the backend plays them back. Samples are stereo 32-bit floats at 44.1KHz.
+#[derive(Clone, Debug, Default)]
+pub struct SampleBuffer(Arc<Mutex<SampleBufferData>>);
+
+#[derive(Debug, Default)]
+struct SampleBufferData {
+    /// Sample frames that have not been played yet.
+    frames: VecDeque<[f32; 2]>,
+
+    /// The total number of sample frames ever appended.
+    position: u64,
+
+    /// Whether or not more sample frames will be appended.
+    is_closed: bool,
+}
+
+impl SampleBuffer {
+    /// The sample rate of all generated sounds.
+    pub const SAMPLE_RATE: u16 = 44100;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a buffer whose first sample frame plays at the given position.
+    pub fn with_position(position: u64) -> Self {
+        Self(Arc::new(Mutex::new(SampleBufferData {
+            position,
+            ..Default::default()
+        })))
+    }
+
+    /// Append sample frames to the end of the buffer.
+    pub fn append(&self, frames: impl IntoIterator<Item = [f32; 2]>) {
+        let mut data = self.0.lock().expect("SampleBuffer lock poisoned");
+        let len = data.frames.len();
+        data.frames.extend(frames);
+        data.position += (data.frames.len() - len) as u64;
+    }
+
+    /// Mark the buffer as complete; the sound ends once its remaining sample
+    /// frames have been played.
+    pub fn close(&self) {
+        self.0.lock().expect("SampleBuffer lock poisoned").is_closed = true;
+    }
+
+    /// Take the next sample frame from the front of the buffer.
+    pub fn pop(&self) -> Option<[f32; 2]> {
+        self.0
+            .lock()
+            .expect("SampleBuffer lock poisoned")
+            .frames
+            .pop_front()
+    }
+
+    /// The number of sample frames that have been appended but not yet played.
+    pub fn buffered_frames(&self) -> usize {
+        self.0
+            .lock()
+            .expect("SampleBuffer lock poisoned")
+            .frames
+            .len()
+    }
+
+    /// The position in sample frames at which the next appended frame will
+    /// play, counted from the start of the sound.
+    pub fn position(&self) -> u64 {
+        self.0.lock().expect("SampleBuffer lock poisoned").position
+    }
+
+    /// Returns true if no more sample frames will be appended.
+    pub fn is_closed(&self) -> bool {
+        self.0.lock().expect("SampleBuffer lock poisoned").is_closed
+    }
+
+    /// Returns true if the buffer has been closed and drained.
+    pub fn is_finished(&self) -> bool {
+        let data = self.0.lock().expect("SampleBuffer lock poisoned");
+        data.is_closed && data.frames.is_empty()
+    }
+}
+
+/// Reads the stereo 32-bit float sample frames that a `sampleData` event
+/// handler wrote, up to the most that a single event may provide.
+///
+/// Also returns whether there were too few frames for the sound to continue.
+fn read_sample_data(bytes: &[u8], endian: Endian) -> (Vec<[f32; 2]>, bool) {
+    let samples: Vec<f32> = bytes
+        .chunks_exact(4)
+        .take(AudioManager::SAMPLE_DATA_MAX_SAMPLES * 2)
+        .map(|bytes| {
+            let bytes = bytes.try_into().expect("Chunks are 4 bytes long");
+            match endian {
+                Endian::Big => f32::from_be_bytes(bytes),
+                Endian::Little => f32::from_le_bytes(bytes),
+            }
+        })
+        .collect();
+
+    let frames: Vec<[f32; 2]> = samples
+        .chunks_exact(2)
+        .map(|frame| [frame[0], frame[1]])
+        .collect();
+    let is_last = frames.len() < AudioManager::SAMPLE_DATA_MIN_SAMPLES;
+    (frames, is_last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_data(frames: usize) -> Vec<u8> {
+        (0..frames * 2)
+            .flat_map(|i| (i as f32 / 100.0).to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn sample_data_is_read_as_frames() {
+        let bytes: Vec<u8> = [0.5f32, -0.5, 0.25]
+            .iter()
+            .flat_map(|sample| sample.to_be_bytes())
+            .chain([0, 0])
+            .collect();
+        // The incomplete frame and sample at the end are ignored.
+        let (frames, is_last) = read_sample_data(&bytes, Endian::Big);
+        assert_eq!(frames, [[0.5, -0.5]]);
+        assert!(is_last);
+    }
+
+    #[test]
+    fn sample_data_min_and_max() {
+        let min = AudioManager::SAMPLE_DATA_MIN_SAMPLES;
+        let max = AudioManager::SAMPLE_DATA_MAX_SAMPLES;
+
+        let (frames, is_last) = read_sample_data(&sample_data(min - 1), Endian::Little);
+        assert_eq!(frames.len(), min - 1);
+        assert!(is_last);
+
+        let (frames, is_last) = read_sample_data(&sample_data(min), Endian::Little);
+        assert_eq!(frames.len(), min);
+        assert!(!is_last);
+
+        let (frames, is_last) = read_sample_data(&sample_data(max + 100), Endian::Little);
+        assert_eq!(frames.len(), max);
+        assert_eq!(frames[1], [0.02, 0.03]);
+        assert!(!is_last);
+    }
+
+    #[test]
+    fn sample_buffer_underrun() {
+        let buffer = SampleBuffer::with_position(10);
+        assert_eq!(buffer.pop(), None);
+        assert!(!buffer.is_finished());
+
+        buffer.append([[0.5, 0.5], [1.0, 1.0]]);
+        assert_eq!(buffer.position(), 12);
+        assert_eq!(buffer.buffered_frames(), 2);
+        assert_eq!(buffer.pop(), Some([0.5, 0.5]));
+        assert_eq!(buffer.pop(), Some([1.0, 1.0]));
+        assert_eq!(buffer.pop(), None);
+        assert!(!buffer.is_finished());
+
+        buffer.append([[0.25, 0.25]]);
+        buffer.close();
+        assert!(!buffer.is_finished());
+        assert_eq!(buffer.pop(), Some([0.25, 0.25]));
+        assert!(buffer.is_finished());
+        assert_eq!(buffer.position(), 13);
+    }
+}
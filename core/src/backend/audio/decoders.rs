@@ -12,8 +12,9 @@ pub use adpcm::AdpcmDecoder;
 pub use mp3::{mp3_metadata, Mp3Decoder};
 #[cfg(feature = "nellymoser")]
 pub use nellymoser::NellymoserDecoder;
-pub use pcm::PcmDecoder;
+pub use pcm::{FloatPcmDecoder, PcmDecoder};
 
+use crate::backend::audio::Substream;
 use crate::tag_utils::{ControlFlow, SwfSlice};
 use std::io::{Cursor, Read};
 use swf::{AudioCompression, SoundFormat, TagCode};
@@ -218,6 +219,124 @@ pub fn make_stream_decoder(
     Ok(decoder)
 }
 
+/// Decodes the packets of a `Substream` as they are appended by the player.
+///
+/// The packets are decoded as one continuous stream by a single decoder, so
+/// that state such as the MP3 bit reservoir carries over from one packet to the
+/// next. ADPCM is the exception, as every ADPCM packet starts with a header of
+/// its own and is decoded separately.
+///
+/// While waiting for more data, the decoder outputs silence instead of ending
+/// the sound. Decoding then continues with a new decoder.
+pub struct SubstreamDecoder {
+    format: SoundFormat,
+    substream: Substream,
+    decoder: Option<Box<dyn Decoder>>,
+}
+
+impl SubstreamDecoder {
+    pub fn new(format: &SoundFormat, substream: Substream) -> Self {
+        Self {
+            format: format.clone(),
+            substream,
+            decoder: None,
+        }
+    }
+
+    /// Start decoding the packets at the front of the substream.
+    fn start_decoder(&mut self) {
+        let decoder = if self.format.compression == AudioCompression::Adpcm {
+            let packet = self.substream.pop().unwrap_or_default();
+            make_decoder(&self.format, Cursor::new(packet))
+        } else {
+            make_decoder(&self.format, SubstreamReader::new(self.substream.clone()))
+        };
+
+        match decoder {
+            Ok(decoder) => self.decoder = Some(decoder),
+            Err(e) => {
+                // Skip over packets that we can't decode.
+                tracing::warn!("Couldn't decode substream packet: {}", e);
+                self.decoder = None;
+                self.substream.pop();
+            }
+        }
+    }
+}
+
+impl Decoder for SubstreamDecoder {
+    fn num_channels(&self) -> u8 {
+        if self.format.is_stereo {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn sample_rate(&self) -> u16 {
+        self.format.sample_rate
+    }
+}
+
+impl Iterator for SubstreamDecoder {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sample_frame) = self.decoder.as_mut().and_then(|d| d.next()) {
+                return Some(sample_frame);
+            }
+
+            if self.substream.is_finished() {
+                return None;
+            } else if self.substream.is_empty() {
+                // Buffer underrun; play silence until more data arrives.
+                self.decoder = None;
+                return Some([0, 0]);
+            } else {
+                self.start_decoder();
+            }
+        }
+    }
+}
+
+/// Reads the packets of a `Substream` as one continuous stream of bytes.
+///
+/// A read never returns bytes from more than one packet, so a decoder that
+/// runs out of data always stops at the end of a packet.
+struct SubstreamReader {
+    substream: Substream,
+
+    /// The rest of the packet that is being read.
+    packet: Cursor<Vec<u8>>,
+}
+
+impl SubstreamReader {
+    fn new(substream: Substream) -> Self {
+        Self {
+            substream,
+            packet: Cursor::default(),
+        }
+    }
+}
+
+impl Read for SubstreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let len = self.packet.read(buf)?;
+            if len > 0 || buf.is_empty() {
+                return Ok(len);
+            }
+
+            match self.substream.pop() {
+                Some(packet) => self.packet = Cursor::new(packet),
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
 /// Adds seeking ability to decoders where the underline stream is `std::io::Seek`.
 pub trait SeekableDecoder: Decoder {
     /// Resets the decoder to the beginning of the stream.
@@ -370,3 +489,45 @@ pub struct Mp3Metadata {
     pub sample_rate: u16,
     pub num_sample_frames: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pcm_format() -> SoundFormat {
+        SoundFormat {
+            compression: AudioCompression::Uncompressed,
+            sample_rate: 44100,
+            is_stereo: false,
+            is_16_bit: true,
+        }
+    }
+
+    #[test]
+    fn substream_is_one_stream() {
+        let substream = Substream::new();
+        let decoder = SubstreamDecoder::new(&pcm_format(), substream.clone());
+
+        // The second sample is split between the two packets.
+        substream.append(vec![1, 0, 2]);
+        substream.append(vec![0, 3, 0]);
+        substream.close();
+        assert_eq!(decoder.collect::<Vec<_>>(), [[1, 1], [2, 2], [3, 3]]);
+    }
+
+    #[test]
+    fn substream_underrun() {
+        let substream = Substream::new();
+        let mut decoder = SubstreamDecoder::new(&pcm_format(), substream.clone());
+        assert_eq!(decoder.next(), Some([0, 0]));
+
+        substream.append(vec![5, 0]);
+        assert_eq!(decoder.next(), Some([5, 5]));
+        assert_eq!(decoder.next(), Some([0, 0]));
+
+        substream.append(vec![6, 0]);
+        substream.close();
+        assert_eq!(decoder.next(), Some([6, 6]));
+        assert_eq!(decoder.next(), None);
+    }
+}
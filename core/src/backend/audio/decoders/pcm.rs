@@ -75,3 +75,70 @@ impl<R: AsRef<[u8]> + Send + Sync> SeekableDecoder for PcmDecoder<Cursor<R>> {
         self.inner.set_position(pos);
     }
 }
+
+/// Decoder for 32-bit float PCM audio data, such as the samples given to
+/// `Sound.loadPCMFromByteArray`.
+pub struct FloatPcmDecoder<R: Read> {
+    inner: R,
+    sample_rate: u16,
+    is_stereo: bool,
+}
+
+impl<R: Read> FloatPcmDecoder<R> {
+    pub fn new(inner: R, is_stereo: bool, sample_rate: u16) -> Self {
+        Self {
+            inner,
+            is_stereo,
+            sample_rate,
+        }
+    }
+
+    /// Returns the next sample frame without converting it to 16-bit.
+    pub fn next_float_frame(&mut self) -> Option<[f32; 2]> {
+        let left = self.inner.read_f32::<LittleEndian>().ok()?;
+        let right = if self.is_stereo {
+            self.inner.read_f32::<LittleEndian>().ok()?
+        } else {
+            left
+        };
+        Some([left, right])
+    }
+}
+
+impl<R: Read> Iterator for FloatPcmDecoder<R> {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let frame = self.next_float_frame()?;
+        Some(frame.map(|sample| (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16))
+    }
+}
+
+impl<R: Read + Send + Sync> Decoder for FloatPcmDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        if self.is_stereo {
+            2
+        } else {
+            1
+        }
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+impl<R: AsRef<[u8]> + Send + Sync> SeekableDecoder for FloatPcmDecoder<Cursor<R>> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.set_position(0);
+    }
+
+    #[inline]
+    fn seek_to_sample_frame(&mut self, frame: u32) {
+        let pos = u64::from(frame) * u64::from(self.num_channels()) * 4;
+        self.inner.set_position(pos);
+    }
+}
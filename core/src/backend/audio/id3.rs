@@ -0,0 +1,295 @@
+//! Reading of ID3 metadata tags from MP3 files.
+
+/// A text frame read from an ID3 tag, such as the song title.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Id3Frame {
+    /// The ID3v2.3 frame identifier, such as `TIT2`.
+    pub id: String,
+
+    /// The decoded text of the frame.
+    pub text: String,
+}
+
+/// Reads the text frames of the ID3 tag at the start of the given MP3 data.
+///
+/// If the data has no ID3v2 tag, the ID3v1 tag at the end of the data is used
+/// instead, with its fields translated into the equivalent ID3v2 frames.
+/// Returns an empty list if the data has no readable tag.
+pub fn read_id3_frames(data: &[u8]) -> Vec<Id3Frame> {
+    read_id3v2(data).unwrap_or_else(|| read_id3v1(data).unwrap_or_default())
+}
+
+/// Decodes a 28-bit "synchsafe" integer, where the top bit of each byte is zero.
+fn synchsafe(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .fold(0, |acc, &byte| (acc << 7) | usize::from(byte & 0x7f))
+}
+
+fn big_endian(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .fold(0, |acc, &byte| (acc << 8) | usize::from(byte))
+}
+
+fn read_id3v2(data: &[u8]) -> Option<Vec<Id3Frame>> {
+    let header = data.get(..10)?;
+    if &header[..3] != b"ID3" {
+        return None;
+    }
+    let version = header[3];
+    let flags = header[5];
+    let tag_size = synchsafe(&header[6..10]);
+    let mut tag = data.get(10..)?;
+    tag = &tag[..tag_size.min(tag.len())];
+
+    // Skip the extended header, if there is one.
+    if flags & 0x40 != 0 {
+        let size = match version {
+            3 => big_endian(tag.get(..4)?) + 4,
+            _ => synchsafe(tag.get(..4)?),
+        };
+        tag = tag.get(size..)?;
+    }
+
+    let (id_len, header_len) = if version == 2 { (3, 6) } else { (4, 10) };
+    let mut frames = Vec::new();
+    while tag.len() >= header_len {
+        let id = &tag[..id_len];
+        // The rest of the tag is padding.
+        if id[0] == 0 {
+            break;
+        }
+        let size = match version {
+            2 => big_endian(&tag[3..6]),
+            3 => big_endian(&tag[4..8]),
+            _ => synchsafe(&tag[4..8]),
+        };
+        let Some(body) = tag.get(header_len..header_len + size) else {
+            break;
+        };
+        tag = &tag[header_len + size..];
+
+        let id = match std::str::from_utf8(id) {
+            Ok(id) if version == 2 => match upgrade_v22_id(id) {
+                Some(id) => id,
+                None => continue,
+            },
+            Ok(id) => id,
+            Err(_) => break,
+        };
+        let text = match id {
+            "COMM" => body.get(4..).map(|text| {
+                // Skip the language code and the short content description.
+                let encoding = body[0];
+                let (_description, text) = split_terminated(encoding, text);
+                decode_text(encoding, text)
+            }),
+            id if id.starts_with('T') && id != "TXXX" => body
+                .split_first()
+                .map(|(&encoding, text)| decode_text(encoding, text)),
+            _ => None,
+        };
+        if let Some(text) = text {
+            frames.push(Id3Frame {
+                id: id.to_string(),
+                text,
+            });
+        }
+    }
+
+    Some(frames)
+}
+
+/// Translates the three character identifier of an ID3v2.2 frame to the
+/// equivalent ID3v2.3 identifier, for the frames we care about.
+fn upgrade_v22_id(id: &str) -> Option<&'static str> {
+    Some(match id {
+        "TT2" => "TIT2",
+        "TP1" => "TPE1",
+        "TAL" => "TALB",
+        "TYE" => "TYER",
+        "TCO" => "TCON",
+        "TRK" => "TRCK",
+        "COM" => "COMM",
+        _ => return None,
+    })
+}
+
+/// Splits a null-terminated string in the given encoding off the front of `data`.
+fn split_terminated(encoding: u8, data: &[u8]) -> (&[u8], &[u8]) {
+    let terminator = if encoding == 1 || encoding == 2 {
+        data.chunks_exact(2)
+            .position(|pair| pair == [0, 0])
+            .map(|i| (i * 2, 2))
+    } else {
+        data.iter().position(|&byte| byte == 0).map(|i| (i, 1))
+    };
+    match terminator {
+        Some((end, len)) => (&data[..end], &data[end + len..]),
+        None => (data, &[]),
+    }
+}
+
+/// Decodes the text of a frame, dropping any trailing terminator.
+fn decode_text(encoding: u8, data: &[u8]) -> String {
+    let (text, _) = split_terminated(encoding, data);
+    match encoding {
+        // UTF-16 with a byte order mark, or big endian UTF-16.
+        1 | 2 => {
+            let mut units = text
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .peekable();
+            let little_endian = match units.peek() {
+                Some(0xfeff) => {
+                    units.next();
+                    false
+                }
+                Some(0xfffe) => {
+                    units.next();
+                    true
+                }
+                _ => false,
+            };
+            let units = units.map(|unit| {
+                if little_endian {
+                    unit.swap_bytes()
+                } else {
+                    unit
+                }
+            });
+            char::decode_utf16(units)
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect()
+        }
+        3 => String::from_utf8_lossy(text).into_owned(),
+        // ISO-8859-1.
+        _ => text.iter().map(|&byte| char::from(byte)).collect(),
+    }
+}
+
+fn read_id3v1(data: &[u8]) -> Option<Vec<Id3Frame>> {
+    let tag = data.get(data.len().checked_sub(128)?..)?;
+    if &tag[..3] != b"TAG" {
+        return None;
+    }
+
+    let field = |range: std::ops::Range<usize>| {
+        let text = decode_text(0, &tag[range]);
+        text.trim_end().to_string()
+    };
+    let mut fields = vec![
+        ("TIT2", field(3..33)),
+        ("TPE1", field(33..63)),
+        ("TALB", field(63..93)),
+        ("TYER", field(93..97)),
+    ];
+    // ID3v1.1 stores the track number in the last byte of the comment.
+    if tag[125] == 0 && tag[126] != 0 {
+        fields.push(("COMM", field(97..125)));
+        fields.push(("TRCK", tag[126].to_string()));
+    } else {
+        fields.push(("COMM", field(97..127)));
+    }
+    // Genres are an index into a fixed list, which ID3v2 writes as "(n)".
+    if tag[127] != 0xff {
+        fields.push(("TCON", format!("({})", tag[127])));
+    }
+
+    Some(
+        fields
+            .into_iter()
+            .filter(|(_, text)| !text.is_empty())
+            .map(|(id, text)| Id3Frame {
+                id: id.to_string(),
+                text,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(id: &str, text: &str) -> Id3Frame {
+        Id3Frame {
+            id: id.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn id3v23_text_frames() {
+        let mut frames = Vec::new();
+        for (id, body) in [
+            (&b"TIT2"[..], &b"\x00Song\x00"[..]),
+            (b"TPE1", b"\x03Artist \xc3\xa9"),
+            (b"COMM", b"\x00engdesc\x00Comment"),
+            (b"APIC", b"\x00image"),
+        ] {
+            frames.extend_from_slice(id);
+            frames.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            frames.extend_from_slice(&[0, 0]);
+            frames.extend_from_slice(body);
+        }
+        frames.extend_from_slice(&[0; 16]);
+
+        let mut data = b"ID3\x03\x00\x00".to_vec();
+        let size = frames.len();
+        data.extend([
+            (size >> 21) as u8 & 0x7f,
+            (size >> 14) as u8 & 0x7f,
+            (size >> 7) as u8 & 0x7f,
+            size as u8 & 0x7f,
+        ]);
+        data.extend(frames);
+        data.extend_from_slice(b"\xff\xfb");
+
+        assert_eq!(
+            read_id3_frames(&data),
+            vec![
+                frame("TIT2", "Song"),
+                frame("TPE1", "Artist é"),
+                frame("COMM", "Comment"),
+            ]
+        );
+    }
+
+    #[test]
+    fn id3v2_utf16() {
+        assert_eq!(decode_text(1, b"\xff\xfeA\x00B\x00\x00\x00"), "AB");
+        assert_eq!(decode_text(1, b"\xfe\xff\x00A\x00B"), "AB");
+        assert_eq!(decode_text(2, b"\x00A\x00B"), "AB");
+    }
+
+    #[test]
+    fn id3v1_fallback() {
+        let mut tag = [0u8; 128];
+        tag[..3].copy_from_slice(b"TAG");
+        tag[3..7].copy_from_slice(b"Song");
+        tag[33..39].copy_from_slice(b"Artist");
+        tag[93..97].copy_from_slice(b"1999");
+        tag[126] = 7;
+        tag[127] = 17;
+        let mut data = vec![0xff, 0xfb, 0x90, 0x00];
+        data.extend_from_slice(&tag);
+
+        assert_eq!(
+            read_id3_frames(&data),
+            vec![
+                frame("TIT2", "Song"),
+                frame("TPE1", "Artist"),
+                frame("TYER", "1999"),
+                frame("TRCK", "7"),
+                frame("TCON", "(17)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_tag() {
+        assert!(read_id3_frames(&[0xff, 0xfb, 0x90, 0x00]).is_empty());
+    }
+}
@@ -1,5 +1,5 @@
-use super::decoders::{self, AdpcmDecoder, Decoder, PcmDecoder, SeekableDecoder};
-use super::{SoundHandle, SoundInstanceHandle, SoundTransform};
+use super::decoders::{self, AdpcmDecoder, Decoder, FloatPcmDecoder, PcmDecoder, SeekableDecoder};
+use super::{SampleBuffer, SoundHandle, SoundInstanceHandle, SoundTransform, Substream};
 use crate::backend::audio::{DecodeError, RegisterError};
 use crate::tag_utils::SwfSlice;
 use generational_arena::Arena;
@@ -86,6 +86,64 @@ trait Stream: dasp::signal::Signal<Frame = [i16; 2]> + Send + Sync {
     fn source_sample_rate(&self) -> u16;
 }
 
+/// A stream that plays the sample frames appended to a `SampleBuffer`.
+///
+/// The stream outputs silence without advancing its position while it waits
+/// for more sample frames.
+struct SampleBufferStream {
+    buffer: SampleBuffer,
+    position: u32,
+    is_exhausted: bool,
+}
+
+impl SampleBufferStream {
+    /// Creates a stream that starts at the position of the next frame appended to the buffer.
+    fn new(buffer: SampleBuffer) -> Self {
+        let position = buffer.position() as u32;
+        Self {
+            buffer,
+            position,
+            is_exhausted: false,
+        }
+    }
+}
+
+impl Stream for SampleBufferStream {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        self.position
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        SampleBuffer::SAMPLE_RATE
+    }
+}
+
+impl dasp::signal::Signal for SampleBufferStream {
+    type Frame = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> [i16; 2] {
+        use dasp::Sample;
+        if let Some([left, right]) = self.buffer.pop() {
+            self.position += 1;
+            [
+                left.clamp(-1.0, 1.0).to_sample(),
+                right.clamp(-1.0, 1.0).to_sample(),
+            ]
+        } else {
+            self.is_exhausted = self.buffer.is_finished();
+            Default::default()
+        }
+    }
+
+    #[inline]
+    fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
 /// A stream that wraps a `Decoder`.
 struct DecoderStream<D> {
     decoder: D,
@@ -158,6 +216,16 @@ struct Sound {
     /// `skip_sample_frames` indicates how many sample frames to skip to bypass the delay.
     /// This is `0` unless `format.compression` is `AudioCompression::Mp3`.
     skip_sample_frames: u16,
+
+    /// Whether `data` holds 32-bit float PCM samples, as registered by
+    /// `AudioMixer::register_float_pcm`.
+    ///
+    /// `format.compression` is `AudioCompression::Uncompressed` for these sounds.
+    is_float: bool,
+
+    /// The whole sound decoded to 44.1KHz stereo, once it has been requested by
+    /// `AudioMixer::get_sound_samples`.
+    decoded: Option<Box<[[f32; 2]]>>,
 }
 
 /// An actively playing instance of a sound.
@@ -291,9 +359,18 @@ impl AudioMixer {
     ///  * "Event" sounds on the timeline with custom start/loop settings
     ///  * ActionScript sounds that may have a custom start and loop setting
     fn make_seekable_decoder(
-        format: &swf::SoundFormat,
+        sound: &Sound,
         data: Cursor<ArcAsRef>,
     ) -> Result<Box<dyn SeekableDecoder>, decoders::Error> {
+        let format = &sound.format;
+        if sound.is_float {
+            return Ok(Box::new(FloatPcmDecoder::new(
+                data,
+                format.is_stereo,
+                format.sample_rate,
+            )));
+        }
+
         let decoder: Box<dyn SeekableDecoder> = match format.compression {
             AudioCompression::UncompressedUnknownEndian => {
                 // Cross fingers that it's little endian.
@@ -354,7 +431,7 @@ impl AudioMixer {
         data: Cursor<ArcAsRef>,
     ) -> Result<Box<dyn Stream>, DecodeError> {
         // Instantiate a decoder for the compression that the sound data uses.
-        let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+        let decoder = Self::make_seekable_decoder(sound, data)?;
 
         // Wrap the decoder into an event sound stream (controls looping/envelope)
         let stream = EventSoundStream::new_with_settings(
@@ -504,6 +581,8 @@ impl AudioMixer {
             data: Arc::from(data),
             num_sample_frames: swf_sound.num_samples,
             skip_sample_frames,
+            is_float: false,
+            decoded: None,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -524,6 +603,8 @@ impl AudioMixer {
             data,
             num_sample_frames: metadata.num_sample_frames,
             skip_sample_frames: 0,
+            is_float: false,
+            decoded: None,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -533,6 +614,36 @@ impl AudioMixer {
         Err(decoders::Error::UnhandledCompression(AudioCompression::Mp3))
     }
 
+    /// Registers a sound made of 32-bit float PCM samples with the audio mixer.
+    ///
+    /// The samples of stereo sounds are interleaved.
+    pub fn register_float_pcm(
+        &mut self,
+        samples: &[f32],
+        is_stereo: bool,
+        sample_rate: u16,
+    ) -> SoundHandle {
+        let num_channels = if is_stereo { 2 } else { 1 };
+        let data: Vec<u8> = samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        let sound = Sound {
+            format: swf::SoundFormat {
+                compression: AudioCompression::Uncompressed,
+                sample_rate,
+                is_stereo,
+                is_16_bit: false,
+            },
+            data: Arc::from(data),
+            num_sample_frames: (samples.len() / num_channels) as u32,
+            skip_sample_frames: 0,
+            is_float: true,
+            decoded: None,
+        };
+        self.sounds.insert(sound)
+    }
+
     /// Starts a timeline audio stream.
     pub fn start_stream(
         &mut self,
@@ -554,6 +665,41 @@ impl AudioMixer {
         Ok(handle)
     }
 
+    /// Starts a substream sound, whose data is appended by the player as it plays.
+    pub fn start_substream(
+        &mut self,
+        substream: Substream,
+        format: &swf::SoundFormat,
+    ) -> Result<SoundInstanceHandle, DecodeError> {
+        let decoder = decoders::SubstreamDecoder::new(format, substream);
+        let stream = DecoderStream::new(decoder);
+        let stream = Box::new(self.make_resampler(stream));
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let handle = sound_instances.insert(SoundInstance::new_stream(stream));
+        Ok(handle)
+    }
+
+    /// Starts a sound whose sample frames are generated by script and appended
+    /// to `buffer` as it plays.
+    pub fn start_sample_stream(
+        &mut self,
+        buffer: SampleBuffer,
+    ) -> Result<SoundInstanceHandle, DecodeError> {
+        let stream = SampleBufferStream::new(buffer);
+        let stream = Box::new(self.make_resampler(stream));
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let handle = sound_instances.insert(SoundInstance::new_stream(stream));
+        Ok(handle)
+    }
+
     /// Starts a sound.
     ///
     /// The sound must have been registered using `AudioMixer::register_sound`.
@@ -566,6 +712,7 @@ impl AudioMixer {
         let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
         // Create a stream that decodes and resamples the sound.
         let stream = if sound.skip_sample_frames == 0
+            && !sound.is_float
             && settings.in_sample.is_none()
             && settings.out_sample.is_none()
             && settings.num_loops <= 1
@@ -658,6 +805,98 @@ impl AudioMixer {
         self.sounds.get(sound).map(|s| &s.format)
     }
 
+    /// Returns up to `num_frames` sample frames of a registered sound, starting
+    /// at sample frame `position`, decoded and resampled to 44.1KHz stereo.
+    ///
+    /// The whole sound is decoded the first time its samples are requested.
+    /// Returns `None` if the sound is not registered or could not be decoded.
+    pub fn get_sound_samples(
+        &mut self,
+        sound: SoundHandle,
+        position: u32,
+        num_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        let sound = self.sounds.get_mut(sound)?;
+        if sound.decoded.is_none() {
+            match Self::decode_sound(sound) {
+                Ok(decoded) => sound.decoded = Some(decoded.into()),
+                Err(e) => {
+                    tracing::error!("Couldn't decode sound to extract its samples: {}", e);
+                    return None;
+                }
+            }
+        }
+        let decoded = sound.decoded.as_deref()?;
+
+        let start = (position as usize).min(decoded.len());
+        let end = start.saturating_add(num_frames as usize).min(decoded.len());
+        Some(decoded[start..end].to_vec())
+    }
+
+    /// Decodes a whole sound and resamples it to 44.1KHz.
+    ///
+    /// Float PCM sounds keep their full precision.
+    fn decode_sound(sound: &Sound) -> Result<Vec<[f32; 2]>, DecodeError> {
+        use dasp::Sample;
+
+        let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+        if sound.is_float {
+            let mut decoder =
+                FloatPcmDecoder::new(data, sound.format.is_stereo, sound.format.sample_rate);
+            let frames = std::iter::from_fn(|| decoder.next_float_frame());
+            return Ok(Self::resample_sound(
+                frames,
+                sound.format.sample_rate.into(),
+                sound.num_sample_frames,
+            ));
+        }
+
+        let decoder = decoders::make_decoder(&sound.format, data)?;
+        let sample_rate = u32::from(decoder.sample_rate());
+        let frames = decoder
+            .skip(sound.skip_sample_frames.into())
+            .take(sound.num_sample_frames as usize);
+        Ok(
+            Self::resample_sound(frames, sample_rate, sound.num_sample_frames)
+                .into_iter()
+                .map(|[left, right]| [left.to_sample(), right.to_sample()])
+                .collect(),
+        )
+    }
+
+    /// Resamples the `num_sample_frames` sample frames of a whole sound to 44.1KHz.
+    fn resample_sound<F>(
+        frames: impl Iterator<Item = F>,
+        sample_rate: u32,
+        num_sample_frames: u32,
+    ) -> Vec<F>
+    where
+        F: dasp::Frame,
+        F::Sample: dasp::sample::Duplex<f64>,
+    {
+        use dasp::signal::Signal;
+        const SAMPLE_RATE: u32 = 44100;
+
+        let frames = frames.take(num_sample_frames as usize);
+        if sample_rate == SAMPLE_RATE {
+            return frames.collect();
+        }
+
+        let num_frames =
+            u64::from(num_sample_frames) * u64::from(SAMPLE_RATE) / u64::from(sample_rate);
+        let mut signal = dasp::signal::from_iter(frames);
+        let left = signal.next();
+        let right = signal.next();
+        let interpolator = dasp::interpolate::linear::Linear::new(left, right);
+        let converter = dasp::signal::interpolate::Converter::from_hz_to_hz(
+            signal,
+            interpolator,
+            sample_rate.into(),
+            SAMPLE_RATE.into(),
+        );
+        converter.take(num_frames as usize).collect()
+    }
+
     /// Sets the sound transform for the given playing sound.
     pub fn set_sound_transform(
         &mut self,
@@ -1050,6 +1289,17 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.register_mp3(data)
         }
 
+        #[inline]
+        fn register_float_pcm(
+            &mut self,
+            samples: &[f32],
+            is_stereo: bool,
+            sample_rate: u16,
+        ) -> SoundHandle {
+            self.$mixer
+                .register_float_pcm(samples, is_stereo, sample_rate)
+        }
+
         #[inline]
         fn start_stream(
             &mut self,
@@ -1062,6 +1312,23 @@ macro_rules! impl_audio_mixer_backend {
                 .start_stream(stream_handle, clip_frame, clip_data, stream_info)
         }
 
+        #[inline]
+        fn start_substream(
+            &mut self,
+            substream: $crate::backend::audio::Substream,
+            format: &swf::SoundFormat,
+        ) -> Result<SoundInstanceHandle, DecodeError> {
+            self.$mixer.start_substream(substream, format)
+        }
+
+        #[inline]
+        fn start_sample_stream(
+            &mut self,
+            buffer: $crate::backend::audio::SampleBuffer,
+        ) -> Result<SoundInstanceHandle, DecodeError> {
+            self.$mixer.start_sample_stream(buffer)
+        }
+
         #[inline]
         fn start_sound(
             &mut self,
@@ -1101,6 +1368,16 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.get_sound_format(sound)
         }
 
+        #[inline]
+        fn get_sound_samples(
+            &mut self,
+            sound: SoundHandle,
+            position: u32,
+            num_frames: u32,
+        ) -> Option<Vec<[f32; 2]>> {
+            self.$mixer.get_sound_samples(sound, position, num_frames)
+        }
+
         #[inline]
         fn set_sound_transform(
             &mut self,
@@ -1130,3 +1407,47 @@ macro_rules! impl_audio_mixer_backend {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dasp::signal::Signal;
+
+    #[test]
+    fn sample_buffer_stream_underrun() {
+        let buffer = SampleBuffer::new();
+        let mut stream = SampleBufferStream::new(buffer.clone());
+
+        // While waiting for samples, the stream is silent and doesn't advance.
+        assert_eq!(stream.next(), [0, 0]);
+        assert_eq!(stream.source_position(), 0);
+        assert!(!stream.is_exhausted());
+
+        buffer.append([[1.0, 0.0], [2.0, 0.0]]);
+        let first = stream.next();
+        assert_ne!(first, [0, 0]);
+        // Samples are clamped to the valid range.
+        assert_eq!(stream.next(), first);
+        assert_eq!(stream.source_position(), 2);
+
+        assert_eq!(stream.next(), [0, 0]);
+        assert_eq!(stream.source_position(), 2);
+        assert!(!stream.is_exhausted());
+
+        buffer.close();
+        stream.next();
+        assert!(stream.is_exhausted());
+    }
+
+    #[test]
+    fn sample_buffer_stream_start_position() {
+        let buffer = SampleBuffer::with_position(44100);
+        let mut stream = SampleBufferStream::new(buffer.clone());
+        assert_eq!(stream.source_position(), 44100);
+
+        buffer.append([[0.0, 0.0]]);
+        stream.next();
+        assert_eq!(stream.source_position(), 44101);
+        assert_eq!(buffer.position(), 44101);
+    }
+}
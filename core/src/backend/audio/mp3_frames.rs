@@ -0,0 +1,261 @@
+//! Splitting of MP3 data into whole frames as it is downloaded.
+
+/// Collects MP3 data as it arrives, and hands it back as runs of whole frames
+/// that can be decoded on their own.
+///
+/// ID3v2 tags at the start of the data and any bytes between frames are
+/// skipped.
+#[derive(Debug, Default)]
+pub struct Mp3FrameSplitter {
+    /// Data that has not been returned yet.
+    buffer: Vec<u8>,
+
+    /// The number of bytes still to skip, such as the rest of an ID3 tag.
+    skip: usize,
+
+    /// Whether any data has been seen yet, for detecting a leading ID3 tag.
+    has_started: bool,
+
+    /// The sample rate of the first frame.
+    sample_rate: Option<u16>,
+
+    /// The number of sample frames in all of the frames returned so far.
+    num_sample_frames: u64,
+}
+
+/// The properties of an MP3 frame, read from its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FrameHeader {
+    /// The length of the frame, including its header.
+    length: usize,
+
+    sample_rate: u16,
+
+    /// The number of sample frames that the frame decodes to.
+    num_sample_frames: u16,
+}
+
+impl Mp3FrameSplitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The sample rate of the MP3 data, once its first frame has been read.
+    pub fn sample_rate(&self) -> Option<u16> {
+        self.sample_rate
+    }
+
+    /// The duration of the frames returned so far, in milliseconds.
+    pub fn duration(&self) -> f64 {
+        match self.sample_rate {
+            Some(sample_rate) => self.num_sample_frames as f64 * 1000.0 / f64::from(sample_rate),
+            None => 0.0,
+        }
+    }
+
+    /// Append downloaded data, returning all of the whole frames that are now
+    /// available.
+    ///
+    /// Returns `None` if no new frames were completed.
+    pub fn push(&mut self, data: &[u8]) -> Option<Vec<u8>> {
+        let skipped = self.skip.min(data.len());
+        self.skip -= skipped;
+        self.buffer.extend_from_slice(&data[skipped..]);
+
+        if !self.has_started {
+            if self.buffer.len() < 10 {
+                return None;
+            }
+            self.has_started = true;
+            if self.buffer.starts_with(b"ID3") {
+                let flags = self.buffer[5];
+                let mut tag_length = 10 + synchsafe(&self.buffer[6..10]);
+                // The tag is followed by a footer.
+                if flags & 0x10 != 0 {
+                    tag_length += 10;
+                }
+                let skipped = tag_length.min(self.buffer.len());
+                self.buffer.drain(..skipped);
+                self.skip = tag_length - skipped;
+            }
+        }
+
+        let mut pos = 0;
+        let mut end = 0;
+        while let Some(header) = self.buffer.get(pos..pos + 4) {
+            match FrameHeader::read(header) {
+                Some(header)
+                    if self.sample_rate.unwrap_or(header.sample_rate) == header.sample_rate =>
+                {
+                    if pos + header.length > self.buffer.len() {
+                        break;
+                    }
+                    if end < pos {
+                        // Drop the junk between the previous frame and this one.
+                        self.buffer.drain(end..pos);
+                        pos = end;
+                    }
+                    self.sample_rate = Some(header.sample_rate);
+                    self.num_sample_frames += u64::from(header.num_sample_frames);
+                    pos += header.length;
+                    end = pos;
+                }
+                _ => pos += 1,
+            }
+        }
+
+        if end == 0 {
+            return None;
+        }
+        let rest = self.buffer.split_off(end);
+        Some(std::mem::replace(&mut self.buffer, rest))
+    }
+}
+
+impl FrameHeader {
+    /// Reads the four byte header at the start of an MP3 frame.
+    fn read(header: &[u8]) -> Option<Self> {
+        if header[0] != 0xff || header[1] & 0xe0 != 0xe0 {
+            return None;
+        }
+
+        // 0 = MPEG 2.5, 2 = MPEG 2, 3 = MPEG 1
+        let version = (header[1] >> 3) & 0b11;
+        // 1 = Layer III, 2 = Layer II, 3 = Layer I
+        let layer = (header[1] >> 1) & 0b11;
+        let bitrate_index = usize::from(header[2] >> 4);
+        let sample_rate_index = usize::from((header[2] >> 2) & 0b11);
+        let padding = usize::from((header[2] >> 1) & 1);
+        if version == 1 || layer == 0 || bitrate_index == 0 || bitrate_index == 15 {
+            return None;
+        }
+
+        const BITRATES: [[u16; 14]; 5] = [
+            // MPEG 1, Layer I
+            [
+                32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+            ],
+            // MPEG 1, Layer II
+            [
+                32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+            ],
+            // MPEG 1, Layer III
+            [
+                32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+            ],
+            // MPEG 2 and 2.5, Layer I
+            [
+                32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+            ],
+            // MPEG 2 and 2.5, Layers II and III
+            [8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+        ];
+        const SAMPLE_RATES: [[u16; 3]; 3] = [
+            [44100, 48000, 32000],
+            [22050, 24000, 16000],
+            [11025, 12000, 8000],
+        ];
+
+        let is_mpeg1 = version == 3;
+        let bitrates = match (is_mpeg1, layer) {
+            (true, 3) => &BITRATES[0],
+            (true, 2) => &BITRATES[1],
+            (true, _) => &BITRATES[2],
+            (false, 3) => &BITRATES[3],
+            (false, _) => &BITRATES[4],
+        };
+        let bitrate = usize::from(bitrates[bitrate_index - 1]) * 1000;
+        let sample_rates = match version {
+            3 => &SAMPLE_RATES[0],
+            2 => &SAMPLE_RATES[1],
+            _ => &SAMPLE_RATES[2],
+        };
+        let sample_rate = *sample_rates.get(sample_rate_index)?;
+
+        let (length, num_sample_frames) = match layer {
+            3 => ((12 * bitrate / usize::from(sample_rate) + padding) * 4, 384),
+            2 => (144 * bitrate / usize::from(sample_rate) + padding, 1152),
+            _ if is_mpeg1 => (144 * bitrate / usize::from(sample_rate) + padding, 1152),
+            _ => (72 * bitrate / usize::from(sample_rate) + padding, 576),
+        };
+
+        Some(Self {
+            length,
+            sample_rate,
+            num_sample_frames,
+        })
+    }
+}
+
+/// Decodes a 28-bit "synchsafe" integer, where the top bit of each byte is zero.
+fn synchsafe(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .fold(0, |acc, &byte| (acc << 7) | usize::from(byte & 0x7f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An MPEG 1 Layer III frame at 32kbps and 44.1KHz, which is 104 bytes long.
+    fn frame() -> Vec<u8> {
+        let mut frame = vec![0; 104];
+        frame[..4].copy_from_slice(&[0xff, 0xfb, 0x10, 0xc4]);
+        frame
+    }
+
+    #[test]
+    fn frame_header() {
+        assert_eq!(
+            FrameHeader::read(&[0xff, 0xfb, 0x10, 0xc4]),
+            Some(FrameHeader {
+                length: 104,
+                sample_rate: 44100,
+                num_sample_frames: 1152,
+            })
+        );
+        // MPEG 2, Layer III, 64kbps, 22.05KHz, padded.
+        assert_eq!(
+            FrameHeader::read(&[0xff, 0xf3, 0x82, 0xc4]),
+            Some(FrameHeader {
+                length: 209,
+                sample_rate: 22050,
+                num_sample_frames: 576,
+            })
+        );
+        assert_eq!(FrameHeader::read(&[0xff, 0xfb, 0xf0, 0xc4]), None);
+        assert_eq!(FrameHeader::read(b"TAG\0"), None);
+    }
+
+    #[test]
+    fn split_in_chunks() {
+        let data = [frame(), frame(), frame()].concat();
+        let mut splitter = Mp3FrameSplitter::new();
+
+        assert_eq!(splitter.push(&data[..50]), None);
+        assert_eq!(splitter.sample_rate(), None);
+        assert_eq!(splitter.push(&data[50..150]), Some(frame()));
+        assert_eq!(splitter.sample_rate(), Some(44100));
+        assert_eq!(
+            splitter.push(&data[150..]),
+            Some([frame(), frame()].concat())
+        );
+        assert_eq!(splitter.duration(), 3.0 * 1152.0 * 1000.0 / 44100.0);
+    }
+
+    #[test]
+    fn skip_id3_and_junk() {
+        let mut data = b"ID3\x03\0\0\0\0\0\x05hello".to_vec();
+        data.extend_from_slice(&frame());
+        data.extend_from_slice(b"junk");
+        data.extend_from_slice(&frame());
+
+        let mut splitter = Mp3FrameSplitter::new();
+        assert_eq!(splitter.push(&data[..12]), None);
+        assert_eq!(
+            splitter.push(&data[12..]),
+            Some([frame(), frame()].concat())
+        );
+    }
+}
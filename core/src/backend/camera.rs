@@ -0,0 +1,400 @@
+//! Video capture for `Camera`.
+
+use ruffle_render::bitmap::{Bitmap, BitmapFormat};
+use std::time::Duration;
+
+/// A source of captured video, such as a webcam.
+///
+/// Cameras are identified by their index in the list returned by `names`.
+/// Frames are opaque RGBA bitmaps of whatever size the camera captures at;
+/// the player scales them to the size that the movie asked for.
+pub trait CameraBackend {
+    /// The names of the available cameras.
+    fn names(&self) -> Vec<String>;
+
+    /// Start capturing video from a camera.
+    ///
+    /// Returns `false` if the camera is unavailable, or the user did not
+    /// allow access to it.
+    fn open(&mut self, index: usize) -> bool;
+
+    /// Take the newest frame that an open camera has captured since the last
+    /// call, or `None` if it hasn't captured anything new.
+    ///
+    /// `elapsed` is how much time the player has advanced by since the last
+    /// call. Backends that capture in real time may ignore it.
+    fn capture(&mut self, index: usize, elapsed: Duration) -> Option<Bitmap>;
+}
+
+/// A camera backend for when there are no cameras.
+#[derive(Default)]
+pub struct NullCameraBackend;
+
+impl NullCameraBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CameraBackend for NullCameraBackend {
+    fn names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn open(&mut self, _index: usize) -> bool {
+        false
+    }
+
+    fn capture(&mut self, _index: usize, _elapsed: Duration) -> Option<Bitmap> {
+        None
+    }
+}
+
+/// Errors that can occur when reading the frames of a `SequenceCameraBackend`.
+#[derive(Debug, thiserror::Error)]
+pub enum CameraError {
+    #[error("Camera has no frames")]
+    NoFrames,
+
+    #[error("Invalid camera frame: {0}")]
+    InvalidImage(#[from] ruffle_render::error::Error),
+
+    #[error("Not a YUV4MPEG2 file")]
+    NotY4m,
+
+    #[error("YUV4MPEG2 header is missing a valid {0} parameter")]
+    MissingParameter(&'static str),
+
+    #[error("Invalid YUV4MPEG2 {0} parameter")]
+    InvalidParameter(&'static str),
+
+    #[error("Unsupported YUV4MPEG2 colorspace {0}")]
+    UnsupportedColorspace(String),
+
+    #[error("YUV4MPEG2 frame {0} is truncated")]
+    TruncatedFrame(usize),
+}
+
+/// A single camera that plays back a sequence of still frames, as if they
+/// had been captured live.
+///
+/// Frames follow the player's clock rather than the wall clock, so that the
+/// same frames arrive on the same player frames every time. Once the last
+/// frame has been reached, the camera keeps showing it.
+pub struct SequenceCameraBackend {
+    name: String,
+
+    frames: Vec<Bitmap>,
+
+    /// The rate at which the frames were captured, as a fraction of frames
+    /// per second.
+    frame_rate: (u32, u32),
+
+    /// How long the camera has been capturing for.
+    time: Duration,
+
+    /// The index of the last frame that was captured.
+    last_frame: Option<usize>,
+
+    is_open: bool,
+}
+
+impl SequenceCameraBackend {
+    /// Create a camera called `name` from the bytes of a sequence of PNG,
+    /// JPEG or GIF images, which are played at `frame_rate` frames per
+    /// second.
+    pub fn from_images<I, D>(
+        name: impl Into<String>,
+        images: I,
+        frame_rate: u32,
+    ) -> Result<Self, CameraError>
+    where
+        I: IntoIterator<Item = D>,
+        D: AsRef<[u8]>,
+    {
+        let frames = images
+            .into_iter()
+            .map(|data| {
+                let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data.as_ref(), None)?;
+                let mut bitmap = bitmap.to_rgba();
+                // Cameras don't capture transparency.
+                for pixel in bitmap.data_mut().chunks_exact_mut(4) {
+                    pixel[3] = 0xFF;
+                }
+                Ok(bitmap)
+            })
+            .collect::<Result<Vec<_>, CameraError>>()?;
+        Self::new(name.into(), frames, (frame_rate.max(1), 1))
+    }
+
+    /// Create a camera called `name` from the bytes of a YUV4MPEG2 (`.y4m`)
+    /// file, which is played at the frame rate given in its header.
+    ///
+    /// 8-bit 4:2:0, 4:2:2, 4:4:4 and monochrome files are supported.
+    pub fn from_y4m(name: impl Into<String>, data: &[u8]) -> Result<Self, CameraError> {
+        let (frame_rate, frames) = read_y4m(data)?;
+        Self::new(name.into(), frames, frame_rate)
+    }
+
+    fn new(name: String, frames: Vec<Bitmap>, frame_rate: (u32, u32)) -> Result<Self, CameraError> {
+        if frames.is_empty() {
+            return Err(CameraError::NoFrames);
+        }
+        Ok(Self {
+            name,
+            frames,
+            frame_rate,
+            time: Duration::ZERO,
+            last_frame: None,
+            is_open: false,
+        })
+    }
+
+    /// The index of the frame being shown at the current time.
+    fn current_frame(&self) -> usize {
+        let (numerator, denominator) = self.frame_rate;
+        // Allow for rounding in the frame durations that the player passes
+        // in, so that frames don't arrive a player frame late.
+        let nanos = self.time.as_nanos() + 1_000;
+        let frame = nanos * u128::from(numerator) / (u128::from(denominator) * 1_000_000_000);
+        (frame as usize).min(self.frames.len() - 1)
+    }
+}
+
+impl CameraBackend for SequenceCameraBackend {
+    fn names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+
+    fn open(&mut self, index: usize) -> bool {
+        if index != 0 {
+            return false;
+        }
+        self.is_open = true;
+        true
+    }
+
+    fn capture(&mut self, index: usize, elapsed: Duration) -> Option<Bitmap> {
+        if index != 0 || !self.is_open {
+            return None;
+        }
+
+        let frame = self.current_frame();
+        self.time += elapsed;
+        if self.last_frame == Some(frame) {
+            return None;
+        }
+        self.last_frame = Some(frame);
+        self.frames.get(frame).cloned()
+    }
+}
+
+/// The ways that the chroma planes of a YUV4MPEG2 file can be subsampled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Chroma {
+    /// Half width and half height.
+    C420,
+    /// Half width and full height.
+    C422,
+    /// Full width and full height.
+    C444,
+    /// No chroma planes at all.
+    Mono,
+}
+
+/// Read the frame rate and the frames of a YUV4MPEG2 file.
+fn read_y4m(data: &[u8]) -> Result<((u32, u32), Vec<Bitmap>), CameraError> {
+    let header_end = data
+        .iter()
+        .position(|&b| b == b'\n')
+        .ok_or(CameraError::NotY4m)?;
+    let header = std::str::from_utf8(&data[..header_end]).map_err(|_| CameraError::NotY4m)?;
+    let mut params = header.split(' ');
+    if params.next() != Some("YUV4MPEG2") {
+        return Err(CameraError::NotY4m);
+    }
+
+    let mut width = None;
+    let mut height = None;
+    let mut frame_rate = (25, 1);
+    let mut chroma = Chroma::C420;
+    for param in params.filter(|param| !param.is_empty()) {
+        let (Some(key), Some(value)) = (param.get(..1), param.get(1..)) else {
+            continue;
+        };
+        match key {
+            "W" => width = value.parse().ok(),
+            "H" => height = value.parse().ok(),
+            "F" => {
+                frame_rate = value
+                    .split_once(':')
+                    .and_then(|(n, d)| Some((n.parse().ok()?, d.parse().ok()?)))
+                    .filter(|&(n, d)| n > 0 && d > 0)
+                    .ok_or(CameraError::InvalidParameter("F"))?;
+            }
+            "C" => {
+                chroma = match value {
+                    "420" | "420jpeg" | "420paldv" | "420mpeg2" => Chroma::C420,
+                    "422" => Chroma::C422,
+                    "444" => Chroma::C444,
+                    "mono" => Chroma::Mono,
+                    _ => return Err(CameraError::UnsupportedColorspace(value.to_string())),
+                }
+            }
+            // Interlacing, aspect ratio and extensions don't affect decoding.
+            _ => {}
+        }
+    }
+    let width: usize = width.ok_or(CameraError::MissingParameter("W"))?;
+    let height: usize = height.ok_or(CameraError::MissingParameter("H"))?;
+    if width == 0 {
+        return Err(CameraError::InvalidParameter("W"));
+    }
+    if height == 0 {
+        return Err(CameraError::InvalidParameter("H"));
+    }
+
+    let (chroma_width, chroma_height) = match chroma {
+        Chroma::C420 => ((width + 1) / 2, (height + 1) / 2),
+        Chroma::C422 => ((width + 1) / 2, height),
+        Chroma::C444 => (width, height),
+        Chroma::Mono => (0, 0),
+    };
+    let luma_len = width * height;
+    let chroma_len = chroma_width * chroma_height;
+    let frame_len = luma_len + 2 * chroma_len;
+
+    let mut frames = Vec::new();
+    let mut data = &data[header_end + 1..];
+    while !data.is_empty() {
+        let line_end = data
+            .iter()
+            .position(|&b| b == b'\n')
+            .ok_or(CameraError::TruncatedFrame(frames.len()))?;
+        if !data.starts_with(b"FRAME") {
+            return Err(CameraError::TruncatedFrame(frames.len()));
+        }
+        let planes = data
+            .get(line_end + 1..line_end + 1 + frame_len)
+            .ok_or(CameraError::TruncatedFrame(frames.len()))?;
+        data = &data[line_end + 1 + frame_len..];
+
+        let (luma, chroma_planes) = planes.split_at(luma_len);
+        let (u_plane, v_plane) = chroma_planes.split_at(chroma_len);
+        let mut rgba = Vec::with_capacity(luma_len * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let (u, v) = if chroma == Chroma::Mono {
+                    (128, 128)
+                } else {
+                    let cx = x * chroma_width / width;
+                    let cy = y * chroma_height / height;
+                    let i = cy * chroma_width + cx;
+                    (u_plane[i], v_plane[i])
+                };
+                let [r, g, b] = yuv_to_rgb(luma[y * width + x], u, v);
+                rgba.extend_from_slice(&[r, g, b, 0xFF]);
+            }
+        }
+        frames.push(Bitmap::new(
+            width as u32,
+            height as u32,
+            BitmapFormat::Rgba,
+            rgba,
+        ));
+    }
+
+    Ok((frame_rate, frames))
+}
+
+/// Convert a limited range BT.601 color to RGB.
+fn yuv_to_rgb(y: u8, u: u8, v: u8) -> [u8; 3] {
+    let c = 1.164 * (f32::from(y) - 16.0);
+    let d = f32::from(u) - 128.0;
+    let e = f32::from(v) - 128.0;
+    let r = c + 1.596 * e;
+    let g = c - 0.392 * d - 0.813 * e;
+    let b = c + 2.017 * d;
+    [r, g, b].map(|channel| channel.round().clamp(0.0, 255.0) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn y4m(header: &str, frames: &[&[u8]]) -> Vec<u8> {
+        let mut data = format!("{header}\n").into_bytes();
+        for frame in frames {
+            data.extend_from_slice(b"FRAME\n");
+            data.extend_from_slice(frame);
+        }
+        data
+    }
+
+    #[test]
+    fn read_y4m_420() {
+        // Two 2x2 frames with one chroma sample each: a black and white
+        // checkerboard, then red.
+        let data = y4m(
+            "YUV4MPEG2 W2 H2 F30:1 Ip A1:1 C420jpeg",
+            &[&[16, 235, 235, 16, 128, 128], &[81, 81, 81, 81, 90, 240]],
+        );
+        let (frame_rate, frames) = read_y4m(&data).unwrap();
+        assert_eq!(frame_rate, (30, 1));
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].width(), 2);
+        assert_eq!(frames[0].height(), 2);
+        assert_eq!(
+            frames[0].data(),
+            &[0, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255, 0, 0, 0, 255]
+        );
+        let [r, g, b, a] = [0, 1, 2, 3].map(|i| frames[1].data()[i]);
+        assert!(r > 250 && g < 5 && b < 5 && a == 255);
+    }
+
+    #[test]
+    fn read_y4m_mono() {
+        let data = y4m("YUV4MPEG2 W1 H1 Cmono", &[&[16], &[235]]);
+        let (frame_rate, frames) = read_y4m(&data).unwrap();
+        assert_eq!(frame_rate, (25, 1));
+        assert_eq!(frames[0].data(), &[0, 0, 0, 255]);
+        assert_eq!(frames[1].data(), &[255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn read_y4m_invalid() {
+        assert!(matches!(
+            read_y4m(b"not a y4m file\n"),
+            Err(CameraError::NotY4m)
+        ));
+        assert!(matches!(
+            read_y4m(b"YUV4MPEG2 H2\n"),
+            Err(CameraError::MissingParameter("W"))
+        ));
+        assert!(matches!(
+            read_y4m(b"YUV4MPEG2 W1 H1 C420p10\n"),
+            Err(CameraError::UnsupportedColorspace(_))
+        ));
+        assert!(matches!(
+            read_y4m(&y4m("YUV4MPEG2 W2 H2", &[&[0, 0]])),
+            Err(CameraError::TruncatedFrame(0))
+        ));
+    }
+
+    #[test]
+    fn sequence_follows_player_clock() {
+        let data = y4m("YUV4MPEG2 W1 H1 F10:1 Cmono", &[&[16], &[126], &[235]]);
+        let mut camera = SequenceCameraBackend::from_y4m("Test Camera", &data).unwrap();
+        let frame = Duration::from_secs_f64(1.0 / 20.0);
+        assert!(camera.capture(0, frame).is_none());
+        assert!(camera.open(0));
+
+        let captured: Vec<Option<u8>> = (0..8)
+            .map(|_| camera.capture(0, frame).map(|bitmap| bitmap.data()[0]))
+            .collect();
+        assert_eq!(
+            captured,
+            vec![Some(0), None, Some(128), None, Some(255), None, None, None]
+        );
+    }
+}
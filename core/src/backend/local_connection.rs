@@ -0,0 +1,249 @@
+//! Routing of `LocalConnection` messages between players.
+
+use flash_lso::types::Value as AmfValue;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+/// A message sent with `LocalConnection.send`.
+#[derive(Clone, Debug)]
+pub struct LocalConnectionMessage {
+    /// The fully qualified, lowercase name of the receiving connection.
+    pub connection_name: String,
+
+    /// The domain of the movie that sent the message.
+    pub sender_domain: String,
+
+    /// The method to call on the receiving connection.
+    pub method_name: String,
+
+    /// The AMF0-encoded arguments to pass to the method.
+    pub arguments: Vec<AmfValue>,
+}
+
+/// A way of passing `LocalConnection` messages to players in other
+/// processes.
+pub trait LocalConnectionTransport {
+    /// Claim a connection name in every other process.
+    ///
+    /// Returns `false` if another process is already listening on it.
+    fn connect(&mut self, name: &str) -> bool;
+
+    /// Release a connection name that was claimed with `connect`.
+    fn close(&mut self, name: &str);
+
+    /// Pass a message on to whichever process is listening on its
+    /// connection name.
+    ///
+    /// Returns `false` if no process is listening.
+    fn send(&mut self, message: &LocalConnectionMessage) -> bool;
+
+    /// Take any messages that other processes have sent to names claimed
+    /// with `connect`.
+    fn receive(&mut self) -> Vec<LocalConnectionMessage>;
+}
+
+/// Routes `LocalConnection` messages between every player that shares it,
+/// and, through an optional transport, to players in other processes.
+///
+/// Each player gets its own endpoint with `LocalConnectionBroker::endpoint`.
+/// Cloning a broker gives another handle to the same broker.
+#[derive(Clone, Default)]
+pub struct LocalConnectionBroker(Rc<RefCell<BrokerState>>);
+
+#[derive(Default)]
+struct BrokerState {
+    next_endpoint_id: u64,
+
+    /// The endpoint that is listening on each connection name.
+    listeners: HashMap<String, u64>,
+
+    /// Messages that have not been collected yet by each endpoint.
+    inboxes: HashMap<u64, VecDeque<LocalConnectionMessage>>,
+
+    transport: Option<Box<dyn LocalConnectionTransport>>,
+}
+
+impl LocalConnectionBroker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a broker that also talks to other processes through
+    /// `transport`.
+    pub fn with_transport(transport: impl 'static + LocalConnectionTransport) -> Self {
+        let broker = Self::new();
+        broker.0.borrow_mut().transport = Some(Box::new(transport));
+        broker
+    }
+
+    /// Create a new endpoint for a player to send and receive messages
+    /// with.
+    pub fn endpoint(&self) -> LocalConnectionEndpoint {
+        let mut state = self.0.borrow_mut();
+        let id = state.next_endpoint_id;
+        state.next_endpoint_id += 1;
+        state.inboxes.insert(id, VecDeque::new());
+        LocalConnectionEndpoint {
+            broker: self.clone(),
+            id,
+        }
+    }
+}
+
+impl BrokerState {
+    /// Move messages that arrived through the transport into the inboxes
+    /// of their listeners.
+    fn receive_from_transport(&mut self) {
+        let Some(transport) = &mut self.transport else {
+            return;
+        };
+
+        for message in transport.receive() {
+            if let Some(id) = self.listeners.get(&message.connection_name) {
+                if let Some(inbox) = self.inboxes.get_mut(id) {
+                    inbox.push_back(message);
+                }
+            }
+        }
+    }
+}
+
+/// A single player's connection to a `LocalConnectionBroker`.
+///
+/// Any connection names that are still claimed are released when the
+/// endpoint is dropped.
+pub struct LocalConnectionEndpoint {
+    broker: LocalConnectionBroker,
+    id: u64,
+}
+
+impl LocalConnectionEndpoint {
+    /// Start listening for messages sent to `name`.
+    ///
+    /// Returns `false` if any player is already listening on it.
+    pub fn connect(&self, name: &str) -> bool {
+        let mut state = self.broker.0.borrow_mut();
+        if state.listeners.contains_key(name) {
+            return false;
+        }
+        if let Some(transport) = &mut state.transport {
+            if !transport.connect(name) {
+                return false;
+            }
+        }
+
+        state.listeners.insert(name.to_string(), self.id);
+        true
+    }
+
+    /// Stop listening for messages sent to `name`.
+    pub fn close(&self, name: &str) {
+        let mut state = self.broker.0.borrow_mut();
+        if state.listeners.get(name) == Some(&self.id) {
+            state.listeners.remove(name);
+            if let Some(transport) = &mut state.transport {
+                transport.close(name);
+            }
+        }
+    }
+
+    /// Send a message to whichever player is listening on its connection
+    /// name.
+    ///
+    /// Returns `false` if no player is listening.
+    pub fn send(&self, message: LocalConnectionMessage) -> bool {
+        let mut state = self.broker.0.borrow_mut();
+        let state = &mut *state;
+        if let Some(id) = state.listeners.get(&message.connection_name) {
+            if let Some(inbox) = state.inboxes.get_mut(id) {
+                inbox.push_back(message);
+                return true;
+            }
+        }
+
+        match &mut state.transport {
+            Some(transport) => transport.send(&message),
+            None => false,
+        }
+    }
+
+    /// Take all messages that have been sent to names this endpoint is
+    /// listening on.
+    pub fn receive(&self) -> Vec<LocalConnectionMessage> {
+        let mut state = self.broker.0.borrow_mut();
+        state.receive_from_transport();
+        state
+            .inboxes
+            .get_mut(&self.id)
+            .map(|inbox| inbox.drain(..).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for LocalConnectionEndpoint {
+    fn drop(&mut self) {
+        let mut state = self.broker.0.borrow_mut();
+        let id = self.id;
+        let names: Vec<String> = state
+            .listeners
+            .iter()
+            .filter(|(_, listener)| **listener == id)
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in names {
+            state.listeners.remove(&name);
+            if let Some(transport) = &mut state.transport {
+                transport.close(&name);
+            }
+        }
+        state.inboxes.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(connection_name: &str) -> LocalConnectionMessage {
+        LocalConnectionMessage {
+            connection_name: connection_name.to_string(),
+            sender_domain: "localhost".to_string(),
+            method_name: "test".to_string(),
+            arguments: vec![AmfValue::Number(1.0)],
+        }
+    }
+
+    #[test]
+    fn routes_between_endpoints() {
+        let broker = LocalConnectionBroker::new();
+        let sender = broker.endpoint();
+        let receiver = broker.endpoint();
+
+        assert!(!sender.send(message("_test")));
+        assert!(receiver.connect("_test"));
+        assert!(!sender.connect("_test"));
+        assert!(sender.send(message("_test")));
+        assert!(sender.receive().is_empty());
+
+        let received = receiver.receive();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].method_name, "test");
+        assert!(receiver.receive().is_empty());
+
+        receiver.close("_test");
+        assert!(!sender.send(message("_test")));
+    }
+
+    #[test]
+    fn dropped_endpoint_releases_names() {
+        let broker = LocalConnectionBroker::new();
+        let sender = broker.endpoint();
+        let receiver = broker.endpoint();
+
+        assert!(receiver.connect("_test"));
+        drop(receiver);
+        assert!(!sender.send(message("_test")));
+        assert!(sender.connect("_test"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Audio capture for `Microphone`.
+
+use std::time::Duration;
+
+/// A source of captured audio, such as a sound card's inputs.
+///
+/// Microphones are identified by their index in the list returned by `names`.
+/// All samples are mono, in the range `[-1.0, 1.0]`.
+pub trait MicrophoneBackend {
+    /// The names of the available microphones.
+    fn names(&self) -> Vec<String>;
+
+    /// Start capturing audio from a microphone.
+    ///
+    /// Returns `false` if the microphone is unavailable, or the user did not
+    /// allow access to it.
+    fn open(&mut self, index: usize) -> bool;
+
+    /// Take the samples that an open microphone has captured since the last
+    /// call, resampled to `sample_rate`.
+    ///
+    /// `elapsed` is how much time the player has advanced by since the last
+    /// call. Backends that capture in real time may ignore it.
+    fn capture(&mut self, index: usize, sample_rate: u32, elapsed: Duration) -> Vec<f32>;
+}
+
+/// A microphone backend for when there are no microphones.
+#[derive(Default)]
+pub struct NullMicrophoneBackend;
+
+impl NullMicrophoneBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MicrophoneBackend for NullMicrophoneBackend {
+    fn names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn open(&mut self, _index: usize) -> bool {
+        false
+    }
+
+    fn capture(&mut self, _index: usize, _sample_rate: u32, _elapsed: Duration) -> Vec<f32> {
+        Vec::new()
+    }
+}
+
+/// Errors that can occur when reading a WAV file.
+#[derive(Debug, thiserror::Error)]
+pub enum WavError {
+    #[error("Not a RIFF WAVE file")]
+    NotWav,
+
+    #[error("WAV file is missing its {0} chunk")]
+    MissingChunk(&'static str),
+
+    #[error("Unsupported WAV sample format {format} with {bits} bits per sample")]
+    UnsupportedFormat { format: u16, bits: u16 },
+}
+
+/// A single microphone that plays back the contents of a WAV file, as if it
+/// had been captured live.
+///
+/// Captured audio follows the player's clock rather than the wall clock, so
+/// that the same samples arrive on the same frames every time. Once the file
+/// has been played to the end, the microphone captures silence.
+pub struct WavMicrophoneBackend {
+    name: String,
+
+    /// The samples of the file, mixed down to mono.
+    samples: Vec<f32>,
+
+    /// The sample rate of the file.
+    sample_rate: u32,
+
+    /// How long the microphone has been capturing for.
+    time: Duration,
+
+    is_open: bool,
+}
+
+impl WavMicrophoneBackend {
+    /// Create a microphone called `name` from the bytes of a WAV file.
+    ///
+    /// 8-bit and 16-bit integer PCM, and 32-bit float samples are supported.
+    pub fn new(name: impl Into<String>, data: &[u8]) -> Result<Self, WavError> {
+        let (sample_rate, samples) = read_wav(data)?;
+        Ok(Self {
+            name: name.into(),
+            samples,
+            sample_rate,
+            time: Duration::ZERO,
+            is_open: false,
+        })
+    }
+
+    /// Linearly interpolate the sample at `time` seconds into the file.
+    fn sample_at(&self, time: f64) -> f32 {
+        let position = time * f64::from(self.sample_rate);
+        let index = position as usize;
+        let fraction = (position - index as f64) as f32;
+        match (self.samples.get(index), self.samples.get(index + 1)) {
+            (Some(a), Some(b)) => a + (b - a) * fraction,
+            (Some(a), None) => *a,
+            _ => 0.0,
+        }
+    }
+}
+
+impl MicrophoneBackend for WavMicrophoneBackend {
+    fn names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+
+    fn open(&mut self, index: usize) -> bool {
+        if index != 0 {
+            return false;
+        }
+        self.is_open = true;
+        true
+    }
+
+    fn capture(&mut self, index: usize, sample_rate: u32, elapsed: Duration) -> Vec<f32> {
+        if index != 0 || !self.is_open || sample_rate == 0 {
+            return Vec::new();
+        }
+
+        // Count samples from the start of the capture, so that rounding
+        // doesn't drift over many calls.
+        let rate = u128::from(sample_rate);
+        let start = self.time.as_nanos() * rate / 1_000_000_000;
+        self.time += elapsed;
+        let end = self.time.as_nanos() * rate / 1_000_000_000;
+
+        (start..end)
+            .map(|i| self.sample_at(i as f64 / f64::from(sample_rate)))
+            .collect()
+    }
+}
+
+/// Read the sample rate and the samples, mixed down to mono, of a WAV file.
+fn read_wav(data: &[u8]) -> Result<(u32, Vec<f32>), WavError> {
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return Err(WavError::NotWav);
+    }
+
+    let mut format = None;
+    let mut samples = None;
+    let mut chunks = &data[12..];
+    while chunks.len() >= 8 {
+        let id = &chunks[0..4];
+        let len = u32::from_le_bytes([chunks[4], chunks[5], chunks[6], chunks[7]]) as usize;
+        let body = &chunks[8..chunks.len().min(8 + len)];
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let read_u16 = |i: usize| u16::from_le_bytes([body[i], body[i + 1]]);
+                let sample_rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+                format = Some((read_u16(0), read_u16(2), sample_rate, read_u16(14)));
+            }
+            b"data" => samples = Some(body),
+            _ => (),
+        }
+        // Chunks are padded to an even length.
+        let next = (8 + len + (len & 1)).min(chunks.len());
+        chunks = &chunks[next..];
+    }
+
+    let (format, channels, sample_rate, bits) = format.ok_or(WavError::MissingChunk("fmt"))?;
+    let data = samples.ok_or(WavError::MissingChunk("data"))?;
+    let decoded: Vec<f32> = match (format, bits) {
+        // Unsigned 8-bit PCM.
+        (1, 8) => data
+            .iter()
+            .map(|&sample| (f32::from(sample) - 128.0) / 128.0)
+            .collect(),
+        // Signed 16-bit PCM.
+        (1, 16) => data
+            .chunks_exact(2)
+            .map(|bytes| f32::from(i16::from_le_bytes([bytes[0], bytes[1]])) / 32768.0)
+            .collect(),
+        // IEEE float.
+        (3, 32) => data
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect(),
+        _ => return Err(WavError::UnsupportedFormat { format, bits }),
+    };
+
+    let channels = usize::from(channels.max(1));
+    let samples = decoded
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect();
+    Ok((sample_rate, samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(format: u16, channels: u16, sample_rate: u32, bits: u16, data: &[u8]) -> Vec<u8> {
+        let mut wav = b"RIFF\0\0\0\0WAVE".to_vec();
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&format.to_le_bytes());
+        wav.extend_from_slice(&channels.to_le_bytes());
+        wav.extend_from_slice(&sample_rate.to_le_bytes());
+        let block_align = channels * bits / 8;
+        wav.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+        wav.extend_from_slice(&block_align.to_le_bytes());
+        wav.extend_from_slice(&bits.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(data);
+        wav
+    }
+
+    #[test]
+    fn read_pcm16_stereo() {
+        let data: Vec<u8> = [16384i16, 0, -32768, -32768]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        let (sample_rate, samples) = read_wav(&wav(1, 2, 8000, 16, &data)).unwrap();
+        assert_eq!(sample_rate, 8000);
+        assert_eq!(samples, vec![0.25, -1.0]);
+    }
+
+    #[test]
+    fn read_unsupported() {
+        assert!(matches!(read_wav(b"not a wav file"), Err(WavError::NotWav)));
+        assert!(matches!(
+            read_wav(&wav(1, 1, 8000, 24, &[0; 3])),
+            Err(WavError::UnsupportedFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn capture_follows_elapsed_time() {
+        let data: Vec<u8> = (0..8u8).map(|i| 128 + i * 16).collect();
+        let mut backend = WavMicrophoneBackend::new("Test", &wav(1, 1, 8, 8, &data)).unwrap();
+        assert!(backend.capture(0, 8, Duration::from_millis(500)).is_empty());
+
+        assert!(backend.open(0));
+        let samples = backend.capture(0, 8, Duration::from_millis(500));
+        assert_eq!(samples, vec![0.0, 0.125, 0.25, 0.375]);
+
+        // Upsampling interpolates between samples.
+        let samples = backend.capture(0, 16, Duration::from_millis(125));
+        assert_eq!(samples, vec![0.5, 0.5625]);
+
+        // Past the end of the file, the microphone is silent.
+        let samples = backend.capture(0, 8, Duration::from_millis(1000));
+        assert_eq!(samples.len(), 8);
+        assert_eq!(samples[5..], [0.0, 0.0, 0.0]);
+    }
+}
@@ -6,6 +6,8 @@ use indexmap::IndexMap;
 use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::Duration;
 use swf::avm1::types::SendVarsMethod;
 use url::Url;
 
@@ -109,6 +111,70 @@ pub struct Response {
     pub body: Vec<u8>,
 }
 
+/// A response to a fetch request whose body is received as it downloads.
+pub struct StreamingResponse {
+    /// The final URL obtained after any redirects.
+    pub url: String,
+
+    /// The length of the response body in bytes, if it is known in advance.
+    pub expected_length: Option<u64>,
+
+    /// The contents of the response body.
+    pub body: Box<dyn ResponseBody>,
+}
+
+impl From<Response> for StreamingResponse {
+    fn from(response: Response) -> Self {
+        Self {
+            url: response.url,
+            expected_length: Some(response.body.len() as u64),
+            body: Box::new(CompleteBody(Some(response.body))),
+        }
+    }
+}
+
+/// The body of a `StreamingResponse`.
+pub trait ResponseBody {
+    /// Wait for the next chunk of the body to arrive.
+    ///
+    /// Returns `None` once the whole body has been received.
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>>;
+}
+
+/// A response body that has already been received in full, and is returned
+/// as a single chunk.
+struct CompleteBody(Option<Vec<u8>>);
+
+impl ResponseBody for CompleteBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        let chunk = self.0.take();
+        Box::pin(async move { Ok(chunk) })
+    }
+}
+
+/// The outcome of an attempt to open a socket.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connected,
+    Failed,
+    TimedOut,
+}
+
+/// Something that happened to a socket opened with
+/// `NavigatorBackend::connect_socket`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SocketAction {
+    /// The connection attempt finished. This is always the first action sent,
+    /// and no further actions are sent if the connection was not made.
+    Connect(ConnectionState),
+
+    /// Data was received from the peer.
+    Data(Vec<u8>),
+
+    /// The peer closed the connection, or the connection was lost.
+    Close,
+}
+
 /// Type alias for pinned, boxed, and owned futures that output a falliable
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
@@ -147,6 +213,16 @@ pub trait NavigatorBackend {
     /// Fetch data and return it some time in the future.
     fn fetch(&self, request: Request) -> OwnedFuture<Response, Error>;
 
+    /// Fetch data, receiving the body in chunks as it downloads.
+    ///
+    /// The returned future completes once the response starts to arrive.
+    /// Backends that can only fetch whole responses may rely on the default
+    /// implementation, which returns the body of `fetch` as a single chunk.
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, Error> {
+        let fetch = self.fetch(request);
+        Box::pin(async move { Ok(fetch.await?.into()) })
+    }
+
     /// Arrange for a future to be run at some point in the... well, future.
     ///
     /// This function must be called to ensure a future is actually computed.
@@ -157,6 +233,28 @@ pub trait NavigatorBackend {
     /// This seems highly limiting.
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>);
 
+    /// Open a TCP connection to the given host and port.
+    ///
+    /// The outcome of the connection attempt, and anything that happens to
+    /// the connection after that, must be reported through `sender`, starting
+    /// with a `SocketAction::Connect`. If the attempt takes longer than
+    /// `timeout`, it should be abandoned and reported as
+    /// `ConnectionState::TimedOut`.
+    ///
+    /// Data received from `receiver` must be written to the socket, in order.
+    /// The socket should be closed once `receiver` is disconnected.
+    ///
+    /// Backends that cannot open sockets should report
+    /// `ConnectionState::Failed`.
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    );
+
     /// Handle any context specific pre-processing
     ///
     /// Changing http -> https for example. This function may alter any part of the
@@ -305,6 +403,17 @@ impl NavigatorBackend for NullNavigatorBackend {
         self.spawner.spawn_local(future);
     }
 
+    fn connect_socket(
+        &mut self,
+        _host: String,
+        _port: u16,
+        _timeout: Duration,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        let _ = sender.send(SocketAction::Connect(ConnectionState::Failed));
+    }
+
     fn pre_process_url(&self, url: Url) -> Url {
         url
     }
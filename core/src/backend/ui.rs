@@ -1,9 +1,16 @@
 use crate::events::{KeyCode, PlayerEvent};
+use chrono::{DateTime, Utc};
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
 
 pub type FullscreenError = Cow<'static, str>;
 
+/// The files chosen in a file dialog, which are empty if the user cancelled
+/// the dialog.
+pub type FileDialogFuture = Pin<Box<dyn Future<Output = Vec<Box<dyn FileDialogResult>>>>>;
+
 pub trait UiBackend {
     fn mouse_visible(&self) -> bool;
 
@@ -31,6 +38,68 @@ pub trait UiBackend {
 
     // Only used on web.
     fn open_virtual_keyboard(&self);
+
+    /// Displays a dialog that lets the user choose one or more existing files,
+    /// as used by `FileReference.browse`.
+    ///
+    /// Returns `None` if file dialogs are not supported.
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Option<FileDialogFuture>;
+
+    /// Displays a dialog that lets the user choose where to save a file, as
+    /// used by `FileReference.save` and `FileReference.download`.
+    ///
+    /// The chosen file is not written to until `FileDialogResult::write` is
+    /// called. Returns `None` if file dialogs are not supported.
+    fn display_file_save_dialog(&mut self, file_name: String) -> Option<FileDialogFuture>;
+}
+
+/// A type of file that the user may choose in a file open dialog.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileFilter {
+    /// The name of the file type, such as "Images".
+    pub description: String,
+
+    /// The file extensions of this type, without the leading `*.`.
+    pub extensions: Vec<String>,
+
+    /// The Mac file types of this type, which are used instead of the
+    /// extensions on classic Mac OS.
+    pub mac_types: Vec<String>,
+}
+
+impl FileFilter {
+    /// Whether a file with the given name belongs to this type.
+    pub fn matches(&self, file_name: &str) -> bool {
+        let Some((_, extension)) = file_name.rsplit_once('.') else {
+            return false;
+        };
+        self.extensions
+            .iter()
+            .any(|e| e == "*" || e.eq_ignore_ascii_case(extension))
+    }
+}
+
+/// A file chosen by the user in a file dialog.
+pub trait FileDialogResult {
+    /// The name of the file, without its directory.
+    fn file_name(&self) -> String;
+
+    /// The size of the file in bytes, if known.
+    fn size(&self) -> Option<u64>;
+
+    fn creation_time(&self) -> Option<DateTime<Utc>>;
+
+    fn modification_time(&self) -> Option<DateTime<Utc>>;
+
+    /// Reads the whole file, returning `None` if it could not be read.
+    fn contents(&self) -> Option<Vec<u8>>;
+
+    /// Replaces the contents of the file, returning whether it succeeded.
+    fn write(&self, data: &[u8]) -> bool;
 }
 
 /// A mouse cursor icon displayed by the Flash Player.
@@ -153,6 +222,18 @@ impl UiBackend for NullUiBackend {
     fn message(&self, _message: &str) {}
 
     fn open_virtual_keyboard(&self) {}
+
+    fn display_file_open_dialog(
+        &mut self,
+        _filters: Vec<FileFilter>,
+        _multiple: bool,
+    ) -> Option<FileDialogFuture> {
+        None
+    }
+
+    fn display_file_save_dialog(&mut self, _file_name: String) -> Option<FileDialogFuture> {
+        None
+    }
 }
 
 impl Default for NullUiBackend {
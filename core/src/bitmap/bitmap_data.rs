@@ -10,7 +10,6 @@ use gc_arena::Collect;
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, SyncHandle};
 use ruffle_render::commands::{CommandHandler, CommandList};
-use ruffle_render::filters::Filter;
 use ruffle_render::matrix::Matrix;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
@@ -225,6 +224,10 @@ pub struct BitmapData<'gc> {
     avm2_object: Option<Avm2Object<'gc>>,
 
     dirty_state: DirtyState,
+
+    /// Incremented whenever the pixels of this bitmap change, so that
+    /// `Bitmap`s displaying it can tell when their cached rendering is stale.
+    generation: u32,
 }
 
 #[derive(Clone, Collect, Default, Debug)]
@@ -247,6 +250,8 @@ mod wrapper {
     use ruffle_render::commands::CommandHandler;
 
     use super::{copy_pixels_to_bitmapdata, BitmapData, DirtyState};
+    use crate::bitmap::filters;
+    use ruffle_render::filters::Filter;
 
     #[derive(Collect, Copy, Clone)]
     #[collect(no_drop)]
@@ -339,6 +344,63 @@ mod wrapper {
             self.0
         }
 
+        /// Applies a filter to an area of `source`, and draws the result at `dest_point`.
+        ///
+        /// The render backend is tried first, which lets any pending GPU-side changes
+        /// stay on the GPU. If it can't apply the filter, both bitmaps are synced and
+        /// the filter is applied to the CPU-side pixels instead.
+        ///
+        /// `displacement_map` is the `mapBitmap` of a `DisplacementMapFilter`, which
+        /// is only needed when applying the filter on the CPU.
+        #[allow(clippy::too_many_arguments)]
+        pub fn apply_filter(
+            &self,
+            context: &mut UpdateContext<'_, 'gc>,
+            source: BitmapDataWrapper<'gc>,
+            source_point: (u32, u32),
+            source_size: (u32, u32),
+            dest_point: (u32, u32),
+            filter: Filter,
+            displacement_map: Option<BitmapDataWrapper<'gc>>,
+        ) {
+            let mut handle = |bitmap: &Self| {
+                let mut bitmap_data = bitmap.0.write(context.gc_context);
+                bitmap_data.update_dirty_texture(context.renderer);
+                bitmap_data.bitmap_handle(context.renderer)
+            };
+            let sync_handle = match (handle(&source), handle(self)) {
+                (Some(source_handle), Some(dest_handle)) => context.renderer.apply_filter(
+                    source_handle,
+                    source_point,
+                    source_size,
+                    dest_handle,
+                    dest_point,
+                    filter.clone(),
+                ),
+                _ => None,
+            };
+            if let Some(sync_handle) = sync_handle {
+                // The new sync includes any changes that a pending sync would have retrieved.
+                let mut write = self.0.write(context.gc_context);
+                write.dirty_state = DirtyState::GpuModified(sync_handle);
+                write.generation = write.generation.wrapping_add(1);
+                return;
+            }
+
+            let source = source.sync();
+            let displacement_map = displacement_map.map(|map| map.sync());
+            let dest = self.sync();
+            let pixels = filters::apply_filter(
+                &source.read(),
+                source_point,
+                source_size,
+                &filter,
+                displacement_map.as_ref().map(|map| map.read()).as_deref(),
+            );
+            dest.write(context.gc_context)
+                .draw_filter_result(&pixels, source_size, dest_point);
+        }
+
         // These methods do not require a sync to complete, as they do not depend on the
         // CPU-side pixels. They are implemented directly on `BitmapDataWrapper`, allowing
         // callers to avoid calling sync()
@@ -363,6 +425,11 @@ mod wrapper {
             self.0.read().transparency
         }
 
+        /// A counter that changes whenever the pixels of this bitmap change.
+        pub fn generation(&self) -> u32 {
+            self.0.read().generation
+        }
+
         pub fn check_valid(
             &self,
             activation: &mut crate::avm2::Activation<'_, 'gc>,
@@ -432,6 +499,7 @@ impl<'gc> BitmapData<'gc> {
             bitmap_handle: None,
             avm2_object: None,
             dirty_state: DirtyState::Clean,
+            generation: 0,
         }
     }
 
@@ -474,6 +542,7 @@ impl<'gc> BitmapData<'gc> {
         // There's no longer a handle to update
         self.dirty_state = DirtyState::Clean;
         self.disposed = true;
+        self.generation = self.generation.wrapping_add(1);
     }
 
     pub fn bitmap_handle(&mut self, renderer: &mut dyn RenderBackend) -> Option<BitmapHandle> {
@@ -510,6 +579,9 @@ impl<'gc> BitmapData<'gc> {
                 panic!("Attempted to modify CPU dirty state while GPU sync is in progress!")
             }
         }
+        if dirty {
+            self.generation = self.generation.wrapping_add(1);
+        }
     }
 
     pub fn pixels(&self) -> &[Color] {
@@ -1365,6 +1437,7 @@ impl<'gc> BitmapData<'gc> {
                 avm2_object: None,
                 disposed: false,
                 dirty_state: DirtyState::Clean,
+                generation: 0,
             })
         } else {
             None
@@ -1381,38 +1454,26 @@ impl<'gc> BitmapData<'gc> {
         self.avm2_object = Some(object)
     }
 
-    pub fn apply_filter(
+    /// Writes the output of a filter or shader that was run on the CPU.
+    pub fn draw_filter_result(
         &mut self,
-        context: &mut UpdateContext<'_, 'gc>,
-        source: BitmapHandle,
-        source_point: (u32, u32),
-        source_size: (u32, u32),
+        pixels: &[Color],
+        size: (u32, u32),
         dest_point: (u32, u32),
-        filter: Filter,
     ) {
-        let dest = self.bitmap_handle(context.renderer).unwrap();
-
-        self.update_dirty_texture(context.renderer);
-        let sync_handle = context.renderer.apply_filter(
-            source,
-            source_point,
-            source_size,
-            dest,
-            dest_point,
-            filter,
-        );
-        match sync_handle {
-            Some(sync_handle) => match self.dirty_state {
-                DirtyState::Clean => self.dirty_state = DirtyState::GpuModified(sync_handle),
-                DirtyState::CpuModified | DirtyState::GpuModified(_) => panic!(
-                    "Called BitmapData.render while already dirty: {:?}",
-                    self.dirty_state
-                ),
-            },
-            None => {
-                tracing::warn!("BitmapData.apply_filter: Renderer not yet implemented")
+        for (i, color) in pixels.iter().enumerate() {
+            let x = dest_point.0 + i as u32 % size.0;
+            let y = dest_point.1 + i as u32 / size.0;
+            if x < self.width && y < self.height {
+                let color = if self.transparency {
+                    *color
+                } else {
+                    color.with_alpha(0xFF)
+                };
+                self.set_pixel32_raw(x, y, color);
             }
         }
+        self.set_cpu_dirty(true);
     }
 
     pub fn hit_test_point(&self, alpha_threshold: u32, test_point: (i32, i32)) -> bool {
@@ -1586,6 +1647,7 @@ impl<'gc> BitmapData<'gc> {
                         self.dirty_state
                     ),
                 }
+                self.generation = self.generation.wrapping_add(1);
                 Ok(())
             }
             None => Err(BitmapDataDrawError::Unimplemented),
@@ -0,0 +1,809 @@
+//! Software implementations of the bitmap filters.
+//!
+//! These are used by `BitmapData.applyFilter` whenever the render backend can't
+//! apply a filter by itself, such as in headless players. They follow the same
+//! conventions as the filter shaders of the wgpu backend, and serve as the
+//! reference that the output of those shaders can be compared against.
+
+use crate::bitmap::bitmap_data::{BitmapData, Color};
+use ruffle_render::filters::{
+    DisplacementMapFilter, DisplacementMapFilterMode, Filter, ShaderFilter,
+};
+use ruffle_render::pixel_bender::interpreter::run_shader;
+use ruffle_render::pixel_bender::{PixelBenderImage, PixelBenderShaderArgument};
+use std::sync::Arc;
+use swf::{
+    BevelFilter, BlurFilter, ColorMatrixFilter, ConvolutionFilter, DropShadowFilter, Fixed16,
+    GlowFilter, GradientFilter, GradientRecord,
+};
+
+/// A premultiplied RGBA color, with every channel between 0 and 1.
+type Rgba = [f32; 4];
+
+/// How many colors are in the gradient that colors a glow.
+const GRADIENT_SIZE: usize = 256;
+
+/// A block of pixels that is read from or written to by a filter.
+#[derive(Clone)]
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgba>,
+}
+
+impl Image {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; width * height],
+        }
+    }
+
+    /// Copies an area of a bitmap. Anything outside of the bitmap is transparent.
+    fn from_bitmap(bitmap: &BitmapData, point: (u32, u32), size: (u32, u32)) -> Self {
+        let mut image = Self::new(size.0 as usize, size.1 as usize);
+        for y in 0..image.height {
+            for x in 0..image.width {
+                let bitmap_x = point.0 as i32 + x as i32;
+                let bitmap_y = point.1 as i32 + y as i32;
+                if bitmap.is_point_in_bounds(bitmap_x, bitmap_y) {
+                    let color = bitmap.pixels()
+                        [bitmap_x as usize + bitmap_y as usize * bitmap.width() as usize];
+                    image.pixels[x + y * image.width] = to_rgba(color);
+                }
+            }
+        }
+        image
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32
+    }
+
+    /// Returns the pixel at the given position, or transparent black when it's outside of the image.
+    fn get(&self, x: i32, y: i32) -> Rgba {
+        if self.contains(x, y) {
+            self.pixels[x as usize + y as usize * self.width]
+        } else {
+            [0.0; 4]
+        }
+    }
+
+    /// Returns the pixel at the given position, repeating the closest edge
+    /// pixel when it's outside of the image.
+    fn get_clamped(&self, x: i32, y: i32) -> Rgba {
+        if self.pixels.is_empty() {
+            return [0.0; 4];
+        }
+        let x = x.clamp(0, self.width as i32 - 1);
+        let y = y.clamp(0, self.height as i32 - 1);
+        self.pixels[x as usize + y as usize * self.width]
+    }
+
+    fn map(&self, mut f: impl FnMut(i32, i32, Rgba) -> Rgba) -> Self {
+        let mut image = Self::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = x + y * self.width;
+                image.pixels[i] = f(x as i32, y as i32, self.pixels[i]);
+            }
+        }
+        image
+    }
+}
+
+fn to_rgba(color: Color) -> Rgba {
+    [
+        f32::from(color.red()) / 255.0,
+        f32::from(color.green()) / 255.0,
+        f32::from(color.blue()) / 255.0,
+        f32::from(color.alpha()) / 255.0,
+    ]
+}
+
+fn to_color(rgba: Rgba) -> Color {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let alpha = channel(rgba[3]);
+    // Premultiplied colors can never be brighter than their alpha.
+    let channel = |c: f32| channel(c).min(alpha);
+    Color::argb(alpha, channel(rgba[0]), channel(rgba[1]), channel(rgba[2]))
+}
+
+fn unmultiply(rgba: Rgba) -> Rgba {
+    let [r, g, b, a] = rgba;
+    if a <= 0.0 {
+        [0.0; 4]
+    } else {
+        [r / a, g / a, b / a, a]
+    }
+}
+
+fn premultiply(rgba: Rgba) -> Rgba {
+    let [r, g, b, a] = rgba;
+    [r * a, g * a, b * a, a]
+}
+
+fn swf_color_to_rgba(color: &swf::Color) -> Rgba {
+    [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ]
+}
+
+/// Applies `filter` to an area of `source`, returning the premultiplied
+/// pixels of the result row by row. The result has the same size as the area.
+///
+/// `displacement_map` holds the pixels of `DisplacementMapFilter.mapBitmap`,
+/// as the filter itself only refers to the GPU copy of them.
+pub fn apply_filter(
+    source: &BitmapData,
+    source_point: (u32, u32),
+    source_size: (u32, u32),
+    filter: &Filter,
+    displacement_map: Option<&BitmapData>,
+) -> Vec<Color> {
+    let image = Image::from_bitmap(source, source_point, source_size);
+    let result = match filter {
+        Filter::BevelFilter(filter) => apply_glow(&image, &Glow::from(filter)),
+        Filter::BlurFilter(filter) => apply_blur(&image, filter),
+        Filter::ColorMatrixFilter(filter) => apply_color_matrix(&image, filter),
+        Filter::ConvolutionFilter(filter) => apply_convolution(&image, filter),
+        Filter::DisplacementMapFilter(filter) => {
+            apply_displacement_map(&image, filter, displacement_map)
+        }
+        Filter::DropShadowFilter(filter) => apply_glow(&image, &Glow::from(filter)),
+        Filter::GlowFilter(filter) => apply_glow(&image, &Glow::from(filter)),
+        Filter::GradientBevelFilter(filter) => apply_glow(&image, &Glow::gradient_bevel(filter)),
+        Filter::GradientGlowFilter(filter) => apply_glow(&image, &Glow::gradient_glow(filter)),
+        Filter::ShaderFilter(filter) => apply_shader(&image, filter),
+    };
+    result.pixels.into_iter().map(to_color).collect()
+}
+
+/// Calculates the area that `filter` may draw to when it's applied to
+/// `source_rect`, as returned by `BitmapData.generateFilterRect`.
+///
+/// Rectangles are given as `(x, y, width, height)` in pixels.
+pub fn calculate_dest_rect(
+    filter: &Filter,
+    source_rect: (i32, i32, i32, i32),
+) -> (i32, i32, i32, i32) {
+    let (x, y, width, height) = source_rect;
+    let (left, top, right, bottom) = match filter {
+        Filter::BlurFilter(filter) => {
+            let (blur_x, blur_y) = blur_extent(filter.blur_x, filter.blur_y, filter.num_passes());
+            (blur_x, blur_y, blur_x, blur_y)
+        }
+        Filter::GlowFilter(filter) if !filter.is_inner() => {
+            let (blur_x, blur_y) = blur_extent(filter.blur_x, filter.blur_y, filter.num_passes());
+            (blur_x, blur_y, blur_x, blur_y)
+        }
+        Filter::DropShadowFilter(filter) if !filter.is_inner() => shadow_extent(
+            blur_extent(filter.blur_x, filter.blur_y, filter.num_passes()),
+            offset(filter.angle, filter.distance),
+        ),
+        Filter::GradientGlowFilter(filter) if !filter.is_inner() => shadow_extent(
+            blur_extent(filter.blur_x, filter.blur_y, filter.num_passes()),
+            offset(filter.angle, filter.distance),
+        ),
+        Filter::BevelFilter(filter) if !filter.is_inner() => bevel_extent(
+            blur_extent(filter.blur_x, filter.blur_y, filter.num_passes()),
+            offset(filter.angle, filter.distance),
+        ),
+        Filter::GradientBevelFilter(filter) if !filter.is_inner() => bevel_extent(
+            blur_extent(filter.blur_x, filter.blur_y, filter.num_passes()),
+            offset(filter.angle, filter.distance),
+        ),
+        Filter::ShaderFilter(filter) => (
+            filter.left_extension.max(0),
+            filter.top_extension.max(0),
+            filter.right_extension.max(0),
+            filter.bottom_extension.max(0),
+        ),
+        // Everything else only ever draws inside of the source.
+        _ => (0, 0, 0, 0),
+    };
+    (
+        x - left,
+        y - top,
+        width + left + right,
+        height + top + bottom,
+    )
+}
+
+/// How far a blur spreads out from the source on each side, in pixels.
+fn blur_extent(blur_x: Fixed16, blur_y: Fixed16, passes: u8) -> (i32, i32) {
+    let extent = |blur: Fixed16| box_radius(blur.to_f32()).ceil() as i32 * i32::from(passes);
+    (extent(blur_x), extent(blur_y))
+}
+
+/// How far a blur that is moved by `offset` spreads out from the source on each side.
+/// Returned as `(left, top, right, bottom)`.
+fn shadow_extent(blur: (i32, i32), offset: (f32, f32)) -> (i32, i32, i32, i32) {
+    let (offset_x, offset_y) = (offset.0.round() as i32, offset.1.round() as i32);
+    (
+        (blur.0 - offset_x).max(0),
+        (blur.1 - offset_y).max(0),
+        (blur.0 + offset_x).max(0),
+        (blur.1 + offset_y).max(0),
+    )
+}
+
+/// Bevels draw their highlight and shadow on opposite sides of the source.
+fn bevel_extent(blur: (i32, i32), offset: (f32, f32)) -> (i32, i32, i32, i32) {
+    let (offset_x, offset_y) = (offset.0.round().abs() as i32, offset.1.round().abs() as i32);
+    (
+        blur.0 + offset_x,
+        blur.1 + offset_y,
+        blur.0 + offset_x,
+        blur.1 + offset_y,
+    )
+}
+
+/// Converts the angle (in radians) and distance of a filter into an offset in pixels.
+fn offset(angle: Fixed16, distance: Fixed16) -> (f32, f32) {
+    let (angle, distance) = (angle.to_f32(), distance.to_f32());
+    (angle.cos() * distance, angle.sin() * distance)
+}
+
+/// Flash blurs with a box that is `blur` pixels wide. Boxes with a fractional
+/// width give partial weight to the outermost pixels on either side.
+fn box_radius(blur: f32) -> f32 {
+    (blur - 1.0).max(0.0) / 2.0
+}
+
+fn apply_blur(image: &Image, filter: &BlurFilter) -> Image {
+    blur(
+        image,
+        filter.blur_x.to_f32(),
+        filter.blur_y.to_f32(),
+        filter.num_passes(),
+    )
+}
+
+/// Blurs horizontally and then vertically, once for every pass. More passes
+/// (a higher quality) approximate a gaussian blur more closely.
+fn blur(image: &Image, blur_x: f32, blur_y: f32, passes: u8) -> Image {
+    let (radius_x, radius_y) = (box_radius(blur_x), box_radius(blur_y));
+    let mut image = image.clone();
+    for _ in 0..passes {
+        if radius_x > 0.0 {
+            for y in 0..image.height {
+                let row = y * image.width..(y + 1) * image.width;
+                let blurred = box_blur_line(&image.pixels[row.clone()], radius_x);
+                image.pixels[row].copy_from_slice(&blurred);
+            }
+        }
+        if radius_y > 0.0 {
+            let mut column = vec![[0.0; 4]; image.height];
+            for x in 0..image.width {
+                for (y, pixel) in column.iter_mut().enumerate() {
+                    *pixel = image.pixels[x + y * image.width];
+                }
+                for (y, pixel) in box_blur_line(&column, radius_y).into_iter().enumerate() {
+                    image.pixels[x + y * image.width] = pixel;
+                }
+            }
+        }
+    }
+    image
+}
+
+/// Averages every pixel of a line with the `radius` pixels on either side of it.
+/// Pixels beyond the ends of the line are transparent.
+fn box_blur_line(line: &[Rgba], radius: f32) -> Vec<Rgba> {
+    let len = line.len() as isize;
+    let full = radius.floor() as isize;
+    let edge_weight = f64::from(radius - radius.floor());
+    let total = f64::from(radius) * 2.0 + 1.0;
+
+    // Running sums let every pixel be calculated in constant time, however wide the blur is.
+    let mut sums = vec![[0.0f64; 4]; line.len() + 1];
+    for (i, pixel) in line.iter().enumerate() {
+        for c in 0..4 {
+            sums[i + 1][c] = sums[i][c] + f64::from(pixel[c]);
+        }
+    }
+    let sum = |from: isize, to: isize, c: usize| {
+        let from = from.clamp(0, len) as usize;
+        let to = (to + 1).clamp(0, len) as usize;
+        if to > from {
+            sums[to][c] - sums[from][c]
+        } else {
+            0.0
+        }
+    };
+
+    (0..len)
+        .map(|i| {
+            let mut pixel = [0.0; 4];
+            for (c, value) in pixel.iter_mut().enumerate() {
+                let mut total_value = sum(i - full, i + full, c);
+                if edge_weight > 0.0 {
+                    total_value += (sum(i - full - 1, i - full - 1, c)
+                        + sum(i + full + 1, i + full + 1, c))
+                        * edge_weight;
+                }
+                *value = (total_value / total) as f32;
+            }
+            pixel
+        })
+        .collect()
+}
+
+/// Runs the shader of a `ShaderFilter` on the Pixel Bender interpreter,
+/// with `image` as the first texture input of the shader.
+fn apply_shader(image: &Image, filter: &ShaderFilter) -> Image {
+    let input = PixelBenderImage::from_premultiplied(
+        image.width as u32,
+        image.height as u32,
+        4,
+        image.pixels.iter().copied(),
+    );
+    let mut arguments = filter.arguments.clone();
+    if let Some(PixelBenderShaderArgument::ImageInput { image, .. }) = arguments
+        .iter_mut()
+        .find(|argument| matches!(argument, PixelBenderShaderArgument::ImageInput { .. }))
+    {
+        *image = Some(Arc::new(input));
+    }
+    let output = run_shader(
+        &filter.shader,
+        &arguments,
+        image.width as u32,
+        image.height as u32,
+    );
+    Image {
+        width: image.width,
+        height: image.height,
+        pixels: output.to_premultiplied(),
+    }
+}
+
+fn apply_color_matrix(image: &Image, filter: &ColorMatrixFilter) -> Image {
+    let m = &filter.matrix;
+    image.map(|_, _, pixel| {
+        let [r, g, b, a] = unmultiply(pixel);
+        let row = |i: usize| {
+            (m[i * 5] * r
+                + m[i * 5 + 1] * g
+                + m[i * 5 + 2] * b
+                + m[i * 5 + 3] * a
+                + m[i * 5 + 4] / 255.0)
+                .clamp(0.0, 1.0)
+        };
+        premultiply([row(0), row(1), row(2), row(3)])
+    })
+}
+
+fn apply_convolution(image: &Image, filter: &ConvolutionFilter) -> Image {
+    let columns = i32::from(filter.num_matrix_cols);
+    let rows = i32::from(filter.num_matrix_rows);
+    // Flash doesn't divide at all rather than divide by zero.
+    let divisor = filter.divisor.to_f32();
+    let divisor = if divisor == 0.0 { 1.0 } else { divisor };
+    let bias = filter.bias.to_f32() / 255.0;
+    let default_color = swf_color_to_rgba(&filter.default_color);
+    image.map(|x, y, pixel| {
+        let mut color = [0.0; 4];
+        for row in 0..rows {
+            for column in 0..columns {
+                let weight = filter
+                    .matrix
+                    .get((row * columns + column) as usize)
+                    .map_or(0.0, |value| value.to_f32());
+                let (sample_x, sample_y) = (x + column - columns / 2, y + row - rows / 2);
+                let sample = if filter.is_clamped() {
+                    unmultiply(image.get_clamped(sample_x, sample_y))
+                } else if image.contains(sample_x, sample_y) {
+                    unmultiply(image.get(sample_x, sample_y))
+                } else {
+                    default_color
+                };
+                for c in 0..4 {
+                    color[c] += sample[c] * weight;
+                }
+            }
+        }
+        for value in &mut color {
+            *value = (*value / divisor + bias).clamp(0.0, 1.0);
+        }
+        if filter.is_preserve_alpha() {
+            color[3] = pixel[3];
+        }
+        premultiply(color)
+    })
+}
+
+fn apply_displacement_map(
+    image: &Image,
+    filter: &DisplacementMapFilter,
+    map: Option<&BitmapData>,
+) -> Image {
+    let color = premultiply(swf_color_to_rgba(&filter.color));
+    image.map(|x, y, pixel| {
+        let map_x = x - filter.map_point.0;
+        let map_y = y - filter.map_point.1;
+        // Outside of the map, nothing moves.
+        let map_color = match map {
+            Some(map) if map.is_point_in_bounds(map_x, map_y) => map.pixels()
+                [map_x as usize + map_y as usize * map.width() as usize]
+                .to_un_multiplied_alpha(),
+            _ => return pixel,
+        };
+        // A component value of 128 means no displacement.
+        let component = |component: u8| match component {
+            1 => map_color.red(),
+            2 => map_color.green(),
+            4 => map_color.blue(),
+            8 => map_color.alpha(),
+            _ => 128,
+        };
+        let displacement_x = (f32::from(component(filter.component_x)) - 128.0) * filter.scale_x;
+        let displacement_y = (f32::from(component(filter.component_y)) - 128.0) * filter.scale_y;
+        let mut source_x = (x as f32 + 0.5 + displacement_x / 256.0).floor() as i32;
+        let mut source_y = (y as f32 + 0.5 + displacement_y / 256.0).floor() as i32;
+        if !image.contains(source_x, source_y) {
+            match filter.mode {
+                DisplacementMapFilterMode::Clamp => return image.get_clamped(source_x, source_y),
+                DisplacementMapFilterMode::Color => return color,
+                DisplacementMapFilterMode::Ignore => return pixel,
+                DisplacementMapFilterMode::Wrap => {
+                    source_x = source_x.rem_euclid(image.width.max(1) as i32);
+                    source_y = source_y.rem_euclid(image.height.max(1) as i32);
+                }
+            }
+        }
+        image.get(source_x, source_y)
+    })
+}
+
+/// Everything needed to draw a glow, drop shadow or bevel, or one of their
+/// gradient variants. They all blur the alpha of the source, and then color
+/// the result with a gradient.
+struct Glow {
+    /// The straight alpha colors of the effect. Glows go from transparent at
+    /// the start to fully colored at the end, whilst bevels go from the
+    /// highlight at the start to the shadow at the end.
+    colors: [Rgba; GRADIENT_SIZE],
+    blur_x: f32,
+    blur_y: f32,
+    passes: u8,
+    /// How far the effect is moved from the source, in pixels.
+    offset: (f32, f32),
+    strength: f32,
+    bevel: bool,
+    inner: bool,
+    knockout: bool,
+    composite_source: bool,
+    on_top: bool,
+}
+
+impl From<&GlowFilter> for Glow {
+    fn from(filter: &GlowFilter) -> Self {
+        Self {
+            colors: gradient_colors(&[
+                GradientRecord {
+                    ratio: 0,
+                    color: swf::Color {
+                        a: 0,
+                        ..filter.color
+                    },
+                },
+                GradientRecord {
+                    ratio: 255,
+                    color: filter.color.clone(),
+                },
+            ]),
+            blur_x: filter.blur_x.to_f32(),
+            blur_y: filter.blur_y.to_f32(),
+            passes: filter.num_passes(),
+            offset: (0.0, 0.0),
+            strength: filter.strength.to_f32(),
+            bevel: false,
+            inner: filter.is_inner(),
+            knockout: filter.is_knockout(),
+            composite_source: filter
+                .flags
+                .contains(swf::GlowFilterFlags::COMPOSITE_SOURCE),
+            on_top: false,
+        }
+    }
+}
+
+impl From<&DropShadowFilter> for Glow {
+    fn from(filter: &DropShadowFilter) -> Self {
+        Self {
+            colors: gradient_colors(&[
+                GradientRecord {
+                    ratio: 0,
+                    color: swf::Color {
+                        a: 0,
+                        ..filter.color
+                    },
+                },
+                GradientRecord {
+                    ratio: 255,
+                    color: filter.color.clone(),
+                },
+            ]),
+            blur_x: filter.blur_x.to_f32(),
+            blur_y: filter.blur_y.to_f32(),
+            passes: filter.num_passes(),
+            offset: offset(filter.angle, filter.distance),
+            strength: filter.strength.to_f32(),
+            bevel: false,
+            inner: filter.is_inner(),
+            knockout: filter.is_knockout(),
+            composite_source: !filter.hide_object(),
+            on_top: false,
+        }
+    }
+}
+
+impl From<&BevelFilter> for Glow {
+    fn from(filter: &BevelFilter) -> Self {
+        let highlight = &filter.highlight_color;
+        let shadow = &filter.shadow_color;
+        // The middle of the gradient is used where there's neither highlight nor shadow.
+        Self {
+            colors: gradient_colors(&[
+                GradientRecord {
+                    ratio: 0,
+                    color: highlight.clone(),
+                },
+                GradientRecord {
+                    ratio: 127,
+                    color: swf::Color { a: 0, ..*highlight },
+                },
+                GradientRecord {
+                    ratio: 128,
+                    color: swf::Color { a: 0, ..*shadow },
+                },
+                GradientRecord {
+                    ratio: 255,
+                    color: shadow.clone(),
+                },
+            ]),
+            blur_x: filter.blur_x.to_f32(),
+            blur_y: filter.blur_y.to_f32(),
+            passes: filter.num_passes(),
+            offset: offset(filter.angle, filter.distance),
+            strength: filter.strength.to_f32(),
+            bevel: true,
+            inner: filter.is_inner(),
+            knockout: filter.is_knockout(),
+            composite_source: filter
+                .flags
+                .contains(swf::BevelFilterFlags::COMPOSITE_SOURCE),
+            on_top: filter.is_on_top(),
+        }
+    }
+}
+
+impl Glow {
+    fn gradient_glow(filter: &GradientFilter) -> Self {
+        Self {
+            bevel: false,
+            ..Self::gradient_bevel(filter)
+        }
+    }
+
+    fn gradient_bevel(filter: &GradientFilter) -> Self {
+        Self {
+            colors: gradient_colors(&filter.colors),
+            blur_x: filter.blur_x.to_f32(),
+            blur_y: filter.blur_y.to_f32(),
+            passes: filter.num_passes(),
+            offset: offset(filter.angle, filter.distance),
+            strength: filter.strength.to_f32(),
+            bevel: true,
+            inner: filter.is_inner(),
+            knockout: filter.is_knockout(),
+            composite_source: filter
+                .flags
+                .contains(swf::GradientFilterFlags::COMPOSITE_SOURCE),
+            on_top: filter.is_on_top(),
+        }
+    }
+}
+
+/// Interpolates the straight alpha colors of a gradient.
+fn gradient_colors(records: &[GradientRecord]) -> [Rgba; GRADIENT_SIZE] {
+    let mut colors = [[0.0; 4]; GRADIENT_SIZE];
+    let (Some(first), Some(last)) = (records.first(), records.last()) else {
+        return colors;
+    };
+    for (t, color) in colors.iter_mut().enumerate() {
+        let next = records
+            .iter()
+            .position(|record| usize::from(record.ratio) >= t)
+            .unwrap_or(records.len() - 1);
+        let (from, to) = if usize::from(first.ratio) >= t {
+            (first, first)
+        } else if usize::from(last.ratio) < t {
+            (last, last)
+        } else {
+            (&records[next.saturating_sub(1)], &records[next])
+        };
+        let a = if to.ratio == from.ratio {
+            0.0
+        } else {
+            (t as f32 - f32::from(from.ratio)) / (f32::from(to.ratio) - f32::from(from.ratio))
+        };
+        let (from, to) = (swf_color_to_rgba(&from.color), swf_color_to_rgba(&to.color));
+        for c in 0..4 {
+            color[c] = from[c] + (to[c] - from[c]) * a;
+        }
+    }
+    colors
+}
+
+fn apply_glow(image: &Image, glow: &Glow) -> Image {
+    let blurred = blur(image, glow.blur_x, glow.blur_y, glow.passes);
+    let (offset_x, offset_y) = (glow.offset.0.round() as i32, glow.offset.1.round() as i32);
+    // Nothing outside of the source can cast a shadow.
+    let blurred_alpha = |x: i32, y: i32| blurred.get(x, y)[3];
+
+    image.map(|x, y, src| {
+        let amount = if glow.bevel {
+            // The middle of the gradient is neither highlight nor shadow.
+            let shade = blurred_alpha(x - offset_x, y - offset_y)
+                - blurred_alpha(x + offset_x, y + offset_y);
+            (shade * glow.strength).clamp(-1.0, 1.0) * 0.5 + 0.5
+        } else if glow.inner {
+            ((1.0 - blurred_alpha(x - offset_x, y - offset_y)) * glow.strength).clamp(0.0, 1.0)
+        } else {
+            (blurred_alpha(x - offset_x, y - offset_y) * glow.strength).clamp(0.0, 1.0)
+        };
+        let mut color = premultiply(glow.colors[(amount * 255.0).round() as usize]);
+
+        let over = |top: Rgba, bottom: Rgba| {
+            let mut result = [0.0; 4];
+            for c in 0..4 {
+                result[c] = top[c] + bottom[c] * (1.0 - top[3]);
+            }
+            result
+        };
+        if glow.inner {
+            for value in &mut color {
+                *value *= src[3];
+            }
+        }
+        if glow.inner || glow.on_top {
+            if glow.knockout || !glow.composite_source {
+                return color;
+            }
+            return over(color, src);
+        }
+
+        if glow.knockout {
+            return color.map(|value| value * (1.0 - src[3]));
+        }
+        if !glow.composite_source {
+            return color;
+        }
+        over(src, color)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{BlurFilterFlags, DropShadowFilterFlags, Fixed8};
+
+    fn bitmap(width: u32, height: u32, pixels: &[i32]) -> BitmapData<'static> {
+        let mut bitmap = BitmapData::default();
+        bitmap.init_pixels(width, height, true, 0);
+        for (i, pixel) in pixels.iter().enumerate() {
+            let (x, y) = (i as u32 % width, i as u32 / width);
+            bitmap.set_pixel32(x, y, Color::from(*pixel));
+        }
+        bitmap
+    }
+
+    fn blur_filter(blur_x: f64, blur_y: f64, passes: u8) -> Filter {
+        Filter::BlurFilter(BlurFilter {
+            blur_x: Fixed16::from_f64(blur_x),
+            blur_y: Fixed16::from_f64(blur_y),
+            flags: BlurFilterFlags::from_passes(passes),
+        })
+    }
+
+    #[test]
+    fn identity_color_matrix() {
+        let source = bitmap(2, 1, &[0xFF336699u32 as i32, 0x80FF0000u32 as i32]);
+        let result = apply_filter(
+            &source,
+            (0, 0),
+            (2, 1),
+            &Filter::ColorMatrixFilter(ColorMatrixFilter::default()),
+            None,
+        );
+        assert_eq!(result, source.pixels());
+    }
+
+    #[test]
+    fn box_blur() {
+        let source = bitmap(3, 1, &[0, 0xFFFFFFFFu32 as i32, 0]);
+        let result = apply_filter(&source, (0, 0), (3, 1), &blur_filter(3.0, 0.0, 1), None);
+        let alphas: Vec<_> = result.iter().map(|color| color.alpha()).collect();
+        assert_eq!(alphas, [85, 85, 85]);
+
+        // A blur of one pixel or less doesn't do anything.
+        let result = apply_filter(&source, (0, 0), (3, 1), &blur_filter(1.0, 1.0, 3), None);
+        assert_eq!(result, source.pixels());
+    }
+
+    #[test]
+    fn shader_passthrough() {
+        // `dst = sampleNearest(src, outCoord())`
+        let mut bytecode = vec![0xA1, 1, 2, 0, 0, 0xC];
+        bytecode.extend(b"_OutCoord\0");
+        bytecode.extend([0xA3, 0, 4]);
+        bytecode.extend(b"src\0");
+        bytecode.extend([0xA1, 2, 4, 1, 0, 0xF]);
+        bytecode.extend(b"dst\0");
+        bytecode.extend([0x30, 1, 0, 0xF1, 0, 0, 0x10, 0]);
+        let shader = ruffle_render::pixel_bender::parse_shader(&bytecode).expect("valid shader");
+
+        let filter = Filter::ShaderFilter(ShaderFilter {
+            shader: Arc::new(shader),
+            arguments: vec![PixelBenderShaderArgument::ImageInput {
+                index: 1,
+                image: None,
+            }],
+            bottom_extension: 0,
+            left_extension: 0,
+            right_extension: 0,
+            top_extension: 0,
+        });
+        let source = bitmap(2, 1, &[0xFF336699u32 as i32, 0x80FF0000u32 as i32]);
+        let result = apply_filter(&source, (0, 0), (2, 1), &filter, None);
+        assert_eq!(result, source.pixels());
+    }
+
+    #[test]
+    fn filter_rects() {
+        assert_eq!(
+            calculate_dest_rect(&blur_filter(4.0, 8.0, 2), (10, 10, 100, 50)),
+            (6, 2, 108, 66)
+        );
+        assert_eq!(
+            calculate_dest_rect(
+                &Filter::ColorMatrixFilter(ColorMatrixFilter::default()),
+                (0, 0, 10, 10)
+            ),
+            (0, 0, 10, 10)
+        );
+
+        let drop_shadow = |flags| {
+            Filter::DropShadowFilter(DropShadowFilter {
+                color: swf::Color::BLACK,
+                blur_x: Fixed16::from_f64(5.0),
+                blur_y: Fixed16::from_f64(5.0),
+                angle: Fixed16::ZERO,
+                distance: Fixed16::from_f64(4.0),
+                strength: Fixed8::ONE,
+                flags,
+            })
+        };
+        assert_eq!(
+            calculate_dest_rect(
+                &drop_shadow(DropShadowFilterFlags::from_passes(1)),
+                (0, 0, 10, 10)
+            ),
+            (0, -2, 16, 14)
+        );
+        assert_eq!(
+            calculate_dest_rect(
+                &drop_shadow(DropShadowFilterFlags::INNER_SHADOW),
+                (0, 0, 10, 10)
+            ),
+            (0, 0, 10, 10)
+        );
+    }
+}
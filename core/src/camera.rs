@@ -0,0 +1,613 @@
+//! Camera capture
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    Attribute as Avm1Attribute, ExecutionReason as Avm1ExecutionReason,
+    ScriptObject as Avm1ScriptObject, TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::events::dispatch_status_event;
+use crate::avm2::{Activation as Avm2Activation, Avm2, Error as Avm2Error, Object as Avm2Object};
+use crate::context::UpdateContext;
+use crate::vminterface::AvmObject;
+use gc_arena::{Collect, GcCell, MutationContext};
+use ruffle_render::backend::RenderBackend;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapInfo};
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+/// How much the brightness of a pixel has to change by between two frames
+/// for it to count as motion.
+const MOTION_THRESHOLD: i32 = 16;
+
+/// Frames may arrive this much earlier than the camera's frame rate asks
+/// for, to allow for rounding in frame durations.
+const FRAME_TOLERANCE: Duration = Duration::from_micros(1);
+
+/// Manager for the cameras that have been requested with `Camera.get` or
+/// `Camera.getCamera`.
+///
+/// Captured video is collected once per frame.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct Cameras<'gc> {
+    /// Every camera that has been opened. There is at most one for each
+    /// camera in the backend.
+    cameras: Vec<Camera<'gc>>,
+
+    /// Cameras that were opened since the last frame. They are told whether
+    /// access to them was allowed on the next frame.
+    pending_statuses: Vec<Camera<'gc>>,
+}
+
+impl<'gc> Cameras<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The camera that was already opened at this index, if any.
+    pub fn get(&self, index: usize) -> Option<Camera<'gc>> {
+        self.cameras
+            .iter()
+            .find(|camera| camera.index() == index)
+            .copied()
+    }
+
+    /// Ask the backend for access to a newly created camera, and start
+    /// capturing from it if that was allowed.
+    pub fn open(context: &mut UpdateContext<'_, 'gc>, camera: Camera<'gc>) {
+        let allowed = context.camera.open(camera.index());
+        camera.0.write(context.gc_context).muted = !allowed;
+        context.cameras.cameras.push(camera);
+        context.cameras.pending_statuses.push(camera);
+    }
+
+    /// Deliver status events, and the video captured during the last frame.
+    pub fn update(context: &mut UpdateContext<'_, 'gc>) {
+        let statuses = std::mem::take(&mut context.cameras.pending_statuses);
+        for camera in statuses {
+            let code = if camera.muted() {
+                "Camera.Muted"
+            } else {
+                "Camera.Unmuted"
+            };
+            camera.trigger_status_event(context, code, "status");
+        }
+
+        let elapsed = Duration::from_secs_f64(1.0 / context.frame_rate.max(1.0));
+        let cameras = context.cameras.cameras.clone();
+        for camera in cameras {
+            camera.capture(context, elapsed);
+        }
+    }
+}
+
+/// A camera that has been opened by a movie.
+///
+/// It corresponds directly to the AVM1 and AVM2 `Camera` classes. Each
+/// captured frame is scaled to the size set with `setMode`, uploaded to the
+/// renderer for any `Video`s that the camera is attached to, and compared
+/// with the previous frame to measure how much motion the camera sees.
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct Camera<'gc>(GcCell<'gc, CameraData<'gc>>);
+
+impl fmt::Debug for Camera<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Camera")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct CameraData<'gc> {
+    /// The index of the camera in the backend.
+    index: usize,
+
+    name: String,
+
+    /// The width of captured frames, in pixels.
+    width: u32,
+
+    /// The height of captured frames, in pixels.
+    height: u32,
+
+    /// The maximum number of frames to capture per second.
+    fps: f64,
+
+    /// The number of frames that were captured during the last second.
+    current_fps: f64,
+
+    /// The maximum amount of bandwidth that outgoing video may use, in bytes
+    /// per second.
+    bandwidth: i32,
+
+    /// The picture quality of outgoing video, from 1 to 100, or 0 if it
+    /// may vary to stay within the bandwidth.
+    quality: i32,
+
+    /// How often outgoing video contains a full frame.
+    key_frame_interval: i32,
+
+    /// Whether local video is compressed as if it were sent out.
+    loopback: bool,
+
+    /// The amount of motion, from 0 to 100, that makes the camera active.
+    motion_level: i32,
+
+    /// How long in milliseconds there must be no motion for before the
+    /// camera stops being active.
+    motion_timeout: i32,
+
+    /// The amount of motion in the last frame, from 0 to 100, or -1 if no
+    /// frame has been captured yet.
+    activity_level: f64,
+
+    /// Whether access to the camera was denied.
+    muted: bool,
+
+    /// Whether the camera is currently seeing motion above the motion level.
+    is_active: bool,
+
+    /// How long the camera has been seeing no motion for.
+    #[collect(require_static)]
+    still_time: Duration,
+
+    /// How long the camera has been capturing for.
+    #[collect(require_static)]
+    time: Duration,
+
+    /// How long it has been since the last frame was captured.
+    #[collect(require_static)]
+    since_last_frame: Duration,
+
+    /// The times at which frames were captured during the last second.
+    #[collect(require_static)]
+    frame_times: VecDeque<Duration>,
+
+    /// The newest frame from the backend that has not been captured yet,
+    /// because the camera's frame rate is lower than the backend's.
+    #[collect(require_static)]
+    pending_frame: Option<Bitmap>,
+
+    /// The last captured frame, as it came from the backend.
+    #[collect(require_static)]
+    source_frame: Option<Bitmap>,
+
+    /// The last captured frame scaled to the camera's size, as RGBA.
+    pixels: Vec<u8>,
+
+    /// The brightness of each pixel of the last captured frame.
+    luma: Vec<u8>,
+
+    /// The last captured frame, as uploaded to the renderer.
+    bitmap: Option<BitmapInfo>,
+
+    /// The AVM side of this camera, which receives status and activity
+    /// events.
+    avm_object: Option<AvmObject<'gc>>,
+}
+
+impl<'gc> Camera<'gc> {
+    pub fn new(gc_context: MutationContext<'gc, '_>, avm_object: Option<AvmObject<'gc>>) -> Self {
+        Self(GcCell::allocate(
+            gc_context,
+            CameraData {
+                index: 0,
+                name: String::new(),
+                width: 160,
+                height: 120,
+                fps: 15.0,
+                current_fps: 0.0,
+                bandwidth: 16384,
+                quality: 0,
+                key_frame_interval: 15,
+                loopback: false,
+                motion_level: 50,
+                motion_timeout: 2000,
+                activity_level: -1.0,
+                muted: true,
+                is_active: false,
+                still_time: Duration::ZERO,
+                time: Duration::ZERO,
+                since_last_frame: Duration::ZERO,
+                frame_times: VecDeque::new(),
+                pending_frame: None,
+                source_frame: None,
+                pixels: Vec::new(),
+                luma: Vec::new(),
+                bitmap: None,
+                avm_object,
+            },
+        ))
+    }
+
+    pub fn set_avm_object(self, gc_context: MutationContext<'gc, '_>, avm_object: AvmObject<'gc>) {
+        self.0.write(gc_context).avm_object = Some(avm_object);
+    }
+
+    pub fn avm_object(self) -> Option<AvmObject<'gc>> {
+        self.0.read().avm_object
+    }
+
+    /// Set which camera in the backend this is.
+    pub fn set_device(self, gc_context: MutationContext<'gc, '_>, index: usize, name: String) {
+        let mut write = self.0.write(gc_context);
+        write.index = index;
+        write.name = name;
+    }
+
+    pub fn index(self) -> usize {
+        self.0.read().index
+    }
+
+    pub fn name(self) -> String {
+        self.0.read().name.clone()
+    }
+
+    pub fn width(self) -> u32 {
+        self.0.read().width
+    }
+
+    pub fn height(self) -> u32 {
+        self.0.read().height
+    }
+
+    pub fn fps(self) -> f64 {
+        self.0.read().fps
+    }
+
+    pub fn current_fps(self) -> f64 {
+        self.0.read().current_fps
+    }
+
+    pub fn bandwidth(self) -> i32 {
+        self.0.read().bandwidth
+    }
+
+    pub fn quality(self) -> i32 {
+        self.0.read().quality
+    }
+
+    pub fn key_frame_interval(self) -> i32 {
+        self.0.read().key_frame_interval
+    }
+
+    pub fn loopback(self) -> bool {
+        self.0.read().loopback
+    }
+
+    pub fn motion_level(self) -> i32 {
+        self.0.read().motion_level
+    }
+
+    pub fn motion_timeout(self) -> i32 {
+        self.0.read().motion_timeout
+    }
+
+    pub fn activity_level(self) -> f64 {
+        self.0.read().activity_level
+    }
+
+    pub fn muted(self) -> bool {
+        self.0.read().muted
+    }
+
+    /// The last captured frame, as uploaded to the renderer.
+    pub fn bitmap(self) -> Option<BitmapInfo> {
+        self.0.read().bitmap.clone()
+    }
+
+    /// Set the size and frame rate to capture at.
+    ///
+    /// Any frame that was already captured is rescaled to the new size.
+    pub fn set_mode(self, context: &mut UpdateContext<'_, 'gc>, width: i32, height: i32, fps: f64) {
+        let mut write = self.0.write(context.gc_context);
+        write.width = width.clamp(1, 4096) as u32;
+        write.height = height.clamp(1, 4096) as u32;
+        if fps.is_finite() && fps > 0.0 {
+            write.fps = fps;
+        }
+        if let Some(frame) = write.source_frame.clone() {
+            write.pixels = scale_frame(&frame, write.width, write.height);
+            write.luma = luma(&write.pixels);
+            write.upload(context.renderer);
+        }
+    }
+
+    pub fn set_quality(self, gc_context: MutationContext<'gc, '_>, bandwidth: i32, quality: i32) {
+        let mut write = self.0.write(gc_context);
+        write.bandwidth = bandwidth.max(0);
+        write.quality = quality.clamp(0, 100);
+    }
+
+    pub fn set_key_frame_interval(self, gc_context: MutationContext<'gc, '_>, interval: i32) {
+        self.0.write(gc_context).key_frame_interval = interval.clamp(1, 300);
+    }
+
+    pub fn set_loopback(self, gc_context: MutationContext<'gc, '_>, loopback: bool) {
+        self.0.write(gc_context).loopback = loopback;
+    }
+
+    pub fn set_motion_level(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        motion_level: i32,
+        timeout: i32,
+    ) {
+        let mut write = self.0.write(gc_context);
+        write.motion_level = motion_level.clamp(0, 100);
+        write.motion_timeout = timeout.max(0);
+    }
+
+    /// The pixels of a region of the last captured frame, as ARGB.
+    ///
+    /// Returns `None` if the region is not entirely inside the frame.
+    pub fn pixels_argb(self, x: i32, y: i32, width: i32, height: i32) -> Option<Vec<u32>> {
+        let read = self.0.read();
+        if x < 0
+            || y < 0
+            || width < 0
+            || height < 0
+            || i64::from(x) + i64::from(width) > i64::from(read.width)
+            || i64::from(y) + i64::from(height) > i64::from(read.height)
+        {
+            return None;
+        }
+
+        let mut pixels = Vec::with_capacity((width * height) as usize);
+        for y in y..y + height {
+            for x in x..x + width {
+                let i = (y as usize * read.width as usize + x as usize) * 4;
+                let pixel = match read.pixels.get(i..i + 4) {
+                    Some(&[r, g, b, _]) => u32::from_be_bytes([0xFF, r, g, b]),
+                    // Nothing has been captured yet.
+                    _ => 0xFF000000,
+                };
+                pixels.push(pixel);
+            }
+        }
+        Some(pixels)
+    }
+
+    /// Take the newest frame from the backend, if it's time for the camera
+    /// to capture another one.
+    fn capture(self, context: &mut UpdateContext<'_, 'gc>, elapsed: Duration) {
+        if self.muted() {
+            return;
+        }
+
+        let frame = context.camera.capture(self.index(), elapsed);
+        let mut write = self.0.write(context.gc_context);
+        if frame.is_some() {
+            write.pending_frame = frame;
+        }
+        write.time += elapsed;
+        write.since_last_frame += elapsed;
+
+        let interval = Duration::from_secs_f64(1.0 / write.fps);
+        let is_first_frame = write.source_frame.is_none();
+        if !is_first_frame && write.since_last_frame + FRAME_TOLERANCE < interval {
+            return;
+        }
+        let Some(frame) = write.pending_frame.take() else {
+            return;
+        };
+        let since_last_frame = std::mem::replace(&mut write.since_last_frame, Duration::ZERO);
+
+        let now = write.time;
+        write.frame_times.push_back(now);
+        while let Some(&time) = write.frame_times.front() {
+            if now - time < Duration::from_secs(1) {
+                break;
+            }
+            write.frame_times.pop_front();
+        }
+        write.current_fps = write.frame_times.len() as f64;
+
+        write.pixels = scale_frame(&frame, write.width, write.height);
+        let luma = luma(&write.pixels);
+        let level = if is_first_frame {
+            0.0
+        } else {
+            motion_level(&write.luma, &luma)
+        };
+        write.luma = luma;
+        write.source_frame = Some(frame);
+        write.upload(context.renderer);
+
+        let activating = write.update_activity(level, since_last_frame);
+        drop(write);
+
+        if let Some(activating) = activating {
+            self.trigger_activity_event(context, activating);
+        }
+    }
+
+    /// Send a `StatusEvent` (AVM2) or call `onStatus` (AVM1) on this camera's
+    /// AVM object.
+    fn trigger_status_event(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        code: &'static str,
+        level: &'static str,
+    ) {
+        match self.avm_object() {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                if let Err(e) = dispatch_status_event(&mut activation, object, code.into(), level) {
+                    tracing::error!("Unhandled AVM2 exception in status event handler: {}", e);
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[Camera Status Event]"),
+                );
+                let info = Avm1ScriptObject::new(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().object),
+                );
+                info.define_value(
+                    activation.context.gc_context,
+                    "code",
+                    code.into(),
+                    Avm1Attribute::empty(),
+                );
+                info.define_value(
+                    activation.context.gc_context,
+                    "level",
+                    level.into(),
+                    Avm1Attribute::empty(),
+                );
+                let _ = object.call_method(
+                    "onStatus".into(),
+                    &[info.into()],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                );
+            }
+            None => {}
+        }
+    }
+
+    /// Send an `ActivityEvent` (AVM2) or call `onActivity` (AVM1) on this
+    /// camera's AVM object.
+    fn trigger_activity_event(self, context: &mut UpdateContext<'_, 'gc>, activating: bool) {
+        match self.avm_object() {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                if let Err(e) = dispatch_avm2_activity_event(&mut activation, object, activating) {
+                    tracing::error!("Unhandled AVM2 exception in activity event handler: {}", e);
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[Camera Activity Event]"),
+                );
+                let _ = object.call_method(
+                    "onActivity".into(),
+                    &[Avm1Value::Bool(activating)],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                );
+            }
+            None => {}
+        }
+    }
+}
+
+impl<'gc> CameraData<'gc> {
+    /// Upload the last captured frame to the renderer.
+    fn upload(&mut self, renderer: &mut dyn RenderBackend) {
+        let (width, height) = (self.width, self.height);
+        let existing = self
+            .bitmap
+            .as_ref()
+            .filter(|bitmap| u32::from(bitmap.width) == width && u32::from(bitmap.height) == height)
+            .map(|bitmap| bitmap.handle.clone());
+        let result = if let Some(handle) = existing {
+            renderer.update_texture(&handle, width, height, self.pixels.clone())
+        } else {
+            let bitmap = Bitmap::new(width, height, BitmapFormat::Rgba, self.pixels.clone());
+            renderer.register_bitmap(bitmap).map(|handle| {
+                self.bitmap = Some(BitmapInfo {
+                    handle,
+                    width: width as u16,
+                    height: height as u16,
+                });
+            })
+        };
+        if let Err(e) = result {
+            tracing::error!("Unable to upload camera frame: {}", e);
+        }
+    }
+
+    /// Track whether the camera is seeing any motion, given the activity
+    /// level of the latest frame.
+    ///
+    /// Returns the new state if the camera has just started or stopped being
+    /// active.
+    fn update_activity(&mut self, level: f64, elapsed: Duration) -> Option<bool> {
+        self.activity_level = level;
+        if level >= f64::from(self.motion_level) {
+            self.still_time = Duration::ZERO;
+            if !self.is_active {
+                self.is_active = true;
+                return Some(true);
+            }
+        } else if self.is_active {
+            self.still_time += elapsed;
+            let timeout = Duration::from_millis(self.motion_timeout.max(0) as u64);
+            if self.still_time >= timeout {
+                self.is_active = false;
+                return Some(false);
+            }
+        }
+        None
+    }
+}
+
+fn dispatch_avm2_activity_event<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    object: Avm2Object<'gc>,
+    activating: bool,
+) -> Result<(), Avm2Error<'gc>> {
+    let event = activation.avm2().classes().activityevent.construct(
+        activation,
+        &[
+            "activity".into(),
+            false.into(),
+            false.into(),
+            activating.into(),
+        ],
+    )?;
+    Avm2::dispatch_event(&mut activation.context, event, object)?;
+    Ok(())
+}
+
+/// Scale a frame from the backend to the camera's size, as RGBA.
+fn scale_frame(frame: &Bitmap, width: u32, height: u32) -> Vec<u8> {
+    let (frame_width, frame_height) = (frame.width() as usize, frame.height() as usize);
+    let bytes_per_pixel = frame.format().bytes_per_pixel();
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    for y in 0..height as usize {
+        let source_y = y * frame_height / height as usize;
+        for x in 0..width as usize {
+            let source_x = x * frame_width / width as usize;
+            let i = (source_y * frame_width + source_x) * bytes_per_pixel;
+            match frame.data().get(i..i + 3) {
+                Some(&[r, g, b]) => pixels.extend_from_slice(&[r, g, b, 0xFF]),
+                _ => pixels.extend_from_slice(&[0, 0, 0, 0xFF]),
+            }
+        }
+    }
+    pixels
+}
+
+/// The brightness of each pixel of an RGBA frame.
+fn luma(pixels: &[u8]) -> Vec<u8> {
+    pixels
+        .chunks_exact(4)
+        .map(|p| {
+            ((299 * u32::from(p[0]) + 587 * u32::from(p[1]) + 114 * u32::from(p[2])) / 1000) as u8
+        })
+        .collect()
+}
+
+/// The percentage of pixels whose brightness changed noticeably between two
+/// frames of the same size.
+fn motion_level(previous: &[u8], current: &[u8]) -> f64 {
+    if previous.len() != current.len() || current.is_empty() {
+        return 0.0;
+    }
+    let changed = previous
+        .iter()
+        .zip(current)
+        .filter(|(&a, &b)| (i32::from(a) - i32::from(b)).abs() > MOTION_THRESHOLD)
+        .count();
+    (changed as f64 * 100.0 / current.len() as f64).round()
+}
@@ -6,11 +6,14 @@ use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    camera::CameraBackend,
     log::LogBackend,
+    microphone::MicrophoneBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
     ui::{InputManager, UiBackend},
 };
+use crate::camera::Cameras;
 use crate::context_menu::ContextMenuState;
 use crate::display_object::{EditText, InteractiveObject, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
@@ -18,8 +21,13 @@ use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
+use crate::microphone::Microphones;
+use crate::net_connection::NetConnections;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::security::SecurityManager;
+use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::stub::StubCollection;
 use crate::tag_utils::{SwfMovie, SwfSlice};
@@ -82,6 +90,12 @@ pub struct UpdateContext<'a, 'gc> {
     /// The UI backend, used to detect user interactions.
     pub ui: &'a mut dyn UiBackend,
 
+    /// The microphone backend, used to capture audio.
+    pub microphone: &'a mut dyn MicrophoneBackend,
+
+    /// The camera backend, used to capture video.
+    pub camera: &'a mut dyn CameraBackend,
+
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
@@ -129,6 +143,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// The system properties
     pub system: &'a mut SystemProperties,
 
+    /// Which domains have given which movies access to their content.
+    pub security: &'a mut SecurityManager,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     pub instance_counter: &'a mut i32,
 
@@ -187,6 +204,21 @@ pub struct UpdateContext<'a, 'gc> {
 
     /// Manager of in-progress media streams.
     pub stream_manager: &'a mut StreamManager<'gc>,
+
+    /// List of open connections to media servers.
+    pub net_connections: &'a mut NetConnections<'gc>,
+
+    /// List of open TCP sockets.
+    pub sockets: &'a mut Sockets<'gc>,
+
+    /// List of `LocalConnection`s, and the endpoint they send messages with.
+    pub local_connections: &'a mut LocalConnections<'gc>,
+
+    /// List of microphones that have been opened.
+    pub microphones: &'a mut Microphones<'gc>,
+
+    /// List of cameras that have been opened.
+    pub cameras: &'a mut Cameras<'gc>,
 }
 
 /// Convenience methods for controlling audio.
@@ -226,6 +258,15 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    pub fn start_sample_data_sound(
+        &mut self,
+        sound_object: Avm2Object<'gc>,
+        start_position: u64,
+    ) -> Option<SoundInstanceHandle> {
+        self.audio_manager
+            .start_sample_data_sound(self.audio, sound_object, start_position)
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -310,6 +351,8 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             renderer: self.renderer,
             log: self.log,
             ui: self.ui,
+            microphone: self.microphone,
+            camera: self.camera,
             video: self.video,
             storage: self.storage,
             rng: self.rng,
@@ -322,6 +365,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             player: self.player.clone(),
             load_manager: self.load_manager,
             system: self.system,
+            security: self.security,
             instance_counter: self.instance_counter,
             avm1_shared_objects: self.avm1_shared_objects,
             avm2_shared_objects: self.avm2_shared_objects,
@@ -341,6 +385,11 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             actions_since_timeout_check: self.actions_since_timeout_check,
             frame_phase: self.frame_phase,
             stream_manager: self.stream_manager,
+            net_connections: self.net_connections,
+            sockets: self.sockets,
+            local_connections: self.local_connections,
+            microphones: self.microphones,
+            cameras: self.cameras,
         }
     }
 
@@ -23,6 +23,7 @@ use swf::{BlendMode, ColorTransform, Fixed8};
 mod avm1_button;
 mod avm2_button;
 mod bitmap;
+mod bitmap_cache;
 mod container;
 mod edit_text;
 mod graphic;
@@ -30,8 +31,10 @@ mod interactive;
 mod loader_display;
 mod morph_shape;
 mod movie_clip;
+mod scaling_grid;
 mod stage;
 mod text;
+mod transform_3d;
 mod video;
 
 use crate::avm1::Activation;
@@ -48,12 +51,18 @@ pub use interactive::{Avm2MousePick, InteractiveObject, TInteractiveObject};
 pub use loader_display::LoaderDisplay;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, MovieClipWeak, Scene};
-use ruffle_render::commands::CommandHandler;
+use ruffle_render::commands::{BlendShader, CommandHandler};
 use ruffle_render::filters::Filter;
+use ruffle_render::matrix3d::{Decomposition, Matrix3D};
+pub use scaling_grid::ScalingGrid;
 pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
 pub use text::Text;
+pub use transform_3d::{
+    global_to_local_3d, local_3d_to_global, PerspectiveProjection, Transform3D,
+};
 pub use video::Video;
 
+use self::bitmap_cache::BitmapCache;
 use self::loader_display::LoaderDisplayWeak;
 
 #[derive(Clone, Collect)]
@@ -100,6 +109,10 @@ pub struct DisplayObjectBase<'gc> {
     #[collect(require_static)]
     blend_mode: BlendMode,
 
+    /// The shader that replaces the blend mode, set by `DisplayObject.blendShader`.
+    #[collect(require_static)]
+    blend_shader: Option<BlendShader>,
+
     /// The opaque background color of this display object.
     /// The bounding box of the display object will be filled with the given color. This also
     /// triggers cache-as-bitmap behavior. Only solid backgrounds are supported; the alpha channel
@@ -120,6 +133,31 @@ pub struct DisplayObjectBase<'gc> {
     /// changes immediately (without needing wait for a render)
     #[collect(require_static)]
     next_scroll_rect: Rectangle<Twips>,
+
+    /// The scaling grid ('scale9Grid') of this object, in its own coordinate space.
+    /// Shapes inside this object are distorted by this grid when the object is scaled.
+    /// An invalid rectangle means that no grid is set.
+    #[collect(require_static)]
+    scaling_grid: Rectangle<Twips>,
+
+    /// The surface this object was last rendered to, if it is cached as a bitmap.
+    #[collect(require_static)]
+    bitmap_cache: Option<BitmapCache>,
+
+    /// The matrix used to render this object's cached bitmap ('cacheAsBitmapMatrix').
+    /// When not set, the cached bitmap is rendered with the object's concatenated matrix.
+    #[collect(require_static)]
+    cache_as_bitmap_matrix: Option<Matrix>,
+
+    /// The parts of this object's transform that are only possible in 3D.
+    /// `None` if this object only has a 2D transform.
+    #[collect(require_static)]
+    transform_3d: Option<Transform3D>,
+
+    /// The perspective projection applied to the 3D descendants of this object.
+    /// When not set, the projection of the nearest ancestor is used.
+    #[collect(require_static)]
+    perspective_projection: Option<PerspectiveProjection>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -141,10 +179,16 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             maskee: None,
             sound_transform: Default::default(),
             blend_mode: Default::default(),
+            blend_shader: None,
             opaque_background: Default::default(),
             flags: DisplayObjectFlags::VISIBLE,
             scroll_rect: None,
             next_scroll_rect: Default::default(),
+            scaling_grid: Default::default(),
+            bitmap_cache: None,
+            cache_as_bitmap_matrix: None,
+            transform_3d: None,
+            perspective_projection: None,
         }
     }
 }
@@ -337,6 +381,32 @@ impl<'gc> DisplayObjectBase<'gc> {
         matrix.d = (cos * value.unit()) as f32;
     }
 
+    fn set_transform_3d(&mut self, value: Option<Transform3D>) {
+        self.set_transformed_by_script(true);
+        self.transform_3d = value;
+        self.update_bitmap_cache();
+    }
+
+    /// The transform of this object relative to its parent, including its 3D transform.
+    fn matrix_3d(&mut self) -> Matrix3D {
+        match self.transform_3d {
+            Some(transform_3d) => Matrix3D::recompose(&Decomposition {
+                translation: [self.x(), self.y(), transform_3d.z],
+                rotation: [
+                    transform_3d.rotation_x.into_radians(),
+                    transform_3d.rotation_y.into_radians(),
+                    self.rotation().into_radians(),
+                ],
+                scale: [
+                    self.scale_x().unit(),
+                    self.scale_y().unit(),
+                    transform_3d.scale_z,
+                ],
+            }),
+            None => Matrix3D::from(self.transform.matrix),
+        }
+    }
+
     fn name(&self) -> AvmString<'gc> {
         self.name
     }
@@ -351,6 +421,7 @@ impl<'gc> DisplayObjectBase<'gc> {
 
     fn set_filters(&mut self, filters: Vec<Filter>) {
         self.filters = filters;
+        self.update_bitmap_cache();
     }
 
     fn alpha(&self) -> f64 {
@@ -443,6 +514,14 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.blend_mode = value;
     }
 
+    fn blend_shader(&self) -> Option<&BlendShader> {
+        self.blend_shader.as_ref()
+    }
+
+    fn set_blend_shader(&mut self, value: Option<BlendShader>) {
+        self.blend_shader = value;
+    }
+
     /// The opaque background color of this display object.
     /// The bounding box of the display object will be filled with this color.
     fn opaque_background(&self) -> Option<Color> {
@@ -494,12 +573,33 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.flags.set(DisplayObjectFlags::PLACED_BY_SCRIPT, value);
     }
 
+    /// Whether this object is cached as a bitmap.
+    /// Objects with filters are always cached, regardless of `cacheAsBitmap`.
     fn is_bitmap_cached(&self) -> bool {
-        self.flags.contains(DisplayObjectFlags::CACHE_AS_BITMAP)
+        self.flags.contains(DisplayObjectFlags::CACHE_AS_BITMAP) || !self.filters.is_empty()
     }
 
     fn set_is_bitmap_cached(&mut self, value: bool) {
         self.flags.set(DisplayObjectFlags::CACHE_AS_BITMAP, value);
+        self.update_bitmap_cache();
+    }
+
+    /// Marks the cached bitmap of this object, if any, as out of date.
+    fn invalidate_cached_bitmap(&mut self) {
+        if let Some(cache) = &mut self.bitmap_cache {
+            cache.invalidate();
+        }
+    }
+
+    /// Invalidates the cached bitmap after a change to how this object is cached,
+    /// or releases it if this object no longer needs one.
+    fn update_bitmap_cache(&mut self) {
+        // Objects with a 3D transform are always drawn through a surface.
+        if self.is_bitmap_cached() || self.transform_3d.is_some() {
+            self.invalidate_cached_bitmap();
+        } else {
+            self.bitmap_cache = None;
+        }
     }
 
     fn instantiated_by_timeline(&self) -> bool {
@@ -549,15 +649,21 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
+    // Objects with a 3D transform are projected from a surface, which is positioned
+    // relative to the parent's transform. Masks are always drawn flat.
+    let is_projected = context.allow_mask && this.transform_3d().is_some();
+    let parent_matrix = context.transform_stack.transform().matrix;
     context.transform_stack.push(this.base().transform());
     let blend_mode = this.blend_mode();
-    let original_commands = if blend_mode != BlendMode::Normal {
+    let blend_shader = this.blend_shader();
+    let original_commands = if blend_mode != BlendMode::Normal || blend_shader.is_some() {
         Some(std::mem::take(&mut context.commands))
     } else {
         None
     };
 
-    let scroll_rect_matrix = if let Some(rect) = this.scroll_rect() {
+    // The surface of a projected object is already cropped to its scroll rect.
+    let scroll_rect_matrix = if let Some(rect) = this.scroll_rect().filter(|_| !is_projected) {
         let cur_transform = context.transform_stack.transform();
         // The matrix we use for actually drawing a rectangle for cropping purposes
         // Note that we do *not* apply the translation yet
@@ -585,6 +691,20 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if let Some(m) = mask {
         mask_transform.matrix = this.global_to_local_matrix();
         mask_transform.matrix *= m.local_to_global_matrix();
+    }
+
+    // When both an object and its mask are cached as bitmaps, the mask's alpha channel
+    // is used instead of its shape. The content is rendered into its own layer, which the
+    // mask is then blended into.
+    let alpha_mask =
+        mask.filter(|m| context.allow_mask && this.is_bitmap_cached() && m.is_bitmap_cached());
+    let commands_before_alpha_mask = if alpha_mask.is_some() {
+        Some(std::mem::take(&mut context.commands))
+    } else {
+        None
+    };
+
+    if let Some(m) = mask.filter(|_| alpha_mask.is_none()) {
         context.commands.push_mask();
         context.allow_mask = false;
         context.transform_stack.push(&mask_transform);
@@ -612,7 +732,13 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.commands.activate_mask();
     }
 
-    this.render_self(context);
+    if !(is_projected && transform_3d::render_projected(this, context, parent_matrix))
+        && !(this.is_bitmap_cached()
+            && context.allow_mask
+            && bitmap_cache::render_cached(this, context))
+    {
+        this.render_self(context);
+    }
 
     if let Some(rect_mat) = scroll_rect_matrix {
         // Draw the rectangle again after deactivating the mask,
@@ -622,7 +748,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.commands.pop_mask();
     }
 
-    if let Some(m) = mask {
+    if let Some(m) = mask.filter(|_| alpha_mask.is_none()) {
         context.commands.deactivate_mask();
         context.allow_mask = false;
         context.transform_stack.push(&mask_transform);
@@ -632,9 +758,25 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.commands.pop_mask();
     }
 
+    if let (Some(m), Some(original_commands)) = (alpha_mask, commands_before_alpha_mask) {
+        let content_commands = std::mem::take(&mut context.commands);
+        context.transform_stack.push(&mask_transform);
+        if !bitmap_cache::render_cached(m, context) {
+            m.render_self(context);
+        }
+        context.transform_stack.pop();
+        let mask_commands = std::mem::replace(&mut context.commands, content_commands);
+        context.commands.blend(mask_commands, BlendMode::Alpha);
+        let layer_commands = std::mem::replace(&mut context.commands, original_commands);
+        context.commands.blend(layer_commands, BlendMode::Layer);
+    }
+
     if let Some(original_commands) = original_commands {
         let sub_commands = std::mem::replace(&mut context.commands, original_commands);
-        context.commands.blend(sub_commands, blend_mode);
+        match blend_shader {
+            Some(shader) => context.commands.blend_shader(sub_commands, shader),
+            None => context.commands.blend(sub_commands, blend_mode),
+        }
     }
 
     if scroll_rect_matrix.is_some() {
@@ -726,18 +868,56 @@ pub trait TDisplayObject<'gc>:
                 };
         }
 
-        let mut bounds = *matrix * self.self_bounds();
+        // A scaling grid distorts our own drawing and any shapes directly inside us.
+        let scaling_grid = self.scaling_grid_transform();
+        let self_bounds = self.self_bounds();
+        let mut bounds = match &scaling_grid {
+            Some(scaling_grid) => *matrix * scaling_grid.transform_bounds(&self_bounds),
+            None => *matrix * self_bounds,
+        };
 
         if let Some(ctr) = self.as_container() {
             for child in ctr.iter_render_list() {
-                let matrix = *matrix * *child.base().matrix();
-                bounds = bounds.union(&child.bounds_with_transform(&matrix));
+                let child_bounds = match (&scaling_grid, child) {
+                    (Some(scaling_grid), DisplayObject::Graphic(_)) => {
+                        *matrix * scaling_grid.transform_bounds(&child.local_bounds())
+                    }
+                    _ => child.bounds_with_transform(&(*matrix * *child.base().matrix())),
+                };
+                bounds = bounds.union(&child_bounds);
             }
         }
 
         bounds
     }
 
+    /// The distortion that this object's scaling grid applies to its shapes,
+    /// or `None` if this object has no scaling grid.
+    fn scaling_grid_transform(&self) -> Option<ScalingGrid> {
+        let scaling_grid = self.scaling_grid();
+        if !scaling_grid.is_valid() {
+            return None;
+        }
+
+        ScalingGrid::new(
+            &scaling_grid,
+            &self.scaling_grid_bounds(),
+            self.base().matrix(),
+        )
+    }
+
+    /// The undistorted bounds of all of our content, which a scaling grid
+    /// divides up.
+    fn scaling_grid_bounds(&self) -> Rectangle<Twips> {
+        let mut bounds = self.self_bounds();
+        if let Some(ctr) = self.as_container() {
+            for child in ctr.iter_render_list() {
+                bounds = bounds.union(&child.local_bounds());
+            }
+        }
+        bounds
+    }
+
     fn place_frame(&self) -> u16 {
         self.base().place_frame()
     }
@@ -746,7 +926,11 @@ pub trait TDisplayObject<'gc>:
     }
 
     fn set_matrix(&self, gc_context: MutationContext<'gc, '_>, matrix: Matrix) {
+        let changed = *self.base().matrix() != matrix;
         self.base_mut(gc_context).set_matrix(matrix);
+        if changed {
+            self.invalidate_parent_cached_bitmap(gc_context);
+        }
     }
 
     fn set_color_transform(
@@ -754,8 +938,118 @@ pub trait TDisplayObject<'gc>:
         gc_context: MutationContext<'gc, '_>,
         color_transform: ColorTransform,
     ) {
-        self.base_mut(gc_context)
-            .set_color_transform(color_transform)
+        if *self.base().color_transform() != color_transform {
+            self.base_mut(gc_context)
+                .set_color_transform(color_transform);
+            self.invalidate_parent_cached_bitmap(gc_context);
+        }
+    }
+
+    /// Marks the cached bitmap of this object and of all of its ancestors as out of date.
+    /// This must be called whenever the content of this object changes.
+    fn invalidate_cached_bitmap(&self, gc_context: MutationContext<'gc, '_>) {
+        self.base_mut(gc_context).invalidate_cached_bitmap();
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// Marks the cached bitmaps of the ancestors of this object as out of date.
+    /// This must be called whenever this object changes how it is placed in its parent,
+    /// which doesn't affect its own cached bitmap.
+    fn invalidate_parent_cached_bitmap(&self, gc_context: MutationContext<'gc, '_>) {
+        let mut node = self.parent();
+        while let Some(parent) = node {
+            parent.base_mut(gc_context).invalidate_cached_bitmap();
+            node = parent.parent();
+        }
+    }
+
+    /// The matrix used to render the cached bitmap of this object, or `None` to use the
+    /// object's concatenated matrix.
+    /// Returned by the `cacheAsBitmapMatrix` ActionScript property.
+    fn cache_as_bitmap_matrix(&self) -> Option<Matrix> {
+        self.base().cache_as_bitmap_matrix
+    }
+
+    /// Sets the matrix used to render the cached bitmap of this object.
+    /// Set by the `cacheAsBitmapMatrix` ActionScript property.
+    fn set_cache_as_bitmap_matrix(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        matrix: Option<Matrix>,
+    ) {
+        self.base_mut(gc_context).cache_as_bitmap_matrix = matrix;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// The parts of this object's transform that are only possible in 3D,
+    /// or `None` if it only has a 2D transform.
+    fn transform_3d(&self) -> Option<Transform3D> {
+        self.base().transform_3d
+    }
+
+    /// Sets the 3D part of this object's transform.
+    /// Setting it to `None` turns this object back into a 2D object.
+    fn set_transform_3d(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        transform_3d: Option<Transform3D>,
+    ) {
+        if self.base().transform_3d != transform_3d {
+            self.base_mut(gc_context).set_transform_3d(transform_3d);
+            self.invalidate_parent_cached_bitmap(gc_context);
+        }
+    }
+
+    /// The transform of this object relative to its parent, including its 3D transform.
+    fn matrix_3d(&self, gc_context: MutationContext<'gc, '_>) -> Matrix3D {
+        self.base_mut(gc_context).matrix_3d()
+    }
+
+    /// Sets both the 2D and 3D parts of this object's transform from a 3D matrix.
+    /// Set by the `Transform.matrix3D` ActionScript property.
+    ///
+    /// Only translation, rotation and scale are kept; matrices that can't be
+    /// decomposed are ignored.
+    fn set_matrix_3d(&self, gc_context: MutationContext<'gc, '_>, matrix: &Matrix3D) {
+        let Some(decomposition) = matrix.decompose() else {
+            return;
+        };
+        let [x, y, z] = decomposition.translation;
+        let [rotation_x, rotation_y, rotation_z] = decomposition.rotation;
+        let [scale_x, scale_y, scale_z] = decomposition.scale;
+        let matrix = Matrix3D::recompose(&Decomposition {
+            translation: [x, y, 0.0],
+            rotation: [0.0, 0.0, rotation_z],
+            scale: [scale_x, scale_y, 1.0],
+        })
+        .to_matrix();
+        self.set_matrix(gc_context, matrix);
+        self.set_transform_3d(
+            gc_context,
+            Some(Transform3D {
+                z,
+                rotation_x: Degrees::from_radians(rotation_x),
+                rotation_y: Degrees::from_radians(rotation_y),
+                scale_z,
+            }),
+        );
+    }
+
+    /// The perspective projection applied to the 3D descendants of this object, if set.
+    /// Returned by the `Transform.perspectiveProjection` ActionScript property.
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.base().perspective_projection
+    }
+
+    /// Sets the perspective projection applied to the 3D descendants of this object.
+    /// Set by the `Transform.perspectiveProjection` ActionScript property.
+    fn set_perspective_projection(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        perspective_projection: Option<PerspectiveProjection>,
+    ) {
+        self.base_mut(gc_context).perspective_projection = perspective_projection;
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     /// Should only be used to implement 'Transform.concatenatedMatrix'
@@ -813,6 +1107,7 @@ pub trait TDisplayObject<'gc>:
     /// Set by the `_x`/`x` ActionScript properties.
     fn set_x(&self, gc_context: MutationContext<'gc, '_>, value: f64) {
         self.base_mut(gc_context).set_x(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The `y` position in pixels of this display object in local space.
@@ -825,6 +1120,7 @@ pub trait TDisplayObject<'gc>:
     /// Set by the `_y`/`y` ActionScript properties.
     fn set_y(&self, gc_context: MutationContext<'gc, '_>, value: f64) {
         self.base_mut(gc_context).set_y(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The rotation in degrees this display object in local space.
@@ -840,6 +1136,7 @@ pub trait TDisplayObject<'gc>:
     fn set_rotation(&self, gc_context: MutationContext<'gc, '_>, radians: Degrees) {
         self.base_mut(gc_context).set_rotation(radians);
         self.set_scale_rotation_cached(gc_context);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The X axis scale for this display object in local space.
@@ -855,6 +1152,7 @@ pub trait TDisplayObject<'gc>:
     fn set_scale_x(&self, gc_context: MutationContext<'gc, '_>, value: Percent) {
         self.base_mut(gc_context).set_scale_x(value);
         self.set_scale_rotation_cached(gc_context);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The Y axis scale for this display object in local space.
@@ -870,6 +1168,7 @@ pub trait TDisplayObject<'gc>:
     fn set_scale_y(&self, gc_context: MutationContext<'gc, '_>, value: Percent) {
         self.base_mut(gc_context).set_scale_y(value);
         self.set_scale_rotation_cached(gc_context);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// Gets the pixel width of the AABB containing this display object in local space.
@@ -978,7 +1277,8 @@ pub trait TDisplayObject<'gc>:
     /// 1 is fully opaque.
     /// Set by the `_alpha`/`alpha` ActionScript properties.
     fn set_alpha(&self, gc_context: MutationContext<'gc, '_>, value: f64) {
-        self.base_mut(gc_context).set_alpha(value)
+        self.base_mut(gc_context).set_alpha(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     fn name(&self) -> AvmString<'gc> {
@@ -993,7 +1293,8 @@ pub trait TDisplayObject<'gc>:
     }
 
     fn set_filters(&self, gc_context: MutationContext<'gc, '_>, filters: Vec<Filter>) {
-        self.base_mut(gc_context).set_filters(filters)
+        self.base_mut(gc_context).set_filters(filters);
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     /// Returns the dot-syntax path to this display object, e.g. `_level0.foo.clip`
@@ -1042,6 +1343,7 @@ pub trait TDisplayObject<'gc>:
     }
     fn set_clip_depth(&self, gc_context: MutationContext<'gc, '_>, depth: Depth) {
         self.base_mut(gc_context).set_clip_depth(depth);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// Retrieve the parent of this display object.
@@ -1100,6 +1402,7 @@ pub trait TDisplayObject<'gc>:
             }
         }
         self.base_mut(gc_context).set_masker(node);
+        self.invalidate_cached_bitmap(gc_context);
     }
     fn maskee(&self) -> Option<DisplayObject<'gc>> {
         self.base().maskee()
@@ -1116,6 +1419,7 @@ pub trait TDisplayObject<'gc>:
             }
         }
         self.base_mut(gc_context).set_maskee(node);
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     fn scroll_rect(&self) -> Option<Rectangle<Twips>> {
@@ -1132,6 +1436,16 @@ pub trait TDisplayObject<'gc>:
         rectangle: Rectangle<Twips>,
     ) {
         self.base_mut(gc_context).next_scroll_rect = rectangle;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    fn scaling_grid(&self) -> Rectangle<Twips> {
+        self.base().scaling_grid.clone()
+    }
+
+    fn set_scaling_grid(&self, gc_context: MutationContext<'gc, '_>, rect: Rectangle<Twips>) {
+        self.base_mut(gc_context).scaling_grid = rect;
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     /// Whether this object has been removed. Only applies to AVM1.
@@ -1161,6 +1475,7 @@ pub trait TDisplayObject<'gc>:
     /// Returned by the `_visible`/`visible` ActionScript properties.
     fn set_visible(&self, gc_context: MutationContext<'gc, '_>, value: bool) {
         self.base_mut(gc_context).set_visible(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The blend mode used when rendering this display object.
@@ -1173,6 +1488,19 @@ pub trait TDisplayObject<'gc>:
     /// Values other than the default `BlendMode::Normal` implicitly cause cache-as-bitmap behavior.
     fn set_blend_mode(&self, gc_context: MutationContext<'gc, '_>, value: BlendMode) {
         self.base_mut(gc_context).set_blend_mode(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
+    }
+
+    /// The shader used to blend this display object, in place of its blend mode.
+    fn blend_shader(&self) -> Option<BlendShader> {
+        self.base().blend_shader().cloned()
+    }
+
+    /// Sets the shader used to blend this display object.
+    /// Set by the `blendShader` ActionScript property.
+    fn set_blend_shader(&self, gc_context: MutationContext<'gc, '_>, value: Option<BlendShader>) {
+        self.base_mut(gc_context).set_blend_shader(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// The opaque background color of this display object.
@@ -1186,6 +1514,7 @@ pub trait TDisplayObject<'gc>:
     /// is ignored.
     fn set_opaque_background(&self, gc_context: MutationContext<'gc, '_>, value: Option<Color>) {
         self.base_mut(gc_context).set_opaque_background(value);
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     /// Whether this display object represents the root of loaded content.
@@ -1234,16 +1563,17 @@ pub trait TDisplayObject<'gc>:
     }
 
     /// Whether this display object is cached into a bitmap rendering.
-    /// This is set implicitly when a filter or blend mode is applied, or explicitly by the user
+    /// This is set implicitly when a filter is applied, or explicitly by the user
     /// via the `cacheAsBitmap` property.
     fn is_bitmap_cached(&self) -> bool {
         self.base().is_bitmap_cached()
     }
 
     /// Explicilty sets this display object to be cached into a bitmap rendering.
-    /// Note that the object will still be bitmap cached if a filter or blend mode is active.
+    /// Note that the object will still be bitmap cached if a filter is active.
     fn set_is_bitmap_cached(&self, gc_context: MutationContext<'gc, '_>, value: bool) {
-        self.base_mut(gc_context).set_is_bitmap_cached(value)
+        self.base_mut(gc_context).set_is_bitmap_cached(value);
+        self.invalidate_parent_cached_bitmap(gc_context);
     }
 
     /// Whether this display object has a scroll rectangle applied.
@@ -1253,7 +1583,8 @@ pub trait TDisplayObject<'gc>:
 
     /// Sets whether this display object has a scroll rectangle applied.
     fn set_has_scroll_rect(&self, gc_context: MutationContext<'gc, '_>, value: bool) {
-        self.base_mut(gc_context).set_has_scroll_rect(value)
+        self.base_mut(gc_context).set_has_scroll_rect(value);
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     /// Called whenever the focus tracker has deemed this display object worthy, or no longer worthy,
@@ -1574,6 +1905,12 @@ pub trait TDisplayObject<'gc>:
             if let Some(blend_mode) = place_object.blend_mode {
                 self.set_blend_mode(context.gc_context, blend_mode);
             }
+            if let Some(filters) = &place_object.filters {
+                self.set_filters(
+                    context.gc_context,
+                    filters.iter().map(Filter::from).collect(),
+                );
+            }
             if self.swf_version() >= 11 {
                 if let Some(visible) = place_object.is_visible {
                     self.set_visible(context.gc_context, visible);
@@ -1593,7 +1930,6 @@ pub trait TDisplayObject<'gc>:
             // name, clip_depth, clip_actions
             // These properties are only set on initial placement in `MovieClip::instantiate_child`
             // and can not be modified by subsequent PlaceObject tags.
-            // TODO: Filters need to be applied here.
         }
     }
 
@@ -144,6 +144,7 @@ impl<'gc> Avm1Button<'gc> {
 
         let movie = self.movie();
         self.0.write(context.gc_context).state = state;
+        self.invalidate_cached_bitmap(context.gc_context);
 
         // Create any new children that exist in this state, and remove children
         // that only exist in the previous state.
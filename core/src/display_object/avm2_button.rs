@@ -266,6 +266,7 @@ impl<'gc> Avm2Button<'gc> {
     /// Change the rendered state of the button.
     pub fn set_state(self, context: &mut UpdateContext<'_, 'gc>, state: ButtonState) {
         self.0.write(context.gc_context).state = state;
+        self.invalidate_cached_bitmap(context.gc_context);
         let button = self.0.read();
         if let Some(state) = button.up_state {
             state.set_parent(context, None);
@@ -313,6 +314,7 @@ impl<'gc> Avm2Button<'gc> {
             swf::ButtonState::HIT_TEST => self.0.write(context.gc_context).hit_area = child,
             _ => (),
         }
+        self.invalidate_cached_bitmap(context.gc_context);
 
         if let Some(child) = child {
             if let Some(mut parent) = child.parent().and_then(|parent| parent.as_container()) {
@@ -81,6 +81,10 @@ pub struct BitmapData<'gc> {
     /// Whether or not bitmap smoothing is enabled.
     smoothing: bool,
 
+    /// The generation of the bitmap data when this Bitmap's cached rendering was last
+    /// checked. See `BitmapDataWrapper::generation`.
+    bitmap_data_generation: u32,
+
     /// The AVM2 side of this object.
     ///
     /// AVM1 code cannot directly reference `Bitmap`s, so this does not support
@@ -110,6 +114,7 @@ impl<'gc> Bitmap<'gc> {
 
         let width = bitmap_data.width();
         let height = bitmap_data.height();
+        let bitmap_data_generation = bitmap_data.generation();
 
         Bitmap(GcCell::allocate(
             context.gc_context,
@@ -120,6 +125,7 @@ impl<'gc> Bitmap<'gc> {
                 width,
                 height,
                 smoothing,
+                bitmap_data_generation,
                 avm2_object: None,
                 avm2_bitmap_class: BitmapClass::NoSubclass,
                 movie: context.swf.clone(),
@@ -200,6 +206,19 @@ impl<'gc> Bitmap<'gc> {
         write.width = bitmap_data.read().width();
         write.height = bitmap_data.read().height();
         write.bitmap_data = BitmapDataWrapper::new(bitmap_data);
+        write.bitmap_data_generation = write.bitmap_data.generation();
+        drop(write);
+        self.invalidate_cached_bitmap(context.gc_context);
+    }
+
+    /// Invalidates the cached rendering of this `Bitmap` if the pixels of its
+    /// bitmap data changed since this was last called.
+    pub fn invalidate_if_bitmap_data_changed(self, gc_context: MutationContext<'gc, '_>) {
+        let generation = self.0.read().bitmap_data.generation();
+        if self.0.read().bitmap_data_generation != generation {
+            self.0.write(gc_context).bitmap_data_generation = generation;
+            self.invalidate_cached_bitmap(gc_context);
+        }
     }
 
     pub fn avm2_bitmapdata_class(self) -> Option<Avm2ClassObject<'gc>> {
@@ -238,6 +257,7 @@ impl<'gc> Bitmap<'gc> {
 
     pub fn set_smoothing(self, mc: MutationContext<'gc, '_>, smoothing: bool) {
         self.0.write(mc).smoothing = smoothing;
+        self.invalidate_cached_bitmap(mc);
     }
 }
 
@@ -0,0 +1,321 @@
+//! Bitmap caching of display objects (`cacheAsBitmap`).
+//!
+//! A cached display object is rendered to an offscreen surface once, and that
+//! surface is then drawn in place of the object until something about its
+//! content changes. Filters are applied to the cached surface.
+//!
+//! Objects with a 3D transform are drawn through the same surface, which is then
+//! projected by `transform_3d::render_projected`.
+
+use crate::bitmap::filters::calculate_dest_rect;
+use crate::context::RenderContext;
+use crate::prelude::*;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle};
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::filters::Filter;
+use ruffle_render::transform::{Transform, TransformStack};
+
+/// The largest width or height of a cached surface. Flash Player does not cache
+/// objects that would be larger than this.
+const MAX_SURFACE_SIZE: u32 = 8191;
+
+/// The largest number of pixels in a cached surface.
+const MAX_SURFACE_PIXELS: u32 = 16_777_215;
+
+/// The surface that a bitmap cached display object was last rendered to.
+#[derive(Debug, Default)]
+pub struct BitmapCache {
+    /// Whether the content of the object changed since the surface was drawn.
+    dirty: bool,
+
+    /// The matrix that the surface was drawn with, without its translation.
+    matrix: Option<Matrix>,
+
+    /// The position and size of the surface, in pixels, relative to the object's origin
+    /// after `matrix` was applied.
+    rect: (i32, i32, u32, u32),
+
+    /// The surface that the object is rendered to.
+    surface: Option<BitmapHandle>,
+
+    /// A second surface of the same size, used when applying filters.
+    filter_surface: Option<BitmapHandle>,
+
+    /// Which of the two surfaces holds the final, filtered content.
+    result: Option<BitmapHandle>,
+}
+
+impl Clone for BitmapCache {
+    /// Surfaces are never shared between display objects, so a clone starts out empty
+    /// and is drawn again when it is first used.
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl BitmapCache {
+    /// Marks the surface as out of date, so that it is redrawn before it is used next.
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_valid(&self, matrix: &Matrix, rect: (i32, i32, u32, u32)) -> bool {
+        !self.dirty
+            && self.result.is_some()
+            && self.matrix.as_ref() == Some(matrix)
+            && self.rect == rect
+    }
+
+    /// Returns a blank surface of the given size, reusing `surface` if possible.
+    fn clear_surface(
+        surface: Option<BitmapHandle>,
+        size_changed: bool,
+        width: u32,
+        height: u32,
+        context: &mut RenderContext<'_, '_>,
+    ) -> Option<BitmapHandle> {
+        let pixels = vec![0; width as usize * height as usize * 4];
+        if let Some(surface) = surface.filter(|_| !size_changed) {
+            if context
+                .renderer
+                .update_texture(&surface, width, height, pixels.clone())
+                .is_ok()
+            {
+                return Some(surface);
+            }
+        }
+
+        let bitmap = Bitmap::new(width, height, BitmapFormat::Rgba, pixels);
+        match context.renderer.register_bitmap(bitmap) {
+            Ok(surface) => Some(surface),
+            Err(e) => {
+                tracing::warn!("Failed to create surface for cached bitmap: {:?}", e);
+                None
+            }
+        }
+    }
+
+    /// Renders `this` into the surface, and applies its filters.
+    /// Returns `false` if the surface could not be drawn.
+    fn redraw<'gc>(
+        &mut self,
+        this: DisplayObject<'gc>,
+        context: &mut RenderContext<'_, 'gc>,
+        matrix: Matrix,
+        rect: (i32, i32, u32, u32),
+        filters: &[Filter],
+    ) -> bool {
+        let (x, y, width, height) = rect;
+        let size_changed = (self.rect.2, self.rect.3) != (width, height);
+        self.result = None;
+
+        let surface =
+            match Self::clear_surface(self.surface.take(), size_changed, width, height, context) {
+                Some(surface) => surface,
+                None => return false,
+            };
+        self.surface = Some(surface.clone());
+
+        // Everything below the cached object is drawn relative to the surface's top-left corner,
+        // and the object's color transform is only applied when the surface itself is drawn.
+        let mut transform_stack = TransformStack::new();
+        transform_stack.push(&Transform {
+            matrix: Matrix {
+                tx: Twips::from_pixels_i32(-x),
+                ty: Twips::from_pixels_i32(-y),
+                ..matrix
+            },
+            color_transform: Default::default(),
+        });
+        let mut offscreen_context = RenderContext {
+            renderer: context.renderer,
+            commands: CommandList::new(),
+            gc_context: context.gc_context,
+            library: context.library,
+            transform_stack: &mut transform_stack,
+            is_offscreen: true,
+            stage: context.stage,
+            allow_mask: true,
+        };
+        this.render_self(&mut offscreen_context);
+        let commands = offscreen_context.commands;
+
+        let quality = context.stage.quality();
+        if context
+            .renderer
+            .render_offscreen(surface.clone(), width, height, commands, quality)
+            .is_none()
+        {
+            return false;
+        }
+
+        let mut result = surface;
+        if !filters.is_empty() {
+            let filter_surface = match self.filter_surface.take().filter(|_| !size_changed) {
+                Some(filter_surface) => Some(filter_surface),
+                None => Self::clear_surface(None, true, width, height, context),
+            };
+            if let Some(mut spare) = filter_surface {
+                for filter in filters {
+                    // Filters that the backend can't apply are skipped.
+                    if context
+                        .renderer
+                        .apply_filter(
+                            result.clone(),
+                            (0, 0),
+                            (width, height),
+                            spare.clone(),
+                            (0, 0),
+                            filter.clone(),
+                        )
+                        .is_some()
+                    {
+                        std::mem::swap(&mut result, &mut spare);
+                    }
+                }
+                self.filter_surface = Some(spare);
+            }
+        }
+
+        self.dirty = false;
+        self.matrix = Some(matrix);
+        self.rect = rect;
+        self.result = Some(result);
+        true
+    }
+}
+
+/// The result of bringing the cached surface of a display object up to date.
+pub enum CachedSurface {
+    /// The object has nothing to draw.
+    Empty,
+
+    /// The surface, and the position of its top-left corner in pixels, relative to
+    /// the object's origin after the surface's matrix was applied.
+    Drawn(BitmapHandle, i32, i32),
+}
+
+/// Redraws the cached surface of `this` with the given matrix if it is out of date.
+///
+/// `matrix` must not have a translation. Returns `None` if the object can't be cached,
+/// in which case it should be rendered as usual.
+pub fn update_surface<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    matrix: Matrix,
+) -> Option<CachedSurface> {
+    invalidate_changed_content(this, context);
+
+    if matrix.a * matrix.d - matrix.b * matrix.c == 0.0 {
+        return None;
+    }
+
+    // A scroll rect crops the content; the scroll itself has already been pushed
+    // onto the transform stack.
+    let bounds = match this.scroll_rect() {
+        Some(scroll_rect) => matrix * scroll_rect,
+        None => this.bounds_with_transform(&matrix),
+    };
+    if !bounds.is_valid() {
+        return Some(CachedSurface::Empty);
+    }
+
+    let x = bounds.x_min.to_pixels().floor() as i32;
+    let y = bounds.y_min.to_pixels().floor() as i32;
+    let width = bounds.x_max.to_pixels().ceil() as i32 - x;
+    let height = bounds.y_max.to_pixels().ceil() as i32 - y;
+    let filters = this.filters();
+    let (x, y, width, height) = filters.iter().fold((x, y, width, height), |rect, filter| {
+        calculate_dest_rect(filter, rect)
+    });
+    if width <= 0 || height <= 0 {
+        return Some(CachedSurface::Empty);
+    }
+    let (width, height) = (width as u32, height as u32);
+    if width > MAX_SURFACE_SIZE
+        || height > MAX_SURFACE_SIZE
+        || u64::from(width) * u64::from(height) > u64::from(MAX_SURFACE_PIXELS)
+    {
+        return None;
+    }
+    let rect = (x, y, width, height);
+
+    let mut cache = this
+        .base_mut(context.gc_context)
+        .bitmap_cache
+        .take()
+        .unwrap_or_default();
+    let is_drawn =
+        cache.is_valid(&matrix, rect) || cache.redraw(this, context, matrix, rect, &filters);
+    let surface = cache.result.clone().filter(|_| is_drawn);
+    this.base_mut(context.gc_context).bitmap_cache = Some(cache);
+    surface.map(|surface| CachedSurface::Drawn(surface, x, y))
+}
+
+/// Renders the content of a bitmap cached display object through its cache,
+/// redrawing the cache first if it is out of date.
+///
+/// This replaces `render_self` in `render_base`, and so expects the object's own
+/// transform to already be on the transform stack. Returns `false` if the object
+/// can't be cached, in which case it should be rendered as usual.
+pub fn render_cached<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) -> bool {
+    let transform = context.transform_stack.transform();
+
+    // The surface is drawn with the object's scale, rotation and skew, or with
+    // `cacheAsBitmapMatrix` when set. Translation never invalidates the surface.
+    let cache_as_bitmap_matrix = this.cache_as_bitmap_matrix();
+    let matrix = Matrix {
+        tx: Twips::ZERO,
+        ty: Twips::ZERO,
+        ..cache_as_bitmap_matrix.unwrap_or(transform.matrix)
+    };
+    let (surface, x, y) = match update_surface(this, context, matrix) {
+        Some(CachedSurface::Drawn(surface, x, y)) => (surface, x, y),
+        Some(CachedSurface::Empty) => return true,
+        None => return false,
+    };
+
+    let (x_offset, y_offset) = (Twips::from_pixels_i32(x), Twips::from_pixels_i32(y));
+    let surface_matrix = match cache_as_bitmap_matrix {
+        Some(_) => {
+            let mut inverse = matrix;
+            inverse.invert();
+            transform.matrix * inverse * Matrix::translate(x_offset, y_offset)
+        }
+        // Without `cacheAsBitmapMatrix`, the surface is snapped to whole pixels.
+        None => Matrix::translate(
+            Twips::from_pixels((transform.matrix.tx + x_offset).to_pixels().round()),
+            Twips::from_pixels((transform.matrix.ty + y_offset).to_pixels().round()),
+        ),
+    };
+    context.commands.render_bitmap(
+        surface,
+        Transform {
+            matrix: surface_matrix,
+            color_transform: transform.color_transform,
+        },
+        cache_as_bitmap_matrix.is_some(),
+    );
+    true
+}
+
+/// Invalidates the cache of `this` if it, or any of its descendants, changed in a way
+/// that can't be tracked when the change happens.
+///
+/// This also runs `pre_render` on all descendants, which otherwise would only happen
+/// when the cache is redrawn.
+fn invalidate_changed_content<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    if let Some(bitmap) = this.as_bitmap() {
+        bitmap.invalidate_if_bitmap_data_changed(context.gc_context);
+    } else if this.as_video().is_some() {
+        // Video frames can be replaced at any time.
+        this.invalidate_cached_bitmap(context.gc_context);
+    }
+
+    if let Some(container) = this.as_container() {
+        for child in container.iter_render_list() {
+            child.pre_render(context);
+            invalidate_changed_content(child, context);
+        }
+    }
+}
@@ -217,6 +217,9 @@ pub trait TDisplayObjectContainer<'gc>:
             removed_child.set_parent(context, None);
         }
 
+        let this: DisplayObject<'_> = self.into();
+        this.invalidate_cached_bitmap(context.gc_context);
+
         removed_child
     }
 
@@ -241,6 +244,7 @@ pub trait TDisplayObjectContainer<'gc>:
 
         self.raw_container_mut(context.gc_context)
             .swap_at_depth(context, this, child, depth);
+        this.invalidate_cached_bitmap(context.gc_context);
     }
 
     /// Insert a child display object into the container at a specific position
@@ -280,6 +284,7 @@ pub trait TDisplayObjectContainer<'gc>:
 
         self.raw_container_mut(context.gc_context)
             .insert_at_id(child, index);
+        this.invalidate_cached_bitmap(context.gc_context);
 
         if parent_changed {
             dispatch_added_event(this, child, child_was_on_stage, context);
@@ -297,6 +302,8 @@ pub trait TDisplayObjectContainer<'gc>:
     ) {
         self.raw_container_mut(context.gc_context)
             .swap_at_id(index1, index2);
+        let this: DisplayObject<'_> = (*self).into();
+        this.invalidate_cached_bitmap(context.gc_context);
     }
 
     /// Remove (and unloads) a child display object from this container's render and depth lists.
@@ -356,6 +363,9 @@ pub trait TDisplayObjectContainer<'gc>:
         drop(write);
 
         if removed_from_render_list {
+            let this: DisplayObject<'_> = (*self).into();
+            this.invalidate_cached_bitmap(context.gc_context);
+
             if !context.is_action_script_3() {
                 child.avm1_unload(context);
             }
@@ -424,6 +434,10 @@ pub trait TDisplayObjectContainer<'gc>:
 
             write = self.raw_container_mut(context.gc_context);
         }
+        drop(write);
+
+        let this: DisplayObject<'_> = (*self).into();
+        this.invalidate_cached_bitmap(context.gc_context);
     }
 
     /// Determine if the container is empty.
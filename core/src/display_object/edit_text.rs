@@ -638,6 +638,8 @@ impl<'gc> EditText<'gc> {
 
     /// Redraw the border of this `EditText`.
     fn redraw_border(self, gc_context: MutationContext<'gc, '_>) {
+        self.invalidate_cached_bitmap(gc_context);
+
         let mut write = self.0.write(gc_context);
 
         write.drawing.clear();
@@ -695,6 +697,8 @@ impl<'gc> EditText<'gc> {
     /// have already been calculated and applied to HTML trees lowered into the
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc>) {
+        self.invalidate_cached_bitmap(context.gc_context);
+
         let mut edit_text = self.0.write(context.gc_context);
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.flags.contains(EditTextFlag::WORD_WRAP);
@@ -1092,6 +1096,8 @@ impl<'gc> EditText<'gc> {
         } else {
             text.selection = None;
         }
+        drop(text);
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     pub fn render_settings(self) -> TextRenderSettings {
@@ -1103,7 +1109,8 @@ impl<'gc> EditText<'gc> {
         gc_context: MutationContext<'gc, '_>,
         settings: TextRenderSettings,
     ) {
-        self.0.write(gc_context).render_settings = settings
+        self.0.write(gc_context).render_settings = settings;
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     pub fn hscroll(self) -> f64 {
@@ -1112,6 +1119,7 @@ impl<'gc> EditText<'gc> {
 
     pub fn set_hscroll(self, hscroll: f64, context: &mut UpdateContext<'_, 'gc>) {
         self.0.write(context.gc_context).hscroll = hscroll;
+        self.invalidate_cached_bitmap(context.gc_context);
     }
 
     pub fn scroll(self) -> usize {
@@ -1129,6 +1137,7 @@ impl<'gc> EditText<'gc> {
         };
         let clamped = scroll_lines.clamp(1, self.maxscroll());
         self.0.write(context.gc_context).scroll = clamped;
+        self.invalidate_cached_bitmap(context.gc_context);
     }
 
     pub fn max_chars(self) -> i32 {
@@ -1778,6 +1787,8 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         if !focused {
             text.selection = None;
         }
+        drop(text);
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     fn is_focusable(&self) -> bool {
@@ -107,10 +107,22 @@ impl<'gc> Graphic<'gc> {
     }
 
     pub fn drawing(&self, gc_context: MutationContext<'gc, '_>) -> RefMut<'_, Drawing> {
+        // We're about to change graphics, so invalidate on the next frame
+        self.invalidate_cached_bitmap(gc_context);
         RefMut::map(self.0.write(gc_context), |w| {
             w.drawing.get_or_insert_with(Drawing::new)
         })
     }
+
+    fn render_shape(&self, context: &mut RenderContext<'_, 'gc>) {
+        if let Some(drawing) = &self.0.read().drawing {
+            drawing.render(context);
+        } else if let Some(render_handle) = self.0.read().static_data.render_handle.clone() {
+            context
+                .commands
+                .render_shape(render_handle, context.transform_stack.transform())
+        }
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
@@ -173,6 +185,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             .get_graphic(id)
         {
             self.0.write(context.gc_context).static_data = new_graphic.0.read().static_data;
+            self.invalidate_cached_bitmap(context.gc_context);
         } else {
             tracing::warn!("PlaceObject: expected Graphic at character ID {}", id);
         }
@@ -182,18 +195,26 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         // Noop
     }
 
-    fn render_self(&self, context: &mut RenderContext) {
+    fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
         if !context.is_offscreen && !self.world_bounds().intersects(&context.stage.view_bounds()) {
             // Off-screen; culled
             return;
         }
 
-        if let Some(drawing) = &self.0.read().drawing {
-            drawing.render(context);
-        } else if let Some(render_handle) = self.0.read().static_data.render_handle.clone() {
-            context
-                .commands
-                .render_shape(render_handle, context.transform_stack.transform())
+        // Shapes are distorted by their own scaling grid, or otherwise by that of their parent.
+        if let Some(scaling_grid) = self.scaling_grid_transform() {
+            let bounds = self.self_bounds();
+            scaling_grid.render(context, &Matrix::IDENTITY, &bounds, |context| {
+                self.render_shape(context)
+            });
+        } else if let Some(scaling_grid) = self.parent().and_then(|p| p.scaling_grid_transform()) {
+            let matrix = *self.base().matrix();
+            let bounds = self.local_bounds();
+            scaling_grid.render(context, &matrix, &bounds, |context| {
+                self.render_shape(context)
+            });
+        } else {
+            self.render_shape(context);
         }
     }
 
@@ -546,6 +546,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_morph_shape(context, reader, 2),
+                TagCode::DefineScalingGrid => self
+                    .0
+                    .write(context.gc_context)
+                    .define_scaling_grid(context, reader),
                 TagCode::DefineShape => self
                     .0
                     .write(context.gc_context)
@@ -740,7 +744,10 @@ impl<'gc> MovieClip<'gc> {
         let data = reader.read_slice_to_end();
         if !data.is_empty() {
             let movie = self.movie();
-            let domain = context.library.library_for_movie_mut(movie).avm2_domain();
+            let domain = context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .avm2_domain();
 
             // DoAbc tag seems to be equivalent to a DoAbc2 with Lazy flag set
             if let Err(e) = Avm2::do_abc(
@@ -749,6 +756,7 @@ impl<'gc> MovieClip<'gc> {
                 None,
                 swf::DoAbc2Flag::LAZY_INITIALIZE,
                 domain,
+                movie,
             ) {
                 tracing::warn!("Error loading ABC file: {}", e);
             }
@@ -771,12 +779,22 @@ impl<'gc> MovieClip<'gc> {
         let do_abc = reader.read_do_abc_2()?;
         if !do_abc.data.is_empty() {
             let movie = self.movie();
-            let domain = context.library.library_for_movie_mut(movie).avm2_domain();
+            let domain = context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .avm2_domain();
 
             let name = do_abc.name.to_str_lossy(reader.encoding());
             let name = AvmString::new_utf8(context.gc_context, name);
 
-            if let Err(e) = Avm2::do_abc(context, do_abc.data, Some(name), do_abc.flags, domain) {
+            if let Err(e) = Avm2::do_abc(
+                context,
+                do_abc.data,
+                Some(name),
+                do_abc.flags,
+                domain,
+                movie,
+            ) {
                 tracing::warn!("Error loading ABC file: {}", e);
             }
         }
@@ -2254,6 +2272,8 @@ impl<'gc> MovieClip<'gc> {
     }
 
     pub fn drawing(&self, gc_context: MutationContext<'gc, '_>) -> RefMut<'_, Drawing> {
+        // We're about to change graphics, so invalidate on the next frame
+        self.invalidate_cached_bitmap(gc_context);
         RefMut::map(self.0.write(gc_context), |s| &mut s.drawing)
     }
 
@@ -2556,7 +2576,14 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
-        self.0.read().drawing.render(context);
+        if let Some(scaling_grid) = self.scaling_grid_transform() {
+            let bounds = self.self_bounds();
+            scaling_grid.render(context, &Matrix::IDENTITY, &bounds, |context| {
+                self.0.read().drawing.render(context)
+            });
+        } else {
+            self.0.read().drawing.render(context);
+        }
         self.render_children(context);
     }
 
@@ -3493,6 +3520,38 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_scaling_grid(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc>,
+        reader: &mut SwfStream<'a>,
+    ) -> Result<(), Error> {
+        let id = reader.read_u16()?;
+        let rect = reader.read_rectangle()?;
+        let library = context.library.library_for_movie_mut(self.movie());
+        match library.character_by_id(id) {
+            Some(Character::MovieClip(clip)) => {
+                clip.set_scaling_grid(context.gc_context, rect);
+            }
+            Some(Character::Avm1Button(button)) => {
+                button.set_scaling_grid(context.gc_context, rect);
+            }
+            Some(Character::Avm2Button(button)) => {
+                button.set_scaling_grid(context.gc_context, rect);
+            }
+            Some(_) => {
+                tracing::warn!(
+                    "DefineScalingGrid: Tried to apply on non-sprite or button ID {}",
+                    id
+                );
+            }
+            None => {
+                tracing::warn!("DefineScalingGrid: Character ID {} doesn't exist", id);
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     fn define_button_sound(
         &mut self,
@@ -0,0 +1,298 @@
+//! 9-slice scaling (`scale9Grid`) of shapes.
+//!
+//! A display object with a scaling grid splits its content into a 3x3 grid.
+//! When the object is scaled, the corners of the grid keep their size, the
+//! edges are only stretched along their length, and the center takes up the
+//! rest. Only shapes directly inside the object are affected by this; any
+//! other children are transformed as usual.
+
+use crate::context::RenderContext;
+use crate::prelude::*;
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::transform::Transform;
+
+/// One axis of a scaling grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Axis {
+    /// The edges of the three slices in the untransformed content, in twips.
+    source: [f64; 4],
+
+    /// The edges of the three slices after distortion, in twips.
+    dest: [f64; 4],
+}
+
+impl Axis {
+    fn new(bounds_min: f64, grid_min: f64, grid_max: f64, bounds_max: f64, scale: f64) -> Self {
+        let grid_min = grid_min.clamp(bounds_min, bounds_max);
+        let grid_max = grid_max.clamp(grid_min, bounds_max);
+
+        // The outer slices must keep their size after the object's own scale is applied.
+        let scale = scale.abs();
+        let (mut start, mut end) = (grid_min - bounds_min, bounds_max - grid_max);
+        if scale > 0.0 {
+            start /= scale;
+            end /= scale;
+        }
+
+        // If the object is scaled down so far that the outer slices don't fit,
+        // they're shrunk proportionally and the center disappears.
+        let size = bounds_max - bounds_min;
+        if start + end > size {
+            let factor = size / (start + end);
+            start *= factor;
+            end *= factor;
+        }
+
+        Self {
+            source: [bounds_min, grid_min, grid_max, bounds_max],
+            dest: [bounds_min, bounds_min + start, bounds_max - end, bounds_max],
+        }
+    }
+
+    /// The `(scale, translation)` that maps slice `index` to its destination.
+    fn slice_transform(&self, index: usize) -> (f64, f64) {
+        let source_size = self.source[index + 1] - self.source[index];
+        let dest_size = self.dest[index + 1] - self.dest[index];
+        let scale = if source_size > 0.0 {
+            dest_size / source_size
+        } else {
+            1.0
+        };
+        (scale, self.dest[index] - self.source[index] * scale)
+    }
+
+    /// Maps a coordinate of the untransformed content to its distorted position.
+    fn map(&self, value: f64) -> f64 {
+        let index = if value < self.source[1] {
+            0
+        } else if value < self.source[2] {
+            1
+        } else {
+            2
+        };
+        let (scale, translation) = self.slice_transform(index);
+        value * scale + translation
+    }
+}
+
+/// The distortion a scaling grid applies to the shapes of a display object.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScalingGrid {
+    x: Axis,
+    y: Axis,
+}
+
+impl ScalingGrid {
+    /// Builds the distortion for an object with the given scaling grid, content bounds
+    /// and matrix. All rectangles are in the object's own coordinate space.
+    ///
+    /// Returns `None` if the grid or the bounds are empty, in which case the object's
+    /// content is transformed as usual.
+    pub fn new(
+        grid: &Rectangle<Twips>,
+        bounds: &Rectangle<Twips>,
+        matrix: &Matrix,
+    ) -> Option<Self> {
+        if !grid.is_valid()
+            || grid.width() <= Twips::ZERO
+            || grid.height() <= Twips::ZERO
+            || !bounds.is_valid()
+            || bounds.width() <= Twips::ZERO
+            || bounds.height() <= Twips::ZERO
+        {
+            return None;
+        }
+
+        let scale_x = f64::from(matrix.a).hypot(f64::from(matrix.b));
+        let scale_y = f64::from(matrix.c).hypot(f64::from(matrix.d));
+        Some(Self {
+            x: Axis::new(
+                bounds.x_min.get().into(),
+                grid.x_min.get().into(),
+                grid.x_max.get().into(),
+                bounds.x_max.get().into(),
+                scale_x,
+            ),
+            y: Axis::new(
+                bounds.y_min.get().into(),
+                grid.y_min.get().into(),
+                grid.y_max.get().into(),
+                bounds.y_max.get().into(),
+                scale_y,
+            ),
+        })
+    }
+
+    /// Maps a bounding box of the untransformed content to the bounding box of the
+    /// distorted content.
+    pub fn transform_bounds(&self, bounds: &Rectangle<Twips>) -> Rectangle<Twips> {
+        if !bounds.is_valid() {
+            return bounds.clone();
+        }
+
+        // Each axis is mapped monotonically, so the corners stay the corners.
+        let map =
+            |axis: &Axis, value: Twips| Twips::new(axis.map(value.get().into()).round() as i32);
+        Rectangle {
+            x_min: map(&self.x, bounds.x_min),
+            x_max: map(&self.x, bounds.x_max),
+            y_min: map(&self.y, bounds.y_min),
+            y_max: map(&self.y, bounds.y_max),
+        }
+    }
+
+    /// Renders distorted content by rendering it once per slice of the grid, each
+    /// time stretched by that slice's transform and masked to the slice's destination.
+    ///
+    /// `content_matrix` maps from the content's coordinate space to the coordinate
+    /// space of the object owning the grid, and the top of the transform stack must
+    /// already include it. `content_bounds` are the bounds of the content in the
+    /// owner's coordinate space, and are used to skip slices that would be empty.
+    pub fn render<'gc>(
+        &self,
+        context: &mut RenderContext<'_, 'gc>,
+        content_matrix: &Matrix,
+        content_bounds: &Rectangle<Twips>,
+        mut render: impl FnMut(&mut RenderContext<'_, 'gc>),
+    ) {
+        if !context.allow_mask
+            || content_matrix.a * content_matrix.d == content_matrix.b * content_matrix.c
+        {
+            // The slices are clipped with masks, which can't be used while rendering a mask.
+            // Content that has been scaled down to nothing doesn't need slicing either.
+            render(context);
+            return;
+        }
+
+        let mut inverse_content_matrix = *content_matrix;
+        inverse_content_matrix.invert();
+        let owner_matrix = context.transform_stack.transform().matrix * inverse_content_matrix;
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let source = Rectangle {
+                    x_min: Twips::new(self.x.source[column] as i32),
+                    x_max: Twips::new(self.x.source[column + 1] as i32),
+                    y_min: Twips::new(self.y.source[row] as i32),
+                    y_max: Twips::new(self.y.source[row + 1] as i32),
+                };
+                if source.width() <= Twips::ZERO
+                    || source.height() <= Twips::ZERO
+                    || !content_bounds.intersects(&source)
+                {
+                    continue;
+                }
+
+                let (dest_x, dest_y) = (
+                    Twips::new(self.x.dest[column].round() as i32),
+                    Twips::new(self.y.dest[row].round() as i32),
+                );
+                let dest_width = self.x.dest[column + 1] - self.x.dest[column];
+                let dest_height = self.y.dest[row + 1] - self.y.dest[row];
+                if dest_width <= 0.0 || dest_height <= 0.0 {
+                    continue;
+                }
+
+                let (scale_x, translate_x) = self.x.slice_transform(column);
+                let (scale_y, translate_y) = self.y.slice_transform(row);
+                let slice_matrix = Matrix::create_box(
+                    scale_x as f32,
+                    scale_y as f32,
+                    0.0,
+                    Twips::new(translate_x.round() as i32),
+                    Twips::new(translate_y.round() as i32),
+                );
+
+                // The color doesn't matter, as this is a mask.
+                let mask_matrix = owner_matrix
+                    * Matrix::translate(dest_x, dest_y)
+                    * Matrix::scale(
+                        (dest_width / f64::from(Twips::TWIPS_PER_PIXEL)) as f32,
+                        (dest_height / f64::from(Twips::TWIPS_PER_PIXEL)) as f32,
+                    );
+                context.commands.push_mask();
+                context.commands.draw_rect(Color::WHITE, mask_matrix);
+                context.commands.activate_mask();
+
+                context.transform_stack.push(&Transform {
+                    matrix: inverse_content_matrix * slice_matrix * *content_matrix,
+                    color_transform: Default::default(),
+                });
+                render(context);
+                context.transform_stack.pop();
+
+                context.commands.deactivate_mask();
+                context.commands.draw_rect(Color::WHITE, mask_matrix);
+                context.commands.pop_mask();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x_min: i32, y_min: i32, x_max: i32, y_max: i32) -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::new(x_min),
+            y_min: Twips::new(y_min),
+            x_max: Twips::new(x_max),
+            y_max: Twips::new(y_max),
+        }
+    }
+
+    #[test]
+    fn corners_keep_their_size() {
+        let grid = ScalingGrid::new(
+            &rect(100, 100, 900, 900),
+            &rect(0, 0, 1000, 1000),
+            &Matrix::scale(2.0, 4.0),
+        )
+        .unwrap();
+
+        // After the 2x4 scale, the corners are still 100 twips on screen.
+        assert_eq!(grid.x.dest, [0.0, 50.0, 950.0, 1000.0]);
+        assert_eq!(grid.y.dest, [0.0, 25.0, 975.0, 1000.0]);
+        assert_eq!(
+            grid.transform_bounds(&rect(0, 100, 1000, 500)),
+            rect(0, 25, 1000, 500)
+        );
+    }
+
+    #[test]
+    fn corners_shrink_when_they_do_not_fit() {
+        let grid = ScalingGrid::new(
+            &rect(400, 400, 600, 600),
+            &rect(0, 0, 1000, 1000),
+            &Matrix::scale(0.5, 0.5),
+        )
+        .unwrap();
+
+        assert_eq!(grid.x.dest, [0.0, 500.0, 500.0, 1000.0]);
+        assert_eq!(
+            grid.transform_bounds(&rect(0, 0, 200, 1000)),
+            rect(0, 0, 250, 1000)
+        );
+    }
+
+    #[test]
+    fn empty_grid_is_ignored() {
+        assert_eq!(
+            ScalingGrid::new(
+                &Default::default(),
+                &rect(0, 0, 1000, 1000),
+                &Matrix::scale(2.0, 2.0)
+            ),
+            None
+        );
+        assert_eq!(
+            ScalingGrid::new(
+                &rect(100, 100, 100, 900),
+                &rect(0, 0, 1000, 1000),
+                &Matrix::scale(2.0, 2.0)
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! 3D transforms of display objects ('2.5D').
+//!
+//! Besides its 2D matrix, a display object can be moved along the Z axis, rotated around
+//! the X and Y axes and scaled along the Z axis. Such an object is drawn flat onto a
+//! surface, which is then projected onto the stage using the perspective projection of
+//! its nearest ancestor that has one.
+//!
+//! Bounds and hit tests only consider the 2D part of the transform.
+
+use crate::context::RenderContext;
+use crate::display_object::bitmap_cache::{self, CachedSurface};
+use crate::display_object::{DisplayObject, Stage, TDisplayObject};
+use crate::prelude::*;
+use crate::types::Degrees;
+use gc_arena::MutationContext;
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix3d::Matrix3D;
+
+/// The field of view of a perspective projection that was not set by a movie, in degrees.
+pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+/// The parts of a display object's transform that its 2D matrix can't represent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform3D {
+    /// The position along the Z axis, in pixels. Larger values are further away.
+    pub z: f64,
+
+    /// The rotation around the X axis.
+    pub rotation_x: Degrees,
+
+    /// The rotation around the Y axis.
+    pub rotation_y: Degrees,
+
+    /// The scale along the Z axis, where 1.0 is unscaled.
+    pub scale_z: f64,
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self {
+            z: 0.0,
+            rotation_x: Degrees::from(0.0),
+            rotation_y: Degrees::from(0.0),
+            scale_z: 1.0,
+        }
+    }
+}
+
+/// The perspective projection that a display object applies to its 3D descendants.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The field of view, in degrees. This is always between 0 and 180.
+    pub field_of_view: f64,
+
+    /// The vanishing point, in pixels, in the coordinate space of the object.
+    pub center: (f64, f64),
+}
+
+impl PerspectiveProjection {
+    /// The projection used when no ancestor of an object has one, centered on the stage.
+    pub fn for_stage(stage: Stage<'_>) -> Self {
+        let (width, height) = stage.stage_size();
+        Self {
+            field_of_view: DEFAULT_FIELD_OF_VIEW,
+            center: (f64::from(width) / 2.0, f64::from(height) / 2.0),
+        }
+    }
+
+    /// The distance between the viewer and the `z = 0` plane, in pixels.
+    /// The field of view always spans the width of the stage.
+    pub fn focal_length(&self, stage_width: f64) -> f64 {
+        stage_width / 2.0 / (self.field_of_view.to_radians() / 2.0).tan()
+    }
+}
+
+/// The perspective projection that applies to `this`, as a matrix from stage
+/// coordinates to stage coordinates.
+pub fn projection_matrix<'gc>(this: DisplayObject<'gc>, stage: Stage<'gc>) -> Matrix3D {
+    let stage_width = f64::from(stage.stage_size().0);
+    let mut node = this.parent();
+    while let Some(ancestor) = node {
+        if let Some(projection) = ancestor.perspective_projection() {
+            let (x, y) = ancestor.local_to_global((
+                Twips::from_pixels(projection.center.0),
+                Twips::from_pixels(projection.center.1),
+            ));
+            return Matrix3D::perspective(
+                projection.focal_length(stage_width),
+                x.to_pixels(),
+                y.to_pixels(),
+            );
+        }
+        node = ancestor.parent();
+    }
+
+    let projection = PerspectiveProjection::for_stage(stage);
+    Matrix3D::perspective(
+        projection.focal_length(stage_width),
+        projection.center.0,
+        projection.center.1,
+    )
+}
+
+/// The transform from the local space of `this` to the stage, including the 3D transforms
+/// of `this` and its ancestors, but not the perspective projection.
+pub fn concatenated_matrix_3d<'gc>(
+    this: DisplayObject<'gc>,
+    gc_context: MutationContext<'gc, '_>,
+) -> Matrix3D {
+    let mut matrix = Matrix3D::IDENTITY;
+    let mut node = Some(this);
+    while let Some(display_object) = node {
+        // We want to transform to Stage-local coordinates,
+        // so do *not* apply the Stage's matrix
+        if display_object.as_stage().is_some() {
+            break;
+        }
+        if let Some(rect) = display_object.scroll_rect() {
+            matrix =
+                Matrix3D::translate(-rect.x_min.to_pixels(), -rect.y_min.to_pixels(), 0.0) * matrix;
+        }
+        matrix = display_object.matrix_3d(gc_context) * matrix;
+        node = display_object.parent();
+    }
+    matrix
+}
+
+/// Projects a point in the local space of `this` onto the stage.
+///
+/// Returns `None` if the point is behind the viewer.
+pub fn local_3d_to_global<'gc>(
+    this: DisplayObject<'gc>,
+    gc_context: MutationContext<'gc, '_>,
+    stage: Stage<'gc>,
+    point: (f64, f64, f64),
+) -> Option<(f64, f64)> {
+    let matrix = projection_matrix(this, stage) * concatenated_matrix_3d(this, gc_context);
+    matrix.project_point(point.0, point.1, point.2)
+}
+
+/// Finds the point on the `z = 0` plane of `this` that is displayed at the given
+/// stage position.
+///
+/// Returns `None` if there is no such point.
+pub fn global_to_local_3d<'gc>(
+    this: DisplayObject<'gc>,
+    gc_context: MutationContext<'gc, '_>,
+    stage: Stage<'gc>,
+    point: (f64, f64),
+) -> Option<(f64, f64)> {
+    let matrix = projection_matrix(this, stage) * concatenated_matrix_3d(this, gc_context);
+    matrix.unproject_point(point.0, point.1)
+}
+
+/// Renders the content of a display object with a 3D transform by projecting its cached
+/// surface.
+///
+/// This replaces `render_self` in `render_base`, and so expects the object's own 2D
+/// transform to already be on the transform stack. `parent_matrix` is the matrix that
+/// was on the transform stack before that. Returns `false` if the object can't be
+/// drawn to a surface, in which case it should be rendered as usual.
+pub fn render_projected<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    parent_matrix: Matrix,
+) -> bool {
+    let transform = context.transform_stack.transform();
+
+    // The surface is drawn with the scale, rotation and skew that the object
+    // would have without its 3D transform.
+    let matrix = Matrix {
+        tx: Twips::ZERO,
+        ty: Twips::ZERO,
+        ..transform.matrix
+    };
+    let (surface, x, y) = match bitmap_cache::update_surface(this, context, matrix) {
+        Some(CachedSurface::Drawn(surface, x, y)) => (surface, x, y),
+        Some(CachedSurface::Empty) => return true,
+        None => return false,
+    };
+
+    // Maps the surface back to the object's local space.
+    let mut surface_to_local = matrix;
+    surface_to_local.invert();
+    let mut surface_to_local =
+        surface_to_local * Matrix::translate(Twips::from_pixels_i32(x), Twips::from_pixels_i32(y));
+    if let Some(rect) = this.scroll_rect() {
+        surface_to_local = Matrix::translate(-rect.x_min, -rect.y_min) * surface_to_local;
+    }
+
+    // The projection happens in stage coordinates, so the part of the parent's
+    // transform that maps the stage to the render target is applied afterwards.
+    let parent_to_stage = this
+        .parent()
+        .map(|parent| parent.local_to_global_matrix())
+        .unwrap_or_default();
+    let mut stage_to_target = parent_to_stage;
+    stage_to_target.invert();
+    let stage_to_target = parent_matrix * stage_to_target;
+
+    let projected = Matrix3D::from(stage_to_target)
+        * projection_matrix(this, context.stage)
+        * Matrix3D::from(parent_to_stage)
+        * this.matrix_3d(context.gc_context)
+        * Matrix3D::from(surface_to_local);
+    context
+        .commands
+        .render_bitmap_projected(surface, projected, transform.color_transform, true);
+    true
+}
@@ -1,9 +1,10 @@
 //! Video player display object
 
-use crate::avm1::{Object as Avm1Object, StageObject as Avm1StageObject};
+use crate::avm1::{Object as Avm1Object, StageObject as Avm1StageObject, Value as Avm1Value};
 use crate::avm2::{
     Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
 };
+use crate::camera::Camera;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
 use crate::prelude::*;
@@ -116,6 +117,27 @@ pub enum VideoSource<'gc> {
         #[collect(require_static)]
         size: Option<Rectangle<Twips>>,
     },
+    /// An attached Camera.
+    Camera {
+        /// The movie whose code created the Video object.
+        movie: Arc<SwfMovie>,
+
+        /// The camera that frames are captured from.
+        camera: Camera<'gc>,
+
+        /// The size of the video.
+        #[collect(require_static)]
+        size: Rectangle<Twips>,
+    },
+    /// A video created by script that has nothing attached to it yet.
+    Unconnected {
+        /// The movie whose code created the Video object.
+        movie: Arc<SwfMovie>,
+
+        /// The size of the video.
+        #[collect(require_static)]
+        size: Rectangle<Twips>,
+    },
 }
 
 impl<'gc> Video<'gc> {
@@ -147,22 +169,110 @@ impl<'gc> Video<'gc> {
         ))
     }
 
+    /// Construct a Video object with no video source, as created by the
+    /// AVM2 `Video` constructor.
+    pub fn new(
+        context: &mut UpdateContext<'_, 'gc>,
+        width: i32,
+        height: i32,
+        object: Option<AvmObject<'gc>>,
+    ) -> Self {
+        let source = GcCell::allocate(
+            context.gc_context,
+            VideoSource::Unconnected {
+                movie: context.swf.clone(),
+                size: Rectangle {
+                    x_min: Twips::ZERO,
+                    y_min: Twips::ZERO,
+                    x_max: Twips::from_pixels_i32(width),
+                    y_max: Twips::from_pixels_i32(height),
+                },
+            },
+        );
+
+        Video(GcCell::allocate(
+            context.gc_context,
+            VideoData {
+                base: Default::default(),
+                source,
+                stream: VideoStream::Uninstantiated(0),
+                decoded_frame: None,
+                object,
+                keyframes: BTreeSet::new(),
+            },
+        ))
+    }
+
     /// Convert this Video into a NetStream sourced video.
     ///
     /// Existing video state related to the old video stream will be dropped.
+    /// The video keeps its current size, and the stream's frames will be
+    /// scaled to fit it.
     pub fn attach_netstream(self, context: &mut UpdateContext<'_, 'gc>, stream: NetStream<'gc>) {
         let movie = self.movie();
+        let size = self.self_bounds();
         let mut video = self.0.write(context.gc_context);
 
         *video.source.write(context.gc_context) = VideoSource::NetStream {
             movie,
             stream,
             num_frames: None,
-            size: None,
+            size: Some(size),
+        };
+
+        video.stream = VideoStream::Uninstantiated(0);
+        video.keyframes = BTreeSet::new();
+    }
+
+    /// Convert this Video into a Camera sourced video.
+    ///
+    /// Existing video state related to the old video stream will be dropped.
+    /// The video keeps its current size, and the camera's frames will be
+    /// scaled to fit it.
+    pub fn attach_camera(self, context: &mut UpdateContext<'_, 'gc>, camera: Camera<'gc>) {
+        let movie = self.movie();
+        let size = self.self_bounds();
+        let mut video = self.0.write(context.gc_context);
+
+        *video.source.write(context.gc_context) = VideoSource::Camera {
+            movie,
+            camera,
+            size,
         };
 
         video.stream = VideoStream::Uninstantiated(0);
         video.keyframes = BTreeSet::new();
+        video.decoded_frame = None;
+    }
+
+    /// Disconnect this Video from its NetStream or Camera, leaving it empty.
+    pub fn detach(self, context: &mut UpdateContext<'_, 'gc>) {
+        let movie = self.movie();
+        let size = self.self_bounds();
+        let mut video = self.0.write(context.gc_context);
+
+        *video.source.write(context.gc_context) = VideoSource::Unconnected { movie, size };
+
+        video.stream = VideoStream::Uninstantiated(0);
+        video.keyframes = BTreeSet::new();
+        video.decoded_frame = None;
+    }
+
+    /// The size of the most recently decoded video frame, in pixels.
+    ///
+    /// This is zero until the first frame has been decoded.
+    pub fn video_size(self) -> (u16, u16) {
+        let read = self.0.read();
+        let bitmap = match &*read.source.read() {
+            VideoSource::NetStream { stream, .. } => stream.last_decoded_bitmap(),
+            VideoSource::Camera { camera, .. } => camera.bitmap(),
+            _ => read
+                .decoded_frame
+                .as_ref()
+                .map(|(_, bitmap)| bitmap.clone()),
+        };
+
+        bitmap.map_or((0, 0), |bitmap| (bitmap.width, bitmap.height))
     }
 
     /// Preload frame data from an SWF.
@@ -190,7 +300,9 @@ impl<'gc> Video<'gc> {
 
                 frames.insert(tag.frame_num.into(), (subslice.start, subslice.end));
             }
-            VideoSource::NetStream { .. } => {}
+            VideoSource::NetStream { .. }
+            | VideoSource::Camera { .. }
+            | VideoSource::Unconnected { .. } => {}
         }
     }
 
@@ -215,6 +327,7 @@ impl<'gc> Video<'gc> {
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => Some(streamdef.num_frames as usize),
             VideoSource::NetStream { num_frames, .. } => *num_frames,
+            VideoSource::Camera { .. } | VideoSource::Unconnected { .. } => None,
         };
 
         if let Some(num_frames) = num_frames {
@@ -308,7 +421,9 @@ impl<'gc> Video<'gc> {
                     }
                 }
             },
-            VideoSource::NetStream { .. } => return,
+            VideoSource::NetStream { .. }
+            | VideoSource::Camera { .. }
+            | VideoSource::Unconnected { .. } => return,
         };
 
         drop(read);
@@ -404,7 +519,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
                 (stream, movie.clone(), keyframes)
             }
-            VideoSource::NetStream { .. } => return,
+            VideoSource::NetStream { .. }
+            | VideoSource::Camera { .. }
+            | VideoSource::Unconnected { .. } => return,
         };
 
         let starting_seek = if let VideoStream::Uninstantiated(seek_to) = write.stream {
@@ -460,7 +577,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     fn id(&self) -> CharacterId {
         match (*self.0.read().source.read()).borrow() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
-            VideoSource::NetStream { .. } => 0,
+            VideoSource::NetStream { .. }
+            | VideoSource::Camera { .. }
+            | VideoSource::Unconnected { .. } => 0,
         }
     }
 
@@ -473,6 +592,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 y_max: Twips::from_pixels_i32(streamdef.height.into()),
             },
             VideoSource::NetStream { size, .. } => size.clone().unwrap_or_default(),
+            VideoSource::Camera { size, .. } | VideoSource::Unconnected { size, .. } => {
+                size.clone()
+            }
         }
     }
 
@@ -486,7 +608,18 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
         let read = self.0.read();
 
-        if let Some((_frame_id, ref bitmap)) = read.decoded_frame {
+        // NetStreams decode their own frames, and cameras capture their own
+        // frames, which may be shared by several videos.
+        let bitmap = match &*read.source.read() {
+            VideoSource::NetStream { stream, .. } => stream.last_decoded_bitmap(),
+            VideoSource::Camera { camera, .. } => camera.bitmap(),
+            _ => read
+                .decoded_frame
+                .as_ref()
+                .map(|(_, bitmap)| bitmap.clone()),
+        };
+
+        if let Some(ref bitmap) = bitmap {
             let mut transform = context.transform_stack.transform();
             let bounds = self.self_bounds();
 
@@ -507,6 +640,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 VideoSource::NetStream { num_frames, .. } => {
                     (false, num_frames.unwrap_or(0), self.movie().version())
                 }
+                VideoSource::Camera { movie, .. } | VideoSource::Unconnected { movie, .. } => {
+                    (false, 0, movie.version())
+                }
             };
 
             let smoothing = match (context.stage.quality(), version) {
@@ -527,6 +663,24 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         context.transform_stack.pop();
     }
 
+    fn object(&self) -> Avm1Value<'gc> {
+        self.0
+            .read()
+            .object
+            .and_then(|o| o.as_avm1_object())
+            .map(Avm1Value::from)
+            .unwrap_or(Avm1Value::Undefined)
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .object
+            .and_then(|o| o.as_avm2_object())
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Null)
+    }
+
     fn set_object2(&self, context: &mut UpdateContext<'_, 'gc>, to: Avm2Object<'gc>) {
         self.0.write(context.gc_context).object = Some(to.into());
     }
@@ -535,6 +689,8 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         match &*self.0.read().source.read() {
             VideoSource::Swf { movie, .. } => movie.clone(),
             VideoSource::NetStream { movie, .. } => movie.clone(),
+            VideoSource::Camera { movie, .. } => movie.clone(),
+            VideoSource::Unconnected { movie, .. } => movie.clone(),
         }
     }
 }
@@ -3,11 +3,17 @@ use gc_arena::Collect;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
 use ruffle_render::commands::CommandHandler;
-use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
+use ruffle_render::shape_utils::{
+    cubic_curve_to_quadratics, DistilledShape, DrawCommand, DrawPath, FillRule,
+};
 use std::cell::{Cell, RefCell};
-use swf::{FillStyle, LineStyle, Rectangle, Twips};
+use swf::{FillStyle, Fixed16, LineStyle, Matrix, Rectangle, Twips};
 
-#[derive(Clone, Debug, Collect)]
+/// The number of parts that each edge of a triangle with perspective-correct
+/// texture coordinates is split into.
+const TRIANGLE_SUBDIVISIONS: usize = 4;
+
+#[derive(Debug, Collect)]
 #[collect(require_static)]
 pub struct Drawing {
     render_handle: RefCell<Option<ShapeHandle>>,
@@ -21,7 +27,6 @@ pub struct Drawing {
     pending_lines: Vec<DrawingLine>,
     cursor: (Twips, Twips),
     fill_start: (Twips, Twips),
-    winding_rule: FillRule,
 }
 
 impl Default for Drawing {
@@ -30,6 +35,25 @@ impl Default for Drawing {
     }
 }
 
+impl Clone for Drawing {
+    /// Copies the content of this drawing. The copy registers its own shape when rendered.
+    fn clone(&self) -> Self {
+        Self {
+            render_handle: RefCell::new(None),
+            shape_bounds: self.shape_bounds.clone(),
+            edge_bounds: self.edge_bounds.clone(),
+            dirty: Cell::new(true),
+            paths: self.paths.clone(),
+            bitmaps: self.bitmaps.clone(),
+            current_fill: self.current_fill.clone(),
+            current_line: self.current_line.clone(),
+            pending_lines: self.pending_lines.clone(),
+            cursor: self.cursor,
+            fill_start: self.fill_start,
+        }
+    }
+}
+
 impl Drawing {
     pub fn new() -> Self {
         Self {
@@ -44,7 +68,6 @@ impl Drawing {
             pending_lines: Vec::new(),
             cursor: (Twips::ZERO, Twips::ZERO),
             fill_start: (Twips::ZERO, Twips::ZERO),
-            winding_rule: FillRule::EvenOdd,
         }
     }
 
@@ -61,11 +84,6 @@ impl Drawing {
             pending_lines: Vec::new(),
             cursor: (Twips::ZERO, Twips::ZERO),
             fill_start: (Twips::ZERO, Twips::ZERO),
-            winding_rule: if shape.flags.contains(swf::ShapeFlag::NON_ZERO_WINDING_RULE) {
-                FillRule::NonZero
-            } else {
-                FillRule::EvenOdd
-            },
         };
 
         let shape: DistilledShape = shape.into();
@@ -89,8 +107,8 @@ impl Drawing {
                     commands,
                     winding_rule,
                 } => {
-                    this.set_winding_rule(winding_rule);
                     this.set_fill_style(Some(style.clone()));
+                    this.set_winding_rule(winding_rule);
 
                     for command in commands {
                         this.draw_command(command);
@@ -104,8 +122,14 @@ impl Drawing {
         this
     }
 
+    /// Sets the winding rule of the current fill.
+    ///
+    /// Only the current path is affected, fills started afterwards use the even-odd rule.
     pub fn set_winding_rule(&mut self, rule: FillRule) {
-        self.winding_rule = rule;
+        if let Some(fill) = &mut self.current_fill {
+            fill.winding_rule = rule;
+            self.dirty.set(true);
+        }
     }
 
     pub fn set_fill_style(&mut self, style: Option<FillStyle>) {
@@ -135,6 +159,7 @@ impl Drawing {
                     x: self.cursor.0,
                     y: self.cursor.1,
                 }],
+                winding_rule: FillRule::EvenOdd,
             });
         }
         self.fill_start = self.cursor;
@@ -224,82 +249,163 @@ impl Drawing {
         self.dirty.set(true);
     }
 
-    pub fn add_bitmap(&mut self, bitmap: BitmapInfo) -> u16 {
-        let id = self.bitmaps.len() as u16;
-        self.bitmaps.push(bitmap);
-        id
+    /// Draws a cubic Bezier curve from the current position.
+    /// Curves are always drawn as quadratic curves, so the cubic is approximated.
+    pub fn cubic_curve_to(
+        &mut self,
+        control_a: (Twips, Twips),
+        control_b: (Twips, Twips),
+        anchor: (Twips, Twips),
+    ) {
+        for command in cubic_curve_to_quadratics(self.cursor, control_a, control_b, anchor) {
+            self.draw_command(command);
+        }
     }
 
-    pub fn render(&self, context: &mut RenderContext) {
-        if self.dirty.get() {
-            self.dirty.set(false);
-            let mut paths = Vec::with_capacity(self.paths.len());
-
-            for path in &self.paths {
-                match path {
-                    DrawingPath::Fill(fill) => {
-                        paths.push(DrawPath::Fill {
-                            style: &fill.style,
-                            commands: fill.commands.to_owned(),
-                            winding_rule: FillRule::EvenOdd,
-                        });
-                    }
-                    DrawingPath::Line(line) => {
-                        paths.push(DrawPath::Stroke {
-                            style: &line.style,
-                            commands: line.commands.to_owned(),
-                            is_closed: line.is_closed,
-                        });
+    /// Draws each triangle as a separate path, filled with the current fill and
+    /// outlined with the current line.
+    ///
+    /// When the current fill is a bitmap fill and a triangle has texture coordinates,
+    /// the bitmap is mapped onto that triangle instead of using the fill's own matrix.
+    pub fn draw_triangles(&mut self, triangles: &[Triangle]) {
+        // Finish the current path, so that it stays below the triangles.
+        let style = self.current_fill.as_ref().map(|fill| fill.style.clone());
+        self.set_fill_style(style);
+
+        for triangle in triangles {
+            if let Some(fill) = &self.current_fill {
+                let mut fills = Vec::new();
+                match (&fill.style, triangle.uvt) {
+                    (FillStyle::Bitmap { id, matrix, .. }, Some(uvt)) => {
+                        // Texture coordinates are normalized, but the fill matrix maps bitmap pixels.
+                        let (width, height) = self
+                            .bitmaps
+                            .get(usize::from(*id))
+                            .map_or((1.0, 1.0), |bitmap| {
+                                (f64::from(bitmap.width), f64::from(bitmap.height))
+                            });
+                        for (points, uv) in subdivide_triangle(triangle.points, uvt) {
+                            let uv = uv.map(|(u, v)| (u * width, v * height));
+                            let mut style = fill.style.clone();
+                            if let FillStyle::Bitmap { matrix: m, .. } = &mut style {
+                                *m = bitmap_fill_matrix(points, uv).unwrap_or(*matrix);
+                            }
+                            fills.push((style, points));
+                        }
                     }
+                    _ => fills.push((fill.style.clone(), triangle.points)),
                 }
+                self.paths.extend(fills.into_iter().map(|(style, points)| {
+                    DrawingPath::Fill(DrawingFill {
+                        style,
+                        commands: triangle_commands(points),
+                        winding_rule: FillRule::EvenOdd,
+                    })
+                }));
             }
 
-            if let Some(fill) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style: &fill.style,
-                    commands: fill.commands.to_owned(),
-                    winding_rule: FillRule::EvenOdd,
-                })
-            }
+            let stroke_width = if let Some(line) = &self.current_line {
+                self.paths.push(DrawingPath::Line(DrawingLine {
+                    style: line.style.clone(),
+                    commands: triangle_commands(triangle.points),
+                    is_closed: true,
+                }));
+                line.style.width()
+            } else {
+                Twips::ZERO
+            };
 
-            for line in &self.pending_lines {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo {
-                        x: self.fill_start.0,
-                        y: self.fill_start.1,
-                    });
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
+            for command in triangle_commands(triangle.points) {
+                self.shape_bounds = stretch_bounds(&self.shape_bounds, &command, stroke_width);
+                self.edge_bounds = stretch_bounds(&self.edge_bounds, &command, Twips::ZERO);
             }
+        }
 
-            if let Some(line) = &self.current_line {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo {
-                        x: self.fill_start.0,
-                        y: self.fill_start.1,
+        self.dirty.set(true);
+    }
+
+    pub fn add_bitmap(&mut self, bitmap: BitmapInfo) -> u16 {
+        let id = self.bitmaps.len() as u16;
+        self.bitmaps.push(bitmap);
+        id
+    }
+
+    /// The paths of this drawing, in the order they are drawn, including the ones that
+    /// are still being drawn.
+    pub fn draw_paths(&self) -> Vec<DrawPath<'_>> {
+        let mut paths = Vec::with_capacity(self.paths.len());
+
+        for path in &self.paths {
+            match path {
+                DrawingPath::Fill(fill) => {
+                    paths.push(DrawPath::Fill {
+                        style: &fill.style,
+                        commands: fill.commands.to_owned(),
+                        winding_rule: fill.winding_rule,
                     });
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
+                }
+                DrawingPath::Line(line) => {
+                    paths.push(DrawPath::Stroke {
+                        style: &line.style,
+                        commands: line.commands.to_owned(),
+                        is_closed: line.is_closed,
+                    });
+                }
             }
+        }
+
+        if let Some(fill) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+                winding_rule: fill.winding_rule,
+            })
+        }
+
+        for line in &self.pending_lines {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo {
+                    x: self.fill_start.0,
+                    y: self.fill_start.1,
+                });
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        if let Some(line) = &self.current_line {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo {
+                    x: self.fill_start.0,
+                    y: self.fill_start.1,
+                });
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
 
+        paths
+    }
+
+    pub fn render(&self, context: &mut RenderContext) {
+        if self.dirty.get() {
+            self.dirty.set(false);
             let shape = DistilledShape {
-                paths,
+                paths: self.draw_paths(),
                 shape_bounds: self.shape_bounds.clone(),
                 edge_bounds: self.edge_bounds.clone(),
                 id: 0,
@@ -328,7 +434,11 @@ impl Drawing {
         for path in &self.paths {
             match path {
                 DrawingPath::Fill(fill) => {
-                    if shape_utils::draw_command_fill_hit_test(&fill.commands, point) {
+                    if shape_utils::draw_command_fill_hit_test(
+                        &fill.commands,
+                        fill.winding_rule,
+                        point,
+                    ) {
                         return true;
                     }
                 }
@@ -347,7 +457,7 @@ impl Drawing {
 
         // The pending fill will auto-close.
         if let Some(fill) = &self.current_fill {
-            if shape_utils::draw_command_fill_hit_test(&fill.commands, point) {
+            if shape_utils::draw_command_fill_hit_test(&fill.commands, fill.winding_rule, point) {
                 return true;
             }
         }
@@ -436,6 +546,7 @@ impl BitmapSource for Drawing {
 struct DrawingFill {
     style: FillStyle,
     commands: Vec<DrawCommand>,
+    winding_rule: FillRule,
 }
 
 #[derive(Debug, Clone)]
@@ -451,6 +562,101 @@ enum DrawingPath {
     Line(DrawingLine),
 }
 
+/// A triangle drawn by `Graphics.drawTriangles`.
+#[derive(Clone, Copy, Debug)]
+pub struct Triangle {
+    /// The corners of the triangle, in pixels.
+    pub points: [(f64, f64); 3],
+
+    /// The position of each corner on the bitmap of a bitmap fill, as `(u, v, t)`.
+    /// `u` and `v` are in units of the bitmap's size, and `t` is the perspective factor
+    /// that the texture coordinates are corrected with.
+    pub uvt: Option<[(f64, f64, f64); 3]>,
+}
+
+fn triangle_commands(points: [(f64, f64); 3]) -> Vec<DrawCommand> {
+    let [(x0, y0), (x1, y1), (x2, y2)] =
+        points.map(|(x, y)| (Twips::from_pixels(x), Twips::from_pixels(y)));
+    vec![
+        DrawCommand::MoveTo { x: x0, y: y0 },
+        DrawCommand::LineTo { x: x1, y: y1 },
+        DrawCommand::LineTo { x: x2, y: y2 },
+        DrawCommand::LineTo { x: x0, y: y0 },
+    ]
+}
+
+/// The corners of a triangle, and the texture coordinates of each corner.
+type AffineTriangle = ([(f64, f64); 3], [(f64, f64); 3]);
+
+/// Splits a triangle into triangles with affine texture coordinates.
+///
+/// If the texture coordinates of the triangle need perspective correction, the triangle
+/// is subdivided so that the bitmap is approximately correct on each part.
+fn subdivide_triangle(points: [(f64, f64); 3], uvt: [(f64, f64, f64); 3]) -> Vec<AffineTriangle> {
+    let is_perspective =
+        uvt.iter().all(|&(_, _, t)| t > 0.0) && (uvt[0].2 != uvt[1].2 || uvt[0].2 != uvt[2].2);
+    if !is_perspective {
+        return vec![(points, uvt.map(|(u, v, _)| (u, v)))];
+    }
+
+    // Positions are interpolated linearly on the screen, while `u * t`, `v * t`
+    // and `t` are interpolated linearly to correct the texture coordinates.
+    let vertex = |i: usize, j: usize| {
+        let n = TRIANGLE_SUBDIVISIONS as f64;
+        let weights = [(n - (i + j) as f64) / n, i as f64 / n, j as f64 / n];
+        let (mut x, mut y, mut u, mut v, mut t) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for ((weight, point), uvt) in weights.iter().zip(points).zip(uvt) {
+            x += weight * point.0;
+            y += weight * point.1;
+            u += weight * uvt.0 * uvt.2;
+            v += weight * uvt.1 * uvt.2;
+            t += weight * uvt.2;
+        }
+        ((x, y), (u / t, v / t))
+    };
+    let triangle = |corners: [(usize, usize); 3]| {
+        let [a, b, c] = corners.map(|(i, j)| vertex(i, j));
+        ([a.0, b.0, c.0], [a.1, b.1, c.1])
+    };
+
+    let mut triangles = Vec::with_capacity(TRIANGLE_SUBDIVISIONS * TRIANGLE_SUBDIVISIONS);
+    for i in 0..TRIANGLE_SUBDIVISIONS {
+        for j in 0..TRIANGLE_SUBDIVISIONS - i {
+            triangles.push(triangle([(i, j), (i + 1, j), (i, j + 1)]));
+            if i + j + 1 < TRIANGLE_SUBDIVISIONS {
+                triangles.push(triangle([(i + 1, j), (i + 1, j + 1), (i, j + 1)]));
+            }
+        }
+    }
+    triangles
+}
+
+/// The matrix of a bitmap fill that maps the given texture coordinates onto the corners
+/// of a triangle, or `None` if the texture coordinates don't span an area.
+fn bitmap_fill_matrix(points: [(f64, f64); 3], uv: [(f64, f64); 3]) -> Option<Matrix> {
+    let (du1, dv1) = (uv[1].0 - uv[0].0, uv[1].1 - uv[0].1);
+    let (du2, dv2) = (uv[2].0 - uv[0].0, uv[2].1 - uv[0].1);
+    let (dx1, dy1) = (points[1].0 - points[0].0, points[1].1 - points[0].1);
+    let (dx2, dy2) = (points[2].0 - points[0].0, points[2].1 - points[0].1);
+    let det = du1 * dv2 - du2 * dv1;
+    if det == 0.0 || !det.is_finite() {
+        return None;
+    }
+
+    let a = (dx1 * dv2 - dx2 * dv1) / det;
+    let b = (dy1 * dv2 - dy2 * dv1) / det;
+    let c = (dx2 * du1 - dx1 * du2) / det;
+    let d = (dy2 * du1 - dy1 * du2) / det;
+    Some(Matrix {
+        a: Fixed16::from_f64(a),
+        b: Fixed16::from_f64(b),
+        c: Fixed16::from_f64(c),
+        d: Fixed16::from_f64(d),
+        tx: Twips::from_pixels(points[0].0 - a * uv[0].0 - c * uv[0].1),
+        ty: Twips::from_pixels(points[0].1 - b * uv[0].0 - d * uv[0].1),
+    })
+}
+
 fn stretch_bounds(
     bounds: &Rectangle<Twips>,
     command: &DrawCommand,
@@ -472,3 +678,62 @@ fn stretch_bounds(
             .encompass(x2 + radius, y2 + radius),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitmap_fill_matrix_maps_corners() {
+        let points = [(10.0, 20.0), (110.0, 30.0), (40.0, 120.0)];
+        let uv = [(0.0, 0.0), (1.0, 0.0), (0.5, 1.0)];
+        let matrix = bitmap_fill_matrix(points, uv).unwrap();
+        for (point, (u, v)) in points.iter().zip(uv) {
+            let x = matrix.a.to_f64() * u + matrix.c.to_f64() * v + matrix.tx.to_pixels();
+            let y = matrix.b.to_f64() * u + matrix.d.to_f64() * v + matrix.ty.to_pixels();
+            assert!((x - point.0).abs() < 0.01 && (y - point.1).abs() < 0.01);
+        }
+
+        assert!(bitmap_fill_matrix(points, [(0.0, 0.0), (1.0, 1.0), (0.5, 0.5)]).is_none());
+    }
+
+    #[test]
+    fn subdivide_perspective_triangle() {
+        let points = [(0.0, 0.0), (100.0, 0.0), (0.0, 100.0)];
+        let affine =
+            subdivide_triangle(points, [(0.0, 0.0, 1.0), (1.0, 0.0, 1.0), (0.0, 1.0, 1.0)]);
+        assert_eq!(affine, vec![(points, [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)])]);
+
+        let perspective =
+            subdivide_triangle(points, [(0.0, 0.0, 1.0), (1.0, 0.0, 0.5), (0.0, 1.0, 1.0)]);
+        assert_eq!(
+            perspective.len(),
+            TRIANGLE_SUBDIVISIONS * TRIANGLE_SUBDIVISIONS
+        );
+        // Halfway along the receding edge on screen is only a third of the way along the bitmap.
+        let (_, uv) = perspective
+            .iter()
+            .find(|(points, _)| points[0] == (50.0, 0.0))
+            .unwrap();
+        assert!((uv[0].0 - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn winding_rule_only_applies_to_current_fill() {
+        let mut drawing = Drawing::new();
+        drawing.set_fill_style(Some(FillStyle::Color(swf::Color::BLACK)));
+        drawing.set_winding_rule(FillRule::NonZero);
+        drawing.set_fill_style(Some(FillStyle::Color(swf::Color::BLACK)));
+        drawing.set_fill_style(None);
+
+        let winding_rules: Vec<FillRule> = drawing
+            .paths
+            .iter()
+            .filter_map(|path| match path {
+                DrawingPath::Fill(fill) => Some(fill.winding_rule),
+                DrawingPath::Line(_) => None,
+            })
+            .collect();
+        assert_eq!(winding_rules, vec![FillRule::NonZero, FillRule::EvenOdd]);
+    }
+}
@@ -36,8 +36,7 @@ pub struct EvalParameters {
 
 impl EvalParameters {
     /// Construct eval parameters from their individual parts.
-    #[allow(dead_code)]
-    fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
+    pub fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
         Self {
             height,
             letter_spacing,
@@ -326,6 +325,26 @@ impl<'gc> Font<'gc> {
         (width, height)
     }
 
+    /// Measure the advance of every character of `text`, in order.
+    ///
+    /// Unlike `evaluate`, this yields an advance for characters without a
+    /// glyph, which take up no space.
+    pub fn char_advances(&self, text: &WStr, params: EvalParameters) -> Vec<Twips> {
+        let char_positions: Vec<usize> = text.char_indices().map(|(pos, _)| pos).collect();
+        let mut advances = vec![Twips::ZERO; char_positions.len()];
+        self.evaluate(
+            text,
+            Default::default(),
+            params,
+            |pos, _transform, _glyph, advance, _x| {
+                if let Ok(i) = char_positions.binary_search(&pos) {
+                    advances[i] = advance;
+                }
+            },
+        );
+        advances
+    }
+
     /// Given a line of text, find the first breakpoint within the text.
     ///
     /// This function assumes only `" "` is valid whitespace to split words on,
@@ -418,6 +437,71 @@ impl<'gc> Font<'gc> {
     }
 }
 
+/// A single unit of text being laid out by `flash.text.engine`, such as a
+/// character or a graphic.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineAtom {
+    /// The horizontal space that the atom takes up.
+    pub advance: Twips,
+
+    /// Whether the atom is whitespace, which is allowed to hang past the end
+    /// of a line.
+    pub is_whitespace: bool,
+
+    /// Whether a line may be broken after this atom.
+    pub can_break_after: bool,
+
+    /// Whether a line must be broken after this atom.
+    pub forces_break: bool,
+}
+
+/// The result of fitting atoms onto a line with `fit_line`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineFit {
+    /// The line ends after this many atoms, at a break opportunity or after
+    /// all of the atoms.
+    Break(usize),
+
+    /// No break opportunity fit on the line, so it was broken in the middle
+    /// of a word after this many atoms.
+    Emergency(usize),
+
+    /// Not even a single atom fit on the line.
+    InsufficientWidth,
+}
+
+/// Find how many atoms of a `flash.text.engine` text block fit onto a line of
+/// the given `width`.
+///
+/// Lines are broken after the last break opportunity that fits. When
+/// `fit_something` is set, a line always holds at least one atom, even if it
+/// is wider than the line.
+pub fn fit_line(atoms: &[LineAtom], width: Twips, fit_something: bool) -> LineFit {
+    let mut x = Twips::ZERO;
+    let mut last_break = None;
+
+    for (i, atom) in atoms.iter().enumerate() {
+        if !atom.is_whitespace && x + atom.advance > width {
+            return match (last_break, i) {
+                (Some(end), _) => LineFit::Break(end),
+                (None, 0) if fit_something => LineFit::Emergency(1),
+                (None, 0) => LineFit::InsufficientWidth,
+                (None, i) => LineFit::Emergency(i),
+            };
+        }
+
+        x += atom.advance;
+        if atom.forces_break {
+            return LineFit::Break(i + 1);
+        }
+        if atom.can_break_after {
+            last_break = Some(i + 1);
+        }
+    }
+
+    LineFit::Break(atoms.len())
+}
+
 #[derive(Debug, Clone)]
 pub struct Glyph {
     // Handle to registered shape.
@@ -687,7 +771,7 @@ impl Default for TextRenderSettings {
 
 #[cfg(test)]
 mod tests {
-    use crate::font::{EvalParameters, Font};
+    use crate::font::{fit_line, EvalParameters, Font, LineAtom, LineFit};
     use crate::player::Player;
     use crate::string::WStr;
     use gc_arena::{rootless_arena, MutationContext};
@@ -867,4 +951,78 @@ mod tests {
             assert_eq!(None, breakpoint5);
         });
     }
+
+    #[test]
+    fn char_advances_match_measure() {
+        with_device_font(|_mc, df| {
+            let params =
+                EvalParameters::from_parts(Twips::from_pixels(12.0), Twips::from_pixels(0.0), true);
+            let string = WStr::from_units(b"abcd efgh");
+            let advances = df.char_advances(string, params);
+
+            assert_eq!(string.len(), advances.len());
+            let total = advances.iter().fold(Twips::ZERO, |total, a| total + *a);
+            assert_eq!(df.measure(string, params, false).0, total);
+        });
+    }
+
+    fn words(text: &str) -> Vec<LineAtom> {
+        text.chars()
+            .map(|c| LineAtom {
+                advance: Twips::from_pixels(10.0),
+                is_whitespace: c == ' ',
+                can_break_after: c == ' ',
+                forces_break: c == '\u{2028}',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fit_line_breaks_after_whitespace() {
+        let atoms = words("abc def ghi");
+        assert_eq!(
+            LineFit::Break(8),
+            fit_line(&atoms, Twips::from_pixels(95.0), false)
+        );
+        // Trailing whitespace may hang past the end of the line.
+        assert_eq!(
+            LineFit::Break(4),
+            fit_line(&atoms, Twips::from_pixels(30.0), false)
+        );
+        assert_eq!(
+            LineFit::Break(11),
+            fit_line(&atoms, Twips::from_pixels(110.0), false)
+        );
+    }
+
+    #[test]
+    fn fit_line_forced_break() {
+        let atoms = words("ab\u{2028}cd");
+        assert_eq!(
+            LineFit::Break(3),
+            fit_line(&atoms, Twips::from_pixels(100.0), false)
+        );
+    }
+
+    #[test]
+    fn fit_line_emergency_break() {
+        let atoms = words("abcdef gh");
+        assert_eq!(
+            LineFit::Emergency(3),
+            fit_line(&atoms, Twips::from_pixels(35.0), false)
+        );
+    }
+
+    #[test]
+    fn fit_line_insufficient_width() {
+        let atoms = words("abc");
+        assert_eq!(
+            LineFit::InsufficientWidth,
+            fit_line(&atoms, Twips::from_pixels(5.0), false)
+        );
+        assert_eq!(
+            LineFit::Emergency(1),
+            fit_line(&atoms, Twips::from_pixels(5.0), true)
+        );
+    }
 }
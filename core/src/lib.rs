@@ -19,6 +19,7 @@ mod avm1;
 mod avm2;
 mod binary_data;
 pub mod bitmap;
+mod camera;
 mod character;
 pub mod context;
 pub mod context_menu;
@@ -33,9 +34,15 @@ mod html;
 mod library;
 pub mod limits;
 pub mod loader;
+mod local_connection;
 mod locale;
+mod microphone;
+mod net_connection;
 mod player;
 mod prelude;
+mod remote_shared_object;
+mod security;
+mod socket;
 mod streams;
 pub mod string;
 pub mod tag_utils;
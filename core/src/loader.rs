@@ -5,15 +5,18 @@ use crate::avm1::ExecutionReason;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{Object, SoundObject, TObject, Value};
 use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::ArrayObject;
 use crate::avm2::object::ByteArrayObject;
 use crate::avm2::object::EventObject as Avm2EventObject;
 use crate::avm2::object::LoaderStream;
 use crate::avm2::object::TObject as _;
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Object as Avm2Object,
-    Value as Avm2Value,
+    Activation as Avm2Activation, ArrayStorage, Avm2, Domain as Avm2Domain, Error as Avm2Error,
+    Multiname, Namespace, Object as Avm2Object, Value as Avm2Value,
 };
+use crate::backend::audio::id3;
 use crate::backend::navigator::{OwnedFuture, Request};
+use crate::backend::ui::{FileDialogFuture, FileDialogResult};
 use crate::context::{ActionQueue, ActionType, UpdateContext};
 use crate::display_object::{
     Bitmap, DisplayObject, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
@@ -31,6 +34,7 @@ use gc_arena::{Collect, CollectionContext};
 use generational_arena::{Arena, Index};
 use ruffle_render::utils::{determine_jpeg_tag_format, JpegTagFormat};
 use std::fmt;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use swf::read::{extract_swz, read_compression_type};
@@ -152,6 +156,9 @@ pub enum Error {
     #[error("Non-NetStream loader spawned as NetStream loader")]
     NotNetStreamLoader,
 
+    #[error("Non-FileReference loader spawned as FileReference loader")]
+    NotFileReferenceLoader,
+
     #[error("Could not fetch: {0}")]
     FetchError(String),
 
@@ -216,11 +223,17 @@ impl<'gc> LoadManager<'gc> {
             | Loader::LoadURLLoader { self_handle, .. }
             | Loader::SoundAvm1 { self_handle, .. }
             | Loader::SoundAvm2 { self_handle, .. }
-            | Loader::NetStream { self_handle, .. } => *self_handle = Some(handle),
+            | Loader::NetStream { self_handle, .. }
+            | Loader::FileReference { self_handle, .. } => *self_handle = Some(handle),
         }
         handle
     }
 
+    /// Remove a loader, which cancels its load.
+    pub fn remove_loader(&mut self, handle: Handle) {
+        self.0.remove(handle);
+    }
+
     /// Retrieve a loader by handle.
     pub fn get_loader(&self, handle: Handle) -> Option<&Loader<'gc>> {
         self.0.get(handle)
@@ -424,6 +437,62 @@ impl<'gc> LoadManager<'gc> {
         loader.stream_loader(player, request)
     }
 
+    /// Find the loader that is loading an MP3 into the given AVM2 `Sound`.
+    pub fn sound_avm2_loader(&self, target_object: Avm2Object<'gc>) -> Option<Handle> {
+        self.0.iter().find_map(|(handle, loader)| match loader {
+            Loader::SoundAvm2 {
+                target_object: target,
+                ..
+            } if Avm2Object::ptr_eq(*target, target_object) => Some(handle),
+            _ => None,
+        })
+    }
+
+    /// Find the loader that is showing a dialog for, or transferring the file
+    /// of, the given `FileReference` or `FileReferenceList`.
+    pub fn file_reference_loader(&self, target_object: Avm2Object<'gc>) -> Option<Handle> {
+        self.0.iter().find_map(|(handle, loader)| match loader {
+            Loader::FileReference {
+                target_object: target,
+                ..
+            } if Avm2Object::ptr_eq(*target, target_object) => Some(handle),
+            _ => None,
+        })
+    }
+
+    /// Start showing a dialog for, or transferring the file of, a
+    /// `FileReference` or `FileReferenceList`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn file_reference_operation(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        operation: FileReferenceOperation,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::FileReference {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        match operation {
+            FileReferenceOperation::Browse(dialog) => loader.file_dialog_loader(player, dialog),
+            FileReferenceOperation::Load => loader.file_load_loader(player),
+            FileReferenceOperation::Save(dialog, data) => {
+                loader.file_save_loader(player, dialog, data)
+            }
+            FileReferenceOperation::Download(dialog, request) => {
+                loader.file_download_loader(player, dialog, request)
+            }
+            FileReferenceOperation::Upload {
+                request,
+                test_request,
+                size,
+            } => loader.file_upload_loader(player, request, test_request, size),
+        }
+    }
+
     /// Process tags on all loaders in the Parsing phase.
     ///
     /// Returns true if *all* loaders finished preloading.
@@ -476,6 +545,34 @@ pub enum MovieLoaderEventHandler<'gc> {
     Avm2LoaderInfo(Avm2Object<'gc>),
 }
 
+/// What a `FileReference` or `FileReferenceList` asks its loader to do.
+pub enum FileReferenceOperation {
+    /// Wait for the user to choose files in the given file open dialog.
+    Browse(FileDialogFuture),
+
+    /// Read the chosen file into the `data` property.
+    Load,
+
+    /// Save data to the file that the user chooses in the given file save
+    /// dialog.
+    Save(FileDialogFuture, Vec<u8>),
+
+    /// Download a URL to the file that the user chooses in the given file
+    /// save dialog.
+    Download(FileDialogFuture, Request),
+
+    /// Send a request that uploads the chosen file, which is `size` bytes
+    /// long.
+    ///
+    /// If there is a `test_request`, it is sent first, and the upload only
+    /// goes ahead if it succeeds.
+    Upload {
+        request: Request,
+        test_request: Option<Request>,
+        size: usize,
+    },
+}
+
 #[derive(Collect, Clone, Copy)]
 #[collect(no_drop)]
 pub struct Avm2LoaderData<'gc> {
@@ -578,7 +675,7 @@ pub enum Loader<'gc> {
         #[collect(require_static)]
         self_handle: Option<Handle>,
 
-        /// The target AVM1 object to load the audio into.
+        /// The target AVM2 object to load the audio into.
         target_object: Avm2Object<'gc>,
     },
 
@@ -591,6 +688,17 @@ pub enum Loader<'gc> {
         /// The stream to buffer data into.
         target_stream: NetStream<'gc>,
     },
+
+    /// Loader that is showing a file dialog for, or transferring the file of,
+    /// a `FileReference` or `FileReferenceList`.
+    FileReference {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The `FileReference` or `FileReferenceList` to dispatch events to.
+        target_object: Avm2Object<'gc>,
+    },
 }
 
 impl<'gc> Loader<'gc> {
@@ -1248,7 +1356,7 @@ impl<'gc> Loader<'gc> {
         })
     }
 
-    /// Creates a future for a LoadURLLoader load call.
+    /// Creates a future for a `Sound.load` call.
     fn sound_loader_avm2(
         &mut self,
         player: Weak<Mutex<Player>>,
@@ -1258,7 +1366,7 @@ impl<'gc> Loader<'gc> {
             Loader::SoundAvm2 { self_handle, .. } => {
                 self_handle.expect("Loader not self-introduced")
             }
-            _ => return Box::pin(async { Err(Error::NotLoadDataLoader) }),
+            _ => return Box::pin(async { Err(Error::NotSoundLoader) }),
         };
 
         let player = player
@@ -1266,93 +1374,191 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
-            let response = fetch.await;
-
             player.lock().unwrap().update(|uc| {
-                let loader = uc.load_manager.get_loader(handle);
-                let sound_object = match loader {
-                    Some(&Loader::SoundAvm2 { target_object, .. }) => target_object,
-                    None => return Err(Error::Cancelled),
-                    _ => return Err(Error::NotSoundLoader),
-                };
+                let sound_object = Loader::sound_avm2_target(uc, handle)?;
+                Loader::dispatch_avm2_event(uc, sound_object, "open");
+                Ok(())
+            })?;
 
+            let fetch = player.lock().unwrap().navigator().fetch_stream(request);
+            let response = fetch.await;
+
+            let mut body = player.lock().unwrap().update(|uc| {
+                let sound_object = Loader::sound_avm2_target(uc, handle)?;
                 match response {
                     Ok(response) => {
-                        let handle = uc.audio.register_mp3(&response.body)?;
-                        if let Err(e) = sound_object
+                        sound_object
                             .as_sound_object()
                             .expect("Not a sound object")
-                            .set_sound(uc, handle)
-                        {
-                            tracing::error!("Encountered AVM2 error when setting sound: {}", e);
-                        }
+                            .set_bytes_total(uc.gc_context, response.expected_length);
+                        Ok(Some(response.body))
+                    }
+                    Err(_) => {
+                        Loader::finish_sound_avm2_load(uc, handle, sound_object);
+                        Loader::dispatch_avm2_io_error(uc, sound_object)?;
+                        Ok(None)
+                    }
+                }
+            })?;
+            let Some(body) = &mut body else {
+                return Ok(());
+            };
 
-                        // FIXME - the "open" event should be fired earlier, and not fired in case of ioerror.
-                        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
-                        let open_evt =
-                            Avm2EventObject::bare_default_event(&mut activation.context, "open");
-                        if let Err(e) =
-                            Avm2::dispatch_event(&mut activation.context, open_evt, sound_object)
-                        {
-                            tracing::error!(
-                                "Encountered AVM2 error when broadcasting `open` event: {}",
-                                e
-                            );
+            loop {
+                let chunk = body.next_chunk().await;
+                let is_complete = player.lock().unwrap().update(|uc| {
+                    let sound_object = Loader::sound_avm2_target(uc, handle)?;
+                    let sound = sound_object.as_sound_object().expect("Not a sound object");
+                    match chunk {
+                        Ok(Some(chunk)) => {
+                            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                            if let Err(e) = sound.append_load_data(&chunk, &mut activation) {
+                                tracing::error!(
+                                    "Encountered AVM2 error when streaming sound: {}",
+                                    e
+                                );
+                            }
+                            let bytes_loaded = sound.bytes_loaded().unwrap_or_default();
+                            let bytes_total = sound.bytes_total().unwrap_or(bytes_loaded);
+                            Loader::dispatch_avm2_progress(
+                                uc,
+                                sound_object,
+                                bytes_loaded,
+                                bytes_total,
+                            )?;
+                            Ok(false)
                         }
-
-                        let complete_evt = Avm2EventObject::bare_default_event(
-                            &mut activation.context,
-                            "complete",
-                        );
-                        if let Err(e) = Avm2::dispatch_event(uc, complete_evt, sound_object) {
-                            tracing::error!(
-                                "Encountered AVM2 error when broadcasting `complete` event: {}",
-                                e
-                            );
+                        Ok(None) => {
+                            Loader::sound_avm2_loaded(uc, handle, sound_object)?;
+                            Ok(true)
                         }
-                    }
-                    Err(_err) => {
-                        // FIXME: Match the exact error message generated by Flash.
-                        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
-                        let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
-                        let io_error_evt = io_error_evt_cls
-                            .construct(
-                                &mut activation,
-                                &[
-                                    "ioError".into(),
-                                    false.into(),
-                                    false.into(),
-                                    "Error #2032: Stream Error".into(),
-                                    2032.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
-
-                        if let Err(e) = Avm2::dispatch_event(uc, io_error_evt, sound_object) {
-                            tracing::error!(
-                                "Encountered AVM2 error when broadcasting `ioError` event: {}",
-                                e
-                            );
+                        Err(_) => {
+                            Loader::finish_sound_avm2_load(uc, handle, sound_object);
+                            Loader::dispatch_avm2_io_error(uc, sound_object)?;
+                            Ok(true)
                         }
                     }
+                })?;
+                if is_complete {
+                    return Ok(());
                 }
-
-                Ok(())
-            })
+            }
         })
     }
 
+    /// Returns the `Sound` that a `Sound.load` loader is loading into.
+    fn sound_avm2_target(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+    ) -> Result<Avm2Object<'gc>, Error> {
+        match uc.load_manager.get_loader(handle) {
+            Some(&Loader::SoundAvm2 { target_object, .. }) => Ok(target_object),
+            None => Err(Error::Cancelled),
+            _ => Err(Error::NotSoundLoader),
+        }
+    }
+
+    /// Stops a `Sound.load` loader, returning all of the data it received.
+    fn finish_sound_avm2_load(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+        sound_object: Avm2Object<'gc>,
+    ) -> Vec<u8> {
+        uc.load_manager.remove_loader(handle);
+        sound_object
+            .as_sound_object()
+            .expect("Not a sound object")
+            .finish_load(uc.gc_context)
+            .unwrap_or_default()
+    }
+
+    /// Registers the MP3 file downloaded by `Sound.load` once all of it has
+    /// been received.
+    fn sound_avm2_loaded(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+        sound_object: Avm2Object<'gc>,
+    ) -> Result<(), Error> {
+        let data = Loader::finish_sound_avm2_load(uc, handle, sound_object);
+
+        let frames = id3::read_id3_frames(&data);
+        if !frames.is_empty() {
+            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+            let id3 = Loader::id3_info(&mut activation, frames)
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            sound_object
+                .as_sound_object()
+                .expect("Not a sound object")
+                .set_id3(uc.gc_context, id3);
+            Loader::dispatch_avm2_event(uc, sound_object, "id3");
+        }
+
+        let sound = match uc.audio.register_mp3(&data) {
+            Ok(sound) => sound,
+            Err(e) => {
+                tracing::error!("Could not decode MP3 loaded by Sound.load: {}", e);
+                return Loader::dispatch_avm2_io_error(uc, sound_object);
+            }
+        };
+        if let Err(e) = sound_object
+            .as_sound_object()
+            .expect("Not a sound object")
+            .set_sound(uc, sound)
+        {
+            tracing::error!("Encountered AVM2 error when setting sound: {}", e);
+        }
+
+        Loader::dispatch_avm2_event(uc, sound_object, "complete");
+
+        Ok(())
+    }
+
+    /// Builds the `ID3Info` object for the ID3 text frames of a loaded sound.
+    ///
+    /// Every frame is available under its frame identifier, and the common
+    /// ones are also available under their `ID3Info` property names.
+    fn id3_info(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        frames: Vec<id3::Id3Frame>,
+    ) -> Result<Avm2Object<'gc>, Avm2Error<'gc>> {
+        let mut id3 = activation
+            .avm2()
+            .classes()
+            .id3info
+            .construct(activation, &[])?;
+
+        for frame in frames {
+            let property = match frame.id.as_str() {
+                "TALB" => Some("album"),
+                "TPE1" => Some("artist"),
+                "COMM" => Some("comment"),
+                "TCON" => Some("genre"),
+                "TIT2" => Some("songName"),
+                "TRCK" => Some("track"),
+                "TYER" | "TDRC" => Some("year"),
+                _ => None,
+            };
+            let text = AvmString::new_utf8(activation.context.gc_context, frame.text);
+            if let Some(property) = property {
+                id3.set_public_property(property, text.into(), activation)?;
+            }
+            let id = AvmString::new_utf8(activation.context.gc_context, frame.id);
+            id3.set_public_property(id, text.into(), activation)?;
+        }
+
+        Ok(id3)
+    }
+
     fn stream_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
-            Loader::SoundAvm2 { self_handle, .. } => {
+            Loader::NetStream { self_handle, .. } => {
                 self_handle.expect("Loader not self-introduced")
             }
-            _ => return Box::pin(async { Err(Error::NotLoadDataLoader) }),
+            _ => return Box::pin(async { Err(Error::NotNetStreamLoader) }),
         };
 
         let player = player
@@ -1373,10 +1579,10 @@ impl<'gc> Loader<'gc> {
 
                 match response {
                     Ok(mut response) => {
-                        stream.load_buffer(uc.gc_context, &mut response.body);
+                        stream.load_buffer(uc.gc_context, &mut response.body, true);
                     }
                     Err(err) => {
-                        stream.report_error(err);
+                        stream.report_error(uc, err);
                     }
                 }
 
@@ -1385,6 +1591,412 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future that waits for the user to choose files for a
+    /// `FileReference` or `FileReferenceList`.
+    fn file_dialog_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: FileDialogFuture,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let files = dialog.await;
+
+            player.lock().unwrap().update(|uc| {
+                let mut target = Loader::file_reference_target(uc, handle)?;
+                uc.load_manager.remove_loader(handle);
+
+                if files.is_empty() {
+                    Loader::dispatch_avm2_event(uc, target, "cancel");
+                    return Ok(());
+                }
+
+                if let Some(file_reference) = target.as_file_reference() {
+                    let file = files.into_iter().next().map(Rc::from);
+                    file_reference.set_file(uc.gc_context, file);
+                } else {
+                    // This is a `FileReferenceList`, which gets a new
+                    // `FileReference` for each file.
+                    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                    let mut file_list = Vec::with_capacity(files.len());
+                    for file in files {
+                        let file_reference = activation
+                            .avm2()
+                            .classes()
+                            .filereference
+                            .construct(&mut activation, &[])
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                        if let Some(file_reference) = file_reference.as_file_reference() {
+                            file_reference
+                                .set_file(activation.context.gc_context, Some(Rc::from(file)));
+                        }
+                        file_list.push(Some(file_reference.into()));
+                    }
+
+                    let file_list = ArrayObject::from_storage(
+                        &mut activation,
+                        ArrayStorage::from_storage(file_list),
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    let name = Multiname::new(
+                        Namespace::package("__ruffle__", activation.context.gc_context),
+                        "_fileList",
+                    );
+                    target
+                        .set_property(&name, file_list.into(), &mut activation)
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                }
+
+                Loader::dispatch_avm2_event(uc, target, "select");
+                Ok(())
+            })
+        })
+    }
+
+    /// Creates a future that reads the file chosen for a `FileReference`.
+    fn file_load_loader(&mut self, player: Weak<Mutex<Player>>) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                uc.load_manager.remove_loader(handle);
+                let file_reference = target
+                    .as_file_reference()
+                    .ok_or(Error::NotFileReferenceLoader)?;
+
+                Loader::dispatch_avm2_event(uc, target, "open");
+
+                let Some(contents) = file_reference.file().and_then(|file| file.contents()) else {
+                    return Loader::dispatch_avm2_io_error(uc, target);
+                };
+                let length = contents.len();
+
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                let data = ByteArrayObject::from_storage(
+                    &mut activation,
+                    ByteArrayStorage::from_vec(contents),
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                file_reference.set_data(activation.context.gc_context, Some(data));
+
+                Loader::dispatch_avm2_progress(uc, target, length, length)?;
+                Loader::dispatch_avm2_event(uc, target, "complete");
+                Ok(())
+            })
+        })
+    }
+
+    /// Creates a future that saves data to the file that the user chooses
+    /// for a `FileReference`.
+    fn file_save_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: FileDialogFuture,
+        data: Vec<u8>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let files = dialog.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                uc.load_manager.remove_loader(handle);
+                let file_reference = target
+                    .as_file_reference()
+                    .ok_or(Error::NotFileReferenceLoader)?;
+
+                let Some(file) = files.into_iter().next() else {
+                    Loader::dispatch_avm2_event(uc, target, "cancel");
+                    return Ok(());
+                };
+                let file: Rc<dyn FileDialogResult> = Rc::from(file);
+                file_reference.set_file(uc.gc_context, Some(file.clone()));
+                Loader::dispatch_avm2_event(uc, target, "select");
+                Loader::dispatch_avm2_event(uc, target, "open");
+
+                if !file.write(&data) {
+                    return Loader::dispatch_avm2_io_error(uc, target);
+                }
+
+                Loader::dispatch_avm2_progress(uc, target, data.len(), data.len())?;
+                Loader::dispatch_avm2_event(uc, target, "complete");
+                Ok(())
+            })
+        })
+    }
+
+    /// Creates a future that downloads a URL to the file that the user
+    /// chooses for a `FileReference`.
+    fn file_download_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: FileDialogFuture,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let files = dialog.await;
+
+            let file = player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                let file_reference = target
+                    .as_file_reference()
+                    .ok_or(Error::NotFileReferenceLoader)?;
+
+                let Some(file) = files.into_iter().next() else {
+                    uc.load_manager.remove_loader(handle);
+                    Loader::dispatch_avm2_event(uc, target, "cancel");
+                    return Ok(None);
+                };
+                let file: Rc<dyn FileDialogResult> = Rc::from(file);
+                file_reference.set_file(uc.gc_context, Some(file.clone()));
+                Loader::dispatch_avm2_event(uc, target, "select");
+                Loader::dispatch_avm2_event(uc, target, "open");
+                Ok(Some(file))
+            })?;
+            let Some(file) = file else {
+                return Ok(());
+            };
+
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = fetch.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                uc.load_manager.remove_loader(handle);
+
+                let body = match response {
+                    Ok(response) => response.body,
+                    Err(_) => return Loader::dispatch_avm2_io_error(uc, target),
+                };
+                if !file.write(&body) {
+                    return Loader::dispatch_avm2_io_error(uc, target);
+                }
+
+                Loader::dispatch_avm2_progress(uc, target, body.len(), body.len())?;
+                Loader::dispatch_avm2_event(uc, target, "complete");
+                Ok(())
+            })
+        })
+    }
+
+    /// Creates a future that sends an upload request for the file of a
+    /// `FileReference`, which is `size` bytes long, after an optional test
+    /// request.
+    fn file_upload_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+        test_request: Option<Request>,
+        size: usize,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            if let Some(test_request) = test_request {
+                let fetch = player.lock().unwrap().navigator().fetch(test_request);
+                if fetch.await.is_err() {
+                    return player.lock().unwrap().update(|uc| {
+                        let target = Loader::file_reference_target(uc, handle)?;
+                        uc.load_manager.remove_loader(handle);
+                        Loader::dispatch_avm2_io_error(uc, target)
+                    });
+                }
+            }
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                Loader::dispatch_avm2_event(uc, target, "open");
+                Ok(())
+            })?;
+
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = fetch.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                uc.load_manager.remove_loader(handle);
+
+                let response = match response {
+                    Ok(response) => response,
+                    Err(_) => return Loader::dispatch_avm2_io_error(uc, target),
+                };
+
+                Loader::dispatch_avm2_progress(uc, target, size, size)?;
+                Loader::dispatch_avm2_event(uc, target, "complete");
+
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                let data = AvmString::new_utf8_bytes(activation.context.gc_context, &response.body);
+                let upload_complete_evt = activation
+                    .avm2()
+                    .classes()
+                    .dataevent
+                    .construct(
+                        &mut activation,
+                        &[
+                            "uploadCompleteData".into(),
+                            false.into(),
+                            false.into(),
+                            data.into(),
+                        ],
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                if let Err(e) = Avm2::dispatch_event(uc, upload_complete_evt, target) {
+                    tracing::error!(
+                        "Encountered AVM2 error when broadcasting `uploadCompleteData` event: {}",
+                        e
+                    );
+                }
+
+                Ok(())
+            })
+        })
+    }
+
+    /// Find the object that a `FileReference` loader dispatches events to.
+    ///
+    /// The loader is gone if its transfer was cancelled.
+    fn file_reference_target(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+    ) -> Result<Avm2Object<'gc>, Error> {
+        match uc.load_manager.get_loader(handle) {
+            Some(&Loader::FileReference { target_object, .. }) => Ok(target_object),
+            None => Err(Error::Cancelled),
+            _ => Err(Error::NotFileReferenceLoader),
+        }
+    }
+
+    fn dispatch_avm2_event(
+        uc: &mut UpdateContext<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        event_type: &'static str,
+    ) {
+        let event = Avm2EventObject::bare_default_event(uc, event_type);
+        if let Err(e) = Avm2::dispatch_event(uc, event, target) {
+            tracing::error!(
+                "Encountered AVM2 error when broadcasting `{}` event: {}",
+                event_type,
+                e
+            );
+        }
+    }
+
+    /// Report that `bytes_loaded` out of `bytes_total` bytes have been
+    /// transferred to an AVM2 object.
+    fn dispatch_avm2_progress(
+        uc: &mut UpdateContext<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        bytes_loaded: usize,
+        bytes_total: usize,
+    ) -> Result<(), Error> {
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+        let progress_evt = activation
+            .avm2()
+            .classes()
+            .progressevent
+            .construct(
+                &mut activation,
+                &[
+                    "progress".into(),
+                    false.into(),
+                    false.into(),
+                    bytes_loaded.into(),
+                    bytes_total.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        if let Err(e) = Avm2::dispatch_event(uc, progress_evt, target) {
+            tracing::error!(
+                "Encountered AVM2 error when broadcasting `progress` event: {}",
+                e
+            );
+        }
+
+        Ok(())
+    }
+
+    fn dispatch_avm2_io_error(
+        uc: &mut UpdateContext<'_, 'gc>,
+        target: Avm2Object<'gc>,
+    ) -> Result<(), Error> {
+        // FIXME: Match the exact error message generated by Flash.
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+        let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
+        let io_error_evt = io_error_evt_cls
+            .construct(
+                &mut activation,
+                &[
+                    "ioError".into(),
+                    false.into(),
+                    false.into(),
+                    "Error #2032: Stream Error".into(),
+                    2032.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        if let Err(e) = Avm2::dispatch_event(uc, io_error_evt, target) {
+            tracing::error!(
+                "Encountered AVM2 error when broadcasting `ioError` event: {}",
+                e
+            );
+        }
+
+        Ok(())
+    }
+
     /// Report a movie loader start event to script code.
     fn movie_loader_start(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
         let me = uc.load_manager.get_loader_mut(handle);
@@ -1558,7 +2170,7 @@ impl<'gc> Loader<'gc> {
                         Loader::movie_loader_progress(handle, uc, 0, length)?;
                     }
 
-                    let movie = Arc::new(SwfMovie::from_loaded_image(url, length));
+                    let movie = Arc::new(SwfMovie::from_loaded_image(url, loader_url, length));
 
                     let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data, None)?;
                     let bitmap_obj = Bitmap::new(uc, 0, bitmap)?;
@@ -0,0 +1,440 @@
+//! LocalConnection implementation
+
+use crate::avm1::globals::shared_object::deserialize_value as avm1_deserialize_value;
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    Attribute as Avm1Attribute, ExecutionReason as Avm1ExecutionReason,
+    ScriptObject as Avm1ScriptObject, TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::events::dispatch_status_event;
+use crate::avm2::{Activation as Avm2Activation, Value as Avm2Value};
+use crate::backend::local_connection::{LocalConnectionEndpoint, LocalConnectionMessage};
+use crate::context::UpdateContext;
+use crate::net_connection::call_avm2_client;
+use crate::string::AvmString;
+use crate::vminterface::AvmObject;
+use flash_lso::types::Value as AmfValue;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::fmt;
+use url::Url;
+
+/// Methods of `LocalConnection` itself, which may not be called by a message.
+const RESERVED_METHODS: &[&str] = &[
+    "send",
+    "connect",
+    "close",
+    "allowDomain",
+    "allowInsecureDomain",
+    "domain",
+];
+
+/// Manager for all `LocalConnection`s in this player.
+///
+/// Messages from other players arrive through the endpoint, and are
+/// delivered once per tick.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct LocalConnections<'gc> {
+    /// The connections that are listening for messages.
+    listening: Vec<LocalConnection<'gc>>,
+
+    /// Connections that sent a message, and whether anyone was listening
+    /// for it. They are told about it on the next tick.
+    pending_statuses: Vec<(LocalConnection<'gc>, bool)>,
+
+    /// This player's connection to the broker shared with other players.
+    #[collect(require_static)]
+    endpoint: LocalConnectionEndpoint,
+}
+
+impl<'gc> LocalConnections<'gc> {
+    pub fn new(endpoint: LocalConnectionEndpoint) -> Self {
+        Self {
+            listening: Vec::new(),
+            pending_statuses: Vec::new(),
+            endpoint,
+        }
+    }
+
+    /// Deliver status events and received messages.
+    pub fn tick(context: &mut UpdateContext<'_, 'gc>) {
+        let statuses = std::mem::take(&mut context.local_connections.pending_statuses);
+        for (connection, delivered) in statuses {
+            connection.trigger_status_event(context, delivered);
+        }
+
+        let messages = context.local_connections.endpoint.receive();
+        for message in messages {
+            let connection = context
+                .local_connections
+                .listening
+                .iter()
+                .find(|connection| {
+                    connection.0.read().name.as_deref() == Some(&message.connection_name)
+                })
+                .copied();
+            if let Some(connection) = connection {
+                connection.receive(context, message);
+            }
+        }
+    }
+}
+
+/// The domain of a movie, as seen by `LocalConnection`.
+///
+/// Local movies all share the `localhost` domain.
+pub fn movie_domain(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    if url.scheme() == "file" {
+        return Some("localhost".to_string());
+    }
+    Some(url.domain().unwrap_or("localhost").to_string())
+}
+
+/// Turn the name given to `connect` or `send` into the name that connections
+/// are known by across all players.
+///
+/// Names are case-insensitive, and those without a leading underscore are
+/// prefixed with the domain of the listening movie. When sending, a name that
+/// already includes a domain is left as it is.
+fn qualify_name(name: &str, domain: &str, sending: bool) -> String {
+    let name = name.to_ascii_lowercase();
+    if name.starts_with('_') || (sending && name.contains(':')) {
+        name
+    } else {
+        format!("{}:{name}", domain.to_ascii_lowercase())
+    }
+}
+
+/// Whether a message may call `method_name` on a connection.
+pub fn is_reserved_method(method_name: &str) -> bool {
+    RESERVED_METHODS.contains(&method_name)
+}
+
+/// One `LocalConnection` object of either VM.
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct LocalConnection<'gc>(GcCell<'gc, LocalConnectionData<'gc>>);
+
+impl<'gc> PartialEq for LocalConnection<'gc> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ptr() == other.0.as_ptr()
+    }
+}
+
+impl<'gc> Eq for LocalConnection<'gc> {}
+
+impl fmt::Debug for LocalConnection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalConnection")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct LocalConnectionData<'gc> {
+    /// The AVM side of this connection, which receives its messages and
+    /// events.
+    avm_object: Option<AvmObject<'gc>>,
+
+    /// The fully qualified name this connection is listening on, if any.
+    #[collect(require_static)]
+    name: Option<String>,
+
+    /// The domain of the movie that is listening.
+    #[collect(require_static)]
+    domain: String,
+
+    /// Domains that were given access with the AVM2 `allowDomain` method.
+    #[collect(require_static)]
+    allowed_domains: Vec<String>,
+}
+
+impl<'gc> LocalConnection<'gc> {
+    pub fn new(gc_context: MutationContext<'gc, '_>, avm_object: Option<AvmObject<'gc>>) -> Self {
+        Self(GcCell::allocate(
+            gc_context,
+            LocalConnectionData {
+                avm_object,
+                name: None,
+                domain: String::new(),
+                allowed_domains: Vec::new(),
+            },
+        ))
+    }
+
+    pub fn set_avm_object(self, gc_context: MutationContext<'gc, '_>, avm_object: AvmObject<'gc>) {
+        self.0.write(gc_context).avm_object = Some(avm_object);
+    }
+
+    /// Whether this connection is listening for messages.
+    pub fn is_connected(self) -> bool {
+        self.0.read().name.is_some()
+    }
+
+    /// Start listening for messages sent to `name` by any player.
+    ///
+    /// Returns `false` if this connection is already listening, or if the
+    /// name is in use.
+    pub fn connect(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        movie_url: &str,
+        name: &str,
+    ) -> bool {
+        if self.is_connected() {
+            return false;
+        }
+
+        let domain = movie_domain(movie_url).unwrap_or_else(|| "localhost".to_string());
+        let name = qualify_name(name, &domain, false);
+        if !context.local_connections.endpoint.connect(&name) {
+            return false;
+        }
+
+        let mut write = self.0.write(context.gc_context);
+        write.name = Some(name);
+        write.domain = domain;
+        drop(write);
+        context.local_connections.listening.push(self);
+        true
+    }
+
+    /// Stop listening for messages.
+    ///
+    /// Returns `false` if this connection was not listening.
+    pub fn close(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        let Some(name) = self.0.write(context.gc_context).name.take() else {
+            return false;
+        };
+
+        context.local_connections.endpoint.close(&name);
+        context.local_connections.listening.retain(|c| *c != self);
+        true
+    }
+
+    /// Send a message to the connection listening on `connection_name`.
+    ///
+    /// The outcome is reported to this connection's status handler on the
+    /// next tick.
+    pub fn send(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        movie_url: &str,
+        connection_name: &str,
+        method_name: String,
+        arguments: Vec<AmfValue>,
+    ) {
+        let sender_domain = movie_domain(movie_url).unwrap_or_else(|| "localhost".to_string());
+        let message = LocalConnectionMessage {
+            connection_name: qualify_name(connection_name, &sender_domain, true),
+            sender_domain,
+            method_name,
+            arguments,
+        };
+        let delivered = context.local_connections.endpoint.send(message);
+        context
+            .local_connections
+            .pending_statuses
+            .push((self, delivered));
+    }
+
+    /// Give movies from `domains` permission to send messages to this
+    /// connection.
+    pub fn allow_domains(self, gc_context: MutationContext<'gc, '_>, domains: &[String]) {
+        let mut write = self.0.write(gc_context);
+        for domain in domains {
+            let domain = domain.to_ascii_lowercase();
+            if !write.allowed_domains.contains(&domain) {
+                write.allowed_domains.push(domain);
+            }
+        }
+    }
+
+    /// Call the method named by a message on the AVM object, if the sender
+    /// is allowed to.
+    ///
+    /// In AVM2, the sender's domain must have been given to `allowDomain`,
+    /// and the method is called on the connection's `client`. In AVM1, the
+    /// connection's own `allowDomain` method decides, and the method is
+    /// called on the connection itself.
+    fn receive(self, context: &mut UpdateContext<'_, 'gc>, message: LocalConnectionMessage) {
+        let (avm_object, same_domain) = {
+            let read = self.0.read();
+            (
+                read.avm_object,
+                read.domain.eq_ignore_ascii_case(&message.sender_domain),
+            )
+        };
+
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let allowed = same_domain || {
+                    let read = self.0.read();
+                    let sender_domain = message.sender_domain.to_ascii_lowercase();
+                    read.allowed_domains
+                        .iter()
+                        .any(|domain| domain == "*" || *domain == sender_domain)
+                };
+                if !allowed {
+                    tracing::warn!(
+                        "LocalConnection: message from {} was not allowed",
+                        message.sender_domain
+                    );
+                    return;
+                }
+
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let name = AvmString::new_utf8(activation.context.gc_context, message.method_name);
+                let result = message
+                    .arguments
+                    .iter()
+                    .map(|argument| crate::avm2::amf::deserialize_value(&mut activation, argument))
+                    .collect::<Result<Vec<_>, _>>()
+                    .and_then(|arguments| {
+                        call_avm2_client(
+                            &mut activation,
+                            object,
+                            "flash.net.LocalConnection",
+                            name,
+                            &arguments,
+                        )
+                    });
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in LocalConnection {} handler: {}",
+                        name,
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[LocalConnection Call]"),
+                );
+                if !same_domain {
+                    let sender_domain =
+                        AvmString::new_utf8(activation.context.gc_context, message.sender_domain);
+                    let allowed = object
+                        .call_method(
+                            "allowDomain".into(),
+                            &[sender_domain.into()],
+                            &mut activation,
+                            Avm1ExecutionReason::Special,
+                        )
+                        .map_or(false, |allowed| allowed.as_bool(activation.swf_version()));
+                    if !allowed {
+                        tracing::warn!(
+                            "LocalConnection: message from {} was not allowed",
+                            sender_domain
+                        );
+                        return;
+                    }
+                }
+
+                let arguments: Vec<_> = message
+                    .arguments
+                    .iter()
+                    .map(|argument| avm1_deserialize_value(&mut activation, argument))
+                    .collect();
+                let name = AvmString::new_utf8(activation.context.gc_context, message.method_name);
+                if let Err(e) = object.call_method(
+                    name,
+                    &arguments,
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!(
+                        "Unhandled AVM1 error in LocalConnection {} handler: {}",
+                        name,
+                        e
+                    );
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Tell the AVM object whether a message it sent was received.
+    ///
+    /// AVM2 gets a `StatusEvent`, and AVM1 a call to `onStatus`, with a
+    /// level of either `status` or `error`.
+    fn trigger_status_event(self, context: &mut UpdateContext<'_, 'gc>, delivered: bool) {
+        let level = if delivered { "status" } else { "error" };
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let result = dispatch_status_event(&mut activation, object, Avm2Value::Null, level);
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in status event handler: {}",
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[LocalConnection onStatus]"),
+                );
+                let info = Avm1ScriptObject::new(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().object),
+                );
+                info.define_value(
+                    activation.context.gc_context,
+                    "level",
+                    level.into(),
+                    Avm1Attribute::empty(),
+                );
+                let info: Avm1Value<'gc> = info.into();
+                let _ = object.call_method(
+                    "onStatus".into(),
+                    &[info],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                );
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{movie_domain, qualify_name};
+
+    #[test]
+    fn qualify_names() {
+        assert_eq!(qualify_name("_Shared", "example.com", false), "_shared");
+        assert_eq!(
+            qualify_name("Game", "Example.com", false),
+            "example.com:game"
+        );
+        assert_eq!(
+            qualify_name("other.com:game", "example.com", true),
+            "other.com:game"
+        );
+        assert_eq!(
+            qualify_name("game", "example.com", true),
+            "example.com:game"
+        );
+    }
+
+    #[test]
+    fn movie_domains() {
+        assert_eq!(
+            movie_domain("http://www.example.com/movie.swf").as_deref(),
+            Some("www.example.com")
+        );
+        assert_eq!(
+            movie_domain("file:///movie.swf").as_deref(),
+            Some("localhost")
+        );
+        assert_eq!(movie_domain("not a url"), None);
+    }
+}
@@ -0,0 +1,351 @@
+//! Microphone capture
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::events::dispatch_status_event;
+use crate::avm2::object::{ByteArrayObject, MicrophoneObject, TObject};
+use crate::avm2::{Activation as Avm2Activation, Avm2, Error as Avm2Error, Object as Avm2Object};
+use crate::backend::audio::{SampleBuffer, SoundInstanceHandle};
+use crate::context::UpdateContext;
+use gc_arena::Collect;
+use std::time::Duration;
+
+/// The rates that a microphone can capture at, as the number of kHz that
+/// ActionScript uses and the actual sample rate in Hz.
+const RATES: [(u32, u32); 6] = [
+    (5, 5512),
+    (8, 8000),
+    (11, 11025),
+    (16, 16000),
+    (22, 22050),
+    (44, 44100),
+];
+
+/// Manager for the microphones that have been requested with
+/// `Microphone.getMicrophone`.
+///
+/// Captured audio is collected once per frame.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct Microphones<'gc> {
+    /// Every microphone that has been opened. There is at most one for each
+    /// microphone in the backend.
+    microphones: Vec<MicrophoneObject<'gc>>,
+
+    /// Microphones that were opened since the last frame. They are told
+    /// whether access to them was allowed on the next frame.
+    pending_statuses: Vec<MicrophoneObject<'gc>>,
+}
+
+impl<'gc> Microphones<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The object for a microphone that was already opened, if any.
+    pub fn get(&self, index: usize) -> Option<MicrophoneObject<'gc>> {
+        self.microphones
+            .iter()
+            .find(|microphone| microphone.microphone().index == index)
+            .copied()
+    }
+
+    /// Ask the backend for access to a newly created microphone, and start
+    /// capturing from it if that was allowed.
+    pub fn open(context: &mut UpdateContext<'_, 'gc>, microphone: MicrophoneObject<'gc>) {
+        let index = microphone.microphone().index;
+        let allowed = context.microphone.open(index);
+        microphone.microphone_mut(context.gc_context).muted = !allowed;
+        context.microphones.microphones.push(microphone);
+        context.microphones.pending_statuses.push(microphone);
+    }
+
+    /// Deliver status events, and the audio captured during the last frame.
+    pub fn update(context: &mut UpdateContext<'_, 'gc>) {
+        let statuses = std::mem::take(&mut context.microphones.pending_statuses);
+        for microphone in statuses {
+            if let Err(e) = Self::dispatch_status(context, microphone) {
+                tracing::error!("Unhandled AVM2 exception in status event handler: {}", e);
+            }
+        }
+
+        let elapsed = Duration::from_secs_f64(1.0 / context.frame_rate.max(1.0));
+        let microphones = context.microphones.microphones.clone();
+        for microphone in microphones {
+            if let Err(e) = Self::capture(context, microphone, elapsed) {
+                tracing::error!(
+                    "Unhandled AVM2 exception in microphone event handler: {}",
+                    e
+                );
+            }
+        }
+    }
+
+    /// Tell a microphone whether the user allowed access to it.
+    fn dispatch_status(
+        context: &mut UpdateContext<'_, 'gc>,
+        microphone: MicrophoneObject<'gc>,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let code = if microphone.microphone().muted {
+            "Microphone.Muted"
+        } else {
+            "Microphone.Unmuted"
+        };
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        dispatch_status_event(&mut activation, microphone.into(), code.into(), "status")
+    }
+
+    /// Take the audio that a microphone captured during the last frame, and
+    /// pass it on to `sampleData` event handlers and the loopback sound.
+    ///
+    /// Nothing is measured unless the audio is being used by one of those.
+    fn capture(
+        context: &mut UpdateContext<'_, 'gc>,
+        microphone: MicrophoneObject<'gc>,
+        elapsed: Duration,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let (index, sample_rate, gain) = {
+            let settings = microphone.microphone();
+            if settings.muted {
+                return Ok(());
+            }
+            (settings.index, settings.sample_rate(), settings.gain)
+        };
+
+        let mut samples = context.microphone.capture(index, sample_rate, elapsed);
+        let gain = (gain / 50.0) as f32;
+        for sample in &mut samples {
+            *sample = (*sample * gain).clamp(-1.0, 1.0);
+        }
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let object: Avm2Object<'gc> = microphone.into();
+        let has_sample_data = object
+            .call_public_property("hasEventListener", &["sampleData".into()], &mut activation)?
+            .coerce_to_boolean();
+        let has_loopback = microphone.microphone().has_loopback();
+        if !has_sample_data && !has_loopback {
+            microphone
+                .microphone_mut(activation.context.gc_context)
+                .activity_level = -1.0;
+            return Ok(());
+        }
+
+        let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let level = (f64::from(peak) * 100.0).round();
+        let activating = microphone
+            .microphone_mut(activation.context.gc_context)
+            .update_activity(level, elapsed);
+        if let Some(activating) = activating {
+            let event = activation.avm2().classes().activityevent.construct(
+                &mut activation,
+                &[
+                    "activity".into(),
+                    false.into(),
+                    false.into(),
+                    activating.into(),
+                ],
+            )?;
+            Avm2::dispatch_event(&mut activation.context, event, object)?;
+        }
+
+        if let Some(loopback) = &mut microphone
+            .microphone_mut(activation.context.gc_context)
+            .loopback
+        {
+            loopback.append(&samples, sample_rate);
+        }
+
+        if has_sample_data && !samples.is_empty() {
+            let position = {
+                let mut settings = microphone.microphone_mut(activation.context.gc_context);
+                let position = settings.position;
+                settings.position += samples.len() as u64;
+                position
+            };
+            let mut storage = ByteArrayStorage::new();
+            for sample in &samples {
+                storage.write_float(*sample)?;
+            }
+            let data = ByteArrayObject::from_storage(&mut activation, storage)?;
+            let event = activation.avm2().classes().sampledataevent.construct(
+                &mut activation,
+                &[
+                    "sampleData".into(),
+                    false.into(),
+                    false.into(),
+                    (position as f64).into(),
+                    data.into(),
+                ],
+            )?;
+            Avm2::dispatch_event(&mut activation.context, event, object)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The settings and capture state of one microphone.
+#[derive(Clone, Debug)]
+pub struct Microphone {
+    /// The index of the microphone in the backend.
+    pub index: usize,
+
+    pub name: String,
+
+    /// The amount to amplify captured audio by, from 0 to 100. At 50, audio is
+    /// left as it is.
+    pub gain: f64,
+
+    /// The capture rate in kHz, which is one of the rates in `RATES`.
+    pub rate: u32,
+
+    /// The activity level below which the microphone is considered silent,
+    /// from 0 to 100.
+    pub silence_level: f64,
+
+    /// How long in milliseconds the microphone has to be silent for before
+    /// it stops being active.
+    pub silence_timeout: i32,
+
+    /// The loudness of the last captured audio, from 0 to 100, or -1 if
+    /// nothing is using the captured audio.
+    pub activity_level: f64,
+
+    /// Whether access to the microphone was denied.
+    pub muted: bool,
+
+    /// Whether the microphone is currently hearing something above the
+    /// silence level.
+    is_active: bool,
+
+    /// How long the microphone has been below the silence level for.
+    silent_time: Duration,
+
+    /// The number of samples that have been delivered to `sampleData`
+    /// event handlers.
+    position: u64,
+
+    /// The sound playing the captured audio back, if loopback is enabled.
+    loopback: Option<Loopback>,
+}
+
+impl Default for Microphone {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            name: String::new(),
+            gain: 50.0,
+            rate: 8,
+            silence_level: 10.0,
+            silence_timeout: 2000,
+            activity_level: -1.0,
+            muted: true,
+            is_active: false,
+            silent_time: Duration::ZERO,
+            position: 0,
+            loopback: None,
+        }
+    }
+}
+
+impl Microphone {
+    /// Set the capture rate to whichever supported rate is closest to `rate`
+    /// kHz.
+    pub fn set_rate(&mut self, rate: i32) {
+        self.rate = RATES
+            .iter()
+            .map(|&(khz, _)| khz)
+            .min_by_key(|&khz| (i64::from(khz) - i64::from(rate)).abs())
+            .unwrap_or(8);
+    }
+
+    /// The capture rate in Hz.
+    pub fn sample_rate(&self) -> u32 {
+        RATES
+            .iter()
+            .find(|&&(khz, _)| khz == self.rate)
+            .map_or(8000, |&(_, hz)| hz)
+    }
+
+    pub fn has_loopback(&self) -> bool {
+        self.loopback.is_some()
+    }
+
+    /// Start playing captured audio back through the given sound, which plays
+    /// whatever is appended to `buffer`.
+    pub fn start_loopback(&mut self, buffer: SampleBuffer, instance: Option<SoundInstanceHandle>) {
+        self.loopback = Some(Loopback {
+            buffer,
+            instance,
+            phase: 0.0,
+        });
+    }
+
+    /// Stop playing captured audio back, returning the sound that was
+    /// playing it.
+    pub fn stop_loopback(&mut self) -> Option<SoundInstanceHandle> {
+        let loopback = self.loopback.take()?;
+        loopback.buffer.close();
+        loopback.instance
+    }
+
+    /// Track whether the microphone is hearing anything, given the activity
+    /// level of the latest audio.
+    ///
+    /// Returns the new state if the microphone has just started or stopped
+    /// being active.
+    fn update_activity(&mut self, level: f64, elapsed: Duration) -> Option<bool> {
+        self.activity_level = level;
+        if level >= self.silence_level {
+            self.silent_time = Duration::ZERO;
+            if !self.is_active {
+                self.is_active = true;
+                return Some(true);
+            }
+        } else if self.is_active {
+            self.silent_time += elapsed;
+            let timeout = Duration::from_millis(self.silence_timeout.max(0) as u64);
+            if self.silent_time >= timeout {
+                self.is_active = false;
+                return Some(false);
+            }
+        }
+        None
+    }
+}
+
+/// Playback of captured audio through the speakers.
+#[derive(Clone, Debug)]
+struct Loopback {
+    /// The buffer that the loopback sound plays from.
+    buffer: SampleBuffer,
+
+    /// The loopback sound, if the audio backend could start it.
+    instance: Option<SoundInstanceHandle>,
+
+    /// The position between input samples at which the next output sample
+    /// falls.
+    phase: f64,
+}
+
+impl Loopback {
+    /// Don't let more than half a second of audio build up, such as when the
+    /// audio backend isn't playing anything.
+    const MAX_BUFFERED_FRAMES: usize = SampleBuffer::SAMPLE_RATE as usize / 2;
+
+    /// Resample captured audio to the rate of generated sounds, and queue it up
+    /// to be played.
+    fn append(&mut self, samples: &[f32], sample_rate: u32) {
+        let step = f64::from(sample_rate) / f64::from(SampleBuffer::SAMPLE_RATE);
+        let mut frames = Vec::new();
+        while let Some(&sample) = samples.get(self.phase as usize) {
+            frames.push([sample, sample]);
+            self.phase += step;
+        }
+        self.phase = (self.phase - samples.len() as f64).max(0.0);
+
+        if self.buffer.buffered_frames() < Self::MAX_BUFFERED_FRAMES {
+            self.buffer.append(frames);
+        }
+    }
+}
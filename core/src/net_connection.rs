@@ -0,0 +1,1267 @@
+//! NetConnection implementation
+
+use crate::avm1::globals::shared_object::deserialize_value as avm1_deserialize_value;
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    Attribute as Avm1Attribute, ExecutionReason as Avm1ExecutionReason, Object as Avm1Object,
+    ScriptObject as Avm1ScriptObject, TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::error::reference_error;
+use crate::avm2::events::{dispatch_event, dispatch_net_status_event, net_status_info};
+use crate::avm2::object::ResponderCallback;
+use crate::avm2::{
+    Activation as Avm2Activation, Error as Avm2Error, Object as Avm2Object, TObject as Avm2TObject,
+    Value as Avm2Value,
+};
+use crate::backend::navigator::{
+    ConnectionState, NavigatorBackend, OwnedFuture, Request, Response, SocketAction,
+};
+use crate::context::UpdateContext;
+use crate::loader::Error;
+use crate::remote_shared_object::RemoteSharedObject;
+use crate::string::AvmString;
+use crate::vminterface::AvmObject;
+use flash_lso::types::{AMFVersion, Element, Value as AmfValue};
+use gc_arena::{Collect, GcCell, MutationContext};
+use rtmp::remoting::{self, Packet};
+use rtmp::{Command, Message, Session, SharedObjectMessage};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+use url::Url;
+
+/// How long to wait for an RTMP server to accept a connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The transaction ID of the `connect` command.
+const CONNECT_TRANSACTION_ID: u32 = 1;
+
+/// Manager for all open `NetConnection`s.
+///
+/// Connections to a server are polled once per tick for data from the
+/// network, and calls to a remoting gateway are sent once per tick.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct NetConnections<'gc> {
+    /// List of connections that are connecting to, or connected to, a server.
+    open_connections: Vec<NetConnection<'gc>>,
+}
+
+impl<'gc> Default for NetConnections<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'gc> NetConnections<'gc> {
+    pub fn new() -> Self {
+        Self {
+            open_connections: Vec::new(),
+        }
+    }
+
+    fn add(context: &mut UpdateContext<'_, 'gc>, connection: NetConnection<'gc>) {
+        if !context
+            .net_connections
+            .open_connections
+            .contains(&connection)
+        {
+            context.net_connections.open_connections.push(connection);
+        }
+    }
+
+    fn remove(context: &mut UpdateContext<'_, 'gc>, connection: NetConnection<'gc>) {
+        context
+            .net_connections
+            .open_connections
+            .retain(|c| *c != connection);
+    }
+
+    /// Process network activity on all open connections.
+    ///
+    /// The `dt` parameter indicates how many milliseconds have elapsed since
+    /// the last tick.
+    pub fn tick(context: &mut UpdateContext<'_, 'gc>, dt: f64) {
+        let connections = context.net_connections.open_connections.clone();
+        for connection in connections {
+            connection.tick(context, dt);
+        }
+    }
+}
+
+/// A connection to a media server, application server or remoting gateway.
+///
+/// It corresponds directly to the `NetConnection` class of either VM.
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct NetConnection<'gc>(GcCell<'gc, NetConnectionData<'gc>>);
+
+impl<'gc> PartialEq for NetConnection<'gc> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ptr() == other.0.as_ptr()
+    }
+}
+
+impl<'gc> Eq for NetConnection<'gc> {}
+
+impl fmt::Debug for NetConnection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NetConnection")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct NetConnectionData<'gc> {
+    /// The AVM side of this connection, which receives status events and
+    /// calls from the server.
+    avm_object: Option<AvmObject<'gc>>,
+
+    /// The URI that was passed to `connect`, if any.
+    uri: Option<String>,
+
+    /// How the connection is made, or `None` if it has not been made.
+    #[collect(require_static)]
+    protocol: Option<NetConnectionProtocol>,
+
+    /// The responders of calls that are waiting for a result, keyed by
+    /// transaction ID. These are `Responder`s in AVM2, and any object with
+    /// `onResult` and `onStatus` methods in AVM1.
+    responders: HashMap<u32, AvmObject<'gc>>,
+
+    /// The remote shared objects that are synced over this connection.
+    shared_objects: Vec<RemoteSharedObject<'gc>>,
+}
+
+enum NetConnectionProtocol {
+    /// `connect(null)`: there is no server, and the connection is only used
+    /// to play local media files.
+    Local,
+
+    /// A connection to an RTMP server.
+    Rtmp(Box<RtmpConnection>),
+
+    /// A Flash Remoting gateway, which is called over HTTP.
+    Remoting(Box<RemotingConnection>),
+}
+
+impl<'gc> NetConnection<'gc> {
+    pub fn new(gc_context: MutationContext<'gc, '_>, avm_object: Option<AvmObject<'gc>>) -> Self {
+        Self(GcCell::allocate(
+            gc_context,
+            NetConnectionData {
+                avm_object,
+                uri: None,
+                protocol: None,
+                responders: HashMap::new(),
+                shared_objects: Vec::new(),
+            },
+        ))
+    }
+
+    pub fn set_avm_object(self, gc_context: MutationContext<'gc, '_>, avm_object: AvmObject<'gc>) {
+        self.0.write(gc_context).avm_object = Some(avm_object);
+    }
+
+    /// Whether a connection has been made and accepted.
+    ///
+    /// Remoting gateways are never connected, as there is no persistent
+    /// connection to them.
+    pub fn is_connected(self) -> bool {
+        match &self.0.read().protocol {
+            Some(NetConnectionProtocol::Local) => true,
+            Some(NetConnectionProtocol::Rtmp(rtmp)) => rtmp.connected,
+            Some(NetConnectionProtocol::Remoting(_)) | None => false,
+        }
+    }
+
+    /// The URI that was passed to `connect`, or `None` if there isn't one.
+    pub fn uri(self) -> Option<String> {
+        self.0.read().uri.clone()
+    }
+
+    /// Connect to a server, closing any existing connection first.
+    ///
+    /// `None` connects to nothing, for playing local media. `rtmp://` and
+    /// `rtmpt://` URIs connect to an RTMP server, sending `arguments` along
+    /// with the `connect` command. The outcome is reported through status
+    /// events. `http://` and `https://` URIs point to a Flash Remoting
+    /// gateway, which needs no connection, so no events are sent.
+    ///
+    /// Returns `false` if the URI has an unsupported protocol.
+    pub fn connect(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        uri: Option<String>,
+        object_encoding: AMFVersion,
+        arguments: Vec<AmfValue>,
+    ) -> bool {
+        self.close(context);
+
+        let Some(uri) = uri else {
+            let mut write = self.0.write(context.gc_context);
+            write.uri = Some("null".to_string());
+            write.protocol = Some(NetConnectionProtocol::Local);
+            drop(write);
+            self.trigger_status_event(context, "NetConnection.Connect.Success", "status");
+            return true;
+        };
+        self.0.write(context.gc_context).uri = Some(uri.clone());
+
+        let url = match Url::parse(&uri) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {
+                self.0.write(context.gc_context).protocol = Some(NetConnectionProtocol::Remoting(
+                    Box::new(RemotingConnection::new(url.to_string(), object_encoding)),
+                ));
+                NetConnections::add(context, self);
+                return true;
+            }
+            Ok(url) if matches!(url.scheme(), "rtmp" | "rtmpt") => url,
+            _ => {
+                tracing::warn!("NetConnection: unsupported protocol in {uri}");
+                self.trigger_status_event(context, "NetConnection.Connect.Failed", "error");
+                return false;
+            }
+        };
+
+        let connect_object = connect_command_object(context, &url, object_encoding);
+        let mut session = Session::client();
+        let command = Message::Command(Command {
+            name: "connect".to_string(),
+            transaction_id: CONNECT_TRANSACTION_ID.into(),
+            command_object: connect_object,
+            arguments,
+        });
+        if let Err(e) = session.send(0, &command) {
+            tracing::warn!("NetConnection: could not send connect command: {e}");
+            self.trigger_status_event(context, "NetConnection.Connect.Failed", "error");
+            return true;
+        }
+
+        let transport = if url.scheme() == "rtmpt" {
+            RtmpTransport::Tunnel(RtmptTunnel::open(context.navigator, &url))
+        } else {
+            let (data_sender, data_receiver) = channel();
+            let (action_sender, action_receiver) = channel();
+            context.navigator.connect_socket(
+                url.host_str().unwrap_or_default().to_string(),
+                url.port().unwrap_or(1935),
+                CONNECT_TIMEOUT,
+                data_receiver,
+                action_sender,
+            );
+            RtmpTransport::Socket {
+                sender: data_sender,
+                receiver: action_receiver,
+            }
+        };
+
+        self.0.write(context.gc_context).protocol =
+            Some(NetConnectionProtocol::Rtmp(Box::new(RtmpConnection {
+                session,
+                transport,
+                connected: false,
+                next_transaction_id: CONNECT_TRANSACTION_ID + 1,
+                object_encoding,
+            })));
+        NetConnections::add(context, self);
+        true
+    }
+
+    /// Close the connection, sending `NetConnection.Connect.Closed` if it
+    /// was connected.
+    pub fn close(self, context: &mut UpdateContext<'_, 'gc>) {
+        let was_connected = self.is_connected();
+        self.disconnect(context);
+        if was_connected {
+            self.trigger_status_event(context, "NetConnection.Connect.Closed", "status");
+        }
+    }
+
+    /// Tear down the connection without sending any events.
+    fn disconnect(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        if let Some(NetConnectionProtocol::Rtmp(rtmp)) = write.protocol.take() {
+            rtmp.transport.close(context.navigator);
+        }
+        write.responders.clear();
+        let shared_objects = std::mem::take(&mut write.shared_objects);
+        drop(write);
+        for shared_object in shared_objects {
+            shared_object.disconnect(context.gc_context);
+        }
+        NetConnections::remove(context, self);
+    }
+
+    /// The object encoding that the connection was made with.
+    pub fn object_encoding(self) -> AMFVersion {
+        match &self.0.read().protocol {
+            Some(NetConnectionProtocol::Rtmp(rtmp)) => rtmp.object_encoding,
+            Some(NetConnectionProtocol::Remoting(remoting)) => remoting.object_encoding,
+            _ => AMFVersion::AMF3,
+        }
+    }
+
+    /// Call a procedure on the server.
+    ///
+    /// If a `responder` is given, it is called back with the result of the
+    /// call. Returns `false` if there is no server to call.
+    pub fn call(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        command: String,
+        responder: Option<AvmObject<'gc>>,
+        arguments: Vec<AmfValue>,
+    ) -> bool {
+        let mut write = self.0.write(context.gc_context);
+        let transaction_id = match &mut write.protocol {
+            Some(NetConnectionProtocol::Rtmp(rtmp)) => {
+                match rtmp.call(context.navigator, command, responder.is_some(), arguments) {
+                    Ok(transaction_id) => transaction_id,
+                    Err(e) => {
+                        tracing::warn!("NetConnection: could not send call: {e}");
+                        return true;
+                    }
+                }
+            }
+            Some(NetConnectionProtocol::Remoting(remoting)) => remoting.call(command, arguments),
+            _ => return false,
+        };
+
+        if let Some(responder) = responder {
+            write.responders.insert(transaction_id, responder);
+        }
+        true
+    }
+
+    /// Add a header to every subsequent request to a remoting gateway,
+    /// replacing any header with the same name.
+    pub fn add_header(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        name: String,
+        must_understand: bool,
+        value: AmfValue,
+    ) {
+        if let Some(NetConnectionProtocol::Remoting(remoting)) =
+            &mut self.0.write(gc_context).protocol
+        {
+            remoting.set_header(remoting::Header {
+                name,
+                must_understand,
+                value,
+            });
+        }
+    }
+
+    /// Send a message about a remote shared object to the server.
+    ///
+    /// Returns `false` if there is no RTMP server to send it to.
+    pub fn send_shared_object_message(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        message: SharedObjectMessage,
+    ) -> bool {
+        let mut write = self.0.write(context.gc_context);
+        let Some(NetConnectionProtocol::Rtmp(rtmp)) = &mut write.protocol else {
+            return false;
+        };
+        if !rtmp.connected {
+            return false;
+        }
+
+        if let Err(e) = rtmp.session.send(0, &Message::SharedObject(message)) {
+            tracing::warn!("NetConnection: could not send shared object message: {e}");
+        }
+        rtmp.flush(context.navigator);
+        true
+    }
+
+    pub fn add_shared_object(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        shared_object: RemoteSharedObject<'gc>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        if !write.shared_objects.contains(&shared_object) {
+            write.shared_objects.push(shared_object);
+        }
+    }
+
+    pub fn remove_shared_object(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        shared_object: RemoteSharedObject<'gc>,
+    ) {
+        self.0
+            .write(gc_context)
+            .shared_objects
+            .retain(|s| *s != shared_object);
+    }
+
+    /// Process network activity on this connection.
+    fn tick(self, context: &mut UpdateContext<'_, 'gc>, dt: f64) {
+        let mut write = self.0.write(context.gc_context);
+        let rtmp = match &mut write.protocol {
+            Some(NetConnectionProtocol::Rtmp(rtmp)) => rtmp,
+            Some(NetConnectionProtocol::Remoting(remoting)) => {
+                remoting.flush(context.navigator);
+                let responses: Vec<_> = remoting.receiver.try_iter().collect();
+                drop(write);
+
+                for response in responses {
+                    if !self.is_open() {
+                        return;
+                    }
+                    self.handle_remoting_response(context, response);
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        let mut messages = Vec::new();
+        let mut lost = false;
+        for action in rtmp.transport.poll(context.navigator, dt) {
+            match action {
+                SocketAction::Connect(ConnectionState::Connected) => {}
+                SocketAction::Connect(_) | SocketAction::Close => lost = true,
+                SocketAction::Data(data) => match rtmp.session.receive(&data) {
+                    Ok(received) => messages.extend(received),
+                    Err(e) => {
+                        tracing::warn!("NetConnection: invalid data from server: {e}");
+                        lost = true;
+                    }
+                },
+            }
+            if lost {
+                break;
+            }
+        }
+        rtmp.flush(context.navigator);
+        drop(write);
+
+        for message in messages {
+            if !self.is_open() {
+                return;
+            }
+            self.handle_message(context, message);
+        }
+
+        if lost && self.is_open() {
+            let was_connected = self.is_connected();
+            self.disconnect(context);
+            if was_connected {
+                self.trigger_status_event(context, "NetConnection.Connect.Closed", "status");
+            } else {
+                self.trigger_status_event(context, "NetConnection.Connect.Failed", "error");
+            }
+            return;
+        }
+
+        let shared_objects = self.0.read().shared_objects.clone();
+        for shared_object in shared_objects {
+            shared_object.tick(context, dt);
+        }
+    }
+
+    /// Whether the connection has not been closed since it was opened.
+    fn is_open(self) -> bool {
+        self.0.read().protocol.is_some()
+    }
+
+    fn handle_message(self, context: &mut UpdateContext<'_, 'gc>, received: rtmp::ReceivedMessage) {
+        // Messages on other streams are meant for `NetStream`s.
+        if received.stream_id != 0 {
+            return;
+        }
+        let command = match received.message {
+            Message::Command(command) => command,
+            Message::SharedObject(message) => {
+                let shared_object = self
+                    .0
+                    .read()
+                    .shared_objects
+                    .iter()
+                    .find(|shared_object| shared_object.name() == message.name)
+                    .copied();
+                if let Some(shared_object) = shared_object {
+                    shared_object.handle_message(context, message);
+                }
+                return;
+            }
+            _ => return,
+        };
+        let first_argument = command
+            .arguments
+            .first()
+            .cloned()
+            .unwrap_or(AmfValue::Undefined);
+        let transaction_id = command.transaction_id as u32;
+
+        match command.name.as_str() {
+            "_result" | "_error"
+                if transaction_id == CONNECT_TRANSACTION_ID && !self.is_connected() =>
+            {
+                if command.name == "_result" {
+                    if let Some(NetConnectionProtocol::Rtmp(rtmp)) =
+                        &mut self.0.write(context.gc_context).protocol
+                    {
+                        rtmp.connected = true;
+                    }
+                    self.trigger_server_status_event(context, &first_argument);
+                } else {
+                    // The server rejected the connection.
+                    self.trigger_server_status_event(context, &first_argument);
+                    self.disconnect(context);
+                    self.trigger_status_event(context, "NetConnection.Connect.Closed", "status");
+                }
+            }
+            "_result" | "_error" => {
+                let responder = self
+                    .0
+                    .write(context.gc_context)
+                    .responders
+                    .remove(&transaction_id);
+                if let Some(responder) = responder {
+                    let callback = if command.name == "_result" {
+                        ResponderCallback::Result
+                    } else {
+                        ResponderCallback::Status
+                    };
+                    send_responder_callback(context, responder, callback, &first_argument);
+                }
+            }
+            "onStatus" => self.trigger_server_status_event(context, &first_argument),
+            "close" => self.close(context),
+            _ => self.call_client(context, command),
+        }
+    }
+
+    /// Handle the response of a remoting gateway to a batch of calls.
+    fn handle_remoting_response(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        response: RemotingResponse,
+    ) {
+        let packet = match response.result {
+            Ok(packet) => packet,
+            Err(code) => {
+                let mut write = self.0.write(context.gc_context);
+                for id in &response.response_ids {
+                    write.responders.remove(id);
+                }
+                drop(write);
+                self.trigger_status_event(context, code, "error");
+                return;
+            }
+        };
+
+        if let Some(NetConnectionProtocol::Remoting(remoting)) =
+            &mut self.0.write(context.gc_context).protocol
+        {
+            for header in &packet.headers {
+                remoting.handle_header(header);
+            }
+        }
+
+        for message in packet.messages {
+            // Results are sent to `/<id>/onResult` or `/<id>/onStatus`.
+            let Some((id, callback)) = message
+                .target_uri
+                .strip_prefix('/')
+                .and_then(|target| target.split_once('/'))
+            else {
+                continue;
+            };
+            let callback = match callback {
+                "onResult" => ResponderCallback::Result,
+                "onStatus" => ResponderCallback::Status,
+                _ => continue,
+            };
+            let Ok(id) = id.parse::<u32>() else {
+                continue;
+            };
+
+            let responder = self.0.write(context.gc_context).responders.remove(&id);
+            if let Some(responder) = responder {
+                send_responder_callback(context, responder, callback, &message.contents);
+            }
+            if !self.is_open() {
+                return;
+            }
+        }
+
+        // Calls that the gateway did not answer will never get a result.
+        let mut write = self.0.write(context.gc_context);
+        for id in &response.response_ids {
+            write.responders.remove(id);
+        }
+    }
+
+    /// Call a method that the server invoked on this connection.
+    ///
+    /// In AVM2, the method is called on the connection's `client`, and in
+    /// AVM1 on the connection itself.
+    fn call_client(self, context: &mut UpdateContext<'_, 'gc>, command: Command) {
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let name = AvmString::new_utf8(activation.context.gc_context, command.name);
+                let result = command
+                    .arguments
+                    .iter()
+                    .map(|argument| crate::avm2::amf::deserialize_value(&mut activation, argument))
+                    .collect::<Result<Vec<_>, _>>()
+                    .and_then(|arguments| {
+                        call_avm2_client(
+                            &mut activation,
+                            object,
+                            "flash.net.NetConnection",
+                            name,
+                            &arguments,
+                        )
+                    });
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in NetConnection {} handler: {}",
+                        name,
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[NetConnection Call]"),
+                );
+                let arguments: Vec<_> = command
+                    .arguments
+                    .iter()
+                    .map(|argument| avm1_deserialize_value(&mut activation, argument))
+                    .collect();
+                let name = AvmString::new_utf8(activation.context.gc_context, command.name);
+                let _ = object.call_method(
+                    name,
+                    &arguments,
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                );
+            }
+            None => {}
+        }
+    }
+
+    /// Send a status event with an info object sent by the server.
+    fn trigger_server_status_event(self, context: &mut UpdateContext<'_, 'gc>, info: &AmfValue) {
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let result = crate::avm2::amf::deserialize_value(&mut activation, info)
+                    .and_then(|info| dispatch_net_status_event(&mut activation, object, info));
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in netStatus event handler: {}",
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[NetConnection Status Event]"),
+                );
+                let info = avm1_deserialize_value(&mut activation, info);
+                call_avm1_on_status(&mut activation, object, info);
+            }
+            None => {}
+        }
+    }
+
+    /// Send a `NetStatusEvent` (AVM2) or call `onStatus` (AVM1) with the
+    /// given info code and level.
+    pub fn trigger_status_event(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        code: &'static str,
+        level: &'static str,
+    ) {
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let result = net_status_info(&mut activation, code.into(), level.into())
+                    .and_then(|info| dispatch_net_status_event(&mut activation, object, info));
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in netStatus event handler: {}",
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[NetConnection Status Event]"),
+                );
+                let info = Avm1ScriptObject::new(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().object),
+                );
+                info.define_value(
+                    activation.context.gc_context,
+                    "code",
+                    code.into(),
+                    Avm1Attribute::empty(),
+                );
+                info.define_value(
+                    activation.context.gc_context,
+                    "level",
+                    level.into(),
+                    Avm1Attribute::empty(),
+                );
+                call_avm1_on_status(&mut activation, object, info.into());
+            }
+            None => {}
+        }
+    }
+}
+
+pub(crate) fn call_avm1_on_status<'gc>(
+    activation: &mut Avm1Activation<'_, 'gc>,
+    object: Avm1Object<'gc>,
+    info: Avm1Value<'gc>,
+) {
+    let _ = object.call_method(
+        "onStatus".into(),
+        &[info],
+        activation,
+        Avm1ExecutionReason::Special,
+    );
+}
+
+fn send_responder_callback<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    responder: AvmObject<'gc>,
+    callback: ResponderCallback,
+    value: &AmfValue,
+) {
+    match responder {
+        AvmObject::Avm2(responder) => {
+            let Some(responder) = responder.as_responder() else {
+                return;
+            };
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let result = crate::avm2::amf::deserialize_value(&mut activation, value)
+                .and_then(|value| responder.send_callback(&mut activation, callback, value));
+            if let Err(e) = result {
+                tracing::error!(
+                    "Unhandled AVM2 exception in Responder callback: {}",
+                    e.detailed_message(&mut activation)
+                );
+            }
+        }
+        AvmObject::Avm1(responder) => {
+            let mut activation = Avm1Activation::from_stub(
+                context.reborrow(),
+                Avm1ActivationIdentifier::root("[NetConnection Responder]"),
+            );
+            let value = avm1_deserialize_value(&mut activation, value);
+            let method = match callback {
+                ResponderCallback::Result => "onResult",
+                ResponderCallback::Status => "onStatus",
+            };
+            let _ = responder.call_method(
+                method.into(),
+                &[value],
+                &mut activation,
+                Avm1ExecutionReason::Special,
+            );
+        }
+    }
+}
+
+/// Call a method on the `client` of an AVM2 `NetConnection` or `NetStream`.
+///
+/// If the client has no such method, an `asyncError` event is sent to the
+/// `target` instead.
+pub(crate) fn call_avm2_client<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    target: Avm2Object<'gc>,
+    class_name: &str,
+    name: AvmString<'gc>,
+    arguments: &[Avm2Value<'gc>],
+) -> Result<(), Avm2Error<'gc>> {
+    let client = target
+        .get_public_property("client", activation)?
+        .coerce_to_object(activation)?;
+    if client.has_public_property(name, activation) {
+        client.call_public_property(name, arguments, activation)?;
+    } else {
+        let message = format!("Error #2095: {class_name} was unable to invoke callback {name}.");
+        let error = reference_error(activation, &message, 2095)?;
+        let message = AvmString::new_utf8(activation.context.gc_context, message);
+        let event = activation.avm2().classes().asyncerrorevent.construct(
+            activation,
+            &[
+                "asyncError".into(),
+                false.into(),
+                false.into(),
+                message.into(),
+                error,
+            ],
+        )?;
+        dispatch_event(activation, target, event)?;
+    }
+
+    Ok(())
+}
+
+/// Build the properties of the `connect` command, which tell the server
+/// about the player and the movie.
+fn connect_command_object(
+    context: &mut UpdateContext<'_, '_>,
+    url: &Url,
+    object_encoding: AMFVersion,
+) -> AmfValue {
+    let string = |s: &str| AmfValue::String(s.to_string());
+    let app = url.path().trim_start_matches('/');
+    let flash_version = context.system.get_version_string(context.avm1);
+    let object_encoding = match object_encoding {
+        AMFVersion::AMF0 => 0.0,
+        AMFVersion::AMF3 => 3.0,
+    };
+
+    AmfValue::Object(
+        vec![
+            Element::new("app", string(app)),
+            Element::new("flashVer", string(&flash_version)),
+            Element::new("swfUrl", string(context.swf.url())),
+            Element::new("tcUrl", string(url.as_str())),
+            Element::new("fpad", AmfValue::Bool(false)),
+            // All the capabilities, audio codecs and video codecs that Flash
+            // Player 10 advertises.
+            Element::new("capabilities", AmfValue::Number(239.0)),
+            Element::new("audioCodecs", AmfValue::Number(3575.0)),
+            Element::new("videoCodecs", AmfValue::Number(252.0)),
+            Element::new("videoFunction", AmfValue::Number(1.0)),
+            Element::new("pageUrl", AmfValue::Undefined),
+            Element::new("objectEncoding", AmfValue::Number(object_encoding)),
+        ],
+        None,
+    )
+}
+
+/// The state of a connection to an RTMP server.
+struct RtmpConnection {
+    session: Session,
+    transport: RtmpTransport,
+
+    /// Whether the server has accepted the `connect` command.
+    connected: bool,
+
+    next_transaction_id: u32,
+    object_encoding: AMFVersion,
+}
+
+impl RtmpConnection {
+    /// Call a procedure on the server, returning the transaction ID of the
+    /// call.
+    fn call(
+        &mut self,
+        navigator: &mut dyn NavigatorBackend,
+        command: String,
+        wants_result: bool,
+        arguments: Vec<AmfValue>,
+    ) -> Result<u32, rtmp::Error> {
+        // A transaction ID of 0 tells the server not to respond.
+        let transaction_id = if wants_result {
+            let id = self.next_transaction_id;
+            self.next_transaction_id += 1;
+            id
+        } else {
+            0
+        };
+        let message = Message::Command(Command {
+            name: command,
+            transaction_id: transaction_id.into(),
+            command_object: AmfValue::Null,
+            arguments,
+        });
+        self.session.send(0, &message)?;
+        self.flush(navigator);
+        Ok(transaction_id)
+    }
+
+    /// Send any pending output of the session to the server.
+    fn flush(&mut self, navigator: &mut dyn NavigatorBackend) {
+        let output = self.session.take_output();
+        if !output.is_empty() {
+            self.transport.send(navigator, output);
+        }
+    }
+}
+
+/// How bytes get to and from an RTMP server.
+enum RtmpTransport {
+    /// A TCP connection, for `rtmp://` URIs.
+    Socket {
+        sender: Sender<Vec<u8>>,
+        receiver: Receiver<SocketAction>,
+    },
+
+    /// A series of HTTP requests, for `rtmpt://` URIs.
+    Tunnel(RtmptTunnel),
+}
+
+impl RtmpTransport {
+    fn send(&mut self, navigator: &mut dyn NavigatorBackend, data: Vec<u8>) {
+        match self {
+            Self::Socket { sender, .. } => {
+                let _ = sender.send(data);
+            }
+            Self::Tunnel(tunnel) => {
+                tunnel.outgoing.extend(data);
+                tunnel.request(navigator);
+            }
+        }
+    }
+
+    fn poll(&mut self, navigator: &mut dyn NavigatorBackend, dt: f64) -> Vec<SocketAction> {
+        match self {
+            Self::Socket { receiver, .. } => receiver.try_iter().collect(),
+            Self::Tunnel(tunnel) => tunnel.poll(navigator, dt),
+        }
+    }
+
+    fn close(self, navigator: &mut dyn NavigatorBackend) {
+        // Dropping the sender of a socket closes it.
+        if let Self::Tunnel(tunnel) = self {
+            tunnel.close(navigator);
+        }
+    }
+}
+
+/// The outcome of an RTMPT request.
+enum TunnelResponse {
+    /// The server opened a session with the given ID.
+    Opened(String),
+
+    /// The server answered a request with a polling delay and some data.
+    Data(u8, Vec<u8>),
+
+    Failed,
+}
+
+/// A session of the RTMPT protocol, which tunnels RTMP through HTTP POST
+/// requests.
+///
+/// The client opens a session with `/open/1`, and then sends its data with
+/// `/send/<session>/<sequence>`, or polls for data with
+/// `/idle/<session>/<sequence>`. Only one request is in flight at a time.
+struct RtmptTunnel {
+    /// The `http://host:port` prefix of all requests.
+    base_url: String,
+
+    session_id: Option<String>,
+    sequence: u32,
+    in_flight: bool,
+
+    /// Data waiting to be sent with the next request.
+    outgoing: Vec<u8>,
+
+    /// How long to wait between idle polls, in milliseconds.
+    poll_interval: f64,
+    time_since_request: f64,
+
+    sender: Sender<TunnelResponse>,
+    receiver: Receiver<TunnelResponse>,
+}
+
+impl RtmptTunnel {
+    fn open(navigator: &mut dyn NavigatorBackend, url: &Url) -> Self {
+        let (sender, receiver) = channel();
+        let mut tunnel = Self {
+            base_url: format!(
+                "http://{}:{}",
+                url.host_str().unwrap_or_default(),
+                url.port().unwrap_or(80)
+            ),
+            session_id: None,
+            sequence: 0,
+            in_flight: true,
+            outgoing: Vec::new(),
+            poll_interval: 0.0,
+            time_since_request: 0.0,
+            sender,
+            receiver,
+        };
+        tunnel.post(navigator, "/open/1".to_string(), vec![0], true);
+        tunnel
+    }
+
+    fn post(
+        &mut self,
+        navigator: &mut dyn NavigatorBackend,
+        path: String,
+        body: Vec<u8>,
+        open: bool,
+    ) {
+        let request = Request::post(
+            format!("{}{}", self.base_url, path),
+            Some((body, "application/x-fcs".to_string())),
+        );
+        let fetch = navigator.fetch(request);
+        navigator.spawn_future(Box::pin(tunnel_request(fetch, open, self.sender.clone())));
+        self.in_flight = true;
+        self.time_since_request = 0.0;
+    }
+
+    /// Send outgoing data, or poll for incoming data if it is time to.
+    fn request(&mut self, navigator: &mut dyn NavigatorBackend) {
+        let Some(session_id) = self.session_id.clone() else {
+            return;
+        };
+        if self.in_flight
+            || (self.outgoing.is_empty() && self.time_since_request < self.poll_interval)
+        {
+            return;
+        }
+
+        self.sequence += 1;
+        let path = if self.outgoing.is_empty() {
+            format!("/idle/{}/{}", session_id, self.sequence)
+        } else {
+            format!("/send/{}/{}", session_id, self.sequence)
+        };
+        let body = std::mem::take(&mut self.outgoing);
+        self.post(navigator, path, body, false);
+    }
+
+    fn poll(&mut self, navigator: &mut dyn NavigatorBackend, dt: f64) -> Vec<SocketAction> {
+        let mut actions = Vec::new();
+        for response in self.receiver.try_iter() {
+            self.in_flight = false;
+            match response {
+                TunnelResponse::Opened(session_id) => {
+                    self.session_id = Some(session_id);
+                    actions.push(SocketAction::Connect(ConnectionState::Connected));
+                }
+                TunnelResponse::Data(interval, data) => {
+                    // The server asks for slower polling while the connection
+                    // is idle. We count each unit of the delay as a frame at
+                    // 60 FPS.
+                    self.poll_interval = f64::from(interval) * 1000.0 / 60.0;
+                    if !data.is_empty() {
+                        actions.push(SocketAction::Data(data));
+                    }
+                }
+                TunnelResponse::Failed if self.session_id.is_none() => {
+                    actions.push(SocketAction::Connect(ConnectionState::Failed));
+                }
+                TunnelResponse::Failed => actions.push(SocketAction::Close),
+            }
+        }
+
+        self.time_since_request += dt;
+        self.request(navigator);
+        actions
+    }
+
+    fn close(mut self, navigator: &mut dyn NavigatorBackend) {
+        if let Some(session_id) = self.session_id.take() {
+            self.post(navigator, format!("/close/{session_id}"), vec![0], false);
+        }
+    }
+}
+
+/// Wait for the response to an RTMPT request, and pass it on to the tunnel.
+async fn tunnel_request(
+    fetch: OwnedFuture<Response, Error>,
+    open: bool,
+    sender: Sender<TunnelResponse>,
+) -> Result<(), Error> {
+    let response = match fetch.await {
+        Ok(response) if open => {
+            TunnelResponse::Opened(String::from_utf8_lossy(&response.body).trim().to_string())
+        }
+        Ok(response) => match response.body.split_first() {
+            Some((interval, data)) => TunnelResponse::Data(*interval, data.to_vec()),
+            None => TunnelResponse::Failed,
+        },
+        Err(e) => {
+            tracing::warn!("RTMPT request failed: {e}");
+            TunnelResponse::Failed
+        }
+    };
+    let _ = sender.send(response);
+    Ok(())
+}
+
+/// The state of a connection to a Flash Remoting gateway.
+///
+/// Every call made during a frame is sent to the gateway in a single POST
+/// request at the end of it.
+struct RemotingConnection {
+    /// The URL that requests are posted to, which the gateway may change.
+    gateway_url: String,
+
+    object_encoding: AMFVersion,
+
+    /// Headers sent with every request.
+    headers: Vec<remoting::Header>,
+
+    /// Calls waiting to be sent with the next request, along with their
+    /// response IDs.
+    pending_calls: Vec<(u32, remoting::Message)>,
+
+    next_response_id: u32,
+
+    sender: Sender<RemotingResponse>,
+    receiver: Receiver<RemotingResponse>,
+}
+
+/// The outcome of a request to a remoting gateway.
+struct RemotingResponse {
+    /// The IDs of the calls that were sent with the request.
+    response_ids: Vec<u32>,
+
+    /// The packet sent back by the gateway, or the code of the status event
+    /// to send if there isn't one.
+    result: Result<Packet, &'static str>,
+}
+
+impl RemotingConnection {
+    fn new(gateway_url: String, object_encoding: AMFVersion) -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            gateway_url,
+            object_encoding,
+            headers: Vec::new(),
+            pending_calls: Vec::new(),
+            next_response_id: 1,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Queue a call to be sent with the next request, returning its response
+    /// ID.
+    fn call(&mut self, command: String, arguments: Vec<AmfValue>) -> u32 {
+        let id = self.next_response_id;
+        self.next_response_id += 1;
+        let message = remoting::Message {
+            target_uri: command,
+            response_uri: format!("/{id}"),
+            contents: AmfValue::StrictArray(arguments.into_iter().map(Rc::new).collect()),
+        };
+        self.pending_calls.push((id, message));
+        id
+    }
+
+    fn set_header(&mut self, header: remoting::Header) {
+        self.headers.retain(|h| h.name != header.name);
+        self.headers.push(header);
+    }
+
+    /// Apply a header sent by the gateway.
+    fn handle_header(&mut self, header: &remoting::Header) {
+        match (header.name.as_str(), &header.value) {
+            ("AppendToGatewayUrl", AmfValue::String(suffix)) => {
+                self.gateway_url.push_str(suffix);
+            }
+            ("ReplaceGatewayUrl", AmfValue::String(url)) => {
+                self.gateway_url = url.clone();
+            }
+            ("RequestPersistentHeader", AmfValue::Object(properties, _)) => {
+                let property = |name: &str| {
+                    properties
+                        .iter()
+                        .find(|element| element.name == name)
+                        .map(|element| element.value().clone())
+                };
+                if let Some(AmfValue::String(name)) = property("name") {
+                    self.set_header(remoting::Header {
+                        name,
+                        must_understand: matches!(
+                            property("mustUnderstand"),
+                            Some(AmfValue::Bool(true))
+                        ),
+                        value: property("data").unwrap_or(AmfValue::Undefined),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Send all queued calls to the gateway.
+    fn flush(&mut self, navigator: &mut dyn NavigatorBackend) {
+        if self.pending_calls.is_empty() {
+            return;
+        }
+
+        let (response_ids, messages): (Vec<u32>, Vec<_>) =
+            std::mem::take(&mut self.pending_calls).into_iter().unzip();
+        let packet = Packet {
+            version: match self.object_encoding {
+                AMFVersion::AMF0 => 0,
+                AMFVersion::AMF3 => 3,
+            },
+            headers: self.headers.clone(),
+            messages,
+        };
+        let body = match packet.to_bytes() {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!("NetConnection: could not serialize remoting call: {e}");
+                let _ = self.sender.send(RemotingResponse {
+                    response_ids,
+                    result: Err("NetConnection.Call.BadVersion"),
+                });
+                return;
+            }
+        };
+
+        let request = Request::post(
+            self.gateway_url.clone(),
+            Some((body, remoting::CONTENT_TYPE.to_string())),
+        );
+        let fetch = navigator.fetch(request);
+        navigator.spawn_future(Box::pin(remoting_request(
+            fetch,
+            response_ids,
+            self.sender.clone(),
+        )));
+    }
+}
+
+/// Wait for the response of a remoting gateway, and pass it on to the
+/// connection.
+async fn remoting_request(
+    fetch: OwnedFuture<Response, Error>,
+    response_ids: Vec<u32>,
+    sender: Sender<RemotingResponse>,
+) -> Result<(), Error> {
+    let result = match fetch.await {
+        Ok(response) => Packet::parse(&response.body).map_err(|e| {
+            tracing::warn!("NetConnection: invalid response from remoting gateway: {e}");
+            "NetConnection.Call.BadVersion"
+        }),
+        Err(e) => {
+            tracing::warn!("NetConnection: remoting call failed: {e}");
+            Err("NetConnection.Call.Failed")
+        }
+    };
+    let _ = sender.send(RemotingResponse {
+        response_ids,
+        result,
+    });
+    Ok(())
+}
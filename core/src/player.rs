@@ -12,11 +12,15 @@ use crate::avm2::{
 };
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    camera::CameraBackend,
+    local_connection::LocalConnectionBroker,
     log::LogBackend,
+    microphone::MicrophoneBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
     ui::{InputManager, MouseCursor, UiBackend},
 };
+use crate::camera::Cameras;
 use crate::compatibility_rules::CompatibilityRules;
 use crate::config::Letterbox;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
@@ -37,8 +41,13 @@ use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
+use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
+use crate::microphone::Microphones;
+use crate::net_connection::NetConnections;
 use crate::prelude::*;
+use crate::security::SecurityManager;
+use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::AvmString;
 use crate::stub::StubCollection;
@@ -157,6 +166,21 @@ struct GcRootData<'gc> {
 
     /// List of actively playing streams to decode.
     stream_manager: StreamManager<'gc>,
+
+    /// List of open connections to media servers.
+    net_connections: NetConnections<'gc>,
+
+    /// List of open TCP sockets.
+    sockets: Sockets<'gc>,
+
+    /// List of `LocalConnection`s, and the endpoint they send messages with.
+    local_connections: LocalConnections<'gc>,
+
+    /// List of microphones that have been opened.
+    microphones: Microphones<'gc>,
+
+    /// List of cameras that have been opened.
+    cameras: Cameras<'gc>,
 }
 
 impl<'gc> GcRootData<'gc> {
@@ -181,6 +205,11 @@ impl<'gc> GcRootData<'gc> {
         &mut ExternalInterface<'gc>,
         &mut AudioManager<'gc>,
         &mut StreamManager<'gc>,
+        &mut NetConnections<'gc>,
+        &mut Sockets<'gc>,
+        &mut LocalConnections<'gc>,
+        &mut Microphones<'gc>,
+        &mut Cameras<'gc>,
     ) {
         (
             self.stage,
@@ -198,6 +227,11 @@ impl<'gc> GcRootData<'gc> {
             &mut self.external_interface,
             &mut self.audio_manager,
             &mut self.stream_manager,
+            &mut self.net_connections,
+            &mut self.sockets,
+            &mut self.local_connections,
+            &mut self.microphones,
+            &mut self.cameras,
         )
     }
 }
@@ -209,6 +243,8 @@ type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
 type Log = Box<dyn LogBackend>;
+type Microphone = Box<dyn MicrophoneBackend>;
+type Camera = Box<dyn CameraBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
 
@@ -239,6 +275,8 @@ pub struct Player {
     log: Log,
     ui: Ui,
     video: Video,
+    microphone: Microphone,
+    camera: Camera,
 
     transform_stack: TransformStack,
 
@@ -273,6 +311,9 @@ pub struct Player {
 
     system: SystemProperties,
 
+    /// Which domains have given which movies access to their content.
+    security: SecurityManager,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     instance_counter: i32,
 
@@ -537,11 +578,38 @@ impl Player {
 
             self.update_timers(dt);
             self.update(|context| {
+                NetConnections::tick(context, dt);
                 StreamManager::tick(context, dt);
+                Sockets::tick(context);
+                LocalConnections::tick(context);
             });
             self.audio.tick();
         }
     }
+
+    /// Process network activity on all open sockets.
+    ///
+    /// This is part of `tick`, and is only needed separately by frontends
+    /// that run frames themselves, such as the regression test runner.
+    pub fn update_sockets(&mut self) {
+        self.update(|context| Sockets::tick(context));
+    }
+
+    /// Process responses for all open `NetConnection`s, and send any calls
+    /// that are waiting.
+    ///
+    /// Like `update_sockets`, this is part of `tick`.
+    pub fn update_net_connections(&mut self, dt: f64) {
+        self.update(|context| NetConnections::tick(context, dt));
+    }
+
+    /// Deliver `LocalConnection` messages and status events.
+    ///
+    /// Like `update_sockets`, this is part of `tick`.
+    pub fn update_local_connections(&mut self) {
+        self.update(|context| LocalConnections::tick(context));
+    }
+
     pub fn time_til_next_timer(&self) -> Option<f64> {
         self.time_til_next_timer
     }
@@ -1500,6 +1568,8 @@ impl Player {
                 Avm1::run_frame(context);
             }
             AudioManager::update_sounds(context);
+            Microphones::update(context);
+            Cameras::update(context);
         });
 
         self.needs_render = true;
@@ -1725,6 +1795,11 @@ impl Player {
                 external_interface,
                 audio_manager,
                 stream_manager,
+                net_connections,
+                sockets,
+                local_connections,
+                microphones,
+                cameras,
             ) = root_data.update_context_params();
 
             let mut update_context = UpdateContext {
@@ -1736,6 +1811,8 @@ impl Player {
                 audio: self.audio.deref_mut(),
                 navigator: self.navigator.deref_mut(),
                 ui: self.ui.deref_mut(),
+                microphone: self.microphone.deref_mut(),
+                camera: self.camera.deref_mut(),
                 action_queue,
                 gc_context,
                 stage,
@@ -1747,6 +1824,7 @@ impl Player {
                 player: self.self_reference.clone(),
                 load_manager,
                 system: &mut self.system,
+                security: &mut self.security,
                 instance_counter: &mut self.instance_counter,
                 storage: self.storage.deref_mut(),
                 log: self.log.deref_mut(),
@@ -1772,6 +1850,11 @@ impl Player {
                 frame_phase: &mut self.frame_phase,
                 stub_tracker: &mut self.stub_tracker,
                 stream_manager,
+                net_connections,
+                sockets,
+                local_connections,
+                microphones,
+                cameras,
             };
 
             let old_frame_rate = *update_context.frame_rate;
@@ -1882,6 +1965,11 @@ impl Player {
             self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
     }
 
+    /// Advance all playing media streams by `dt` milliseconds.
+    pub fn update_streams(&mut self, dt: f64) {
+        self.update(|context| StreamManager::tick(context, dt));
+    }
+
     /// Returns whether this player consumes mouse wheel events.
     /// Used by web to prevent scrolling.
     pub fn should_prevent_scrolling(&mut self) -> bool {
@@ -1947,6 +2035,9 @@ pub struct PlayerBuilder {
     storage: Option<Storage>,
     ui: Option<Ui>,
     video: Option<Video>,
+    microphone: Option<Microphone>,
+    camera: Option<Camera>,
+    local_connection_broker: Option<LocalConnectionBroker>,
 
     // Misc. player configuration
     autoplay: bool,
@@ -1965,6 +2056,7 @@ pub struct PlayerBuilder {
     player_version: Option<u8>,
     quality: StageQuality,
     sandbox_type: SandboxType,
+    security_sandbox: bool,
 }
 
 impl PlayerBuilder {
@@ -1984,6 +2076,9 @@ impl PlayerBuilder {
             storage: None,
             ui: None,
             video: None,
+            microphone: None,
+            camera: None,
+            local_connection_broker: None,
 
             autoplay: false,
             scale_mode: StageScaleMode::ShowAll,
@@ -2006,6 +2101,7 @@ impl PlayerBuilder {
             player_version: None,
             quality: StageQuality::High,
             sandbox_type: SandboxType::LocalTrusted,
+            security_sandbox: false,
         }
     }
 
@@ -2065,6 +2161,31 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the microphone backend of the player.
+    #[inline]
+    pub fn with_microphone(mut self, microphone: impl 'static + MicrophoneBackend) -> Self {
+        self.microphone = Some(Box::new(microphone));
+        self
+    }
+
+    /// Sets the camera backend of the player.
+    #[inline]
+    pub fn with_camera(mut self, camera: impl 'static + CameraBackend) -> Self {
+        self.camera = Some(Box::new(camera));
+        self
+    }
+
+    /// Sets the broker that routes `LocalConnection` messages to and from
+    /// other players.
+    ///
+    /// By default, each player gets its own broker, so that `LocalConnection`
+    /// only works within the player.
+    #[inline]
+    pub fn with_local_connection_broker(mut self, broker: LocalConnectionBroker) -> Self {
+        self.local_connection_broker = Some(broker);
+        self
+    }
+
     /// Sets whether the movie will start playing immediately upon load.
     #[inline]
     pub fn with_autoplay(mut self, autoplay: bool) -> Self {
@@ -2157,6 +2278,13 @@ impl PlayerBuilder {
         self
     }
 
+    /// Enforces cross-domain restrictions on movies that are not in the
+    /// local-trusted sandbox (default is `false`).
+    pub fn with_security_sandbox(mut self, enabled: bool) -> Self {
+        self.security_sandbox = enabled;
+        self
+    }
+
     /// Builds the player, wiring up the backends and configuring the specified settings.
     pub fn build(self) -> Arc<Mutex<Player>> {
         use crate::backend::*;
@@ -2186,6 +2314,14 @@ impl PlayerBuilder {
         let video = self
             .video
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
+        let microphone = self
+            .microphone
+            .unwrap_or_else(|| Box::new(microphone::NullMicrophoneBackend::new()));
+        let camera = self
+            .camera
+            .unwrap_or_else(|| Box::new(camera::NullCameraBackend::new()));
+
+        let local_connection_endpoint = self.local_connection_broker.unwrap_or_default().endpoint();
 
         let player_version = self.player_version.unwrap_or(NEWEST_PLAYER_VERSION);
 
@@ -2202,6 +2338,8 @@ impl PlayerBuilder {
                 storage,
                 ui,
                 video,
+                microphone,
+                camera,
 
                 // SWF info
                 swf: fake_movie.clone(),
@@ -2227,6 +2365,7 @@ impl PlayerBuilder {
                 // Misc. state
                 rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
                 system: SystemProperties::new(self.sandbox_type),
+                security: SecurityManager::new(self.security_sandbox),
                 transform_stack: TransformStack::new(),
                 instance_counter: 0,
                 player_version,
@@ -2269,6 +2408,11 @@ impl PlayerBuilder {
                                 timers: Timers::new(),
                                 unbound_text_fields: Vec::new(),
                                 stream_manager: StreamManager::new(),
+                                net_connections: NetConnections::new(),
+                                sockets: Sockets::new(),
+                                local_connections: LocalConnections::new(local_connection_endpoint),
+                                microphones: Microphones::new(),
+                                cameras: Cameras::new(),
                             },
                         ),
                     },
@@ -0,0 +1,766 @@
+//! Remote SharedObject implementation
+
+use crate::avm1::globals::shared_object::{
+    deserialize_value as avm1_deserialize_value, recursive_serialize as avm1_recursive_serialize,
+};
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    ArrayObject as Avm1ArrayObject, Attribute as Avm1Attribute,
+    ExecutionReason as Avm1ExecutionReason, ScriptObject as Avm1ScriptObject,
+    TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::events::{dispatch_event, dispatch_net_status_event, net_status_info};
+use crate::avm2::{
+    Activation as Avm2Activation, ArrayObject as Avm2ArrayObject, ArrayStorage as Avm2ArrayStorage,
+    Error as Avm2Error, TObject as Avm2TObject,
+};
+use crate::context::UpdateContext;
+use crate::net_connection::{call_avm1_on_status, call_avm2_client, NetConnection};
+use crate::string::AvmString;
+use crate::vminterface::AvmObject;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+use gc_arena::{Collect, GcCell, MutationContext};
+use rtmp::{SharedObjectEvent, SharedObjectMessage};
+use std::fmt;
+use std::rc::Rc;
+
+/// A shared object that is kept in sync with an RTMP server, through a
+/// `NetConnection`.
+///
+/// It corresponds to a `SharedObject` made by `getRemote` in either VM. The
+/// properties of its `data` object are compared with the values last agreed
+/// with the server, and any differences are sent as requests to change them.
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct RemoteSharedObject<'gc>(GcCell<'gc, RemoteSharedObjectData<'gc>>);
+
+impl<'gc> PartialEq for RemoteSharedObject<'gc> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ptr() == other.0.as_ptr()
+    }
+}
+
+impl<'gc> Eq for RemoteSharedObject<'gc> {}
+
+impl fmt::Debug for RemoteSharedObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSharedObject")
+            .field("ptr", &self.0.as_ptr())
+            .field("name", &self.0.read().name)
+            .finish()
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct RemoteSharedObjectData<'gc> {
+    /// The AVM side of this shared object, which owns the `data` object and
+    /// receives sync events.
+    avm_object: Option<AvmObject<'gc>>,
+
+    /// The name of the shared object on the server.
+    name: String,
+
+    /// The URI of the application that the shared object belongs to, which
+    /// must be the URI that the connection was made to.
+    uri: String,
+
+    /// Whether the server should keep the shared object after every client
+    /// has stopped using it.
+    persistent: bool,
+
+    /// The connection that the shared object is synced over, if any.
+    connection: Option<NetConnection<'gc>>,
+
+    /// Whether the server has accepted our request to use the shared object.
+    in_use: bool,
+
+    /// The version of the shared object that the server last told us about.
+    version: u32,
+
+    /// The value of each property, as last agreed with the server.
+    #[collect(require_static)]
+    properties: Vec<(String, AmfValue)>,
+
+    /// How often changes to `data` are sent to the server.
+    update_rate: UpdateRate,
+
+    /// Milliseconds since changes were last sent to the server.
+    time_since_update: f64,
+}
+
+#[derive(Clone, Copy, Collect, Debug, PartialEq)]
+#[collect(require_static)]
+enum UpdateRate {
+    /// Changes are sent every tick.
+    EveryTick,
+
+    /// Changes are sent every so many milliseconds.
+    Interval(f64),
+
+    /// Changes are not sent until the rate is set again.
+    Paused,
+}
+
+/// An entry of the change list passed to `onSync` (AVM1) or a `SyncEvent`
+/// (AVM2).
+struct SyncChange {
+    /// One of `clear`, `change`, `success` or `delete`.
+    code: &'static str,
+
+    /// The name of the property that changed, except for `clear`.
+    name: Option<String>,
+
+    /// The value that the property had before it was changed by the server.
+    old_value: Option<AmfValue>,
+}
+
+/// A change to make to the `data` object because of a message from the
+/// server.
+enum DataUpdate {
+    Clear,
+    Set(String, AmfValue),
+    Delete(String),
+}
+
+impl<'gc> RemoteSharedObject<'gc> {
+    /// The key that `SharedObject.getRemote` caches an object under, so that
+    /// it returns the same object for the same name, URI and persistence.
+    ///
+    /// These keys share a map with those of local shared objects. Neither
+    /// those keys nor the names of shared objects contain spaces, so the two
+    /// never clash.
+    pub fn cache_key(name: &str, uri: &str, persistent: bool) -> String {
+        format!("{uri} {name} {persistent}")
+    }
+
+    pub fn new(
+        gc_context: MutationContext<'gc, '_>,
+        name: String,
+        uri: String,
+        persistent: bool,
+        avm_object: Option<AvmObject<'gc>>,
+    ) -> Self {
+        Self(GcCell::allocate(
+            gc_context,
+            RemoteSharedObjectData {
+                avm_object,
+                name,
+                uri,
+                persistent,
+                connection: None,
+                in_use: false,
+                version: 0,
+                properties: Vec::new(),
+                update_rate: UpdateRate::EveryTick,
+                time_since_update: 0.0,
+            },
+        ))
+    }
+
+    pub fn name(self) -> String {
+        self.0.read().name.clone()
+    }
+
+    /// The connection that the shared object is synced over, if any.
+    pub fn connection(self) -> Option<NetConnection<'gc>> {
+        self.0.read().connection
+    }
+
+    /// Start syncing the shared object over a connection, leaving any
+    /// previous connection.
+    ///
+    /// Returns `false` if the connection is not connected to an RTMP server,
+    /// or to a different application than the one the shared object belongs
+    /// to.
+    pub fn connect(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        connection: NetConnection<'gc>,
+    ) -> bool {
+        let same_uri = connection.uri().map_or(false, |uri| {
+            uri.trim_end_matches('/') == self.0.read().uri.trim_end_matches('/')
+        });
+        if !same_uri {
+            return false;
+        }
+        self.close(context);
+
+        let message = self.message(vec![SharedObjectEvent::Use]);
+        if !connection.send_shared_object_message(context, message) {
+            return false;
+        }
+        connection.add_shared_object(context.gc_context, self);
+        self.0.write(context.gc_context).connection = Some(connection);
+        true
+    }
+
+    /// Stop syncing the shared object, and tell the server that we are no
+    /// longer using it.
+    pub fn close(self, context: &mut UpdateContext<'_, 'gc>) {
+        let Some(connection) = self.0.read().connection else {
+            return;
+        };
+        let message = self.message(vec![SharedObjectEvent::Release]);
+        connection.send_shared_object_message(context, message);
+        connection.remove_shared_object(context.gc_context, self);
+        self.disconnect(context.gc_context);
+    }
+
+    /// Forget the connection, which has been closed or has stopped using
+    /// this shared object.
+    pub(crate) fn disconnect(self, gc_context: MutationContext<'gc, '_>) {
+        let mut write = self.0.write(gc_context);
+        write.connection = None;
+        write.in_use = false;
+        write.version = 0;
+        write.properties.clear();
+    }
+
+    /// Call a method on this shared object in every client that is using
+    /// it, including this one. The first argument is the name of the method.
+    pub fn send(self, context: &mut UpdateContext<'_, 'gc>, arguments: Vec<AmfValue>) {
+        let Some(connection) = self.0.read().connection else {
+            return;
+        };
+        let message = self.message(vec![SharedObjectEvent::SendMessage(arguments)]);
+        connection.send_shared_object_message(context, message);
+    }
+
+    /// Set how many times per second changes to `data` are sent to the
+    /// server.
+    ///
+    /// A rate of 0 sends any changes right away and then stops sending them,
+    /// and a negative rate goes back to sending them every tick.
+    pub fn set_fps(self, context: &mut UpdateContext<'_, 'gc>, fps: f64) {
+        let update_rate = if fps > 0.0 {
+            UpdateRate::Interval(1000.0 / fps)
+        } else if fps == 0.0 {
+            self.send_changes(context);
+            UpdateRate::Paused
+        } else {
+            UpdateRate::EveryTick
+        };
+        let mut write = self.0.write(context.gc_context);
+        write.update_rate = update_rate;
+        write.time_since_update = 0.0;
+    }
+
+    /// Make the next update resend a property, even if its value looks the
+    /// same as the one last agreed with the server.
+    ///
+    /// This is needed when a property holds an object whose contents have
+    /// changed, as only the object itself is compared in AVM2.
+    pub fn set_dirty(self, gc_context: MutationContext<'gc, '_>, name: &str) {
+        self.0
+            .write(gc_context)
+            .properties
+            .retain(|(property, _)| property != name);
+    }
+
+    /// Send changes to `data` if it is time to.
+    ///
+    /// The `dt` parameter indicates how many milliseconds have elapsed since
+    /// the last tick.
+    pub(crate) fn tick(self, context: &mut UpdateContext<'_, 'gc>, dt: f64) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.in_use {
+            return;
+        }
+        match write.update_rate {
+            UpdateRate::EveryTick => {}
+            UpdateRate::Interval(interval) => {
+                write.time_since_update += dt;
+                if write.time_since_update < interval {
+                    return;
+                }
+                write.time_since_update %= interval;
+            }
+            UpdateRate::Paused => return,
+        }
+        drop(write);
+
+        self.send_changes(context);
+    }
+
+    /// Compare `data` to the values last agreed with the server, and ask the
+    /// server to make any changes.
+    ///
+    /// The new values are taken to be agreed, so they are only sent once.
+    fn send_changes(self, context: &mut UpdateContext<'_, 'gc>) {
+        let (connection, avm_object) = {
+            let read = self.0.read();
+            if !read.in_use {
+                return;
+            }
+            match (read.connection, read.avm_object) {
+                (Some(connection), Some(avm_object)) => (connection, avm_object),
+                _ => return,
+            }
+        };
+
+        let current = read_data(context, avm_object, connection.object_encoding());
+        let mut write = self.0.write(context.gc_context);
+        let mut events = Vec::new();
+        for (name, value) in &current {
+            let old_value = write.properties.iter().find(|(n, _)| n == name);
+            if old_value.map(|(_, v)| v) != Some(value) {
+                events.push(SharedObjectEvent::RequestChange {
+                    name: name.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+        for (name, _) in &write.properties {
+            if !current.iter().any(|(n, _)| n == name) {
+                events.push(SharedObjectEvent::RequestRemove { name: name.clone() });
+            }
+        }
+        write.properties = current;
+        drop(write);
+
+        if !events.is_empty() {
+            let message = self.message(events);
+            connection.send_shared_object_message(context, message);
+        }
+    }
+
+    /// Build a message about this shared object to send to the server.
+    fn message(self, events: Vec<SharedObjectEvent>) -> SharedObjectMessage {
+        let read = self.0.read();
+        SharedObjectMessage {
+            name: read.name.clone(),
+            version: read.version,
+            persistent: read.persistent,
+            events,
+        }
+    }
+
+    /// Handle a message from the server about this shared object.
+    pub(crate) fn handle_message(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        message: SharedObjectMessage,
+    ) {
+        let mut changes = Vec::new();
+        let mut updates = Vec::new();
+        let mut calls = Vec::new();
+        let mut statuses = Vec::new();
+
+        let mut write = self.0.write(context.gc_context);
+        write.version = message.version;
+        for event in message.events {
+            match event {
+                SharedObjectEvent::UseSuccess => write.in_use = true,
+                SharedObjectEvent::Clear => {
+                    write.properties.clear();
+                    updates.push(DataUpdate::Clear);
+                    changes.push(SyncChange {
+                        code: "clear",
+                        name: None,
+                        old_value: None,
+                    });
+                }
+                SharedObjectEvent::Change { name, value } => {
+                    let old_value = match write.properties.iter_mut().find(|(n, _)| *n == name) {
+                        Some((_, old_value)) => Some(std::mem::replace(old_value, value.clone())),
+                        None => {
+                            write.properties.push((name.clone(), value.clone()));
+                            None
+                        }
+                    };
+                    updates.push(DataUpdate::Set(name.clone(), value));
+                    changes.push(SyncChange {
+                        code: "change",
+                        name: Some(name),
+                        old_value,
+                    });
+                }
+                SharedObjectEvent::Success { name } => changes.push(SyncChange {
+                    code: "success",
+                    name: Some(name),
+                    old_value: None,
+                }),
+                SharedObjectEvent::Remove { name } => {
+                    let index = write.properties.iter().position(|(n, _)| *n == name);
+                    let old_value = index.map(|index| write.properties.remove(index).1);
+                    updates.push(DataUpdate::Delete(name.clone()));
+                    changes.push(SyncChange {
+                        code: "delete",
+                        name: Some(name),
+                        old_value,
+                    });
+                }
+                SharedObjectEvent::SendMessage(arguments) => calls.push(arguments),
+                SharedObjectEvent::Status { code, level } => statuses.push((code, level)),
+                SharedObjectEvent::Use
+                | SharedObjectEvent::Release
+                | SharedObjectEvent::RequestChange { .. }
+                | SharedObjectEvent::RequestRemove { .. } => {
+                    tracing::warn!("SharedObject: unexpected {event:?} from server");
+                }
+            }
+        }
+        let avm_object = write.avm_object;
+        drop(write);
+
+        let Some(avm_object) = avm_object else {
+            return;
+        };
+        if !updates.is_empty() {
+            update_data(context, avm_object, updates);
+        }
+        if !changes.is_empty() {
+            trigger_sync_event(context, avm_object, &changes);
+        }
+        for arguments in calls {
+            call_handler(context, avm_object, arguments);
+        }
+        for (code, level) in statuses {
+            trigger_status_event(context, avm_object, &code, &level);
+        }
+    }
+}
+
+/// Read the properties of the `data` object of a shared object.
+fn read_data<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    avm_object: AvmObject<'gc>,
+    object_encoding: AMFVersion,
+) -> Vec<(String, AmfValue)> {
+    let mut elements = Vec::new();
+    match avm_object {
+        AvmObject::Avm2(object) => {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let result = (|| {
+                let data = object
+                    .get_public_property("data", &mut activation)?
+                    .coerce_to_object(&mut activation)?;
+                crate::avm2::amf::recursive_serialize(
+                    &mut activation,
+                    data,
+                    &mut elements,
+                    object_encoding,
+                )
+            })();
+            if let Err(e) = result {
+                tracing::error!(
+                    "Unhandled AVM2 exception while reading SharedObject data: {}",
+                    e.detailed_message(&mut activation)
+                );
+            }
+        }
+        AvmObject::Avm1(object) => {
+            let mut activation = Avm1Activation::from_stub(
+                context.reborrow(),
+                Avm1ActivationIdentifier::root("[SharedObject Sync]"),
+            );
+            if let Ok(data) = object.get("data", &mut activation) {
+                let data = data.coerce_to_object(&mut activation);
+                avm1_recursive_serialize(&mut activation, data, &mut elements);
+            }
+        }
+    }
+
+    elements
+        .iter()
+        .map(|element| {
+            let value: &AmfValue = element.value();
+            let value = match object_encoding {
+                // AMF3 values are sent wrapped in an AMF0 "switch to AMF3"
+                // marker, as Flash Player does.
+                AMFVersion::AMF3 => AmfValue::AMF3(Rc::new(value.clone())),
+                AMFVersion::AMF0 => value.clone(),
+            };
+            (element.name.clone(), value)
+        })
+        .collect()
+}
+
+/// Apply changes from the server to the `data` object of a shared object.
+fn update_data<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    avm_object: AvmObject<'gc>,
+    updates: Vec<DataUpdate>,
+) {
+    match avm_object {
+        AvmObject::Avm2(mut object) => {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let result = (|| -> Result<(), Avm2Error<'gc>> {
+                for update in &updates {
+                    let data = object
+                        .get_public_property("data", &mut activation)?
+                        .coerce_to_object(&mut activation)?;
+                    match update {
+                        DataUpdate::Clear => {
+                            let data = activation
+                                .avm2()
+                                .classes()
+                                .object
+                                .construct(&mut activation, &[])?;
+                            object.set_public_property("data", data.into(), &mut activation)?;
+                        }
+                        DataUpdate::Set(name, value) => {
+                            let name = AvmString::new_utf8(activation.context.gc_context, name);
+                            let value =
+                                crate::avm2::amf::deserialize_value(&mut activation, value)?;
+                            data.set_public_property(name, value, &mut activation)?;
+                        }
+                        DataUpdate::Delete(name) => {
+                            let name = AvmString::new_utf8(activation.context.gc_context, name);
+                            data.delete_public_property(&mut activation, name)?;
+                        }
+                    }
+                }
+                Ok(())
+            })();
+            if let Err(e) = result {
+                tracing::error!(
+                    "Unhandled AVM2 exception while updating SharedObject data: {}",
+                    e.detailed_message(&mut activation)
+                );
+            }
+        }
+        AvmObject::Avm1(object) => {
+            let mut activation = Avm1Activation::from_stub(
+                context.reborrow(),
+                Avm1ActivationIdentifier::root("[SharedObject Sync]"),
+            );
+            let Ok(data) = object.get("data", &mut activation) else {
+                return;
+            };
+            let data = data.coerce_to_object(&mut activation);
+            for update in &updates {
+                match update {
+                    DataUpdate::Clear => {
+                        for key in data.get_keys(&mut activation) {
+                            data.delete(&mut activation, key);
+                        }
+                    }
+                    DataUpdate::Set(name, value) => {
+                        let name = AvmString::new_utf8(activation.context.gc_context, name);
+                        let value = avm1_deserialize_value(&mut activation, value);
+                        let _ = data.set(name, value, &mut activation);
+                    }
+                    DataUpdate::Delete(name) => {
+                        let name = AvmString::new_utf8(activation.context.gc_context, name);
+                        data.delete(&mut activation, name);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Send a `SyncEvent` (AVM2) or call `onSync` (AVM1) with a change list.
+fn trigger_sync_event<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    avm_object: AvmObject<'gc>,
+    changes: &[SyncChange],
+) {
+    match avm_object {
+        AvmObject::Avm2(object) => {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let result = (|| -> Result<(), Avm2Error<'gc>> {
+                let mut change_list = Vec::with_capacity(changes.len());
+                for change in changes {
+                    let mut entry = activation
+                        .avm2()
+                        .classes()
+                        .object
+                        .construct(&mut activation, &[])?;
+                    entry.set_public_property("code", change.code.into(), &mut activation)?;
+                    if let Some(name) = &change.name {
+                        let name = AvmString::new_utf8(activation.context.gc_context, name);
+                        entry.set_public_property("name", name.into(), &mut activation)?;
+                    }
+                    if let Some(old_value) = &change.old_value {
+                        let old_value =
+                            crate::avm2::amf::deserialize_value(&mut activation, old_value)?;
+                        entry.set_public_property("oldValue", old_value, &mut activation)?;
+                    }
+                    change_list.push(entry.into());
+                }
+                let change_list = Avm2ArrayObject::from_storage(
+                    &mut activation,
+                    Avm2ArrayStorage::from_args(&change_list),
+                )?;
+
+                let event = activation.avm2().classes().syncevent.construct(
+                    &mut activation,
+                    &[
+                        "sync".into(),
+                        false.into(),
+                        false.into(),
+                        change_list.into(),
+                    ],
+                )?;
+                dispatch_event(&mut activation, object, event)?;
+                Ok(())
+            })();
+            if let Err(e) = result {
+                tracing::error!(
+                    "Unhandled AVM2 exception in sync event handler: {}",
+                    e.detailed_message(&mut activation)
+                );
+            }
+        }
+        AvmObject::Avm1(object) => {
+            let mut activation = Avm1Activation::from_stub(
+                context.reborrow(),
+                Avm1ActivationIdentifier::root("[SharedObject Sync]"),
+            );
+            let mut change_list = Vec::with_capacity(changes.len());
+            for change in changes {
+                let entry = Avm1ScriptObject::new(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().object),
+                );
+                entry.define_value(
+                    activation.context.gc_context,
+                    "code",
+                    change.code.into(),
+                    Avm1Attribute::empty(),
+                );
+                if let Some(name) = &change.name {
+                    let name = AvmString::new_utf8(activation.context.gc_context, name);
+                    entry.define_value(
+                        activation.context.gc_context,
+                        "name",
+                        name.into(),
+                        Avm1Attribute::empty(),
+                    );
+                }
+                if let Some(old_value) = &change.old_value {
+                    let old_value = avm1_deserialize_value(&mut activation, old_value);
+                    entry.define_value(
+                        activation.context.gc_context,
+                        "oldValue",
+                        old_value,
+                        Avm1Attribute::empty(),
+                    );
+                }
+                change_list.push(entry.into());
+            }
+            let change_list = Avm1ArrayObject::new(
+                activation.context.gc_context,
+                activation.context.avm1.prototypes().array,
+                change_list,
+            );
+
+            let _ = object.call_method(
+                "onSync".into(),
+                &[change_list.into()],
+                &mut activation,
+                Avm1ExecutionReason::Special,
+            );
+        }
+    }
+}
+
+/// Call a method that a client invoked with `send`.
+///
+/// In AVM2, the method is called on the shared object's `client`, and in
+/// AVM1 on the shared object itself.
+fn call_handler<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    avm_object: AvmObject<'gc>,
+    arguments: Vec<AmfValue>,
+) {
+    let mut arguments = arguments.iter();
+    let Some(AmfValue::String(name)) = arguments.next() else {
+        return;
+    };
+
+    match avm_object {
+        AvmObject::Avm2(object) => {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let result = arguments
+                .map(|argument| crate::avm2::amf::deserialize_value(&mut activation, argument))
+                .collect::<Result<Vec<_>, _>>()
+                .and_then(|arguments| {
+                    call_avm2_client(
+                        &mut activation,
+                        object,
+                        "flash.net.SharedObject",
+                        name,
+                        &arguments,
+                    )
+                });
+            if let Err(e) = result {
+                tracing::error!(
+                    "Unhandled AVM2 exception in SharedObject {} handler: {}",
+                    name,
+                    e.detailed_message(&mut activation)
+                );
+            }
+        }
+        AvmObject::Avm1(object) => {
+            let mut activation = Avm1Activation::from_stub(
+                context.reborrow(),
+                Avm1ActivationIdentifier::root("[SharedObject Send]"),
+            );
+            let arguments: Vec<Avm1Value<'gc>> = arguments
+                .map(|argument| avm1_deserialize_value(&mut activation, argument))
+                .collect();
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let _ = object.call_method(
+                name,
+                &arguments,
+                &mut activation,
+                Avm1ExecutionReason::Special,
+            );
+        }
+    }
+}
+
+/// Send a `NetStatusEvent` (AVM2) or call `onStatus` (AVM1) with a status
+/// reported by the server.
+fn trigger_status_event<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    avm_object: AvmObject<'gc>,
+    code: &str,
+    level: &str,
+) {
+    match avm_object {
+        AvmObject::Avm2(object) => {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let code = AvmString::new_utf8(activation.context.gc_context, code);
+            let level = AvmString::new_utf8(activation.context.gc_context, level);
+            let result = net_status_info(&mut activation, code, level)
+                .and_then(|info| dispatch_net_status_event(&mut activation, object, info));
+            if let Err(e) = result {
+                tracing::error!(
+                    "Unhandled AVM2 exception in netStatus event handler: {}",
+                    e.detailed_message(&mut activation)
+                );
+            }
+        }
+        AvmObject::Avm1(object) => {
+            let mut activation = Avm1Activation::from_stub(
+                context.reborrow(),
+                Avm1ActivationIdentifier::root("[SharedObject Status Event]"),
+            );
+            let info = Avm1ScriptObject::new(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            let code = AvmString::new_utf8(activation.context.gc_context, code);
+            let level = AvmString::new_utf8(activation.context.gc_context, level);
+            info.define_value(
+                activation.context.gc_context,
+                "code",
+                code.into(),
+                Avm1Attribute::empty(),
+            );
+            info.define_value(
+                activation.context.gc_context,
+                "level",
+                level.into(),
+                Avm1Attribute::empty(),
+            );
+            call_avm1_on_status(&mut activation, object, info.into());
+        }
+    }
+}
@@ -0,0 +1,693 @@
+//! Security sandbox
+//!
+//! Flash Player keeps content from different domains apart: a movie may only
+//! script another movie, read the pixels of an image, or open a socket to a
+//! server, if the other domain has given it permission. Permission is given
+//! by movies through `Security.allowDomain`, and by servers through policy
+//! files, which are `crossdomain.xml` files for HTTP servers and socket
+//! policy files for socket servers.
+//!
+//! This is opt-in, as most content is played far away from the domain it was
+//! made for, where the checks would only get in the way.
+
+use crate::avm1::globals::system::SandboxType;
+use crate::backend::navigator::{NavigatorBackend, OwnedFuture, Request};
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::loader::Error;
+use crate::player::Player;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, Weak};
+use url::{Origin, Url};
+
+/// The port that socket servers serve their master policy file on.
+pub const MASTER_SOCKET_POLICY_PORT: u16 = 843;
+
+/// What a socket sends to ask a server for its policy file.
+pub const SOCKET_POLICY_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
+/// Socket policy files served from this port or above may only allow
+/// connections to ports in the same range.
+const FIRST_UNPRIVILEGED_PORT: u16 = 1024;
+
+/// The domain that content belongs to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SecurityDomain {
+    /// Content on a server, which is identified by its scheme, host and port.
+    Remote(Origin),
+
+    /// Local files, which all belong to the same domain.
+    Local,
+}
+
+impl SecurityDomain {
+    pub fn from_url(url: &str) -> Self {
+        match Url::parse(url).map(|url| url.origin()) {
+            Ok(origin) if origin.is_tuple() => Self::Remote(origin),
+            _ => Self::Local,
+        }
+    }
+
+    fn host(&self) -> Option<String> {
+        match self {
+            Self::Remote(Origin::Tuple(_, host, _)) => Some(host.to_string()),
+            _ => None,
+        }
+    }
+
+    fn is_secure(&self) -> bool {
+        matches!(self, Self::Remote(Origin::Tuple(scheme, _, _)) if scheme == "https")
+    }
+}
+
+/// Whether a domain pattern from a policy file or `Security.allowDomain`
+/// matches a host.
+///
+/// The pattern may be `*` to match any host, or start with `*.` to match a
+/// domain and all of its subdomains.
+fn domain_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+    if pattern == "*" {
+        true
+    } else if let Some(suffix) = pattern.strip_prefix("*.") {
+        host == suffix || host.ends_with(&format!(".{suffix}"))
+    } else {
+        pattern == host
+    }
+}
+
+/// A domain that was passed to `Security.allowDomain` or
+/// `Security.allowInsecureDomain`.
+#[derive(Clone, Debug)]
+struct AllowedDomain {
+    pattern: String,
+
+    /// Whether movies loaded over HTTP may access a movie loaded over HTTPS.
+    insecure: bool,
+}
+
+/// An `<allow-access-from>` rule of a policy file.
+#[derive(Clone, Debug, PartialEq)]
+struct AllowAccessFrom {
+    domain: String,
+
+    /// The ports that sockets may connect to. Only socket policy files use
+    /// this.
+    to_ports: Vec<(u16, u16)>,
+
+    /// Whether only movies loaded over HTTPS are allowed, if the policy file
+    /// itself was loaded over HTTPS.
+    secure: bool,
+}
+
+/// A `crossdomain.xml` file, or a socket policy file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PolicyFile {
+    rules: Vec<AllowAccessFrom>,
+}
+
+impl PolicyFile {
+    /// Parse a policy file, returning `None` if it is not one.
+    ///
+    /// A policy that disables itself through `<site-control>` has no rules.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(data);
+        let mut buf = Vec::new();
+        let mut depth = 0;
+        let mut is_policy = false;
+        let mut rules = Vec::new();
+        let mut disabled = false;
+
+        loop {
+            buf.clear();
+            let (element, is_empty) = match reader.read_event(&mut buf).ok()? {
+                Event::Start(element) => (element, false),
+                Event::Empty(element) => (element, true),
+                Event::End(_) => {
+                    depth -= 1;
+                    continue;
+                }
+                Event::Eof => break,
+                _ => continue,
+            };
+
+            if depth == 0 {
+                is_policy = element.name() == b"cross-domain-policy";
+            } else if depth == 1 && is_policy {
+                match element.name() {
+                    b"allow-access-from" => {
+                        if let Some(domain) = attribute(&element, b"domain") {
+                            let to_ports = attribute(&element, b"to-ports");
+                            let secure = attribute(&element, b"secure");
+                            rules.push(AllowAccessFrom {
+                                domain,
+                                to_ports: to_ports
+                                    .map_or_else(Vec::new, |ports| parse_ports(&ports)),
+                                secure: secure.as_deref() != Some("false"),
+                            });
+                        }
+                    }
+                    b"site-control" => {
+                        disabled = attribute(&element, b"permitted-cross-domain-policies")
+                            .as_deref()
+                            == Some("none");
+                    }
+                    _ => {}
+                }
+            }
+
+            if !is_empty {
+                depth += 1;
+            }
+        }
+
+        if !is_policy || depth != 0 {
+            return None;
+        }
+        if disabled {
+            rules.clear();
+        }
+        Some(Self { rules })
+    }
+
+    /// Whether this policy allows content from `requester` to access the
+    /// server that it came from.
+    ///
+    /// `port` is the port that a socket wants to connect to, or `None` for
+    /// HTTP requests. `secure` is whether the policy was loaded over HTTPS.
+    fn allows(&self, requester: &SecurityDomain, port: Option<u16>, secure: bool) -> bool {
+        self.rules.iter().any(|rule| {
+            let domain_allowed = match requester.host() {
+                Some(host) => domain_matches(&rule.domain, &host),
+                None => rule.domain == "*",
+            };
+            let scheme_allowed = !secure || !rule.secure || requester.is_secure();
+            let port_allowed = port.map_or(true, |port| {
+                rule.to_ports
+                    .iter()
+                    .any(|(start, end)| (*start..=*end).contains(&port))
+            });
+            domain_allowed && scheme_allowed && port_allowed
+        })
+    }
+}
+
+/// Whether the `crossdomain.xml` file at `location` governs `url`, which is
+/// the case for everything in the directory of the policy file and below.
+fn policy_covers(location: &Url, url: &Url) -> bool {
+    let path = location.path();
+    let directory = &path[..path.rfind('/').map_or(0, |end| end + 1)];
+    location.origin() == url.origin() && url.path().starts_with(directory)
+}
+
+fn attribute(element: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .with_checks(false)
+        .flatten()
+        .find(|attribute| attribute.key == name)
+        .and_then(|attribute| {
+            attribute
+                .unescaped_value()
+                .ok()
+                .map(|value| String::from_utf8_lossy(&value).into_owned())
+        })
+}
+
+/// Parse the `to-ports` attribute of a socket policy file, which is a comma
+/// separated list of ports and port ranges, or `*` for all ports.
+fn parse_ports(ports: &str) -> Vec<(u16, u16)> {
+    ports
+        .split(',')
+        .filter_map(|ports| {
+            let ports = ports.trim();
+            if ports == "*" {
+                return Some((1, u16::MAX));
+            }
+            match ports.split_once('-') {
+                Some((start, end)) => Some((start.trim().parse().ok()?, end.trim().parse().ok()?)),
+                None => ports.parse().ok().map(|port| (port, port)),
+            }
+        })
+        .collect()
+}
+
+/// Whether a movie may open a socket to a server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SocketPermission {
+    Allowed,
+
+    /// The server has to be asked for a policy file first.
+    NeedsPolicy,
+
+    /// Movies in the local-with-filesystem sandbox may not use sockets.
+    Denied,
+}
+
+/// Tracks which domains have given which movies access to their content.
+pub struct SecurityManager {
+    /// Whether the sandbox is enforced at all.
+    enabled: bool,
+
+    /// The domains that each movie has allowed to script it, by the URL of
+    /// the movie.
+    allowed_domains: HashMap<String, Vec<AllowedDomain>>,
+
+    /// The `crossdomain.xml` files that have been loaded, by their URL.
+    url_policies: HashMap<Url, PolicyFile>,
+
+    /// The URLs of policy files that have been requested, so that they are
+    /// only loaded once.
+    requested_policies: HashSet<String>,
+
+    /// The ports that `Security.loadPolicyFile` told us to look for socket
+    /// policy files on, by host.
+    socket_policy_ports: HashMap<String, Vec<u16>>,
+
+    /// The socket policy files that have been received, by the host and
+    /// port that served them.
+    socket_policies: HashMap<(String, u16), PolicyFile>,
+}
+
+impl SecurityManager {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            allowed_domains: HashMap::new(),
+            url_policies: HashMap::new(),
+            requested_policies: HashSet::new(),
+            socket_policy_ports: HashMap::new(),
+            socket_policies: HashMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether content is checked at all for a movie in the given sandbox.
+    ///
+    /// Local movies that the user trusts may access anything.
+    fn is_enforced(&self, sandbox_type: &SandboxType) -> bool {
+        self.enabled && !matches!(sandbox_type, SandboxType::LocalTrusted)
+    }
+
+    /// Implements `Security.allowDomain` and `Security.allowInsecureDomain`,
+    /// letting movies from `domains` script the movie at `movie_url`.
+    ///
+    /// Each domain may be a host name, a URL, or `*` for any domain.
+    pub fn allow_domains(&mut self, movie_url: &str, domains: &[String], insecure: bool) {
+        let allowed = self
+            .allowed_domains
+            .entry(movie_url.to_string())
+            .or_default();
+        for domain in domains {
+            let pattern = Url::parse(domain)
+                .ok()
+                .and_then(|url| url.host_str().map(|host| host.to_string()))
+                .unwrap_or_else(|| domain.clone());
+            allowed.push(AllowedDomain { pattern, insecure });
+        }
+    }
+
+    /// Whether the movie at `target_url` has let movies from the domain of
+    /// `accessor_url` script it.
+    fn movie_allows(&self, target_url: &str, accessor_url: &str) -> bool {
+        let accessor = SecurityDomain::from_url(accessor_url);
+        let target = SecurityDomain::from_url(target_url);
+        if accessor == target {
+            return true;
+        }
+
+        let Some(allowed) = self.allowed_domains.get(target_url) else {
+            return false;
+        };
+        allowed.iter().any(|allowed| {
+            let domain_allowed = match accessor.host() {
+                Some(host) => domain_matches(&allowed.pattern, &host),
+                None => allowed.pattern == "*",
+            };
+            domain_allowed && (allowed.insecure || !target.is_secure() || accessor.is_secure())
+        })
+    }
+
+    /// Whether the movie at `accessor_url` may script the movie at
+    /// `target_url`, such as through `Loader.content`.
+    pub fn can_script(
+        &self,
+        sandbox_type: &SandboxType,
+        accessor_url: &str,
+        target_url: &str,
+    ) -> bool {
+        !self.is_enforced(sandbox_type) || self.movie_allows(target_url, accessor_url)
+    }
+
+    /// Whether the movie at `accessor_url` may read the contents of the
+    /// movie or image at `target_url`, such as through `BitmapData.draw`.
+    ///
+    /// Besides scripting permission, this is allowed by a policy file on the
+    /// server of the content.
+    pub fn can_read(
+        &self,
+        sandbox_type: &SandboxType,
+        accessor_url: &str,
+        target_url: &str,
+    ) -> bool {
+        if self.can_script(sandbox_type, accessor_url, target_url) {
+            return true;
+        }
+
+        let Ok(target) = Url::parse(target_url) else {
+            return false;
+        };
+        let accessor = SecurityDomain::from_url(accessor_url);
+        self.url_policies.iter().any(|(location, policy)| {
+            policy_covers(location, &target)
+                && policy.allows(&accessor, None, location.scheme() == "https")
+        })
+    }
+
+    /// Find loaded content under `object` that the movie at `accessor_url`
+    /// may not read, such as when drawing it with `BitmapData.draw`.
+    ///
+    /// Returns the URL of the first such content.
+    pub fn find_unreadable_content<'gc>(
+        &self,
+        sandbox_type: &SandboxType,
+        accessor_url: &str,
+        object: DisplayObject<'gc>,
+    ) -> Option<String> {
+        if !self.is_enforced(sandbox_type) {
+            return None;
+        }
+
+        // Only content loaded by another movie can be from another domain.
+        let movie = object.movie();
+        if movie.loader_url().is_some() && !self.can_read(sandbox_type, accessor_url, movie.url()) {
+            return Some(movie.url().to_string());
+        }
+
+        object
+            .as_container()?
+            .iter_render_list()
+            .find_map(|child| self.find_unreadable_content(sandbox_type, accessor_url, child))
+    }
+
+    /// Implements `Security.loadPolicyFile`.
+    ///
+    /// Policy files on HTTP servers are loaded right away. For socket
+    /// servers, which use `xmlsocket://host:port` URLs, the port is
+    /// remembered, and asked for a policy file when a socket connects.
+    pub fn load_policy_file(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        navigator: &mut dyn NavigatorBackend,
+        url: &str,
+    ) {
+        let Ok(parsed) = Url::parse(url) else {
+            tracing::warn!("Security.loadPolicyFile: invalid URL {url}");
+            return;
+        };
+
+        if parsed.scheme() == "xmlsocket" {
+            if let (Some(host), Some(port)) = (parsed.host_str(), parsed.port()) {
+                let ports = self
+                    .socket_policy_ports
+                    .entry(host.to_ascii_lowercase())
+                    .or_default();
+                if !ports.contains(&port) {
+                    ports.push(port);
+                }
+            }
+        } else if self.enabled && self.requested_policies.insert(parsed.to_string()) {
+            let future = fetch_policy(player, navigator, parsed);
+            navigator.spawn_future(future);
+        }
+    }
+
+    /// Load the default policy file of the server that `content_url` is on,
+    /// as when content is loaded with `LoaderContext.checkPolicyFile`.
+    ///
+    /// Returns the future that loads the policy file, if it has not been
+    /// loaded yet. The content should only finish loading once it completes,
+    /// so that the policy file applies to it right away.
+    pub fn check_policy_file(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        navigator: &mut dyn NavigatorBackend,
+        content_url: &str,
+    ) -> Option<OwnedFuture<(), Error>> {
+        if !self.enabled {
+            return None;
+        }
+
+        let url = Url::parse(content_url).ok()?;
+        if !url.origin().is_tuple() {
+            return None;
+        }
+        let policy_url = url.join("/crossdomain.xml").ok()?;
+        if self.url_policies.contains_key(&policy_url) {
+            return None;
+        }
+
+        self.requested_policies.insert(policy_url.to_string());
+        Some(fetch_policy(player, navigator, policy_url))
+    }
+
+    /// Store a `crossdomain.xml` file that has finished loading.
+    fn add_url_policy(&mut self, location: Url, policy: PolicyFile) {
+        self.url_policies.insert(location, policy);
+    }
+
+    /// Whether the movie at `movie_url` may open a socket to a server.
+    pub fn socket_permission(
+        &self,
+        sandbox_type: &SandboxType,
+        movie_url: &str,
+        host: &str,
+        port: u16,
+    ) -> SocketPermission {
+        if !self.is_enforced(sandbox_type) {
+            SocketPermission::Allowed
+        } else if matches!(sandbox_type, SandboxType::LocalWithFile) {
+            SocketPermission::Denied
+        } else if self.socket_policy_allows(movie_url, host, port) {
+            SocketPermission::Allowed
+        } else {
+            SocketPermission::NeedsPolicy
+        }
+    }
+
+    /// Whether a socket policy file that has been received from `host` lets
+    /// the movie at `movie_url` connect to `port`.
+    pub fn socket_policy_allows(&self, movie_url: &str, host: &str, port: u16) -> bool {
+        let requester = SecurityDomain::from_url(movie_url);
+        let host = host.to_ascii_lowercase();
+        self.socket_policies
+            .iter()
+            .any(|((policy_host, policy_port), policy)| {
+                *policy_host == host
+                    && (*policy_port < FIRST_UNPRIVILEGED_PORT || port >= FIRST_UNPRIVILEGED_PORT)
+                    && policy.allows(&requester, Some(port), false)
+            })
+    }
+
+    /// The ports to ask for a socket policy file, in order, before
+    /// connecting to `port` on `host`.
+    ///
+    /// These are the ports given to `Security.loadPolicyFile`, then the
+    /// master policy port, and finally the port itself.
+    pub fn socket_policy_ports(&self, host: &str, port: u16) -> Vec<u16> {
+        let mut ports = self
+            .socket_policy_ports
+            .get(&host.to_ascii_lowercase())
+            .cloned()
+            .unwrap_or_default();
+        for port in [MASTER_SOCKET_POLICY_PORT, port] {
+            if !ports.contains(&port) {
+                ports.push(port);
+            }
+        }
+        ports
+    }
+
+    /// Store the socket policy file that was served from `port` on `host`,
+    /// replacing any earlier one from there.
+    pub fn add_socket_policy(&mut self, host: &str, port: u16, policy: PolicyFile) {
+        self.socket_policies
+            .insert((host.to_ascii_lowercase(), port), policy);
+    }
+}
+
+/// Load a `crossdomain.xml` file, and store it in the security manager.
+fn fetch_policy(
+    player: Weak<Mutex<Player>>,
+    navigator: &mut dyn NavigatorBackend,
+    location: Url,
+) -> OwnedFuture<(), Error> {
+    let fetch = navigator.fetch(Request::get(location.to_string()));
+    Box::pin(async move {
+        let policy = match fetch.await {
+            Ok(response) => {
+                let policy = PolicyFile::parse(&response.body);
+                if policy.is_none() {
+                    tracing::warn!("Invalid policy file at {}", response.url);
+                }
+                policy
+            }
+            Err(e) => {
+                tracing::warn!("Couldn't load policy file: {e}");
+                None
+            }
+        };
+
+        if let (Some(policy), Some(player)) = (policy, player.upgrade()) {
+            player
+                .lock()
+                .unwrap()
+                .update(|uc| uc.security.add_url_policy(location, policy));
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_policy_file() {
+        let policy = PolicyFile::parse(
+            br#"<?xml version="1.0"?>
+            <!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+            <cross-domain-policy>
+                <site-control permitted-cross-domain-policies="master-only"/>
+                <allow-access-from domain="*.example.com" to-ports="843,5000-5010"/>
+                <allow-access-from domain="ruffle.rs" secure="false"/>
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+
+        let example = SecurityDomain::from_url("http://www.example.com/movie.swf");
+        let secure_example = SecurityDomain::from_url("https://example.com/movie.swf");
+        let other = SecurityDomain::from_url("http://example.org/movie.swf");
+        let ruffle = SecurityDomain::from_url("http://ruffle.rs/movie.swf");
+
+        assert!(policy.allows(&example, None, false));
+        assert!(policy.allows(&example, Some(5005), false));
+        assert!(!policy.allows(&example, Some(5011), false));
+        assert!(!policy.allows(&example, None, true));
+        assert!(policy.allows(&secure_example, None, true));
+        assert!(!policy.allows(&other, None, false));
+        assert!(policy.allows(&ruffle, None, true));
+        assert!(!policy.allows(&SecurityDomain::Local, None, false));
+    }
+
+    #[test]
+    fn parse_disabled_policy_file() {
+        let policy = PolicyFile::parse(
+            br#"<cross-domain-policy>
+                <site-control permitted-cross-domain-policies="none"/>
+                <allow-access-from domain="*"/>
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+        assert!(!policy.allows(&SecurityDomain::Local, None, false));
+    }
+
+    #[test]
+    fn parse_invalid_policy_file() {
+        assert_eq!(
+            PolicyFile::parse(b"<html><body>Not found</body></html>"),
+            None
+        );
+        assert_eq!(PolicyFile::parse(b"<cross-domain-policy>"), None);
+    }
+
+    #[test]
+    fn allow_domain() {
+        let mut security = SecurityManager::new(true);
+        let sandbox = SandboxType::Remote;
+        let child = "https://cdn.example.com/child.swf";
+        let parent = "http://www.example.com/parent.swf";
+
+        assert!(!security.can_script(&sandbox, parent, child));
+        assert!(security.can_script(&SandboxType::LocalTrusted, parent, child));
+
+        security.allow_domains(child, &["*.example.com".to_string()], false);
+        assert!(!security.can_script(&sandbox, parent, child));
+
+        security.allow_domains(child, &["http://www.example.com/".to_string()], true);
+        assert!(security.can_script(&sandbox, parent, child));
+        assert!(!security.can_script(&sandbox, child, parent));
+    }
+
+    #[test]
+    fn url_policy_location() {
+        let mut security = SecurityManager::new(true);
+        let sandbox = SandboxType::Remote;
+        let movie = "http://example.org/movie.swf";
+        let policy = br#"<cross-domain-policy>
+            <allow-access-from domain="example.org"/>
+        </cross-domain-policy>"#;
+        security.url_policies.insert(
+            Url::parse("http://example.com/data/crossdomain.xml").unwrap(),
+            PolicyFile::parse(policy).unwrap(),
+        );
+
+        assert!(security.can_read(&sandbox, movie, "http://example.com/data/image.png"));
+        assert!(security.can_read(&sandbox, movie, "http://example.com/data/a/image.png"));
+        assert!(!security.can_read(&sandbox, movie, "http://example.com/image.png"));
+        assert!(!security.can_read(&sandbox, movie, "https://example.com/data/image.png"));
+        assert!(!security.can_read(&sandbox, movie, "http://cdn.example.com/data/image.png"));
+    }
+
+    #[test]
+    fn socket_policy() {
+        let mut security = SecurityManager::new(true);
+        let sandbox = SandboxType::Remote;
+        let movie = "http://example.com/movie.swf";
+
+        assert_eq!(
+            security.socket_permission(&sandbox, movie, "example.com", 5000),
+            SocketPermission::NeedsPolicy
+        );
+        assert_eq!(
+            security.socket_permission(&SandboxType::LocalWithFile, movie, "example.com", 5000),
+            SocketPermission::Denied
+        );
+
+        let policy = br#"<cross-domain-policy>
+            <allow-access-from domain="example.com" to-ports="5000"/>
+        </cross-domain-policy>"#;
+        security.add_socket_policy("Example.com", 5000, PolicyFile::parse(policy).unwrap());
+        assert_eq!(
+            security.socket_permission(&sandbox, movie, "example.com", 5000),
+            SocketPermission::Allowed
+        );
+        assert_eq!(
+            security.socket_permission(&sandbox, movie, "example.com", 5001),
+            SocketPermission::NeedsPolicy
+        );
+
+        // A policy from an unprivileged port may not open privileged ones.
+        let policy = br#"<cross-domain-policy>
+            <allow-access-from domain="*" to-ports="*"/>
+        </cross-domain-policy>"#;
+        security.add_socket_policy("example.com", 5002, PolicyFile::parse(policy).unwrap());
+        assert_eq!(
+            security.socket_permission(&sandbox, movie, "example.com", 5001),
+            SocketPermission::Allowed
+        );
+        assert_eq!(
+            security.socket_permission(&sandbox, movie, "example.com", 80),
+            SocketPermission::NeedsPolicy
+        );
+        assert_eq!(
+            security.socket_policy_ports("example.com", 5000),
+            vec![843, 5000]
+        );
+    }
+}
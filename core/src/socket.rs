@@ -0,0 +1,748 @@
+//! Socket and XMLSocket implementation
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    ExecutionReason as Avm1ExecutionReason, Object as Avm1Object, TObject as Avm1TObject,
+    Value as Avm1Value,
+};
+use crate::avm2::events::dispatch_event;
+use crate::avm2::{
+    Activation as Avm2Activation, Error as Avm2Error, Object as Avm2Object, TObject as Avm2TObject,
+};
+use crate::backend::navigator::{ConnectionState, SocketAction};
+use crate::context::UpdateContext;
+use crate::security::{PolicyFile, SocketPermission, SOCKET_POLICY_REQUEST};
+use crate::string::AvmString;
+use crate::vminterface::AvmObject;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::fmt;
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+use url::Url;
+
+/// Manager for all open sockets.
+///
+/// Sockets are polled once per tick for activity on the network.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct Sockets<'gc> {
+    /// List of sockets that are connecting, or connected, to a server.
+    open_sockets: Vec<Socket<'gc>>,
+}
+
+impl<'gc> Default for Sockets<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'gc> Sockets<'gc> {
+    pub fn new() -> Self {
+        Self {
+            open_sockets: Vec::new(),
+        }
+    }
+
+    fn add(context: &mut UpdateContext<'_, 'gc>, socket: Socket<'gc>) {
+        if !context.sockets.open_sockets.contains(&socket) {
+            context.sockets.open_sockets.push(socket);
+        }
+    }
+
+    fn remove(context: &mut UpdateContext<'_, 'gc>, socket: Socket<'gc>) {
+        context.sockets.open_sockets.retain(|s| *s != socket);
+    }
+
+    /// Process network activity on all open sockets.
+    pub fn tick(context: &mut UpdateContext<'_, 'gc>) {
+        let sockets = context.sockets.open_sockets.clone();
+        for socket in sockets {
+            socket.tick(context);
+        }
+    }
+}
+
+/// What is sent over a socket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SocketKind {
+    /// Arbitrary binary data, as sent by the AVM2 `Socket` class.
+    Binary,
+
+    /// XML documents, or any other strings, each terminated by a null byte,
+    /// as sent by the `XMLSocket` class of either VM.
+    Xml,
+}
+
+/// A TCP connection to a server.
+///
+/// It corresponds directly to the AVM2 `Socket` class, and to the
+/// `XMLSocket` class of either VM.
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct Socket<'gc>(GcCell<'gc, SocketData<'gc>>);
+
+impl<'gc> PartialEq for Socket<'gc> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ptr() == other.0.as_ptr()
+    }
+}
+
+impl<'gc> Eq for Socket<'gc> {}
+
+impl fmt::Debug for Socket<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Socket")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct SocketData<'gc> {
+    /// The AVM side of this socket, which receives its events.
+    avm_object: Option<AvmObject<'gc>>,
+
+    #[collect(require_static)]
+    kind: SocketKind,
+
+    /// The connection that was last opened, or `None` if the socket is
+    /// closed.
+    #[collect(require_static)]
+    connection: Option<Connection>,
+}
+
+/// The state of an open socket.
+struct Connection {
+    host: String,
+    port: u16,
+    timeout: Duration,
+
+    /// Whether the connection attempt has succeeded.
+    connected: bool,
+
+    /// While the sandbox is waiting for a socket policy file, the progress
+    /// of that request. The connection is then to a policy port rather than
+    /// to `port`.
+    policy_request: Option<PolicyRequest>,
+
+    /// Data to write to the socket.
+    sender: Sender<Vec<u8>>,
+
+    /// Things that happened to the socket. This is shared with `tick`, so
+    /// that it can tell whether the socket was reopened by a handler.
+    receiver: Rc<Receiver<SocketAction>>,
+
+    /// For XML sockets, the start of a message that has not been terminated
+    /// yet.
+    partial_message: Vec<u8>,
+}
+
+/// A request for a socket policy file, which is made before connecting to a
+/// server when the security sandbox is enforced.
+struct PolicyRequest {
+    /// The URL of the movie that is connecting.
+    movie_url: String,
+
+    /// The policy port that is being asked.
+    port: u16,
+
+    /// The policy ports that have not been tried yet.
+    remaining_ports: Vec<u16>,
+
+    /// The policy file received so far.
+    response: Vec<u8>,
+}
+
+/// Why a connection attempt failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ConnectFailure {
+    /// The server could not be reached.
+    Io,
+
+    /// The server did not allow the movie to connect, or did not answer in
+    /// time.
+    Security,
+}
+
+impl<'gc> Socket<'gc> {
+    pub fn new(
+        gc_context: MutationContext<'gc, '_>,
+        avm_object: Option<AvmObject<'gc>>,
+        kind: SocketKind,
+    ) -> Self {
+        Self(GcCell::allocate(
+            gc_context,
+            SocketData {
+                avm_object,
+                kind,
+                connection: None,
+            },
+        ))
+    }
+
+    pub fn set_avm_object(self, gc_context: MutationContext<'gc, '_>, avm_object: AvmObject<'gc>) {
+        self.0.write(gc_context).avm_object = Some(avm_object);
+    }
+
+    /// Whether the socket is connected to a server.
+    pub fn is_connected(self) -> bool {
+        self.0
+            .read()
+            .connection
+            .as_ref()
+            .map_or(false, |connection| connection.connected)
+    }
+
+    /// Open a connection to a server, closing any existing connection first.
+    ///
+    /// If `host` is `None`, the host that the movie was loaded from is used.
+    /// The outcome is reported to the AVM object once the attempt finishes,
+    /// or after `timeout`.
+    ///
+    /// If the security sandbox is enforced, the server is first asked for a
+    /// socket policy file that allows the movie at `movie_url` to connect.
+    /// Returns `false` if the movie may not use sockets at all.
+    pub fn connect(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        movie_url: &str,
+        host: Option<String>,
+        port: u16,
+        timeout: Duration,
+    ) -> bool {
+        self.close(context);
+
+        let host = host.unwrap_or_else(|| {
+            Url::parse(context.swf.url())
+                .ok()
+                .and_then(|url| url.host_str().map(|host| host.to_string()))
+                .unwrap_or_else(|| "localhost".to_string())
+        });
+        match context.security.socket_permission(
+            &context.system.sandbox_type,
+            movie_url,
+            &host,
+            port,
+        ) {
+            SocketPermission::Allowed => self.open(context, host, port, timeout, None),
+            SocketPermission::NeedsPolicy => {
+                let mut remaining_ports = context.security.socket_policy_ports(&host, port);
+                let policy_request = PolicyRequest {
+                    movie_url: movie_url.to_string(),
+                    port: remaining_ports.remove(0),
+                    remaining_ports,
+                    response: Vec::new(),
+                };
+                self.open(context, host, port, timeout, Some(policy_request));
+            }
+            SocketPermission::Denied => return false,
+        }
+        true
+    }
+
+    /// Start connecting to `port` on `host`, replacing any existing
+    /// connection without closing it.
+    ///
+    /// With a `policy_request`, the policy port is connected to instead.
+    fn open(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        policy_request: Option<PolicyRequest>,
+    ) {
+        let connect_port = policy_request
+            .as_ref()
+            .map_or(port, |policy_request| policy_request.port);
+        let (sender, receiver) = start_connection(context, &host, connect_port, timeout);
+
+        self.0.write(context.gc_context).connection = Some(Connection {
+            host,
+            port,
+            timeout,
+            connected: false,
+            policy_request,
+            sender,
+            receiver,
+            partial_message: Vec::new(),
+        });
+        Sockets::add(context, self);
+    }
+
+    /// Handle the end of a policy file request, either because a policy file
+    /// was received or because the policy port could not be reached.
+    ///
+    /// The real connection is made if any policy file allows it, otherwise
+    /// the next policy port is tried.
+    fn finish_policy_request(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        policy: Option<PolicyFile>,
+    ) {
+        let connection = self.0.write(context.gc_context).connection.take();
+        let (host, port, timeout, mut policy_request) = match connection {
+            Some(Connection {
+                host,
+                port,
+                timeout,
+                policy_request: Some(policy_request),
+                ..
+            }) => (host, port, timeout, policy_request),
+            connection => {
+                self.0.write(context.gc_context).connection = connection;
+                return;
+            }
+        };
+
+        if let Some(policy) = policy {
+            context
+                .security
+                .add_socket_policy(&host, policy_request.port, policy);
+        }
+
+        if context
+            .security
+            .socket_policy_allows(&policy_request.movie_url, &host, port)
+        {
+            self.open(context, host, port, timeout, None);
+        } else if !policy_request.remaining_ports.is_empty() {
+            policy_request.port = policy_request.remaining_ports.remove(0);
+            policy_request.response.clear();
+            self.open(context, host, port, timeout, Some(policy_request));
+        } else {
+            self.close(context);
+            self.trigger_connect_failed_event(context, ConnectFailure::Security, &host, port);
+        }
+    }
+
+    /// Whether the connection is currently asking for a policy file.
+    fn is_requesting_policy(self) -> bool {
+        self.0
+            .read()
+            .connection
+            .as_ref()
+            .map_or(false, |connection| connection.policy_request.is_some())
+    }
+
+    /// Write data to the socket.
+    ///
+    /// XML sockets terminate the data with a null byte. Returns `false` if
+    /// the socket is not connected.
+    pub fn send(self, mut data: Vec<u8>) -> bool {
+        let read = self.0.read();
+        let Some(connection) = read.connection.as_ref().filter(|c| c.connected) else {
+            return false;
+        };
+
+        if read.kind == SocketKind::Xml {
+            data.push(0);
+        }
+        // If the backend has gone away, it will report the socket as closed.
+        let _ = connection.sender.send(data);
+        true
+    }
+
+    /// Close the socket without sending any events.
+    ///
+    /// Returns `false` if the socket was not open.
+    pub fn close(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        // Dropping the sender tells the backend to close the socket.
+        let connection = self.0.write(context.gc_context).connection.take();
+        Sockets::remove(context, self);
+        connection.is_some()
+    }
+
+    /// Process network activity on this socket.
+    fn tick(self, context: &mut UpdateContext<'_, 'gc>) {
+        let Some(receiver) = self
+            .0
+            .read()
+            .connection
+            .as_ref()
+            .map(|connection| connection.receiver.clone())
+        else {
+            return;
+        };
+
+        for action in receiver.try_iter() {
+            // The socket may have been closed or reopened by a handler.
+            let is_current = self
+                .0
+                .read()
+                .connection
+                .as_ref()
+                .map_or(false, |connection| {
+                    Rc::ptr_eq(&connection.receiver, &receiver)
+                });
+            if !is_current {
+                return;
+            }
+
+            if self.is_requesting_policy() {
+                self.tick_policy_request(context, action);
+                continue;
+            }
+
+            match action {
+                SocketAction::Connect(ConnectionState::Connected) => {
+                    if let Some(connection) = &mut self.0.write(context.gc_context).connection {
+                        connection.connected = true;
+                    }
+                    self.trigger_connect_event(context);
+                }
+                SocketAction::Connect(state) => {
+                    let address = self
+                        .0
+                        .read()
+                        .connection
+                        .as_ref()
+                        .map(|connection| (connection.host.clone(), connection.port));
+                    self.close(context);
+                    let failure = if state == ConnectionState::TimedOut {
+                        ConnectFailure::Security
+                    } else {
+                        ConnectFailure::Io
+                    };
+                    if let Some((host, port)) = address {
+                        self.trigger_connect_failed_event(context, failure, &host, port);
+                    }
+                }
+                SocketAction::Data(data) => self.receive(context, data),
+                SocketAction::Close => {
+                    self.close(context);
+                    self.trigger_close_event(context);
+                }
+            }
+        }
+    }
+
+    /// Process network activity on a connection to a policy port.
+    fn tick_policy_request(self, context: &mut UpdateContext<'_, 'gc>, action: SocketAction) {
+        match action {
+            SocketAction::Connect(ConnectionState::Connected) => {
+                if let Some(connection) = &self.0.read().connection {
+                    let _ = connection.sender.send(SOCKET_POLICY_REQUEST.to_vec());
+                }
+            }
+            SocketAction::Connect(_) | SocketAction::Close => {
+                self.finish_policy_request(context, None);
+            }
+            SocketAction::Data(data) => {
+                let response = match &mut self.0.write(context.gc_context).connection {
+                    Some(Connection {
+                        policy_request: Some(policy_request),
+                        ..
+                    }) => {
+                        let response = &mut policy_request.response;
+                        response.extend_from_slice(&data);
+                        match response.iter().position(|&b| b == 0) {
+                            Some(end) => response[..end].to_vec(),
+                            None => return,
+                        }
+                    }
+                    _ => return,
+                };
+                self.finish_policy_request(context, PolicyFile::parse(&response));
+            }
+        }
+    }
+
+    /// Pass received data on to the AVM object.
+    fn receive(self, context: &mut UpdateContext<'_, 'gc>, data: Vec<u8>) {
+        let kind = self.0.read().kind;
+        match kind {
+            SocketKind::Binary => {
+                let avm_object = self.0.read().avm_object;
+                if let Some(AvmObject::Avm2(object)) = avm_object {
+                    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                    let result = append_to_socket_input(&mut activation, object, &data)
+                        .and_then(|()| {
+                            activation.avm2().classes().progressevent.construct(
+                                &mut activation,
+                                &[
+                                    "socketData".into(),
+                                    false.into(),
+                                    false.into(),
+                                    data.len().into(),
+                                    0.into(),
+                                ],
+                            )
+                        })
+                        .and_then(|event| dispatch_event(&mut activation, object, event));
+                    if let Err(e) = result {
+                        tracing::error!(
+                            "Unhandled AVM2 exception in socketData event handler: {}",
+                            e.detailed_message(&mut activation)
+                        );
+                    }
+                }
+            }
+            SocketKind::Xml => {
+                let messages = match &mut self.0.write(context.gc_context).connection {
+                    Some(connection) => split_messages(&mut connection.partial_message, &data),
+                    None => return,
+                };
+                for message in messages {
+                    self.trigger_data_event(context, message);
+                }
+            }
+        }
+    }
+
+    /// Send an XML socket message to the AVM object.
+    fn trigger_data_event(self, context: &mut UpdateContext<'_, 'gc>, message: String) {
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let message = AvmString::new_utf8(activation.context.gc_context, message);
+                let result = activation
+                    .avm2()
+                    .classes()
+                    .dataevent
+                    .construct(
+                        &mut activation,
+                        &["data".into(), false.into(), false.into(), message.into()],
+                    )
+                    .and_then(|event| dispatch_event(&mut activation, object, event));
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in data event handler: {}",
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[XMLSocket onData]"),
+                );
+                let message = AvmString::new_utf8(activation.context.gc_context, message);
+                call_avm1_method(&mut activation, object, "onData", &[message.into()]);
+            }
+            None => {}
+        }
+    }
+
+    fn trigger_connect_event(self, context: &mut UpdateContext<'_, 'gc>) {
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let result = activation
+                    .avm2()
+                    .classes()
+                    .event
+                    .construct(&mut activation, &["connect".into()])
+                    .and_then(|event| dispatch_event(&mut activation, object, event));
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in connect event handler: {}",
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[XMLSocket onConnect]"),
+                );
+                call_avm1_method(&mut activation, object, "onConnect", &[true.into()]);
+            }
+            None => {}
+        }
+    }
+
+    /// Report a failed connection attempt to the AVM object.
+    ///
+    /// In AVM2, a refused connection is an `ioError`, and a timeout or a
+    /// missing policy file is a `securityError`.
+    fn trigger_connect_failed_event(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        failure: ConnectFailure,
+        host: &str,
+        port: u16,
+    ) {
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let (class, event_type, message, code) = if failure == ConnectFailure::Security {
+                    let message = format!(
+                        "Error #2048: Security sandbox violation: {} cannot load data from {}:{}.",
+                        activation.context.swf.url(),
+                        host,
+                        port
+                    );
+                    (
+                        activation.avm2().classes().securityerrorevent,
+                        "securityError",
+                        message,
+                        2048,
+                    )
+                } else {
+                    (
+                        activation.avm2().classes().ioerrorevent,
+                        "ioError",
+                        format!("Error #2031: Socket Error. URL: {host}"),
+                        2031,
+                    )
+                };
+                let message = AvmString::new_utf8(activation.context.gc_context, message);
+                let result = class
+                    .construct(
+                        &mut activation,
+                        &[
+                            event_type.into(),
+                            false.into(),
+                            false.into(),
+                            message.into(),
+                            code.into(),
+                        ],
+                    )
+                    .and_then(|event| dispatch_event(&mut activation, object, event));
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in {} event handler: {}",
+                        event_type,
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[XMLSocket onConnect]"),
+                );
+                call_avm1_method(&mut activation, object, "onConnect", &[false.into()]);
+            }
+            None => {}
+        }
+    }
+
+    fn trigger_close_event(self, context: &mut UpdateContext<'_, 'gc>) {
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let result = activation
+                    .avm2()
+                    .classes()
+                    .event
+                    .construct(&mut activation, &["close".into()])
+                    .and_then(|event| dispatch_event(&mut activation, object, event));
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in close event handler: {}",
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[XMLSocket onClose]"),
+                );
+                call_avm1_method(&mut activation, object, "onClose", &[]);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Ask the navigator to connect to `port` on `host`, returning the channels
+/// to talk to the socket with.
+fn start_connection(
+    context: &mut UpdateContext<'_, '_>,
+    host: &str,
+    port: u16,
+    timeout: Duration,
+) -> (Sender<Vec<u8>>, Rc<Receiver<SocketAction>>) {
+    let (data_sender, data_receiver) = channel();
+    let (action_sender, action_receiver) = channel();
+    context.navigator.connect_socket(
+        host.to_string(),
+        port,
+        timeout,
+        data_receiver,
+        action_sender,
+    );
+    (data_sender, Rc::new(action_receiver))
+}
+
+/// Append received data to the input buffer of an AVM2 `Socket`.
+fn append_to_socket_input<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    object: Avm2Object<'gc>,
+    data: &[u8],
+) -> Result<(), Avm2Error<'gc>> {
+    let Some(input) = object.as_socket().and_then(|socket| socket.input()) else {
+        return Ok(());
+    };
+    let Some(mut input) = input.as_bytearray_mut(activation.context.gc_context) else {
+        return Ok(());
+    };
+
+    // Once everything has been read, the buffer can start over.
+    if input.bytes_available() == 0 {
+        input.clear();
+    }
+    let end = input.len();
+    input.write_at(data, end)
+}
+
+fn call_avm1_method<'gc>(
+    activation: &mut Avm1Activation<'_, 'gc>,
+    object: Avm1Object<'gc>,
+    name: &'static str,
+    arguments: &[Avm1Value<'gc>],
+) {
+    if let Err(e) = object.call_method(
+        name.into(),
+        arguments,
+        activation,
+        Avm1ExecutionReason::Special,
+    ) {
+        tracing::error!("Unhandled AVM1 error in XMLSocket {} handler: {}", name, e);
+    }
+}
+
+/// Split data received by an XML socket into null-terminated messages.
+///
+/// Any data after the last null byte is kept in `partial_message` until the
+/// rest of the message arrives.
+fn split_messages(partial_message: &mut Vec<u8>, data: &[u8]) -> Vec<String> {
+    partial_message.extend_from_slice(data);
+    let Some(end) = partial_message.iter().rposition(|&b| b == 0) else {
+        return Vec::new();
+    };
+
+    let rest = partial_message.split_off(end + 1);
+    let complete = std::mem::replace(partial_message, rest);
+    complete[..end]
+        .split(|&b| b == 0)
+        .map(|message| String::from_utf8_lossy(message).into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_messages;
+
+    #[test]
+    fn split_xml_messages() {
+        let mut partial = Vec::new();
+        assert_eq!(split_messages(&mut partial, b"<a/>\0<b"), vec!["<a/>"]);
+        assert_eq!(partial, b"<b");
+        assert!(split_messages(&mut partial, b"/>").is_empty());
+        assert_eq!(
+            split_messages(&mut partial, b"\0\0<c/>\0"),
+            vec!["<b/>", "", "<c/>"]
+        );
+        assert!(partial.is_empty());
+    }
+}
@@ -1,10 +1,25 @@
 //! NetStream implementation
 
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    Attribute as Avm1Attribute, ExecutionReason as Avm1ExecutionReason,
+    ScriptObject as Avm1ScriptObject, TObject as Avm1TObject,
+};
+use crate::avm2::events::{dispatch_net_status_event, net_status_info};
+use crate::avm2::Activation as Avm2Activation;
+use crate::backend::audio::{SoundInstanceHandle, Substream};
 use crate::backend::navigator::Request;
 use crate::context::UpdateContext;
 use crate::loader::Error;
+use crate::net_connection::call_avm2_client;
 use crate::string::AvmString;
+use crate::vminterface::AvmObject;
 use gc_arena::{Collect, GcCell, MutationContext};
+use ruffle_render::bitmap::BitmapInfo;
+use ruffle_video::frame::EncodedFrame;
+use ruffle_video::VideoStreamHandle;
+use std::fmt;
+use swf::{AudioCompression, SoundFormat, VideoCodec};
 
 /// Manager for all media streams.
 ///
@@ -51,17 +66,8 @@ impl<'gc> StreamManager<'gc> {
         }
     }
 
-    pub fn toggle_paused(context: &mut UpdateContext<'_, 'gc>, stream: NetStream<'gc>) {
-        let index = context
-            .stream_manager
-            .playing_streams
-            .iter()
-            .position(|x| *x == stream);
-        if let Some(index) = index {
-            context.stream_manager.playing_streams.remove(index);
-        } else {
-            context.stream_manager.playing_streams.push(stream);
-        }
+    pub fn is_playing(context: &UpdateContext<'_, 'gc>, stream: NetStream<'gc>) -> bool {
+        context.stream_manager.playing_streams.contains(&stream)
     }
 
     /// Process all playing media streams.
@@ -71,7 +77,12 @@ impl<'gc> StreamManager<'gc> {
     /// support video framerates separate from the Stage frame rate.
     ///
     /// This does not borrow `&mut self` as we need the `UpdateContext`, too.
-    pub fn tick(_context: &mut UpdateContext<'_, 'gc>, _dt: f64) {}
+    pub fn tick(context: &mut UpdateContext<'_, 'gc>, dt: f64) {
+        let streams = context.stream_manager.playing_streams.clone();
+        for stream in streams {
+            stream.tick(context, dt)
+        }
+    }
 }
 
 /// A stream representing download of some (audiovisual) data.
@@ -81,16 +92,16 @@ impl<'gc> StreamManager<'gc> {
 ///
 ///  * `LoadManager` fills individual `NetStream` buffers with data (or, in the
 ///    future, empties them out for media upload)
-///  * `StreamManager` processes media data in the `NetStream` buffer (in the
-///    future, sending it to the audio backend or `SoundManager`)
+///  * `StreamManager` processes media data in the `NetStream` buffer, sending
+///    video frames to the video backend and audio to the audio backend
 ///  * `Video` display objects linked to this `NetStream` display the latest
 ///    decoded frame.
 ///
 /// It corresponds directly to the AVM1 and AVM2 `NetStream` classes; it's API
 /// is intended to be a VM-agnostic version of those.
-#[derive(Clone, Debug, Collect, Copy)]
+#[derive(Clone, Collect, Copy)]
 #[collect(no_drop)]
-pub struct NetStream<'gc>(GcCell<'gc, NetStreamData>);
+pub struct NetStream<'gc>(GcCell<'gc, NetStreamData<'gc>>);
 
 impl<'gc> PartialEq for NetStream<'gc> {
     fn eq(&self, other: &Self) -> bool {
@@ -100,27 +111,248 @@ impl<'gc> PartialEq for NetStream<'gc> {
 
 impl<'gc> Eq for NetStream<'gc> {}
 
+impl fmt::Debug for NetStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NetStream")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+/// The container format of the media in a `NetStream`, along with any state
+/// needed to demux it.
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
-pub struct NetStreamData {
+pub enum NetStreamType {
+    /// The stream is an FLV file.
+    Flv {
+        header: flv::Header,
+
+        /// The video decoder stream, once the first decodable video frame has
+        /// been seen.
+        video_stream: Option<VideoStreamHandle>,
+
+        /// The number of video frames sent to the decoder so far.
+        frame_id: u32,
+    },
+}
+
+/// An action that can be taken on a stream in data generation mode.
+///
+/// These correspond to the values of the AVM2 `NetStreamAppendBytesAction`
+/// enumeration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppendBytesAction {
+    /// There is a discontinuity in the stream; the next appended data starts
+    /// with a new FLV header.
+    ResetBegin,
+
+    /// The stream has been seeked; the next appended data continues with FLV
+    /// tags from the new position.
+    ResetSeek,
+
+    /// No more data will be appended to the stream.
+    EndSequence,
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct NetStreamData<'gc> {
     /// All data currently loaded in the stream.
     buffer: Vec<u8>,
+
+    /// Whether or not the buffer holds the entire resource.
+    ///
+    /// Until this is set, running out of data means that we need to wait for
+    /// more of it, not that the stream has ended.
+    is_complete: bool,
+
+    /// The container format of the stream, once it has been sniffed from the
+    /// start of the buffer.
+    stream_type: Option<NetStreamType>,
+
+    /// The byte offset of the next tag to be processed.
+    offset: usize,
+
+    /// Whether or not the stream is in data generation mode.
+    ///
+    /// Data generation streams are fed by script code through `appendBytes`,
+    /// rather than downloaded. Processed data is discarded from the buffer.
+    is_data_generation: bool,
+
+    /// Whether or not the playhead should jump to the timestamp of the next
+    /// tag, as the timeline has been reset.
+    needs_time_sync: bool,
+
+    /// The current playhead position in milliseconds.
+    stream_time: f64,
+
+    /// Whether or not playback has reached the end of the stream.
+    ///
+    /// A stream that has ended is no longer ticked, but unlike a paused
+    /// stream, it resumes playing if it is seeked.
+    has_ended: bool,
+
+    /// The number of seconds of media that should be buffered before
+    /// playback starts.
+    buffer_time: f64,
+
+    /// The byte offset of the first tag whose timestamp has not been added
+    /// to `buffered_time` yet.
+    buffered_offset: usize,
+
+    /// The timestamp of the last tag that has been loaded, in milliseconds.
+    buffered_time: f64,
+
+    /// Whether or not playback is waiting for `buffer_time` seconds of
+    /// media to be loaded.
+    is_buffering: bool,
+
+    /// The seekable points in the stream, as listed in its `onMetaData`.
+    #[collect(require_static)]
+    keyframes: flv::KeyframeIndex,
+
+    /// The audio track of the stream, which is shared with the audio backend.
+    #[collect(require_static)]
+    audio_stream: Option<(SoundFormat, Substream)>,
+
+    /// The sound instance playing `audio_stream`.
+    #[collect(require_static)]
+    sound_instance: Option<SoundInstanceHandle>,
+
+    /// The last video frame decoded from the stream.
+    last_decoded_bitmap: Option<BitmapInfo>,
+
+    /// The AVM side of this stream, which receives status events and
+    /// script data callbacks.
+    avm_object: Option<AvmObject<'gc>>,
+}
+
+impl NetStreamData<'_> {
+    /// Advance `buffered_time` over any tags that were loaded since the last
+    /// call.
+    ///
+    /// Tags are only skipped over, not decoded, so this is cheap to call
+    /// whenever new data arrives.
+    fn scan_buffered(&mut self) {
+        if self.stream_type.is_none() {
+            return;
+        }
+
+        let mut reader = flv::Reader::with_position(&self.buffer, self.buffered_offset);
+        while let Ok(timestamp) = reader.skip_tag() {
+            self.buffered_time = f64::from(timestamp);
+        }
+        self.buffered_offset = reader.position();
+    }
+
+    /// The amount of media loaded ahead of the playhead, in milliseconds.
+    fn buffered_length(&self) -> f64 {
+        (self.buffered_time - self.stream_time).max(0.0)
+    }
+
+    /// Start waiting for `buffer_time` seconds of media from the byte
+    /// `offset` onwards, which is where the playhead currently is.
+    fn start_buffering(&mut self, offset: usize) {
+        self.buffered_offset = offset;
+        self.buffered_time = self.stream_time;
+        self.is_buffering = true;
+        self.scan_buffered();
+    }
+
+    /// Append data to a stream in data generation mode.
+    ///
+    /// Returns true if the stream had run out of data, and can now continue.
+    fn append_generated_data(&mut self, data: &[u8]) -> bool {
+        self.buffer.extend_from_slice(data);
+        self.scan_buffered();
+
+        if self.has_ended && !self.is_complete {
+            self.has_ended = false;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Apply an `appendBytesAction` to the data of a stream in data generation
+    /// mode.
+    ///
+    /// Returns true if the stream had ended, and should continue with the
+    /// data appended from now on.
+    fn apply_append_bytes_action(&mut self, action: AppendBytesAction) -> bool {
+        match action {
+            AppendBytesAction::ResetBegin | AppendBytesAction::ResetSeek => {
+                if action == AppendBytesAction::ResetBegin {
+                    self.stream_type = None;
+                }
+
+                self.buffer.clear();
+                self.offset = 0;
+                self.is_complete = false;
+                self.needs_time_sync = true;
+                self.start_buffering(0);
+                self.audio_stream = None;
+                std::mem::take(&mut self.has_ended)
+            }
+            AppendBytesAction::EndSequence => {
+                self.is_complete = true;
+                false
+            }
+        }
+    }
 }
 
 impl<'gc> NetStream<'gc> {
-    pub fn new(gc_context: MutationContext<'gc, '_>) -> Self {
+    pub fn new(gc_context: MutationContext<'gc, '_>, avm_object: Option<AvmObject<'gc>>) -> Self {
         Self(GcCell::allocate(
             gc_context,
-            NetStreamData { buffer: Vec::new() },
+            NetStreamData {
+                buffer: Vec::new(),
+                is_complete: false,
+                stream_type: None,
+                offset: 0,
+                is_data_generation: false,
+                needs_time_sync: false,
+                stream_time: 0.0,
+                has_ended: false,
+                buffer_time: 0.1,
+                buffered_offset: 0,
+                buffered_time: 0.0,
+                is_buffering: false,
+                keyframes: flv::KeyframeIndex::new(),
+                audio_stream: None,
+                sound_instance: None,
+                last_decoded_bitmap: None,
+                avm_object,
+            },
         ))
     }
 
-    pub fn load_buffer(self, gc_context: MutationContext<'gc, '_>, data: &mut Vec<u8>) {
-        self.0.write(gc_context).buffer.append(data);
+    pub fn set_avm_object(self, gc_context: MutationContext<'gc, '_>, avm_object: AvmObject<'gc>) {
+        self.0.write(gc_context).avm_object = Some(avm_object);
     }
 
-    pub fn report_error(self, _error: Error) {
-        //TODO: Report an `asyncError` to AVM1 or 2.
+    /// Append data to the stream's buffer.
+    ///
+    /// If `is_complete` is set, no more data will be loaded into the stream.
+    pub fn load_buffer(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        data: &mut Vec<u8>,
+        is_complete: bool,
+    ) {
+        let mut write = self.0.write(gc_context);
+        write.buffer.append(data);
+        write.is_complete = is_complete;
+        write.scan_buffered();
+    }
+
+    /// Report a failure to load the stream's media to script code.
+    pub fn report_error(self, context: &mut UpdateContext<'_, 'gc>, error: Error) {
+        tracing::error!("Error loading NetStream media: {}", error);
+        StreamManager::ensure_paused(context, self);
+        self.trigger_status_event(context, "NetStream.Play.StreamNotFound", "error");
     }
 
     pub fn bytes_loaded(self) -> usize {
@@ -131,34 +363,852 @@ impl<'gc> NetStream<'gc> {
         self.0.read().buffer.len()
     }
 
+    /// The current playhead position, in seconds.
+    pub fn time(self) -> f64 {
+        self.0.read().stream_time / 1000.0
+    }
+
+    /// The number of seconds of media that should be buffered before
+    /// playback starts.
+    pub fn buffer_time(self) -> f64 {
+        self.0.read().buffer_time
+    }
+
+    pub fn set_buffer_time(self, gc_context: MutationContext<'gc, '_>, buffer_time: f64) {
+        self.0.write(gc_context).buffer_time = buffer_time.max(0.0);
+    }
+
+    /// The number of seconds of media that have been loaded ahead of the
+    /// playhead.
+    pub fn buffer_length(self) -> f64 {
+        self.0.read().buffered_length() / 1000.0
+    }
+
+    /// The most recently decoded frame of video in this stream, if any.
+    pub fn last_decoded_bitmap(self) -> Option<BitmapInfo> {
+        self.0.read().last_decoded_bitmap.clone()
+    }
+
     /// Start playing media from this NetStream.
     ///
     /// If `name` is specified, this will also trigger streaming download of
-    /// the given resource. Otherwise, the stream will play whatever data is
-    /// available in the buffer.
+    /// the given resource. Otherwise, the stream enters data generation mode
+    /// and plays whatever data is appended to it with `append_bytes`.
     pub fn play(self, context: &mut UpdateContext<'_, 'gc>, name: Option<AvmString<'gc>>) {
+        self.reset(context);
+
         if let Some(name) = name {
             let request = Request::get(name.to_string());
-            context
+            let future = context
                 .load_manager
                 .load_netstream(context.player.clone(), self, request);
+            context.navigator.spawn_future(future);
+        } else {
+            let mut write = self.0.write(context.gc_context);
+            // Only AVM2 streams can be fed with `appendBytes`.
+            write.is_data_generation = matches!(write.avm_object, Some(AvmObject::Avm2(_)));
         }
 
         StreamManager::ensure_playing(context, self);
     }
 
+    /// Append data to a stream in data generation mode.
+    pub fn append_bytes(self, context: &mut UpdateContext<'_, 'gc>, data: &[u8]) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.is_data_generation {
+            tracing::warn!(
+                "NetStream.appendBytes called on a stream that is not in data generation mode"
+            );
+            return;
+        }
+
+        // New data may let a stream that ran out of data continue.
+        if write.append_generated_data(data) {
+            drop(write);
+            StreamManager::ensure_playing(context, self);
+        }
+    }
+
+    /// Signal a discontinuity or the end of data to a stream in data
+    /// generation mode.
+    pub fn append_bytes_action(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        action: AppendBytesAction,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.is_data_generation {
+            return;
+        }
+
+        if action != AppendBytesAction::EndSequence {
+            if let Some(instance) = write.sound_instance.take() {
+                context.audio_manager.stop_sound(context.audio, instance);
+            }
+        }
+
+        if write.apply_append_bytes_action(action) {
+            drop(write);
+            StreamManager::ensure_playing(context, self);
+        }
+    }
+
+    /// Discard all media in the stream, and stop any playing audio.
+    fn reset(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        if let Some(instance) = write.sound_instance.take() {
+            context.audio_manager.stop_sound(context.audio, instance);
+        }
+
+        write.buffer.clear();
+        write.is_complete = false;
+        write.stream_type = None;
+        write.offset = 0;
+        write.is_data_generation = false;
+        write.needs_time_sync = false;
+        write.stream_time = 0.0;
+        write.has_ended = false;
+        write.buffered_offset = 0;
+        write.buffered_time = 0.0;
+        write.is_buffering = false;
+        write.keyframes = flv::KeyframeIndex::new();
+        write.audio_stream = None;
+        write.last_decoded_bitmap = None;
+    }
+
+    /// Stop playback and discard all media in the stream.
+    pub fn close(self, context: &mut UpdateContext<'_, 'gc>) {
+        StreamManager::ensure_paused(context, self);
+        self.reset(context);
+    }
+
+    /// Move the playhead to the keyframe nearest to `offset` seconds.
+    ///
+    /// Only media that has already been loaded can be seeked to. If the
+    /// stream's metadata does not list its keyframes, or lists keyframes
+    /// that have not been loaded yet, the loaded part of the stream is
+    /// scanned for them instead.
+    pub fn seek(self, context: &mut UpdateContext<'_, 'gc>, offset: f64) {
+        let target = (offset * 1000.0).max(0.0) as i32;
+
+        let mut write = self.0.write(context.gc_context);
+        if write.is_data_generation {
+            // The script is responsible for appending data from the new
+            // position, after signalling `AppendBytesAction::ResetSeek`.
+            write.buffer.clear();
+            write.offset = 0;
+            write.stream_time = f64::from(target);
+            write.start_buffering(0);
+            if let Some(instance) = write.sound_instance.take() {
+                context.audio_manager.stop_sound(context.audio, instance);
+            }
+            write.audio_stream = None;
+            drop(write);
+
+            self.trigger_status_event(context, "NetStream.Seek.Notify", "status");
+            return;
+        }
+
+        if write.stream_type.is_none() {
+            drop(write);
+            self.trigger_status_event(context, "NetStream.Seek.InvalidTime", "error");
+            return;
+        }
+
+        let keyframe = write
+            .keyframes
+            .seek(target)
+            .filter(|keyframe| keyframe.position < write.buffer.len())
+            .or_else(|| {
+                flv::KeyframeIndex::scan(&write.buffer)
+                    .ok()
+                    .and_then(|index| index.seek(target))
+            });
+        let keyframe = match keyframe {
+            Some(keyframe) => keyframe,
+            None => {
+                drop(write);
+                self.trigger_status_event(context, "NetStream.Seek.InvalidTime", "error");
+                return;
+            }
+        };
+
+        write.offset = keyframe.position;
+        write.stream_time = f64::from(keyframe.time);
+        write.start_buffering(keyframe.position);
+
+        // Throw away any audio that was buffered for the old position; the
+        // next audio tag will start a new sound.
+        if let Some(instance) = write.sound_instance.take() {
+            context.audio_manager.stop_sound(context.audio, instance);
+        }
+        write.audio_stream = None;
+
+        let has_ended = std::mem::take(&mut write.has_ended);
+        drop(write);
+
+        if has_ended {
+            StreamManager::ensure_playing(context, self);
+        }
+
+        self.trigger_status_event(context, "NetStream.Seek.Notify", "status");
+
+        // Show the frame we seeked to, even if we're paused.
+        if !StreamManager::is_playing(context, self) {
+            self.tick(context, 0.0);
+        }
+    }
+
     /// Pause stream playback.
     pub fn pause(self, context: &mut UpdateContext<'_, 'gc>) {
+        if !StreamManager::is_playing(context, self) {
+            return;
+        }
+
         StreamManager::ensure_paused(context, self);
+
+        // The substream keeps any audio that has not been played yet, so
+        // that it can pick up where it left off once we resume.
+        if let Some(instance) = self.0.write(context.gc_context).sound_instance.take() {
+            context.audio_manager.stop_sound(context.audio, instance);
+        }
+
+        self.trigger_status_event(context, "NetStream.Pause.Notify", "status");
     }
 
     /// Resume stream playback.
     pub fn resume(self, context: &mut UpdateContext<'_, 'gc>) {
-        StreamManager::ensure_playing(context, self);
+        if StreamManager::is_playing(context, self) {
+            return;
+        }
+
+        let mut write = self.0.write(context.gc_context);
+        if !write.has_ended {
+            StreamManager::ensure_playing(context, self);
+        }
+
+        if let Some((format, substream)) = &write.audio_stream {
+            if write.sound_instance.is_none() && !substream.is_finished() {
+                let instance =
+                    context
+                        .audio_manager
+                        .start_substream(context.audio, substream.clone(), format);
+                write.sound_instance = instance;
+            }
+        }
+        drop(write);
+
+        self.trigger_status_event(context, "NetStream.Unpause.Notify", "status");
     }
 
     /// Resume stream playback if paused, pause otherwise.
     pub fn toggle_paused(self, context: &mut UpdateContext<'_, 'gc>) {
-        StreamManager::toggle_paused(context, self);
+        if StreamManager::is_playing(context, self) {
+            self.pause(context);
+        } else {
+            self.resume(context);
+        }
+    }
+
+    /// Process the media in this stream's buffer up to the current playhead,
+    /// after advancing it by `dt` milliseconds.
+    pub fn tick(self, context: &mut UpdateContext<'_, 'gc>, dt: f64) {
+        let is_playing = StreamManager::is_playing(context, self);
+        let mut write = self.0.write(context.gc_context);
+
+        if write.stream_type.is_none() {
+            let mut reader = flv::Reader::new(&write.buffer);
+            match reader.read_header() {
+                Ok(header) => {
+                    let offset = reader.position();
+                    write.offset = offset;
+                    write.stream_type = Some(NetStreamType::Flv {
+                        header,
+                        video_stream: None,
+                        frame_id: 0,
+                    });
+                    write.start_buffering(offset);
+
+                    // Headers following a reset don't restart playback.
+                    if !write.needs_time_sync {
+                        drop(write);
+                        self.trigger_status_event(context, "NetStream.Play.Start", "status");
+                        write = self.0.write(context.gc_context);
+                    }
+                }
+                Err(flv::Error::EndOfData) if !write.is_complete => return,
+                // Ending a data generation stream before anything was
+                // appended to it is not an error.
+                Err(flv::Error::EndOfData)
+                    if write.is_data_generation && write.buffer.is_empty() =>
+                {
+                    write.has_ended = true;
+                    drop(write);
+                    self.end_of_stream(context);
+                    return;
+                }
+                Err(e) => {
+                    drop(write);
+
+                    tracing::error!("NetStream media is not a valid FLV file: {}", e);
+                    StreamManager::ensure_paused(context, self);
+                    self.trigger_status_event(
+                        context,
+                        "NetStream.Play.FileStructureInvalid",
+                        "error",
+                    );
+                    return;
+                }
+            }
+        }
+
+        // After a reset, the timeline continues from the first new tag.
+        if write.needs_time_sync {
+            let mut reader = flv::Reader::with_position(&write.buffer, write.offset);
+            if let Ok(timestamp) = reader.skip_tag() {
+                write.stream_time = f64::from(timestamp);
+                write.buffered_time = write.buffered_time.max(write.stream_time);
+                write.needs_time_sync = false;
+            }
+        }
+
+        // Playback is held until enough media has been loaded, or there is no
+        // more media to wait for.
+        let mut buffer_full = false;
+        let has_buffered_enough = write.buffered_offset > write.offset
+            && write.buffered_length() >= write.buffer_time * 1000.0;
+        if write.is_buffering && (write.is_complete || has_buffered_enough) {
+            write.is_buffering = false;
+            buffer_full = true;
+        }
+
+        if !write.is_buffering {
+            write.stream_time += dt;
+        }
+
+        let NetStreamData {
+            buffer,
+            is_complete,
+            stream_type,
+            offset,
+            is_data_generation,
+            stream_time,
+            has_ended,
+            buffered_offset,
+            is_buffering,
+            keyframes,
+            audio_stream,
+            sound_instance,
+            last_decoded_bitmap,
+            ..
+        } = &mut *write;
+
+        let (video_stream, frame_id) = match stream_type {
+            Some(NetStreamType::Flv {
+                video_stream,
+                frame_id,
+                ..
+            }) => (video_stream, frame_id),
+            None => unreachable!("Stream type was sniffed above"),
+        };
+
+        let mut script_data = Vec::new();
+        let mut end_of_stream = false;
+
+        loop {
+            let mut reader = flv::Reader::with_position(buffer, *offset);
+            let tag = match reader.read_tag() {
+                Ok(tag) => tag,
+                Err(flv::Error::EndOfData) => {
+                    end_of_stream = *is_complete;
+                    break;
+                }
+                Err(e) => {
+                    tracing::error!("Invalid FLV tag in NetStream: {}", e);
+                    end_of_stream = true;
+                    break;
+                }
+            };
+
+            if f64::from(tag.timestamp) > *stream_time {
+                break;
+            }
+
+            *offset = reader.position();
+
+            match tag.data {
+                flv::TagData::Video(video) => {
+                    if let Some(bitmap) = decode_video(context, video_stream, frame_id, video) {
+                        *last_decoded_bitmap = Some(bitmap);
+                    }
+                }
+                flv::TagData::Audio(audio) => {
+                    let (format, data) = match (sound_format(&audio), audio.data) {
+                        (Some(format), flv::AudioDataType::Raw(data)) => (format, data),
+                        _ => {
+                            tracing::warn!("Unsupported FLV audio format {:?}", audio.format);
+                            continue;
+                        }
+                    };
+
+                    let is_same_format =
+                        matches!(audio_stream, Some((old_format, _)) if *old_format == format);
+                    if !is_same_format {
+                        // This is either the first audio tag, or the audio format
+                        // changed and we need to start over with a fresh decoder.
+                        if let Some(instance) = sound_instance.take() {
+                            context.audio_manager.stop_sound(context.audio, instance);
+                        }
+
+                        // Paused streams will start the sound when resumed.
+                        let substream = Substream::new();
+                        if is_playing {
+                            *sound_instance = context.audio_manager.start_substream(
+                                context.audio,
+                                substream.clone(),
+                                &format,
+                            );
+                        }
+                        *audio_stream = Some((format, substream));
+                    }
+
+                    if let Some((_, substream)) = audio_stream {
+                        substream.append(data.to_vec());
+                    }
+                }
+                flv::TagData::Script(data) => {
+                    if data.name == "onMetaData" {
+                        if let Some(index) = flv::KeyframeIndex::from_metadata(&data) {
+                            *keyframes = index;
+                        }
+                    }
+
+                    script_data.push(data);
+                }
+                flv::TagData::Unknown { tag_type, .. } => {
+                    tracing::warn!("Unknown FLV tag type {}", tag_type);
+                }
+            }
+        }
+
+        // Running out of media before the end of the stream means waiting for
+        // more of it to be loaded.
+        let buffer_empty = !end_of_stream
+            && !buffer_full
+            && !*is_buffering
+            && is_playing
+            && *offset >= *buffered_offset;
+        if buffer_empty {
+            *is_buffering = true;
+        }
+
+        if *is_data_generation {
+            buffer.drain(..*offset);
+            *buffered_offset = buffered_offset.saturating_sub(*offset);
+            *offset = 0;
+        }
+
+        if end_of_stream {
+            if let Some((_, substream)) = audio_stream {
+                substream.close();
+            }
+
+            *has_ended = true;
+        }
+
+        drop(write);
+
+        for data in script_data {
+            self.handle_script_data(context, data);
+        }
+
+        if buffer_full {
+            self.trigger_status_event(context, "NetStream.Buffer.Full", "status");
+        }
+
+        if buffer_empty {
+            self.trigger_status_event(context, "NetStream.Buffer.Empty", "status");
+        }
+
+        if end_of_stream {
+            self.end_of_stream(context);
+        }
+    }
+
+    /// Stop playback, and tell script code that the stream has ended.
+    fn end_of_stream(self, context: &mut UpdateContext<'_, 'gc>) {
+        StreamManager::ensure_paused(context, self);
+        self.trigger_status_event(context, "NetStream.Buffer.Flush", "status");
+        self.trigger_status_event(context, "NetStream.Play.Stop", "status");
+        self.trigger_status_event(context, "NetStream.Buffer.Empty", "status");
+    }
+
+    /// Call the script handler named by an FLV script data tag.
+    ///
+    /// In AVM2, the handler is looked up on the stream's `client`, and an
+    /// `asyncError` event is sent if it does not exist; in AVM1, it is a
+    /// method of the stream itself.
+    fn handle_script_data(self, context: &mut UpdateContext<'_, 'gc>, data: flv::ScriptData) {
+        let flv::ScriptData { name, value } = data;
+
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+                let result = crate::avm2::amf::deserialize_value(&mut activation, &value).and_then(
+                    |argument| {
+                        call_avm2_client(
+                            &mut activation,
+                            object,
+                            "flash.net.NetStream",
+                            name,
+                            &[argument],
+                        )
+                    },
+                );
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in NetStream {} handler: {}",
+                        name,
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[NetStream Script Data]"),
+                );
+                let argument =
+                    crate::avm1::globals::shared_object::deserialize_value(&mut activation, &value);
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+                let _ = object.call_method(
+                    name,
+                    &[argument],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                );
+            }
+            None => {}
+        }
+    }
+
+    /// Send a `NetStatusEvent` (AVM2) or call `onStatus` (AVM1) on this
+    /// stream's AVM object.
+    pub fn trigger_status_event(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        code: &'static str,
+        level: &'static str,
+    ) {
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let result = net_status_info(&mut activation, code.into(), level.into())
+                    .and_then(|info| dispatch_net_status_event(&mut activation, object, info));
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled AVM2 exception in netStatus event handler: {}",
+                        e.detailed_message(&mut activation)
+                    );
+                }
+            }
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[NetStream Status Event]"),
+                );
+                let info = Avm1ScriptObject::new(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().object),
+                );
+                info.define_value(
+                    activation.context.gc_context,
+                    "code",
+                    code.into(),
+                    Avm1Attribute::empty(),
+                );
+                info.define_value(
+                    activation.context.gc_context,
+                    "level",
+                    level.into(),
+                    Avm1Attribute::empty(),
+                );
+                let _ = object.call_method(
+                    "onStatus".into(),
+                    &[info.into()],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                );
+            }
+            None => {}
+        }
+    }
+}
+
+/// Send a video tag to the video backend, registering a decoder for the
+/// stream if this is the first frame we can decode.
+///
+/// Returns the newly decoded frame, if any.
+fn decode_video<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    video_stream: &mut Option<VideoStreamHandle>,
+    frame_id: &mut u32,
+    video: flv::VideoData<'_>,
+) -> Option<BitmapInfo> {
+    let codec = match video.codec_id {
+        flv::CodecId::SorensonH263 => VideoCodec::H263,
+        flv::CodecId::ScreenVideo => VideoCodec::ScreenVideo,
+        flv::CodecId::On2Vp6 => VideoCodec::Vp6,
+        flv::CodecId::On2Vp6Alpha => VideoCodec::Vp6WithAlpha,
+        flv::CodecId::ScreenVideo2 => VideoCodec::ScreenVideoV2,
+        flv::CodecId::Jpeg | flv::CodecId::Avc => {
+            tracing::warn!("Unsupported FLV video codec {:?}", video.codec_id);
+            return None;
+        }
+    };
+
+    let (data, size) = match video.data {
+        flv::VideoPacket::Data(data) => (data, (0, 0)),
+        flv::VideoPacket::Vp6 {
+            horizontal_adjustment,
+            vertical_adjustment,
+            data,
+        } => {
+            let size = vp6_frame_size(codec, data, horizontal_adjustment, vertical_adjustment);
+            (data, size.unwrap_or((0, 0)))
+        }
+        // Command frames don't carry any video.
+        _ => return None,
+    };
+
+    let stream = match *video_stream {
+        Some(stream) => stream,
+        None => {
+            // We can't start decoding in the middle of a group of pictures.
+            if !video.frame_type.is_keyframe() {
+                return None;
+            }
+
+            match context.video.register_video_stream(
+                0,
+                size,
+                codec,
+                swf::VideoDeblocking::UseVideoPacketValue,
+            ) {
+                Ok(stream) => *video_stream.insert(stream),
+                Err(e) => {
+                    tracing::error!("Could not register NetStream video stream: {}", e);
+                    return None;
+                }
+            }
+        }
+    };
+
+    let encoded_frame = EncodedFrame {
+        codec,
+        data,
+        frame_id: *frame_id,
+    };
+    *frame_id += 1;
+
+    match context
+        .video
+        .decode_video_stream_frame(stream, encoded_frame, context.renderer)
+    {
+        Ok(bitmap) => Some(bitmap),
+        Err(e) => {
+            tracing::error!("Got error when decoding NetStream video frame: {}", e);
+            None
+        }
+    }
+}
+
+/// Read the encoded size of a VP6 video from its keyframe header.
+///
+/// `data` is laid out the same way as a VP6 frame embedded in a SWF file, and
+/// the adjustments are the number of pixels that FLV asks us to crop off of
+/// the right and bottom edges of the encoded frame.
+fn vp6_frame_size(
+    codec: VideoCodec,
+    data: &[u8],
+    horizontal_adjustment: u8,
+    vertical_adjustment: u8,
+) -> Option<(u16, u16)> {
+    // VP6 with alpha starts with the 24-bit offset of the alpha plane.
+    let data = if codec == VideoCodec::Vp6WithAlpha {
+        data.get(3..)?
+    } else {
+        data
+    };
+
+    let (&frame_info, data) = data.split_first()?;
+    if frame_info & 0b1000_0000 != 0 {
+        // Only keyframes carry the frame size.
+        return None;
+    }
+
+    let (&version, data) = data.split_first()?;
+    let separated_coeff = frame_info & 0b1 != 0;
+    let filter_header = version & 0b110;
+    let data = if separated_coeff || filter_header == 0 {
+        // Skip over the offset of the second partition.
+        data.get(2..)?
+    } else {
+        data
+    };
+
+    let macroblock_rows = u16::from(*data.first()?);
+    let macroblock_cols = u16::from(*data.get(1)?);
+    Some((
+        (macroblock_cols * 16).saturating_sub(horizontal_adjustment.into()),
+        (macroblock_rows * 16).saturating_sub(vertical_adjustment.into()),
+    ))
+}
+
+/// Convert the format of an FLV audio tag into the equivalent SWF sound
+/// format, if Flash Player supports it in both.
+fn sound_format(audio: &flv::AudioData<'_>) -> Option<SoundFormat> {
+    let (compression, sample_rate) = match audio.format {
+        flv::SoundFormat::LinearPcmPlatformEndian => {
+            (AudioCompression::UncompressedUnknownEndian, audio.rate.hz())
+        }
+        flv::SoundFormat::Adpcm => (AudioCompression::Adpcm, audio.rate.hz()),
+        flv::SoundFormat::Mp3 => (AudioCompression::Mp3, audio.rate.hz()),
+        flv::SoundFormat::LinearPcmLittleEndian => {
+            (AudioCompression::Uncompressed, audio.rate.hz())
+        }
+        flv::SoundFormat::Nellymoser16kHzMono => (AudioCompression::Nellymoser, 16000),
+        flv::SoundFormat::Nellymoser8kHzMono => (AudioCompression::Nellymoser, 8000),
+        flv::SoundFormat::Nellymoser => (AudioCompression::Nellymoser, audio.rate.hz()),
+        flv::SoundFormat::Speex => (AudioCompression::Speex, 16000),
+        flv::SoundFormat::Mp3_8kHz => (AudioCompression::Mp3, 8000),
+        _ => return None,
+    };
+
+    Some(SoundFormat {
+        compression,
+        sample_rate: sample_rate as u16,
+        is_stereo: audio.sound_type == flv::SoundType::Stereo,
+        is_16_bit: audio.size == flv::SoundSize::Bits16,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gc_arena::rootless_arena;
+
+    /// An FLV script data tag with the given timestamp.
+    fn tag(timestamp: u32) -> Vec<u8> {
+        let [_, a, b, c] = timestamp.to_be_bytes();
+        vec![18, 0, 0, 1, a, b, c, 0, 0, 0, 0, 5, 0, 0, 0, 12]
+    }
+
+    fn flv_stream_type() -> NetStreamType {
+        NetStreamType::Flv {
+            header: flv::Header {
+                version: 1,
+                type_flags: flv::TypeFlags::HAS_VIDEO,
+                data_offset: flv::Header::LENGTH,
+            },
+            video_stream: None,
+            frame_id: 0,
+        }
+    }
+
+    fn with_data_generation_stream<F>(callback: F)
+    where
+        F: for<'gc> FnOnce(&mut NetStreamData<'gc>),
+    {
+        rootless_arena(|mc| {
+            let stream = NetStream::new(mc, None);
+            let mut write = stream.0.write(mc);
+            write.is_data_generation = true;
+            write.stream_type = Some(flv_stream_type());
+            callback(&mut write);
+        })
+    }
+
+    #[test]
+    fn append_bytes_buffers_tags() {
+        with_data_generation_stream(|data| {
+            assert!(!data.append_generated_data(&tag(0)));
+            // Incomplete tags are buffered once the rest of them is appended.
+            let second = tag(500);
+            data.append_generated_data(&second[..6]);
+            assert_eq!(data.buffered_length(), 0.0);
+            data.append_generated_data(&second[6..]);
+            assert_eq!(data.buffered_length(), 500.0);
+            assert_eq!(data.buffer.len(), 32);
+        });
+    }
+
+    #[test]
+    fn append_bytes_continues_ended_stream() {
+        with_data_generation_stream(|data| {
+            data.has_ended = true;
+            assert!(data.append_generated_data(&tag(0)));
+            assert!(!data.has_ended);
+
+            data.apply_append_bytes_action(AppendBytesAction::EndSequence);
+            data.has_ended = true;
+            assert!(!data.append_generated_data(&tag(100)));
+            assert!(data.has_ended);
+        });
+    }
+
+    #[test]
+    fn append_bytes_action_reset_begin() {
+        with_data_generation_stream(|data| {
+            data.append_generated_data(&tag(0));
+            data.append_generated_data(&tag(1000));
+            data.offset = 16;
+            data.apply_append_bytes_action(AppendBytesAction::EndSequence);
+
+            assert!(!data.apply_append_bytes_action(AppendBytesAction::ResetBegin));
+            // The next data starts with a new FLV header.
+            assert!(data.stream_type.is_none());
+            assert!(data.buffer.is_empty());
+            assert_eq!(data.offset, 0);
+            assert!(!data.is_complete);
+            assert!(data.needs_time_sync);
+            assert!(data.is_buffering);
+        });
+    }
+
+    #[test]
+    fn append_bytes_action_reset_seek() {
+        with_data_generation_stream(|data| {
+            data.stream_time = 2000.0;
+            data.append_generated_data(&tag(2000));
+            data.has_ended = true;
+
+            assert!(data.apply_append_bytes_action(AppendBytesAction::ResetSeek));
+            assert!(!data.has_ended);
+            // The next data continues with tags of the same stream.
+            assert!(data.stream_type.is_some());
+            assert!(data.buffer.is_empty());
+            assert!(data.needs_time_sync);
+            assert_eq!(data.buffered_length(), 0.0);
+
+            data.append_generated_data(&tag(2500));
+            assert_eq!(data.buffered_length(), 500.0);
+        });
+    }
+
+    #[test]
+    fn append_bytes_action_end_sequence() {
+        with_data_generation_stream(|data| {
+            data.append_generated_data(&tag(0));
+            data.has_ended = true;
+
+            assert!(!data.apply_append_bytes_action(AppendBytesAction::EndSequence));
+            assert!(data.is_complete);
+            assert!(data.has_ended);
+            assert_eq!(data.buffer.len(), 16);
+        });
     }
 }
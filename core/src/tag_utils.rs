@@ -113,12 +113,12 @@ impl SwfMovie {
     }
 
     /// Construct a movie based on a loaded image (JPEG, GIF or PNG).
-    pub fn from_loaded_image(url: String, length: usize) -> Self {
+    pub fn from_loaded_image(url: String, loader_url: Option<String>, length: usize) -> Self {
         Self {
             header: HeaderExt::default_with_uncompressed_len(length as u32),
             data: vec![],
             url,
-            loader_url: None,
+            loader_url,
             parameters: Vec::new(),
             encoding: swf::UTF_8,
             compressed_len: length,
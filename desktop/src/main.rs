@@ -9,6 +9,7 @@
 mod audio;
 mod custom_event;
 mod executor;
+mod microphone;
 mod navigator;
 mod storage;
 mod task;
@@ -20,9 +21,12 @@ use anyhow::{anyhow, Context, Error};
 use clap::Parser;
 use isahc::{config::RedirectPolicy, prelude::*, HttpClient};
 use rfd::FileDialog;
+use ruffle_core::backend::camera::SequenceCameraBackend;
+use ruffle_core::backend::microphone::WavMicrophoneBackend;
 use ruffle_core::{
     config::Letterbox, events::KeyCode, tag_utils::SwfMovie, LoadBehavior, Player, PlayerBuilder,
-    PlayerEvent, StageDisplayState, StageScaleMode, StaticCallstack, ViewportDimensions,
+    PlayerEvent, SandboxType, StageDisplayState, StageScaleMode, StaticCallstack,
+    ViewportDimensions,
 };
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
@@ -141,6 +145,49 @@ struct Opt {
     /// The version of the player to emulate
     #[clap(long)]
     player_version: Option<u8>,
+
+    /// Enforce Flash Player's cross-domain security sandbox, including
+    /// policy files for sockets and loaded content.
+    #[clap(long, action)]
+    security_sandbox: bool,
+
+    /// Play a WAV file through the microphone, instead of capturing audio
+    /// from the computer's microphones.
+    #[clap(long)]
+    microphone_file: Option<PathBuf>,
+
+    /// Play back a video through the camera. This is either a YUV4MPEG2
+    /// (`.y4m`) file, or a directory of PNG or JPEG images which are shown
+    /// in alphabetical order at 15 frames per second.
+    #[clap(long)]
+    camera_file: Option<PathBuf>,
+}
+
+/// Load a camera that plays back a `.y4m` file or a directory of images.
+fn load_camera_file(path: &Path) -> Result<SequenceCameraBackend, Error> {
+    let name = path
+        .file_name()
+        .map_or_else(|| "Video File".into(), |name| name.to_string_lossy());
+    if path.is_dir() {
+        let mut paths = std::fs::read_dir(path)
+            .with_context(|| format!("Couldn't read camera directory {}", path.display()))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.retain(|path| path.is_file());
+        paths.sort();
+        let images = paths
+            .iter()
+            .map(|path| {
+                std::fs::read(path)
+                    .with_context(|| format!("Couldn't read camera image {}", path.display()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SequenceCameraBackend::from_images(name, images, 15)?)
+    } else {
+        let data = std::fs::read(path)
+            .with_context(|| format!("Couldn't read camera file {}", path.display()))?;
+        Ok(SequenceCameraBackend::from_y4m(name, &data)?)
+    }
 }
 
 #[cfg(feature = "render_trace")]
@@ -305,6 +352,22 @@ impl App {
             }
         };
 
+        match &opt.microphone_file {
+            Some(path) => {
+                let data = std::fs::read(path)
+                    .with_context(|| format!("Couldn't read microphone file {}", path.display()))?;
+                let name = path
+                    .file_name()
+                    .map_or_else(|| "WAV File".into(), |name| name.to_string_lossy());
+                builder = builder.with_microphone(WavMicrophoneBackend::new(name, &data)?);
+            }
+            None => builder = builder.with_microphone(microphone::CpalMicrophoneBackend::new()),
+        }
+
+        if let Some(path) = &opt.camera_file {
+            builder = builder.with_camera(load_camera_file(path)?);
+        }
+
         let (executor, channel) = GlutinAsyncExecutor::new(event_loop.create_proxy());
         let navigator = navigator::ExternalNavigatorBackend::new(
             movie_url.to_owned(),
@@ -347,6 +410,16 @@ impl App {
             .with_load_behavior(opt.load_behavior)
             .with_spoofed_url(opt.spoof_url.clone().map(|url| url.to_string()))
             .with_player_version(opt.player_version);
+        if opt.security_sandbox {
+            let sandbox_type = if movie_url.scheme() == "file" {
+                SandboxType::LocalWithNetwork
+            } else {
+                SandboxType::Remote
+            };
+            builder = builder
+                .with_security_sandbox(true)
+                .with_sandbox_type(sandbox_type);
+        }
 
         let player = builder.build();
 
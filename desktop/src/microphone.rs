@@ -0,0 +1,184 @@
+use anyhow::{anyhow, Context, Error};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use ruffle_core::backend::microphone::MicrophoneBackend;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How many seconds of audio to keep while nothing is taking it.
+const MAX_BUFFERED_SECONDS: usize = 1;
+
+/// Captures audio from the computer's input devices.
+///
+/// The default input device is always the first microphone.
+pub struct CpalMicrophoneBackend {
+    devices: Vec<cpal::Device>,
+    names: Vec<String>,
+    inputs: HashMap<usize, Input>,
+}
+
+/// An input device that is being captured from.
+struct Input {
+    /// Capturing stops when the stream is dropped.
+    _stream: cpal::Stream,
+    sample_rate: u32,
+
+    /// Mono samples that have been captured but not taken yet.
+    samples: Arc<Mutex<VecDeque<f32>>>,
+
+    /// The position between captured samples at which the next resampled
+    /// sample falls.
+    phase: f64,
+}
+
+impl CpalMicrophoneBackend {
+    pub fn new() -> Self {
+        let host = cpal::default_host();
+        let mut devices: Vec<cpal::Device> = match host.input_devices() {
+            Ok(devices) => devices.collect(),
+            Err(e) => {
+                tracing::error!("Unable to list input devices: {}", e);
+                Vec::new()
+            }
+        };
+        let default_name = host
+            .default_input_device()
+            .and_then(|device| device.name().ok());
+        if let Some(default_name) = default_name {
+            if let Some(i) = devices
+                .iter()
+                .position(|device| device.name().ok().as_ref() == Some(&default_name))
+            {
+                let device = devices.remove(i);
+                devices.insert(0, device);
+            }
+        }
+        let names = devices
+            .iter()
+            .map(|device| device.name().unwrap_or_else(|_| "Microphone".to_string()))
+            .collect();
+
+        Self {
+            devices,
+            names,
+            inputs: HashMap::new(),
+        }
+    }
+
+    fn start_input(device: &cpal::Device) -> Result<Input, Error> {
+        let config = device
+            .default_input_config()
+            .context("Failed to get default input config")?;
+        let sample_format = config.sample_format();
+        let config = cpal::StreamConfig::from(config);
+        let channels = usize::from(config.channels.max(1));
+        let sample_rate = config.sample_rate.0;
+        let samples = Arc::new(Mutex::new(VecDeque::new()));
+
+        let stream = {
+            let samples = samples.clone();
+            let max_len = sample_rate as usize * MAX_BUFFERED_SECONDS;
+            let push = move |data: &[f32]| {
+                let mut samples = samples.lock().expect("Microphone lock poisoned");
+                samples.extend(
+                    data.chunks_exact(channels)
+                        .map(|frame| frame.iter().sum::<f32>() / channels as f32),
+                );
+                let excess = samples.len().saturating_sub(max_len);
+                samples.drain(..excess);
+            };
+            let error_handler = move |err| tracing::error!("Microphone stream error: {}", err);
+
+            match sample_format {
+                cpal::SampleFormat::F32 => device.build_input_stream(
+                    &config,
+                    move |data: &[f32], _| push(data),
+                    error_handler,
+                    None,
+                ),
+                cpal::SampleFormat::I16 => device.build_input_stream(
+                    &config,
+                    move |data: &[i16], _| {
+                        let data: Vec<f32> = data
+                            .iter()
+                            .map(|&sample| f32::from(sample) / 32768.0)
+                            .collect();
+                        push(&data)
+                    },
+                    error_handler,
+                    None,
+                ),
+                cpal::SampleFormat::U16 => device.build_input_stream(
+                    &config,
+                    move |data: &[u16], _| {
+                        let data: Vec<f32> = data
+                            .iter()
+                            .map(|&sample| (f32::from(sample) - 32768.0) / 32768.0)
+                            .collect();
+                        push(&data)
+                    },
+                    error_handler,
+                    None,
+                ),
+                _ => anyhow::bail!("Unsupported sample format {sample_format:?}"),
+            }?
+        };
+
+        stream.play().context("Couldn't start the input stream")?;
+
+        Ok(Input {
+            _stream: stream,
+            sample_rate,
+            samples,
+            phase: 0.0,
+        })
+    }
+}
+
+impl MicrophoneBackend for CpalMicrophoneBackend {
+    fn names(&self) -> Vec<String> {
+        self.names.clone()
+    }
+
+    fn open(&mut self, index: usize) -> bool {
+        if self.inputs.contains_key(&index) {
+            return true;
+        }
+        let result = self
+            .devices
+            .get(index)
+            .ok_or_else(|| anyhow!("No such input device"))
+            .and_then(Self::start_input);
+        match result {
+            Ok(input) => {
+                self.inputs.insert(index, input);
+                true
+            }
+            Err(e) => {
+                tracing::error!("Unable to open microphone: {}", e);
+                false
+            }
+        }
+    }
+
+    fn capture(&mut self, index: usize, sample_rate: u32, _elapsed: Duration) -> Vec<f32> {
+        let Some(input) = self.inputs.get_mut(&index) else {
+            return Vec::new();
+        };
+        let captured: Vec<f32> = input
+            .samples
+            .lock()
+            .expect("Microphone lock poisoned")
+            .drain(..)
+            .collect();
+
+        let step = f64::from(input.sample_rate) / f64::from(sample_rate.max(1));
+        let mut samples = Vec::new();
+        while let Some(&sample) = captured.get(input.phase as usize) {
+            samples.push(sample);
+            input.phase += step;
+        }
+        input.phase = (input.phase - captured.len() as f64).max(0.0);
+        samples
+    }
+}
@@ -1,16 +1,25 @@
 //! Navigator backend for web
 
 use crate::custom_event::RuffleEvent;
+use futures::AsyncReadExt;
 use isahc::{
-    config::RedirectPolicy, prelude::*, AsyncReadResponseExt, HttpClient, Request as IsahcRequest,
+    config::RedirectPolicy, prelude::*, AsyncBody, AsyncReadResponseExt, HttpClient,
+    Request as IsahcRequest, Response as IsahcResponse,
 };
 use ruffle_core::backend::navigator::{
-    NavigationMethod, NavigatorBackend, OwnedFuture, Request, Response,
+    ConnectionState, NavigationMethod, NavigatorBackend, OwnedFuture, Request, Response,
+    ResponseBody, SocketAction, StreamingResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::pin::Pin;
 use std::rc::Rc;
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread;
+use std::time::Duration;
 use url::Url;
 use winit::event_loop::EventLoopProxy;
 
@@ -63,6 +72,14 @@ impl ExternalNavigatorBackend {
             upgrade_to_https,
         }
     }
+
+    /// Resolve the URL of a request against the movie's URL.
+    fn resolve_url(&self, url: &str) -> Result<Url, Error> {
+        match self.base_url.join(url) {
+            Ok(url) => Ok(self.pre_process_url(url)),
+            Err(e) => Err(Error::FetchError(format!("Invalid URL {url}: {e}"))),
+        }
+    }
 }
 
 impl NavigatorBackend for ExternalNavigatorBackend {
@@ -114,16 +131,11 @@ impl NavigatorBackend for ExternalNavigatorBackend {
 
     fn fetch(&self, request: Request) -> OwnedFuture<Response, Error> {
         // TODO: honor sandbox type (local-with-filesystem, local-with-network, remote, ...)
-        let full_url = match self.base_url.join(request.url()) {
+        let processed_url = match self.resolve_url(request.url()) {
             Ok(url) => url,
-            Err(e) => {
-                let msg = format!("Invalid URL {}: {e}", request.url());
-                return Box::pin(async move { Err(Error::FetchError(msg)) });
-            }
+            Err(e) => return Box::pin(async move { Err(e) }),
         };
 
-        let processed_url = self.pre_process_url(full_url);
-
         let client = self.client.clone();
 
         match processed_url.scheme() {
@@ -158,36 +170,7 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 Ok(Response { url, body })
             }),
             _ => Box::pin(async move {
-                let client =
-                    client.ok_or_else(|| Error::FetchError("Network unavailable".to_string()))?;
-
-                let isahc_request = match request.method() {
-                    NavigationMethod::Get => IsahcRequest::get(processed_url.to_string()),
-                    NavigationMethod::Post => IsahcRequest::post(processed_url.to_string()),
-                };
-
-                let (body_data, _) = request.body().clone().unwrap_or_default();
-                let body = isahc_request
-                    .body(body_data)
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
-
-                let mut response = client
-                    .send_async(body)
-                    .await
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
-
-                if !response.status().is_success() {
-                    return Err(Error::FetchError(format!(
-                        "HTTP status is not ok, got {}",
-                        response.status()
-                    )));
-                }
-
-                let url = if let Some(uri) = response.effective_uri() {
-                    uri.to_string()
-                } else {
-                    processed_url.into()
-                };
+                let (url, mut response) = send_request(client, request, processed_url).await?;
 
                 let mut body = vec![];
                 response
@@ -200,6 +183,29 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         }
     }
 
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, Error> {
+        let processed_url = match self.resolve_url(request.url()) {
+            Ok(url) => url,
+            Err(e) => return Box::pin(async move { Err(e) }),
+        };
+
+        // Local files are read all at once.
+        if processed_url.scheme() == "file" {
+            let fetch = self.fetch(request);
+            return Box::pin(async move { Ok(fetch.await?.into()) });
+        }
+
+        let client = self.client.clone();
+        Box::pin(async move {
+            let (url, response) = send_request(client, request, processed_url).await?;
+            Ok(StreamingResponse {
+                url,
+                expected_length: response.body().len(),
+                body: Box::new(HttpBody(response.into_body())),
+            })
+        })
+    }
+
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
         self.channel.send(future).expect("working channel send");
 
@@ -210,6 +216,66 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         }
     }
 
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        thread::spawn(move || {
+            let stream = match connect_with_timeout(&host, port, timeout) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    tracing::warn!("Failed to connect to {host}:{port}: {e}");
+                    let state = if e.kind() == io::ErrorKind::TimedOut {
+                        ConnectionState::TimedOut
+                    } else {
+                        ConnectionState::Failed
+                    };
+                    let _ = sender.send(SocketAction::Connect(state));
+                    return;
+                }
+            };
+            let _ = sender.send(SocketAction::Connect(ConnectionState::Connected));
+
+            // Writes happen on this thread, and reads on another one.
+            let mut reader = match stream.try_clone() {
+                Ok(reader) => reader,
+                Err(_) => {
+                    let _ = sender.send(SocketAction::Close);
+                    return;
+                }
+            };
+            thread::spawn(move || {
+                let mut buffer = vec![0; 4096];
+                loop {
+                    match reader.read(&mut buffer) {
+                        Ok(0) | Err(_) => break,
+                        Ok(len) => {
+                            if sender
+                                .send(SocketAction::Data(buffer[..len].to_vec()))
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                    }
+                }
+                let _ = sender.send(SocketAction::Close);
+            });
+
+            let mut writer = stream;
+            for data in receiver {
+                if writer.write_all(&data).is_err() {
+                    break;
+                }
+            }
+            let _ = writer.shutdown(Shutdown::Both);
+        });
+    }
+
     fn pre_process_url(&self, mut url: Url) -> Url {
         if self.upgrade_to_https && url.scheme() == "http" && url.set_scheme("https").is_err() {
             tracing::error!("Url::set_scheme failed on: {}", url);
@@ -217,3 +283,76 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         url
     }
 }
+
+/// Send an HTTP request, returning the final URL and the response once its
+/// headers have arrived.
+async fn send_request(
+    client: Option<Rc<HttpClient>>,
+    request: Request,
+    url: Url,
+) -> Result<(String, IsahcResponse<AsyncBody>), Error> {
+    let client = client.ok_or_else(|| Error::FetchError("Network unavailable".to_string()))?;
+
+    let isahc_request = match request.method() {
+        NavigationMethod::Get => IsahcRequest::get(url.to_string()),
+        NavigationMethod::Post => IsahcRequest::post(url.to_string()),
+    };
+
+    let (body_data, _) = request.body().clone().unwrap_or_default();
+    let body = isahc_request
+        .body(body_data)
+        .map_err(|e| Error::FetchError(e.to_string()))?;
+
+    let response = client
+        .send_async(body)
+        .await
+        .map_err(|e| Error::FetchError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(Error::FetchError(format!(
+            "HTTP status is not ok, got {}",
+            response.status()
+        )));
+    }
+
+    let url = if let Some(uri) = response.effective_uri() {
+        uri.to_string()
+    } else {
+        url.into()
+    };
+
+    Ok((url, response))
+}
+
+/// The body of an HTTP response, read as it downloads.
+struct HttpBody(AsyncBody);
+
+impl ResponseBody for HttpBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        Box::pin(async move {
+            let mut chunk = vec![0; 64 * 1024];
+            let length = self
+                .0
+                .read(&mut chunk)
+                .await
+                .map_err(|e| Error::FetchError(e.to_string()))?;
+            if length == 0 {
+                return Ok(None);
+            }
+            chunk.truncate(length);
+            Ok(Some(chunk))
+        })
+    }
+}
+
+/// Connect to the first address of `host` that accepts the connection.
+fn connect_with_timeout(host: &str, port: u16, timeout: Duration) -> io::Result<TcpStream> {
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "No addresses found");
+    for address in (host, port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
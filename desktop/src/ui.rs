@@ -1,7 +1,11 @@
 use anyhow::{Context, Error};
 use arboard::Clipboard;
-use rfd::{MessageButtons, MessageDialog, MessageLevel};
-use ruffle_core::backend::ui::{FullscreenError, MouseCursor, UiBackend};
+use chrono::{DateTime, Utc};
+use rfd::{AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageLevel};
+use ruffle_core::backend::ui::{
+    FileDialogFuture, FileDialogResult, FileFilter, FullscreenError, MouseCursor, UiBackend,
+};
+use std::path::PathBuf;
 use std::rc::Rc;
 use tracing::error;
 use winit::window::{Fullscreen, Window};
@@ -30,6 +34,54 @@ Some parts of the content may not work as expected.
 See the following link for more info:
 https://github.com/ruffle-rs/ruffle/wiki/Frequently-Asked-Questions-For-Users";
 
+/// A file chosen in a native file dialog.
+struct DesktopFileDialogResult {
+    path: PathBuf,
+}
+
+impl DesktopFileDialogResult {
+    fn new(handle: FileHandle) -> Box<dyn FileDialogResult> {
+        Box::new(Self {
+            path: handle.path().to_owned(),
+        })
+    }
+}
+
+impl FileDialogResult for DesktopFileDialogResult {
+    fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn size(&self) -> Option<u64> {
+        std::fs::metadata(&self.path).ok().map(|m| m.len())
+    }
+
+    fn creation_time(&self) -> Option<DateTime<Utc>> {
+        let time = std::fs::metadata(&self.path).ok()?.created().ok()?;
+        Some(time.into())
+    }
+
+    fn modification_time(&self) -> Option<DateTime<Utc>> {
+        let time = std::fs::metadata(&self.path).ok()?.modified().ok()?;
+        Some(time.into())
+    }
+
+    fn contents(&self) -> Option<Vec<u8>> {
+        std::fs::read(&self.path).ok()
+    }
+
+    fn write(&self, data: &[u8]) -> bool {
+        if let Err(e) = std::fs::write(&self.path, data) {
+            error!("Couldn't write to {}: {}", self.path.display(), e);
+            return false;
+        }
+        true
+    }
+}
+
 const DOWNLOAD_FAILED_MESSAGE: &str = "Ruffle failed to open or download this file.";
 
 impl UiBackend for DesktopUiBackend {
@@ -97,4 +149,48 @@ impl UiBackend for DesktopUiBackend {
 
     // Unused on desktop
     fn open_virtual_keyboard(&self) {}
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Option<FileDialogFuture> {
+        let mut dialog = AsyncFileDialog::new();
+        for filter in &filters {
+            dialog = dialog.add_filter(&filter.description, filter.extensions.as_slice());
+        }
+
+        Some(if multiple {
+            Box::pin(async move {
+                dialog
+                    .pick_files()
+                    .await
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(DesktopFileDialogResult::new)
+                    .collect()
+            })
+        } else {
+            Box::pin(async move {
+                dialog
+                    .pick_file()
+                    .await
+                    .into_iter()
+                    .map(DesktopFileDialogResult::new)
+                    .collect()
+            })
+        })
+    }
+
+    fn display_file_save_dialog(&mut self, file_name: String) -> Option<FileDialogFuture> {
+        let dialog = AsyncFileDialog::new().set_file_name(&file_name);
+        Some(Box::pin(async move {
+            dialog
+                .save_file()
+                .await
+                .into_iter()
+                .map(DesktopFileDialogResult::new)
+                .collect()
+        }))
+    }
 }
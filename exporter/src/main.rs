@@ -5,7 +5,8 @@ use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::PlayerBuilder;
+use ruffle_core::{PlayerBuilder, ViewportDimensions};
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::WgpuRenderBackend;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -72,6 +73,11 @@ struct Opt {
     #[clap(long, short, default_value = "high")]
     power: PowerPreference,
 
+    /// Render on the CPU instead of using a graphics device.
+    /// This is slower, but works on machines without a GPU.
+    #[clap(long, action)]
+    software: bool,
+
     /// Location to store a wgpu trace output
     #[clap(long)]
     #[cfg(feature = "render_trace")]
@@ -83,8 +89,10 @@ struct Opt {
 }
 
 /// Captures a screenshot. The resulting image uses straight alpha
+///
+/// The software renderer is used if there are no wgpu `descriptors`.
 fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+    descriptors: Option<Arc<Descriptors>>,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
@@ -110,12 +118,20 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
+    let builder = if let Some(descriptors) = descriptors {
+        let target = TextureTarget::new(&descriptors.device, (width, height))
+            .map_err(|e| anyhow!(e.to_string()))?;
+        PlayerBuilder::new().with_renderer(
             WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
         )
+    } else {
+        PlayerBuilder::new().with_renderer(SoftwareRenderBackend::new(ViewportDimensions {
+            width,
+            height,
+            scale_factor: size.scale,
+        }))
+    };
+    let player = builder
         .with_movie(movie)
         .with_viewport_dimensions(width, height, size.scale)
         .build();
@@ -139,11 +155,15 @@ fn take_screenshot(
             match catch_unwind(|| {
                 player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
+                let renderer = player.renderer_mut();
+                if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                {
+                    renderer.capture_frame()
+                } else {
+                    renderer
+                        .downcast_mut::<SoftwareRenderBackend>()
+                        .map(|renderer| renderer.capture_frame())
+                }
             }) {
                 Ok(Some(image)) => result.push(image),
                 Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
@@ -195,7 +215,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
@@ -272,7 +292,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -380,21 +400,25 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-        backends: opt.graphics.into(),
-        dx12_shader_compiler: wgpu::Dx12Compiler::default(),
-    });
-    let (adapter, device, queue) =
-        futures::executor::block_on(WgpuRenderBackend::<TextureTarget>::request_device(
-            opt.graphics.into(),
-            instance,
-            None,
-            opt.power.into(),
-            trace_path(&opt),
-        ))
-        .map_err(|e| anyhow!(e.to_string()))?;
-
-    let descriptors = Arc::new(Descriptors::new(adapter, device, queue));
+    let descriptors = if opt.software {
+        None
+    } else {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: opt.graphics.into(),
+            dx12_shader_compiler: wgpu::Dx12Compiler::default(),
+        });
+        let (adapter, device, queue) =
+            futures::executor::block_on(WgpuRenderBackend::<TextureTarget>::request_device(
+                opt.graphics.into(),
+                instance,
+                None,
+                opt.power.into(),
+                trace_path(&opt),
+            ))
+            .map_err(|e| anyhow!(e.to_string()))?;
+
+        Some(Arc::new(Descriptors::new(adapter, device, queue)))
+    };
 
     if opt.swf.is_file() {
         capture_single_swf(descriptors, &opt)?;
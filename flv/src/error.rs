@@ -0,0 +1,39 @@
+use std::{borrow, error, fmt};
+
+/// A `Result` from reading FLV data.
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The data ended before a complete structure could be read.
+    ///
+    /// When reading a file that is still being downloaded, this means that
+    /// the read should be retried once more data is available.
+    EndOfData,
+
+    /// The data does not start with the `FLV` signature.
+    WrongMagic,
+
+    /// Invalid or unknown data was encountered.
+    InvalidData(borrow::Cow<'static, str>),
+}
+
+impl Error {
+    /// Helper method to create `Error::InvalidData`.
+    #[inline]
+    pub fn invalid_data(message: impl Into<borrow::Cow<'static, str>>) -> Self {
+        Self::InvalidData(message.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::EndOfData => f.write_str("Unexpected end of FLV data"),
+            Self::WrongMagic => f.write_str("Not an FLV file"),
+            Self::InvalidData(message) => write!(f, "Invalid data: {message}"),
+        }
+    }
+}
+
+impl error::Error for Error {}
@@ -0,0 +1,59 @@
+use crate::error::{Error, Result};
+use crate::read::Reader;
+use bitflags::bitflags;
+
+bitflags! {
+    /// Which kinds of media an FLV file declares that it contains.
+    ///
+    /// These flags are advisory; players are expected to handle whatever
+    /// tags actually appear in the file.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct TypeFlags: u8 {
+        const HAS_VIDEO = 0b0000_0001;
+        const HAS_AUDIO = 0b0000_0100;
+    }
+}
+
+/// The header at the start of every FLV file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// The FLV format version. This is always 1 for files produced by Flash.
+    pub version: u8,
+
+    /// What kinds of media this file declares that it contains.
+    pub type_flags: TypeFlags,
+
+    /// The byte offset of the end of the header.
+    pub data_offset: u32,
+}
+
+impl Header {
+    /// The length of the header in every version of the format.
+    pub const LENGTH: u32 = 9;
+
+    pub(crate) fn parse(reader: &mut Reader<'_>) -> Result<Self> {
+        let start = reader.position();
+        if reader.read_bytes(3)? != b"FLV" {
+            return Err(Error::WrongMagic);
+        }
+
+        let version = reader.read_u8()?;
+        let type_flags = TypeFlags::from_bits_truncate(reader.read_u8()?);
+        let data_offset = reader.read_u32()?;
+        if data_offset < Self::LENGTH {
+            return Err(Error::invalid_data("FLV header is too short"));
+        }
+
+        // Skip any header extensions, then the back-pointer of the
+        // (nonexistent) tag before the first one.
+        reader.set_position(start);
+        reader.read_bytes(data_offset as usize)?;
+        reader.read_u32()?;
+
+        Ok(Self {
+            version,
+            type_flags,
+            data_offset,
+        })
+    }
+}
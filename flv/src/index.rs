@@ -0,0 +1,242 @@
+use crate::error::Result;
+use crate::read::Reader;
+use crate::script::{get_property, ScriptData};
+use crate::tag::TagData;
+use flash_lso::types::Value;
+
+/// A point in an FLV file that playback can be started from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Keyframe {
+    /// The timestamp of the keyframe's tag, in milliseconds.
+    pub time: i32,
+
+    /// The byte offset of the keyframe's tag from the start of the file.
+    pub position: usize,
+}
+
+/// The seekable points of an FLV file, sorted by time.
+///
+/// An index can be built ahead of time from the `keyframes` property that
+/// many encoders add to `onMetaData`, or incrementally as tags are read.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyframeIndex {
+    keyframes: Vec<Keyframe>,
+}
+
+impl KeyframeIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build an index from the `keyframes` property of an `onMetaData` tag.
+    ///
+    /// Returns `None` if the metadata does not contain a usable index.
+    pub fn from_metadata(metadata: &ScriptData) -> Option<Self> {
+        let keyframes = metadata.get("keyframes")?;
+        let times = get_property(keyframes, "times")?;
+        let positions = get_property(keyframes, "filepositions")?;
+        let (Value::StrictArray(times), Value::StrictArray(positions)) = (times, positions) else {
+            return None;
+        };
+
+        let mut index = Self::new();
+        for (time, position) in times.iter().zip(positions) {
+            let (time, position): (&Value, &Value) = (time, position);
+            if let (Value::Number(time), Value::Number(position)) = (time, position) {
+                index.insert(Keyframe {
+                    time: (time * 1000.0) as i32,
+                    position: *position as usize,
+                });
+            }
+        }
+
+        (!index.is_empty()).then_some(index)
+    }
+
+    /// Build an index by reading every tag in a file.
+    ///
+    /// Video keyframes are indexed; in a file without video, every audio tag
+    /// is a keyframe. If the file is truncated, the tags that could be read
+    /// are indexed.
+    pub fn scan(data: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(data);
+        reader.read_header()?;
+
+        let mut index = Self::new();
+        let mut audio_index = Self::new();
+        while !reader.is_empty() {
+            let position = reader.position();
+            let tag = match reader.read_tag() {
+                Ok(tag) => tag,
+                Err(crate::Error::EndOfData) => break,
+                Err(e) => return Err(e),
+            };
+            let keyframe = Keyframe {
+                time: tag.timestamp,
+                position,
+            };
+            match tag.data {
+                TagData::Video(video) if video.frame_type.is_keyframe() => index.insert(keyframe),
+                TagData::Audio(_) => audio_index.insert(keyframe),
+                _ => {}
+            }
+        }
+
+        if index.is_empty() {
+            Ok(audio_index)
+        } else {
+            Ok(index)
+        }
+    }
+
+    /// Add a keyframe to the index.
+    ///
+    /// Keyframes that are already in the index are ignored.
+    pub fn insert(&mut self, keyframe: Keyframe) {
+        let i = self
+            .keyframes
+            .partition_point(|k| (k.time, k.position) < (keyframe.time, keyframe.position));
+        if self.keyframes.get(i) != Some(&keyframe) {
+            self.keyframes.insert(i, keyframe);
+        }
+    }
+
+    /// Find the keyframe to start playback from in order to display the
+    /// given time, in milliseconds.
+    ///
+    /// This is the last keyframe at or before `time`, or the first keyframe
+    /// if `time` is before all of them.
+    pub fn seek(&self, time: i32) -> Option<Keyframe> {
+        let i = self.keyframes.partition_point(|k| k.time <= time);
+        self.keyframes
+            .get(i.saturating_sub(1))
+            .or_else(|| self.keyframes.first())
+            .copied()
+    }
+
+    /// All keyframes in the index, sorted by time.
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    pub fn len(&self) -> usize {
+        self.keyframes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::{Header, TypeFlags};
+    use crate::tag::Tag;
+    use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::write::Writer;
+    use flash_lso::types::Element;
+
+    fn video_tag(timestamp: i32, frame_type: FrameType) -> Tag<'static> {
+        Tag {
+            timestamp,
+            stream_id: 0,
+            data: TagData::Video(VideoData {
+                frame_type,
+                codec_id: CodecId::SorensonH263,
+                data: VideoPacket::Data(&[0, 0, 0x84, 0]),
+            }),
+        }
+    }
+
+    #[test]
+    fn seek() {
+        let mut index = KeyframeIndex::new();
+        for (time, position) in [(2000, 300), (0, 13), (1000, 150), (1000, 150)] {
+            index.insert(Keyframe { time, position });
+        }
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.seek(-5).map(|k| k.position), Some(13));
+        assert_eq!(index.seek(999).map(|k| k.position), Some(13));
+        assert_eq!(index.seek(1000).map(|k| k.position), Some(150));
+        assert_eq!(index.seek(5000).map(|k| k.position), Some(300));
+        assert_eq!(KeyframeIndex::new().seek(0), None);
+    }
+
+    #[test]
+    fn scan() {
+        let mut writer = Writer::new(Vec::new());
+        writer
+            .write_header(&Header {
+                version: 1,
+                type_flags: TypeFlags::HAS_VIDEO,
+                data_offset: Header::LENGTH,
+            })
+            .unwrap();
+        let mut positions = vec![];
+        for (timestamp, frame_type) in [
+            (0, FrameType::Keyframe),
+            (40, FrameType::Interframe),
+            (80, FrameType::Keyframe),
+        ] {
+            positions.push(writer.get_ref().len());
+            writer.write_tag(&video_tag(timestamp, frame_type)).unwrap();
+        }
+        let data = writer.into_inner();
+
+        let index = KeyframeIndex::scan(&data).unwrap();
+        assert_eq!(
+            index.keyframes(),
+            &[
+                Keyframe {
+                    time: 0,
+                    position: positions[0],
+                },
+                Keyframe {
+                    time: 80,
+                    position: positions[2],
+                },
+            ]
+        );
+
+        // Truncated files index what they can.
+        let index = KeyframeIndex::scan(&data[..data.len() - 1]).unwrap();
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn from_metadata() {
+        let numbers = |values: &[f64]| {
+            Value::StrictArray(values.iter().map(|n| Value::Number(*n).into()).collect())
+        };
+        let metadata = ScriptData {
+            name: "onMetaData".to_string(),
+            value: Value::ECMAArray(
+                vec![],
+                vec![
+                    Element::new("duration", Value::Number(2.0)),
+                    Element::new(
+                        "keyframes",
+                        Value::Object(
+                            vec![
+                                Element::new("times", numbers(&[0.0, 1.5])),
+                                Element::new("filepositions", numbers(&[13.0, 4096.0])),
+                            ],
+                            None,
+                        ),
+                    ),
+                ],
+                2,
+            ),
+        };
+
+        let index = KeyframeIndex::from_metadata(&metadata).unwrap();
+        assert_eq!(
+            index.seek(2000),
+            Some(Keyframe {
+                time: 1500,
+                position: 4096,
+            })
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! # flv
+//!
+//! Library for reading and writing Adobe Flash Video (FLV) files.
+//!
+//! # Organization
+//!
+//! An FLV file consists of a `Header` followed by a sequence of `Tag`s, each
+//! of which carries a single packet of audio, video, or script data. All of
+//! the parsing in this library is done through a `Reader`, which is designed
+//! to work on files that are still being downloaded: any structure that
+//! cannot be read in full leaves the reader where it was, so the read can be
+//! retried once more data has arrived. Files can be written with a `Writer`.
+//!
+//! Script data tags are decoded with `flash_lso`, whose AMF0 `Value` type is
+//! used for their arguments. A `KeyframeIndex` maps times to the tags that
+//! playback can be started from, for seeking.
+
+mod error;
+mod header;
+mod index;
+mod read;
+mod script;
+mod sound;
+mod tag;
+mod video;
+mod write;
+
+pub use error::{Error, Result};
+pub use header::{Header, TypeFlags};
+pub use index::{Keyframe, KeyframeIndex};
+pub use read::Reader;
+pub use script::{write_amf0_value, ScriptData};
+pub use sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
+pub use tag::{Tag, TagData};
+pub use video::{CodecId, CommandFrame, FrameType, VideoData, VideoPacket};
+pub use write::Writer;
@@ -0,0 +1,192 @@
+use crate::error::{Error, Result};
+use crate::header::Header;
+use crate::tag::Tag;
+
+/// A cursor over (possibly incomplete) FLV data.
+///
+/// Every read either consumes a complete structure, or fails and leaves the
+/// reader at the position it started from. `Error::EndOfData` in particular
+/// means that the structure is truncated, not that it is invalid.
+#[derive(Clone, Debug)]
+pub struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Create a reader positioned at the start of `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Create a reader positioned at a given byte offset into `data`.
+    ///
+    /// This is intended to resume parsing at a position previously obtained
+    /// from `Reader::position`.
+    pub fn with_position(data: &'a [u8], pos: usize) -> Self {
+        Self { data, pos }
+    }
+
+    /// The byte offset of the next structure to be read.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Move the reader to a given byte offset.
+    pub fn set_position(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    /// All data that has not been read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.data.get(self.pos..).unwrap_or_default()
+    }
+
+    /// Returns true if the reader has consumed all of its data.
+    pub fn is_empty(&self) -> bool {
+        self.remaining().is_empty()
+    }
+
+    /// Read the FLV file header.
+    ///
+    /// The reader must be positioned at the start of the file. On success,
+    /// the reader is left at the first tag of the file.
+    pub fn read_header(&mut self) -> Result<Header> {
+        self.atomic(Header::parse)
+    }
+
+    /// Read the next tag, including the back-pointer that follows it.
+    pub fn read_tag(&mut self) -> Result<Tag<'a>> {
+        self.atomic(Tag::parse)
+    }
+
+    /// Skip over the next tag without decoding it, returning its timestamp.
+    ///
+    /// This is much cheaper than `read_tag` when only the timing of the
+    /// stream is of interest.
+    pub fn skip_tag(&mut self) -> Result<i32> {
+        self.atomic(Tag::skip)
+    }
+
+    /// Run a parse function, restoring the reader position if it fails.
+    fn atomic<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let start = self.pos;
+        let result = parse(self);
+        if result.is_err() {
+            self.pos = start;
+        }
+        result
+    }
+
+    pub(crate) fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).ok_or(Error::EndOfData)?;
+        let bytes = self.data.get(self.pos..end).ok_or(Error::EndOfData)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub(crate) fn read_u24(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes(3)?;
+        Ok(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::TypeFlags;
+    use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
+    use crate::tag::TagData;
+
+    const HEADER: &[u8] = &[b'F', b'L', b'V', 1, 5, 0, 0, 0, 9, 0, 0, 0, 0];
+
+    const AUDIO_TAG: &[u8] = &[
+        8, 0, 0, 3, 0, 1, 0, 0, 0, 0, 0, 0x2E, 0xAB, 0xCD, 0, 0, 0, 14,
+    ];
+
+    const MALFORMED_SCRIPT_TAG: &[u8] =
+        &[18, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0xFF, 0xFF, 0, 0, 0, 13];
+
+    #[test]
+    fn read_header() {
+        let mut reader = Reader::new(HEADER);
+        let header = reader.read_header().unwrap();
+        assert_eq!(
+            header,
+            Header {
+                version: 1,
+                type_flags: TypeFlags::HAS_AUDIO | TypeFlags::HAS_VIDEO,
+                data_offset: 9,
+            }
+        );
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn read_header_wrong_magic() {
+        let mut reader = Reader::new(b"FWS\x0A\x00\x00\x00\x00\x00");
+        assert_eq!(reader.read_header(), Err(Error::WrongMagic));
+        assert_eq!(reader.position(), 0);
+    }
+
+    #[test]
+    fn read_audio_tag() {
+        let mut reader = Reader::new(AUDIO_TAG);
+        let tag = reader.read_tag().unwrap();
+        assert_eq!(tag.timestamp, 256);
+        assert_eq!(
+            tag.data,
+            TagData::Audio(AudioData {
+                format: SoundFormat::Mp3,
+                rate: SoundRate::R44_000,
+                size: SoundSize::Bits16,
+                sound_type: SoundType::Mono,
+                data: AudioDataType::Raw(&[0xAB, 0xCD]),
+            })
+        );
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn read_truncated_tag() {
+        for len in 0..AUDIO_TAG.len() {
+            let mut reader = Reader::new(&AUDIO_TAG[..len]);
+            assert_eq!(reader.read_tag(), Err(Error::EndOfData));
+            assert_eq!(reader.position(), 0);
+        }
+    }
+
+    #[test]
+    fn read_malformed_script_tag() {
+        let mut reader = Reader::new(MALFORMED_SCRIPT_TAG);
+        let tag = reader.read_tag().unwrap();
+        assert_eq!(
+            tag.data,
+            TagData::Unknown {
+                tag_type: 18,
+                data: &[0xFF, 0xFF],
+            }
+        );
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn skip_tag() {
+        let mut reader = Reader::new(AUDIO_TAG);
+        assert_eq!(reader.skip_tag(), Ok(256));
+        assert!(reader.is_empty());
+
+        let mut reader = Reader::new(&AUDIO_TAG[..AUDIO_TAG.len() - 1]);
+        assert_eq!(reader.skip_tag(), Err(Error::EndOfData));
+        assert_eq!(reader.position(), 0);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::error::{Error, Result};
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::{AMFVersion, Element, Lso, Value};
+
+/// The contents of a script data tag.
+///
+/// Script data tags call a named handler on the player's client object,
+/// such as `onMetaData` or `onCuePoint`, with a single AMF0 value as its
+/// argument.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptData {
+    /// The name of the handler to call.
+    pub name: String,
+
+    /// The argument to pass to the handler.
+    ///
+    /// This is `Value::Undefined` if the tag did not contain an argument.
+    pub value: Value,
+}
+
+impl ScriptData {
+    pub(crate) fn parse(data: &[u8]) -> Result<Self> {
+        let mut decoder = AMF0Decoder::default();
+        let (rest, name) = decoder
+            .parse_single_element(data)
+            .map_err(|_| Error::invalid_data("Invalid script data name"))?;
+        let name: &Value = &name;
+        let name = match name {
+            Value::String(name) => name.clone(),
+            _ => return Err(Error::invalid_data("Script data name is not a string")),
+        };
+
+        let value = if rest.is_empty() {
+            Value::Undefined
+        } else {
+            let (_, value) = decoder
+                .parse_single_element(rest)
+                .map_err(|_| Error::invalid_data("Invalid script data value"))?;
+            let value: &Value = &value;
+            value.clone()
+        };
+
+        Ok(Self { name, value })
+    }
+
+    pub(crate) fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        write_amf0_value(&mut bytes, &Value::String(self.name.clone()))?;
+        if !matches!(self.value, Value::Undefined) {
+            write_amf0_value(&mut bytes, &self.value)?;
+        }
+        Ok(bytes)
+    }
+
+    /// Look up a property of the argument, if it is an object or associative
+    /// array.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        get_property(&self.value, name)
+    }
+}
+
+/// Look up a property of an AMF0 object or associative array.
+pub(crate) fn get_property<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+    let properties = match value {
+        Value::Object(properties, _) => properties,
+        Value::ECMAArray(_, properties, _) => properties,
+        _ => return None,
+    };
+    let element = properties.iter().find(|element| element.name == name)?;
+    let value: &Value = element.value();
+    Some(value)
+}
+
+/// Serialize a single AMF0 value onto the end of `output`.
+///
+/// This is also used by other crates that send AMF0 values, such as `rtmp`.
+pub fn write_amf0_value(output: &mut Vec<u8>, value: &Value) -> Result<()> {
+    // `flash_lso` only knows how to write whole LSO files, so we write a file
+    // containing just this value and cut the value back out of it. The
+    // element is preceded by the LSO header and its (empty) name, and followed
+    // by a padding byte.
+    let mut lso = Lso::new(vec![Element::new("", value.clone())], "", AMFVersion::AMF0);
+    let bytes = flash_lso::write::write_to_bytes(&mut lso)
+        .map_err(|_| Error::invalid_data("Unserializable AMF0 value"))?;
+    let start = flash_lso::write::header_length(&lso.header) as usize + 8;
+    let value = bytes
+        .get(start..bytes.len().saturating_sub(1))
+        .ok_or_else(|| Error::invalid_data("Unserializable AMF0 value"))?;
+    output.extend_from_slice(value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let script_data = ScriptData {
+            name: "onCuePoint".to_string(),
+            value: Value::Object(
+                vec![
+                    Element::new("name", Value::String("intro".to_string())),
+                    Element::new("time", Value::Number(1.5)),
+                ],
+                None,
+            ),
+        };
+        let bytes = script_data.to_bytes().unwrap();
+        assert_eq!(ScriptData::parse(&bytes), Ok(script_data.clone()));
+        assert_eq!(script_data.get("time"), Some(&Value::Number(1.5)));
+    }
+
+    #[test]
+    fn parse_without_argument() {
+        // AMF0 string "onLastSecond".
+        let bytes = b"\x02\x00\x0ConLastSecond";
+        let script_data = ScriptData::parse(bytes).unwrap();
+        assert_eq!(script_data.name, "onLastSecond");
+        assert_eq!(script_data.value, Value::Undefined);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::error::{Error, Result};
+
+/// The compression used by an audio tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundFormat {
+    LinearPcmPlatformEndian = 0,
+    Adpcm = 1,
+    Mp3 = 2,
+    LinearPcmLittleEndian = 3,
+    Nellymoser16kHzMono = 4,
+    Nellymoser8kHzMono = 5,
+    Nellymoser = 6,
+    G711ALawPcm = 7,
+    G711MuLawPcm = 8,
+    Aac = 10,
+    Speex = 11,
+    Mp3_8kHz = 14,
+    DeviceSpecific = 15,
+}
+
+impl SoundFormat {
+    fn from_u8(n: u8) -> Option<Self> {
+        Some(match n {
+            0 => Self::LinearPcmPlatformEndian,
+            1 => Self::Adpcm,
+            2 => Self::Mp3,
+            3 => Self::LinearPcmLittleEndian,
+            4 => Self::Nellymoser16kHzMono,
+            5 => Self::Nellymoser8kHzMono,
+            6 => Self::Nellymoser,
+            7 => Self::G711ALawPcm,
+            8 => Self::G711MuLawPcm,
+            10 => Self::Aac,
+            11 => Self::Speex,
+            14 => Self::Mp3_8kHz,
+            15 => Self::DeviceSpecific,
+            _ => return None,
+        })
+    }
+}
+
+/// The sample rate declared by an audio tag.
+///
+/// Some formats (AAC, Nellymoser at fixed rates, 8kHz MP3) ignore this
+/// field and use a rate implied by the format instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundRate {
+    R5_500 = 0,
+    R11_000 = 1,
+    R22_000 = 2,
+    R44_000 = 3,
+}
+
+impl SoundRate {
+    /// The sample rate in Hz.
+    pub fn hz(self) -> u32 {
+        match self {
+            Self::R5_500 => 5512,
+            Self::R11_000 => 11025,
+            Self::R22_000 => 22050,
+            Self::R44_000 => 44100,
+        }
+    }
+}
+
+/// The size of each uncompressed sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundSize {
+    Bits8 = 0,
+    Bits16 = 1,
+}
+
+/// The number of channels in an audio tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundType {
+    Mono = 0,
+    Stereo = 1,
+}
+
+/// The payload of an audio tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AudioDataType<'a> {
+    /// Encoded audio data, in the format given by the tag.
+    Raw(&'a [u8]),
+
+    /// An AAC `AudioSpecificConfig` structure.
+    AacSequenceHeader(&'a [u8]),
+
+    /// A raw AAC frame.
+    AacRaw(&'a [u8]),
+}
+
+/// The contents of an audio tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AudioData<'a> {
+    pub format: SoundFormat,
+    pub rate: SoundRate,
+    pub size: SoundSize,
+    pub sound_type: SoundType,
+    pub data: AudioDataType<'a>,
+}
+
+impl<'a> AudioData<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Result<Self> {
+        let (&flags, data) = data
+            .split_first()
+            .ok_or_else(|| Error::invalid_data("Empty audio tag"))?;
+
+        let format = SoundFormat::from_u8(flags >> 4)
+            .ok_or_else(|| Error::invalid_data("Invalid audio format"))?;
+        let rate = match (flags >> 2) & 0b11 {
+            0 => SoundRate::R5_500,
+            1 => SoundRate::R11_000,
+            2 => SoundRate::R22_000,
+            _ => SoundRate::R44_000,
+        };
+        let size = if flags & 0b10 != 0 {
+            SoundSize::Bits16
+        } else {
+            SoundSize::Bits8
+        };
+        let sound_type = if flags & 0b1 != 0 {
+            SoundType::Stereo
+        } else {
+            SoundType::Mono
+        };
+
+        let data = if format == SoundFormat::Aac {
+            match data.split_first() {
+                Some((0, data)) => AudioDataType::AacSequenceHeader(data),
+                Some((1, data)) => AudioDataType::AacRaw(data),
+                _ => return Err(Error::invalid_data("Invalid AAC packet type")),
+            }
+        } else {
+            AudioDataType::Raw(data)
+        };
+
+        Ok(Self {
+            format,
+            rate,
+            size,
+            sound_type,
+            data,
+        })
+    }
+}
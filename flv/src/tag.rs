@@ -0,0 +1,93 @@
+use crate::error::Result;
+use crate::read::Reader;
+use crate::script::ScriptData;
+use crate::sound::AudioData;
+use crate::video::VideoData;
+
+/// A single packet of data in an FLV file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tag<'a> {
+    /// The time, in milliseconds, at which this tag's data should be
+    /// presented, relative to the first tag in the file.
+    pub timestamp: i32,
+
+    /// The stream this tag belongs to. This is always 0 in FLV files.
+    pub stream_id: u32,
+
+    /// The contents of the tag.
+    pub data: TagData<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TagData<'a> {
+    /// A packet of audio data.
+    Audio(AudioData<'a>),
+
+    /// A single frame of video data.
+    Video(VideoData<'a>),
+
+    /// A call to a script handler, such as `onMetaData` or `onCuePoint`.
+    Script(ScriptData),
+
+    /// A tag that this library does not understand, such as an encrypted
+    /// packet or a tag type reserved by the format.
+    Unknown { tag_type: u8, data: &'a [u8] },
+}
+
+impl<'a> Tag<'a> {
+    pub(crate) const AUDIO: u8 = 8;
+    pub(crate) const VIDEO: u8 = 9;
+    pub(crate) const SCRIPT_DATA: u8 = 18;
+
+    /// The bit of the tag type that indicates an encrypted packet.
+    const FILTER: u8 = 0b0010_0000;
+
+    pub(crate) fn parse(reader: &mut Reader<'a>) -> Result<Self> {
+        let (tag_type, timestamp, stream_id, data) = Self::parse_envelope(reader)?;
+        let data = match tag_type {
+            Self::AUDIO => TagData::Audio(AudioData::parse(data)?),
+            Self::VIDEO => TagData::Video(VideoData::parse(data)?),
+            // Script data that can't be decoded is passed through as-is, so
+            // that a bad `onMetaData` doesn't stop the rest of the file from
+            // being read.
+            Self::SCRIPT_DATA => match ScriptData::parse(data) {
+                Ok(script_data) => TagData::Script(script_data),
+                Err(_) => TagData::Unknown { tag_type, data },
+            },
+            _ => TagData::Unknown {
+                tag_type: tag_type & !Self::FILTER,
+                data,
+            },
+        };
+
+        Ok(Self {
+            timestamp,
+            stream_id,
+            data,
+        })
+    }
+
+    /// Skip over a tag without decoding its contents, returning its
+    /// timestamp.
+    pub(crate) fn skip(reader: &mut Reader<'a>) -> Result<i32> {
+        let (_, timestamp, _, _) = Self::parse_envelope(reader)?;
+        Ok(timestamp)
+    }
+
+    /// Read the header and back-pointer of a tag, returning its type,
+    /// timestamp, stream ID and undecoded contents.
+    fn parse_envelope(reader: &mut Reader<'a>) -> Result<(u8, i32, u32, &'a [u8])> {
+        let tag_type = reader.read_u8()?;
+        let data_size = reader.read_u24()?;
+        let timestamp = reader.read_u24()?;
+        let timestamp_extended = reader.read_u8()?;
+        let stream_id = reader.read_u24()?;
+        let data = reader.read_bytes(data_size as usize)?;
+        let _previous_tag_size = reader.read_u32()?;
+
+        // The extended byte holds the most significant bits of the timestamp.
+        let timestamp = ((u32::from(timestamp_extended) << 24) | timestamp) as i32;
+
+        Ok((tag_type, timestamp, stream_id, data))
+    }
+}
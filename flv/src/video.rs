@@ -0,0 +1,168 @@
+use crate::error::{Error, Result};
+
+/// The role a video tag plays in the video stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameType {
+    /// A frame that can be decoded without reference to any other frame.
+    Keyframe = 1,
+
+    /// A frame that depends on previously decoded frames.
+    Interframe = 2,
+
+    /// An interframe that no other frame depends on.
+    InterframeDisposable = 3,
+
+    /// A keyframe generated by a server, rather than the encoder.
+    GeneratedKeyframe = 4,
+
+    /// A tag carrying a command or video info rather than a frame.
+    CommandFrame = 5,
+}
+
+impl FrameType {
+    fn from_u8(n: u8) -> Option<Self> {
+        Some(match n {
+            1 => Self::Keyframe,
+            2 => Self::Interframe,
+            3 => Self::InterframeDisposable,
+            4 => Self::GeneratedKeyframe,
+            5 => Self::CommandFrame,
+            _ => return None,
+        })
+    }
+
+    /// Returns true if this frame can be independently seeked to.
+    pub fn is_keyframe(self) -> bool {
+        matches!(self, Self::Keyframe | Self::GeneratedKeyframe)
+    }
+}
+
+/// The codec used by a video tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodecId {
+    Jpeg = 1,
+    SorensonH263 = 2,
+    ScreenVideo = 3,
+    On2Vp6 = 4,
+    On2Vp6Alpha = 5,
+    ScreenVideo2 = 6,
+    Avc = 7,
+}
+
+impl CodecId {
+    fn from_u8(n: u8) -> Option<Self> {
+        Some(match n {
+            1 => Self::Jpeg,
+            2 => Self::SorensonH263,
+            3 => Self::ScreenVideo,
+            4 => Self::On2Vp6,
+            5 => Self::On2Vp6Alpha,
+            6 => Self::ScreenVideo2,
+            7 => Self::Avc,
+            _ => return None,
+        })
+    }
+}
+
+/// A command carried by a video tag of type `FrameType::CommandFrame`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandFrame {
+    StartOfClientSideSeek = 0,
+    EndOfClientSideSeek = 1,
+}
+
+/// The payload of a video tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VideoPacket<'a> {
+    /// A frame of encoded video in the tag's codec.
+    Data(&'a [u8]),
+
+    /// A frame of VP6 video.
+    ///
+    /// FLV prefixes VP6 frames with the number of pixels to crop off of the
+    /// right and bottom edges of the encoded frame. `data` is the rest of the
+    /// frame, which is laid out the same way as a VP6 frame in a SWF file.
+    Vp6 {
+        horizontal_adjustment: u8,
+        vertical_adjustment: u8,
+        data: &'a [u8],
+    },
+
+    /// An AVC `AVCDecoderConfigurationRecord` structure.
+    AvcSequenceHeader(&'a [u8]),
+
+    /// One or more AVC NAL units.
+    AvcNalu {
+        composition_time_offset: i32,
+        data: &'a [u8],
+    },
+
+    /// The end of an AVC sequence.
+    AvcEndOfSequence,
+
+    /// A command for the player, rather than a frame.
+    CommandFrame(CommandFrame),
+}
+
+/// The contents of a video tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VideoData<'a> {
+    pub frame_type: FrameType,
+    pub codec_id: CodecId,
+    pub data: VideoPacket<'a>,
+}
+
+impl<'a> VideoData<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Result<Self> {
+        let (&flags, data) = data
+            .split_first()
+            .ok_or_else(|| Error::invalid_data("Empty video tag"))?;
+
+        let frame_type = FrameType::from_u8(flags >> 4)
+            .ok_or_else(|| Error::invalid_data("Invalid video frame type"))?;
+        let codec_id = CodecId::from_u8(flags & 0b1111)
+            .ok_or_else(|| Error::invalid_data("Invalid video codec"))?;
+
+        let data = match (frame_type, codec_id) {
+            (FrameType::CommandFrame, codec_id) if codec_id != CodecId::Avc => match data.first() {
+                Some(0) => VideoPacket::CommandFrame(CommandFrame::StartOfClientSideSeek),
+                Some(1) => VideoPacket::CommandFrame(CommandFrame::EndOfClientSideSeek),
+                _ => return Err(Error::invalid_data("Invalid video command")),
+            },
+            (_, CodecId::On2Vp6 | CodecId::On2Vp6Alpha) => {
+                let (&adjustment, data) = data
+                    .split_first()
+                    .ok_or_else(|| Error::invalid_data("Missing VP6 adjustment"))?;
+                VideoPacket::Vp6 {
+                    horizontal_adjustment: adjustment >> 4,
+                    vertical_adjustment: adjustment & 0b1111,
+                    data,
+                }
+            }
+            (_, CodecId::Avc) => {
+                if data.len() < 4 {
+                    return Err(Error::invalid_data("Truncated AVC packet"));
+                }
+                // Sign-extend the 24-bit composition time.
+                let composition_time_offset =
+                    i32::from_be_bytes([data[1], data[2], data[3], 0]) >> 8;
+                match data[0] {
+                    0 => VideoPacket::AvcSequenceHeader(&data[4..]),
+                    1 => VideoPacket::AvcNalu {
+                        composition_time_offset,
+                        data: &data[4..],
+                    },
+                    2 => VideoPacket::AvcEndOfSequence,
+                    _ => return Err(Error::invalid_data("Invalid AVC packet type")),
+                }
+            }
+            _ => VideoPacket::Data(data),
+        };
+
+        Ok(Self {
+            frame_type,
+            codec_id,
+            data,
+        })
+    }
+}
@@ -0,0 +1,248 @@
+use crate::header::Header;
+use crate::sound::{AudioData, AudioDataType};
+use crate::tag::{Tag, TagData};
+use crate::video::{VideoData, VideoPacket};
+use std::io::{self, Write};
+
+/// Writes an FLV file to an output stream.
+///
+/// # Example
+/// ```
+/// use flv::*;
+///
+/// let mut writer = Writer::new(Vec::new());
+/// writer
+///     .write_header(&Header {
+///         version: 1,
+///         type_flags: TypeFlags::HAS_VIDEO,
+///         data_offset: Header::LENGTH,
+///     })
+///     .unwrap();
+/// writer
+///     .write_tag(&Tag {
+///         timestamp: 0,
+///         stream_id: 0,
+///         data: TagData::Video(VideoData {
+///             frame_type: FrameType::Keyframe,
+///             codec_id: CodecId::SorensonH263,
+///             data: VideoPacket::Data(&[0, 0, 0x84]),
+///         }),
+///     })
+///     .unwrap();
+/// let output = writer.into_inner();
+/// ```
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    output: W,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(output: W) -> Self {
+        Self { output }
+    }
+
+    /// Get a reference to the output stream.
+    pub fn get_ref(&self) -> &W {
+        &self.output
+    }
+
+    /// Consume the writer, returning the output stream.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    /// Write the FLV file header.
+    ///
+    /// Any space between the end of the header and `data_offset` is filled
+    /// with zeroes.
+    pub fn write_header(&mut self, header: &Header) -> io::Result<()> {
+        let data_offset = header.data_offset.max(Header::LENGTH);
+        self.output.write_all(b"FLV")?;
+        self.output
+            .write_all(&[header.version, header.type_flags.bits()])?;
+        self.output.write_all(&data_offset.to_be_bytes())?;
+        self.output
+            .write_all(&vec![0; (data_offset - Header::LENGTH) as usize])?;
+
+        // The back-pointer of the (nonexistent) tag before the first one.
+        self.output.write_all(&0u32.to_be_bytes())
+    }
+
+    /// Write a tag, including the back-pointer that follows it.
+    pub fn write_tag(&mut self, tag: &Tag<'_>) -> io::Result<()> {
+        let mut data = Vec::new();
+        let tag_type = match &tag.data {
+            TagData::Audio(audio) => {
+                write_audio_data(&mut data, audio);
+                Tag::AUDIO
+            }
+            TagData::Video(video) => {
+                write_video_data(&mut data, video);
+                Tag::VIDEO
+            }
+            TagData::Script(script_data) => {
+                data = script_data
+                    .to_bytes()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                Tag::SCRIPT_DATA
+            }
+            TagData::Unknown {
+                tag_type,
+                data: tag_data,
+            } => {
+                data.extend_from_slice(tag_data);
+                *tag_type
+            }
+        };
+
+        let data_size = u32::try_from(data.len())
+            .ok()
+            .filter(|size| *size < 1 << 24)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Tag is too large"))?;
+        let timestamp = tag.timestamp as u32;
+
+        self.output.write_all(&[tag_type])?;
+        self.write_u24(data_size)?;
+        self.write_u24(timestamp & 0xFF_FFFF)?;
+        self.output.write_all(&[(timestamp >> 24) as u8])?;
+        self.write_u24(tag.stream_id)?;
+        self.output.write_all(&data)?;
+
+        // The back-pointer counts the 11 bytes of the tag header.
+        self.output.write_all(&(data_size + 11).to_be_bytes())
+    }
+
+    fn write_u24(&mut self, n: u32) -> io::Result<()> {
+        self.output.write_all(&n.to_be_bytes()[1..])
+    }
+}
+
+fn write_audio_data(output: &mut Vec<u8>, audio: &AudioData<'_>) {
+    output.push(
+        (audio.format as u8) << 4
+            | (audio.rate as u8) << 2
+            | (audio.size as u8) << 1
+            | audio.sound_type as u8,
+    );
+    let data = match audio.data {
+        AudioDataType::Raw(data) => data,
+        AudioDataType::AacSequenceHeader(data) => {
+            output.push(0);
+            data
+        }
+        AudioDataType::AacRaw(data) => {
+            output.push(1);
+            data
+        }
+    };
+    output.extend_from_slice(data);
+}
+
+fn write_video_data(output: &mut Vec<u8>, video: &VideoData<'_>) {
+    output.push((video.frame_type as u8) << 4 | video.codec_id as u8);
+    match video.data {
+        VideoPacket::Data(data) => output.extend_from_slice(data),
+        VideoPacket::Vp6 {
+            horizontal_adjustment,
+            vertical_adjustment,
+            data,
+        } => {
+            output.push(horizontal_adjustment << 4 | (vertical_adjustment & 0b1111));
+            output.extend_from_slice(data);
+        }
+        VideoPacket::AvcSequenceHeader(data) => {
+            output.extend_from_slice(&[0, 0, 0, 0]);
+            output.extend_from_slice(data);
+        }
+        VideoPacket::AvcNalu {
+            composition_time_offset,
+            data,
+        } => {
+            output.push(1);
+            output.extend_from_slice(&composition_time_offset.to_be_bytes()[1..]);
+            output.extend_from_slice(data);
+        }
+        VideoPacket::AvcEndOfSequence => output.extend_from_slice(&[2, 0, 0, 0]),
+        VideoPacket::CommandFrame(command) => output.push(command as u8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::TypeFlags;
+    use crate::read::Reader;
+    use crate::script::ScriptData;
+    use crate::sound::{SoundFormat, SoundRate, SoundSize, SoundType};
+    use crate::video::{CodecId, FrameType};
+    use flash_lso::types::Value;
+
+    #[test]
+    fn round_trip() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_AUDIO | TypeFlags::HAS_VIDEO,
+            data_offset: 12,
+        };
+        let tags = [
+            Tag {
+                timestamp: 0,
+                stream_id: 0,
+                data: TagData::Script(ScriptData {
+                    name: "onMetaData".to_string(),
+                    value: Value::Number(1.0),
+                }),
+            },
+            Tag {
+                timestamp: 0,
+                stream_id: 0,
+                data: TagData::Audio(AudioData {
+                    format: SoundFormat::Aac,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::AacSequenceHeader(&[0x12, 0x10]),
+                }),
+            },
+            Tag {
+                timestamp: 0x1234_5678,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Keyframe,
+                    codec_id: CodecId::On2Vp6,
+                    data: VideoPacket::Vp6 {
+                        horizontal_adjustment: 4,
+                        vertical_adjustment: 8,
+                        data: &[1, 2, 3],
+                    },
+                }),
+            },
+            Tag {
+                timestamp: 40,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Interframe,
+                    codec_id: CodecId::Avc,
+                    data: VideoPacket::AvcNalu {
+                        composition_time_offset: -40,
+                        data: &[4, 5, 6],
+                    },
+                }),
+            },
+        ];
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&header).unwrap();
+        for tag in &tags {
+            writer.write_tag(tag).unwrap();
+        }
+        let data = writer.into_inner();
+
+        let mut reader = Reader::new(&data);
+        assert_eq!(reader.read_header(), Ok(header));
+        for tag in tags {
+            assert_eq!(reader.read_tag(), Ok(tag));
+        }
+        assert!(reader.is_empty());
+    }
+}
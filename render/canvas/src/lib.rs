@@ -10,6 +10,7 @@ use ruffle_render::bitmap::{
 use ruffle_render::commands::{CommandHandler, CommandList};
 use ruffle_render::error::Error;
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, DrawCommand, LineScaleMode, LineScales};
 use ruffle_render::transform::Transform;
@@ -515,6 +516,29 @@ impl CommandHandler for WebCanvasRenderBackend {
         self.clear_color_filter();
     }
 
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        // Canvas can only draw with 2D transforms, so the projection is approximated.
+        let canvas = &as_bitmap_data(&bitmap).canvas;
+        if let Some(matrix) =
+            matrix.affine_approximation(canvas.width().into(), canvas.height().into())
+        {
+            self.render_bitmap(
+                bitmap,
+                Transform {
+                    matrix,
+                    color_transform,
+                },
+                smoothing,
+            );
+        }
+    }
+
     fn render_stage3d(&mut self, _bitmap: BitmapHandle, _transform: Transform) {
         panic!("Stage3D should not have been created on canvas backend")
     }
@@ -0,0 +1,231 @@
+use crate::commands::CommandRenderer;
+use crate::filters;
+use crate::mesh::Mesh;
+use crate::surface::{Rgba, Surface};
+use crate::texture::{as_texture, Pixels, Texture};
+use gc_arena::MutationContext;
+use ruffle_render::backend::{
+    Context3D, Context3DCommand, RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{Bitmap, BitmapHandle, BitmapSource, RgbaBufRead, SyncHandle};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::filters::Filter;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::ShapeTessellator;
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::Color;
+
+/// A render backend that draws everything on the CPU.
+///
+/// It doesn't need a GPU, so it can be used anywhere, such as in tests on
+/// machines without a graphics adapter. Rendering follows the wgpu backend
+/// as closely as possible, including its antialiasing, so that the output
+/// of both backends can be compared.
+pub struct SoftwareRenderBackend {
+    viewport_dimensions: ViewportDimensions,
+    quality: StageQuality,
+    shape_tessellator: ShapeTessellator,
+    /// The premultiplied pixels of the last submitted frame.
+    frame: Vec<Rgba>,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(viewport_dimensions: ViewportDimensions) -> Self {
+        let frame_size = viewport_dimensions.width as usize * viewport_dimensions.height as usize;
+        Self {
+            viewport_dimensions,
+            quality: StageQuality::default(),
+            shape_tessellator: ShapeTessellator::new(),
+            frame: vec![[0; 4]; frame_size],
+        }
+    }
+
+    /// Returns the last submitted frame, with straight alpha.
+    pub fn capture_frame(&self) -> image::RgbaImage {
+        let mut rgba: Vec<u8> = self.frame.iter().flatten().copied().collect();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut rgba);
+        image::RgbaImage::from_raw(
+            self.viewport_dimensions.width,
+            self.viewport_dimensions.height,
+            rgba,
+        )
+        .expect("Frame should match the viewport dimensions")
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.viewport_dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.viewport_dimensions = dimensions;
+        self.frame = vec![[0; 4]; dimensions.width as usize * dimensions.height as usize];
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let draws = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        ShapeHandle(Arc::new(Mesh::new(draws, bitmap_source, self)))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        commands: CommandList,
+        quality: StageQuality,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let texture = as_texture(&handle);
+        let (width, height, mut surface) = {
+            let pixels = texture.pixels();
+            let width = width.min(pixels.width);
+            let height = height.min(pixels.height);
+            let surface =
+                Surface::from_pixels(width, height, quality, &pixels.data, pixels.width as usize);
+            (width, height, surface)
+        };
+
+        commands.execute(&mut CommandRenderer::new(&mut surface));
+
+        let mut pixels = texture.pixels_mut();
+        let stride = pixels.width as usize;
+        for (y, row) in surface
+            .resolve()
+            .chunks_exact(width.max(1) as usize)
+            .take(height as usize)
+            .enumerate()
+        {
+            pixels.data[y * stride..y * stride + row.len()].copy_from_slice(row);
+        }
+        Some(Box::new(SoftwareSyncHandle::new(&pixels)))
+    }
+
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        // `source` may be the same texture as `destination`, so the filtered
+        // pixels are collected before anything gets written.
+        let filtered = filters::apply_filter(
+            &as_texture(&source).pixels(),
+            source_point,
+            source_size,
+            &filter,
+        )?;
+
+        let mut pixels = as_texture(&destination).pixels_mut();
+        let width = filtered
+            .width
+            .min(pixels.width.saturating_sub(dest_point.0)) as usize;
+        let height = filtered
+            .height
+            .min(pixels.height.saturating_sub(dest_point.1)) as usize;
+        let stride = pixels.width as usize;
+        for y in 0..height {
+            let start = (dest_point.1 as usize + y) * stride + dest_point.0 as usize;
+            let source_start = y * filtered.width as usize;
+            pixels.data[start..start + width]
+                .copy_from_slice(&filtered.data[source_start..source_start + width]);
+        }
+        Some(Box::new(SoftwareSyncHandle::new(&pixels)))
+    }
+
+    fn submit_frame(&mut self, clear: Color, commands: CommandList) {
+        let clear = crate::blend::premultiply([
+            f32::from(clear.r) / 255.0,
+            f32::from(clear.g) / 255.0,
+            f32::from(clear.b) / 255.0,
+            f32::from(clear.a) / 255.0,
+        ]);
+        let mut surface = Surface::new(
+            self.viewport_dimensions.width,
+            self.viewport_dimensions.height,
+            self.quality,
+            clear,
+        );
+        commands.execute(&mut CommandRenderer::new(&mut surface));
+        self.frame = surface.resolve();
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        let bitmap = bitmap.to_rgba();
+        let pixels = Pixels::from_rgba(bitmap.width(), bitmap.height(), bitmap.data());
+        Ok(BitmapHandle(Arc::new(Texture::new(
+            pixels.width,
+            pixels.height,
+            pixels.data,
+        ))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<(), Error> {
+        *as_texture(handle).pixels_mut() = Pixels::from_rgba(width, height, &rgba);
+        Ok(())
+    }
+
+    fn create_context3d(&mut self) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present<'gc>(
+        &mut self,
+        _context: &mut dyn Context3D,
+        _commands: Vec<Context3DCommand<'gc>>,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Renderer: Software")
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+}
+
+/// Holds a copy of the pixels of a texture after rendering to it.
+///
+/// Everything is drawn immediately, so there's never anything to wait for.
+#[derive(Debug)]
+struct SoftwareSyncHandle {
+    width: u32,
+    rgba: Vec<u8>,
+}
+
+impl SoftwareSyncHandle {
+    fn new(pixels: &Pixels) -> Self {
+        Self {
+            width: pixels.width,
+            rgba: pixels.data.iter().flatten().copied().collect(),
+        }
+    }
+}
+
+impl SyncHandle for SoftwareSyncHandle {
+    fn retrieve_offscreen_texture(self: Box<Self>, with_rgba: RgbaBufRead) -> Result<(), Error> {
+        with_rgba(&self.rgba, self.width * 4);
+        Ok(())
+    }
+}
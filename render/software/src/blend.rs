@@ -0,0 +1,229 @@
+use crate::surface::Rgba;
+use swf::{BlendMode, ColorTransform};
+
+/// A color transform, normalized to work on colors in the `0.0..=1.0` range.
+#[derive(Clone, Copy, Debug)]
+pub struct ColorAdjustments {
+    mult: [f32; 4],
+    add: [f32; 4],
+}
+
+impl From<&ColorTransform> for ColorAdjustments {
+    fn from(transform: &ColorTransform) -> Self {
+        Self {
+            mult: transform.mult_rgba_normalized(),
+            add: transform.add_rgba_normalized(),
+        }
+    }
+}
+
+impl ColorAdjustments {
+    /// Transforms a straight alpha color, returning a premultiplied color.
+    pub fn apply_straight(&self, color: [f32; 4]) -> Rgba {
+        let mut out = [0.0; 4];
+        for i in 0..4 {
+            out[i] = (color[i] * self.mult[i] + self.add[i]).clamp(0.0, 1.0);
+        }
+        premultiply(out)
+    }
+
+    /// Transforms a premultiplied color.
+    ///
+    /// Fully transparent colors are left untouched, so that adding to the
+    /// alpha channel does not make the empty parts of a bitmap visible.
+    pub fn apply_premultiplied(&self, color: Rgba) -> Rgba {
+        if color[3] == 0 {
+            return color;
+        }
+        self.apply_straight(unmultiply(color))
+    }
+}
+
+/// Converts a straight alpha color in the `0.0..=1.0` range to a
+/// premultiplied color.
+pub fn premultiply(color: [f32; 4]) -> Rgba {
+    let alpha = color[3];
+    [
+        to_byte(color[0] * alpha),
+        to_byte(color[1] * alpha),
+        to_byte(color[2] * alpha),
+        to_byte(alpha),
+    ]
+}
+
+/// Converts a premultiplied color to a straight alpha color in the
+/// `0.0..=1.0` range.
+pub fn unmultiply(color: Rgba) -> [f32; 4] {
+    let alpha = f32::from(color[3]) / 255.0;
+    if alpha <= 0.0 {
+        return [0.0; 4];
+    }
+    [
+        (f32::from(color[0]) / 255.0 / alpha).min(1.0),
+        (f32::from(color[1]) / 255.0 / alpha).min(1.0),
+        (f32::from(color[2]) / 255.0 / alpha).min(1.0),
+        alpha,
+    ]
+}
+
+fn to_float(color: Rgba) -> [f32; 4] {
+    color.map(|channel| f32::from(channel) / 255.0)
+}
+
+fn to_byte(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Draws `src` over `dst`, the way normal drawing composites colors.
+pub fn source_over(dst: Rgba, src: Rgba) -> Rgba {
+    match src[3] {
+        255 => src,
+        alpha => {
+            let inverse = 255 - u32::from(alpha);
+            let mut out = [0; 4];
+            for i in 0..4 {
+                let value = u32::from(src[i]) + (u32::from(dst[i]) * inverse + 127) / 255;
+                out[i] = value.min(255) as u8;
+            }
+            out
+        }
+    }
+}
+
+/// Composites a pixel of a blended layer onto the pixel below it.
+///
+/// `Alpha` and `Erase` are handled here too; it's up to the caller to make
+/// sure that they're only used on top of a `Layer`.
+pub fn blend(dst: Rgba, src: Rgba, mode: BlendMode) -> Rgba {
+    if src[3] == 0 {
+        return dst;
+    }
+    let s = to_float(src);
+    let d = to_float(dst);
+    let alpha = s[3] + d[3] * (1.0 - s[3]);
+    let out = match mode {
+        BlendMode::Normal | BlendMode::Layer => return source_over(dst, src),
+        BlendMode::Add => {
+            let rgb = |i: usize| (s[i] + d[i]).min(1.0);
+            [rgb(0), rgb(1), rgb(2), alpha]
+        }
+        BlendMode::Subtract => {
+            let rgb = |i: usize| (d[i] - s[i]).max(0.0);
+            [rgb(0), rgb(1), rgb(2), alpha]
+        }
+        BlendMode::Screen => {
+            let rgb = |i: usize| s[i] + d[i] - s[i] * d[i];
+            [rgb(0), rgb(1), rgb(2), alpha]
+        }
+        BlendMode::Multiply => {
+            let rgb = |i: usize| s[i] * d[i] + s[i] * (1.0 - d[3]) + d[i] * (1.0 - s[3]);
+            [rgb(0), rgb(1), rgb(2), alpha]
+        }
+        BlendMode::Alpha => [d[0] * s[3], d[1] * s[3], d[2] * s[3], d[3] * s[3]],
+        BlendMode::Erase => {
+            let keep = 1.0 - s[3];
+            [d[0] * keep, d[1] * keep, d[2] * keep, d[3] * keep]
+        }
+        BlendMode::Lighten
+        | BlendMode::Darken
+        | BlendMode::Difference
+        | BlendMode::Invert
+        | BlendMode::Overlay
+        | BlendMode::HardLight => {
+            // These blend the straight colors where both layers overlap,
+            // and keep the other layer's color where only one is visible.
+            let rgb = |i: usize| {
+                let src_color = s[i] / s[3];
+                let dst_color = if d[3] > 0.0 { d[i] / d[3] } else { 0.0 };
+                let blended = match mode {
+                    BlendMode::Lighten => src_color.max(dst_color),
+                    BlendMode::Darken => src_color.min(dst_color),
+                    BlendMode::Difference => (dst_color - src_color).abs(),
+                    BlendMode::Invert => 1.0 - dst_color,
+                    BlendMode::Overlay => hard_light(dst_color, src_color),
+                    _ => hard_light(src_color, dst_color),
+                };
+                s[i] * (1.0 - d[3]) + d[i] * (1.0 - s[3]) + s[3] * d[3] * blended
+            };
+            [rgb(0), rgb(1), rgb(2), alpha]
+        }
+    };
+    out.map(to_byte)
+}
+
+/// The hard light function, where `a` decides between multiplying and
+/// screening `b`.
+fn hard_light(a: f32, b: f32) -> f32 {
+    if a <= 0.5 {
+        2.0 * a * b
+    } else {
+        1.0 - 2.0 * (1.0 - b) * (1.0 - a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_over_opaque() {
+        assert_eq!(source_over([1, 2, 3, 255], [4, 5, 6, 255]), [4, 5, 6, 255]);
+        assert_eq!(source_over([1, 2, 3, 255], [0, 0, 0, 0]), [1, 2, 3, 255]);
+    }
+
+    #[test]
+    fn source_over_translucent() {
+        // 50% red over opaque blue.
+        assert_eq!(
+            source_over([0, 0, 255, 255], [128, 0, 0, 128]),
+            [128, 0, 127, 255]
+        );
+    }
+
+    #[test]
+    fn blend_modes() {
+        let red = [255, 0, 0, 255];
+        let gray = [128, 128, 128, 255];
+        assert_eq!(blend(gray, red, BlendMode::Multiply), [128, 0, 0, 255]);
+        assert_eq!(blend(gray, red, BlendMode::Screen), [255, 128, 128, 255]);
+        assert_eq!(blend(gray, red, BlendMode::Add), [255, 128, 128, 255]);
+        assert_eq!(blend(gray, red, BlendMode::Subtract), [0, 128, 128, 255]);
+        assert_eq!(blend(gray, red, BlendMode::Lighten), [255, 128, 128, 255]);
+        assert_eq!(blend(gray, red, BlendMode::Darken), [128, 0, 0, 255]);
+        assert_eq!(
+            blend(gray, red, BlendMode::Difference),
+            [127, 128, 128, 255]
+        );
+        assert_eq!(blend(gray, red, BlendMode::Invert), [127, 127, 127, 255]);
+        assert_eq!(
+            blend(gray, [0, 0, 0, 128], BlendMode::Erase),
+            [64, 64, 64, 127]
+        );
+        assert_eq!(
+            blend(gray, [0, 0, 0, 128], BlendMode::Alpha),
+            [64, 64, 64, 128]
+        );
+    }
+
+    #[test]
+    fn transparent_sources_keep_destination() {
+        let dst = [10, 20, 30, 40];
+        for mode in [BlendMode::Multiply, BlendMode::Difference, BlendMode::Erase] {
+            assert_eq!(blend(dst, [0; 4], mode), dst);
+        }
+    }
+
+    #[test]
+    fn color_transform_skips_transparent_pixels() {
+        let transform = ColorTransform {
+            a_add: 255,
+            ..ColorTransform::IDENTITY
+        };
+        let adjustments = ColorAdjustments::from(&transform);
+        assert_eq!(adjustments.apply_premultiplied([0; 4]), [0; 4]);
+        assert_eq!(
+            adjustments.apply_premultiplied([0, 0, 128, 128]),
+            [0, 0, 255, 255]
+        );
+    }
+}
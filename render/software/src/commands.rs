@@ -0,0 +1,557 @@
+use crate::blend::{self, ColorAdjustments};
+use crate::mesh::{as_mesh, transform_point, Fill};
+use crate::surface::{Rgba, Surface};
+use crate::texture::as_texture;
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::BitmapHandle;
+use ruffle_render::commands::{BlendShader, CommandHandler, CommandList};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::transform::Transform;
+use swf::{BlendMode, Color, ColorTransform};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+/// Draws commands onto a `Surface`.
+///
+/// Masks work the same way as in the wgpu renderer: every sample has a
+/// stencil value counting the masks that cover it, and masked content is
+/// only drawn where that count matches the number of active masks.
+pub struct CommandRenderer<'a> {
+    surface: &'a mut Surface,
+    mask_state: MaskState,
+    num_masks: u8,
+    /// Whether a `Layer` blend is being drawn into, which is required for
+    /// `Alpha` and `Erase` blends to have any effect.
+    in_layer: bool,
+}
+
+impl<'a> CommandRenderer<'a> {
+    pub fn new(surface: &'a mut Surface) -> Self {
+        Self::with_layer(surface, false)
+    }
+
+    fn with_layer(surface: &'a mut Surface, in_layer: bool) -> Self {
+        Self {
+            surface,
+            mask_state: MaskState::NoMask,
+            num_masks: 0,
+            in_layer,
+        }
+    }
+
+    /// Draws a quad covering `(0, 0)..(width, height)` in object space.
+    fn draw_quad(
+        &mut self,
+        matrix: &Affine,
+        width: f32,
+        height: f32,
+        composite: fn(Rgba, Rgba) -> Rgba,
+        shade: impl Fn((f32, f32)) -> Rgba,
+    ) {
+        let Some(inverse) = matrix.inverse() else {
+            return;
+        };
+        let corners = [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)]
+            .map(|point| matrix.apply(point));
+        let shade = |point| shade(inverse.apply(point));
+        self.draw_triangle([corners[0], corners[1], corners[2]], composite, &shade);
+        self.draw_triangle([corners[0], corners[2], corners[3]], composite, &shade);
+    }
+
+    /// Draws a triangle given in surface coordinates.
+    ///
+    /// `shade` is called at most once per pixel, with the position of the
+    /// pixel's center, and returns the premultiplied color to composite onto
+    /// every covered sample of that pixel.
+    fn draw_triangle(
+        &mut self,
+        points: [(f32, f32); 3],
+        composite: fn(Rgba, Rgba) -> Rgba,
+        shade: &impl Fn((f32, f32)) -> Rgba,
+    ) {
+        let rasterizer = self.surface.rasterizer();
+        let sample_count = self.surface.sample_count();
+        let width = self.surface.width() as usize;
+        let mask_state = self.mask_state;
+        let num_masks = self.num_masks;
+        let (colors, stencil) = self.surface.buffers_mut();
+        rasterizer.rasterize_triangle(points, |x, y, mask| {
+            let start = (y as usize * width + x as usize) * sample_count;
+            let mut color = None;
+            for sample in 0..sample_count {
+                if mask & (1 << sample) == 0 {
+                    continue;
+                }
+                let index = start + sample;
+                write_sample(
+                    mask_state,
+                    num_masks,
+                    &mut colors[index],
+                    &mut stencil[index],
+                    |dst| {
+                        let src =
+                            *color.get_or_insert_with(|| shade((x as f32 + 0.5, y as f32 + 0.5)));
+                        composite(dst, src)
+                    },
+                );
+            }
+        });
+    }
+}
+
+/// Updates a single sample, respecting the current mask state.
+fn write_sample(
+    mask_state: MaskState,
+    num_masks: u8,
+    color: &mut Rgba,
+    stencil: &mut u8,
+    paint: impl FnOnce(Rgba) -> Rgba,
+) {
+    match mask_state {
+        MaskState::NoMask | MaskState::DrawMaskedContent => {
+            if *stencil == num_masks {
+                *color = paint(*color);
+            }
+        }
+        MaskState::DrawMaskStencil => {
+            if *stencil == num_masks.wrapping_sub(1) {
+                *stencil = num_masks;
+            }
+        }
+        MaskState::ClearMaskStencil => {
+            if *stencil == num_masks {
+                *stencil = num_masks.wrapping_sub(1);
+            }
+        }
+    }
+}
+
+/// Replaces the destination with the source, used for Stage3D content.
+fn replace(_dst: Rgba, src: Rgba) -> Rgba {
+    src
+}
+
+impl<'a> CommandHandler for CommandRenderer<'a> {
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: Transform, smoothing: bool) {
+        let pixels = as_texture(&bitmap).pixels();
+        let (width, height) = (pixels.width as f32, pixels.height as f32);
+        let color_adjustments = ColorAdjustments::from(&transform.color_transform);
+        self.draw_quad(
+            &Affine::from(&transform.matrix),
+            width,
+            height,
+            blend::source_over,
+            |(x, y)| {
+                let color = pixels.sample((x / width, y / height), smoothing, false);
+                color_adjustments.apply_premultiplied(color)
+            },
+        );
+    }
+
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        let pixels = as_texture(&bitmap).pixels();
+        let (width, height) = (pixels.width as f32, pixels.height as f32);
+        let color_adjustments = ColorAdjustments::from(&color_transform);
+        // Content crossing the viewer's plane isn't clipped, so it's skipped entirely.
+        let corners = [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)].map(|(x, y)| {
+            matrix
+                .project_point(x.into(), y.into(), 0.0)
+                .map(|(x, y)| (x as f32, y as f32))
+        });
+        let [Some(a), Some(b), Some(c), Some(d)] = corners else {
+            return;
+        };
+        // Every pixel is mapped back onto the bitmap, which keeps the texture
+        // perspective-correct.
+        let shade = |(x, y): (f32, f32)| match matrix.unproject_point(x.into(), y.into()) {
+            Some((u, v)) => {
+                let color = pixels.sample((u as f32 / width, v as f32 / height), smoothing, false);
+                color_adjustments.apply_premultiplied(color)
+            }
+            None => [0; 4],
+        };
+        self.draw_triangle([a, b, c], blend::source_over, &shade);
+        self.draw_triangle([a, c, d], blend::source_over, &shade);
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        let pixels = as_texture(&bitmap).pixels();
+        let (width, height) = (pixels.width as f32, pixels.height as f32);
+        self.draw_quad(
+            &Affine::from(&transform.matrix),
+            width,
+            height,
+            replace,
+            |(x, y)| pixels.sample((x / width, y / height), false, false),
+        );
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let mesh = as_mesh(&shape);
+        let matrix = Affine::from(&transform.matrix);
+        let Some(inverse) = matrix.inverse() else {
+            return;
+        };
+        let color_adjustments = ColorAdjustments::from(&transform.color_transform);
+
+        for draw in &mesh.draws {
+            let num_indices = if self.mask_state == MaskState::DrawMaskStencil {
+                draw.mask_index_count
+            } else {
+                draw.indices.len()
+            };
+            for triangle in draw.indices[..num_indices].chunks_exact(3) {
+                let vertices = [
+                    draw.vertices[triangle[0] as usize],
+                    draw.vertices[triangle[1] as usize],
+                    draw.vertices[triangle[2] as usize],
+                ];
+                let points = vertices.map(|vertex| matrix.apply(vertex.position));
+                match &draw.fill {
+                    Fill::Color => {
+                        // Solid fills use a single color for every vertex of a triangle.
+                        let color = color_adjustments.apply_straight(vertices[0].color);
+                        self.draw_triangle(points, blend::source_over, &|_| color);
+                    }
+                    Fill::Gradient(gradient) => {
+                        self.draw_triangle(points, blend::source_over, &|point| {
+                            let uv = transform_point(&gradient.matrix, inverse.apply(point));
+                            color_adjustments.apply_straight(gradient.color_at(uv))
+                        });
+                    }
+                    Fill::Bitmap {
+                        matrix: texture_matrix,
+                        handle,
+                        is_smoothed,
+                        is_repeating,
+                    } => {
+                        let pixels = as_texture(handle).pixels();
+                        self.draw_triangle(points, blend::source_over, &|point| {
+                            let uv = transform_point(texture_matrix, inverse.apply(point));
+                            let color = pixels.sample(uv, *is_smoothed, *is_repeating);
+                            color_adjustments.apply_premultiplied(color)
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let color = blend::premultiply([
+            f32::from(color.r) / 255.0,
+            f32::from(color.g) / 255.0,
+            f32::from(color.b) / 255.0,
+            f32::from(color.a) / 255.0,
+        ]);
+        self.draw_quad(&Affine::from(&matrix), 1.0, 1.0, blend::source_over, |_| {
+            color
+        });
+    }
+
+    fn push_mask(&mut self) {
+        debug_assert!(
+            self.mask_state == MaskState::NoMask || self.mask_state == MaskState::DrawMaskedContent
+        );
+        self.num_masks += 1;
+        self.mask_state = MaskState::DrawMaskStencil;
+    }
+
+    fn activate_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskStencil);
+        self.mask_state = MaskState::DrawMaskedContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskedContent);
+        self.mask_state = MaskState::ClearMaskStencil;
+    }
+
+    fn pop_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::ClearMaskStencil);
+        self.num_masks -= 1;
+        self.mask_state = if self.num_masks == 0 {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: BlendMode) {
+        let in_layer = self.in_layer || blend_mode == BlendMode::Layer;
+        if matches!(blend_mode, BlendMode::Alpha | BlendMode::Erase) && !self.in_layer {
+            // An Alpha or Erase with no Layer above it should be ignored
+            return;
+        }
+
+        let mut layer = self.surface.new_layer();
+        commands.execute(&mut CommandRenderer::with_layer(&mut layer, in_layer));
+
+        let mask_state = self.mask_state;
+        let num_masks = self.num_masks;
+        let (colors, stencil) = self.surface.buffers_mut();
+        for ((color, stencil), src) in colors.iter_mut().zip(stencil).zip(layer.colors()) {
+            if src[3] == 0 {
+                continue;
+            }
+            write_sample(mask_state, num_masks, color, stencil, |dst| {
+                blend::blend(dst, *src, blend_mode)
+            });
+        }
+    }
+
+    fn blend_shader(&mut self, commands: CommandList, shader: BlendShader) {
+        let mut layer = self.surface.new_layer();
+        commands.execute(&mut CommandRenderer::with_layer(&mut layer, self.in_layer));
+
+        let width = self.surface.width() as usize;
+        let Some(output) = shader.run(width, &self.surface.resolve(), &layer.resolve()) else {
+            return;
+        };
+
+        let sample_count = self.surface.sample_count();
+        let mask_state = self.mask_state;
+        let num_masks = self.num_masks;
+        let (colors, stencil) = self.surface.buffers_mut();
+        for (i, result) in output.pixels.into_iter().enumerate() {
+            let (x, y) = (output.x + i % output.width, output.y + i / output.width);
+            let start = (y * width + x) * sample_count;
+            for sample in start..start + sample_count {
+                write_sample(
+                    mask_state,
+                    num_masks,
+                    &mut colors[sample],
+                    &mut stencil[sample],
+                    |_| result,
+                );
+            }
+        }
+    }
+}
+
+/// An affine transform in pixels.
+#[derive(Clone, Copy, Debug)]
+struct Affine {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    tx: f32,
+    ty: f32,
+}
+
+impl From<&Matrix> for Affine {
+    fn from(matrix: &Matrix) -> Self {
+        Self {
+            a: matrix.a,
+            b: matrix.b,
+            c: matrix.c,
+            d: matrix.d,
+            tx: matrix.tx.to_pixels() as f32,
+            ty: matrix.ty.to_pixels() as f32,
+        }
+    }
+}
+
+impl Affine {
+    fn apply(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+
+    /// Returns the inverse transform, or `None` if nothing transformed by
+    /// this would be visible.
+    fn inverse(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let a = self.d / det;
+        let b = -self.b / det;
+        let c = -self.c / det;
+        let d = self.a / det;
+        Some(Self {
+            a,
+            b,
+            c,
+            d,
+            tx: -(a * self.tx + c * self.ty),
+            ty: -(b * self.tx + d * self.ty),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::pixel_bender::PixelBenderShaderArgument;
+    use ruffle_render::quality::StageQuality;
+    use std::sync::Arc;
+    use swf::Twips;
+
+    const RED: Color = Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+    const BLUE: Color = Color {
+        r: 0,
+        g: 0,
+        b: 255,
+        a: 255,
+    };
+
+    /// A matrix for a rectangle covering `x..x + width` and `y..y + height`.
+    fn rect(x: i32, y: i32, width: f32, height: f32) -> Matrix {
+        Matrix {
+            a: width,
+            b: 0.0,
+            c: 0.0,
+            d: height,
+            tx: Twips::from_pixels(x.into()),
+            ty: Twips::from_pixels(y.into()),
+        }
+    }
+
+    fn render(commands: impl FnOnce(&mut CommandRenderer)) -> Vec<Rgba> {
+        let mut surface = Surface::new(4, 1, StageQuality::High, [0, 0, 0, 255]);
+        commands(&mut CommandRenderer::new(&mut surface));
+        surface.resolve()
+    }
+
+    #[test]
+    fn rects_cover_whole_pixels() {
+        let pixels = render(|renderer| renderer.draw_rect(RED, rect(1, 0, 2.0, 1.0)));
+        assert_eq!(
+            pixels,
+            vec![
+                [0, 0, 0, 255],
+                [255, 0, 0, 255],
+                [255, 0, 0, 255],
+                [0, 0, 0, 255]
+            ]
+        );
+    }
+
+    #[test]
+    fn masks_clip_content() {
+        let pixels = render(|renderer| {
+            renderer.push_mask();
+            renderer.draw_rect(RED, rect(0, 0, 2.0, 1.0));
+            renderer.activate_mask();
+            renderer.draw_rect(BLUE, rect(1, 0, 3.0, 1.0));
+            renderer.deactivate_mask();
+            renderer.draw_rect(RED, rect(0, 0, 2.0, 1.0));
+            renderer.pop_mask();
+            renderer.draw_rect(RED, rect(3, 0, 1.0, 1.0));
+        });
+        assert_eq!(
+            pixels,
+            vec![
+                [0, 0, 0, 255],
+                [0, 0, 255, 255],
+                [0, 0, 0, 255],
+                [255, 0, 0, 255]
+            ]
+        );
+    }
+
+    #[test]
+    fn erase_only_applies_inside_layers() {
+        let erase = || CommandList {
+            commands: vec![ruffle_render::commands::Command::DrawRect {
+                color: RED,
+                matrix: rect(0, 0, 1.0, 1.0),
+            }],
+        };
+        let layer = CommandList {
+            commands: vec![
+                ruffle_render::commands::Command::DrawRect {
+                    color: BLUE,
+                    matrix: rect(0, 0, 2.0, 1.0),
+                },
+                ruffle_render::commands::Command::Blend(erase(), BlendMode::Erase),
+            ],
+        };
+        let pixels = render(|renderer| {
+            renderer.blend(layer, BlendMode::Layer);
+            renderer.blend(erase(), BlendMode::Erase);
+        });
+        assert_eq!(
+            pixels,
+            vec![
+                [0, 0, 0, 255],
+                [0, 0, 255, 255],
+                [0, 0, 0, 255],
+                [0, 0, 0, 255]
+            ]
+        );
+    }
+
+    #[test]
+    fn blend_shaders_read_background_and_content() {
+        // `dst = sampleNearest(texture, outCoord())`, with two textures
+        let blend_shader = |texture: u8| {
+            let mut bytecode = vec![0xA1, 1, 2, 0, 0, 0xC];
+            bytecode.extend(b"_OutCoord\0");
+            bytecode.extend([0xA3, 0, 4]);
+            bytecode.extend(b"background\0");
+            bytecode.extend([0xA3, 1, 4]);
+            bytecode.extend(b"content\0");
+            bytecode.extend([0xA1, 2, 4, 1, 0, 0xF]);
+            bytecode.extend(b"dst\0");
+            bytecode.extend([0x30, 1, 0, 0xF1, 0, 0, 0x10, texture]);
+            let shader =
+                ruffle_render::pixel_bender::parse_shader(&bytecode).expect("valid shader");
+            BlendShader {
+                shader: Arc::new(shader),
+                arguments: vec![
+                    PixelBenderShaderArgument::ImageInput {
+                        index: 1,
+                        image: None,
+                    },
+                    PixelBenderShaderArgument::ImageInput {
+                        index: 2,
+                        image: None,
+                    },
+                ],
+            }
+        };
+        let content = || CommandList {
+            commands: vec![ruffle_render::commands::Command::DrawRect {
+                color: BLUE,
+                matrix: rect(1, 0, 2.0, 1.0),
+            }],
+        };
+
+        let pixels = render(|renderer| renderer.blend_shader(content(), blend_shader(0)));
+        assert_eq!(pixels, vec![[0, 0, 0, 255]; 4]);
+
+        let pixels = render(|renderer| renderer.blend_shader(content(), blend_shader(1)));
+        assert_eq!(
+            pixels,
+            vec![
+                [0, 0, 0, 255],
+                [0, 0, 255, 255],
+                [0, 0, 255, 255],
+                [0, 0, 0, 255]
+            ]
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use crate::blend::{premultiply, unmultiply};
+use crate::surface::Rgba;
+use crate::texture::Pixels;
+use ruffle_render::filters::{Filter, ShaderFilter};
+use ruffle_render::pixel_bender::interpreter::run_shader;
+use ruffle_render::pixel_bender::{PixelBenderImage, PixelBenderShaderArgument};
+use std::sync::Arc;
+
+/// Applies a filter to the `size` pixels of `source` starting at `point`,
+/// returning the filtered pixels.
+///
+/// Shader filters run on the Pixel Bender interpreter. Returns `None` for
+/// filters that aren't implemented here, so that they can be applied by the
+/// CPU filters in core instead.
+pub fn apply_filter(
+    source: &Pixels,
+    point: (u32, u32),
+    size: (u32, u32),
+    filter: &Filter,
+) -> Option<Pixels> {
+    match filter {
+        Filter::ColorMatrixFilter(filter) => {
+            Some(color_matrix(source, point, size, &filter.matrix))
+        }
+        Filter::BlurFilter(filter) => {
+            let blur_x = (filter.blur_x.to_f32() - 1.0).max(0.0);
+            let blur_y = (filter.blur_y.to_f32() - 1.0).max(0.0);
+            let region = copy_region(source, point, size);
+            let horizontal = blur(&region, (blur_x, 0.0));
+            Some(blur(&horizontal, (0.0, blur_y)))
+        }
+        Filter::ShaderFilter(filter) => Some(shader(source, point, size, filter)),
+        _ => None,
+    }
+}
+
+/// Copies out a region of pixels, clamping reads to the edges of `source`.
+fn copy_region(source: &Pixels, point: (u32, u32), size: (u32, u32)) -> Pixels {
+    let mut data = Vec::with_capacity(size.0 as usize * size.1 as usize);
+    for y in 0..size.1 {
+        for x in 0..size.0 {
+            data.push(get(source, i64::from(point.0 + x), i64::from(point.1 + y)));
+        }
+    }
+    Pixels {
+        width: size.0,
+        height: size.1,
+        data,
+    }
+}
+
+fn get(pixels: &Pixels, x: i64, y: i64) -> Rgba {
+    if pixels.width == 0 || pixels.height == 0 {
+        return [0; 4];
+    }
+    let x = x.clamp(0, i64::from(pixels.width) - 1);
+    let y = y.clamp(0, i64::from(pixels.height) - 1);
+    pixels.data[(y * i64::from(pixels.width) + x) as usize]
+}
+
+fn color_matrix(
+    source: &Pixels,
+    point: (u32, u32),
+    size: (u32, u32),
+    matrix: &[f32; 20],
+) -> Pixels {
+    let mut pixels = copy_region(source, point, size);
+    for pixel in &mut pixels.data {
+        let [r, g, b, a] = unmultiply(*pixel);
+        let mut color = [0.0; 4];
+        for (i, channel) in color.iter_mut().enumerate() {
+            let row = &matrix[i * 5..i * 5 + 5];
+            *channel = (row[0] * r + row[1] * g + row[2] * b + row[3] * a + row[4] / 255.0)
+                .clamp(0.0, 1.0);
+        }
+        *pixel = premultiply(color);
+    }
+    pixels
+}
+
+/// Runs the shader of a filter, with the source pixels as its first texture input.
+fn shader(source: &Pixels, point: (u32, u32), size: (u32, u32), filter: &ShaderFilter) -> Pixels {
+    let region = copy_region(source, point, size);
+    let image = PixelBenderImage::from_premultiplied(
+        size.0,
+        size.1,
+        4,
+        region
+            .data
+            .iter()
+            .map(|pixel| pixel.map(|channel| f32::from(channel) / 255.0)),
+    );
+    let mut arguments = filter.arguments.clone();
+    if let Some(PixelBenderShaderArgument::ImageInput { image: input, .. }) = arguments
+        .iter_mut()
+        .find(|argument| matches!(argument, PixelBenderShaderArgument::ImageInput { .. }))
+    {
+        *input = Some(Arc::new(image));
+    }
+    let output = run_shader(&filter.shader, &arguments, size.0, size.1);
+    Pixels {
+        width: size.0,
+        height: size.1,
+        data: output
+            .to_premultiplied()
+            .into_iter()
+            .map(|pixel| pixel.map(|channel| (channel * 255.0).round() as u8))
+            .collect(),
+    }
+}
+
+/// Averages every pixel with its neighbours up to `radius` pixels away.
+///
+/// Averaging happens on straight colors, and the edges of the image are
+/// extended outwards, both the same as the wgpu renderer.
+fn blur(source: &Pixels, radius: (f32, f32)) -> Pixels {
+    if radius == (0.0, 0.0) {
+        return source.clone();
+    }
+    let straight: Vec<[f32; 4]> = source.data.iter().map(|pixel| unmultiply(*pixel)).collect();
+    let straight = StraightPixels {
+        width: source.width,
+        height: source.height,
+        data: straight,
+    };
+
+    let mut data = Vec::with_capacity(source.data.len());
+    for y in 0..source.height {
+        for x in 0..source.width {
+            let mut sum = [0.0; 4];
+            let mut count = 0.0;
+            let mut offset_y = -radius.1;
+            while offset_y <= radius.1 {
+                let mut offset_x = -radius.0;
+                while offset_x <= radius.0 {
+                    let color = straight.sample(x as f32 + offset_x, y as f32 + offset_y);
+                    for (sum, channel) in sum.iter_mut().zip(color) {
+                        *sum += channel;
+                    }
+                    count += 1.0;
+                    offset_x += 1.0;
+                }
+                offset_y += 1.0;
+            }
+            data.push(premultiply(sum.map(|sum| sum / count)));
+        }
+    }
+    Pixels {
+        width: source.width,
+        height: source.height,
+        data,
+    }
+}
+
+/// Straight alpha pixels in the `0.0..=1.0` range.
+struct StraightPixels {
+    width: u32,
+    height: u32,
+    data: Vec<[f32; 4]>,
+}
+
+impl StraightPixels {
+    /// Samples between pixels linearly at `(x, y)` in pixel coordinates,
+    /// clamping to the edges of the image.
+    fn sample(&self, x: f32, y: f32) -> [f32; 4] {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let top_left = self.get(x0, y0);
+        let top_right = self.get(x0 + 1, y0);
+        let bottom_left = self.get(x0, y0 + 1);
+        let bottom_right = self.get(x0 + 1, y0 + 1);
+        let mut color = [0.0; 4];
+        for (i, channel) in color.iter_mut().enumerate() {
+            let top = top_left[i] + (top_right[i] - top_left[i]) * fx;
+            let bottom = bottom_left[i] + (bottom_right[i] - bottom_left[i]) * fx;
+            *channel = top + (bottom - top) * fy;
+        }
+        color
+    }
+
+    fn get(&self, x: i64, y: i64) -> [f32; 4] {
+        let x = x.clamp(0, i64::from(self.width) - 1);
+        let y = y.clamp(0, i64::from(self.height) - 1);
+        self.data[(y * i64::from(self.width) + x) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{Color, Fixed16, Fixed8, GlowFilter, GlowFilterFlags};
+
+    fn pixels() -> Pixels {
+        Pixels {
+            width: 2,
+            height: 2,
+            data: vec![[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255], [0; 4]],
+        }
+    }
+
+    #[test]
+    fn identity_color_matrix() {
+        let filter = Filter::ColorMatrixFilter(Default::default());
+        let result = apply_filter(&pixels(), (0, 0), (2, 2), &filter).unwrap();
+        assert_eq!(result.data, pixels().data);
+    }
+
+    #[test]
+    fn unsupported_filter() {
+        // These are left to the CPU filters in core.
+        let filter = Filter::GlowFilter(GlowFilter {
+            color: Color::BLACK,
+            blur_x: Fixed16::ONE,
+            blur_y: Fixed16::ONE,
+            strength: Fixed8::ONE,
+            flags: GlowFilterFlags::empty(),
+        });
+        assert!(apply_filter(&pixels(), (0, 0), (2, 2), &filter).is_none());
+    }
+}
@@ -0,0 +1,11 @@
+//! A render backend that draws on the CPU, without needing a GPU.
+
+mod backend;
+mod blend;
+mod commands;
+mod filters;
+mod mesh;
+mod surface;
+mod texture;
+
+pub use backend::SoftwareRenderBackend;
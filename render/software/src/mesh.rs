@@ -0,0 +1,263 @@
+use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::{self, DrawType};
+use swf::{GradientInterpolation, GradientSpread};
+
+/// The number of colors in a gradient lookup table.
+const GRADIENT_SIZE: usize = 256;
+
+/// A tessellated shape, ready to be drawn.
+#[derive(Debug)]
+pub struct Mesh {
+    pub draws: Vec<Draw>,
+}
+
+impl ShapeHandleImpl for Mesh {}
+
+pub fn as_mesh(handle: &ShapeHandle) -> &Mesh {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).unwrap()
+}
+
+impl Mesh {
+    pub fn new(
+        draws: Vec<tessellator::Draw>,
+        bitmap_source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Self {
+        Self {
+            draws: draws
+                .into_iter()
+                .filter_map(|draw| Draw::new(draw, bitmap_source, backend))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub fill: Fill,
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    /// The number of indices to draw when this shape is used as a mask,
+    /// which leaves out any strokes.
+    pub mask_index_count: usize,
+}
+
+impl Draw {
+    fn new(
+        draw: tessellator::Draw,
+        bitmap_source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Option<Self> {
+        let fill = match draw.draw_type {
+            DrawType::Color => Fill::Color,
+            DrawType::Gradient(gradient) => Fill::Gradient(Box::new(Gradient::new(gradient))),
+            DrawType::Bitmap(bitmap) => Fill::Bitmap {
+                matrix: bitmap.matrix,
+                handle: bitmap_source.bitmap_handle(bitmap.bitmap_id, backend)?,
+                is_smoothed: bitmap.is_smoothed,
+                is_repeating: bitmap.is_repeating,
+            },
+        };
+        Some(Self {
+            fill,
+            vertices: draw
+                .vertices
+                .into_iter()
+                .map(|vertex| Vertex {
+                    position: (vertex.x, vertex.y),
+                    color: [
+                        f32::from(vertex.color.r) / 255.0,
+                        f32::from(vertex.color.g) / 255.0,
+                        f32::from(vertex.color.b) / 255.0,
+                        f32::from(vertex.color.a) / 255.0,
+                    ],
+                })
+                .collect(),
+            indices: draw.indices,
+            mask_index_count: draw.mask_index_count as usize,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Vertex {
+    /// The position of the vertex in object space.
+    pub position: (f32, f32),
+    /// The straight alpha color of the vertex, in the `0.0..=1.0` range.
+    pub color: [f32; 4],
+}
+
+#[derive(Debug)]
+pub enum Fill {
+    /// Uses the colors of the vertices.
+    Color,
+    Gradient(Box<Gradient>),
+    Bitmap {
+        /// Maps object space to texture coordinates.
+        matrix: [[f32; 3]; 3],
+        handle: BitmapHandle,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+#[derive(Debug)]
+pub struct Gradient {
+    /// Maps object space to gradient space.
+    pub matrix: [[f32; 3]; 3],
+    gradient_type: GradientType,
+    spread: GradientSpread,
+    focal_point: f32,
+    is_linear_rgb: bool,
+    /// The straight alpha colors of the gradient, as stored in the lookup
+    /// texture of the GPU renderers.
+    colors: Box<[[f32; 4]; GRADIENT_SIZE]>,
+}
+
+impl Gradient {
+    fn new(gradient: tessellator::Gradient) -> Self {
+        let is_linear_rgb = gradient.interpolation == GradientInterpolation::LinearRgb;
+        let mut colors = Box::new([[0.0; 4]; GRADIENT_SIZE]);
+        if !gradient.records.is_empty() {
+            let convert = |c: u8| {
+                let c = f32::from(c) / 255.0;
+                if is_linear_rgb {
+                    srgb_to_linear(c)
+                } else {
+                    c
+                }
+            };
+            for (t, color) in colors.iter_mut().enumerate() {
+                let mut last = 0;
+                let mut next = 0;
+                for (i, record) in gradient.records.iter().enumerate().rev() {
+                    if usize::from(record.ratio) < t {
+                        last = i;
+                        next = (i + 1).min(gradient.records.len() - 1);
+                        break;
+                    }
+                }
+
+                let last_record = &gradient.records[last];
+                let next_record = &gradient.records[next];
+                let a = if next == last {
+                    // This can happen if we are before the first gradient record, or after the last one.
+                    0.0
+                } else {
+                    (t as f32 - f32::from(last_record.ratio))
+                        / (f32::from(next_record.ratio) - f32::from(last_record.ratio))
+                };
+                let lerp = |from: f32, to: f32| from + (to - from) * a;
+                // The lookup texture of the GPU renderers holds bytes.
+                let quantize = |value: f32| (value * 255.0).floor() / 255.0;
+                *color = [
+                    quantize(lerp(
+                        convert(last_record.color.r),
+                        convert(next_record.color.r),
+                    )),
+                    quantize(lerp(
+                        convert(last_record.color.g),
+                        convert(next_record.color.g),
+                    )),
+                    quantize(lerp(
+                        convert(last_record.color.b),
+                        convert(next_record.color.b),
+                    )),
+                    quantize(lerp(
+                        f32::from(last_record.color.a) / 255.0,
+                        f32::from(next_record.color.a) / 255.0,
+                    )),
+                ];
+            }
+        }
+
+        Self {
+            matrix: gradient.matrix,
+            gradient_type: gradient.gradient_type,
+            spread: gradient.repeat_mode,
+            focal_point: gradient.focal_point.to_f32().clamp(-0.98, 0.98),
+            is_linear_rgb,
+            colors,
+        }
+    }
+
+    /// Finds the straight alpha color of the gradient at `uv` in gradient space.
+    pub fn color_at(&self, uv: (f32, f32)) -> [f32; 4] {
+        let t = match self.gradient_type {
+            GradientType::Linear => uv.0,
+            GradientType::Radial => {
+                let (x, y) = (uv.0 * 2.0 - 1.0, uv.1 * 2.0 - 1.0);
+                (x * x + y * y).sqrt()
+            }
+            GradientType::Focal => {
+                let (x, y) = (uv.0 * 2.0 - 1.0, uv.1 * 2.0 - 1.0);
+                let focal = self.focal_point;
+                let (dx, dy) = (focal - x, -y);
+                let length = (dx * dx + dy * dy).sqrt();
+                let (dx, dy) = (dx / length, dy / length);
+                length / ((1.0 - focal * focal * dy * dy).sqrt() + focal * dx)
+            }
+        };
+        let t = match self.spread {
+            GradientSpread::Pad => t.clamp(0.0, 1.0),
+            GradientSpread::Reflect => {
+                let t = t.abs();
+                if (t as i32) & 1 == 0 {
+                    t.fract()
+                } else {
+                    1.0 - t.fract()
+                }
+            }
+            GradientSpread::Repeat => t - t.floor(),
+        };
+        // A NaN here comes from sampling exactly at the focal point.
+        let t = if t.is_nan() { 0.0 } else { t };
+
+        // Sample the lookup table linearly, clamping at its edges.
+        let position = (t * GRADIENT_SIZE as f32 - 0.5).clamp(0.0, (GRADIENT_SIZE - 1) as f32);
+        let index = position as usize;
+        let next = (index + 1).min(GRADIENT_SIZE - 1);
+        let fraction = position - index as f32;
+        let mut color = [0.0; 4];
+        for (i, channel) in color.iter_mut().enumerate() {
+            let from = self.colors[index][i];
+            *channel = from + (self.colors[next][i] - from) * fraction;
+        }
+
+        if self.is_linear_rgb {
+            for channel in &mut color[..3] {
+                *channel = linear_to_srgb(*channel);
+            }
+        }
+        color
+    }
+}
+
+/// Maps `point` through a column-major 3x3 matrix, as used by the
+/// tessellator for texture coordinates.
+pub fn transform_point(matrix: &[[f32; 3]; 3], point: (f32, f32)) -> (f32, f32) {
+    (
+        matrix[0][0] * point.0 + matrix[1][0] * point.1 + matrix[2][0],
+        matrix[0][1] * point.0 + matrix[1][1] * point.1 + matrix[2][1],
+    )
+}
+
+/// Converts a color channel from sRGB to linear color space.
+fn srgb_to_linear(color: f32) -> f32 {
+    if color <= 0.04045 {
+        color / 12.92
+    } else {
+        f32::powf((color + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Converts a color channel from linear to sRGB color space.
+fn linear_to_srgb(color: f32) -> f32 {
+    if color < 0.0031308 {
+        color * 12.92
+    } else {
+        1.055 * color.powf(1.0 / 2.4) - 0.055
+    }
+}
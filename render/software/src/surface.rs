@@ -0,0 +1,308 @@
+use ruffle_render::quality::StageQuality;
+
+/// A premultiplied RGBA color, stored as bytes.
+pub type Rgba = [u8; 4];
+
+/// Standard multisample positions, in sixteenths of a pixel from the
+/// pixel center. These match the patterns that GPUs use for each sample
+/// count, so that antialiased edges come out close to the wgpu renderer.
+const SAMPLES_1: &[(i8, i8)] = &[(0, 0)];
+const SAMPLES_2: &[(i8, i8)] = &[(4, 4), (-4, -4)];
+const SAMPLES_4: &[(i8, i8)] = &[(-2, -6), (6, -2), (-6, 2), (2, 6)];
+const SAMPLES_8: &[(i8, i8)] = &[
+    (1, -3),
+    (-1, 3),
+    (5, 1),
+    (-3, -5),
+    (-5, 5),
+    (-7, -1),
+    (3, 7),
+    (7, -7),
+];
+const SAMPLES_16: &[(i8, i8)] = &[
+    (1, 1),
+    (-1, -3),
+    (-3, 2),
+    (4, -1),
+    (-5, -2),
+    (2, 5),
+    (5, 3),
+    (3, -5),
+    (-2, 6),
+    (0, -7),
+    (-4, -6),
+    (-6, 4),
+    (-8, 0),
+    (7, -4),
+    (6, 7),
+    (-7, -8),
+];
+
+/// A multisampled render target.
+///
+/// Every pixel stores one color and one stencil value per sample, the same
+/// way a multisampled GPU texture does. Drawing operations decide coverage
+/// per sample, and `resolve` averages the samples into final pixels.
+pub struct Surface {
+    width: u32,
+    height: u32,
+    samples: &'static [(i8, i8)],
+    colors: Vec<Rgba>,
+    stencil: Vec<u8>,
+}
+
+impl Surface {
+    /// Creates a surface filled with `clear`.
+    pub fn new(width: u32, height: u32, quality: StageQuality, clear: Rgba) -> Self {
+        let samples = match quality.sample_count() {
+            0 | 1 => SAMPLES_1,
+            2 => SAMPLES_2,
+            3 | 4 => SAMPLES_4,
+            5..=8 => SAMPLES_8,
+            _ => SAMPLES_16,
+        };
+        let len = width as usize * height as usize * samples.len();
+        Self {
+            width,
+            height,
+            samples,
+            colors: vec![clear; len],
+            stencil: vec![0; len],
+        }
+    }
+
+    /// Creates a surface whose samples all start out with the color of the
+    /// corresponding pixel in `pixels`, which has `stride` pixels per row.
+    pub fn from_pixels(
+        width: u32,
+        height: u32,
+        quality: StageQuality,
+        pixels: &[Rgba],
+        stride: usize,
+    ) -> Self {
+        let mut surface = Self::new(width, height, quality, [0; 4]);
+        let sample_count = surface.sample_count();
+        for y in 0..height as usize {
+            for x in 0..width as usize {
+                let color = pixels[y * stride + x];
+                let start = (y * width as usize + x) * sample_count;
+                surface.colors[start..start + sample_count].fill(color);
+            }
+        }
+        surface
+    }
+
+    /// Creates an empty surface with the same size and samples as this one.
+    pub fn new_layer(&self) -> Self {
+        let len = self.colors.len();
+        Self {
+            width: self.width,
+            height: self.height,
+            samples: self.samples,
+            colors: vec![[0; 4]; len],
+            stencil: vec![0; len],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn colors(&self) -> &[Rgba] {
+        &self.colors
+    }
+
+    /// Splits the surface into its colors and stencil values, so that both
+    /// can be written to at the same time.
+    pub fn buffers_mut(&mut self) -> (&mut [Rgba], &mut [u8]) {
+        (&mut self.colors, &mut self.stencil)
+    }
+
+    /// Averages the samples of every pixel, producing `width * height`
+    /// premultiplied pixels.
+    pub fn resolve(&self) -> Vec<Rgba> {
+        let sample_count = self.sample_count();
+        if sample_count == 1 {
+            return self.colors.clone();
+        }
+        let half = sample_count as u32 / 2;
+        self.colors
+            .chunks_exact(sample_count)
+            .map(|samples| {
+                let mut sum = [0u32; 4];
+                for sample in samples {
+                    for (sum, channel) in sum.iter_mut().zip(sample) {
+                        *sum += u32::from(*channel);
+                    }
+                }
+                sum.map(|sum| ((sum + half) / sample_count as u32) as u8)
+            })
+            .collect()
+    }
+
+    /// Returns the rasterizer for this surface's size and samples.
+    pub fn rasterizer(&self) -> Rasterizer {
+        Rasterizer {
+            width: self.width,
+            height: self.height,
+            samples: self.samples,
+        }
+    }
+}
+
+/// Finds the samples of a surface that are covered by triangles.
+///
+/// This is separate from `Surface` so that the surface can be written to
+/// while a triangle is being rasterized.
+#[derive(Clone, Copy)]
+pub struct Rasterizer {
+    width: u32,
+    height: u32,
+    samples: &'static [(i8, i8)],
+}
+
+impl Rasterizer {
+    /// Finds the samples inside a triangle given in surface pixel coordinates.
+    ///
+    /// `on_pixel` is called once for every pixel with at least one covered
+    /// sample, with the pixel position and a bitmask of covered samples.
+    /// Edges follow the top-left rule, so triangles that share an edge never
+    /// both cover the same sample.
+    pub fn rasterize_triangle(
+        &self,
+        points: [(f32, f32); 3],
+        mut on_pixel: impl FnMut(u32, u32, u32),
+    ) {
+        let [a, b, c] = points.map(|(x, y)| (f64::from(x), f64::from(y)));
+        if !(a.0.is_finite()
+            && a.1.is_finite()
+            && b.0.is_finite()
+            && b.1.is_finite()
+            && c.0.is_finite()
+            && c.1.is_finite())
+        {
+            return;
+        }
+
+        // Wind every triangle the same way, so that "inside" is always
+        // a positive edge function.
+        let area = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+        let (b, c) = match area {
+            area if area > 0.0 => (b, c),
+            area if area < 0.0 => (c, b),
+            _ => return,
+        };
+        let edges = [Edge::new(a, b), Edge::new(b, c), Edge::new(c, a)];
+
+        let min_x = a.0.min(b.0).min(c.0).floor().max(0.0);
+        let min_y = a.1.min(b.1).min(c.1).floor().max(0.0);
+        let max_x = a.0.max(b.0).max(c.0).ceil().min(f64::from(self.width));
+        let max_y = a.1.max(b.1).max(c.1).ceil().min(f64::from(self.height));
+        if min_x >= max_x || min_y >= max_y {
+            return;
+        }
+
+        for y in min_y as u32..max_y as u32 {
+            for x in min_x as u32..max_x as u32 {
+                let mut mask = 0;
+                for (i, (offset_x, offset_y)) in self.samples.iter().enumerate() {
+                    let point = (
+                        f64::from(x) + 0.5 + f64::from(*offset_x) / 16.0,
+                        f64::from(y) + 0.5 + f64::from(*offset_y) / 16.0,
+                    );
+                    if edges.iter().all(|edge| edge.contains(point)) {
+                        mask |= 1 << i;
+                    }
+                }
+                if mask != 0 {
+                    on_pixel(x, y, mask);
+                }
+            }
+        }
+    }
+}
+
+/// One edge of a triangle, as an edge function.
+struct Edge {
+    start: (f64, f64),
+    delta: (f64, f64),
+    is_top_left: bool,
+}
+
+impl Edge {
+    fn new(start: (f64, f64), end: (f64, f64)) -> Self {
+        let delta = (end.0 - start.0, end.1 - start.1);
+        // With y pointing down and positive winding, a top edge is exactly
+        // horizontal and goes right, and a left edge goes up.
+        let is_top_left = (delta.1 == 0.0 && delta.0 > 0.0) || delta.1 < 0.0;
+        Self {
+            start,
+            delta,
+            is_top_left,
+        }
+    }
+
+    fn contains(&self, point: (f64, f64)) -> bool {
+        let value =
+            self.delta.0 * (point.1 - self.start.1) - self.delta.1 * (point.0 - self.start.0);
+        value > 0.0 || (value == 0.0 && self.is_top_left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coverage(surface: &Surface, triangles: &[[(f32, f32); 3]]) -> Vec<u32> {
+        let mut counts = vec![0; surface.colors().len() / surface.sample_count()];
+        for triangle in triangles {
+            surface
+                .rasterizer()
+                .rasterize_triangle(*triangle, |x, y, mask| {
+                    counts[(y * surface.width() + x) as usize] += mask.count_ones();
+                });
+        }
+        counts
+    }
+
+    #[test]
+    fn shared_edges_are_covered_once() {
+        let surface = Surface::new(4, 4, StageQuality::High16x16, [0; 4]);
+        // Two triangles making up a square, split along the diagonal.
+        let counts = coverage(
+            &surface,
+            &[
+                [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)],
+                [(0.0, 0.0), (4.0, 4.0), (0.0, 4.0)],
+            ],
+        );
+        assert!(counts.iter().all(|count| *count == 16));
+    }
+
+    #[test]
+    fn winding_does_not_matter() {
+        let surface = Surface::new(4, 4, StageQuality::High, [0; 4]);
+        let clockwise = coverage(&surface, &[[(0.5, 0.5), (3.5, 1.0), (1.0, 3.5)]]);
+        let counter_clockwise = coverage(&surface, &[[(0.5, 0.5), (1.0, 3.5), (3.5, 1.0)]]);
+        assert_eq!(clockwise, counter_clockwise);
+        assert!(clockwise.iter().any(|count| *count > 0));
+    }
+
+    #[test]
+    fn resolve_averages_samples() {
+        let mut surface = Surface::new(1, 1, StageQuality::High, [0; 4]);
+        surface.buffers_mut().0[..2].fill([255, 255, 255, 255]);
+        assert_eq!(surface.resolve(), vec![[128, 128, 128, 128]]);
+    }
+
+    #[test]
+    fn triangles_are_clipped_to_the_surface() {
+        let surface = Surface::new(2, 2, StageQuality::Low, [0; 4]);
+        let counts = coverage(&surface, &[[(-10.0, -10.0), (12.5, -10.0), (-10.0, 12.5)]]);
+        assert_eq!(counts, vec![1, 1, 1, 0]);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::surface::Rgba;
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl};
+use std::cell::{Ref, RefCell, RefMut};
+
+/// The pixels behind a `BitmapHandle`.
+///
+/// Pixels are stored with premultiplied alpha, the same as bitmaps uploaded
+/// to the GPU renderers.
+#[derive(Debug)]
+pub struct Texture {
+    pixels: RefCell<Pixels>,
+}
+
+impl BitmapHandleImpl for Texture {}
+
+impl Texture {
+    pub fn new(width: u32, height: u32, data: Vec<Rgba>) -> Self {
+        Self {
+            pixels: RefCell::new(Pixels {
+                width,
+                height,
+                data,
+            }),
+        }
+    }
+
+    pub fn pixels(&self) -> Ref<'_, Pixels> {
+        self.pixels.borrow()
+    }
+
+    pub fn pixels_mut(&self) -> RefMut<'_, Pixels> {
+        self.pixels.borrow_mut()
+    }
+}
+
+pub fn as_texture(handle: &BitmapHandle) -> &Texture {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0).unwrap()
+}
+
+#[derive(Clone, Debug)]
+pub struct Pixels {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<Rgba>,
+}
+
+impl Pixels {
+    /// Converts RGBA bytes into pixels.
+    pub fn from_rgba(width: u32, height: u32, rgba: &[u8]) -> Self {
+        Self {
+            width,
+            height,
+            data: rgba
+                .chunks_exact(4)
+                .map(|chunk| [chunk[0], chunk[1], chunk[2], chunk[3]])
+                .collect(),
+        }
+    }
+
+    /// Looks up the pixel at `uv`, where `(0, 0)` is the top left corner of
+    /// the texture and `(1, 1)` is its bottom right corner.
+    ///
+    /// Coordinates outside of the texture repeat it when `repeat` is set,
+    /// and are clamped to its edges otherwise. Smoothed lookups blend the
+    /// four nearest pixels.
+    pub fn sample(&self, uv: (f32, f32), smoothed: bool, repeat: bool) -> Rgba {
+        if self.width == 0 || self.height == 0 {
+            return [0; 4];
+        }
+        let x = uv.0 * self.width as f32;
+        let y = uv.1 * self.height as f32;
+        if !smoothed {
+            return self.get(x.floor() as i64, y.floor() as i64, repeat);
+        }
+
+        let x = x - 0.5;
+        let y = y - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let top_left = self.get(x0, y0, repeat);
+        let top_right = self.get(x0 + 1, y0, repeat);
+        let bottom_left = self.get(x0, y0 + 1, repeat);
+        let bottom_right = self.get(x0 + 1, y0 + 1, repeat);
+
+        let mut color = [0; 4];
+        for i in 0..4 {
+            let top = lerp(f32::from(top_left[i]), f32::from(top_right[i]), fx);
+            let bottom = lerp(f32::from(bottom_left[i]), f32::from(bottom_right[i]), fx);
+            color[i] = lerp(top, bottom, fy).round() as u8;
+        }
+        color
+    }
+
+    fn get(&self, x: i64, y: i64, repeat: bool) -> Rgba {
+        let (width, height) = (i64::from(self.width), i64::from(self.height));
+        let (x, y) = if repeat {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+        self.data[(y * width + x) as usize]
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
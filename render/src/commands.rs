@@ -1,11 +1,27 @@
 use crate::backend::ShapeHandle;
 use crate::bitmap::BitmapHandle;
 use crate::matrix::Matrix;
+use crate::matrix3d::Matrix3D;
+use crate::pixel_bender::interpreter::run_shader;
+use crate::pixel_bender::{
+    PixelBenderImage, PixelBenderParam, PixelBenderShader, PixelBenderShaderArgument,
+};
 use crate::transform::Transform;
-use swf::{BlendMode, Color};
+use std::sync::Arc;
+use swf::{BlendMode, Color, ColorTransform};
 
 pub trait CommandHandler {
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: Transform, smoothing: bool);
+
+    /// Renders a bitmap with perspective. `matrix` maps the bitmap's pixels to
+    /// homogeneous coordinates, which are divided by `w` to get a position in pixels.
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    );
     fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform);
     fn render_shape(&mut self, shape: ShapeHandle, transform: Transform);
     fn draw_rect(&mut self, color: Color, matrix: Matrix);
@@ -15,6 +31,109 @@ pub trait CommandHandler {
     fn pop_mask(&mut self);
 
     fn blend(&mut self, commands: CommandList, blend_mode: BlendMode);
+
+    /// Blends `commands` with what was drawn before them by running a Pixel Bender
+    /// shader, as set by `DisplayObject.blendShader`.
+    ///
+    /// Backends that can't run shaders draw the commands with a normal blend instead.
+    fn blend_shader(&mut self, commands: CommandList, _shader: BlendShader) {
+        self.blend(commands, BlendMode::Normal);
+    }
+}
+
+/// A Pixel Bender shader that blends content with the content below it.
+///
+/// The first texture input of the shader is the content below, and the second
+/// is the content being blended.
+#[derive(Debug, Clone)]
+pub struct BlendShader {
+    pub shader: Arc<PixelBenderShader>,
+    /// The values of every other parameter of the shader.
+    pub arguments: Vec<PixelBenderShaderArgument>,
+}
+
+impl BlendShader {
+    /// Runs the shader on the CPU over the bounding box of the visible pixels
+    /// of `content`.
+    ///
+    /// `background` and `content` are premultiplied RGBA pixels in rows of
+    /// `width`. Returns `None` if `content` is entirely transparent.
+    pub fn run(
+        &self,
+        width: usize,
+        background: &[[u8; 4]],
+        content: &[[u8; 4]],
+    ) -> Option<BlendShaderOutput> {
+        let (x_min, y_min, x_max, y_max) = content
+            .iter()
+            .enumerate()
+            .filter(|(_, pixel)| pixel[3] != 0)
+            .fold(None, |bounds, (i, _)| {
+                let (x, y) = (i % width, i / width);
+                Some(match bounds {
+                    Some((x_min, y_min, x_max, y_max)) => {
+                        (x.min(x_min), y.min(y_min), x.max(x_max), y.max(y_max))
+                    }
+                    None => (x, y, x, y),
+                })
+            })?;
+        let (region_width, region_height) = (x_max - x_min + 1, y_max - y_min + 1);
+
+        let mut inputs = [background, content].into_iter();
+        let mut arguments = self.arguments.clone();
+        for argument in &mut arguments {
+            let PixelBenderShaderArgument::ImageInput { index, image } = argument else {
+                continue;
+            };
+            let Some(pixels) = inputs.next() else {
+                break;
+            };
+            let channels = match self.shader.params.get(*index) {
+                Some(PixelBenderParam::Texture { channels, .. }) => *channels,
+                _ => 4,
+            };
+            let region = (y_min..=y_max)
+                .flat_map(|y| &pixels[y * width + x_min..=y * width + x_max])
+                .map(|pixel| pixel.map(|channel| f32::from(channel) / 255.0));
+            *image = Some(Arc::new(PixelBenderImage::from_premultiplied(
+                region_width as u32,
+                region_height as u32,
+                channels,
+                region,
+            )));
+        }
+        let pixels = run_shader(
+            &self.shader,
+            &arguments,
+            region_width as u32,
+            region_height as u32,
+        )
+        .to_premultiplied()
+        .into_iter()
+        .map(|pixel| pixel.map(|channel| (channel * 255.0).round() as u8))
+        .collect();
+
+        Some(BlendShaderOutput {
+            x: x_min,
+            y: y_min,
+            width: region_width,
+            height: region_height,
+            pixels,
+        })
+    }
+}
+
+/// The pixels produced by running a `BlendShader` on the CPU.
+#[derive(Debug)]
+pub struct BlendShaderOutput {
+    /// The left edge of the region that the shader ran over.
+    pub x: usize,
+    /// The top edge of the region that the shader ran over.
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+    /// The premultiplied RGBA pixels that replace the region.
+    pub pixels: Vec<[u8; 4]>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -35,6 +154,12 @@ impl CommandList {
                     transform,
                     smoothing,
                 } => handler.render_bitmap(bitmap, transform, smoothing),
+                Command::RenderBitmapProjected {
+                    bitmap,
+                    matrix,
+                    color_transform,
+                    smoothing,
+                } => handler.render_bitmap_projected(bitmap, matrix, color_transform, smoothing),
                 Command::RenderShape { shape, transform } => handler.render_shape(shape, transform),
                 Command::RenderStage3D { bitmap, transform } => {
                     handler.render_stage3d(bitmap, transform)
@@ -45,6 +170,7 @@ impl CommandList {
                 Command::DeactivateMask => handler.deactivate_mask(),
                 Command::PopMask => handler.pop_mask(),
                 Command::Blend(commands, blend_mode) => handler.blend(commands, blend_mode),
+                Command::BlendShader(commands, shader) => handler.blend_shader(commands, shader),
             }
         }
     }
@@ -59,6 +185,21 @@ impl CommandHandler for CommandList {
         });
     }
 
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        self.commands.push(Command::RenderBitmapProjected {
+            bitmap,
+            matrix,
+            color_transform,
+            smoothing,
+        });
+    }
+
     fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
         self.commands
             .push(Command::RenderStage3D { bitmap, transform });
@@ -92,6 +233,10 @@ impl CommandHandler for CommandList {
     fn blend(&mut self, commands: CommandList, blend_mode: BlendMode) {
         self.commands.push(Command::Blend(commands, blend_mode));
     }
+
+    fn blend_shader(&mut self, commands: CommandList, shader: BlendShader) {
+        self.commands.push(Command::BlendShader(commands, shader));
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -101,6 +246,12 @@ pub enum Command {
         transform: Transform,
         smoothing: bool,
     },
+    RenderBitmapProjected {
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    },
     RenderStage3D {
         bitmap: BitmapHandle,
         transform: Transform,
@@ -118,4 +269,5 @@ pub enum Command {
     DeactivateMask,
     PopMask,
     Blend(CommandList, BlendMode),
+    BlendShader(CommandList, BlendShader),
 }
@@ -1,4 +1,6 @@
 use crate::bitmap::BitmapHandle;
+use crate::pixel_bender::{PixelBenderShader, PixelBenderShaderArgument};
+use std::sync::Arc;
 use swf::Color;
 
 #[derive(Debug, Clone)]
@@ -12,6 +14,7 @@ pub enum Filter {
     GlowFilter(swf::GlowFilter),
     GradientBevelFilter(swf::GradientFilter),
     GradientGlowFilter(swf::GradientFilter),
+    ShaderFilter(ShaderFilter),
 }
 
 impl From<&swf::Filter> for Filter {
@@ -89,3 +92,16 @@ impl Default for DisplacementMapFilter {
         }
     }
 }
+
+/// A filter that runs a Pixel Bender shader, with the filtered pixels as the
+/// image of its first texture input.
+#[derive(Debug, Clone)]
+pub struct ShaderFilter {
+    pub shader: Arc<PixelBenderShader>,
+    /// The values of every other parameter of the shader.
+    pub arguments: Vec<PixelBenderShaderArgument>,
+    pub bottom_extension: i32,
+    pub left_extension: i32,
+    pub right_extension: i32,
+    pub top_extension: i32,
+}
@@ -5,6 +5,8 @@ pub mod bitmap;
 pub mod error;
 pub mod filters;
 pub mod matrix;
+pub mod matrix3d;
+pub mod pixel_bender;
 pub mod shape_utils;
 pub mod transform;
 pub mod utils;
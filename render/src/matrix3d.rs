@@ -0,0 +1,450 @@
+use crate::matrix::Matrix;
+use swf::Twips;
+
+/// A 4x4 matrix used for the 3D transforms of display objects ('2.5D').
+///
+/// Elements are stored in column-major order, matching ActionScript's `Matrix3D.rawData`.
+/// Translations are in pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3D {
+    pub raw_data: [f64; 16],
+}
+
+/// The components of a `Matrix3D` without perspective or skew.
+///
+/// The matrix is rebuilt by scaling, then rotating around the X, Y and Z axes in
+/// that order, then translating. Rotations are in radians.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Decomposition {
+    pub translation: [f64; 3],
+    pub rotation: [f64; 3],
+    pub scale: [f64; 3],
+}
+
+impl Matrix3D {
+    pub const IDENTITY: Self = Self {
+        raw_data: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0,
+        ],
+    };
+
+    /// The element at the given row and column.
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.raw_data[column * 4 + row]
+    }
+
+    fn set(&mut self, row: usize, column: usize, value: f64) {
+        self.raw_data[column * 4 + row] = value;
+    }
+
+    pub fn translate(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.raw_data[12] = x;
+        matrix.raw_data[13] = y;
+        matrix.raw_data[14] = z;
+        matrix
+    }
+
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.raw_data[0] = x;
+        matrix.raw_data[5] = y;
+        matrix.raw_data[10] = z;
+        matrix
+    }
+
+    pub fn rotate_x(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.set(1, 1, cos);
+        matrix.set(2, 1, sin);
+        matrix.set(1, 2, -sin);
+        matrix.set(2, 2, cos);
+        matrix
+    }
+
+    pub fn rotate_y(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 0, cos);
+        matrix.set(2, 0, -sin);
+        matrix.set(0, 2, sin);
+        matrix.set(2, 2, cos);
+        matrix
+    }
+
+    pub fn rotate_z(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 0, cos);
+        matrix.set(1, 0, sin);
+        matrix.set(0, 1, -sin);
+        matrix.set(1, 1, cos);
+        matrix
+    }
+
+    /// A perspective projection towards a viewer `focal_length` pixels in front of
+    /// the point `(center_x, center_y)` on the `z = 0` plane.
+    ///
+    /// Points on that plane keep their position, while points further away (with a
+    /// higher `z`) move towards the center.
+    pub fn perspective(focal_length: f64, center_x: f64, center_y: f64) -> Self {
+        let mut projection = Self::IDENTITY;
+        projection.set(0, 0, focal_length);
+        projection.set(1, 1, focal_length);
+        projection.set(3, 2, 1.0);
+        projection.set(3, 3, focal_length);
+        Self::translate(center_x, center_y, 0.0)
+            * projection
+            * Self::translate(-center_x, -center_y, 0.0)
+    }
+
+    /// Builds the matrix from its components. See `Decomposition`.
+    pub fn recompose(decomposition: &Decomposition) -> Self {
+        let [x, y, z] = decomposition.translation;
+        let [rotation_x, rotation_y, rotation_z] = decomposition.rotation;
+        let [scale_x, scale_y, scale_z] = decomposition.scale;
+        Self::translate(x, y, z)
+            * Self::rotate_z(rotation_z)
+            * Self::rotate_y(rotation_y)
+            * Self::rotate_x(rotation_x)
+            * Self::scale(scale_x, scale_y, scale_z)
+    }
+
+    /// Splits the matrix into its components. See `Decomposition`.
+    ///
+    /// Any perspective is ignored. Returns `None` if the matrix flattens all points
+    /// onto a plane or line, in which case rotations can't be recovered.
+    pub fn decompose(&self) -> Option<Decomposition> {
+        let column = |index: usize| [self.get(0, index), self.get(1, index), self.get(2, index)];
+        let length = |v: [f64; 3]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+
+        let columns = [column(0), column(1), column(2)];
+        let mut scale = columns.map(length);
+        if scale.contains(&0.0) {
+            return None;
+        }
+
+        // A mirrored matrix is treated as having a negative X scale.
+        let determinant = columns[0][0]
+            * (columns[1][1] * columns[2][2] - columns[2][1] * columns[1][2])
+            - columns[1][0] * (columns[0][1] * columns[2][2] - columns[2][1] * columns[0][2])
+            + columns[2][0] * (columns[0][1] * columns[1][2] - columns[1][1] * columns[0][2]);
+        if determinant < 0.0 {
+            scale[0] = -scale[0];
+        }
+
+        // The remaining matrix is `Rz * Ry * Rx`.
+        let r = |row: usize, index: usize| columns[index][row] / scale[index];
+        let rotation_y = (-r(2, 0)).clamp(-1.0, 1.0).asin();
+        let (rotation_x, rotation_z) = if r(2, 0).abs() < 1.0 - 1e-9 {
+            (r(2, 1).atan2(r(2, 2)), r(1, 0).atan2(r(0, 0)))
+        } else {
+            // Gimbal lock: only the difference of the X and Z rotations is known.
+            ((-r(1, 2)).atan2(r(1, 1)), 0.0)
+        };
+
+        Some(Decomposition {
+            translation: [self.get(0, 3), self.get(1, 3), self.get(2, 3)],
+            rotation: [rotation_x, rotation_y, rotation_z],
+            scale,
+        })
+    }
+
+    /// The determinant of the matrix.
+    pub fn determinant(&self) -> f64 {
+        let mut rows = [0, 1, 2, 3].map(|row| [0, 1, 2, 3].map(|column| self.get(row, column)));
+        let mut determinant = 1.0;
+        for column in 0..4 {
+            let pivot = (column..4)
+                .max_by(|&a, &b| rows[a][column].abs().total_cmp(&rows[b][column].abs()))
+                .unwrap_or(column);
+            if rows[pivot][column] == 0.0 {
+                return 0.0;
+            }
+            if pivot != column {
+                rows.swap(pivot, column);
+                determinant = -determinant;
+            }
+            determinant *= rows[column][column];
+            let pivot_row = rows[column];
+            for row in rows.iter_mut().skip(column + 1) {
+                let factor = row[column] / pivot_row[column];
+                for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(column) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+        determinant
+    }
+
+    /// The inverse of the matrix, or `None` if it isn't invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        // Gauss-Jordan elimination with partial pivoting.
+        let mut rows = [0, 1, 2, 3].map(|row| [0, 1, 2, 3].map(|column| self.get(row, column)));
+        let mut inverse =
+            [0, 1, 2, 3].map(|row| [0, 1, 2, 3].map(|column| Self::IDENTITY.get(row, column)));
+        for column in 0..4 {
+            let pivot = (column..4)
+                .max_by(|&a, &b| rows[a][column].abs().total_cmp(&rows[b][column].abs()))
+                .unwrap_or(column);
+            if rows[pivot][column].abs() < 1e-12 {
+                return None;
+            }
+            rows.swap(pivot, column);
+            inverse.swap(pivot, column);
+
+            let scale = rows[column][column];
+            for i in 0..4 {
+                rows[column][i] /= scale;
+                inverse[column][i] /= scale;
+            }
+            for row in 0..4 {
+                if row != column {
+                    let factor = rows[row][column];
+                    for i in 0..4 {
+                        rows[row][i] -= factor * rows[column][i];
+                        inverse[row][i] -= factor * inverse[column][i];
+                    }
+                }
+            }
+        }
+
+        let mut out = Self::IDENTITY;
+        for (row, values) in inverse.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                out.set(row, column, *value);
+            }
+        }
+        Some(out)
+    }
+
+    /// Transforms a point, returning its homogeneous coordinates `[x, y, z, w]`.
+    pub fn transform_point(&self, x: f64, y: f64, z: f64) -> [f64; 4] {
+        [0, 1, 2, 3].map(|row| {
+            self.get(row, 0) * x + self.get(row, 1) * y + self.get(row, 2) * z + self.get(row, 3)
+        })
+    }
+
+    /// Transforms a point and projects it onto the screen.
+    ///
+    /// Returns `None` if the point is at or behind the viewer.
+    pub fn project_point(&self, x: f64, y: f64, z: f64) -> Option<(f64, f64)> {
+        let [x, y, _, w] = self.transform_point(x, y, z);
+        (w > 0.0).then(|| (x / w, y / w))
+    }
+
+    /// Finds the point on the `z = 0` plane that projects onto the given screen position.
+    ///
+    /// Returns `None` if no such point exists, for example when the plane is viewed edge-on.
+    pub fn unproject_point(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        // Restricted to the `z = 0` plane, the projection is the 3x3 homography
+        // taking `(x, y, 1)` to `(x * w, y * w, w)`.
+        let m = |row: usize, column: usize| self.get([0, 1, 3][row], [0, 1, 3][column]);
+        let cofactor = |row: usize, column: usize| {
+            let (r0, r1) = ((row + 1) % 3, (row + 2) % 3);
+            let (c0, c1) = ((column + 1) % 3, (column + 2) % 3);
+            m(r0, c0) * m(r1, c1) - m(r0, c1) * m(r1, c0)
+        };
+        let determinant =
+            m(0, 0) * cofactor(0, 0) + m(0, 1) * cofactor(0, 1) + m(0, 2) * cofactor(0, 2);
+        if determinant.abs() < f64::EPSILON {
+            return None;
+        }
+
+        // The inverse is the transposed cofactor matrix, divided by the determinant.
+        // The division cancels out when dividing by `w`.
+        let point = [x, y, 1.0];
+        let [u, v, w] = [0, 1, 2].map(|row| {
+            (0..3)
+                .map(|column| cofactor(column, row) * point[column])
+                .sum::<f64>()
+        });
+        (w.abs() > f64::EPSILON).then(|| (u / w, v / w))
+    }
+
+    /// The 2D transform that this matrix applies to points on the `z = 0` plane,
+    /// ignoring any perspective.
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix {
+            a: self.get(0, 0) as f32,
+            b: self.get(1, 0) as f32,
+            c: self.get(0, 1) as f32,
+            d: self.get(1, 1) as f32,
+            tx: Twips::from_pixels(self.get(0, 3)),
+            ty: Twips::from_pixels(self.get(1, 3)),
+        }
+    }
+
+    /// The 2D transform that best matches this matrix on the rectangle
+    /// `(0, 0)..(width, height)`. This is used by renderers that can't draw with perspective.
+    ///
+    /// The corners at `(0, 0)`, `(width, 0)` and `(0, height)` are projected exactly.
+    pub fn affine_approximation(&self, width: f64, height: f64) -> Option<Matrix> {
+        let (x0, y0) = self.project_point(0.0, 0.0, 0.0)?;
+        let (x1, y1) = self.project_point(width, 0.0, 0.0)?;
+        let (x2, y2) = self.project_point(0.0, height, 0.0)?;
+        if width == 0.0 || height == 0.0 {
+            return None;
+        }
+        Some(Matrix {
+            a: ((x1 - x0) / width) as f32,
+            b: ((y1 - y0) / width) as f32,
+            c: ((x2 - x0) / height) as f32,
+            d: ((y2 - y0) / height) as f32,
+            tx: Twips::from_pixels(x0),
+            ty: Twips::from_pixels(y0),
+        })
+    }
+
+    /// The matrix as it's used by the shaders of GPU renderers, mapping points on the
+    /// `z = 0` plane to pixels. Depth isn't used, so the resulting `z` is always `0`.
+    pub fn to_world_matrix(&self) -> [[f32; 4]; 4] {
+        [0, 1, 2, 3].map(|column| {
+            [
+                self.get(0, column) as f32,
+                self.get(1, column) as f32,
+                0.0,
+                self.get(3, column) as f32,
+            ]
+        })
+    }
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Matrix> for Matrix3D {
+    fn from(matrix: Matrix) -> Self {
+        let mut matrix_3d = Self::IDENTITY;
+        matrix_3d.set(0, 0, matrix.a.into());
+        matrix_3d.set(1, 0, matrix.b.into());
+        matrix_3d.set(0, 1, matrix.c.into());
+        matrix_3d.set(1, 1, matrix.d.into());
+        matrix_3d.set(0, 3, matrix.tx.to_pixels());
+        matrix_3d.set(1, 3, matrix.ty.to_pixels());
+        matrix_3d
+    }
+}
+
+impl std::ops::Mul for Matrix3D {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let mut out = Self {
+            raw_data: [0.0; 16],
+        };
+        for row in 0..4 {
+            for column in 0..4 {
+                let value = (0..4).map(|i| self.get(row, i) * rhs.get(i, column)).sum();
+                out.set(row, column, value);
+            }
+        }
+        out
+    }
+}
+
+impl std::ops::MulAssign for Matrix3D {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn assert_matrix_eq(left: &Matrix3D, right: &Matrix3D) {
+        for (left, right) in left.raw_data.iter().zip(right.raw_data.iter()) {
+            assert_abs_diff_eq!(left, right, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn recompose_decompose() {
+        let decomposition = Decomposition {
+            translation: [10.0, -20.0, 30.0],
+            rotation: [0.3, -0.7, 1.2],
+            scale: [2.0, 0.5, 3.0],
+        };
+        let matrix = Matrix3D::recompose(&decomposition);
+        let result = matrix.decompose().unwrap();
+        for i in 0..3 {
+            assert_abs_diff_eq!(
+                result.translation[i],
+                decomposition.translation[i],
+                epsilon = 1e-9
+            );
+            assert_abs_diff_eq!(
+                result.rotation[i],
+                decomposition.rotation[i],
+                epsilon = 1e-9
+            );
+            assert_abs_diff_eq!(result.scale[i], decomposition.scale[i], epsilon = 1e-9);
+        }
+        assert_matrix_eq(&Matrix3D::recompose(&result), &matrix);
+    }
+
+    #[test]
+    fn rotation_z_matches_2d() {
+        let matrix = Matrix::rotate(0.5);
+        let matrix_3d = Matrix3D::rotate_z(0.5);
+        assert_matrix_eq(&Matrix3D::from(matrix), &matrix_3d);
+    }
+
+    #[test]
+    fn perspective() {
+        let projection = Matrix3D::perspective(100.0, 50.0, 50.0);
+
+        // Points on the `z = 0` plane stay where they are.
+        let (x, y) = projection.project_point(150.0, 0.0, 0.0).unwrap();
+        assert_abs_diff_eq!(x, 150.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(y, 0.0, epsilon = 1e-9);
+
+        // Points one focal length away are halfway to the center.
+        let (x, y) = projection.project_point(150.0, 0.0, 100.0).unwrap();
+        assert_abs_diff_eq!(x, 100.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(y, 25.0, epsilon = 1e-9);
+
+        // Points behind the viewer aren't visible.
+        assert_eq!(projection.project_point(0.0, 0.0, -200.0), None);
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Matrix3D::perspective(300.0, 100.0, 100.0)
+            * Matrix3D::recompose(&Decomposition {
+                translation: [10.0, -20.0, 30.0],
+                rotation: [0.3, -0.7, 1.2],
+                scale: [2.0, 0.5, 3.0],
+            });
+        let inverse = matrix.inverse().unwrap();
+        assert_matrix_eq(&(matrix * inverse), &Matrix3D::IDENTITY);
+        assert_abs_diff_eq!(
+            matrix.determinant() * inverse.determinant(),
+            1.0,
+            epsilon = 1e-9
+        );
+
+        assert_abs_diff_eq!(Matrix3D::scale(2.0, 3.0, -4.0).determinant(), -24.0);
+        assert_eq!(Matrix3D::scale(2.0, 0.0, 1.0).inverse(), None);
+    }
+
+    #[test]
+    fn unproject() {
+        let matrix = Matrix3D::perspective(300.0, 100.0, 100.0)
+            * Matrix3D::translate(20.0, 10.0, 50.0)
+            * Matrix3D::rotate_y(0.6)
+            * Matrix3D::rotate_x(-0.4);
+        let (x, y) = matrix.project_point(25.0, 40.0, 0.0).unwrap();
+        let (u, v) = matrix.unproject_point(x, y).unwrap();
+        assert_abs_diff_eq!(u, 25.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(v, 40.0, epsilon = 1e-6);
+    }
+}
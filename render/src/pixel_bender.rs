@@ -0,0 +1,931 @@
+//! Pixel Bender shaders, as loaded by `flash.display.Shader`.
+//!
+//! Shaders arrive as compiled `.pbj` bytecode, which is parsed into a [`PixelBenderShader`]
+//! once and can then be run on the CPU by the [`interpreter`]. Backends that can't run
+//! shaders natively fall back to the interpreter.
+
+pub mod interpreter;
+
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Register indices with this bit set refer to an integer register instead of a float
+/// register. Booleans are stored in integer registers as well.
+const INT_REGISTER_FLAG: u16 = 0x8000;
+
+/// The name of the parameter that receives the coordinate of the pixel being shaded.
+pub const OUT_COORD_NAME: &str = "_OutCoord";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PixelBenderParseError {
+    #[error("Unexpected end of shader bytecode")]
+    UnexpectedEnd,
+
+    #[error("Unknown shader opcode {0:#04x}")]
+    UnknownOpcode(u8),
+
+    #[error("Unsupported shader opcode {0:?}")]
+    UnsupportedOpcode(Opcode),
+
+    #[error("Unknown shader value type {0:#04x}")]
+    UnknownType(u8),
+
+    #[error("Unknown shader parameter qualifier {0}")]
+    UnknownQualifier(u8),
+
+    #[error("Shader parameter metadata without a parameter")]
+    OrphanedMetadata,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Nop = 0x00,
+    Add = 0x01,
+    Sub = 0x02,
+    Mul = 0x03,
+    Rcp = 0x04,
+    Div = 0x05,
+    Atan2 = 0x06,
+    Pow = 0x07,
+    Mod = 0x08,
+    Min = 0x09,
+    Max = 0x0A,
+    Step = 0x0B,
+    Sin = 0x0C,
+    Cos = 0x0D,
+    Tan = 0x0E,
+    Asin = 0x0F,
+    Acos = 0x10,
+    Atan = 0x11,
+    Exp = 0x12,
+    Exp2 = 0x13,
+    Log = 0x14,
+    Log2 = 0x15,
+    Sqrt = 0x16,
+    RSqrt = 0x17,
+    Abs = 0x18,
+    Sign = 0x19,
+    Floor = 0x1A,
+    Ceil = 0x1B,
+    Fract = 0x1C,
+    Mov = 0x1D,
+    FloatToInt = 0x1E,
+    IntToFloat = 0x1F,
+    MatMatMul = 0x20,
+    VecMatMul = 0x21,
+    MatVecMul = 0x22,
+    Normalize = 0x23,
+    Length = 0x24,
+    Distance = 0x25,
+    DotProduct = 0x26,
+    CrossProduct = 0x27,
+    Equal = 0x28,
+    NotEqual = 0x29,
+    LessThan = 0x2A,
+    LessThanEqual = 0x2B,
+    LogicalNot = 0x2C,
+    LogicalAnd = 0x2D,
+    LogicalOr = 0x2E,
+    LogicalXor = 0x2F,
+    SampleNearest = 0x30,
+    SampleLinear = 0x31,
+    LoadIntOrFloat = 0x32,
+    Loop = 0x33,
+    If = 0x34,
+    Else = 0x35,
+    EndIf = 0x36,
+    FloatToBool = 0x37,
+    BoolToFloat = 0x38,
+    IntToBool = 0x39,
+    BoolToInt = 0x3A,
+    VectorEqual = 0x3B,
+    VectorNotEqual = 0x3C,
+    BoolAny = 0x3D,
+    BoolAll = 0x3E,
+    KernelMetadata = 0xA0,
+    Parameter = 0xA1,
+    ParameterMetadata = 0xA2,
+    Texture = 0xA3,
+    Name = 0xA4,
+    Version = 0xA5,
+}
+
+impl Opcode {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        use Opcode::*;
+        const INSTRUCTIONS: [Opcode; 0x3F] = [
+            Nop,
+            Add,
+            Sub,
+            Mul,
+            Rcp,
+            Div,
+            Atan2,
+            Pow,
+            Mod,
+            Min,
+            Max,
+            Step,
+            Sin,
+            Cos,
+            Tan,
+            Asin,
+            Acos,
+            Atan,
+            Exp,
+            Exp2,
+            Log,
+            Log2,
+            Sqrt,
+            RSqrt,
+            Abs,
+            Sign,
+            Floor,
+            Ceil,
+            Fract,
+            Mov,
+            FloatToInt,
+            IntToFloat,
+            MatMatMul,
+            VecMatMul,
+            MatVecMul,
+            Normalize,
+            Length,
+            Distance,
+            DotProduct,
+            CrossProduct,
+            Equal,
+            NotEqual,
+            LessThan,
+            LessThanEqual,
+            LogicalNot,
+            LogicalAnd,
+            LogicalOr,
+            LogicalXor,
+            SampleNearest,
+            SampleLinear,
+            LoadIntOrFloat,
+            Loop,
+            If,
+            Else,
+            EndIf,
+            FloatToBool,
+            BoolToFloat,
+            IntToBool,
+            BoolToInt,
+            VectorEqual,
+            VectorNotEqual,
+            BoolAny,
+            BoolAll,
+        ];
+        match value {
+            0xA0 => Some(KernelMetadata),
+            0xA1 => Some(Parameter),
+            0xA2 => Some(ParameterMetadata),
+            0xA3 => Some(Texture),
+            0xA4 => Some(Name),
+            0xA5 => Some(Version),
+            _ => INSTRUCTIONS.get(usize::from(value)).copied(),
+        }
+    }
+}
+
+/// The type of a shader parameter or metadata value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelBenderType {
+    Float = 0x1,
+    Float2 = 0x2,
+    Float3 = 0x3,
+    Float4 = 0x4,
+    Float2x2 = 0x5,
+    Float3x3 = 0x6,
+    Float4x4 = 0x7,
+    Int = 0x8,
+    Int2 = 0x9,
+    Int3 = 0xA,
+    Int4 = 0xB,
+    String = 0xC,
+    Bool = 0xD,
+    Bool2 = 0xE,
+    Bool3 = 0xF,
+    Bool4 = 0x10,
+}
+
+impl PixelBenderType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        use PixelBenderType::*;
+        Some(match value {
+            0x1 => Float,
+            0x2 => Float2,
+            0x3 => Float3,
+            0x4 => Float4,
+            0x5 => Float2x2,
+            0x6 => Float3x3,
+            0x7 => Float4x4,
+            0x8 => Int,
+            0x9 => Int2,
+            0xA => Int3,
+            0xB => Int4,
+            0xC => String,
+            0xD => Bool,
+            0xE => Bool2,
+            0xF => Bool3,
+            0x10 => Bool4,
+            _ => return None,
+        })
+    }
+
+    /// The name of this type, as used by `flash.display.ShaderParameterType`.
+    pub fn name(self) -> &'static str {
+        use PixelBenderType::*;
+        match self {
+            Float => "float",
+            Float2 => "float2",
+            Float3 => "float3",
+            Float4 => "float4",
+            Float2x2 => "matrix2x2",
+            Float3x3 => "matrix3x3",
+            Float4x4 => "matrix4x4",
+            Int => "int",
+            Int2 => "int2",
+            Int3 => "int3",
+            Int4 => "int4",
+            String => "string",
+            Bool => "bool",
+            Bool2 => "bool2",
+            Bool3 => "bool3",
+            Bool4 => "bool4",
+        }
+    }
+
+    /// How many numbers make up a value of this type. Strings have no numbers.
+    pub fn size(self) -> usize {
+        use PixelBenderType::*;
+        match self {
+            Float | Int | Bool => 1,
+            Float2 | Int2 | Bool2 => 2,
+            Float3 | Int3 | Bool3 => 3,
+            Float4 | Int4 | Bool4 | Float2x2 => 4,
+            Float3x3 => 9,
+            Float4x4 => 16,
+            String => 0,
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        (self as u8) <= (PixelBenderType::Float4x4 as u8)
+    }
+}
+
+/// A value of a parameter or of metadata.
+///
+/// Booleans are stored as integers. Matrices are stored column by column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PixelBenderValue {
+    Float(Vec<f32>),
+    Int(Vec<i32>),
+    String(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixelBenderMetadata {
+    pub key: String,
+    pub value_type: PixelBenderType,
+    pub value: PixelBenderValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelBenderParamQualifier {
+    Input = 1,
+    Output = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelBenderRegKind {
+    Float,
+    Int,
+}
+
+/// A reference to (some components of) a register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PixelBenderReg {
+    /// The index of the register, without `INT_REGISTER_FLAG`.
+    pub index: u16,
+    pub kind: PixelBenderRegKind,
+    /// The components that are read or written, from 0 (`r`) to 3 (`a`).
+    /// Empty when the register holds a matrix.
+    pub channels: Vec<u8>,
+    /// The size of the matrix that starts at this register, if it holds one.
+    ///
+    /// A 2x2 matrix fits into a single register. Larger matrices take up one register
+    /// per column.
+    pub matrix: Option<u8>,
+}
+
+impl PixelBenderReg {
+    fn new(raw: u16, channels: Vec<u8>) -> Self {
+        Self {
+            index: raw & !INT_REGISTER_FLAG,
+            kind: if raw & INT_REGISTER_FLAG != 0 {
+                PixelBenderRegKind::Int
+            } else {
+                PixelBenderRegKind::Float
+            },
+            channels,
+            matrix: None,
+        }
+    }
+
+    fn matrix(raw: u16, size: u8) -> Self {
+        Self {
+            matrix: Some(size),
+            ..Self::new(raw, vec![])
+        }
+    }
+
+    /// A register written through a write mask, where bit `3 - i` enables component `i`.
+    fn masked(raw: u16, mask: u8) -> Self {
+        let channels = (0..4).filter(|i| mask & (0x8 >> i) != 0).collect();
+        Self::new(raw, channels)
+    }
+
+    /// A register read through a swizzle of `size` components.
+    fn swizzled(raw: u16, swizzle: u8, size: u8) -> Self {
+        let channels = (0..size).map(|i| (swizzle >> (6 - 2 * i)) & 0x3).collect();
+        Self::new(raw, channels)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PixelBenderParam {
+    Normal {
+        qualifier: PixelBenderParamQualifier,
+        param_type: PixelBenderType,
+        reg: PixelBenderReg,
+        name: String,
+        metadata: Vec<PixelBenderMetadata>,
+    },
+    Texture {
+        index: u8,
+        channels: u8,
+        name: String,
+    },
+}
+
+impl PixelBenderParam {
+    pub fn name(&self) -> &str {
+        match self {
+            PixelBenderParam::Normal { name, .. } | PixelBenderParam::Texture { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    Nop,
+    /// `dst = opcode(dst, src)` for binary operations, or `dst = opcode(src)` for unary ones.
+    Normal {
+        opcode: Opcode,
+        dst: PixelBenderReg,
+        src: PixelBenderReg,
+    },
+    LoadInt {
+        dst: PixelBenderReg,
+        value: i32,
+    },
+    LoadFloat {
+        dst: PixelBenderReg,
+        value: f32,
+    },
+    /// Samples `texture` at the coordinate in `src`.
+    Sample {
+        opcode: Opcode,
+        dst: PixelBenderReg,
+        src: PixelBenderReg,
+        texture: u8,
+    },
+    If {
+        src: PixelBenderReg,
+    },
+    Else,
+    EndIf,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PixelBenderShader {
+    pub name: String,
+    pub version: u32,
+    pub params: Vec<PixelBenderParam>,
+    pub metadata: Vec<PixelBenderMetadata>,
+    pub operations: Vec<Operation>,
+}
+
+impl PixelBenderShader {
+    /// The parameter that the shader writes its result to.
+    pub fn output_param(&self) -> Option<(&PixelBenderReg, PixelBenderType)> {
+        self.params.iter().find_map(|param| match param {
+            PixelBenderParam::Normal {
+                qualifier: PixelBenderParamQualifier::Output,
+                reg,
+                param_type,
+                ..
+            } => Some((reg, *param_type)),
+            _ => None,
+        })
+    }
+}
+
+/// A value that is passed to a shader for one of its parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PixelBenderShaderArgument {
+    /// The value of the parameter at `index` in `PixelBenderShader::params`.
+    ValueInput {
+        index: usize,
+        value: PixelBenderValue,
+    },
+    /// The image for the texture parameter at `index` in `PixelBenderShader::params`.
+    /// Textures without an image read as transparent black.
+    ImageInput {
+        index: usize,
+        image: Option<Arc<PixelBenderImage>>,
+    },
+}
+
+/// A block of pixels that a shader reads from or writes to, stored row by row
+/// with `channels` floats per pixel.
+///
+/// Colors are straight (not premultiplied), with every channel between 0 and 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixelBenderImage {
+    pub width: u32,
+    pub height: u32,
+    pub channels: u8,
+    pub data: Vec<f32>,
+}
+
+impl PixelBenderImage {
+    /// Builds an image from premultiplied RGBA pixels, keeping the first `channels`
+    /// channels of every pixel.
+    pub fn from_premultiplied(
+        width: u32,
+        height: u32,
+        channels: u8,
+        pixels: impl IntoIterator<Item = [f32; 4]>,
+    ) -> Self {
+        let mut data = Vec::with_capacity(width as usize * height as usize * channels as usize);
+        for [r, g, b, a] in pixels {
+            let straight = if a <= 0.0 {
+                [0.0; 4]
+            } else {
+                [r / a, g / a, b / a, a]
+            };
+            data.extend_from_slice(&straight[..usize::from(channels)]);
+        }
+        Self {
+            width,
+            height,
+            channels,
+            data,
+        }
+    }
+
+    /// Returns every pixel as premultiplied RGBA, with channels clamped between 0 and 1.
+    ///
+    /// Missing color channels are black, and missing alpha is opaque.
+    pub fn to_premultiplied(&self) -> Vec<[f32; 4]> {
+        let channels = usize::from(self.channels).max(1);
+        self.data
+            .chunks_exact(channels)
+            .map(|pixel| {
+                let mut rgba = [0.0, 0.0, 0.0, 1.0];
+                for (channel, value) in rgba.iter_mut().zip(pixel) {
+                    *channel = value.clamp(0.0, 1.0);
+                }
+                let alpha = rgba[3];
+                [rgba[0] * alpha, rgba[1] * alpha, rgba[2] * alpha, alpha]
+            })
+            .collect()
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], PixelBenderParseError> {
+        if self.data.len() < len {
+            return Err(PixelBenderParseError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], PixelBenderParseError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, PixelBenderParseError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16_le(&mut self) -> Result<u16, PixelBenderParseError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32_le(&mut self) -> Result<u32, PixelBenderParseError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn i16_be(&mut self) -> Result<i16, PixelBenderParseError> {
+        Ok(i16::from_be_bytes(self.array()?))
+    }
+
+    fn i32_be(&mut self) -> Result<i32, PixelBenderParseError> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    fn f32_be(&mut self) -> Result<f32, PixelBenderParseError> {
+        Ok(f32::from_be_bytes(self.array()?))
+    }
+
+    /// Reads a null-terminated string.
+    fn string(&mut self) -> Result<String, PixelBenderParseError> {
+        let len = self
+            .data
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(PixelBenderParseError::UnexpectedEnd)?;
+        let string = String::from_utf8_lossy(self.bytes(len)?).into_owned();
+        self.bytes(1)?;
+        Ok(string)
+    }
+
+    fn value_type(&mut self) -> Result<PixelBenderType, PixelBenderParseError> {
+        let value = self.u8()?;
+        PixelBenderType::from_u8(value).ok_or(PixelBenderParseError::UnknownType(value))
+    }
+
+    /// Reads a metadata value. Unlike the rest of the bytecode, these are big-endian.
+    fn value(
+        &mut self,
+        value_type: PixelBenderType,
+    ) -> Result<PixelBenderValue, PixelBenderParseError> {
+        if value_type == PixelBenderType::String {
+            return Ok(PixelBenderValue::String(self.string()?));
+        }
+        let size = value_type.size();
+        if value_type.is_float() {
+            let values = (0..size).map(|_| self.f32_be()).collect::<Result<_, _>>()?;
+            Ok(PixelBenderValue::Float(values))
+        } else {
+            let values = (0..size)
+                .map(|_| self.i16_be().map(i32::from))
+                .collect::<Result<_, _>>()?;
+            Ok(PixelBenderValue::Int(values))
+        }
+    }
+
+    fn metadata(&mut self) -> Result<PixelBenderMetadata, PixelBenderParseError> {
+        let value_type = self.value_type()?;
+        let key = self.string()?;
+        let value = self.value(value_type)?;
+        Ok(PixelBenderMetadata {
+            key,
+            value_type,
+            value,
+        })
+    }
+}
+
+/// Parses Pixel Bender bytecode, as passed to `flash.display.Shader`.
+pub fn parse_shader(bytecode: &[u8]) -> Result<PixelBenderShader, PixelBenderParseError> {
+    let mut shader = PixelBenderShader::default();
+    let mut reader = Reader { data: bytecode };
+    while !reader.data.is_empty() {
+        let raw_opcode = reader.u8()?;
+        let opcode =
+            Opcode::from_u8(raw_opcode).ok_or(PixelBenderParseError::UnknownOpcode(raw_opcode))?;
+        match opcode {
+            Opcode::KernelMetadata => shader.metadata.push(reader.metadata()?),
+            Opcode::ParameterMetadata => {
+                let metadata = reader.metadata()?;
+                match shader.params.last_mut() {
+                    Some(PixelBenderParam::Normal {
+                        metadata: param_metadata,
+                        ..
+                    }) => param_metadata.push(metadata),
+                    _ => return Err(PixelBenderParseError::OrphanedMetadata),
+                }
+            }
+            Opcode::Parameter => {
+                let raw_qualifier = reader.u8()?;
+                let qualifier = match raw_qualifier {
+                    1 => PixelBenderParamQualifier::Input,
+                    2 => PixelBenderParamQualifier::Output,
+                    _ => return Err(PixelBenderParseError::UnknownQualifier(raw_qualifier)),
+                };
+                let param_type = reader.value_type()?;
+                let raw_reg = reader.u16_le()?;
+                let mask = reader.u8()?;
+                let name = reader.string()?;
+                let reg = match param_type {
+                    PixelBenderType::Float2x2 => PixelBenderReg::matrix(raw_reg, 2),
+                    PixelBenderType::Float3x3 => PixelBenderReg::matrix(raw_reg, 3),
+                    PixelBenderType::Float4x4 => PixelBenderReg::matrix(raw_reg, 4),
+                    _ => PixelBenderReg::masked(raw_reg, mask & 0xF),
+                };
+                shader.params.push(PixelBenderParam::Normal {
+                    qualifier,
+                    param_type,
+                    reg,
+                    name,
+                    metadata: vec![],
+                });
+            }
+            Opcode::Texture => {
+                let index = reader.u8()?;
+                let channels = reader.u8()?;
+                let name = reader.string()?;
+                shader.params.push(PixelBenderParam::Texture {
+                    index,
+                    channels,
+                    name,
+                });
+            }
+            Opcode::Name => {
+                let len = reader.u16_le()?;
+                shader.name = String::from_utf8_lossy(reader.bytes(len.into())?).into_owned();
+            }
+            Opcode::Version => shader.version = reader.u32_le()?,
+            _ => shader.operations.push(read_operation(&mut reader, opcode)?),
+        }
+    }
+    Ok(shader)
+}
+
+/// Reads the 7 bytes that follow the opcode of an instruction.
+fn read_operation(reader: &mut Reader, opcode: Opcode) -> Result<Operation, PixelBenderParseError> {
+    let operation = match opcode {
+        Opcode::Nop => {
+            reader.bytes(7)?;
+            Operation::Nop
+        }
+        Opcode::Loop => return Err(PixelBenderParseError::UnsupportedOpcode(opcode)),
+        Opcode::LoadIntOrFloat => {
+            let raw_dst = reader.u16_le()?;
+            let mask = reader.u8()?;
+            let dst = PixelBenderReg::masked(raw_dst, mask >> 4);
+            if dst.kind == PixelBenderRegKind::Int {
+                Operation::LoadInt {
+                    dst,
+                    value: reader.i32_be()?,
+                }
+            } else {
+                Operation::LoadFloat {
+                    dst,
+                    value: reader.f32_be()?,
+                }
+            }
+        }
+        Opcode::If => {
+            reader.bytes(3)?;
+            let raw_src = reader.u16_le()?;
+            let swizzle = reader.u8()?;
+            reader.bytes(1)?;
+            Operation::If {
+                src: PixelBenderReg::swizzled(raw_src, swizzle, 1),
+            }
+        }
+        Opcode::Else | Opcode::EndIf => {
+            reader.bytes(7)?;
+            if opcode == Opcode::Else {
+                Operation::Else
+            } else {
+                Operation::EndIf
+            }
+        }
+        _ => {
+            let raw_dst = reader.u16_le()?;
+            let mask = reader.u8()?;
+            let raw_src = reader.u16_le()?;
+            let swizzle = reader.u8()?;
+            let extra = reader.u8()?;
+            let size = (mask & 0x3) + 1;
+            let matrix = (mask >> 2) & 0x3;
+            if matches!(opcode, Opcode::SampleNearest | Opcode::SampleLinear) {
+                Operation::Sample {
+                    opcode,
+                    dst: PixelBenderReg::masked(raw_dst, mask >> 4),
+                    src: PixelBenderReg::swizzled(raw_src, swizzle, 2),
+                    texture: extra,
+                }
+            } else if matrix != 0 {
+                // Matrices are always used whole, so they have neither a mask nor a swizzle.
+                let matrix = matrix + 1;
+                let dst = match opcode {
+                    Opcode::VecMatMul | Opcode::MatVecMul => {
+                        PixelBenderReg::masked(raw_dst, mask >> 4)
+                    }
+                    _ => PixelBenderReg::matrix(raw_dst, matrix),
+                };
+                Operation::Normal {
+                    opcode,
+                    dst,
+                    src: PixelBenderReg::matrix(raw_src, matrix),
+                }
+            } else {
+                Operation::Normal {
+                    opcode,
+                    dst: PixelBenderReg::masked(raw_dst, mask >> 4),
+                    src: PixelBenderReg::swizzled(raw_src, swizzle, size),
+                }
+            }
+        }
+    };
+    Ok(operation)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Assembles shader bytecode by hand.
+    #[derive(Default)]
+    pub struct Assembler(pub Vec<u8>);
+
+    impl Assembler {
+        pub fn param(
+            &mut self,
+            qualifier: u8,
+            ty: PixelBenderType,
+            reg: u16,
+            mask: u8,
+            name: &str,
+        ) {
+            self.0.extend([0xA1, qualifier, ty as u8]);
+            self.0.extend(reg.to_le_bytes());
+            self.0.push(mask);
+            self.0.extend(name.as_bytes());
+            self.0.push(0);
+        }
+
+        pub fn texture(&mut self, index: u8, channels: u8, name: &str) {
+            self.0.extend([0xA3, index, channels]);
+            self.0.extend(name.as_bytes());
+            self.0.push(0);
+        }
+
+        /// A normal instruction, with `src` being read through `swizzle`.
+        pub fn op(&mut self, opcode: Opcode, dst: u16, write_mask: u8, src: u16, swizzle: &[u8]) {
+            let swizzle_bits = swizzle
+                .iter()
+                .enumerate()
+                .fold(0, |bits, (i, c)| bits | (c << (6 - 2 * i)));
+            self.0.push(opcode as u8);
+            self.0.extend(dst.to_le_bytes());
+            self.0.push((write_mask << 4) | (swizzle.len() as u8 - 1));
+            self.0.extend(src.to_le_bytes());
+            self.0.extend([swizzle_bits, 0]);
+        }
+
+        pub fn matrix_op(&mut self, opcode: Opcode, dst: u16, write_mask: u8, src: u16, size: u8) {
+            self.0.push(opcode as u8);
+            self.0.extend(dst.to_le_bytes());
+            self.0.push((write_mask << 4) | ((size - 1) << 2));
+            self.0.extend(src.to_le_bytes());
+            self.0.extend([0, 0]);
+        }
+
+        pub fn sample(&mut self, opcode: Opcode, dst: u16, src: u16, texture: u8) {
+            self.0.push(opcode as u8);
+            self.0.extend(dst.to_le_bytes());
+            self.0.push(0xF1);
+            self.0.extend(src.to_le_bytes());
+            self.0.extend([0b0001_0000, texture]);
+        }
+
+        pub fn load_float(&mut self, dst: u16, write_mask: u8, value: f32) {
+            self.0.push(Opcode::LoadIntOrFloat as u8);
+            self.0.extend(dst.to_le_bytes());
+            self.0.push(write_mask << 4);
+            self.0.extend(value.to_be_bytes());
+        }
+
+        pub fn load_int(&mut self, dst: u16, write_mask: u8, value: i32) {
+            self.load_float(
+                dst | INT_REGISTER_FLAG,
+                write_mask,
+                f32::from_bits(value as u32),
+            );
+        }
+
+        pub fn if_(&mut self, src: u16, component: u8) {
+            self.0.extend([Opcode::If as u8, 0, 0, 0]);
+            self.0.extend((src | INT_REGISTER_FLAG).to_le_bytes());
+            self.0.extend([component << 6, 0]);
+        }
+
+        pub fn simple(&mut self, opcode: Opcode) {
+            self.0.push(opcode as u8);
+            self.0.extend([0; 7]);
+        }
+    }
+
+    #[test]
+    fn parse_header_and_params() {
+        let mut asm = Assembler::default();
+        asm.0.extend([0xA5, 1, 0, 0, 0]);
+        asm.0.extend([0xA4, 6, 0]);
+        asm.0.extend(b"Invert");
+        asm.0.extend([0xA0, PixelBenderType::String as u8]);
+        asm.0.extend(b"vendor\0Ruffle\0");
+        asm.param(1, PixelBenderType::Float2, 0, 0xC, OUT_COORD_NAME);
+        asm.texture(0, 4, "src");
+        asm.param(2, PixelBenderType::Float4, 1, 0xF, "dst");
+        asm.param(1, PixelBenderType::Float, 2, 0x8, "amount");
+        asm.0.extend([0xA2, PixelBenderType::Float as u8]);
+        asm.0.extend(b"defaultValue\0");
+        asm.0.extend(0.5f32.to_be_bytes());
+        asm.param(1, PixelBenderType::Float3x3, 3, 0, "transform");
+        asm.load_float(2, 0x4, 1.0);
+        asm.op(Opcode::Add, 1, 0xE, 4, &[0, 1, 2]);
+        asm.matrix_op(Opcode::MatVecMul, 1, 0xE, 3, 3);
+
+        let shader = parse_shader(&asm.0).expect("valid shader");
+        assert_eq!(shader.version, 1);
+        assert_eq!(shader.name, "Invert");
+        assert_eq!(
+            shader.metadata,
+            vec![PixelBenderMetadata {
+                key: "vendor".to_string(),
+                value_type: PixelBenderType::String,
+                value: PixelBenderValue::String("Ruffle".to_string()),
+            }]
+        );
+        assert_eq!(shader.params.len(), 5);
+        assert_eq!(
+            shader.params[1],
+            PixelBenderParam::Texture {
+                index: 0,
+                channels: 4,
+                name: "src".to_string()
+            }
+        );
+        match &shader.params[3] {
+            PixelBenderParam::Normal {
+                reg,
+                metadata,
+                param_type,
+                ..
+            } => {
+                assert_eq!(*param_type, PixelBenderType::Float);
+                assert_eq!(reg.channels, vec![0]);
+                assert_eq!(metadata[0].value, PixelBenderValue::Float(vec![0.5]));
+            }
+            param => panic!("unexpected param {param:?}"),
+        }
+        match &shader.params[4] {
+            PixelBenderParam::Normal { reg, .. } => assert_eq!(reg.matrix, Some(3)),
+            param => panic!("unexpected param {param:?}"),
+        }
+        assert_eq!(
+            shader.operations,
+            vec![
+                Operation::LoadFloat {
+                    dst: PixelBenderReg::new(2, vec![1]),
+                    value: 1.0,
+                },
+                Operation::Normal {
+                    opcode: Opcode::Add,
+                    dst: PixelBenderReg::new(1, vec![0, 1, 2]),
+                    src: PixelBenderReg::new(4, vec![0, 1, 2]),
+                },
+                Operation::Normal {
+                    opcode: Opcode::MatVecMul,
+                    dst: PixelBenderReg::new(1, vec![0, 1, 2]),
+                    src: PixelBenderReg::matrix(3, 3),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse_shader(&[0xA5, 1, 0]),
+            Err(PixelBenderParseError::UnexpectedEnd)
+        );
+        assert_eq!(
+            parse_shader(&[0x7F]),
+            Err(PixelBenderParseError::UnknownOpcode(0x7F))
+        );
+        assert_eq!(
+            parse_shader(&[0x33, 0, 0, 0, 0, 0, 0, 0]),
+            Err(PixelBenderParseError::UnsupportedOpcode(Opcode::Loop))
+        );
+    }
+}
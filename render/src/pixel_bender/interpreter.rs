@@ -0,0 +1,704 @@
+//! Runs Pixel Bender shaders on the CPU, one pixel at a time.
+
+use super::{
+    Opcode, Operation, PixelBenderImage, PixelBenderParam, PixelBenderReg, PixelBenderRegKind,
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderValue, OUT_COORD_NAME,
+};
+use std::collections::HashMap;
+
+/// Runs `shader` for every pixel of a `width` by `height` image, returning what it outputs.
+///
+/// The output has as many channels as the output parameter of the shader, and its values
+/// are neither clamped nor premultiplied.
+pub fn run_shader(
+    shader: &PixelBenderShader,
+    arguments: &[PixelBenderShaderArgument],
+    width: u32,
+    height: u32,
+) -> PixelBenderImage {
+    let mut interpreter = Interpreter::new(shader, arguments);
+    let output = shader.output_param();
+    let channels = output.map_or(4, |(reg, _)| reg.channels.len().max(1));
+    let mut data = Vec::with_capacity(width as usize * height as usize * channels);
+    for y in 0..height {
+        for x in 0..width {
+            interpreter.run(x as f32 + 0.5, y as f32 + 0.5);
+            match output {
+                Some((reg, _)) => data.extend_from_slice(&interpreter.read_floats(reg)[..channels]),
+                None => data.extend_from_slice(&[0.0; 4]),
+            }
+        }
+    }
+    PixelBenderImage {
+        width,
+        height,
+        channels: channels as u8,
+        data,
+    }
+}
+
+struct Interpreter<'a> {
+    shader: &'a PixelBenderShader,
+    /// Where each `If`, `Else` and `EndIf` jumps to.
+    jumps: Vec<usize>,
+    textures: HashMap<u8, &'a PixelBenderImage>,
+    out_coord: Option<&'a PixelBenderReg>,
+    /// The registers before every run, holding the values of the parameters.
+    initial_floats: Vec<[f32; 4]>,
+    initial_ints: Vec<[i32; 4]>,
+    floats: Vec<[f32; 4]>,
+    ints: Vec<[i32; 4]>,
+}
+
+impl<'a> Interpreter<'a> {
+    fn new(shader: &'a PixelBenderShader, arguments: &'a [PixelBenderShaderArgument]) -> Self {
+        let mut interpreter = Self {
+            shader,
+            jumps: find_jumps(&shader.operations),
+            textures: HashMap::new(),
+            out_coord: None,
+            initial_floats: vec![],
+            initial_ints: vec![],
+            floats: vec![],
+            ints: vec![],
+        };
+
+        for param in &shader.params {
+            if let PixelBenderParam::Normal { reg, name, .. } = param {
+                if name == OUT_COORD_NAME {
+                    interpreter.out_coord = Some(reg);
+                }
+            }
+        }
+
+        for argument in arguments {
+            match argument {
+                PixelBenderShaderArgument::ValueInput { index, value } => {
+                    if let Some(PixelBenderParam::Normal { reg, .. }) = shader.params.get(*index) {
+                        interpreter.write_value(reg, value);
+                    }
+                }
+                PixelBenderShaderArgument::ImageInput {
+                    index,
+                    image: Some(image),
+                } => {
+                    if let Some(PixelBenderParam::Texture { index, .. }) = shader.params.get(*index)
+                    {
+                        interpreter.textures.insert(*index, image.as_ref());
+                    }
+                }
+                PixelBenderShaderArgument::ImageInput { image: None, .. } => {}
+            }
+        }
+
+        interpreter.initial_floats = interpreter.floats.clone();
+        interpreter.initial_ints = interpreter.ints.clone();
+        interpreter
+    }
+
+    /// Writes a parameter value into its register, or registers for matrices.
+    fn write_value(&mut self, reg: &PixelBenderReg, value: &PixelBenderValue) {
+        match (value, reg.matrix) {
+            (PixelBenderValue::Float(values), Some(size)) => {
+                let mut matrix = [[0.0; 4]; 4];
+                for (i, value) in values.iter().enumerate().take(usize::from(size * size)) {
+                    matrix[i / usize::from(size)][i % usize::from(size)] = *value;
+                }
+                self.write_matrix(reg, size, &matrix);
+            }
+            (PixelBenderValue::Float(values), None) => {
+                let mut floats = [0.0; 4];
+                for (float, value) in floats.iter_mut().zip(values) {
+                    *float = *value;
+                }
+                self.write_floats(reg, floats);
+            }
+            (PixelBenderValue::Int(values), _) => {
+                let mut ints = [0; 4];
+                for (int, value) in ints.iter_mut().zip(values) {
+                    *int = *value;
+                }
+                self.write_ints(reg, ints);
+            }
+            (PixelBenderValue::String(_), _) => {}
+        }
+    }
+
+    fn run(&mut self, x: f32, y: f32) {
+        self.floats.clone_from(&self.initial_floats);
+        self.ints.clone_from(&self.initial_ints);
+        if let Some(out_coord) = self.out_coord {
+            self.write_floats(out_coord, [x, y, 0.0, 0.0]);
+        }
+
+        let shader = self.shader;
+        let mut pc = 0;
+        while let Some(operation) = shader.operations.get(pc) {
+            pc += 1;
+            match operation {
+                Operation::Nop | Operation::EndIf => {}
+                Operation::Normal { opcode, dst, src } => self.normal(*opcode, dst, src),
+                Operation::LoadInt { dst, value } => self.write_ints(dst, [*value; 4]),
+                Operation::LoadFloat { dst, value } => self.write_floats(dst, [*value; 4]),
+                Operation::Sample {
+                    opcode,
+                    dst,
+                    src,
+                    texture,
+                } => {
+                    let [x, y, ..] = self.read_floats(src);
+                    let color = match self.textures.get(texture) {
+                        Some(image) if *opcode == Opcode::SampleLinear => {
+                            sample_linear(image, x, y)
+                        }
+                        Some(image) => sample_nearest(image, x, y),
+                        None => [0.0; 4],
+                    };
+                    self.write_floats(dst, color);
+                }
+                Operation::If { src } => {
+                    if self.read_ints(src)[0] == 0 {
+                        pc = self.jumps[pc - 1];
+                    }
+                }
+                // Reaching an `Else` means that its `If` branch was taken.
+                Operation::Else => pc = self.jumps[pc - 1],
+            }
+        }
+    }
+
+    fn normal(&mut self, opcode: Opcode, dst: &PixelBenderReg, src: &PixelBenderReg) {
+        if let Some(size) = src.matrix {
+            self.matrix(opcode, dst, src, size);
+            return;
+        }
+
+        let len = dst.channels.len();
+        match opcode {
+            Opcode::Equal | Opcode::NotEqual | Opcode::LessThan | Opcode::LessThanEqual => {
+                // Comparisons store their results in the first integer register, in the
+                // components that the destination names.
+                let mut result = [0; 4];
+                for (i, result) in result.iter_mut().enumerate().take(len) {
+                    *result = self.compare(opcode, dst, src, i) as i32;
+                }
+                let flags = PixelBenderReg {
+                    index: 0,
+                    kind: PixelBenderRegKind::Int,
+                    ..dst.clone()
+                };
+                self.write_ints(&flags, result);
+            }
+            Opcode::VectorEqual | Opcode::VectorNotEqual => {
+                let n = src.channels.len();
+                let equal = self.read_leading_floats(dst, n)[..n] == self.read_floats(src)[..n];
+                let result = equal == (opcode == Opcode::VectorEqual);
+                self.write_ints(dst, [result as i32; 4]);
+            }
+            Opcode::BoolAny | Opcode::BoolAll => {
+                let values = self.read_ints(src);
+                let values = &values[..src.channels.len()];
+                let result = if opcode == Opcode::BoolAny {
+                    values.iter().any(|v| *v != 0)
+                } else {
+                    values.iter().all(|v| *v != 0)
+                };
+                self.write_ints(dst, [result as i32; 4]);
+            }
+            Opcode::FloatToBool | Opcode::IntToBool => {
+                let values = self.read_floats(src);
+                self.write_ints(dst, values.map(|v| (v != 0.0) as i32));
+            }
+            Opcode::FloatToInt => {
+                let values = self.read_floats(src);
+                self.write_ints(dst, values.map(|v| v as i32));
+            }
+            Opcode::IntToFloat | Opcode::BoolToFloat => {
+                let values = self.read_ints(src);
+                self.write_floats(dst, values.map(|v| v as f32));
+            }
+            Opcode::BoolToInt => {
+                let values = self.read_ints(src);
+                self.write_ints(dst, values);
+            }
+            _ if dst.kind == PixelBenderRegKind::Int => {
+                let result = int_operation(opcode, self.read_ints(dst), self.read_ints(src));
+                self.write_ints(dst, result);
+            }
+            Opcode::Length => {
+                let values = self.read_floats(src);
+                let length = dot(&values, &values, src.channels.len()).sqrt();
+                self.write_floats(dst, [length; 4]);
+            }
+            Opcode::Distance | Opcode::DotProduct => {
+                let n = src.channels.len();
+                let a = self.read_leading_floats(dst, n);
+                let b = self.read_floats(src);
+                let result = if opcode == Opcode::DotProduct {
+                    dot(&a, &b, n)
+                } else {
+                    let difference = [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]];
+                    dot(&difference, &difference, n).sqrt()
+                };
+                self.write_floats(dst, [result; 4]);
+            }
+            Opcode::Normalize => {
+                let values = self.read_floats(src);
+                let length = dot(&values, &values, len).sqrt();
+                self.write_floats(dst, values.map(|v| v / length));
+            }
+            Opcode::CrossProduct => {
+                let a = self.read_floats(dst);
+                let b = self.read_floats(src);
+                self.write_floats(
+                    dst,
+                    [
+                        a[1] * b[2] - a[2] * b[1],
+                        a[2] * b[0] - a[0] * b[2],
+                        a[0] * b[1] - a[1] * b[0],
+                        0.0,
+                    ],
+                );
+            }
+            _ => {
+                let a = self.read_floats(dst);
+                let b = self.read_floats(src);
+                let result = std::array::from_fn(|i| float_operation(opcode, a[i], b[i]));
+                self.write_floats(dst, result);
+            }
+        }
+    }
+
+    fn compare(
+        &self,
+        opcode: Opcode,
+        dst: &PixelBenderReg,
+        src: &PixelBenderReg,
+        i: usize,
+    ) -> bool {
+        if dst.kind == PixelBenderRegKind::Int {
+            let (a, b) = (self.read_ints(dst)[i], self.read_ints(src)[i]);
+            match opcode {
+                Opcode::Equal => a == b,
+                Opcode::NotEqual => a != b,
+                Opcode::LessThan => a < b,
+                _ => a <= b,
+            }
+        } else {
+            let (a, b) = (self.read_floats(dst)[i], self.read_floats(src)[i]);
+            match opcode {
+                Opcode::Equal => a == b,
+                Opcode::NotEqual => a != b,
+                Opcode::LessThan => a < b,
+                _ => a <= b,
+            }
+        }
+    }
+
+    /// Operations where `src` holds a matrix.
+    fn matrix(&mut self, opcode: Opcode, dst: &PixelBenderReg, src: &PixelBenderReg, size: u8) {
+        let n = usize::from(size);
+        let m = self.read_matrix(src, size);
+        match opcode {
+            Opcode::VecMatMul | Opcode::MatVecMul => {
+                let v = self.read_floats(dst);
+                let mut result = [0.0; 4];
+                for (i, result) in result.iter_mut().enumerate().take(n) {
+                    *result = if opcode == Opcode::VecMatMul {
+                        // Row vector times matrix: each result is a column dotted with the vector.
+                        (0..n).map(|j| v[j] * m[i][j]).sum()
+                    } else {
+                        (0..n).map(|j| m[j][i] * v[j]).sum()
+                    };
+                }
+                self.write_floats(dst, result);
+            }
+            _ => {
+                let d = self.read_matrix(dst, size);
+                let mut result = [[0.0; 4]; 4];
+                for column in 0..n {
+                    for row in 0..n {
+                        result[column][row] = if opcode == Opcode::MatMatMul {
+                            (0..n).map(|k| d[k][row] * m[column][k]).sum()
+                        } else {
+                            float_operation(opcode, d[column][row], m[column][row])
+                        };
+                    }
+                }
+                self.write_matrix(dst, size, &result);
+            }
+        }
+    }
+
+    /// Reads a matrix as an array of columns.
+    fn read_matrix(&self, reg: &PixelBenderReg, size: u8) -> [[f32; 4]; 4] {
+        let mut matrix = [[0.0; 4]; 4];
+        let index = usize::from(reg.index);
+        if size == 2 {
+            let values = self.floats.get(index).copied().unwrap_or_default();
+            matrix[0][..2].copy_from_slice(&values[..2]);
+            matrix[1][..2].copy_from_slice(&values[2..]);
+        } else {
+            for (i, column) in matrix.iter_mut().enumerate().take(usize::from(size)) {
+                *column = self.floats.get(index + i).copied().unwrap_or_default();
+            }
+        }
+        matrix
+    }
+
+    fn write_matrix(&mut self, reg: &PixelBenderReg, size: u8, matrix: &[[f32; 4]; 4]) {
+        let index = usize::from(reg.index);
+        if size == 2 {
+            let register = float_register(&mut self.floats, index);
+            register[..2].copy_from_slice(&matrix[0][..2]);
+            register[2..].copy_from_slice(&matrix[1][..2]);
+        } else {
+            for (i, column) in matrix.iter().enumerate().take(usize::from(size)) {
+                let register = float_register(&mut self.floats, index + i);
+                register[..usize::from(size)].copy_from_slice(&column[..usize::from(size)]);
+            }
+        }
+    }
+
+    /// Reads the channels of `reg` as floats, converting integer registers.
+    fn read_floats(&self, reg: &PixelBenderReg) -> [f32; 4] {
+        let mut values = [0.0; 4];
+        for (value, channel) in values.iter_mut().zip(&reg.channels) {
+            let channel = usize::from(*channel);
+            *value = match reg.kind {
+                PixelBenderRegKind::Float => self
+                    .floats
+                    .get(usize::from(reg.index))
+                    .map_or(0.0, |r| r[channel]),
+                PixelBenderRegKind::Int => self
+                    .ints
+                    .get(usize::from(reg.index))
+                    .map_or(0.0, |r| r[channel] as f32),
+            };
+        }
+        values
+    }
+
+    /// Reads `len` consecutive channels of `reg`, starting at its first channel.
+    ///
+    /// Operations that reduce two vectors to a single value only name one destination
+    /// channel, but read as many channels as the source has.
+    fn read_leading_floats(&self, reg: &PixelBenderReg, len: usize) -> [f32; 4] {
+        let first = reg.channels.first().copied().unwrap_or_default();
+        let reg = PixelBenderReg {
+            channels: (first..4).take(len).collect(),
+            ..reg.clone()
+        };
+        self.read_floats(&reg)
+    }
+
+    /// Reads the channels of `reg` as integers, truncating float registers.
+    fn read_ints(&self, reg: &PixelBenderReg) -> [i32; 4] {
+        let mut values = [0; 4];
+        for (value, channel) in values.iter_mut().zip(&reg.channels) {
+            let channel = usize::from(*channel);
+            *value = match reg.kind {
+                PixelBenderRegKind::Float => self
+                    .floats
+                    .get(usize::from(reg.index))
+                    .map_or(0, |r| r[channel] as i32),
+                PixelBenderRegKind::Int => self
+                    .ints
+                    .get(usize::from(reg.index))
+                    .map_or(0, |r| r[channel]),
+            };
+        }
+        values
+    }
+
+    /// Writes `values` to the channels of `reg`, in order.
+    fn write_floats(&mut self, reg: &PixelBenderReg, values: [f32; 4]) {
+        if reg.kind == PixelBenderRegKind::Int {
+            self.write_ints(reg, values.map(|v| v as i32));
+            return;
+        }
+        let register = float_register(&mut self.floats, usize::from(reg.index));
+        for (value, channel) in values.iter().zip(&reg.channels) {
+            register[usize::from(*channel)] = *value;
+        }
+    }
+
+    fn write_ints(&mut self, reg: &PixelBenderReg, values: [i32; 4]) {
+        if reg.kind == PixelBenderRegKind::Float {
+            let register = float_register(&mut self.floats, usize::from(reg.index));
+            for (value, channel) in values.iter().zip(&reg.channels) {
+                register[usize::from(*channel)] = *value as f32;
+            }
+            return;
+        }
+        let index = usize::from(reg.index);
+        if self.ints.len() <= index {
+            self.ints.resize(index + 1, [0; 4]);
+        }
+        for (value, channel) in values.iter().zip(&reg.channels) {
+            self.ints[index][usize::from(*channel)] = *value;
+        }
+    }
+}
+
+fn float_register(floats: &mut Vec<[f32; 4]>, index: usize) -> &mut [f32; 4] {
+    if floats.len() <= index {
+        floats.resize(index + 1, [0.0; 4]);
+    }
+    &mut floats[index]
+}
+
+/// Finds where every `If` jumps to when its condition is false (past its `Else`,
+/// or past its `EndIf`), and where every `Else` jumps to (past its `EndIf`).
+fn find_jumps(operations: &[Operation]) -> Vec<usize> {
+    let mut jumps = vec![operations.len(); operations.len()];
+    let mut stack = vec![];
+    for (i, operation) in operations.iter().enumerate() {
+        match operation {
+            Operation::If { .. } => stack.push(i),
+            Operation::Else => {
+                if let Some(start) = stack.pop() {
+                    jumps[start] = i + 1;
+                }
+                stack.push(i);
+            }
+            Operation::EndIf => {
+                if let Some(start) = stack.pop() {
+                    jumps[start] = i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    jumps
+}
+
+fn dot(a: &[f32; 4], b: &[f32; 4], len: usize) -> f32 {
+    a.iter().zip(b).take(len).map(|(a, b)| a * b).sum()
+}
+
+/// Applies an operation to a single float component, where `a` is the destination
+/// and `b` the source.
+fn float_operation(opcode: Opcode, a: f32, b: f32) -> f32 {
+    match opcode {
+        Opcode::Add => a + b,
+        Opcode::Sub => a - b,
+        Opcode::Mul => a * b,
+        Opcode::Rcp => 1.0 / b,
+        Opcode::Div => a / b,
+        Opcode::Atan2 => a.atan2(b),
+        Opcode::Pow => a.powf(b),
+        Opcode::Mod => a - b * (a / b).floor(),
+        Opcode::Min => a.min(b),
+        Opcode::Max => a.max(b),
+        Opcode::Step => {
+            if b < a {
+                0.0
+            } else {
+                1.0
+            }
+        }
+        Opcode::Sin => b.sin(),
+        Opcode::Cos => b.cos(),
+        Opcode::Tan => b.tan(),
+        Opcode::Asin => b.asin(),
+        Opcode::Acos => b.acos(),
+        Opcode::Atan => b.atan(),
+        Opcode::Exp => b.exp(),
+        Opcode::Exp2 => b.exp2(),
+        Opcode::Log => b.ln(),
+        Opcode::Log2 => b.log2(),
+        Opcode::Sqrt => b.sqrt(),
+        Opcode::RSqrt => 1.0 / b.sqrt(),
+        Opcode::Abs => b.abs(),
+        Opcode::Sign => {
+            if b > 0.0 {
+                1.0
+            } else if b < 0.0 {
+                -1.0
+            } else {
+                0.0
+            }
+        }
+        Opcode::Floor => b.floor(),
+        Opcode::Ceil => b.ceil(),
+        Opcode::Fract => b - b.floor(),
+        Opcode::Mov => b,
+        _ => {
+            tracing::warn!("Unsupported float shader operation {opcode:?}");
+            a
+        }
+    }
+}
+
+/// Applies an operation to every component of an integer or boolean register.
+fn int_operation(opcode: Opcode, a: [i32; 4], b: [i32; 4]) -> [i32; 4] {
+    std::array::from_fn(|i| {
+        let (a, b) = (a[i], b[i]);
+        match opcode {
+            Opcode::Add => a.wrapping_add(b),
+            Opcode::Sub => a.wrapping_sub(b),
+            Opcode::Mul => a.wrapping_mul(b),
+            Opcode::Div => a.checked_div(b).unwrap_or(0),
+            Opcode::Mod => a.checked_rem(b).unwrap_or(0),
+            Opcode::Min => a.min(b),
+            Opcode::Max => a.max(b),
+            Opcode::Abs => b.wrapping_abs(),
+            Opcode::Sign => b.signum(),
+            Opcode::Mov => b,
+            Opcode::LogicalNot => (b == 0) as i32,
+            Opcode::LogicalAnd => (a != 0 && b != 0) as i32,
+            Opcode::LogicalOr => (a != 0 || b != 0) as i32,
+            Opcode::LogicalXor => ((a != 0) != (b != 0)) as i32,
+            _ => {
+                tracing::warn!("Unsupported integer shader operation {opcode:?}");
+                a
+            }
+        }
+    })
+}
+
+/// Returns the channels of the pixel at the given position, or transparent black when
+/// it's outside of the image.
+fn pixel(image: &PixelBenderImage, x: i64, y: i64) -> [f32; 4] {
+    let mut color = [0.0; 4];
+    if x >= 0 && y >= 0 && x < i64::from(image.width) && y < i64::from(image.height) {
+        let channels = usize::from(image.channels);
+        let start = (y as usize * image.width as usize + x as usize) * channels;
+        if let Some(values) = image.data.get(start..start + channels) {
+            color[..channels.min(4)].copy_from_slice(&values[..channels.min(4)]);
+        }
+    }
+    color
+}
+
+fn sample_nearest(image: &PixelBenderImage, x: f32, y: f32) -> [f32; 4] {
+    pixel(image, x.floor() as i64, y.floor() as i64)
+}
+
+/// Bilinearly interpolates between the four pixels whose centers surround the position.
+fn sample_linear(image: &PixelBenderImage, x: f32, y: f32) -> [f32; 4] {
+    let (x, y) = (x - 0.5, y - 0.5);
+    let (left, top) = (x.floor(), y.floor());
+    let (fx, fy) = (x - left, y - top);
+    let (left, top) = (left as i64, top as i64);
+    let corners = [
+        (pixel(image, left, top), (1.0 - fx) * (1.0 - fy)),
+        (pixel(image, left + 1, top), fx * (1.0 - fy)),
+        (pixel(image, left, top + 1), (1.0 - fx) * fy),
+        (pixel(image, left + 1, top + 1), fx * fy),
+    ];
+    let mut color = [0.0; 4];
+    for (corner, weight) in corners {
+        for (channel, value) in color.iter_mut().zip(corner) {
+            *channel += value * weight;
+        }
+    }
+    color
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixel_bender::tests::Assembler;
+    use crate::pixel_bender::{parse_shader, PixelBenderType};
+    use std::sync::Arc;
+
+    /// `dst = sample(src, outCoord)`, with `amount` added to the color channels
+    /// of pixels in the left half of the image.
+    fn brighten_left_half() -> PixelBenderShader {
+        let mut asm = Assembler::default();
+        asm.param(1, PixelBenderType::Float2, 0, 0xC, OUT_COORD_NAME);
+        asm.texture(0, 4, "src");
+        asm.param(2, PixelBenderType::Float4, 1, 0xF, "dst");
+        asm.param(1, PixelBenderType::Float, 2, 0x8, "amount");
+        asm.sample(Opcode::SampleNearest, 1, 0, 0);
+        asm.load_float(3, 0x8, 2.0);
+        asm.op(Opcode::LessThan, 0, 0x8, 3, &[0]);
+        asm.if_(0, 0);
+        asm.op(Opcode::Add, 1, 0xE, 2, &[0, 0, 0]);
+        asm.simple(Opcode::Else);
+        asm.load_float(1, 0x1, 0.5);
+        asm.simple(Opcode::EndIf);
+        parse_shader(&asm.0).expect("valid shader")
+    }
+
+    #[test]
+    fn sample_and_branch() {
+        let shader = brighten_left_half();
+        let source = PixelBenderImage {
+            width: 4,
+            height: 1,
+            channels: 4,
+            data: [[0.1, 0.2, 0.3, 1.0]; 4].concat(),
+        };
+        let arguments = [
+            PixelBenderShaderArgument::ImageInput {
+                index: 1,
+                image: Some(Arc::new(source)),
+            },
+            PixelBenderShaderArgument::ValueInput {
+                index: 3,
+                value: PixelBenderValue::Float(vec![0.25]),
+            },
+        ];
+        let output = run_shader(&shader, &arguments, 4, 1);
+        assert_eq!(output.channels, 4);
+        let expected = [
+            [0.35, 0.45, 0.55, 1.0],
+            [0.35, 0.45, 0.55, 1.0],
+            [0.1, 0.2, 0.3, 0.5],
+            [0.1, 0.2, 0.3, 0.5],
+        ]
+        .concat();
+        for (actual, expected) in output.data.iter().zip(&expected) {
+            assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn missing_texture_is_transparent() {
+        let shader = brighten_left_half();
+        let output = run_shader(&shader, &[], 1, 1);
+        assert_eq!(output.data, vec![0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn matrix_vector_multiply() {
+        let mut asm = Assembler::default();
+        asm.param(2, PixelBenderType::Float3, 0, 0xE, "dst");
+        asm.param(1, PixelBenderType::Float3x3, 1, 0, "m");
+        asm.param(1, PixelBenderType::Float3, 4, 0xE, "v");
+        asm.op(Opcode::Mov, 0, 0xE, 4, &[0, 1, 2]);
+        asm.matrix_op(Opcode::MatVecMul, 0, 0xE, 1, 3);
+        let shader = parse_shader(&asm.0).expect("valid shader");
+
+        // Columns of a matrix that swaps x and y, then doubles z.
+        let matrix = vec![0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 2.0];
+        let arguments = [
+            PixelBenderShaderArgument::ValueInput {
+                index: 1,
+                value: PixelBenderValue::Float(matrix),
+            },
+            PixelBenderShaderArgument::ValueInput {
+                index: 2,
+                value: PixelBenderValue::Float(vec![1.0, 2.0, 3.0]),
+            },
+        ];
+        let output = run_shader(&shader, &arguments, 1, 1);
+        assert_eq!(output.data, vec![2.0, 1.0, 6.0]);
+    }
+
+    #[test]
+    fn linear_sampling() {
+        let image = PixelBenderImage {
+            width: 2,
+            height: 1,
+            channels: 1,
+            data: vec![0.0, 1.0],
+        };
+        assert_eq!(sample_linear(&image, 1.0, 0.5)[0], 0.5);
+        assert_eq!(sample_linear(&image, 0.5, 0.5)[0], 0.0);
+        assert_eq!(sample_nearest(&image, 1.9, 0.5)[0], 1.0);
+    }
+}
@@ -150,6 +150,76 @@ impl DrawCommand {
     }
 }
 
+/// Approximates a cubic Bezier curve from `start` with a series of quadratic curves.
+///
+/// The cubic is split into as few parts as needed for the quadratic approximation of
+/// each part to stay within a tenth of a pixel of the real curve.
+pub fn cubic_curve_to_quadratics(
+    start: (Twips, Twips),
+    control_a: (Twips, Twips),
+    control_b: (Twips, Twips),
+    anchor: (Twips, Twips),
+) -> Vec<DrawCommand> {
+    const TOLERANCE: f64 = 2.0;
+    const MAX_SEGMENTS: f64 = 64.0;
+
+    let point = |(x, y): (Twips, Twips)| (x.get() as f64, y.get() as f64);
+    let (p0, p1, p2, p3) = (
+        point(start),
+        point(control_a),
+        point(control_b),
+        point(anchor),
+    );
+
+    // Replacing the two control points of a cubic with the single control point
+    // `(3 * (p1 + p2) - (p0 + p3)) / 4` is off by at most
+    // `sqrt(3) / 36 * |p3 - 3 * p2 + 3 * p1 - p0|`, and splitting the cubic
+    // into `n` parts divides that error by `n^3`.
+    let error = (3.0f64).sqrt() / 36.0
+        * (p3.0 - 3.0 * p2.0 + 3.0 * p1.0 - p0.0).hypot(p3.1 - 3.0 * p2.1 + 3.0 * p1.1 - p0.1);
+    let segments = ((error / TOLERANCE).cbrt().ceil().min(MAX_SEGMENTS) as usize).max(1);
+
+    let position = |t: f64| {
+        let s = 1.0 - t;
+        let (w0, w1, w2, w3) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+        (
+            w0 * p0.0 + w1 * p1.0 + w2 * p2.0 + w3 * p3.0,
+            w0 * p0.1 + w1 * p1.1 + w2 * p2.1 + w3 * p3.1,
+        )
+    };
+    let tangent = |t: f64| {
+        let s = 1.0 - t;
+        let (w0, w1, w2) = (3.0 * s * s, 6.0 * s * t, 3.0 * t * t);
+        (
+            w0 * (p1.0 - p0.0) + w1 * (p2.0 - p1.0) + w2 * (p3.0 - p2.0),
+            w0 * (p1.1 - p0.1) + w1 * (p2.1 - p1.1) + w2 * (p3.1 - p2.1),
+        )
+    };
+    let to_twips = |value: f64| Twips::new(value.round() as i32);
+
+    let step = 1.0 / segments as f64;
+    (0..segments)
+        .map(|i| {
+            let (t0, t1) = (i as f64 * step, (i + 1) as f64 * step);
+            let (q0, q3) = (position(t0), position(t1));
+            let (d0, d1) = (tangent(t0), tangent(t1));
+            let q1 = (q0.0 + d0.0 * step / 3.0, q0.1 + d0.1 * step / 3.0);
+            let q2 = (q3.0 - d1.0 * step / 3.0, q3.1 - d1.1 * step / 3.0);
+            let (x2, y2) = if i + 1 == segments {
+                anchor
+            } else {
+                (to_twips(q3.0), to_twips(q3.1))
+            };
+            DrawCommand::CurveTo {
+                x1: to_twips((3.0 * (q1.0 + q2.0) - (q0.0 + q3.0)) / 4.0),
+                y1: to_twips((3.0 * (q1.1 + q2.1) - (q0.1 + q3.1)) / 4.0),
+                x2,
+                y2,
+            }
+        })
+        .collect()
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Point {
     x: Twips,
@@ -722,6 +792,85 @@ mod tests {
         }];
         assert_eq!(commands, expected);
     }
+
+    /// A cubic that is an elevated quadratic is converted back into that quadratic.
+    #[test]
+    fn cubic_curve_elevated_quadratic() {
+        let commands = cubic_curve_to_quadratics(
+            (Twips::new(0), Twips::new(0)),
+            (Twips::new(200), Twips::new(400)),
+            (Twips::new(400), Twips::new(400)),
+            (Twips::new(600), Twips::new(0)),
+        );
+        let expected = vec![DrawCommand::CurveTo {
+            x1: Twips::new(300),
+            y1: Twips::new(600),
+            x2: Twips::new(600),
+            y2: Twips::new(0),
+        }];
+        assert_eq!(commands, expected);
+    }
+
+    /// An S-shaped cubic is split into several quadratics that end at the anchor.
+    #[test]
+    fn cubic_curve_s_shape() {
+        let anchor = (Twips::from_pixels(100.0), Twips::from_pixels(0.0));
+        let commands = cubic_curve_to_quadratics(
+            (Twips::ZERO, Twips::ZERO),
+            (Twips::from_pixels(0.0), Twips::from_pixels(100.0)),
+            (Twips::from_pixels(100.0), Twips::from_pixels(-100.0)),
+            anchor,
+        );
+        assert!(commands.len() > 1);
+        assert_eq!(commands.last().map(DrawCommand::end_point), Some(anchor));
+    }
+
+    /// A point inside two overlapping squares is only filled with the non-zero rule.
+    #[test]
+    fn fill_hit_test_winding_rule() {
+        let square = |x: f64| {
+            [
+                DrawCommand::MoveTo {
+                    x: Twips::from_pixels(x),
+                    y: Twips::from_pixels(0.0),
+                },
+                DrawCommand::LineTo {
+                    x: Twips::from_pixels(x + 100.0),
+                    y: Twips::from_pixels(0.0),
+                },
+                DrawCommand::LineTo {
+                    x: Twips::from_pixels(x + 100.0),
+                    y: Twips::from_pixels(100.0),
+                },
+                DrawCommand::LineTo {
+                    x: Twips::from_pixels(x),
+                    y: Twips::from_pixels(100.0),
+                },
+                DrawCommand::LineTo {
+                    x: Twips::from_pixels(x),
+                    y: Twips::from_pixels(0.0),
+                },
+            ]
+        };
+        let commands: Vec<_> = square(0.0).into_iter().chain(square(50.0)).collect();
+        let overlap = (Twips::from_pixels(75.0), Twips::from_pixels(50.0));
+        let single = (Twips::from_pixels(25.0), Twips::from_pixels(50.0));
+        assert!(!draw_command_fill_hit_test(
+            &commands,
+            FillRule::EvenOdd,
+            overlap
+        ));
+        assert!(draw_command_fill_hit_test(
+            &commands,
+            FillRule::NonZero,
+            overlap
+        ));
+        assert!(draw_command_fill_hit_test(
+            &commands,
+            FillRule::EvenOdd,
+            single
+        ));
+    }
 }
 
 /* SHAPEFLAG HITTEST (point-in-contour)
@@ -863,7 +1012,11 @@ pub fn shape_hit_test(
 }
 
 /// Test whether the given point is contained within the paths specified by the draw commands.
-pub fn draw_command_fill_hit_test(commands: &[DrawCommand], test_point: (Twips, Twips)) -> bool {
+pub fn draw_command_fill_hit_test(
+    commands: &[DrawCommand],
+    winding_rule: FillRule,
+    test_point: (Twips, Twips),
+) -> bool {
     let mut cursor = (Twips::ZERO, Twips::ZERO);
     let mut fill_start = (Twips::ZERO, Twips::ZERO);
     let mut winding = 0;
@@ -890,7 +1043,10 @@ pub fn draw_command_fill_hit_test(commands: &[DrawCommand], test_point: (Twips,
         winding += winding_number_line(test_point, cursor, fill_start);
     }
 
-    winding & 0b1 != 0
+    match winding_rule {
+        FillRule::EvenOdd => winding & 0b1 != 0,
+        FillRule::NonZero => winding != 0,
+    }
 }
 
 /// Test whether the given point is contained within the strokes specified by the draw commands.
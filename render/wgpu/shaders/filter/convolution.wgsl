@@ -0,0 +1,65 @@
+#import filter
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct Filter {
+    default_color: vec4<f32>,
+    size: vec2<f32>,
+    columns: i32,
+    rows: i32,
+    divisor: f32,
+    bias: f32,
+    clamped: i32,
+    preserve_alpha: i32,
+    matrix: array<vec4<f32>, 64>,
+}
+
+#if use_push_constants == true
+    @group(2) @binding(0) var<uniform> filter_args: Filter;
+#else
+    @group(4) @binding(0) var<uniform> filter_args: Filter;
+#endif
+
+fn matrix_value(index: i32) -> f32 {
+    let values = filter_args.matrix[index / 4];
+    return values[index % 4];
+}
+
+@vertex
+fn main_vertex(in: filter::FilterVertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let f = filter_args;
+    let src = textureSampleLevel(filter::texture, filter::texture_sampler, in.uv, 0.0);
+
+    var color = vec4<f32>();
+    for (var y = 0; y < f.rows; y += 1) {
+        for (var x = 0; x < f.columns; x += 1) {
+            let offset = vec2<f32>(f32(x - f.columns / 2), f32(y - f.rows / 2));
+            var uv = in.uv + offset / f.size;
+            let outside = any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0));
+            if (f.clamped != 0) {
+                // Pixels beyond the edges repeat the closest edge pixel
+                uv = clamp(uv, vec2<f32>(0.5) / f.size, vec2<f32>(1.0) - vec2<f32>(0.5) / f.size);
+            }
+            var sample = textureSampleLevel(filter::texture, filter::texture_sampler, uv, 0.0);
+            sample = vec4<f32>(sample.rgb / max(sample.a, 0.0001), sample.a);
+            if (f.clamped == 0 && outside) {
+                sample = f.default_color;
+            }
+            color += sample * matrix_value(y * f.columns + x);
+        }
+    }
+    color = saturate(color / f.divisor + vec4<f32>(f.bias / 255.0));
+
+    if (f.preserve_alpha != 0) {
+        color.a = src.a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
@@ -0,0 +1,94 @@
+#import filter
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct Filter {
+    color: vec4<f32>,
+    component_x: i32,
+    component_y: i32,
+    mode: i32,
+    _padding: i32,
+    scale: vec2<f32>,
+    size: vec2<f32>,
+    map_point: vec2<f32>,
+    map_size: vec2<f32>,
+}
+
+#if use_push_constants == true
+    @group(2) @binding(0) var<uniform> filter_args: Filter;
+    @group(2) @binding(1) var map_texture: texture_2d<f32>;
+#else
+    @group(4) @binding(0) var<uniform> filter_args: Filter;
+    @group(4) @binding(1) var map_texture: texture_2d<f32>;
+#endif
+
+/// Reads a channel of the map, matching the values of `BitmapDataChannel`.
+fn map_component(color: vec4<f32>, component: i32) -> f32 {
+    if (component == 1) {
+        return color.r;
+    }
+    if (component == 2) {
+        return color.g;
+    }
+    if (component == 4) {
+        return color.b;
+    }
+    if (component == 8) {
+        return color.a;
+    }
+    // No displacement
+    return 0.5;
+}
+
+@vertex
+fn main_vertex(in: filter::FilterVertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let f = filter_args;
+    let position = in.uv * f.size;
+    let map_uv = (position - f.map_point) / f.map_size;
+    var map_color = textureSampleLevel(map_texture, filter::texture_sampler, map_uv, 0.0);
+    map_color = vec4<f32>(map_color.rgb / max(map_color.a, 0.0001), map_color.a);
+    if (any(map_uv < vec2<f32>(0.0)) || any(map_uv > vec2<f32>(1.0))) {
+        // Outside of the map, nothing moves.
+        map_color = vec4<f32>(0.5);
+    }
+
+    // A component value of 128 means no displacement.
+    let displacement = vec2<f32>(
+        (floor(map_component(map_color, f.component_x) * 255.0 + 0.5) - 128.0) * f.scale.x,
+        (floor(map_component(map_color, f.component_y) * 255.0 + 0.5) - 128.0) * f.scale.y,
+    ) / 256.0;
+    var uv = (position + displacement) / f.size;
+    let outside = any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0));
+
+    switch (f.mode) {
+        // Clamp
+        case 0: {
+            uv = clamp(uv, vec2<f32>(0.0), vec2<f32>(1.0));
+        }
+        // Color
+        case 1: {
+            if (outside) {
+                return f.color;
+            }
+        }
+        // Ignore
+        case 2: {
+            if (outside) {
+                uv = in.uv;
+            }
+        }
+        // Wrap
+        default: {
+            uv = fract(uv);
+        }
+    }
+    return textureSampleLevel(filter::texture, filter::texture_sampler, uv, 0.0);
+}
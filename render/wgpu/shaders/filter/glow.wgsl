@@ -0,0 +1,79 @@
+#import filter
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+/// Draws glows, drop shadows and bevels from a blurred copy of the source.
+/// The color of the effect comes from a gradient, indexed by its strength.
+struct Filter {
+    offset: vec2<f32>,
+    strength: f32,
+    bevel: i32,
+    inner: i32,
+    knockout: i32,
+    composite_source: i32,
+    on_top: i32,
+}
+
+#if use_push_constants == true
+    @group(2) @binding(0) var<uniform> filter_args: Filter;
+    @group(2) @binding(1) var blurred: texture_2d<f32>;
+    @group(2) @binding(2) var gradient: texture_2d<f32>;
+#else
+    @group(4) @binding(0) var<uniform> filter_args: Filter;
+    @group(4) @binding(1) var blurred: texture_2d<f32>;
+    @group(4) @binding(2) var gradient: texture_2d<f32>;
+#endif
+
+fn blurred_alpha(uv: vec2<f32>) -> f32 {
+    let alpha = textureSampleLevel(blurred, filter::texture_sampler, uv, 0.0).a;
+    // Nothing outside of the source can cast a shadow
+    if (any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0))) {
+        return 0.0;
+    }
+    return alpha;
+}
+
+@vertex
+fn main_vertex(in: filter::FilterVertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let f = filter_args;
+    let src = textureSample(filter::texture, filter::texture_sampler, in.uv);
+
+    var amount: f32;
+    if (f.bevel != 0) {
+        // The middle of the gradient is neither highlight nor shadow.
+        let shade = blurred_alpha(in.uv - f.offset) - blurred_alpha(in.uv + f.offset);
+        amount = clamp(shade * f.strength, -1.0, 1.0) * 0.5 + 0.5;
+    } else if (f.inner != 0) {
+        amount = saturate((1.0 - blurred_alpha(in.uv - f.offset)) * f.strength);
+    } else {
+        amount = saturate(blurred_alpha(in.uv - f.offset) * f.strength);
+    }
+    let color = textureSampleLevel(gradient, filter::texture_sampler, vec2<f32>((amount * 255.0 + 0.5) / 256.0, 0.5), 0.0);
+    var glow = vec4<f32>(color.rgb * color.a, color.a);
+
+    if (f.inner != 0) {
+        glow *= src.a;
+    }
+    if (f.inner != 0 || f.on_top != 0) {
+        if (f.knockout != 0 || f.composite_source == 0) {
+            return glow;
+        }
+        return glow + src * (1.0 - glow.a);
+    }
+
+    if (f.knockout != 0) {
+        return glow * (1.0 - src.a);
+    }
+    if (f.composite_source == 0) {
+        return glow;
+    }
+    return src + glow * (1.0 - src.a);
+}
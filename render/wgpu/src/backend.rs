@@ -647,6 +647,12 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         dest_point: (u32, u32),
         filter: Filter,
     ) -> Option<Box<dyn SyncHandle>> {
+        if let Filter::ShaderFilter(_) = filter {
+            // Pixel Bender shaders aren't translated to WGSL yet, so let the
+            // player run them on the CPU instead.
+            return None;
+        }
+
         let source_texture = as_texture(&source);
         let dest_texture = as_texture(&destination);
 
@@ -12,6 +12,7 @@ pub enum ComplexBlend {
     Erase,      // Can't be trivial, requires layer tracking
     Overlay,    // Can't be trivial, big math expression
     HardLight,  // Can't be trivial, big math expression
+    Shader,     // Pixel Bender blend shader, already run on the CPU
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -1,5 +1,8 @@
 use crate::globals::GlobalsUniform;
-use crate::{ColorAdjustments, GradientUniforms, TextureTransforms, Transforms};
+use crate::{
+    ColorAdjustments, ConvolutionFilterUniforms, DisplacementMapFilterUniforms, GlowFilterUniforms,
+    GradientUniforms, TextureTransforms, Transforms,
+};
 
 #[derive(Debug)]
 pub struct BindLayouts {
@@ -11,6 +14,9 @@ pub struct BindLayouts {
     pub blend: wgpu::BindGroupLayout,
     pub color_matrix_filter: wgpu::BindGroupLayout,
     pub blur_filter: wgpu::BindGroupLayout,
+    pub glow_filter: wgpu::BindGroupLayout,
+    pub convolution_filter: wgpu::BindGroupLayout,
+    pub displacement_map_filter: wgpu::BindGroupLayout,
 }
 
 impl BindLayouts {
@@ -211,6 +217,56 @@ impl BindLayouts {
             label: create_debug_label!("Blur filter binds").as_deref(),
         });
 
+        let filter_uniform_entry = |size: usize| wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: wgpu::BufferSize::new(size as u64),
+            },
+            count: None,
+        };
+        let filter_texture_entry = |binding: u32| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                multisampled: false,
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+            },
+            count: None,
+        };
+
+        let glow_filter = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &[
+                filter_uniform_entry(std::mem::size_of::<GlowFilterUniforms>()),
+                // The blurred copy of the source
+                filter_texture_entry(1),
+                // The colors of the glow, indexed by its strength
+                filter_texture_entry(2),
+            ],
+            label: create_debug_label!("Glow filter binds").as_deref(),
+        });
+
+        let convolution_filter =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[filter_uniform_entry(std::mem::size_of::<
+                    ConvolutionFilterUniforms,
+                >())],
+                label: create_debug_label!("Convolution filter binds").as_deref(),
+            });
+
+        let displacement_map_filter =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[
+                    filter_uniform_entry(std::mem::size_of::<DisplacementMapFilterUniforms>()),
+                    // The map bitmap
+                    filter_texture_entry(1),
+                ],
+                label: create_debug_label!("Displacement map filter binds").as_deref(),
+            });
+
         Self {
             globals,
             transforms,
@@ -220,6 +276,9 @@ impl BindLayouts {
             blend,
             color_matrix_filter,
             blur_filter,
+            glow_filter,
+            convolution_filter,
+            displacement_map_filter,
         }
     }
 }
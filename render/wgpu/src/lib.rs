@@ -163,6 +163,56 @@ impl From<TessGradient> for GradientUniforms {
     }
 }
 
+/// The arguments of the glow filter shader, which also draws drop shadows
+/// and bevels along with their gradient variants.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct GlowFilterUniforms {
+    /// How far the shadow is moved from the object, in texture coordinates.
+    offset: [f32; 2],
+    strength: f32,
+    bevel: i32,
+    inner: i32,
+    knockout: i32,
+    composite_source: i32,
+    on_top: i32,
+}
+
+/// The number of values that the convolution shader has room for,
+/// which fits the largest 15x15 matrix that Flash allows.
+const MAX_CONVOLUTION_MATRIX_SIZE: usize = 256;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct ConvolutionFilterUniforms {
+    default_color: [f32; 4],
+    /// The size of the filtered texture, in pixels.
+    size: [f32; 2],
+    columns: i32,
+    rows: i32,
+    divisor: f32,
+    bias: f32,
+    clamped: i32,
+    preserve_alpha: i32,
+    /// The matrix, packed into vectors to match the layout of uniform arrays.
+    matrix: [[f32; 4]; MAX_CONVOLUTION_MATRIX_SIZE / 4],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct DisplacementMapFilterUniforms {
+    color: [f32; 4],
+    component_x: i32,
+    component_y: i32,
+    mode: i32,
+    _padding: i32,
+    scale: [f32; 2],
+    /// The size of the filtered texture, in pixels.
+    size: [f32; 2],
+    map_point: [f32; 2],
+    map_size: [f32; 2],
+}
+
 #[derive(Debug)]
 pub enum QueueSyncHandle {
     AlreadyCopied {
@@ -48,6 +48,10 @@ pub struct Pipelines {
     pub complex_blends: EnumMap<ComplexBlend, ShapePipeline>,
     pub color_matrix_filter: wgpu::RenderPipeline,
     pub blur_filter: wgpu::RenderPipeline,
+    /// Draws glows, drop shadows and bevels, including their gradient variants.
+    pub glow_filter: wgpu::RenderPipeline,
+    pub convolution_filter: wgpu::RenderPipeline,
+    pub displacement_map_filter: wgpu::RenderPipeline,
 }
 
 impl ShapePipeline {
@@ -257,112 +261,44 @@ impl Pipelines {
             msaa_sample_count,
         ));
 
-        let color_matrix_filter_bindings = if device.limits().max_push_constant_size > 0 {
-            vec![
-                &bind_layouts.globals,
-                &bind_layouts.bitmap,
-                &bind_layouts.color_matrix_filter,
-            ]
-        } else {
-            vec![
-                &bind_layouts.globals,
-                &bind_layouts.transforms,
-                &bind_layouts.color_transforms,
-                &bind_layouts.bitmap,
-                &bind_layouts.color_matrix_filter,
-            ]
-        };
-
-        let color_matrix_filter_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: None,
-                bind_group_layouts: &color_matrix_filter_bindings,
-                push_constant_ranges: full_push_constants,
-            });
-
-        let color_matrix_filter = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: create_debug_label!("Color Matrix Filter").as_deref(),
-            layout: Some(&color_matrix_filter_layout),
-            vertex: wgpu::VertexState {
-                module: &shaders.color_matrix_filter,
-                entry_point: "main_vertex",
-                buffers: &VERTEX_BUFFERS_DESCRIPTION_POS,
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::default(),
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: msaa_sample_count,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shaders.color_matrix_filter,
-                entry_point: "main_fragment",
-                targets: &[Some(format.into())],
-            }),
-            multiview: None,
-        });
-
-        let blur_filter_bindings = if device.limits().max_push_constant_size > 0 {
-            vec![
-                &bind_layouts.globals,
-                &bind_layouts.bitmap,
-                &bind_layouts.blur_filter,
-            ]
-        } else {
-            vec![
-                &bind_layouts.globals,
-                &bind_layouts.transforms,
-                &bind_layouts.color_transforms,
-                &bind_layouts.bitmap,
-                &bind_layouts.blur_filter,
-            ]
-        };
-
-        let blur_filter_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: None,
-            bind_group_layouts: &blur_filter_bindings,
-            push_constant_ranges: full_push_constants,
-        });
-
-        let blur_filter = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: create_debug_label!("Blur Filter").as_deref(),
-            layout: Some(&blur_filter_layout),
-            vertex: wgpu::VertexState {
-                module: &shaders.blur_filter,
-                entry_point: "main_vertex",
-                buffers: &VERTEX_BUFFERS_DESCRIPTION_POS,
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::default(),
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: msaa_sample_count,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shaders.blur_filter,
-                entry_point: "main_fragment",
-                targets: &[Some(format.into())],
-            }),
-            multiview: None,
-        });
+        let filter_pipeline =
+            |name: &str, shader: &wgpu::ShaderModule, filter_layout: &wgpu::BindGroupLayout| {
+                create_filter_pipeline(
+                    name,
+                    device,
+                    format,
+                    shader,
+                    msaa_sample_count,
+                    bind_layouts,
+                    filter_layout,
+                    full_push_constants,
+                )
+            };
+        let color_matrix_filter = filter_pipeline(
+            "Color Matrix Filter",
+            &shaders.color_matrix_filter,
+            &bind_layouts.color_matrix_filter,
+        );
+        let blur_filter = filter_pipeline(
+            "Blur Filter",
+            &shaders.blur_filter,
+            &bind_layouts.blur_filter,
+        );
+        let glow_filter = filter_pipeline(
+            "Glow Filter",
+            &shaders.glow_filter,
+            &bind_layouts.glow_filter,
+        );
+        let convolution_filter = filter_pipeline(
+            "Convolution Filter",
+            &shaders.convolution_filter,
+            &bind_layouts.convolution_filter,
+        );
+        let displacement_map_filter = filter_pipeline(
+            "Displacement Map Filter",
+            &shaders.displacement_map_filter,
+            &bind_layouts.displacement_map_filter,
+        );
 
         Self {
             color: color_pipelines,
@@ -373,6 +309,9 @@ impl Pipelines {
             complex_blends: complex_blend_pipelines,
             color_matrix_filter,
             blur_filter,
+            glow_filter,
+            convolution_filter,
+            displacement_map_filter,
         }
     }
 }
@@ -420,6 +359,49 @@ fn create_pipeline_descriptor<'a>(
     }
 }
 
+/// Creates a pipeline that draws a filter over a whole quad, with the
+/// filter's own bindings following the bitmap being filtered.
+#[allow(clippy::too_many_arguments)]
+fn create_filter_pipeline(
+    name: &str,
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    shader: &wgpu::ShaderModule,
+    msaa_sample_count: u32,
+    bind_layouts: &BindLayouts,
+    filter_layout: &wgpu::BindGroupLayout,
+    push_constant_ranges: &[wgpu::PushConstantRange],
+) -> wgpu::RenderPipeline {
+    let bind_group_layouts = if device.limits().max_push_constant_size > 0 {
+        vec![&bind_layouts.globals, &bind_layouts.bitmap, filter_layout]
+    } else {
+        vec![
+            &bind_layouts.globals,
+            &bind_layouts.transforms,
+            &bind_layouts.color_transforms,
+            &bind_layouts.bitmap,
+            filter_layout,
+        ]
+    };
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: create_debug_label!("{} pipeline layout", name).as_deref(),
+        bind_group_layouts: &bind_group_layouts,
+        push_constant_ranges,
+    });
+
+    device.create_render_pipeline(&create_pipeline_descriptor(
+        create_debug_label!("{}", name).as_deref(),
+        shader,
+        shader,
+        &pipeline_layout,
+        None,
+        &[Some(format.into())],
+        &VERTEX_BUFFERS_DESCRIPTION_POS,
+        msaa_sample_count,
+    ))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn create_shape_pipeline(
     name: &str,
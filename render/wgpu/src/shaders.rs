@@ -23,6 +23,9 @@ pub struct Shaders {
     pub blend_shaders: EnumMap<ComplexBlend, wgpu::ShaderModule>,
     pub color_matrix_filter: wgpu::ShaderModule,
     pub blur_filter: wgpu::ShaderModule,
+    pub glow_filter: wgpu::ShaderModule,
+    pub convolution_filter: wgpu::ShaderModule,
+    pub displacement_map_filter: wgpu::ShaderModule,
 }
 
 impl Shaders {
@@ -87,6 +90,27 @@ impl Shaders {
             "filter/blur.wgsl",
             include_str!("../shaders/filter/blur.wgsl"),
         );
+        let glow_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/glow.wgsl",
+            include_str!("../shaders/filter/glow.wgsl"),
+        );
+        let convolution_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/convolution.wgsl",
+            include_str!("../shaders/filter/convolution.wgsl"),
+        );
+        let displacement_map_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/displacement_map.wgsl",
+            include_str!("../shaders/filter/displacement_map.wgsl"),
+        );
         let gradient_shader = make_shader(
             device,
             &mut composer,
@@ -104,6 +128,7 @@ impl Shaders {
             ComplexBlend::Erase => make_shader(device, &mut composer, &shader_defs, "blend/erase.wgsl", include_str!("../shaders/blend/erase.wgsl")),
             ComplexBlend::Overlay => make_shader(device, &mut composer, &shader_defs, "blend/overlay.wgsl", include_str!("../shaders/blend/overlay.wgsl")),
             ComplexBlend::HardLight => make_shader(device, &mut composer, &shader_defs, "blend/hardlight.wgsl", include_str!("../shaders/blend/hardlight.wgsl")),
+            ComplexBlend::Shader => make_shader(device, &mut composer, &shader_defs, "blend/shader.wgsl", include_str!("../shaders/blend/shader.wgsl")),
         };
 
         Self {
@@ -116,6 +141,9 @@ impl Shaders {
             blend_shaders,
             color_matrix_filter,
             blur_filter,
+            glow_filter,
+            convolution_filter,
+            displacement_map_filter,
         }
     }
 }
@@ -1,11 +1,15 @@
+mod blend_shader;
 mod commands;
+mod filters;
 pub mod target;
 
 use crate::backend::RenderTargetMode;
 use crate::blend::ComplexBlend;
 use crate::buffer_pool::TexturePool;
 use crate::mesh::Mesh;
+use crate::surface::blend_shader::run_blend_shader;
 use crate::surface::commands::{chunk_blends, Chunk, CommandRenderer, LayerRef};
+use crate::surface::filters::Glow;
 use crate::uniform_buffer::BufferStorage;
 use crate::utils::{remove_srgb, supported_sample_count};
 use crate::{
@@ -186,7 +190,7 @@ impl Surface {
         );
 
         for chunk in chunks {
-            match chunk {
+            let (texture, blend_mode, needs_depth) = match chunk {
                 Chunk::Draw(chunk, needs_depth) => {
                     let mut render_pass =
                         draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -225,136 +229,145 @@ impl Surface {
 
                     num_masks = renderer.num_masks();
                     mask_state = renderer.mask_state();
+                    continue;
                 }
                 Chunk::Blend(texture, blend_mode, needs_depth) => {
-                    let parent = match blend_mode {
-                        ComplexBlend::Alpha | ComplexBlend::Erase => match nearest_layer {
-                            LayerRef::None => {
-                                // An Alpha or Erase with no Layer above it should be ignored
-                                continue;
-                            }
-                            LayerRef::Current => &target,
-                            LayerRef::Parent(layer) => layer,
-                        },
-                        _ => &target,
-                    };
-
-                    let parent_blend_buffer =
-                        parent.update_blend_buffer(descriptors, texture_pool, draw_encoder);
-
-                    let blend_bind_group =
-                        descriptors
-                            .device
-                            .create_bind_group(&wgpu::BindGroupDescriptor {
-                                label: create_debug_label!(
-                                    "Complex blend binds {:?} {}",
-                                    blend_mode,
-                                    if needs_depth {
-                                        "(with depth)"
-                                    } else {
-                                        "(Depthless)"
-                                    }
-                                )
-                                .as_deref(),
-                                layout: &descriptors.bind_layouts.blend,
-                                entries: &[
-                                    wgpu::BindGroupEntry {
-                                        binding: 0,
-                                        resource: wgpu::BindingResource::TextureView(
-                                            parent_blend_buffer.view(),
-                                        ),
-                                    },
-                                    wgpu::BindGroupEntry {
-                                        binding: 1,
-                                        resource: wgpu::BindingResource::TextureView(
-                                            texture.view(),
-                                        ),
-                                    },
-                                    wgpu::BindGroupEntry {
-                                        binding: 2,
-                                        resource: wgpu::BindingResource::Sampler(
-                                            descriptors.bitmap_samplers.get_sampler(false, false),
-                                        ),
-                                    },
-                                ],
-                            });
+                    (texture, blend_mode, needs_depth)
+                }
+                Chunk::ShaderBlend(texture, shader, needs_depth) => {
+                    // The shader runs on the CPU, and its result replaces everything below.
+                    let texture = run_blend_shader(
+                        descriptors,
+                        uniform_buffers,
+                        color_buffers,
+                        uniform_encoder,
+                        draw_encoder,
+                        &target,
+                        &texture,
+                        &shader,
+                    );
+                    (texture, ComplexBlend::Shader, needs_depth)
+                }
+            };
 
-                    let mut render_pass =
-                        draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                            label: create_debug_label!(
-                                "Complex blend {:?} {}",
-                                blend_mode,
-                                if needs_depth {
-                                    "(with depth)"
-                                } else {
-                                    "(Depthless)"
-                                }
-                            )
-                            .as_deref(),
-                            color_attachments: &[target.color_attachments()],
-                            depth_stencil_attachment: if needs_depth {
-                                target.depth_attachment(descriptors, texture_pool)
+            let parent = match blend_mode {
+                ComplexBlend::Alpha | ComplexBlend::Erase => match nearest_layer {
+                    LayerRef::None => {
+                        // An Alpha or Erase with no Layer above it should be ignored
+                        continue;
+                    }
+                    LayerRef::Current => &target,
+                    LayerRef::Parent(layer) => layer,
+                },
+                _ => &target,
+            };
+
+            let parent_blend_buffer =
+                parent.update_blend_buffer(descriptors, texture_pool, draw_encoder);
+
+            let blend_bind_group =
+                descriptors
+                    .device
+                    .create_bind_group(&wgpu::BindGroupDescriptor {
+                        label: create_debug_label!(
+                            "Complex blend binds {:?} {}",
+                            blend_mode,
+                            if needs_depth {
+                                "(with depth)"
                             } else {
-                                None
+                                "(Depthless)"
+                            }
+                        )
+                        .as_deref(),
+                        layout: &descriptors.bind_layouts.blend,
+                        entries: &[
+                            wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: wgpu::BindingResource::TextureView(
+                                    parent_blend_buffer.view(),
+                                ),
                             },
-                        });
-                    render_pass.set_bind_group(0, target.globals().bind_group(), &[]);
+                            wgpu::BindGroupEntry {
+                                binding: 1,
+                                resource: wgpu::BindingResource::TextureView(texture.view()),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 2,
+                                resource: wgpu::BindingResource::Sampler(
+                                    descriptors.bitmap_samplers.get_sampler(false, false),
+                                ),
+                            },
+                        ],
+                    });
 
+            let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: create_debug_label!(
+                    "Complex blend {:?} {}",
+                    blend_mode,
                     if needs_depth {
-                        match mask_state {
-                            MaskState::NoMask => {}
-                            MaskState::DrawMaskStencil => {
-                                render_pass.set_stencil_reference(num_masks - 1);
-                            }
-                            MaskState::DrawMaskedContent => {
-                                render_pass.set_stencil_reference(num_masks);
-                            }
-                            MaskState::ClearMaskStencil => {
-                                render_pass.set_stencil_reference(num_masks);
-                            }
-                        }
-                        render_pass.set_pipeline(
-                            self.pipelines.complex_blends[blend_mode].pipeline_for(mask_state),
-                        );
+                        "(with depth)"
                     } else {
-                        render_pass.set_pipeline(
-                            self.pipelines.complex_blends[blend_mode].depthless_pipeline(),
-                        );
+                        "(Depthless)"
                     }
+                )
+                .as_deref(),
+                color_attachments: &[target.color_attachments()],
+                depth_stencil_attachment: if needs_depth {
+                    target.depth_attachment(descriptors, texture_pool)
+                } else {
+                    None
+                },
+            });
+            render_pass.set_bind_group(0, target.globals().bind_group(), &[]);
 
-                    if descriptors.limits.max_push_constant_size > 0 {
-                        render_pass.set_push_constants(
-                            wgpu::ShaderStages::VERTEX,
-                            0,
-                            bytemuck::cast_slice(&[Transforms {
-                                world_matrix: [
-                                    [self.size.width as f32, 0.0, 0.0, 0.0],
-                                    [0.0, self.size.height as f32, 0.0, 0.0],
-                                    [0.0, 0.0, 1.0, 0.0],
-                                    [0.0, 0.0, 0.0, 1.0],
-                                ],
-                            }]),
-                        );
-                        render_pass.set_bind_group(1, &blend_bind_group, &[]);
-                    } else {
-                        render_pass.set_bind_group(
-                            1,
-                            target.whole_frame_bind_group(descriptors),
-                            &[0],
-                        );
-                        render_pass.set_bind_group(2, &blend_bind_group, &[]);
+            if needs_depth {
+                match mask_state {
+                    MaskState::NoMask => {}
+                    MaskState::DrawMaskStencil => {
+                        render_pass.set_stencil_reference(num_masks - 1);
+                    }
+                    MaskState::DrawMaskedContent => {
+                        render_pass.set_stencil_reference(num_masks);
+                    }
+                    MaskState::ClearMaskStencil => {
+                        render_pass.set_stencil_reference(num_masks);
                     }
-
-                    render_pass.set_vertex_buffer(0, descriptors.quad.vertices_pos.slice(..));
-                    render_pass.set_index_buffer(
-                        descriptors.quad.indices.slice(..),
-                        wgpu::IndexFormat::Uint32,
-                    );
-
-                    render_pass.draw_indexed(0..6, 0, 0..1);
-                    drop(render_pass);
                 }
+                render_pass.set_pipeline(
+                    self.pipelines.complex_blends[blend_mode].pipeline_for(mask_state),
+                );
+            } else {
+                render_pass
+                    .set_pipeline(self.pipelines.complex_blends[blend_mode].depthless_pipeline());
+            }
+
+            if descriptors.limits.max_push_constant_size > 0 {
+                render_pass.set_push_constants(
+                    wgpu::ShaderStages::VERTEX,
+                    0,
+                    bytemuck::cast_slice(&[Transforms {
+                        world_matrix: [
+                            [self.size.width as f32, 0.0, 0.0, 0.0],
+                            [0.0, self.size.height as f32, 0.0, 0.0],
+                            [0.0, 0.0, 1.0, 0.0],
+                            [0.0, 0.0, 0.0, 1.0],
+                        ],
+                    }]),
+                );
+                render_pass.set_bind_group(1, &blend_bind_group, &[]);
+            } else {
+                render_pass.set_bind_group(1, target.whole_frame_bind_group(descriptors), &[0]);
+                render_pass.set_bind_group(2, &blend_bind_group, &[]);
             }
+
+            render_pass.set_vertex_buffer(0, descriptors.quad.vertices_pos.slice(..));
+            render_pass.set_index_buffer(
+                descriptors.quad.indices.slice(..),
+                wgpu::IndexFormat::Uint32,
+            );
+
+            render_pass.draw_indexed(0..6, 0, 0..1);
+            drop(render_pass);
         }
 
         target
@@ -404,9 +417,72 @@ impl Surface {
                 source_size,
                 &filter,
             ),
-            _ => {
-                tracing::warn!("Unsupported filter {filter:?}");
-                // Apply a default color matrix - it's essentially a blit
+            Filter::GlowFilter(filter) => self.apply_glow(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &Glow::from(&filter),
+            ),
+            Filter::DropShadowFilter(filter) => self.apply_glow(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &Glow::from(&filter),
+            ),
+            Filter::BevelFilter(filter) => self.apply_glow(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &Glow::from(&filter),
+            ),
+            Filter::GradientGlowFilter(filter) => self.apply_glow(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &Glow::gradient_glow(&filter),
+            ),
+            Filter::GradientBevelFilter(filter) => self.apply_glow(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &Glow::gradient_bevel(&filter),
+            ),
+            Filter::ConvolutionFilter(filter) => self.apply_convolution(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+            ),
+            Filter::DisplacementMapFilter(filter) => self.apply_displacement_map(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+            ),
+            Filter::ShaderFilter(_) => {
+                // The backend refuses shader filters before they get here.
+                tracing::warn!("Shader filters can't be applied on the GPU yet");
                 self.apply_color_matrix(
                     descriptors,
                     texture_pool,
@@ -414,7 +490,7 @@ impl Surface {
                     source_texture,
                     source_point,
                     source_size,
-                    &Default::default(),
+                    &ColorMatrixFilter::default(),
                 )
             }
         };
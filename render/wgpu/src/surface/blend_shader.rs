@@ -0,0 +1,169 @@
+use crate::surface::target::{CommandTarget, PoolOrArcTexture};
+use crate::utils::{capture_image, BufferDimensions};
+use crate::{ColorAdjustments, Descriptors, Transforms, UniformBuffer};
+use ruffle_render::commands::BlendShader;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+/// Runs a Pixel Bender blend shader over `content` and everything that was drawn below it.
+///
+/// Blend shaders only run on the CPU, so this has to submit everything recorded so far
+/// and read both textures back. The returned texture replaces the contents of `parent`.
+#[allow(clippy::too_many_arguments)]
+pub fn run_blend_shader(
+    descriptors: &Descriptors,
+    uniform_buffers: &mut UniformBuffer<Transforms>,
+    color_buffers: &mut UniformBuffer<ColorAdjustments>,
+    uniform_encoder: &mut wgpu::CommandEncoder,
+    draw_encoder: &mut wgpu::CommandEncoder,
+    parent: &CommandTarget,
+    content: &PoolOrArcTexture,
+    shader: &BlendShader,
+) -> PoolOrArcTexture {
+    let size = wgpu::Extent3d {
+        width: parent.width(),
+        height: parent.height(),
+        depth_or_array_layers: 1,
+    };
+    let dimensions = BufferDimensions::new(size.width as usize, size.height as usize);
+
+    parent.ensure_cleared(draw_encoder);
+    let background_buffer = copy_to_buffer(
+        descriptors,
+        draw_encoder,
+        parent.color_texture(),
+        &dimensions,
+        size,
+    );
+    let content_buffer = copy_to_buffer(
+        descriptors,
+        draw_encoder,
+        content.texture(),
+        &dimensions,
+        size,
+    );
+
+    let uniform_label = create_debug_label!("Uniform upload command encoder");
+    let draw_label = create_debug_label!("Draw command encoder");
+    let new_encoder = |label: Option<&str>| {
+        descriptors
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label })
+    };
+    uniform_buffers.flush();
+    color_buffers.flush();
+    let index = descriptors.queue.submit([
+        std::mem::replace(uniform_encoder, new_encoder(uniform_label.as_deref())).finish(),
+        std::mem::replace(draw_encoder, new_encoder(draw_label.as_deref())).finish(),
+    ]);
+    uniform_buffers.recall();
+    color_buffers.recall();
+
+    let is_bgra = parent.format() == wgpu::TextureFormat::Bgra8Unorm;
+    let mut pixels = read_pixels(
+        descriptors,
+        &background_buffer,
+        &dimensions,
+        index.clone(),
+        is_bgra,
+    );
+    let content = read_pixels(descriptors, &content_buffer, &dimensions, index, false);
+
+    if let Some(output) = shader.run(dimensions.width, &pixels, &content) {
+        for (row, y) in output.pixels.chunks_exact(output.width).zip(output.y..) {
+            let start = y * dimensions.width + output.x;
+            pixels[start..start + output.width].copy_from_slice(row);
+        }
+    }
+
+    let label = create_debug_label!("Blend shader output");
+    let texture = descriptors.device.create_texture(&wgpu::TextureDescriptor {
+        label: label.as_deref(),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+    });
+    descriptors.queue.write_texture(
+        wgpu::ImageCopyTexture {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        bytemuck::cast_slice(&pixels),
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: NonZeroU32::new(4 * size.width),
+            rows_per_image: None,
+        },
+        size,
+    );
+    let view = texture.create_view(&Default::default());
+    PoolOrArcTexture::Manual((Arc::new(texture), view))
+}
+
+fn copy_to_buffer(
+    descriptors: &Descriptors,
+    encoder: &mut wgpu::CommandEncoder,
+    texture: &wgpu::Texture,
+    dimensions: &BufferDimensions,
+    size: wgpu::Extent3d,
+) -> wgpu::Buffer {
+    let label = create_debug_label!("Blend shader readback buffer");
+    let buffer = descriptors.device.create_buffer(&wgpu::BufferDescriptor {
+        label: label.as_deref(),
+        size: dimensions.padded_bytes_per_row.get() as u64 * dimensions.height as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::ImageCopyTexture {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(dimensions.padded_bytes_per_row),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+    buffer
+}
+
+fn read_pixels(
+    descriptors: &Descriptors,
+    buffer: &wgpu::Buffer,
+    dimensions: &BufferDimensions,
+    index: wgpu::SubmissionIndex,
+    is_bgra: bool,
+) -> Vec<[u8; 4]> {
+    capture_image(
+        &descriptors.device,
+        buffer,
+        dimensions,
+        Some(index),
+        |data, padded_bytes_per_row| {
+            data.chunks(padded_bytes_per_row as usize)
+                .take(dimensions.height)
+                .flat_map(|row| row[..dimensions.unpadded_bytes_per_row].chunks_exact(4))
+                .map(|p| {
+                    if is_bgra {
+                        [p[2], p[1], p[0], p[3]]
+                    } else {
+                        [p[0], p[1], p[2], p[3]]
+                    }
+                })
+                .collect()
+        },
+    )
+}
@@ -12,8 +12,9 @@ use crate::{
 };
 use ruffle_render::backend::ShapeHandle;
 use ruffle_render::bitmap::BitmapHandle;
-use ruffle_render::commands::Command;
+use ruffle_render::commands::{BlendShader, Command};
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
 use swf::{BlendMode, Color, ColorTransform, Fixed8};
@@ -83,6 +84,12 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 blend_mode,
                 render_stage3d,
             } => self.render_bitmap(bitmap, transform, *smoothing, *blend_mode, *render_stage3d),
+            DrawCommand::RenderBitmapProjected {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            } => self.render_bitmap_projected(bitmap, matrix, color_transform, *smoothing),
             DrawCommand::RenderTexture {
                 _texture,
                 binds,
@@ -188,7 +195,14 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 1.0,
             ],
         ];
+        self.apply_world_matrix(world_matrix, color_adjustments);
+    }
 
+    pub fn apply_world_matrix(
+        &mut self,
+        world_matrix: [[f32; 4]; 4],
+        color_adjustments: &ColorTransform,
+    ) {
         if self.descriptors.limits.max_push_constant_size > 0 {
             self.render_pass.set_push_constants(
                 wgpu::ShaderStages::VERTEX_FRAGMENT,
@@ -266,6 +280,46 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
         }
     }
 
+    pub fn render_bitmap_projected(
+        &mut self,
+        bitmap: &'frame BitmapHandle,
+        matrix: &Matrix3D,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        if cfg!(feature = "render_debug_labels") {
+            self.render_pass
+                .push_debug_group(&format!("render_bitmap_projected {:?}", bitmap.0));
+        }
+        let texture = as_texture(bitmap);
+
+        let descriptors = self.descriptors;
+        let bind = texture.bind_group(
+            smoothing,
+            &descriptors.device,
+            &descriptors.bind_layouts.bitmap,
+            &descriptors.quad,
+            bitmap.clone(),
+            &descriptors.bitmap_samplers,
+        );
+        self.prep_bitmap(&bind.bind_group, TrivialBlend::Normal, false);
+        // The vertex shader keeps the `w` component, so the GPU takes care of the
+        // perspective divide and of perspective-correct texture sampling.
+        let world_matrix = (*matrix
+            * Matrix3D::scale(texture.width as f64, texture.height as f64, 1.0))
+        .to_world_matrix();
+        self.apply_world_matrix(world_matrix, color_transform);
+
+        self.draw(
+            self.descriptors.quad.vertices_pos.slice(..),
+            self.descriptors.quad.indices.slice(..),
+            6,
+        );
+        if cfg!(feature = "render_debug_labels") {
+            self.render_pass.pop_debug_group();
+        }
+    }
+
     pub fn render_texture(
         &mut self,
         transform: &Transform,
@@ -406,6 +460,9 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
 pub enum Chunk {
     Draw(Vec<DrawCommand>, bool),
     Blend(PoolOrArcTexture, ComplexBlend, bool),
+    /// Content blended by a Pixel Bender shader, which has to run on the CPU
+    /// once everything below it has been drawn.
+    ShaderBlend(PoolOrArcTexture, BlendShader, bool),
 }
 
 #[derive(Debug)]
@@ -417,6 +474,12 @@ pub enum DrawCommand {
         blend_mode: TrivialBlend,
         render_stage3d: bool,
     },
+    RenderBitmapProjected {
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    },
     RenderTexture {
         _texture: PoolOrArcTexture,
         binds: wgpu::BindGroup,
@@ -563,6 +626,17 @@ pub fn chunk_blends<'a>(
                 blend_mode: TrivialBlend::Normal,
                 render_stage3d: false,
             }),
+            Command::RenderBitmapProjected {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            } => current.push(DrawCommand::RenderBitmapProjected {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            }),
             Command::RenderStage3D { bitmap, transform } => {
                 current.push(DrawCommand::RenderBitmap {
                     bitmap,
@@ -596,6 +670,39 @@ pub fn chunk_blends<'a>(
                 num_masks -= 1;
                 current.push(DrawCommand::PopMask);
             }
+            Command::BlendShader(commands, shader) => {
+                // Pixel Bender shaders aren't translated to WGSL yet.
+                let mut surface = Surface::new(
+                    descriptors,
+                    quality,
+                    width,
+                    height,
+                    wgpu::TextureFormat::Rgba8Unorm,
+                );
+                let target = surface.draw_commands(
+                    RenderTargetMode::FreshBuffer(wgpu::Color::TRANSPARENT),
+                    descriptors,
+                    meshes,
+                    commands,
+                    uniform_buffers,
+                    color_buffers,
+                    uniform_encoder,
+                    draw_encoder,
+                    nearest_layer,
+                    texture_pool,
+                );
+                target.ensure_cleared(draw_encoder);
+
+                if !current.is_empty() {
+                    result.push(Chunk::Draw(std::mem::take(&mut current), needs_depth));
+                }
+                result.push(Chunk::ShaderBlend(
+                    target.take_color_texture(),
+                    shader,
+                    num_masks > 0,
+                ));
+                needs_depth = num_masks > 0;
+            }
         }
     }
 
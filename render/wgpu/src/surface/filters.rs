@@ -0,0 +1,690 @@
+use crate::backend::RenderTargetMode;
+use crate::buffer_pool::TexturePool;
+use crate::surface::target::CommandTarget;
+use crate::surface::Surface;
+use crate::{
+    as_texture, ConvolutionFilterUniforms, Descriptors, DisplacementMapFilterUniforms,
+    GlowFilterUniforms, PushConstants, Texture, Transforms, DEFAULT_COLOR_ADJUSTMENTS,
+    MAX_CONVOLUTION_MATRIX_SIZE,
+};
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode};
+use swf::{
+    BevelFilter, Color, ConvolutionFilter, DropShadowFilter, GlowFilter, GradientFilter,
+    GradientRecord,
+};
+use wgpu::util::DeviceExt;
+
+/// How many colors are in the gradient that colors a glow.
+const GRADIENT_SIZE: usize = 256;
+
+/// Everything needed to draw a glow, drop shadow or bevel, or one of their
+/// gradient variants. They all blur the alpha of the source, and then color
+/// the result with a gradient.
+pub struct Glow {
+    /// The straight alpha colors of the effect. Glows go from transparent at
+    /// the start to fully colored at the end, whilst bevels go from the
+    /// highlight at the start to the shadow at the end.
+    colors: [u8; GRADIENT_SIZE * 4],
+    blur_x: f32,
+    blur_y: f32,
+    passes: u8,
+    /// How far the effect is moved from the source, in pixels.
+    offset: (f32, f32),
+    strength: f32,
+    bevel: bool,
+    inner: bool,
+    knockout: bool,
+    composite_source: bool,
+    on_top: bool,
+}
+
+impl From<&GlowFilter> for Glow {
+    fn from(filter: &GlowFilter) -> Self {
+        let transparent = Color {
+            a: 0,
+            ..filter.color
+        };
+        Self {
+            colors: gradient_colors(&[
+                GradientRecord {
+                    ratio: 0,
+                    color: transparent,
+                },
+                GradientRecord {
+                    ratio: 255,
+                    color: filter.color.clone(),
+                },
+            ]),
+            blur_x: filter.blur_x.to_f32(),
+            blur_y: filter.blur_y.to_f32(),
+            passes: filter.num_passes(),
+            offset: (0.0, 0.0),
+            strength: filter.strength.to_f32(),
+            bevel: false,
+            inner: filter.is_inner(),
+            knockout: filter.is_knockout(),
+            composite_source: filter
+                .flags
+                .contains(swf::GlowFilterFlags::COMPOSITE_SOURCE),
+            on_top: false,
+        }
+    }
+}
+
+impl From<&DropShadowFilter> for Glow {
+    fn from(filter: &DropShadowFilter) -> Self {
+        let transparent = Color {
+            a: 0,
+            ..filter.color
+        };
+        Self {
+            colors: gradient_colors(&[
+                GradientRecord {
+                    ratio: 0,
+                    color: transparent,
+                },
+                GradientRecord {
+                    ratio: 255,
+                    color: filter.color.clone(),
+                },
+            ]),
+            blur_x: filter.blur_x.to_f32(),
+            blur_y: filter.blur_y.to_f32(),
+            passes: filter.num_passes(),
+            offset: offset(filter.angle.to_f32(), filter.distance.to_f32()),
+            strength: filter.strength.to_f32(),
+            bevel: false,
+            inner: filter.is_inner(),
+            knockout: filter.is_knockout(),
+            composite_source: !filter.hide_object(),
+            on_top: false,
+        }
+    }
+}
+
+impl From<&BevelFilter> for Glow {
+    fn from(filter: &BevelFilter) -> Self {
+        let highlight = &filter.highlight_color;
+        let shadow = &filter.shadow_color;
+        // The middle of the gradient is used where there's neither highlight nor shadow.
+        Self {
+            colors: gradient_colors(&[
+                GradientRecord {
+                    ratio: 0,
+                    color: highlight.clone(),
+                },
+                GradientRecord {
+                    ratio: 127,
+                    color: Color { a: 0, ..*highlight },
+                },
+                GradientRecord {
+                    ratio: 128,
+                    color: Color { a: 0, ..*shadow },
+                },
+                GradientRecord {
+                    ratio: 255,
+                    color: shadow.clone(),
+                },
+            ]),
+            blur_x: filter.blur_x.to_f32(),
+            blur_y: filter.blur_y.to_f32(),
+            passes: filter.num_passes(),
+            offset: offset(filter.angle.to_f32(), filter.distance.to_f32()),
+            strength: filter.strength.to_f32(),
+            bevel: true,
+            inner: filter.is_inner(),
+            knockout: filter.is_knockout(),
+            composite_source: filter
+                .flags
+                .contains(swf::BevelFilterFlags::COMPOSITE_SOURCE),
+            on_top: filter.is_on_top(),
+        }
+    }
+}
+
+impl Glow {
+    pub fn gradient_glow(filter: &GradientFilter) -> Self {
+        Self {
+            bevel: false,
+            ..Self::gradient_bevel(filter)
+        }
+    }
+
+    pub fn gradient_bevel(filter: &GradientFilter) -> Self {
+        Self {
+            colors: gradient_colors(&filter.colors),
+            blur_x: filter.blur_x.to_f32(),
+            blur_y: filter.blur_y.to_f32(),
+            passes: filter.num_passes(),
+            offset: offset(filter.angle.to_f32(), filter.distance.to_f32()),
+            strength: filter.strength.to_f32(),
+            bevel: true,
+            inner: filter.is_inner(),
+            knockout: filter.is_knockout(),
+            composite_source: filter
+                .flags
+                .contains(swf::GradientFilterFlags::COMPOSITE_SOURCE),
+            on_top: filter.is_on_top(),
+        }
+    }
+}
+
+/// Converts the angle (in radians) and distance of a filter into an offset in pixels.
+fn offset(angle: f32, distance: f32) -> (f32, f32) {
+    (angle.cos() * distance, angle.sin() * distance)
+}
+
+/// Interpolates the straight alpha colors of a gradient, as RGBA bytes.
+fn gradient_colors(records: &[GradientRecord]) -> [u8; GRADIENT_SIZE * 4] {
+    let mut colors = [0; GRADIENT_SIZE * 4];
+    let (Some(first), Some(last)) = (records.first(), records.last()) else {
+        return colors;
+    };
+    for (t, color) in colors.chunks_exact_mut(4).enumerate() {
+        let next = records
+            .iter()
+            .position(|record| usize::from(record.ratio) >= t)
+            .unwrap_or(records.len() - 1);
+        let (from, to) = if usize::from(first.ratio) >= t {
+            (first, first)
+        } else if usize::from(last.ratio) < t {
+            (last, last)
+        } else {
+            (&records[next.saturating_sub(1)], &records[next])
+        };
+        let a = if to.ratio == from.ratio {
+            0.0
+        } else {
+            (t as f32 - f32::from(from.ratio)) / (f32::from(to.ratio) - f32::from(from.ratio))
+        };
+        let lerp =
+            |from: u8, to: u8| (f32::from(from) + (f32::from(to) - f32::from(from)) * a) as u8;
+        color.copy_from_slice(&[
+            lerp(from.color.r, to.color.r),
+            lerp(from.color.g, to.color.g),
+            lerp(from.color.b, to.color.b),
+            lerp(from.color.a, to.color.a),
+        ]);
+    }
+    colors
+}
+
+impl Surface {
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_glow(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        glow: &Glow,
+    ) -> CommandTarget {
+        let source = self.copy_source(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+        );
+        let blurred = self.blur_passes(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            &source,
+            ((glow.blur_x - 1.0).max(0.0), (glow.blur_y - 1.0).max(0.0)),
+            glow.passes,
+        );
+        let blurred_view = blurred.as_ref().unwrap_or(&source).color_view();
+
+        let gradient = descriptors.device.create_texture_with_data(
+            &descriptors.queue,
+            &wgpu::TextureDescriptor {
+                label: create_debug_label!("Glow filter gradient").as_deref(),
+                size: wgpu::Extent3d {
+                    width: GRADIENT_SIZE as u32,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            &glow.colors,
+        );
+        let gradient_view = gradient.create_view(&Default::default());
+        let buffer = descriptors
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: create_debug_label!("Filter arguments").as_deref(),
+                contents: bytemuck::cast_slice(&[GlowFilterUniforms {
+                    offset: [
+                        glow.offset.0 / source_size.0 as f32,
+                        glow.offset.1 / source_size.1 as f32,
+                    ],
+                    strength: glow.strength,
+                    bevel: glow.bevel as i32,
+                    inner: glow.inner as i32,
+                    knockout: glow.knockout as i32,
+                    composite_source: glow.composite_source as i32,
+                    on_top: glow.on_top as i32,
+                }]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout: &descriptors.bind_layouts.glow_filter,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(blurred_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&gradient_view),
+                    },
+                ],
+            });
+
+        let target = self.filter_target(descriptors, texture_pool, draw_encoder, source_size);
+        let bitmap_group = filter_bitmap_group(
+            descriptors,
+            source.color_view(),
+            descriptors.quad.texture_transforms.as_entire_binding(),
+            descriptors.bitmap_samplers.get_sampler(false, true),
+        );
+        run_filter_pass(
+            descriptors,
+            draw_encoder,
+            create_debug_label!("Glow filter").as_deref(),
+            &target,
+            &self.pipelines.glow_filter,
+            &bitmap_group,
+            &filter_group,
+        );
+        target
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_convolution(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &ConvolutionFilter,
+    ) -> CommandTarget {
+        if filter.matrix.len() > MAX_CONVOLUTION_MATRIX_SIZE {
+            tracing::warn!(
+                "Convolution matrix of {} values is too large",
+                filter.matrix.len()
+            );
+        }
+        let mut matrix = [0.0; MAX_CONVOLUTION_MATRIX_SIZE];
+        for (value, from) in matrix.iter_mut().zip(&filter.matrix) {
+            *value = from.to_f32();
+        }
+        // Only draw the rows that fit.
+        let max_rows = MAX_CONVOLUTION_MATRIX_SIZE / usize::from(filter.num_matrix_cols.max(1));
+        let divisor = filter.divisor.to_f32();
+        let color = &filter.default_color;
+        let uniforms = ConvolutionFilterUniforms {
+            default_color: [
+                f32::from(color.r) / 255.0,
+                f32::from(color.g) / 255.0,
+                f32::from(color.b) / 255.0,
+                f32::from(color.a) / 255.0,
+            ],
+            size: [source_size.0 as f32, source_size.1 as f32],
+            columns: i32::from(filter.num_matrix_cols),
+            rows: usize::from(filter.num_matrix_rows).min(max_rows) as i32,
+            divisor: if divisor == 0.0 { 1.0 } else { divisor },
+            bias: filter.bias.to_f32(),
+            clamped: filter.is_clamped() as i32,
+            preserve_alpha: filter.is_preserve_alpha() as i32,
+            matrix: bytemuck::cast(matrix),
+        };
+
+        let source = self.copy_source(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+        );
+        let buffer = descriptors
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: create_debug_label!("Filter arguments").as_deref(),
+                contents: bytemuck::cast_slice(&[uniforms]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout: &descriptors.bind_layouts.convolution_filter,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            });
+
+        let target = self.filter_target(descriptors, texture_pool, draw_encoder, source_size);
+        let bitmap_group = filter_bitmap_group(
+            descriptors,
+            source.color_view(),
+            descriptors.quad.texture_transforms.as_entire_binding(),
+            descriptors.bitmap_samplers.get_sampler(false, false),
+        );
+        run_filter_pass(
+            descriptors,
+            draw_encoder,
+            create_debug_label!("Convolution filter").as_deref(),
+            &target,
+            &self.pipelines.convolution_filter,
+            &bitmap_group,
+            &filter_group,
+        );
+        target
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_displacement_map(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &DisplacementMapFilter,
+    ) -> CommandTarget {
+        let source = self.copy_source(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+        );
+        let Some(map) = &filter.map_bitmap else {
+            // Without a map, nothing gets displaced.
+            return source;
+        };
+        let map = as_texture(map);
+        let map_view = map.texture.create_view(&Default::default());
+
+        let color = &filter.color;
+        let alpha = f32::from(color.a) / 255.0;
+        let buffer = descriptors
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: create_debug_label!("Filter arguments").as_deref(),
+                contents: bytemuck::cast_slice(&[DisplacementMapFilterUniforms {
+                    color: [
+                        f32::from(color.r) / 255.0 * alpha,
+                        f32::from(color.g) / 255.0 * alpha,
+                        f32::from(color.b) / 255.0 * alpha,
+                        alpha,
+                    ],
+                    component_x: i32::from(filter.component_x),
+                    component_y: i32::from(filter.component_y),
+                    mode: match filter.mode {
+                        DisplacementMapFilterMode::Clamp => 0,
+                        DisplacementMapFilterMode::Color => 1,
+                        DisplacementMapFilterMode::Ignore => 2,
+                        DisplacementMapFilterMode::Wrap => 3,
+                    },
+                    _padding: 0,
+                    scale: [filter.scale_x, filter.scale_y],
+                    size: [source_size.0 as f32, source_size.1 as f32],
+                    map_point: [filter.map_point.0 as f32, filter.map_point.1 as f32],
+                    map_size: [map.width as f32, map.height as f32],
+                }]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout: &descriptors.bind_layouts.displacement_map_filter,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&map_view),
+                    },
+                ],
+            });
+
+        let target = self.filter_target(descriptors, texture_pool, draw_encoder, source_size);
+        let bitmap_group = filter_bitmap_group(
+            descriptors,
+            source.color_view(),
+            descriptors.quad.texture_transforms.as_entire_binding(),
+            descriptors.bitmap_samplers.get_sampler(false, false),
+        );
+        run_filter_pass(
+            descriptors,
+            draw_encoder,
+            create_debug_label!("Displacement map filter").as_deref(),
+            &target,
+            &self.pipelines.displacement_map_filter,
+            &bitmap_group,
+            &filter_group,
+        );
+        target
+    }
+
+    /// Copies the region of the source that's being filtered into its own texture.
+    fn copy_source(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+    ) -> CommandTarget {
+        // A default color matrix is essentially a blit
+        self.apply_color_matrix(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &Default::default(),
+        )
+    }
+
+    fn filter_target(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        size: (u32, u32),
+    ) -> CommandTarget {
+        CommandTarget::new(
+            descriptors,
+            texture_pool,
+            wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            self.format,
+            self.sample_count,
+            RenderTargetMode::FreshBuffer(wgpu::Color::TRANSPARENT),
+            draw_encoder,
+        )
+    }
+
+    /// Box blurs `source` horizontally and then vertically, `passes` times over.
+    /// Repeated passes approach a gaussian blur, which is how Flash implements quality.
+    ///
+    /// Returns `None` if nothing needed blurring.
+    fn blur_passes(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source: &CommandTarget,
+        blur: (f32, f32),
+        passes: u8,
+    ) -> Option<CommandTarget> {
+        if passes == 0 || blur == (0.0, 0.0) {
+            return None;
+        }
+        let size = (source.width(), source.height());
+        let targets = [
+            self.filter_target(descriptors, texture_pool, draw_encoder, size),
+            self.filter_target(descriptors, texture_pool, draw_encoder, size),
+        ];
+        for i in 0..usize::from(passes) * 2 {
+            let current = &targets[i % 2];
+            let previous = if i == 0 {
+                source
+            } else {
+                &targets[(i - 1) % 2]
+            };
+            let (blur_x, blur_y) = if i % 2 == 0 {
+                (blur.0, 0.0)
+            } else {
+                (0.0, blur.1)
+            };
+            let bitmap_group = filter_bitmap_group(
+                descriptors,
+                previous.color_view(),
+                descriptors.quad.texture_transforms.as_entire_binding(),
+                descriptors.bitmap_samplers.get_sampler(false, true),
+            );
+            let buffer = descriptors
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: create_debug_label!("Filter arguments").as_deref(),
+                    contents: bytemuck::cast_slice(&[blur_x, blur_y, size.0 as f32, size.1 as f32]),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+            let filter_group = descriptors
+                .device
+                .create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: create_debug_label!("Filter group").as_deref(),
+                    layout: &descriptors.bind_layouts.blur_filter,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    }],
+                });
+            run_filter_pass(
+                descriptors,
+                draw_encoder,
+                create_debug_label!("Blur filter").as_deref(),
+                current,
+                &self.pipelines.blur_filter,
+                &bitmap_group,
+                &filter_group,
+            );
+        }
+
+        // There's always an even number of steps, so the last one drew to the second target.
+        targets.into_iter().last()
+    }
+}
+
+fn filter_bitmap_group(
+    descriptors: &Descriptors,
+    view: &wgpu::TextureView,
+    texture_transform: wgpu::BindingResource,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    descriptors
+        .device
+        .create_bind_group(&wgpu::BindGroupDescriptor {
+            label: create_debug_label!("Bitmap copy group").as_deref(),
+            layout: &descriptors.bind_layouts.bitmap,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: texture_transform,
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+}
+
+/// Draws a filter over the whole of `target`.
+fn run_filter_pass(
+    descriptors: &Descriptors,
+    draw_encoder: &mut wgpu::CommandEncoder,
+    label: Option<&str>,
+    target: &CommandTarget,
+    pipeline: &wgpu::RenderPipeline,
+    bitmap_group: &wgpu::BindGroup,
+    filter_group: &wgpu::BindGroup,
+) {
+    let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label,
+        color_attachments: &[target.color_attachments()],
+        depth_stencil_attachment: None,
+    });
+    render_pass.set_pipeline(pipeline);
+
+    render_pass.set_bind_group(0, target.globals().bind_group(), &[]);
+    if descriptors.limits.max_push_constant_size > 0 {
+        render_pass.set_push_constants(
+            wgpu::ShaderStages::VERTEX_FRAGMENT,
+            0,
+            bytemuck::cast_slice(&[PushConstants {
+                transforms: Transforms {
+                    world_matrix: [
+                        [target.width() as f32, 0.0, 0.0, 0.0],
+                        [0.0, target.height() as f32, 0.0, 0.0],
+                        [0.0, 0.0, 1.0, 0.0],
+                        [0.0, 0.0, 0.0, 1.0],
+                    ],
+                },
+                colors: DEFAULT_COLOR_ADJUSTMENTS,
+            }]),
+        );
+        render_pass.set_bind_group(1, bitmap_group, &[]);
+        render_pass.set_bind_group(2, filter_group, &[]);
+    } else {
+        render_pass.set_bind_group(1, target.whole_frame_bind_group(descriptors), &[0]);
+        render_pass.set_bind_group(2, &descriptors.default_color_bind_group, &[0]);
+        render_pass.set_bind_group(3, bitmap_group, &[]);
+        render_pass.set_bind_group(4, filter_group, &[]);
+    }
+
+    render_pass.set_vertex_buffer(0, descriptors.quad.vertices_pos.slice(..));
+    render_pass.set_index_buffer(
+        descriptors.quad.indices.slice(..),
+        wgpu::IndexFormat::Uint32,
+    );
+    render_pass.draw_indexed(0..6, 0, 0..1);
+}
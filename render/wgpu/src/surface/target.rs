@@ -73,6 +73,13 @@ pub enum PoolOrArcTexture {
 }
 
 impl PoolOrArcTexture {
+    pub fn texture(&self) -> &wgpu::Texture {
+        match self {
+            PoolOrArcTexture::Pool(ref texture) => &texture.0,
+            PoolOrArcTexture::Manual(ref texture) => &texture.0,
+        }
+    }
+
     pub fn view(&self) -> &wgpu::TextureView {
         match self {
             PoolOrArcTexture::Pool(ref texture) => &texture.1,
@@ -316,6 +323,10 @@ impl CommandTarget {
         self.size.height
     }
 
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
     pub fn ensure_cleared(&self, encoder: &mut wgpu::CommandEncoder) {
         if self.color_needs_clear.get().is_some() {
             return;
@@ -158,6 +158,19 @@ impl<'a, T: Pod> UniformBuffer<'a, T> {
         }
     }
 
+    /// Closes the staging buffers written so far, so that the uniforms can be
+    /// submitted before the end of a frame. `recall` must be called after that.
+    pub fn flush(&mut self) {
+        self.buffers
+            .with_staging_belt(|belt| belt.borrow_mut().finish());
+    }
+
+    /// Allows the staging buffers closed by `flush` to be written to again.
+    pub fn recall(&mut self) {
+        self.buffers
+            .with_staging_belt(|belt| belt.borrow_mut().recall());
+    }
+
     /// Should be called at the end of a frame.
     pub fn finish(self) {
         self.buffers
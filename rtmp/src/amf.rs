@@ -0,0 +1,30 @@
+use crate::error::{Error, Result};
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::Value;
+
+/// Decode a single AMF0 value from the start of `data`, returning the rest of
+/// the data along with it.
+pub(crate) fn read_value(data: &[u8]) -> Result<(&[u8], Value)> {
+    let (rest, value) = AMF0Decoder::default()
+        .parse_single_element(data)
+        .map_err(|_| Error::invalid_data("Invalid AMF0 value"))?;
+    let value: &Value = &value;
+    Ok((rest, value.clone()))
+}
+
+/// Decode a sequence of AMF0 values that fills all of `data`.
+pub(crate) fn read_values(mut data: &[u8]) -> Result<Vec<Value>> {
+    let mut values = Vec::new();
+    while !data.is_empty() {
+        let (rest, value) = read_value(data)?;
+        values.push(value);
+        data = rest;
+    }
+    Ok(values)
+}
+
+/// Serialize a single AMF0 value onto the end of `output`.
+pub(crate) fn write_value(output: &mut Vec<u8>, value: &Value) -> Result<()> {
+    flv::write_amf0_value(output, value)
+        .map_err(|_| Error::invalid_data("Unserializable AMF0 value"))
+}
@@ -0,0 +1,380 @@
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// The chunk size that both peers use until told otherwise.
+pub(crate) const DEFAULT_CHUNK_SIZE: usize = 128;
+
+/// The largest timestamp that fits in a chunk header; larger timestamps are
+/// written as an extended timestamp after the header.
+const EXTENDED_TIMESTAMP: u32 = 0xFF_FFFF;
+
+/// A message that has been reassembled from chunks, but not yet parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RawMessage {
+    pub timestamp: u32,
+    pub type_id: u8,
+    pub stream_id: u32,
+    pub payload: Vec<u8>,
+}
+
+/// The header values of the last chunk on a chunk stream, which later chunks
+/// may omit.
+#[derive(Clone, Debug, Default)]
+struct ChunkStream {
+    timestamp: u32,
+    timestamp_delta: u32,
+    length: usize,
+    type_id: u8,
+    stream_id: u32,
+    has_extended_timestamp: bool,
+
+    /// The part of the current message that has been received so far.
+    payload: Vec<u8>,
+}
+
+/// A cursor over a chunk that may not have been received in full yet.
+struct Cursor<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn read(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.position..self.position + len)?;
+        self.position += len;
+        Some(bytes)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        self.read(1).map(|bytes| bytes[0])
+    }
+
+    fn read_u24(&mut self) -> Option<u32> {
+        self.read(3)
+            .map(|bytes| u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        self.read(4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_u32_le(&mut self) -> Option<u32> {
+        self.read(4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+/// Reassembles messages from the chunks received from a peer.
+#[derive(Debug)]
+pub(crate) struct ChunkReader {
+    chunk_size: usize,
+    streams: HashMap<u32, ChunkStream>,
+}
+
+impl ChunkReader {
+    pub fn new() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            streams: HashMap::new(),
+        }
+    }
+
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size.max(1);
+    }
+
+    /// Discard the partially received message on a chunk stream.
+    pub fn abort(&mut self, chunk_stream_id: u32) {
+        if let Some(stream) = self.streams.get_mut(&chunk_stream_id) {
+            stream.payload.clear();
+        }
+    }
+
+    /// Read a single chunk from the start of `data`.
+    ///
+    /// Returns `Ok(None)` if `data` does not contain a whole chunk, in which
+    /// case nothing is consumed. Otherwise, returns the number of bytes that
+    /// were consumed, and the message that the chunk completed, if any.
+    pub fn read_chunk(&mut self, data: &[u8]) -> Result<Option<(usize, Option<RawMessage>)>> {
+        let mut cursor = Cursor { data, position: 0 };
+        let Some(first) = cursor.read_u8() else {
+            return Ok(None);
+        };
+        let format = first >> 6;
+        let chunk_stream_id = match first & 0x3F {
+            0 => match cursor.read_u8() {
+                Some(id) => 64 + u32::from(id),
+                None => return Ok(None),
+            },
+            1 => match cursor.read(2) {
+                Some(id) => 64 + u32::from(u16::from_le_bytes([id[0], id[1]])),
+                None => return Ok(None),
+            },
+            id => u32::from(id),
+        };
+
+        // Work on a copy of the chunk stream, so that nothing changes if the
+        // chunk turns out to be incomplete.
+        let mut stream = match (format, self.streams.get(&chunk_stream_id)) {
+            (0, stream) => stream.cloned().unwrap_or_default(),
+            (_, Some(stream)) => stream.clone(),
+            (_, None) => {
+                return Err(Error::invalid_data(
+                    "Compressed chunk header without a previous chunk",
+                ))
+            }
+        };
+
+        let timestamp_field = match format {
+            0 => {
+                let Some((timestamp, length, type_id, stream_id)) = (|| {
+                    Some((
+                        cursor.read_u24()?,
+                        cursor.read_u24()?,
+                        cursor.read_u8()?,
+                        cursor.read_u32_le()?,
+                    ))
+                })() else {
+                    return Ok(None);
+                };
+                stream.length = length as usize;
+                stream.type_id = type_id;
+                stream.stream_id = stream_id;
+                Some(timestamp)
+            }
+            1 => {
+                let Some((delta, length, type_id)) =
+                    (|| Some((cursor.read_u24()?, cursor.read_u24()?, cursor.read_u8()?)))()
+                else {
+                    return Ok(None);
+                };
+                stream.length = length as usize;
+                stream.type_id = type_id;
+                Some(delta)
+            }
+            2 => match cursor.read_u24() {
+                Some(delta) => Some(delta),
+                None => return Ok(None),
+            },
+            _ => None,
+        };
+
+        let extended_timestamp = match timestamp_field {
+            Some(field) => {
+                stream.has_extended_timestamp = field == EXTENDED_TIMESTAMP;
+                if stream.has_extended_timestamp {
+                    match cursor.read_u32() {
+                        Some(timestamp) => timestamp,
+                        None => return Ok(None),
+                    }
+                } else {
+                    field
+                }
+            }
+            // Continuation chunks repeat the extended timestamp of the chunk
+            // that they continue.
+            None if stream.has_extended_timestamp => match cursor.read_u32() {
+                Some(timestamp) => timestamp,
+                None => return Ok(None),
+            },
+            None => stream.timestamp_delta,
+        };
+
+        match (format, timestamp_field) {
+            (0, _) => {
+                stream.timestamp = extended_timestamp;
+                stream.timestamp_delta = 0;
+            }
+            (_, Some(_)) => {
+                stream.timestamp = stream.timestamp.wrapping_add(extended_timestamp);
+                stream.timestamp_delta = extended_timestamp;
+            }
+            // A type 3 chunk that starts a new message has the same delta as
+            // the message before it.
+            _ if stream.payload.is_empty() => {
+                stream.timestamp = stream.timestamp.wrapping_add(stream.timestamp_delta);
+            }
+            _ => {}
+        }
+
+        let remaining = stream.length.saturating_sub(stream.payload.len());
+        let Some(payload) = cursor.read(remaining.min(self.chunk_size)) else {
+            return Ok(None);
+        };
+        stream.payload.extend_from_slice(payload);
+
+        let message = if stream.payload.len() >= stream.length {
+            Some(RawMessage {
+                timestamp: stream.timestamp,
+                type_id: stream.type_id,
+                stream_id: stream.stream_id,
+                payload: std::mem::take(&mut stream.payload),
+            })
+        } else {
+            None
+        };
+        self.streams.insert(chunk_stream_id, stream);
+
+        Ok(Some((cursor.position, message)))
+    }
+}
+
+/// Splits outgoing messages into chunks.
+#[derive(Debug)]
+pub(crate) struct ChunkWriter {
+    chunk_size: usize,
+}
+
+impl ChunkWriter {
+    pub fn new() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size.max(1);
+    }
+
+    /// Write a message onto the end of `output`.
+    ///
+    /// The first chunk always has a full header, and the rest are
+    /// continuation chunks with no header.
+    pub fn write(
+        &self,
+        output: &mut Vec<u8>,
+        chunk_stream_id: u32,
+        message: &RawMessage,
+    ) -> Result<()> {
+        let length = u32::try_from(message.payload.len())
+            .ok()
+            .filter(|length| *length < 1 << 24)
+            .ok_or_else(|| Error::invalid_data("Message is too large"))?;
+        let extended = message.timestamp >= EXTENDED_TIMESTAMP;
+
+        let mut chunks = message.payload.chunks(self.chunk_size);
+        write_basic_header(output, 0, chunk_stream_id);
+        output.extend_from_slice(&message.timestamp.min(EXTENDED_TIMESTAMP).to_be_bytes()[1..]);
+        output.extend_from_slice(&length.to_be_bytes()[1..]);
+        output.push(message.type_id);
+        output.extend_from_slice(&message.stream_id.to_le_bytes());
+        if extended {
+            output.extend_from_slice(&message.timestamp.to_be_bytes());
+        }
+        output.extend_from_slice(chunks.next().unwrap_or_default());
+
+        for chunk in chunks {
+            write_basic_header(output, 3, chunk_stream_id);
+            if extended {
+                output.extend_from_slice(&message.timestamp.to_be_bytes());
+            }
+            output.extend_from_slice(chunk);
+        }
+
+        Ok(())
+    }
+}
+
+fn write_basic_header(output: &mut Vec<u8>, format: u8, chunk_stream_id: u32) {
+    match chunk_stream_id {
+        2..=63 => output.push(format << 6 | chunk_stream_id as u8),
+        64..=319 => output.extend_from_slice(&[format << 6, (chunk_stream_id - 64) as u8]),
+        _ => {
+            output.push(format << 6 | 1);
+            output.extend_from_slice(&((chunk_stream_id - 64) as u16).to_le_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(reader: &mut ChunkReader, mut data: &[u8]) -> Vec<RawMessage> {
+        let mut messages = vec![];
+        while let Some((len, message)) = reader.read_chunk(data).unwrap() {
+            messages.extend(message);
+            data = &data[len..];
+        }
+        assert!(data.is_empty());
+        messages
+    }
+
+    #[test]
+    fn round_trip() {
+        let messages = [
+            RawMessage {
+                timestamp: 1000,
+                type_id: 20,
+                stream_id: 0,
+                payload: (0..=255).collect(),
+            },
+            RawMessage {
+                timestamp: 0x0123_4567,
+                type_id: 9,
+                stream_id: 1,
+                payload: vec![7; 300],
+            },
+        ];
+
+        for chunk_stream_id in [3, 100, 1000] {
+            let writer = ChunkWriter::new();
+            let mut output = vec![];
+            for message in &messages {
+                writer.write(&mut output, chunk_stream_id, message).unwrap();
+            }
+            assert_eq!(read_all(&mut ChunkReader::new(), &output), messages);
+        }
+    }
+
+    #[test]
+    fn incomplete_chunk() {
+        let message = RawMessage {
+            timestamp: 0,
+            type_id: 8,
+            stream_id: 1,
+            payload: vec![1, 2, 3],
+        };
+        let mut output = vec![];
+        ChunkWriter::new().write(&mut output, 4, &message).unwrap();
+
+        let mut reader = ChunkReader::new();
+        for len in 0..output.len() {
+            assert_eq!(reader.read_chunk(&output[..len]), Ok(None));
+        }
+        assert_eq!(
+            reader.read_chunk(&output),
+            Ok(Some((output.len(), Some(message))))
+        );
+    }
+
+    #[test]
+    fn compressed_headers() {
+        #[rustfmt::skip]
+        let data = [
+            // Type 0: timestamp 100, length 2, type 8, stream 1.
+            0x04, 0, 0, 100, 0, 0, 2, 8, 1, 0, 0, 0, 0xAA, 0xBB,
+            // Type 2: delta 20.
+            0x84, 0, 0, 20, 0xCC, 0xDD,
+            // Type 3: same delta again.
+            0xC4, 0xEE, 0xFF,
+            // Type 1: delta 5, length 1, type 9.
+            0x44, 0, 0, 5, 0, 0, 1, 9, 0x11,
+        ];
+        let messages = read_all(&mut ChunkReader::new(), &data);
+        let summary: Vec<_> = messages
+            .iter()
+            .map(|m| (m.timestamp, m.type_id, m.stream_id, m.payload.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (100, 8, 1, vec![0xAA, 0xBB]),
+                (120, 8, 1, vec![0xCC, 0xDD]),
+                (140, 8, 1, vec![0xEE, 0xFF]),
+                (145, 9, 1, vec![0x11]),
+            ]
+        );
+    }
+}
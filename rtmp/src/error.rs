@@ -0,0 +1,34 @@
+use std::{borrow, error, fmt};
+
+/// A `Result` from an RTMP session.
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The peer asked for a version of the protocol other than 3.
+    UnsupportedVersion(u8),
+
+    /// Invalid or unknown data was encountered.
+    InvalidData(borrow::Cow<'static, str>),
+}
+
+impl Error {
+    /// Helper method to create `Error::InvalidData`.
+    #[inline]
+    pub fn invalid_data(message: impl Into<borrow::Cow<'static, str>>) -> Self {
+        Self::InvalidData(message.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported RTMP version {version}")
+            }
+            Self::InvalidData(message) => write!(f, "Invalid data: {message}"),
+        }
+    }
+}
+
+impl error::Error for Error {}
@@ -0,0 +1,30 @@
+//! Client and server sessions for the Real-Time Messaging Protocol (RTMP),
+//! as used by Flash Media Server and its clones.
+//!
+//! A [`Session`] implements one end of a connection without doing any I/O,
+//! so that it can be driven by whatever transport is available: a TCP
+//! socket for `rtmp://` URLs, or HTTP requests for `rtmpt://` tunnels.
+//!
+//! The [`remoting`] module reads and writes the AMF packets that Flash
+//! Remoting sends over HTTP.
+//!
+//! Remote shared objects are kept in sync with
+//! [`Message::SharedObject`] messages.
+//!
+//! [`Server`] is a small blocking server built on top of [`Session`], for
+//! testing clients on localhost.
+
+mod amf;
+mod chunk;
+mod error;
+mod message;
+pub mod remoting;
+mod server;
+mod session;
+mod shared_object;
+
+pub use error::{Error, Result};
+pub use message::{Command, Message, UserControlEvent};
+pub use server::Server;
+pub use session::{ReceivedMessage, Session};
+pub use shared_object::{SharedObjectEvent, SharedObjectMessage};
@@ -0,0 +1,336 @@
+use crate::amf::{read_values, write_value};
+use crate::error::{Error, Result};
+use crate::shared_object::SharedObjectMessage;
+use flash_lso::types::Value;
+
+/// A remote procedure call, or the response to one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Command {
+    /// The name of the procedure, or `_result`/`_error` for responses.
+    pub name: String,
+
+    /// The number used to match responses to calls, or 0 if the caller does
+    /// not expect a response.
+    pub transaction_id: f64,
+
+    /// Information about the call, such as the properties of a `connect`
+    /// request. This is `Value::Null` for most calls.
+    pub command_object: Value,
+
+    /// The arguments of the procedure.
+    pub arguments: Vec<Value>,
+}
+
+impl Command {
+    fn parse(payload: &[u8]) -> Result<Self> {
+        let mut values = read_values(payload)?.into_iter();
+        let name = match values.next() {
+            Some(Value::String(name)) => name,
+            _ => return Err(Error::invalid_data("Command name is not a string")),
+        };
+        let transaction_id = match values.next() {
+            Some(Value::Number(transaction_id)) => transaction_id,
+            _ => 0.0,
+        };
+        let command_object = values.next().unwrap_or(Value::Null);
+
+        Ok(Self {
+            name,
+            transaction_id,
+            command_object,
+            arguments: values.collect(),
+        })
+    }
+
+    /// Whether any of the arguments are AMF3 values, which must be sent in
+    /// an AMF3 command message.
+    fn uses_amf3(&self) -> bool {
+        std::iter::once(&self.command_object)
+            .chain(&self.arguments)
+            .any(|value| matches!(value, Value::AMF3(_)))
+    }
+
+    fn write(&self, output: &mut Vec<u8>) -> Result<()> {
+        write_value(output, &Value::String(self.name.clone()))?;
+        write_value(output, &Value::Number(self.transaction_id))?;
+        write_value(output, &self.command_object)?;
+        for argument in &self.arguments {
+            write_value(output, argument)?;
+        }
+        Ok(())
+    }
+}
+
+/// An event on a message stream, sent by user control messages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UserControlEvent {
+    StreamBegin(u32),
+    StreamEof(u32),
+    StreamDry(u32),
+    SetBufferLength { stream_id: u32, buffer_length: u32 },
+    StreamIsRecorded(u32),
+    PingRequest(u32),
+    PingResponse(u32),
+    Unknown { event_type: u16, data: Vec<u8> },
+}
+
+impl UserControlEvent {
+    fn parse(payload: &[u8]) -> Result<Self> {
+        let (event_type, data) = match payload {
+            [a, b, data @ ..] => (u16::from_be_bytes([*a, *b]), data),
+            _ => return Err(Error::invalid_data("Truncated user control message")),
+        };
+        let argument = |i: usize| {
+            data.get(i * 4..i * 4 + 4)
+                .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .ok_or_else(|| Error::invalid_data("Truncated user control message"))
+        };
+
+        Ok(match event_type {
+            0 => Self::StreamBegin(argument(0)?),
+            1 => Self::StreamEof(argument(0)?),
+            2 => Self::StreamDry(argument(0)?),
+            3 => Self::SetBufferLength {
+                stream_id: argument(0)?,
+                buffer_length: argument(1)?,
+            },
+            4 => Self::StreamIsRecorded(argument(0)?),
+            6 => Self::PingRequest(argument(0)?),
+            7 => Self::PingResponse(argument(0)?),
+            _ => Self::Unknown {
+                event_type,
+                data: data.to_vec(),
+            },
+        })
+    }
+
+    fn write(&self, output: &mut Vec<u8>) {
+        let (event_type, arguments): (u16, &[u32]) = match self {
+            Self::StreamBegin(stream_id) => (0, &[*stream_id]),
+            Self::StreamEof(stream_id) => (1, &[*stream_id]),
+            Self::StreamDry(stream_id) => (2, &[*stream_id]),
+            Self::SetBufferLength {
+                stream_id,
+                buffer_length,
+            } => (3, &[*stream_id, *buffer_length]),
+            Self::StreamIsRecorded(stream_id) => (4, &[*stream_id]),
+            Self::PingRequest(timestamp) => (6, &[*timestamp]),
+            Self::PingResponse(timestamp) => (7, &[*timestamp]),
+            Self::Unknown { event_type, data } => {
+                output.extend_from_slice(&event_type.to_be_bytes());
+                output.extend_from_slice(data);
+                return;
+            }
+        };
+        output.extend_from_slice(&event_type.to_be_bytes());
+        for argument in arguments {
+            output.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+/// A message sent over an RTMP connection.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    SetChunkSize(u32),
+    Abort {
+        chunk_stream_id: u32,
+    },
+    Acknowledgement {
+        sequence_number: u32,
+    },
+    UserControl(UserControlEvent),
+    WindowAcknowledgementSize(u32),
+    SetPeerBandwidth {
+        size: u32,
+        limit_type: u8,
+    },
+
+    /// A packet of audio, laid out the same way as an FLV audio tag.
+    Audio(Vec<u8>),
+
+    /// A packet of video, laid out the same way as an FLV video tag.
+    Video(Vec<u8>),
+
+    /// Script data, such as `onMetaData`, sent to a stream's client.
+    Data(Vec<Value>),
+
+    /// A remote procedure call, or the response to one.
+    Command(Command),
+
+    /// Events on a remote shared object.
+    SharedObject(SharedObjectMessage),
+
+    /// A message type that this library does not understand, such as
+    /// aggregate messages.
+    Unknown {
+        type_id: u8,
+        payload: Vec<u8>,
+    },
+}
+
+impl Message {
+    pub(crate) const SET_CHUNK_SIZE: u8 = 1;
+    pub(crate) const ABORT: u8 = 2;
+    pub(crate) const ACKNOWLEDGEMENT: u8 = 3;
+    pub(crate) const USER_CONTROL: u8 = 4;
+    pub(crate) const WINDOW_ACKNOWLEDGEMENT_SIZE: u8 = 5;
+    pub(crate) const SET_PEER_BANDWIDTH: u8 = 6;
+    pub(crate) const AUDIO: u8 = 8;
+    pub(crate) const VIDEO: u8 = 9;
+    pub(crate) const DATA_AMF3: u8 = 15;
+    pub(crate) const SHARED_OBJECT_AMF3: u8 = 16;
+    pub(crate) const COMMAND_AMF3: u8 = 17;
+    pub(crate) const DATA_AMF0: u8 = 18;
+    pub(crate) const SHARED_OBJECT_AMF0: u8 = 19;
+    pub(crate) const COMMAND_AMF0: u8 = 20;
+
+    pub(crate) fn parse(type_id: u8, payload: Vec<u8>) -> Result<Self> {
+        let u32_argument = || {
+            payload
+                .get(..4)
+                .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .ok_or_else(|| Error::invalid_data("Truncated protocol control message"))
+        };
+
+        Ok(match type_id {
+            Self::SET_CHUNK_SIZE => Self::SetChunkSize(u32_argument()? & 0x7FFF_FFFF),
+            Self::ABORT => Self::Abort {
+                chunk_stream_id: u32_argument()?,
+            },
+            Self::ACKNOWLEDGEMENT => Self::Acknowledgement {
+                sequence_number: u32_argument()?,
+            },
+            Self::USER_CONTROL => Self::UserControl(UserControlEvent::parse(&payload)?),
+            Self::WINDOW_ACKNOWLEDGEMENT_SIZE => Self::WindowAcknowledgementSize(u32_argument()?),
+            Self::SET_PEER_BANDWIDTH => Self::SetPeerBandwidth {
+                size: u32_argument()?,
+                limit_type: payload.get(4).copied().unwrap_or(2),
+            },
+            Self::AUDIO => Self::Audio(payload),
+            Self::VIDEO => Self::Video(payload),
+            Self::DATA_AMF0 => Self::Data(read_values(&payload)?),
+            Self::COMMAND_AMF0 => Self::Command(Command::parse(&payload)?),
+            Self::SHARED_OBJECT_AMF0 => Self::SharedObject(SharedObjectMessage::parse(&payload)?),
+            // AMF3 messages start with a format byte, followed by AMF0 values
+            // that switch to AMF3 where needed.
+            Self::DATA_AMF3 => Self::Data(read_values(payload.get(1..).unwrap_or_default())?),
+            Self::COMMAND_AMF3 => {
+                Self::Command(Command::parse(payload.get(1..).unwrap_or_default())?)
+            }
+            Self::SHARED_OBJECT_AMF3 => Self::SharedObject(SharedObjectMessage::parse(
+                payload.get(1..).unwrap_or_default(),
+            )?),
+            _ => Self::Unknown { type_id, payload },
+        })
+    }
+
+    pub(crate) fn type_id(&self) -> u8 {
+        match self {
+            Self::SetChunkSize(_) => Self::SET_CHUNK_SIZE,
+            Self::Abort { .. } => Self::ABORT,
+            Self::Acknowledgement { .. } => Self::ACKNOWLEDGEMENT,
+            Self::UserControl(_) => Self::USER_CONTROL,
+            Self::WindowAcknowledgementSize(_) => Self::WINDOW_ACKNOWLEDGEMENT_SIZE,
+            Self::SetPeerBandwidth { .. } => Self::SET_PEER_BANDWIDTH,
+            Self::Audio(_) => Self::AUDIO,
+            Self::Video(_) => Self::VIDEO,
+            Self::Data(_) => Self::DATA_AMF0,
+            Self::Command(command) if command.uses_amf3() => Self::COMMAND_AMF3,
+            Self::Command(_) => Self::COMMAND_AMF0,
+            Self::SharedObject(message) if message.uses_amf3() => Self::SHARED_OBJECT_AMF3,
+            Self::SharedObject(_) => Self::SHARED_OBJECT_AMF0,
+            Self::Unknown { type_id, .. } => *type_id,
+        }
+    }
+
+    /// The chunk stream that this message is conventionally sent on.
+    pub(crate) fn chunk_stream_id(&self) -> u32 {
+        match self {
+            Self::SetChunkSize(_)
+            | Self::Abort { .. }
+            | Self::Acknowledgement { .. }
+            | Self::UserControl(_)
+            | Self::WindowAcknowledgementSize(_)
+            | Self::SetPeerBandwidth { .. } => 2,
+            Self::Command(_) | Self::SharedObject(_) => 3,
+            Self::Audio(_) => 4,
+            Self::Video(_) => 5,
+            Self::Data(_) | Self::Unknown { .. } => 6,
+        }
+    }
+
+    pub(crate) fn to_payload(&self) -> Result<Vec<u8>> {
+        let mut payload = Vec::new();
+        match self {
+            Self::SetChunkSize(n)
+            | Self::Abort { chunk_stream_id: n }
+            | Self::Acknowledgement { sequence_number: n }
+            | Self::WindowAcknowledgementSize(n) => payload.extend_from_slice(&n.to_be_bytes()),
+            Self::SetPeerBandwidth { size, limit_type } => {
+                payload.extend_from_slice(&size.to_be_bytes());
+                payload.push(*limit_type);
+            }
+            Self::UserControl(event) => event.write(&mut payload),
+            Self::Audio(data) | Self::Video(data) => payload.extend_from_slice(data),
+            Self::Data(values) => {
+                for value in values {
+                    write_value(&mut payload, value)?;
+                }
+            }
+            Self::Command(command) => {
+                if command.uses_amf3() {
+                    payload.push(0);
+                }
+                command.write(&mut payload)?
+            }
+            Self::SharedObject(message) => {
+                if message.uses_amf3() {
+                    payload.push(0);
+                }
+                message.write(&mut payload)?
+            }
+            Self::Unknown { payload: data, .. } => payload.extend_from_slice(data),
+        }
+        Ok(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_control_round_trip() {
+        for event in [
+            UserControlEvent::StreamBegin(1),
+            UserControlEvent::SetBufferLength {
+                stream_id: 1,
+                buffer_length: 3000,
+            },
+            UserControlEvent::PingRequest(0x1234_5678),
+        ] {
+            let message = Message::UserControl(event);
+            let payload = message.to_payload().unwrap();
+            assert_eq!(
+                Message::parse(Message::USER_CONTROL, payload),
+                Ok(message.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn command_round_trip() {
+        let message = Message::Command(Command {
+            name: "echo".to_string(),
+            transaction_id: 2.0,
+            command_object: Value::Null,
+            arguments: vec![Value::String("hello".to_string()), Value::Bool(true)],
+        });
+        let payload = message.to_payload().unwrap();
+        assert_eq!(
+            Message::parse(Message::COMMAND_AMF0, payload),
+            Ok(message.clone())
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Flash Remoting packets, which carry AMF calls and their results over HTTP.
+//!
+//! A packet is sent as the body of a POST request to a gateway, such as
+//! AMFPHP or BlazeDS, and the gateway answers with a packet of its own. Each
+//! call in a request has a response URI of the form `/<id>`, and its result
+//! comes back as a message with a target URI of `/<id>/onResult`, or
+//! `/<id>/onStatus` if it failed.
+
+use crate::amf::{read_value, write_value};
+use crate::error::{Error, Result};
+use flash_lso::types::Value;
+
+/// The content type of Flash Remoting requests and responses.
+pub const CONTENT_TYPE: &str = "application/x-amf";
+
+/// A header of a remoting packet, such as credentials.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Header {
+    pub name: String,
+
+    /// Whether the receiver must reject the packet if it does not
+    /// understand this header.
+    pub must_understand: bool,
+
+    pub value: Value,
+}
+
+/// A call, or the result of a call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    /// The procedure to call, or the response URI of the call that this is
+    /// the result of, followed by `/onResult` or `/onStatus`.
+    pub target_uri: String,
+
+    /// The URI that the result should be sent to.
+    pub response_uri: String,
+
+    /// The arguments of a call, as a strict array, or the result of one.
+    pub contents: Value,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Packet {
+    /// 0 for packets sent by AS1/AS2 movies, or 3 if the packet may contain
+    /// AMF3 values.
+    pub version: u16,
+
+    pub headers: Vec<Header>,
+    pub messages: Vec<Message>,
+}
+
+/// The length that is written for headers and messages whose length is not
+/// known.
+const UNKNOWN_LENGTH: u32 = 0xFFFF_FFFF;
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn read(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(Error::invalid_data("Truncated remoting packet"));
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_u16(&mut self) -> Result<u16> {
+        self.read(2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        self.read(4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        let len = self.read_u16()?;
+        let bytes = self.read(len.into())?;
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    /// Read a value that is preceded by its length.
+    ///
+    /// The length is ignored, as some gateways get it wrong.
+    fn read_value(&mut self) -> Result<Value> {
+        self.read_u32()?;
+        let (rest, value) = read_value(self.data)?;
+        self.data = rest;
+        Ok(value)
+    }
+}
+
+fn write_string(output: &mut Vec<u8>, string: &str) -> Result<()> {
+    let len = u16::try_from(string.len()).map_err(|_| Error::invalid_data("String is too long"))?;
+    output.extend_from_slice(&len.to_be_bytes());
+    output.extend_from_slice(string.as_bytes());
+    Ok(())
+}
+
+fn write_value_with_length(output: &mut Vec<u8>, value: &Value) -> Result<()> {
+    let mut data = Vec::new();
+    write_value(&mut data, value)?;
+    let len = u32::try_from(data.len()).unwrap_or(UNKNOWN_LENGTH);
+    output.extend_from_slice(&len.to_be_bytes());
+    output.extend_from_slice(&data);
+    Ok(())
+}
+
+impl Packet {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let mut reader = Reader { data };
+        let version = reader.read_u16()?;
+
+        let header_count = reader.read_u16()?;
+        let mut headers = Vec::with_capacity(header_count.into());
+        for _ in 0..header_count {
+            let name = reader.read_string()?;
+            let must_understand = reader.read(1)?[0] != 0;
+            let value = reader.read_value()?;
+            headers.push(Header {
+                name,
+                must_understand,
+                value,
+            });
+        }
+
+        let message_count = reader.read_u16()?;
+        let mut messages = Vec::with_capacity(message_count.into());
+        for _ in 0..message_count {
+            let target_uri = reader.read_string()?;
+            let response_uri = reader.read_string()?;
+            let contents = reader.read_value()?;
+            messages.push(Message {
+                target_uri,
+                response_uri,
+                contents,
+            });
+        }
+
+        Ok(Self {
+            version,
+            headers,
+            messages,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let count = |len: usize| {
+            u16::try_from(len).map_err(|_| Error::invalid_data("Too many headers or messages"))
+        };
+
+        let mut output = Vec::new();
+        output.extend_from_slice(&self.version.to_be_bytes());
+
+        output.extend_from_slice(&count(self.headers.len())?.to_be_bytes());
+        for header in &self.headers {
+            write_string(&mut output, &header.name)?;
+            output.push(header.must_understand.into());
+            write_value_with_length(&mut output, &header.value)?;
+        }
+
+        output.extend_from_slice(&count(self.messages.len())?.to_be_bytes());
+        for message in &self.messages {
+            write_string(&mut output, &message.target_uri)?;
+            write_string(&mut output, &message.response_uri)?;
+            write_value_with_length(&mut output, &message.contents)?;
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let packet = Packet {
+            version: 3,
+            headers: vec![Header {
+                name: "Credentials".to_string(),
+                must_understand: false,
+                value: Value::String("secret".to_string()),
+            }],
+            messages: vec![Message {
+                target_uri: "Service.echo".to_string(),
+                response_uri: "/1".to_string(),
+                contents: Value::StrictArray(vec![Value::Number(1.0).into()]),
+            }],
+        };
+
+        let bytes = packet.to_bytes().unwrap();
+        assert_eq!(Packet::parse(&bytes), Ok(packet));
+    }
+
+    #[test]
+    fn truncated() {
+        let packet = Packet {
+            version: 0,
+            headers: vec![],
+            messages: vec![Message {
+                target_uri: "/1/onResult".to_string(),
+                response_uri: "null".to_string(),
+                contents: Value::Bool(true),
+            }],
+        };
+
+        let bytes = packet.to_bytes().unwrap();
+        assert!(Packet::parse(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn gateway_response() {
+        // A result as sent by an AMFPHP gateway, with an unknown length.
+        let mut bytes = vec![0, 0, 0, 0, 0, 1];
+        bytes.extend_from_slice(b"\x00\x0b/1/onResult\x00\x04null");
+        bytes.extend_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+        bytes.extend_from_slice(b"\x02\x00\x05hello");
+
+        let packet = Packet::parse(&bytes).unwrap();
+        assert_eq!(packet.version, 0);
+        assert!(packet.headers.is_empty());
+        assert_eq!(
+            packet.messages,
+            vec![Message {
+                target_uri: "/1/onResult".to_string(),
+                response_uri: "null".to_string(),
+                contents: Value::String("hello".to_string()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,695 @@
+use crate::amf::{read_value, write_value};
+use crate::message::{Command, Message, UserControlEvent};
+use crate::session::Session;
+use crate::shared_object::{SharedObjectEvent, SharedObjectMessage};
+use flash_lso::types::{Element, Value};
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// A minimal RTMP server, for testing clients against.
+///
+/// The server accepts connections to any application, except one named
+/// `reject`, and answers a handful of commands:
+///
+/// * `createStream` returns a new stream ID.
+/// * `invokeClient` calls the client method named by its first argument,
+///   with the remaining arguments, and then returns `null`.
+/// * Any other command returns its first argument.
+///
+/// Remote shared objects are shared between every connection. Changes that
+/// a client asks for are always accepted, and non-persistent shared objects
+/// are forgotten once no client is using them. Asking to use a shared object
+/// with a different persistence than it was made with is an error.
+///
+/// Each connection is served on its own thread.
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    shared_objects: Arc<Mutex<SharedObjects>>,
+}
+
+/// The remote shared objects that are in use, by name.
+#[derive(Debug, Default)]
+struct SharedObjects {
+    objects: HashMap<String, SharedObjectState>,
+    next_client_id: u64,
+}
+
+/// A remote shared object.
+///
+/// AMF values can't be sent between threads, so properties and messages for
+/// other connections are kept in their serialized form.
+#[derive(Debug, Default)]
+struct SharedObjectState {
+    version: u32,
+    persistent: bool,
+
+    /// The AMF0-encoded value of each property, in the order that they were
+    /// first set.
+    properties: Vec<(String, Vec<u8>)>,
+
+    /// The connections that are using this shared object, which are sent
+    /// serialized `SharedObjectMessage`s through their channels.
+    clients: HashMap<u64, Sender<Vec<u8>>>,
+}
+
+/// How long a connection waits for data from its client before it checks
+/// for shared object messages from other connections.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+impl Server {
+    /// Listen for connections on the given address.
+    ///
+    /// Bind to port 0 to let the OS pick a free port, and use
+    /// [`Server::local_addr`] to find out which one it picked.
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            shared_objects: Default::default(),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept and serve connections forever.
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let mut connection = Connection::new(self.shared_objects.clone());
+            // A misbehaving client only ends its own connection.
+            thread::spawn(move || {
+                let result = connection.serve(stream);
+                connection.release_all();
+                result
+            });
+        }
+        Ok(())
+    }
+
+    /// Run the server on a background thread.
+    pub fn spawn(self) -> JoinHandle<io::Result<()>> {
+        thread::spawn(move || self.run())
+    }
+}
+
+/// The state of a single client connection.
+struct Connection {
+    id: u64,
+    shared_objects: Arc<Mutex<SharedObjects>>,
+
+    /// The names of the shared objects that this client is using.
+    used_shared_objects: Vec<String>,
+
+    /// Serialized shared object messages from other connections, to pass on
+    /// to this client.
+    sender: Sender<Vec<u8>>,
+    receiver: Receiver<Vec<u8>>,
+}
+
+impl Connection {
+    fn new(shared_objects: Arc<Mutex<SharedObjects>>) -> Self {
+        let id = {
+            let mut shared_objects = shared_objects.lock().unwrap();
+            shared_objects.next_client_id += 1;
+            shared_objects.next_client_id
+        };
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            id,
+            shared_objects,
+            used_shared_objects: Vec::new(),
+            sender,
+            receiver,
+        }
+    }
+
+    fn serve(&mut self, mut stream: TcpStream) -> io::Result<()> {
+        let mut session = Session::server();
+        let mut next_stream_id = 1.0;
+        let mut buffer = vec![0; 4096];
+        stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        loop {
+            let len = match stream.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(len) => len,
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    0
+                }
+                Err(e) => return Err(e),
+            };
+            let messages = session
+                .receive(&buffer[..len])
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            for received in messages {
+                let result = match received.message {
+                    Message::Command(command) => {
+                        respond(&mut session, command, &mut next_stream_id)
+                    }
+                    Message::SharedObject(message) => {
+                        self.respond_shared_object(&mut session, message)
+                    }
+                    _ => Ok(()),
+                };
+                result.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            }
+            for data in self.receiver.try_iter() {
+                SharedObjectMessage::parse(&data)
+                    .and_then(|message| session.send(0, &Message::SharedObject(message)))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            }
+            stream.write_all(&session.take_output())?;
+        }
+    }
+
+    fn respond_shared_object(
+        &mut self,
+        session: &mut Session,
+        message: SharedObjectMessage,
+    ) -> crate::Result<()> {
+        let mut shared_objects = self.shared_objects.lock().unwrap();
+        let shared_objects = &mut *shared_objects;
+        let reply_to = |state: &SharedObjectState, events| SharedObjectMessage {
+            name: message.name.clone(),
+            version: state.version,
+            persistent: state.persistent,
+            events,
+        };
+
+        for event in &message.events {
+            if let SharedObjectEvent::Use = event {
+                let state = shared_objects
+                    .objects
+                    .entry(message.name.clone())
+                    .or_insert_with(|| SharedObjectState {
+                        persistent: message.persistent,
+                        ..Default::default()
+                    });
+                if state.persistent != message.persistent {
+                    let status = SharedObjectEvent::Status {
+                        code: "SharedObject.BadPersistence".to_string(),
+                        level: "error".to_string(),
+                    };
+                    session.send(0, &Message::SharedObject(reply_to(state, vec![status])))?;
+                    continue;
+                }
+                state.clients.insert(self.id, self.sender.clone());
+                if !self.used_shared_objects.contains(&message.name) {
+                    self.used_shared_objects.push(message.name.clone());
+                }
+
+                let mut events = vec![SharedObjectEvent::UseSuccess, SharedObjectEvent::Clear];
+                for (name, value) in &state.properties {
+                    let (_, value) = read_value(value)?;
+                    events.push(SharedObjectEvent::Change {
+                        name: name.clone(),
+                        value,
+                    });
+                }
+                session.send(0, &Message::SharedObject(reply_to(state, events)))?;
+                continue;
+            }
+
+            let Some(state) = shared_objects.objects.get_mut(&message.name) else {
+                continue;
+            };
+            if !state.clients.contains_key(&self.id) {
+                continue;
+            }
+
+            match event {
+                SharedObjectEvent::Release => {
+                    self.used_shared_objects
+                        .retain(|used| *used != message.name);
+                    shared_objects.release(self.id, &message.name);
+                }
+                SharedObjectEvent::RequestChange { name, value } => {
+                    let mut data = Vec::new();
+                    write_value(&mut data, value)?;
+                    state.version += 1;
+                    match state.properties.iter_mut().find(|(n, _)| n == name) {
+                        Some((_, old_value)) => *old_value = data,
+                        None => state.properties.push((name.clone(), data)),
+                    }
+
+                    let success = reply_to(
+                        state,
+                        vec![SharedObjectEvent::Success { name: name.clone() }],
+                    );
+                    session.send(0, &Message::SharedObject(success))?;
+                    let change = reply_to(
+                        state,
+                        vec![SharedObjectEvent::Change {
+                            name: name.clone(),
+                            value: value.clone(),
+                        }],
+                    );
+                    state.broadcast(Some(self.id), &change)?;
+                }
+                SharedObjectEvent::RequestRemove { name } => {
+                    state.version += 1;
+                    state.properties.retain(|(n, _)| n != name);
+                    let remove = reply_to(
+                        state,
+                        vec![SharedObjectEvent::Remove { name: name.clone() }],
+                    );
+                    state.broadcast(Some(self.id), &remove)?;
+                }
+                SharedObjectEvent::SendMessage(values) => {
+                    let send =
+                        reply_to(state, vec![SharedObjectEvent::SendMessage(values.clone())]);
+                    state.broadcast(None, &send)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn release_all(&mut self) {
+        let mut shared_objects = self.shared_objects.lock().unwrap();
+        for name in std::mem::take(&mut self.used_shared_objects) {
+            shared_objects.release(self.id, &name);
+        }
+    }
+}
+
+impl SharedObjects {
+    /// Stop a client from using a shared object, and forget the shared
+    /// object if it is not persistent and nobody else is using it.
+    fn release(&mut self, client_id: u64, name: &str) {
+        if let Some(state) = self.objects.get_mut(name) {
+            state.clients.remove(&client_id);
+            if state.clients.is_empty() && !state.persistent {
+                self.objects.remove(name);
+            }
+        }
+    }
+}
+
+impl SharedObjectState {
+    /// Send a message to every client using this shared object, except for
+    /// `except`.
+    fn broadcast(&self, except: Option<u64>, message: &SharedObjectMessage) -> crate::Result<()> {
+        let mut data = Vec::new();
+        message.write(&mut data)?;
+        for (id, sender) in &self.clients {
+            if Some(*id) != except {
+                // The connection may have just closed, in which case it
+                // will release the shared object shortly.
+                let _ = sender.send(data.clone());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn respond(session: &mut Session, command: Command, next_stream_id: &mut f64) -> crate::Result<()> {
+    let reply = |name: &str, command_object: Value, arguments: Vec<Value>| {
+        Message::Command(Command {
+            name: name.to_string(),
+            transaction_id: command.transaction_id,
+            command_object,
+            arguments,
+        })
+    };
+
+    match command.name.as_str() {
+        "connect" => {
+            let app = match property(&command.command_object, "app") {
+                Some(Value::String(app)) => app.as_str(),
+                _ => "",
+            };
+            if app == "reject" {
+                let info = object(vec![
+                    ("level", Value::String("error".to_string())),
+                    (
+                        "code",
+                        Value::String("NetConnection.Connect.Rejected".to_string()),
+                    ),
+                    (
+                        "description",
+                        Value::String("Connection rejected.".to_string()),
+                    ),
+                ]);
+                return session.send(0, &reply("_error", Value::Null, vec![info]));
+            }
+
+            let object_encoding = match property(&command.command_object, "objectEncoding") {
+                Some(Value::Number(encoding)) => *encoding,
+                _ => 0.0,
+            };
+            session.send(0, &Message::WindowAcknowledgementSize(2_500_000))?;
+            session.send(
+                0,
+                &Message::SetPeerBandwidth {
+                    size: 2_500_000,
+                    limit_type: 2,
+                },
+            )?;
+            session.send(0, &Message::UserControl(UserControlEvent::StreamBegin(0)))?;
+
+            let properties = object(vec![
+                ("fmsVer", Value::String("FMS/3,5,7,7009".to_string())),
+                ("capabilities", Value::Number(31.0)),
+            ]);
+            let info = object(vec![
+                ("level", Value::String("status".to_string())),
+                (
+                    "code",
+                    Value::String("NetConnection.Connect.Success".to_string()),
+                ),
+                (
+                    "description",
+                    Value::String("Connection succeeded.".to_string()),
+                ),
+                ("objectEncoding", Value::Number(object_encoding)),
+            ]);
+            session.send(0, &reply("_result", properties, vec![info]))
+        }
+        "createStream" => {
+            let stream_id = *next_stream_id;
+            *next_stream_id += 1.0;
+            session.send(
+                0,
+                &reply("_result", Value::Null, vec![Value::Number(stream_id)]),
+            )
+        }
+        "invokeClient" => {
+            let mut arguments = command.arguments.clone().into_iter();
+            if let Some(Value::String(name)) = arguments.next() {
+                session.send(
+                    0,
+                    &Message::Command(Command {
+                        name,
+                        transaction_id: 0.0,
+                        command_object: Value::Null,
+                        arguments: arguments.collect(),
+                    }),
+                )?;
+            }
+            if command.transaction_id != 0.0 {
+                session.send(0, &reply("_result", Value::Null, vec![Value::Null]))?;
+            }
+            Ok(())
+        }
+        "close" | "deleteStream" => Ok(()),
+        _ if command.transaction_id != 0.0 => {
+            let result = command.arguments.first().cloned().unwrap_or(Value::Null);
+            session.send(0, &reply("_result", Value::Null, vec![result]))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn object(properties: Vec<(&str, Value)>) -> Value {
+    Value::Object(
+        properties
+            .into_iter()
+            .map(|(name, value)| Element::new(name, value))
+            .collect(),
+        None,
+    )
+}
+
+fn property<'a>(object: &'a Value, name: &str) -> Option<&'a Value> {
+    let elements = match object {
+        Value::Object(elements, _) | Value::ECMAArray(_, elements, _) => elements,
+        _ => return None,
+    };
+    let element = elements.iter().find(|element| element.name == name)?;
+    let value: &Value = element.value();
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::ReceivedMessage;
+    use std::collections::VecDeque;
+    use std::time::Duration;
+
+    struct Client {
+        session: Session,
+        stream: TcpStream,
+        commands: VecDeque<Command>,
+        shared_object_messages: VecDeque<SharedObjectMessage>,
+    }
+
+    impl Client {
+        fn connect(address: SocketAddr) -> Self {
+            let stream = TcpStream::connect(address).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(10)))
+                .unwrap();
+            Self {
+                session: Session::client(),
+                stream,
+                commands: VecDeque::new(),
+                shared_object_messages: VecDeque::new(),
+            }
+        }
+
+        fn call(
+            &mut self,
+            name: &str,
+            transaction_id: f64,
+            command_object: Value,
+            arguments: Vec<Value>,
+        ) {
+            self.send(Message::Command(Command {
+                name: name.to_string(),
+                transaction_id,
+                command_object,
+                arguments,
+            }));
+        }
+
+        fn send(&mut self, message: Message) {
+            self.session.send(0, &message).unwrap();
+            self.stream.write_all(&self.session.take_output()).unwrap();
+        }
+
+        fn send_shared_object_events(&mut self, name: &str, events: Vec<SharedObjectEvent>) {
+            self.send(Message::SharedObject(SharedObjectMessage {
+                name: name.to_string(),
+                version: 0,
+                persistent: false,
+                events,
+            }));
+        }
+
+        /// Wait for the next command from the server.
+        fn next_command(&mut self) -> Command {
+            loop {
+                if let Some(command) = self.commands.pop_front() {
+                    return command;
+                }
+                self.receive();
+            }
+        }
+
+        /// Wait for the next shared object message from the server.
+        fn next_shared_object_message(&mut self) -> SharedObjectMessage {
+            loop {
+                if let Some(message) = self.shared_object_messages.pop_front() {
+                    return message;
+                }
+                self.receive();
+            }
+        }
+
+        fn receive(&mut self) {
+            let mut buffer = vec![0; 4096];
+            let len = self.stream.read(&mut buffer).unwrap();
+            assert_ne!(len, 0, "server closed the connection");
+            let messages = self.session.receive(&buffer[..len]).unwrap();
+            self.stream.write_all(&self.session.take_output()).unwrap();
+            for ReceivedMessage { message, .. } in messages {
+                match message {
+                    Message::Command(command) => self.commands.push_back(command),
+                    Message::SharedObject(message) => {
+                        self.shared_object_messages.push_back(message)
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn spawn_server() -> SocketAddr {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        server.spawn();
+        address
+    }
+
+    fn connect_object(app: &str) -> Value {
+        object(vec![
+            ("app", Value::String(app.to_string())),
+            ("objectEncoding", Value::Number(3.0)),
+        ])
+    }
+
+    #[test]
+    fn connect_and_call() {
+        let mut client = Client::connect(spawn_server());
+
+        client.call("connect", 1.0, connect_object("live"), vec![]);
+        let result = client.next_command();
+        assert_eq!(result.name, "_result");
+        assert_eq!(result.transaction_id, 1.0);
+        let info = &result.arguments[0];
+        assert_eq!(
+            property(info, "code"),
+            Some(&Value::String("NetConnection.Connect.Success".to_string()))
+        );
+        assert_eq!(property(info, "objectEncoding"), Some(&Value::Number(3.0)));
+
+        client.call(
+            "echo",
+            2.0,
+            Value::Null,
+            vec![Value::String("hi".to_string())],
+        );
+        let result = client.next_command();
+        assert_eq!(
+            (
+                result.name.as_str(),
+                result.transaction_id,
+                result.arguments
+            ),
+            ("_result", 2.0, vec![Value::String("hi".to_string())])
+        );
+
+        client.call(
+            "invokeClient",
+            3.0,
+            Value::Null,
+            vec![Value::String("ping".to_string()), Value::Number(5.0)],
+        );
+        let call = client.next_command();
+        assert_eq!(
+            (call.name.as_str(), call.transaction_id, call.arguments),
+            ("ping", 0.0, vec![Value::Number(5.0)])
+        );
+        assert_eq!(client.next_command().transaction_id, 3.0);
+    }
+
+    #[test]
+    fn reject() {
+        let mut client = Client::connect(spawn_server());
+
+        client.call("connect", 1.0, connect_object("reject"), vec![]);
+        let result = client.next_command();
+        assert_eq!(result.name, "_error");
+        assert_eq!(
+            property(&result.arguments[0], "code"),
+            Some(&Value::String("NetConnection.Connect.Rejected".to_string()))
+        );
+    }
+
+    #[test]
+    fn shared_objects() {
+        let address = spawn_server();
+        let mut alice = Client::connect(address);
+        let mut bob = Client::connect(address);
+        for client in [&mut alice, &mut bob] {
+            client.call("connect", 1.0, connect_object("chat"), vec![]);
+            assert_eq!(client.next_command().name, "_result");
+        }
+
+        alice.send_shared_object_events("room", vec![SharedObjectEvent::Use]);
+        let message = alice.next_shared_object_message();
+        assert_eq!(
+            message.events,
+            vec![SharedObjectEvent::UseSuccess, SharedObjectEvent::Clear]
+        );
+
+        let topic = Value::String("hello".to_string());
+        alice.send_shared_object_events(
+            "room",
+            vec![SharedObjectEvent::RequestChange {
+                name: "topic".to_string(),
+                value: topic.clone(),
+            }],
+        );
+        let message = alice.next_shared_object_message();
+        assert_eq!(message.version, 1);
+        assert_eq!(
+            message.events,
+            vec![SharedObjectEvent::Success {
+                name: "topic".to_string()
+            }]
+        );
+
+        bob.send_shared_object_events("room", vec![SharedObjectEvent::Use]);
+        let message = bob.next_shared_object_message();
+        assert_eq!(
+            message.events,
+            vec![
+                SharedObjectEvent::UseSuccess,
+                SharedObjectEvent::Clear,
+                SharedObjectEvent::Change {
+                    name: "topic".to_string(),
+                    value: topic,
+                },
+            ]
+        );
+
+        bob.send_shared_object_events(
+            "room",
+            vec![SharedObjectEvent::RequestRemove {
+                name: "topic".to_string(),
+            }],
+        );
+        let message = alice.next_shared_object_message();
+        assert_eq!(
+            message.events,
+            vec![SharedObjectEvent::Remove {
+                name: "topic".to_string()
+            }]
+        );
+
+        let mut carol = Client::connect(address);
+        carol.call("connect", 1.0, connect_object("chat"), vec![]);
+        assert_eq!(carol.next_command().name, "_result");
+        carol.send(Message::SharedObject(SharedObjectMessage {
+            name: "room".to_string(),
+            version: 0,
+            persistent: true,
+            events: vec![SharedObjectEvent::Use],
+        }));
+        assert_eq!(
+            carol.next_shared_object_message().events,
+            vec![SharedObjectEvent::Status {
+                code: "SharedObject.BadPersistence".to_string(),
+                level: "error".to_string(),
+            }]
+        );
+
+        let call = vec![
+            Value::String("say".to_string()),
+            Value::String("hi".to_string()),
+        ];
+        alice.send_shared_object_events("room", vec![SharedObjectEvent::SendMessage(call.clone())]);
+        for client in [&mut alice, &mut bob] {
+            let message = client.next_shared_object_message();
+            assert_eq!(
+                message.events,
+                vec![SharedObjectEvent::SendMessage(call.clone())]
+            );
+        }
+    }
+}
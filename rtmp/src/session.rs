@@ -0,0 +1,326 @@
+use crate::chunk::{ChunkReader, ChunkWriter, RawMessage};
+use crate::error::{Error, Result};
+use crate::message::{Message, UserControlEvent};
+
+/// The only version of RTMP that exists.
+const VERSION: u8 = 3;
+
+/// The length of the C1/S1 and C2/S2 handshake packets.
+const HANDSHAKE_LENGTH: usize = 1536;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    /// The client is waiting for S0, S1 and S2.
+    ClientHandshake,
+
+    /// The server is waiting for C0 and C1.
+    ServerHandshake,
+
+    /// The server is waiting for C2.
+    ServerAwaitingAck,
+
+    Ready,
+}
+
+/// A message received from the peer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceivedMessage {
+    /// The message stream that the message was sent on; 0 for the
+    /// connection itself.
+    pub stream_id: u32,
+
+    /// The timestamp of the message, in milliseconds.
+    pub timestamp: u32,
+
+    pub message: Message,
+}
+
+/// One end of an RTMP connection.
+///
+/// A session does no I/O of its own: bytes received from the peer are fed
+/// to [`Session::receive`], and bytes to send to the peer are collected from
+/// [`Session::take_output`]. This lets the same session be driven by a
+/// socket, an HTTP tunnel, or a test.
+///
+/// The session takes care of the handshake and of protocol control
+/// messages, such as chunk sizes, acknowledgements and pings.
+#[derive(Debug)]
+pub struct Session {
+    state: State,
+    reader: ChunkReader,
+    writer: ChunkWriter,
+
+    /// Received bytes that have not been processed yet.
+    input: Vec<u8>,
+
+    /// Bytes that are ready to be sent to the peer.
+    output: Vec<u8>,
+
+    /// Chunks of messages that were sent before the handshake finished.
+    pending: Vec<u8>,
+
+    bytes_received: u64,
+    last_acknowledgement: u64,
+    window_acknowledgement_size: u32,
+}
+
+impl Session {
+    fn new(state: State) -> Self {
+        Self {
+            state,
+            reader: ChunkReader::new(),
+            writer: ChunkWriter::new(),
+            input: Vec::new(),
+            output: Vec::new(),
+            pending: Vec::new(),
+            bytes_received: 0,
+            last_acknowledgement: 0,
+            window_acknowledgement_size: 0,
+        }
+    }
+
+    /// Start the client end of a connection.
+    ///
+    /// The first part of the handshake is immediately available from
+    /// [`Session::take_output`].
+    pub fn client() -> Self {
+        let mut session = Self::new(State::ClientHandshake);
+        session.output.push(VERSION);
+        session.output.extend_from_slice(&handshake_packet());
+        session
+    }
+
+    /// Start the server end of a connection.
+    pub fn server() -> Self {
+        Self::new(State::ServerHandshake)
+    }
+
+    /// Whether the handshake has finished.
+    ///
+    /// Messages sent before then are held back until it has.
+    pub fn is_ready(&self) -> bool {
+        self.state == State::Ready
+    }
+
+    /// Take the bytes that should be sent to the peer.
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Send a message on the given message stream.
+    pub fn send(&mut self, stream_id: u32, message: &Message) -> Result<()> {
+        let raw = RawMessage {
+            timestamp: 0,
+            type_id: message.type_id(),
+            stream_id,
+            payload: message.to_payload()?,
+        };
+        let output = if self.is_ready() {
+            &mut self.output
+        } else {
+            &mut self.pending
+        };
+        self.writer.write(output, message.chunk_stream_id(), &raw)
+    }
+
+    /// Change the size of the chunks that are sent to the peer.
+    pub fn set_chunk_size(&mut self, chunk_size: u32) -> Result<()> {
+        let chunk_size = chunk_size.clamp(1, 0x7FFF_FFFF);
+        self.send(0, &Message::SetChunkSize(chunk_size))?;
+        self.writer.set_chunk_size(chunk_size as usize);
+        Ok(())
+    }
+
+    /// Process bytes received from the peer, returning any messages that
+    /// they completed.
+    ///
+    /// Protocol control messages are handled by the session and are not
+    /// returned, except for user control events other than pings.
+    pub fn receive(&mut self, data: &[u8]) -> Result<Vec<ReceivedMessage>> {
+        self.input.extend_from_slice(data);
+        self.bytes_received += data.len() as u64;
+
+        let mut messages = Vec::new();
+        let mut position = 0;
+        loop {
+            let input = &self.input[position..];
+            match self.state {
+                State::ClientHandshake => {
+                    if input.len() < 1 + 2 * HANDSHAKE_LENGTH {
+                        break;
+                    }
+                    if input[0] != VERSION {
+                        return Err(Error::UnsupportedVersion(input[0]));
+                    }
+                    // C2 echoes S1.
+                    let s1 = input[1..1 + HANDSHAKE_LENGTH].to_vec();
+                    self.output.extend_from_slice(&s1);
+                    position += 1 + 2 * HANDSHAKE_LENGTH;
+                    self.finish_handshake();
+                }
+                State::ServerHandshake => {
+                    if input.len() < 1 + HANDSHAKE_LENGTH {
+                        break;
+                    }
+                    if input[0] != VERSION {
+                        return Err(Error::UnsupportedVersion(input[0]));
+                    }
+                    // S2 echoes C1.
+                    let c1 = input[1..1 + HANDSHAKE_LENGTH].to_vec();
+                    self.output.push(VERSION);
+                    self.output.extend_from_slice(&handshake_packet());
+                    self.output.extend_from_slice(&c1);
+                    position += 1 + HANDSHAKE_LENGTH;
+                    self.state = State::ServerAwaitingAck;
+                }
+                State::ServerAwaitingAck => {
+                    if input.len() < HANDSHAKE_LENGTH {
+                        break;
+                    }
+                    position += HANDSHAKE_LENGTH;
+                    self.finish_handshake();
+                }
+                State::Ready => match self.reader.read_chunk(input)? {
+                    Some((len, message)) => {
+                        position += len;
+                        if let Some(message) = message {
+                            let stream_id = message.stream_id;
+                            let timestamp = message.timestamp;
+                            let message = Message::parse(message.type_id, message.payload)?;
+                            if let Some(message) = self.handle_message(message)? {
+                                messages.push(ReceivedMessage {
+                                    stream_id,
+                                    timestamp,
+                                    message,
+                                });
+                            }
+                        }
+                    }
+                    None => break,
+                },
+            }
+        }
+        self.input.drain(..position);
+
+        if self.window_acknowledgement_size > 0
+            && self.bytes_received - self.last_acknowledgement
+                >= u64::from(self.window_acknowledgement_size)
+        {
+            self.last_acknowledgement = self.bytes_received;
+            self.send(
+                0,
+                &Message::Acknowledgement {
+                    // The sequence number wraps around every 4GB.
+                    sequence_number: self.bytes_received as u32,
+                },
+            )?;
+        }
+
+        Ok(messages)
+    }
+
+    fn finish_handshake(&mut self) {
+        self.state = State::Ready;
+        self.output.append(&mut self.pending);
+    }
+
+    /// Handle a protocol control message, or return any other message.
+    fn handle_message(&mut self, message: Message) -> Result<Option<Message>> {
+        match message {
+            Message::SetChunkSize(chunk_size) => self.reader.set_chunk_size(chunk_size as usize),
+            Message::Abort { chunk_stream_id } => self.reader.abort(chunk_stream_id),
+            Message::WindowAcknowledgementSize(size) => self.window_acknowledgement_size = size,
+            Message::Acknowledgement { .. } | Message::SetPeerBandwidth { .. } => {}
+            Message::UserControl(UserControlEvent::PingRequest(timestamp)) => self.send(
+                0,
+                &Message::UserControl(UserControlEvent::PingResponse(timestamp)),
+            )?,
+            Message::UserControl(UserControlEvent::PingResponse(_)) => {}
+            message => return Ok(Some(message)),
+        }
+        Ok(None)
+    }
+}
+
+/// Build a C1 or S1 packet: a timestamp, four zero bytes, and filler.
+fn handshake_packet() -> Vec<u8> {
+    let mut packet = vec![0; 8];
+    packet.extend((0..HANDSHAKE_LENGTH - 8).map(|i| (i * 7 + 3) as u8));
+    packet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Command;
+    use flash_lso::types::Value;
+
+    /// Move data between two sessions until neither has anything to say.
+    fn exchange(
+        client: &mut Session,
+        server: &mut Session,
+    ) -> (Vec<ReceivedMessage>, Vec<ReceivedMessage>) {
+        let mut client_messages = vec![];
+        let mut server_messages = vec![];
+        loop {
+            let to_server = client.take_output();
+            let to_client = server.take_output();
+            if to_server.is_empty() && to_client.is_empty() {
+                return (client_messages, server_messages);
+            }
+            server_messages.extend(server.receive(&to_server).unwrap());
+            client_messages.extend(client.receive(&to_client).unwrap());
+        }
+    }
+
+    #[test]
+    fn handshake_and_messages() {
+        let mut client = Session::client();
+        let mut server = Session::server();
+        let command = Message::Command(Command {
+            name: "connect".to_string(),
+            transaction_id: 1.0,
+            command_object: Value::Null,
+            arguments: vec![Value::String("x".repeat(500))],
+        });
+
+        // Sent before the handshake, and in chunks of a non-default size.
+        client.set_chunk_size(4096).unwrap();
+        client.send(0, &command).unwrap();
+        assert!(!client.is_ready());
+
+        let (_, server_messages) = exchange(&mut client, &mut server);
+        assert!(client.is_ready());
+        assert!(server.is_ready());
+        assert_eq!(
+            server_messages,
+            vec![ReceivedMessage {
+                stream_id: 0,
+                timestamp: 0,
+                message: command,
+            }]
+        );
+    }
+
+    #[test]
+    fn ping() {
+        let mut client = Session::client();
+        let mut server = Session::server();
+        exchange(&mut client, &mut server);
+
+        server
+            .send(0, &Message::UserControl(UserControlEvent::PingRequest(42)))
+            .unwrap();
+        let (client_messages, server_messages) = exchange(&mut client, &mut server);
+        assert!(client_messages.is_empty());
+        assert!(server_messages.is_empty());
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let mut client = Session::client();
+        let mut data = vec![6];
+        data.extend_from_slice(&[0; 2 * HANDSHAKE_LENGTH]);
+        assert_eq!(client.receive(&data), Err(Error::UnsupportedVersion(6)));
+    }
+}
@@ -0,0 +1,292 @@
+use crate::amf::{read_value, read_values, write_value};
+use crate::error::{Error, Result};
+use flash_lso::types::Value;
+
+/// A batch of events on a remote shared object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharedObjectMessage {
+    /// The name of the shared object.
+    pub name: String,
+
+    /// The version of the shared object that the events apply to. Each change
+    /// accepted by the server increases it by one.
+    pub version: u32,
+
+    /// Whether the shared object is kept by the server after every client
+    /// has released it.
+    pub persistent: bool,
+
+    pub events: Vec<SharedObjectEvent>,
+}
+
+/// An event on a remote shared object.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SharedObjectEvent {
+    /// The client starts using the shared object.
+    Use,
+
+    /// The client stops using the shared object.
+    Release,
+
+    /// The client asks the server to change a property.
+    RequestChange { name: String, value: Value },
+
+    /// The server tells the client that a property has changed.
+    Change { name: String, value: Value },
+
+    /// The server accepted a change that the client asked for.
+    Success { name: String },
+
+    /// A method call that is broadcast to every client of the shared object,
+    /// starting with the name of the method.
+    SendMessage(Vec<Value>),
+
+    /// The server reports an error or warning.
+    Status { code: String, level: String },
+
+    /// The server tells the client to delete every property before it sends
+    /// the current ones.
+    Clear,
+
+    /// The server tells the client that a property has been deleted.
+    Remove { name: String },
+
+    /// The client asks the server to delete a property.
+    RequestRemove { name: String },
+
+    /// The server accepted the client's `Use` event.
+    UseSuccess,
+}
+
+impl SharedObjectMessage {
+    const PERSISTENT: u32 = 2;
+
+    pub(crate) fn parse(mut data: &[u8]) -> Result<Self> {
+        let name = read_string(&mut data)?;
+        let version = read_u32(&mut data)?;
+        let flags = read_u32(&mut data)?;
+        // The other half of the flags is reserved.
+        read_u32(&mut data)?;
+
+        let mut events = Vec::new();
+        while !data.is_empty() {
+            let event_type = *data
+                .first()
+                .ok_or_else(|| Error::invalid_data("Truncated shared object event"))?;
+            data = &data[1..];
+            let length = read_u32(&mut data)? as usize;
+            let event_data = data
+                .get(..length)
+                .ok_or_else(|| Error::invalid_data("Truncated shared object event"))?;
+            data = &data[length..];
+            events.push(SharedObjectEvent::parse(event_type, event_data)?);
+        }
+
+        Ok(Self {
+            name,
+            version,
+            persistent: flags & Self::PERSISTENT != 0,
+            events,
+        })
+    }
+
+    /// Whether any of the values are AMF3 values, which must be sent in an
+    /// AMF3 shared object message.
+    pub(crate) fn uses_amf3(&self) -> bool {
+        self.events.iter().any(|event| match event {
+            SharedObjectEvent::RequestChange { value, .. }
+            | SharedObjectEvent::Change { value, .. } => matches!(value, Value::AMF3(_)),
+            SharedObjectEvent::SendMessage(values) => {
+                values.iter().any(|value| matches!(value, Value::AMF3(_)))
+            }
+            _ => false,
+        })
+    }
+
+    pub(crate) fn write(&self, output: &mut Vec<u8>) -> Result<()> {
+        write_string(output, &self.name)?;
+        output.extend_from_slice(&self.version.to_be_bytes());
+        let flags = if self.persistent { Self::PERSISTENT } else { 0 };
+        output.extend_from_slice(&flags.to_be_bytes());
+        output.extend_from_slice(&[0; 4]);
+
+        for event in &self.events {
+            let mut data = Vec::new();
+            event.write(&mut data)?;
+            let length = u32::try_from(data.len())
+                .map_err(|_| Error::invalid_data("Shared object event is too long"))?;
+            output.push(event.event_type());
+            output.extend_from_slice(&length.to_be_bytes());
+            output.extend_from_slice(&data);
+        }
+        Ok(())
+    }
+}
+
+impl SharedObjectEvent {
+    fn parse(event_type: u8, mut data: &[u8]) -> Result<Self> {
+        Ok(match event_type {
+            1 => Self::Use,
+            2 => Self::Release,
+            3 | 4 => {
+                let name = read_string(&mut data)?;
+                let (_, value) = read_value(data)?;
+                if event_type == 3 {
+                    Self::RequestChange { name, value }
+                } else {
+                    Self::Change { name, value }
+                }
+            }
+            5 => Self::Success {
+                name: read_string(&mut data)?,
+            },
+            6 => Self::SendMessage(read_values(data)?),
+            7 => Self::Status {
+                code: read_string(&mut data)?,
+                level: read_string(&mut data)?,
+            },
+            8 => Self::Clear,
+            9 => Self::Remove {
+                name: read_string(&mut data)?,
+            },
+            10 => Self::RequestRemove {
+                name: read_string(&mut data)?,
+            },
+            11 => Self::UseSuccess,
+            _ => {
+                return Err(Error::invalid_data(format!(
+                    "Unknown shared object event type {event_type}"
+                )))
+            }
+        })
+    }
+
+    fn event_type(&self) -> u8 {
+        match self {
+            Self::Use => 1,
+            Self::Release => 2,
+            Self::RequestChange { .. } => 3,
+            Self::Change { .. } => 4,
+            Self::Success { .. } => 5,
+            Self::SendMessage(_) => 6,
+            Self::Status { .. } => 7,
+            Self::Clear => 8,
+            Self::Remove { .. } => 9,
+            Self::RequestRemove { .. } => 10,
+            Self::UseSuccess => 11,
+        }
+    }
+
+    fn write(&self, output: &mut Vec<u8>) -> Result<()> {
+        match self {
+            Self::Use | Self::Release | Self::Clear | Self::UseSuccess => {}
+            Self::RequestChange { name, value } | Self::Change { name, value } => {
+                write_string(output, name)?;
+                write_value(output, value)?;
+            }
+            Self::Success { name } | Self::Remove { name } | Self::RequestRemove { name } => {
+                write_string(output, name)?
+            }
+            Self::SendMessage(values) => {
+                for value in values {
+                    write_value(output, value)?;
+                }
+            }
+            Self::Status { code, level } => {
+                write_string(output, code)?;
+                write_string(output, level)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_u32(data: &mut &[u8]) -> Result<u32> {
+    let bytes = data
+        .get(..4)
+        .ok_or_else(|| Error::invalid_data("Truncated shared object message"))?;
+    let value = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    *data = &data[4..];
+    Ok(value)
+}
+
+/// Read a string with a 16-bit length, as used for names in shared object
+/// messages.
+fn read_string(data: &mut &[u8]) -> Result<String> {
+    let length = match data {
+        [a, b, ..] => u16::from_be_bytes([*a, *b]) as usize,
+        _ => return Err(Error::invalid_data("Truncated shared object message")),
+    };
+    let bytes = data
+        .get(2..2 + length)
+        .ok_or_else(|| Error::invalid_data("Truncated shared object message"))?;
+    let string = String::from_utf8_lossy(bytes).into_owned();
+    *data = &data[2 + length..];
+    Ok(string)
+}
+
+fn write_string(output: &mut Vec<u8>, string: &str) -> Result<()> {
+    let length = u16::try_from(string.len())
+        .map_err(|_| Error::invalid_data("Shared object name is too long"))?;
+    output.extend_from_slice(&length.to_be_bytes());
+    output.extend_from_slice(string.as_bytes());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let message = SharedObjectMessage {
+            name: "chat".to_string(),
+            version: 3,
+            persistent: true,
+            events: vec![
+                SharedObjectEvent::Use,
+                SharedObjectEvent::Clear,
+                SharedObjectEvent::Change {
+                    name: "topic".to_string(),
+                    value: Value::String("hello".to_string()),
+                },
+                SharedObjectEvent::Success {
+                    name: "count".to_string(),
+                },
+                SharedObjectEvent::SendMessage(vec![
+                    Value::String("say".to_string()),
+                    Value::Number(1.0),
+                ]),
+                SharedObjectEvent::Status {
+                    code: "SharedObject.BadPersistence".to_string(),
+                    level: "error".to_string(),
+                },
+                SharedObjectEvent::RequestRemove {
+                    name: "topic".to_string(),
+                },
+                SharedObjectEvent::UseSuccess,
+            ],
+        };
+
+        let mut data = Vec::new();
+        message.write(&mut data).unwrap();
+        assert_eq!(SharedObjectMessage::parse(&data), Ok(message));
+    }
+
+    #[test]
+    fn truncated() {
+        let message = SharedObjectMessage {
+            name: "chat".to_string(),
+            version: 0,
+            persistent: false,
+            events: vec![SharedObjectEvent::Remove {
+                name: "topic".to_string(),
+            }],
+        };
+
+        let mut data = Vec::new();
+        message.write(&mut data).unwrap();
+        data.pop();
+        assert!(SharedObjectMessage::parse(&data).is_err());
+    }
+}
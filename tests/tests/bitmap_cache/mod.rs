@@ -0,0 +1,273 @@
+//! Checks when bitmap cached display objects are redrawn, by counting the
+//! offscreen renders of a software renderer while a movie changes them.
+
+use crate::set_logger;
+use gc_arena::MutationContext;
+use image::RgbaImage;
+use ruffle_core::backend::log::LogBackend;
+use ruffle_core::limits::ExecutionLimit;
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::PlayerBuilder;
+use ruffle_render::backend::{
+    Context3D, Context3DCommand, RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{Bitmap, BitmapHandle, BitmapSource, SyncHandle};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::filters::Filter;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render_software::SoftwareRenderBackend;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// A software renderer that counts how many times it renders offscreen,
+/// which is how cached surfaces are drawn.
+struct CountingRenderBackend {
+    inner: SoftwareRenderBackend,
+    offscreen_renders: usize,
+}
+
+impl RenderBackend for CountingRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.inner.viewport_dimensions()
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.inner.set_viewport_dimensions(dimensions)
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        self.inner.register_shape(shape, bitmap_source)
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        commands: CommandList,
+        quality: StageQuality,
+    ) -> Option<Box<dyn SyncHandle>> {
+        self.offscreen_renders += 1;
+        self.inner
+            .render_offscreen(handle, width, height, commands, quality)
+    }
+
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        self.inner.apply_filter(
+            source,
+            source_point,
+            source_size,
+            destination,
+            dest_point,
+            filter,
+        )
+    }
+
+    fn submit_frame(&mut self, clear: ruffle_core::Color, commands: CommandList) {
+        self.inner.submit_frame(clear, commands)
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        self.inner.register_bitmap(bitmap)
+    }
+
+    fn update_texture(
+        &mut self,
+        bitmap: &BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<(), Error> {
+        self.inner.update_texture(bitmap, width, height, rgba)
+    }
+
+    fn create_context3d(&mut self) -> Result<Box<dyn Context3D>, Error> {
+        self.inner.create_context3d()
+    }
+
+    fn context3d_present<'gc>(
+        &mut self,
+        context: &mut dyn Context3D,
+        commands: Vec<Context3DCommand<'gc>>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<(), Error> {
+        self.inner.context3d_present(context, commands, mc)
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        self.inner.debug_info()
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.inner.set_quality(quality)
+    }
+}
+
+struct StepLogBackend {
+    steps: Rc<RefCell<Vec<String>>>,
+}
+
+impl LogBackend for StepLogBackend {
+    fn avm_trace(&self, message: &str) {
+        self.steps.borrow_mut().push(message.to_string());
+    }
+}
+
+/// What is expected after each step of the movie.
+struct Step {
+    name: &'static str,
+
+    /// How many cached surfaces are drawn for this frame.
+    offscreen_renders: usize,
+
+    /// Pixels of the frame and the color that they should be close to.
+    pixels: &'static [((u32, u32), [u8; 3])],
+}
+
+const RED: [u8; 3] = [255, 0, 0];
+const GREEN: [u8; 3] = [0, 255, 0];
+const BLUE: [u8; 3] = [0, 0, 255];
+const WHITE: [u8; 3] = [255, 255, 255];
+
+const STEPS: &[Step] = &[
+    Step {
+        name: "setup",
+        // The red square, the masked rectangle, its mask and the blue square.
+        offscreen_renders: 4,
+        pixels: &[
+            ((20, 20), RED),
+            ((45, 20), WHITE),
+            // The mask's alpha is used, rather than its shape.
+            ((102, 20), GREEN),
+            ((178, 20), WHITE),
+            ((20, 70), BLUE),
+            ((70, 70), WHITE),
+        ],
+    },
+    Step {
+        name: "static",
+        offscreen_renders: 0,
+        pixels: &[((20, 20), RED), ((102, 20), GREEN), ((178, 20), WHITE)],
+    },
+    Step {
+        name: "move",
+        // Only the position of the surface changes.
+        offscreen_renders: 0,
+        pixels: &[((5, 20), WHITE), ((45, 20), RED)],
+    },
+    Step {
+        name: "child transform",
+        offscreen_renders: 1,
+        pixels: &[((12, 20), WHITE), ((52, 20), RED)],
+    },
+    Step {
+        name: "child graphics",
+        offscreen_renders: 1,
+        pixels: &[((30, 43), RED)],
+    },
+    Step {
+        name: "scale with cacheAsBitmapMatrix",
+        // The surface is drawn with `cacheAsBitmapMatrix`, and only stretched.
+        offscreen_renders: 0,
+        pixels: &[((20, 70), BLUE), ((70, 70), BLUE), ((90, 70), WHITE)],
+    },
+    Step {
+        name: "scale",
+        offscreen_renders: 1,
+        pixels: &[((25, 20), RED), ((90, 20), RED)],
+    },
+];
+
+/// How far apart a channel of a pixel may be from what's expected, which
+/// leaves room for anti-aliasing and the edges of the mask's gradient.
+const TOLERANCE: u8 = 16;
+
+fn check_pixels(step: &Step, image: &RgbaImage) -> Result<(), String> {
+    for &((x, y), expected) in step.pixels {
+        let pixel = image.get_pixel(x, y).0;
+        if pixel[..3]
+            .iter()
+            .zip(expected)
+            .any(|(actual, expected)| actual.abs_diff(expected) > TOLERANCE)
+        {
+            return Err(format!(
+                "{}: pixel ({x}, {y}) is {:?} but should be {expected:?}",
+                step.name,
+                &pixel[..3]
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn bitmap_cache() -> Result<(), libtest_mimic::Failed> {
+    set_logger();
+    let movie = SwfMovie::from_path(Path::new("tests/swfs/avm2/bitmap_cache/test.swf"), None)
+        .map_err(|e| e.to_string())?;
+    let width = movie.width().to_pixels() as u32;
+    let height = movie.height().to_pixels() as u32;
+    let steps = Rc::new(RefCell::new(vec![]));
+    let player = PlayerBuilder::new()
+        .with_log(StepLogBackend {
+            steps: steps.clone(),
+        })
+        .with_renderer(CountingRenderBackend {
+            inner: SoftwareRenderBackend::new(ViewportDimensions {
+                width,
+                height,
+                scale_factor: 1.0,
+            }),
+            offscreen_renders: 0,
+        })
+        .with_viewport_dimensions(width, height, 1.0)
+        .with_movie(movie)
+        .build();
+
+    for step in STEPS {
+        let mut player = player.lock().unwrap();
+        while !player.preload(&mut ExecutionLimit::exhausted()) {}
+        player.run_frame();
+        player.render();
+
+        let traced = std::mem::take(&mut *steps.borrow_mut());
+        if traced != [step.name] {
+            return Err(format!(
+                "expected step {:?}, but the movie ran {traced:?}",
+                step.name
+            )
+            .into());
+        }
+
+        let renderer = player
+            .renderer_mut()
+            .downcast_mut::<CountingRenderBackend>()
+            .expect("renderer should be a CountingRenderBackend");
+        let offscreen_renders = std::mem::take(&mut renderer.offscreen_renders);
+        if offscreen_renders != step.offscreen_renders {
+            return Err(format!(
+                "{}: {offscreen_renders} surfaces were drawn instead of {}",
+                step.name, step.offscreen_renders
+            )
+            .into());
+        }
+        check_pixels(step, &renderer.inner.capture_frame())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,285 @@
+//! Compares the filters of the wgpu backend with the software implementations
+//! in `ruffle_core::bitmap::filters`, which serve as their reference.
+
+use crate::set_logger;
+use crate::util::environment::WGPU;
+use ruffle_core::bitmap::bitmap_data::{BitmapData, Color};
+use ruffle_core::bitmap::filters::apply_filter;
+use ruffle_core::swf::{
+    BevelFilter, BevelFilterFlags, BlurFilter, BlurFilterFlags, ColorMatrixFilter,
+    ConvolutionFilter, ConvolutionFilterFlags, DropShadowFilter, DropShadowFilterFlags, Fixed16,
+    Fixed8, GlowFilter, GlowFilterFlags, GradientFilter, GradientFilterFlags, GradientRecord,
+};
+use ruffle_render::backend::RenderBackend;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle};
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+use ruffle_render_wgpu::backend::WgpuRenderBackend;
+use ruffle_render_wgpu::target::TextureTarget;
+use std::f64::consts::FRAC_PI_4;
+
+/// The width and height of the bitmaps that are filtered.
+const SIZE: u32 = 24;
+
+/// How far apart any channel of the two results may be.
+///
+/// The shaders work with floats and sample textures, while the software
+/// implementations round in slightly different places.
+const TOLERANCE: u8 = 3;
+
+/// A partly transparent square with a color gradient, in the middle of
+/// transparent pixels.
+fn source_bitmap() -> BitmapData<'static> {
+    bitmap(|x, y| {
+        if !(6..18).contains(&x) || !(6..18).contains(&y) {
+            return Color::argb(0, 0, 0, 0);
+        }
+        let alpha = if x == 6 || x == 17 || y == 6 || y == 17 {
+            128
+        } else {
+            255
+        };
+        Color::argb(alpha, (x * 10) as u8, (y * 10) as u8, 128)
+    })
+}
+
+/// A map that moves the pixels by a different amount all over the bitmap.
+fn displacement_map() -> BitmapData<'static> {
+    bitmap(|x, y| Color::argb(255, (x * 255 / SIZE) as u8, (y * 255 / SIZE) as u8, 0))
+}
+
+fn bitmap(color: impl Fn(u32, u32) -> Color) -> BitmapData<'static> {
+    let mut bitmap = BitmapData::default();
+    bitmap.init_pixels(SIZE, SIZE, true, 0);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            bitmap.set_pixel32(x, y, color(x, y).to_premultiplied_alpha(true));
+        }
+    }
+    bitmap
+}
+
+fn gradient_colors() -> Vec<GradientRecord> {
+    vec![
+        GradientRecord {
+            ratio: 0,
+            color: ruffle_core::Color::from_rgba(0x00FF0000),
+        },
+        GradientRecord {
+            ratio: 128,
+            color: ruffle_core::Color::from_rgba(0x8000FF00),
+        },
+        GradientRecord {
+            ratio: 255,
+            color: ruffle_core::Color::from_rgba(0xFF0000FF),
+        },
+    ]
+}
+
+fn filters(map: BitmapHandle) -> Vec<(&'static str, Filter)> {
+    let red = ruffle_core::Color::from_rgba(0xFFFF0000);
+    vec![
+        (
+            "color_matrix",
+            Filter::ColorMatrixFilter(ColorMatrixFilter {
+                matrix: [
+                    0.0, 0.0, 1.0, 0.0, 0.0, //
+                    0.0, 1.0, 0.0, 0.0, 20.0, //
+                    1.0, 0.0, 0.0, 0.0, 0.0, //
+                    0.0, 0.0, 0.0, 0.5, 0.0,
+                ],
+            }),
+        ),
+        (
+            "blur",
+            Filter::BlurFilter(BlurFilter {
+                blur_x: Fixed16::from_f64(4.0),
+                blur_y: Fixed16::from_f64(6.0),
+                flags: BlurFilterFlags::from_passes(2),
+            }),
+        ),
+        (
+            "glow",
+            Filter::GlowFilter(GlowFilter {
+                color: red,
+                blur_x: Fixed16::from_f64(4.0),
+                blur_y: Fixed16::from_f64(4.0),
+                strength: Fixed8::from_f64(2.0),
+                flags: GlowFilterFlags::from_passes(1) | GlowFilterFlags::COMPOSITE_SOURCE,
+            }),
+        ),
+        (
+            "inner_knockout_glow",
+            Filter::GlowFilter(GlowFilter {
+                color: red,
+                blur_x: Fixed16::from_f64(3.0),
+                blur_y: Fixed16::from_f64(3.0),
+                strength: Fixed8::ONE,
+                flags: GlowFilterFlags::from_passes(1)
+                    | GlowFilterFlags::INNER_GLOW
+                    | GlowFilterFlags::KNOCKOUT
+                    | GlowFilterFlags::COMPOSITE_SOURCE,
+            }),
+        ),
+        (
+            "drop_shadow",
+            Filter::DropShadowFilter(DropShadowFilter {
+                color: ruffle_core::Color::BLACK,
+                blur_x: Fixed16::from_f64(4.0),
+                blur_y: Fixed16::from_f64(4.0),
+                angle: Fixed16::from_f64(FRAC_PI_4),
+                distance: Fixed16::from_f64(4.0),
+                strength: Fixed8::ONE,
+                flags: DropShadowFilterFlags::from_passes(1)
+                    | DropShadowFilterFlags::COMPOSITE_SOURCE,
+            }),
+        ),
+        (
+            "bevel",
+            Filter::BevelFilter(BevelFilter {
+                shadow_color: ruffle_core::Color::BLACK,
+                highlight_color: ruffle_core::Color::WHITE,
+                blur_x: Fixed16::from_f64(3.0),
+                blur_y: Fixed16::from_f64(3.0),
+                angle: Fixed16::from_f64(FRAC_PI_4),
+                distance: Fixed16::from_f64(3.0),
+                strength: Fixed8::ONE,
+                flags: BevelFilterFlags::from_passes(1)
+                    | BevelFilterFlags::INNER_SHADOW
+                    | BevelFilterFlags::COMPOSITE_SOURCE,
+            }),
+        ),
+        (
+            "gradient_glow",
+            Filter::GradientGlowFilter(GradientFilter {
+                colors: gradient_colors(),
+                blur_x: Fixed16::from_f64(4.0),
+                blur_y: Fixed16::from_f64(4.0),
+                angle: Fixed16::from_f64(FRAC_PI_4),
+                distance: Fixed16::from_f64(2.0),
+                strength: Fixed8::ONE,
+                flags: GradientFilterFlags::from_passes(1) | GradientFilterFlags::COMPOSITE_SOURCE,
+            }),
+        ),
+        (
+            "gradient_bevel",
+            Filter::GradientBevelFilter(GradientFilter {
+                colors: gradient_colors(),
+                blur_x: Fixed16::from_f64(3.0),
+                blur_y: Fixed16::from_f64(3.0),
+                angle: Fixed16::from_f64(FRAC_PI_4),
+                distance: Fixed16::from_f64(3.0),
+                strength: Fixed8::ONE,
+                flags: GradientFilterFlags::from_passes(1)
+                    | GradientFilterFlags::INNER_SHADOW
+                    | GradientFilterFlags::COMPOSITE_SOURCE,
+            }),
+        ),
+        (
+            "convolution",
+            Filter::ConvolutionFilter(ConvolutionFilter {
+                num_matrix_rows: 3,
+                num_matrix_cols: 3,
+                matrix: [0.0, -1.0, 0.0, -1.0, 5.0, -1.0, 0.0, -1.0, 0.0]
+                    .into_iter()
+                    .map(Fixed16::from_f64)
+                    .collect(),
+                divisor: Fixed16::ONE,
+                bias: Fixed16::ZERO,
+                default_color: ruffle_core::Color::from_rgba(0),
+                flags: ConvolutionFilterFlags::CLAMP,
+            }),
+        ),
+        (
+            "displacement_map",
+            Filter::DisplacementMapFilter(DisplacementMapFilter {
+                color: ruffle_core::Color::from_rgba(0x8000FF00),
+                component_x: 1,
+                component_y: 2,
+                map_bitmap: Some(map),
+                map_point: (0, 0),
+                mode: DisplacementMapFilterMode::Color,
+                scale_x: 8.0,
+                scale_y: -6.0,
+            }),
+        ),
+    ]
+}
+
+fn register(
+    renderer: &mut dyn RenderBackend,
+    rgba: Vec<u8>,
+) -> Result<BitmapHandle, libtest_mimic::Failed> {
+    renderer
+        .register_bitmap(Bitmap::new(SIZE, SIZE, BitmapFormat::Rgba, rgba))
+        .map_err(|e| e.to_string().into())
+}
+
+pub fn wgpu_filters() -> Result<(), libtest_mimic::Failed> {
+    set_logger();
+    let descriptors = WGPU.clone().ok_or("wgpu is not available")?;
+    let target =
+        TextureTarget::new(&descriptors.device, (SIZE, SIZE)).map_err(|e| e.to_string())?;
+    let mut renderer = WgpuRenderBackend::new(descriptors, target).map_err(|e| e.to_string())?;
+
+    let source = source_bitmap();
+    let map = displacement_map();
+    let source_handle = register(&mut renderer, source.pixels_rgba())?;
+    let map_handle = register(&mut renderer, map.pixels_rgba())?;
+
+    let mut failures = vec![];
+    for (name, filter) in filters(map_handle) {
+        let expected: Vec<u8> = apply_filter(&source, (0, 0), (SIZE, SIZE), &filter, Some(&map))
+            .into_iter()
+            .flat_map(|color| [color.red(), color.green(), color.blue(), color.alpha()])
+            .collect();
+
+        let dest_handle = register(&mut renderer, vec![0; (SIZE * SIZE * 4) as usize])?;
+        let sync_handle = renderer
+            .apply_filter(
+                source_handle.clone(),
+                (0, 0),
+                (SIZE, SIZE),
+                dest_handle,
+                (0, 0),
+                filter,
+            )
+            .ok_or_else(|| format!("{name}: the filter isn't supported by wgpu"))?;
+        let mut actual = Vec::with_capacity(expected.len());
+        sync_handle.retrieve_offscreen_texture(Box::new(|rgba, row_stride| {
+            for row in rgba.chunks(row_stride as usize).take(SIZE as usize) {
+                actual.extend_from_slice(&row[..SIZE as usize * 4]);
+            }
+        }))?;
+
+        // Report the pixel that is the furthest away, if it's too far.
+        let worst = expected
+            .chunks(4)
+            .zip(actual.chunks(4))
+            .enumerate()
+            .map(|(i, (expected, actual))| {
+                let difference = expected
+                    .iter()
+                    .zip(actual)
+                    .map(|(a, b)| a.abs_diff(*b))
+                    .max()
+                    .unwrap_or_default();
+                (difference, i, expected, actual)
+            })
+            .max_by_key(|(difference, ..)| *difference);
+        if let Some((difference, i, expected, actual)) = worst {
+            if difference > TOLERANCE {
+                failures.push(format!(
+                    "{name}: pixel ({}, {}) is {actual:?} but should be {expected:?}",
+                    i as u32 % SIZE,
+                    i as u32 / SIZE,
+                ));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n").into())
+    }
+}
@@ -2,15 +2,20 @@
 //!
 //! Trace output can be compared with correct output from the official Flash Player.
 
+use crate::bitmap_cache::bitmap_cache;
 use crate::external_interface::tests::{external_interface_avm1, external_interface_avm2};
+use crate::filters::wgpu_filters;
 use crate::shared_object::{shared_object_avm1, shared_object_avm2};
 use anyhow::Context;
 use anyhow::Result;
 use libtest_mimic::{Arguments, Trial};
 use std::path::Path;
+use util::environment::WGPU;
 use util::test::Test;
 
+mod bitmap_cache;
 mod external_interface;
+mod filters;
 mod shared_object;
 mod util;
 
@@ -79,6 +84,7 @@ fn main() {
         .collect();
 
     // Manual tests here, since #[test] doesn't work once we use our own test harness
+    tests.push(Trial::test("bitmap_cache", bitmap_cache));
     tests.push(Trial::test("shared_object_avm1", shared_object_avm1));
     tests.push(Trial::test("shared_object_avm2", shared_object_avm2));
     tests.push(Trial::test(
@@ -89,6 +95,10 @@ fn main() {
         "external_interface_avm2",
         external_interface_avm2,
     ));
+    // Comparing filters needs a GPU, so don't bother creating one just to list tests.
+    tests.push(
+        Trial::test("wgpu_filters", wgpu_filters).with_ignored_flag(!args.list && WGPU.is_none()),
+    );
 
     tests.sort_unstable_by(|a, b| a.name().cmp(b.name()));
 
@@ -0,0 +1,88 @@
+package {
+	import flash.display.GradientType;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.geom.Matrix;
+
+	// Driven by the `bitmap_cache` test in `tests/tests/bitmap_cache`, which checks how
+	// often the cached surfaces are redrawn after each step traced here.
+	public class Test extends Sprite {
+		private var cached:Sprite = new Sprite();
+		private var child:Shape = new Shape();
+		private var matrixed:Sprite = new Sprite();
+		private var frame:int = 0;
+
+		public function Test() {
+			graphics.beginFill(0xFFFFFF);
+			graphics.drawRect(0, 0, 200, 100);
+
+			// A cached container with a red square.
+			child.graphics.beginFill(0xFF0000);
+			child.graphics.drawRect(0, 0, 40, 40);
+			cached.addChild(child);
+			cached.cacheAsBitmap = true;
+			addChild(cached);
+
+			// A green rectangle, with a cached mask that fades out to the right.
+			var masked:Sprite = new Sprite();
+			masked.graphics.beginFill(0x00FF00);
+			masked.graphics.drawRect(0, 0, 80, 40);
+			masked.x = 100;
+			masked.cacheAsBitmap = true;
+			addChild(masked);
+
+			var mask:Sprite = new Sprite();
+			var gradient:Matrix = new Matrix();
+			gradient.createGradientBox(80, 40);
+			mask.graphics.beginGradientFill(GradientType.LINEAR, [0, 0], [1, 0], [0, 255], gradient);
+			mask.graphics.drawRect(0, 0, 80, 40);
+			mask.x = 100;
+			mask.cacheAsBitmap = true;
+			addChild(mask);
+			masked.mask = mask;
+
+			// A blue square that's cached at half of its size.
+			matrixed.graphics.beginFill(0x0000FF);
+			matrixed.graphics.drawRect(0, 0, 40, 40);
+			matrixed.y = 50;
+			matrixed.cacheAsBitmapMatrix = new Matrix(0.5, 0, 0, 0.5);
+			matrixed.cacheAsBitmap = true;
+			addChild(matrixed);
+
+			trace("setup");
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frame++;
+			switch (frame) {
+				case 1:
+					trace("static");
+					break;
+				case 2:
+					cached.x += 10;
+					trace("move");
+					break;
+				case 3:
+					child.x += 5;
+					trace("child transform");
+					break;
+				case 4:
+					child.graphics.clear();
+					child.graphics.beginFill(0xFF0000);
+					child.graphics.drawRect(0, 0, 40, 45);
+					trace("child graphics");
+					break;
+				case 5:
+					matrixed.scaleX = 2;
+					trace("scale with cacheAsBitmapMatrix");
+					break;
+				case 6:
+					cached.scaleX = 2;
+					trace("scale");
+					break;
+			}
+		}
+	}
+}
@@ -0,0 +1,56 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.filters.BevelFilter;
+	import flash.filters.BitmapFilter;
+	import flash.filters.ConvolutionFilter;
+	import flash.filters.DisplacementMapFilter;
+	import flash.filters.DropShadowFilter;
+	import flash.filters.GlowFilter;
+	import flash.filters.GradientBevelFilter;
+	import flash.filters.GradientGlowFilter;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	// Applies every filter with an effect beyond blurring and color matrices
+	// to a red and blue square, and shows the results in a grid of 40x40 cells.
+	public class Test extends Sprite {
+		static const CELL_SIZE:uint = 40;
+		static const COLUMNS:uint = 6;
+
+		public function Test() {
+			var source:BitmapData = new BitmapData(CELL_SIZE, CELL_SIZE, true, 0);
+			source.fillRect(new Rectangle(10, 10, 20, 10), 0xFFFF0000);
+			source.fillRect(new Rectangle(10, 20, 20, 10), 0xFF0000FF);
+
+			// Moves the left half of the source 4 pixels right, and the right half 4 pixels left.
+			var map:BitmapData = new BitmapData(CELL_SIZE, CELL_SIZE, false, 0x008080);
+			map.fillRect(new Rectangle(20, 0, 20, CELL_SIZE), 0xFF8080);
+
+			var filters:Array = [
+				new GlowFilter(0x00FF00, 1, 5, 5, 2, 1, false, false),
+				new GlowFilter(0x00FF00, 1, 5, 5, 2, 1, true, false),
+				new DropShadowFilter(4, 45, 0x000000, 1, 5, 5, 1, 1, false, false, false),
+				new DropShadowFilter(4, 45, 0x000000, 1, 5, 5, 1, 1, true, false, false),
+				new BevelFilter(4, 45, 0xFFFFFF, 1, 0x000000, 1, 5, 5, 1, 1, "inner", false),
+				new BevelFilter(4, 45, 0xFFFF00, 1, 0x00FFFF, 1, 5, 5, 1, 1, "full", true),
+				new GradientGlowFilter(4, 45, [0xFFFF00, 0xFF00FF], [0, 1], [0, 255], 5, 5, 2, 1, "outer", false),
+				new GradientBevelFilter(4, 45, [0xFFFFFF, 0xFF0000, 0x000000], [1, 0, 1], [0, 128, 255], 5, 5, 1, 1, "inner", false),
+				new ConvolutionFilter(3, 3, [-1, -1, -1, -1, 8, -1, -1, -1, -1], 1, 0, false, true),
+				new ConvolutionFilter(3, 3, [1, 1, 1, 1, 1, 1, 1, 1, 1], 9, 0, false, false, 0xFF00FF, 1),
+				new DisplacementMapFilter(map, new Point(0, 0), 1, 2, 8, 8, "color", 0xFFFF00, 1),
+				new GlowFilter(0x000080, 1, 4, 4, 1, 3, false, true)
+			];
+
+			for (var i:uint = 0; i < filters.length; i++) {
+				var result:BitmapData = new BitmapData(CELL_SIZE, CELL_SIZE, true, 0);
+				result.applyFilter(source, source.rect, new Point(0, 0), filters[i] as BitmapFilter);
+				var bitmap:Bitmap = new Bitmap(result);
+				bitmap.x = (i % COLUMNS) * CELL_SIZE;
+				bitmap.y = uint(i / COLUMNS) * CELL_SIZE;
+				addChild(bitmap);
+			}
+		}
+	}
+}
@@ -0,0 +1,59 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.filters.BevelFilter;
+	import flash.filters.BitmapFilter;
+	import flash.filters.ConvolutionFilter;
+	import flash.filters.DisplacementMapFilter;
+	import flash.filters.DropShadowFilter;
+	import flash.filters.GlowFilter;
+	import flash.filters.GradientBevelFilter;
+	import flash.filters.GradientGlowFilter;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	// Applies filters to a red square with a blue center, and traces the
+	// middle row of each result.
+	public class Test extends Sprite {
+		public function Test() {
+			var source:BitmapData = new BitmapData(9, 5, true, 0);
+			source.fillRect(new Rectangle(3, 1, 3, 3), 0xFFFF0000);
+			source.setPixel32(4, 2, 0xFF0000FF);
+
+			// Moves everything 4 pixels right, so that the left edge samples from outside of the source.
+			var map:BitmapData = new BitmapData(9, 5, false, 0x008080);
+
+			test(source, source.rect, "GlowFilter", new GlowFilter(0x00FF00, 1, 3, 3, 1, 1));
+			test(source, source.rect, "GlowFilter quality 2 strength 2", new GlowFilter(0x00FF00, 0.5, 3, 3, 2, 2));
+			test(source, source.rect, "GlowFilter inner", new GlowFilter(0x00FF00, 1, 3, 3, 1, 1, true));
+			test(source, source.rect, "GlowFilter knockout", new GlowFilter(0x00FF00, 1, 3, 3, 1, 1, false, true));
+			test(source, source.rect, "DropShadowFilter", new DropShadowFilter(2, 0, 0x000000, 1, 3, 3));
+			test(source, source.rect, "DropShadowFilter hideObject", new DropShadowFilter(2, 0, 0x000000, 1, 3, 3, 1, 1, false, false, true));
+			test(source, source.rect, "BevelFilter inner", new BevelFilter(1, 0, 0xFFFFFF, 1, 0x000000, 1, 3, 3, 1, 1, "inner"));
+			test(source, source.rect, "BevelFilter outer", new BevelFilter(1, 0, 0xFFFFFF, 1, 0x000000, 1, 3, 3, 1, 1, "outer"));
+			test(source, source.rect, "GradientGlowFilter outer", new GradientGlowFilter(0, 0, [0xFF0000, 0x0000FF], [0, 1], [0, 255], 3, 3, 1, 1, "outer"));
+			test(source, source.rect, "GradientBevelFilter full", new GradientBevelFilter(1, 0, [0xFFFFFF, 0x000000, 0x000000], [1, 0, 1], [0, 128, 255], 3, 3, 1, 1, "full"));
+			test(source, source.rect, "ConvolutionFilter clamp", new ConvolutionFilter(3, 1, [1, 0, -1], 1, 128, false, true));
+			test(source, source.rect, "ConvolutionFilter preserveAlpha", new ConvolutionFilter(3, 1, [1, 1, 1], 3, 0, true, false, 0xFFFF00, 1));
+			test(source, source.rect, "DisplacementMapFilter wrap", new DisplacementMapFilter(map, new Point(0, 0), 1, 2, 8, 8, "wrap"));
+			test(source, source.rect, "DisplacementMapFilter clamp", new DisplacementMapFilter(map, new Point(0, 0), 1, 2, 8, 8, "clamp"));
+			test(source, source.rect, "DisplacementMapFilter ignore", new DisplacementMapFilter(map, new Point(0, 0), 1, 2, 8, 8, "ignore"));
+			test(source, source.rect, "DisplacementMapFilter color", new DisplacementMapFilter(map, new Point(0, 0), 1, 2, 8, 8, "color", 0xFFFF00, 1));
+
+			// A glow of part of the source, drawn 1 pixel right and down of the top left.
+			test(source, new Rectangle(2, 1, 5, 3), "GlowFilter from (2, 1, 5, 3) to (1, 1)", new GlowFilter(0x00FF00, 1, 3, 3, 1, 1), new Point(1, 1));
+		}
+
+		private function test(source:BitmapData, sourceRect:Rectangle, name:String, filter:BitmapFilter, destPoint:Point = null):void {
+			var dest:BitmapData = new BitmapData(source.width, source.height, true, 0);
+			dest.applyFilter(source, sourceRect, destPoint || new Point(0, 0), filter);
+
+			var row:Array = [];
+			for (var x:int = 0; x < dest.width; x++) {
+				row.push(dest.getPixel32(x, 2).toString(16));
+			}
+			trace("// " + name);
+			trace(row.join(" "));
+		}
+	}
+}
@@ -0,0 +1,40 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.filters.BevelFilter;
+	import flash.filters.BitmapFilter;
+	import flash.filters.BlurFilter;
+	import flash.filters.ColorMatrixFilter;
+	import flash.filters.ConvolutionFilter;
+	import flash.filters.DropShadowFilter;
+	import flash.filters.GlowFilter;
+	import flash.filters.GradientBevelFilter;
+	import flash.filters.GradientGlowFilter;
+	import flash.geom.Rectangle;
+
+	public class Test extends Sprite {
+		private var bitmapData:BitmapData = new BitmapData(100, 100, true, 0);
+
+		public function Test() {
+			test("BlurFilter", new BlurFilter());
+			test("BlurFilter quality 3", new BlurFilter(4, 4, 3));
+			test("BlurFilter 0x10 quality 2", new BlurFilter(0, 10, 2));
+			test("GlowFilter", new GlowFilter());
+			test("GlowFilter inner", new GlowFilter(0xFF0000, 1, 6, 6, 2, 1, true));
+			test("DropShadowFilter", new DropShadowFilter());
+			test("DropShadowFilter 225 degrees", new DropShadowFilter(4, 225));
+			test("DropShadowFilter inner", new DropShadowFilter(4, 45, 0, 1, 4, 4, 1, 1, true));
+			test("BevelFilter", new BevelFilter());
+			test("BevelFilter outer", new BevelFilter(4, 45, 0xFFFFFF, 1, 0, 1, 4, 4, 1, 1, "outer"));
+			test("GradientGlowFilter outer", new GradientGlowFilter(0, 45, [0xFF0000, 0x0000FF], [0, 1], [0, 255], 4, 4, 1, 1, "outer"));
+			test("GradientBevelFilter full", new GradientBevelFilter(4, 45, [0xFFFFFF, 0x000000, 0x000000], [1, 0, 1], [0, 128, 255], 4, 4, 1, 1, "full"));
+			test("ColorMatrixFilter", new ColorMatrixFilter());
+			test("ConvolutionFilter", new ConvolutionFilter(3, 3, [1, 1, 1, 1, 1, 1, 1, 1, 1], 9));
+		}
+
+		private function test(name:String, filter:BitmapFilter):void {
+			trace("// " + name);
+			trace(bitmapData.generateFilterRect(new Rectangle(10, 20, 30, 40), filter));
+		}
+	}
+}
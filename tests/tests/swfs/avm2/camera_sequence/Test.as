@@ -0,0 +1,60 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.events.ActivityEvent;
+	import flash.events.Event;
+	import flash.events.StatusEvent;
+	import flash.media.Camera;
+
+	// The camera plays back the images in `camera/` at 15 fps: black, then
+	// red on the left half twice, and then green in the top right quarter as
+	// well.
+	public class Test extends Sprite {
+		private var camera:Camera;
+		private var lastActivityLevel:Number;
+
+		public function Test() {
+			trace("names: " + Camera.names);
+			camera = Camera.getCamera();
+			trace("name: " + camera.name);
+			trace("index: " + camera.index);
+			trace("muted: " + camera.muted);
+			trace("default mode: " + camera.width + "x" + camera.height + " at " + camera.fps + " fps");
+			trace("default motionLevel: " + camera.motionLevel + ", motionTimeout: " + camera.motionTimeout);
+
+			camera.setMode(8, 8, 15);
+			camera.setMotionLevel(40, 100);
+			trace("mode: " + camera.width + "x" + camera.height + " at " + camera.fps + " fps");
+			trace("motionLevel: " + camera.motionLevel + ", motionTimeout: " + camera.motionTimeout);
+			trace("activityLevel: " + camera.activityLevel);
+			lastActivityLevel = camera.activityLevel;
+
+			trace("getCamera again: " + (Camera.getCamera() === camera));
+			trace("getCamera(\"1\"): " + Camera.getCamera("1"));
+
+			camera.addEventListener(StatusEvent.STATUS, function(event:StatusEvent):void {
+				trace("status: " + event.code + " (" + event.level + ")");
+			});
+			camera.addEventListener(ActivityEvent.ACTIVITY, function(event:ActivityEvent):void {
+				trace("activity: " + event.activating + ", activityLevel: " + camera.activityLevel);
+			});
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		// Report each change in the activity level, along with what the camera
+		// is seeing.
+		private function onEnterFrame(event:Event):void {
+			if (camera.activityLevel == lastActivityLevel) {
+				return;
+			}
+			lastActivityLevel = camera.activityLevel;
+
+			var bitmapData:BitmapData = new BitmapData(8, 8, false, 0x0000FF);
+			camera.drawToBitmapData(bitmapData);
+			trace("activityLevel: " + camera.activityLevel +
+				", pixels: " + bitmapData.getPixel32(0, 0).toString(16) +
+				" " + bitmapData.getPixel32(7, 0).toString(16) +
+				" " + bitmapData.getPixel32(7, 7).toString(16));
+		}
+	}
+}
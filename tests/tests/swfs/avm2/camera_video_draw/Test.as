@@ -0,0 +1,37 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.media.Camera;
+	import flash.media.Video;
+
+	// Shows the camera in a `Video` on the left, and a copy of the video made
+	// with `BitmapData.draw` on the right, both scaled up 4 times.
+	public class Test extends Sprite {
+		private var video:Video;
+		private var bitmapData:BitmapData;
+
+		public function Test() {
+			var camera:Camera = Camera.getCamera();
+			camera.setMode(8, 8, 15);
+
+			video = new Video(8, 8);
+			video.attachCamera(camera);
+			video.scaleX = video.scaleY = 4;
+			addChild(video);
+
+			bitmapData = new BitmapData(8, 8, false, 0x0000FF);
+			var bitmap:Bitmap = new Bitmap(bitmapData);
+			bitmap.x = 40;
+			bitmap.scaleX = bitmap.scaleY = 4;
+			addChild(bitmap);
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			bitmapData.draw(video);
+		}
+	}
+}
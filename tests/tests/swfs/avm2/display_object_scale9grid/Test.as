@@ -0,0 +1,61 @@
+package {
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.geom.Rectangle;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var sprite:Sprite = new Sprite();
+			trace("// empty sprite");
+			trace(sprite.scale9Grid);
+			setGrid(sprite, new Rectangle(0, 0, 0, 0));
+
+			trace("// drawn sprite");
+			sprite.graphics.beginFill(0xFF0000);
+			sprite.graphics.drawRect(0, 0, 100, 50);
+			sprite.graphics.endFill();
+			setGrid(sprite, new Rectangle(10, 10, 80, 30));
+
+			trace("// the grid is a copy");
+			var grid:Rectangle = sprite.scale9Grid;
+			grid.x = 20;
+			trace(sprite.scale9Grid);
+
+			trace("// coordinates are rounded to pixels");
+			setGrid(sprite, new Rectangle(10.4, 10.6, 79.2, 29.5));
+
+			trace("// out of bounds");
+			setGrid(sprite, new Rectangle(-10, 10, 80, 30));
+			setGrid(sprite, new Rectangle(10, 10, 100, 30));
+			setGrid(sprite, new Rectangle(10, 30, 80, 30));
+
+			trace("// the bounds include children");
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(0x00FF00);
+			shape.graphics.drawRect(0, 0, 10, 10);
+			shape.graphics.endFill();
+			shape.x = 100;
+			shape.y = 50;
+			sprite.addChild(shape);
+			setGrid(sprite, new Rectangle(10, 10, 90, 45));
+
+			trace("// scaling keeps the size of the bounds");
+			sprite.scaleX = 2;
+			sprite.scaleY = 3;
+			trace(sprite.width, sprite.height);
+
+			trace("// null removes the grid");
+			sprite.scale9Grid = null;
+			trace(sprite.scale9Grid);
+		}
+
+		private function setGrid(sprite:Sprite, grid:Rectangle):void {
+			try {
+				sprite.scale9Grid = grid;
+			} catch (e:Error) {
+				trace(e.name + ": " + e.errorID);
+			}
+			trace(sprite.scale9Grid);
+		}
+	}
+}
@@ -0,0 +1,92 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.geom.PerspectiveProjection;
+	import flash.geom.Point;
+	import flash.geom.Vector3D;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var projection:PerspectiveProjection = new PerspectiveProjection();
+			trace("// new PerspectiveProjection()");
+			trace("fieldOfView: " + projection.fieldOfView);
+			trace("projectionCenter: " + projection.projectionCenter);
+			trace("focalLength: " + round(projection.focalLength));
+			projection.focalLength = 250;
+			trace("fieldOfView for focalLength 250: " + round(projection.fieldOfView));
+			try {
+				projection.fieldOfView = 180;
+			} catch (e:ArgumentError) {
+				trace("fieldOfView = 180: " + e.errorID);
+			}
+
+			var root:PerspectiveProjection = this.transform.perspectiveProjection;
+			trace("// root perspectiveProjection");
+			trace("fieldOfView: " + root.fieldOfView);
+			trace("projectionCenter: " + root.projectionCenter);
+			trace("focalLength: " + round(root.focalLength));
+
+			var sprite:Sprite = new Sprite();
+			sprite.x = 10;
+			sprite.y = 20;
+			addChild(sprite);
+			trace("// 2D sprite");
+			trace("perspectiveProjection: " + sprite.transform.perspectiveProjection);
+			trace("matrix: " + sprite.transform.matrix);
+			trace("matrix3D: " + sprite.transform.matrix3D);
+			trace("local3DToGlobal: " + point(sprite.local3DToGlobal(new Vector3D(5, 5, 0))));
+
+			var focalLength:Number = root.focalLength;
+			sprite.z = focalLength;
+			trace("// sprite at z = focalLength");
+			trace("z: " + round(sprite.z));
+			trace("matrix: " + sprite.transform.matrix);
+			trace("matrix3D position: " + vector(sprite.transform.matrix3D.position));
+			trace("local3DToGlobal center: " + point(sprite.local3DToGlobal(new Vector3D(40, 30, 0))));
+			trace("local3DToGlobal corner: " + point(sprite.local3DToGlobal(new Vector3D(-10, -20, 0))));
+			trace("local3DToGlobal behind the viewer: " + sprite.local3DToGlobal(new Vector3D(0, 0, -3 * focalLength)));
+			trace("globalToLocal3D: " + vector(sprite.globalToLocal3D(new Point(0, 0))));
+
+			sprite.z = 0;
+			sprite.rotationX = 90;
+			trace("// sprite with rotationX = 90");
+			trace("rotationX: " + sprite.rotationX);
+			trace("local3DToGlobal: " + point(sprite.local3DToGlobal(new Vector3D(0, 10, 0))));
+			trace("globalToLocal3D: " + vector(sprite.globalToLocal3D(new Point(0, 0))));
+
+			sprite.rotationX = 0;
+			sprite.rotationY = 180;
+			sprite.scaleZ = 2;
+			trace("// sprite with rotationY = 180");
+			trace("rotationY: " + sprite.rotationY);
+			trace("scaleZ: " + sprite.scaleZ);
+			trace("local3DToGlobal: " + point(sprite.local3DToGlobal(new Vector3D(5, 0, 0))));
+
+			var container:Sprite = new Sprite();
+			addChild(container);
+			var custom:PerspectiveProjection = new PerspectiveProjection();
+			custom.projectionCenter = new Point(0, 0);
+			container.transform.perspectiveProjection = custom;
+			trace("// custom perspectiveProjection");
+			trace("projectionCenter: " + container.transform.perspectiveProjection.projectionCenter);
+			var child:Sprite = new Sprite();
+			child.z = container.transform.perspectiveProjection.focalLength;
+			container.addChild(child);
+			trace("local3DToGlobal: " + point(child.local3DToGlobal(new Vector3D(40, 20, 0))));
+			container.transform.perspectiveProjection = null;
+			trace("after removing: " + container.transform.perspectiveProjection);
+		}
+
+		static function round(value:Number):Number {
+			return Math.round(value * 1000) / 1000;
+		}
+
+		static function point(p:Point):String {
+			return "(" + round(p.x) + ", " + round(p.y) + ")";
+		}
+
+		static function vector(v:Vector3D):String {
+			return "(" + round(v.x) + ", " + round(v.y) + ", " + round(v.z) + ")";
+		}
+	}
+}
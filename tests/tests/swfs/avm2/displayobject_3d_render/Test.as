@@ -0,0 +1,48 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var focalLength:Number = this.transform.perspectiveProjection.focalLength;
+
+			var shape:Shape = square();
+			shape.z = focalLength;
+			trace("// z = focalLength");
+			sample(shape, [[30, 30], [70, 70], [50, 50], [20, 20], [80, 80], [20, 50]]);
+
+			shape = square();
+			shape.rotationX = 60;
+			trace("// rotationX = 60");
+			sample(shape, [[50, 10], [10, 10], [98, 10], [50, 45], [5, 45], [50, 60]]);
+
+			shape = square();
+			shape.x = 50;
+			shape.rotationY = 180;
+			trace("// rotationY = 180");
+			sample(shape, [[25, 50], [45, 5], [75, 50]]);
+		}
+
+		static function square():Shape {
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawRect(0, 0, 100, 100);
+			shape.graphics.endFill();
+			return shape;
+		}
+
+		static function sample(shape:Shape, points:Array):void {
+			var container:Sprite = new Sprite();
+			container.addChild(shape);
+			var target:BitmapData = new BitmapData(100, 100, true, 0);
+			target.draw(container);
+			for each (var point:Array in points) {
+				var alpha:uint = target.getPixel32(point[0], point[1]) >>> 24;
+				var state:String = alpha == 0xFF ? "filled" : (alpha == 0 ? "empty" : "partial");
+				trace(point[0] + "," + point[1] + ": " + state);
+			}
+		}
+	}
+}
@@ -0,0 +1,59 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shader;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		// A blend shader that multiplies the content with the background.
+		static var MULTIPLY:Array = [
+				165, 1, 0, 0, 0, 164, 8, 0, 77, 117, 108, 116, 105, 112, 108, 121,
+				161, 1, 2, 0, 0, 12, 95, 79, 117, 116, 67, 111, 111, 114, 100, 0,
+				163, 0, 4, 98, 97, 99, 107, 103, 114, 111, 117, 110, 100, 0, 163, 1,
+				4, 115, 114, 99, 0, 161, 2, 4, 1, 0, 15, 100, 115, 116, 0, 48,
+				1, 0, 241, 0, 0, 16, 0, 48, 2, 0, 241, 0, 0, 16, 1, 3,
+				1, 0, 243, 2, 0, 27, 0
+		];
+
+		public function Test() {
+			var container:Sprite = new Sprite();
+			container.addChild(rect(0xFF0000, 0, 20));
+			var content:Shape = rect(0x808080, 10, 10);
+			container.addChild(content);
+
+			trace("blendMode: " + content.blendMode);
+			content.blendShader = new Shader(bytes(MULTIPLY));
+			trace("blendMode with blendShader: " + content.blendMode);
+
+			var target:BitmapData = new BitmapData(20, 10, true, 0);
+			target.draw(container);
+			trace("background: " + target.getPixel32(5, 5).toString(16));
+			trace("blended: " + target.getPixel32(15, 5).toString(16));
+
+			content.blendMode = "normal";
+			trace("blendMode after reset: " + content.blendMode);
+			target.fillRect(target.rect, 0);
+			target.draw(container);
+			trace("unblended: " + target.getPixel32(15, 5).toString(16));
+		}
+
+		static function rect(color:uint, x:Number, width:Number):Shape {
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(color);
+			shape.graphics.drawRect(x, 0, width, 10);
+			shape.graphics.endFill();
+			return shape;
+		}
+
+		static function bytes(values:Array):ByteArray {
+			var bytes:ByteArray = new ByteArray();
+			for each (var value:int in values) {
+				bytes.writeByte(value);
+			}
+			bytes.position = 0;
+			return bytes;
+		}
+	}
+}
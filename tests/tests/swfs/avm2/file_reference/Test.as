@@ -0,0 +1,75 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
+    import flash.net.FileFilter;
+    import flash.net.FileReference;
+
+    public class Test extends MovieClip {
+        private var opened:FileReference = new FileReference();
+        private var saved:FileReference = new FileReference();
+
+        public function Test() {
+            listen(opened, "opened");
+            listen(saved, "saved");
+
+            // The first dialog chooses `input.txt`.
+            trace("opened.browse(): " + opened.browse([new FileFilter("Text", "*.txt")]));
+        }
+
+        private function listen(file:FileReference, name:String):void {
+            for each (var type:String in [Event.SELECT, Event.CANCEL, Event.OPEN, Event.COMPLETE]) {
+                file.addEventListener(type, function(e:Event):void {
+                    trace(name + ": " + e.type);
+                    next(file, e.type);
+                });
+            }
+            file.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                trace(name + ": " + e.type + " " + e.bytesLoaded + "/" + e.bytesTotal);
+            });
+            file.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(name + ": " + e.type + " " + e.text);
+            });
+        }
+
+        private var step:int = 0;
+
+        private function next(file:FileReference, type:String):void {
+            if (type != Event.SELECT && type != Event.CANCEL && type != Event.COMPLETE) {
+                return;
+            }
+
+            step++;
+            switch (step) {
+                case 1:
+                    trace("name: " + file.name + ", size: " + file.size + ", type: " + file.type);
+                    file.load();
+                    break;
+                case 2:
+                    trace("data: " + file.data.readUTFBytes(file.data.length));
+                    // The second dialog is cancelled.
+                    trace("opened.browse(): " + file.browse());
+                    break;
+                case 3:
+                    // The third dialog chooses `saved.txt`.
+                    saved.save("Saved text", "saved.txt");
+                    break;
+                case 4:
+                    trace("name: " + file.name);
+                    break;
+                case 5:
+                    file.load();
+                    break;
+                case 6:
+                    trace("data: " + file.data.readUTFBytes(file.data.length));
+                    // The fourth dialog is cancelled.
+                    file.save("Not saved");
+                    break;
+                case 7:
+                    trace("Done");
+                    break;
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+package {
+	import flash.display.GraphicsEndFill;
+	import flash.display.GraphicsPath;
+	import flash.display.GraphicsSolidFill;
+	import flash.display.GraphicsStroke;
+	import flash.display.IGraphicsData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.TriangleCulling;
+	import flash.utils.getQualifiedClassName;
+
+	public class Test extends MovieClip {
+		// Two squares drawn in the same direction, the second inside the first.
+		static const SQUARES_COMMANDS:Vector.<int> = Vector.<int>([1, 2, 2, 2, 2, 1, 2, 2, 2, 2]);
+		static const SQUARES_DATA:Vector.<Number> = Vector.<Number>([
+			0, 0, 100, 0, 100, 100, 0, 100, 0, 0,
+			25, 25, 75, 25, 75, 75, 25, 75, 25, 25
+		]);
+
+		public function Test() {
+			testDrawPath();
+			testWindingDoesNotLeak();
+			testCulling();
+			testIndices();
+			testReadGraphicsData();
+		}
+
+		function addShape():Shape {
+			var shape:Shape = new Shape();
+			addChild(shape);
+			return shape;
+		}
+
+		function testDrawPath():void {
+			trace("// drawPath");
+			var shape:Shape = addShape();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawPath(SQUARES_COMMANDS, SQUARES_DATA);
+			shape.graphics.endFill();
+			trace("default, outer: " + shape.hitTestPoint(10, 10, true));
+			trace("default, inner: " + shape.hitTestPoint(50, 50, true));
+
+			shape.graphics.clear();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawPath(SQUARES_COMMANDS, SQUARES_DATA, "evenOdd");
+			shape.graphics.endFill();
+			trace("evenOdd, outer: " + shape.hitTestPoint(10, 10, true));
+			trace("evenOdd, inner: " + shape.hitTestPoint(50, 50, true));
+
+			shape.graphics.clear();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawPath(SQUARES_COMMANDS, SQUARES_DATA, "nonZero");
+			shape.graphics.endFill();
+			trace("nonZero, outer: " + shape.hitTestPoint(10, 10, true));
+			trace("nonZero, inner: " + shape.hitTestPoint(50, 50, true));
+			removeChild(shape);
+		}
+
+		function testWindingDoesNotLeak():void {
+			trace("// winding rule of later fills");
+			var shape:Shape = addShape();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawPath(SQUARES_COMMANDS, SQUARES_DATA, "nonZero");
+			shape.graphics.beginFill(0x0000FF);
+			shape.graphics.drawRect(100, 0, 100, 100);
+			shape.graphics.drawRect(125, 25, 50, 50);
+			shape.graphics.endFill();
+			trace("nonZero path, inner: " + shape.hitTestPoint(50, 50, true));
+			trace("later fill, outer: " + shape.hitTestPoint(110, 10, true));
+			trace("later fill, inner: " + shape.hitTestPoint(150, 50, true));
+			removeChild(shape);
+		}
+
+		function testCulling():void {
+			trace("// drawTriangles culling");
+			// The first triangle has a positive normal, the second a negative one.
+			var positive:Vector.<Number> = Vector.<Number>([0, 0, 100, 0, 0, 100]);
+			var negative:Vector.<Number> = Vector.<Number>([100, 0, 0, 100, 100, 100]);
+			var shape:Shape = addShape();
+			for each (var culling:String in [TriangleCulling.NONE, TriangleCulling.POSITIVE, TriangleCulling.NEGATIVE]) {
+				shape.graphics.clear();
+				shape.graphics.beginFill(0x00FF00);
+				shape.graphics.drawTriangles(positive, null, null, culling);
+				shape.graphics.drawTriangles(negative, null, null, culling);
+				shape.graphics.endFill();
+				trace(culling + ", positive: " + shape.hitTestPoint(10, 10, true));
+				trace(culling + ", negative: " + shape.hitTestPoint(90, 90, true));
+			}
+
+			try {
+				shape.graphics.drawTriangles(positive, null, null, "sideways");
+			} catch (e:Error) {
+				trace("invalid culling: " + e.errorID);
+			}
+			removeChild(shape);
+		}
+
+		function testIndices():void {
+			trace("// drawTriangles indices");
+			var shape:Shape = addShape();
+			shape.graphics.beginFill(0x00FF00);
+			shape.graphics.drawTriangles(
+				Vector.<Number>([0, 0, 100, 0, 100, 100, 0, 100]),
+				Vector.<int>([0, 1, 3, 1, 2, 3])
+			);
+			shape.graphics.endFill();
+			trace("first: " + shape.hitTestPoint(10, 10, true));
+			trace("second: " + shape.hitTestPoint(90, 90, true));
+			trace("outside: " + shape.hitTestPoint(150, 50, true));
+			trace("width: " + shape.width + ", height: " + shape.height);
+			removeChild(shape);
+		}
+
+		function describe(data:IGraphicsData):void {
+			var name:String = getQualifiedClassName(data);
+			if (data is GraphicsSolidFill) {
+				var fill:GraphicsSolidFill = data as GraphicsSolidFill;
+				trace(name + " color=" + fill.color.toString(16) + " alpha=" + fill.alpha);
+			} else if (data is GraphicsPath) {
+				var path:GraphicsPath = data as GraphicsPath;
+				trace(name + " commands=" + path.commands + " data=" + path.data + " winding=" + path.winding);
+			} else if (data is GraphicsStroke) {
+				var stroke:GraphicsStroke = data as GraphicsStroke;
+				var strokeFill:GraphicsSolidFill = stroke.fill as GraphicsSolidFill;
+				trace(name + " thickness=" + stroke.thickness + " fill=" + (strokeFill ? strokeFill.color.toString(16) : "null"));
+			} else {
+				trace(name);
+			}
+		}
+
+		function testReadGraphicsData():void {
+			trace("// readGraphicsData");
+			var shape:Shape = addShape();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawPath(SQUARES_COMMANDS, SQUARES_DATA, "nonZero");
+			shape.graphics.beginFill(0x0000FF, 1);
+			shape.graphics.drawRect(100, 0, 50, 50);
+			shape.graphics.endFill();
+			shape.graphics.lineStyle(2, 0x00FF00);
+			shape.graphics.moveTo(10, 150);
+			shape.graphics.lineTo(90, 150);
+			shape.graphics.lineStyle();
+			shape.graphics.beginFill(0x123456);
+			shape.graphics.drawTriangles(Vector.<Number>([0, 200, 10, 200, 0, 210]));
+			shape.graphics.endFill();
+
+			for each (var data:IGraphicsData in shape.graphics.readGraphicsData()) {
+				describe(data);
+			}
+			removeChild(shape);
+		}
+	}
+}
@@ -0,0 +1,51 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// A 2x2 texture with a different color in each corner.
+			var texture:BitmapData = new BitmapData(2, 2, false);
+			texture.setPixel(0, 0, 0xFF0000);
+			texture.setPixel(1, 0, 0x00FF00);
+			texture.setPixel(0, 1, 0x0000FF);
+			texture.setPixel(1, 1, 0xFFFFFF);
+
+			// A square made of two triangles, with the texture mirrored horizontally.
+			var vertices:Vector.<Number> = Vector.<Number>([0, 0, 100, 0, 100, 100, 0, 100]);
+			var indices:Vector.<int> = Vector.<int>([0, 1, 2, 0, 2, 3]);
+			var uv:Vector.<Number> = Vector.<Number>([1, 0, 0, 0, 0, 1, 1, 1]);
+			var uvt:Vector.<Number> = Vector.<Number>([1, 0, 1, 0, 0, 1, 0, 1, 1, 1, 1, 1]);
+
+			var shape:Shape = new Shape();
+			shape.graphics.beginBitmapFill(texture, null, false, false);
+			shape.graphics.drawTriangles(vertices, indices, uv);
+			shape.graphics.endFill();
+			describe("uv", shape);
+
+			shape.graphics.clear();
+			shape.graphics.beginBitmapFill(texture, null, false, false);
+			shape.graphics.drawTriangles(vertices, indices, uvt);
+			shape.graphics.endFill();
+			describe("uvt", shape);
+
+			// Without texture coordinates, the fill repeats with its own matrix.
+			shape.graphics.clear();
+			shape.graphics.beginBitmapFill(texture, null, true, false);
+			shape.graphics.drawTriangles(vertices, indices);
+			shape.graphics.endFill();
+			describe("no uv", shape);
+		}
+
+		static function describe(name:String, shape:Shape):void {
+			var target:BitmapData = new BitmapData(100, 100, false, 0);
+			target.draw(shape);
+			trace("// " + name);
+			trace("top left: " + target.getPixel(25, 10).toString(16));
+			trace("top right: " + target.getPixel(75, 40).toString(16));
+			trace("bottom left: " + target.getPixel(25, 90).toString(16));
+			trace("bottom right: " + target.getPixel(75, 60).toString(16));
+		}
+	}
+}
@@ -0,0 +1,41 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.StatusEvent;
+	import flash.net.LocalConnection;
+
+	// Talks to an AVM1 movie from other.example.com, which runs alongside
+	// this one (see `peer.swf`).
+	public class Test extends Sprite {
+		private var receiver:LocalConnection;
+		private var sender:LocalConnection;
+		private var statuses:int = 0;
+
+		public function Test() {
+			receiver = new LocalConnection();
+			receiver.client = {
+				greet: function(message:String, count:Number):void {
+					trace("avm2 greet: " + message + ", " + count);
+				}
+			};
+			receiver.allowDomain("www.example.com");
+			receiver.connect("avm2");
+			trace("avm2 domain: " + receiver.domain);
+
+			sender = new LocalConnection();
+			sender.addEventListener(StatusEvent.STATUS, onStatus);
+			// The peer is not listening yet.
+			sender.send("_avm1", "fromAvm2", "too early");
+		}
+
+		private function onStatus(event:StatusEvent):void {
+			trace("avm2 status: " + event.level + ", code: " + event.code);
+			statuses++;
+			if (statuses == 1) {
+				sender.send("_avm1", "fromAvm2", "hello", 42, true, {x: 1, y: "two"}, [1, 2, 3]);
+				sender.send("_strict", "fromAvm2", "rejected");
+			} else if (statuses == 2) {
+				receiver.allowDomain("other.example.com");
+			}
+		}
+	}
+}
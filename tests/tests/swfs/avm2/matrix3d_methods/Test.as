@@ -0,0 +1,73 @@
+package {
+	import flash.display.MovieClip;
+	import flash.geom.Matrix3D;
+	import flash.geom.Vector3D;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var m:Matrix3D = new Matrix3D();
+			m.appendTranslation(1, 2, 3);
+			m.prependScale(2, 3, 4);
+			trace("prependScale: " + numbers(m.rawData));
+			trace("transformVector: " + vector(m.transformVector(new Vector3D(1, 1, 1))));
+			trace("deltaTransformVector: " + vector(m.deltaTransformVector(new Vector3D(1, 1, 1))));
+
+			var out:Vector.<Number> = new Vector.<Number>();
+			m.transformVectors(Vector.<Number>([1, 1, 1, 0, 0, 0]), out);
+			trace("transformVectors: " + numbers(out));
+
+			trace("determinant: " + m.determinant);
+			trace("invert: " + m.invert());
+			trace("inverted: " + numbers(m.rawData));
+
+			var singular:Matrix3D = new Matrix3D(Vector.<Number>([
+				1, 2, 3, 4,
+				2, 4, 6, 8,
+				0, 0, 1, 0,
+				0, 0, 0, 1
+			]));
+			trace("singular determinant: " + singular.determinant);
+			trace("singular invert: " + singular.invert());
+			trace("singular unchanged: " + numbers(singular.rawData));
+
+			var rotation:Matrix3D = new Matrix3D();
+			rotation.prependTranslation(10, 0, 0);
+			rotation.prependRotation(90, Vector3D.Z_AXIS);
+			trace("prependRotation: " + vector(rotation.transformVector(new Vector3D(1, 0, 0))));
+			var pivoted:Matrix3D = new Matrix3D();
+			pivoted.prependRotation(90, Vector3D.Z_AXIS, new Vector3D(1, 1, 0));
+			trace("prependRotation with pivot: " + vector(pivoted.transformVector(new Vector3D(2, 1, 0))));
+
+			var composed:Matrix3D = new Matrix3D();
+			composed.appendScale(2, 3, 4);
+			composed.appendRotation(90, Vector3D.Z_AXIS);
+			composed.appendTranslation(5, 6, 7);
+			var components:Vector.<Vector3D> = composed.decompose();
+			trace("decompose: " + components.length);
+			trace("translation: " + vector(components[0]));
+			trace("rotation: " + vector(components[1]));
+			trace("scale: " + vector(components[2]));
+
+			var recomposed:Matrix3D = new Matrix3D();
+			trace("recompose: " + recomposed.recompose(components));
+			trace("recomposed: " + numbers(recomposed.rawData));
+			trace("original: " + numbers(composed.rawData));
+		}
+
+		static function round(value:Number):Number {
+			return Math.round(value * 1000) / 1000;
+		}
+
+		static function numbers(values:Vector.<Number>):String {
+			var rounded:Array = [];
+			for (var i:int = 0; i < values.length; i++) {
+				rounded.push(round(values[i]));
+			}
+			return rounded.join(",");
+		}
+
+		static function vector(v:Vector3D):String {
+			return "(" + round(v.x) + ", " + round(v.y) + ", " + round(v.z) + ")";
+		}
+	}
+}
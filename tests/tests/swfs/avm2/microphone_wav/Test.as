@@ -0,0 +1,49 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.ActivityEvent;
+	import flash.events.SampleDataEvent;
+	import flash.events.StatusEvent;
+	import flash.media.Microphone;
+
+	// Captures `input.wav`, which has one frame each of silence and square
+	// waves with peaks of 0.5, 0.25 and 0.05, followed by more silence.
+	public class Test extends Sprite {
+		private var microphone:Microphone;
+
+		public function Test() {
+			trace("names: " + Microphone.names);
+			microphone = Microphone.getMicrophone();
+			microphone.setSilenceLevel(10, 200);
+			trace("name: " + microphone.name);
+			trace("index: " + microphone.index);
+			trace("muted: " + microphone.muted);
+			trace("rate: " + microphone.rate);
+			trace("gain: " + microphone.gain);
+			trace("silenceLevel: " + microphone.silenceLevel);
+			trace("silenceTimeout: " + microphone.silenceTimeout);
+			trace("activityLevel: " + microphone.activityLevel);
+
+			microphone.addEventListener(StatusEvent.STATUS, function(event:StatusEvent):void {
+				trace("status: " + event.code + " (" + event.level + ")");
+			});
+			microphone.addEventListener(ActivityEvent.ACTIVITY, function(event:ActivityEvent):void {
+				trace("activity: " + event.activating + ", activityLevel: " + microphone.activityLevel);
+			});
+			microphone.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+		}
+
+		private function onSampleData(event:SampleDataEvent):void {
+			var samples:int = event.data.length / 4;
+			event.data.position = 0;
+			var first:Number = event.data.readFloat();
+			var second:Number = event.data.readFloat();
+			var peak:Number = Math.max(Math.abs(first), Math.abs(second));
+			while (event.data.bytesAvailable > 0) {
+				peak = Math.max(peak, Math.abs(event.data.readFloat()));
+			}
+			trace("sampleData: position " + event.position + ", samples " + samples +
+				", first " + first + ", " + second + ", peak " + peak +
+				", activityLevel " + microphone.activityLevel);
+		}
+	}
+}
@@ -0,0 +1,48 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.NetStatusEvent;
+	import flash.net.NetConnection;
+	import flash.net.Responder;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var connection:NetConnection = new NetConnection();
+			connection.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+			connection.connect("http://localhost/gateway");
+			trace("connected: " + connection.connected);
+			trace("uri: " + connection.uri);
+
+			connection.call("test.add", new Responder(function(result:*):void {
+				trace("test.add result: " + result);
+			}, onStatus), 1, 2.5);
+			connection.call("test.echo", new Responder(function(result:*):void {
+				trace("test.echo result: " + result.length + " [" + result.join(", ") + "]");
+			}, onStatus), "hello", 3, true);
+			connection.call("test.add", null, 4, 5);
+			connection.call("test.fail", new Responder(onResult, onStatus), "Something went wrong");
+			connection.call("test.missing", new Responder(onResult, function(status:Object):void {
+				onStatus(status);
+				callMissingGateway();
+			}));
+		}
+
+		private function callMissingGateway():void {
+			var connection:NetConnection = new NetConnection();
+			connection.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+			connection.connect("http://localhost/missing");
+			connection.call("test.add", new Responder(onResult, onStatus), 1, 2);
+		}
+
+		private function onResult(result:*):void {
+			trace("result: " + result);
+		}
+
+		private function onStatus(status:Object):void {
+			trace("status: " + status.code + " (" + status.level + "): " + status.description);
+		}
+
+		private function onNetStatus(event:NetStatusEvent):void {
+			trace("netStatus: " + event.info.code + " (" + event.info.level + ")");
+		}
+	}
+}
@@ -0,0 +1,70 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.NetStatusEvent;
+	import flash.net.NetConnection;
+	import flash.net.Responder;
+
+	public class Test extends Sprite {
+		private var connection:NetConnection;
+
+		public function Test() {
+			connection = new NetConnection();
+			connection.client = {
+				greet: function(name:String, count:Number):void {
+					trace("client.greet: " + name + ", " + count);
+				}
+			};
+			connection.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void {
+				onNetStatus(event);
+				if (event.info.code == "NetConnection.Connect.Success") {
+					trace("connected: " + connection.connected);
+					callEcho();
+				}
+			});
+			connection.connect("rtmp://localhost/test");
+			trace("connected: " + connection.connected);
+		}
+
+		private function callEcho():void {
+			connection.call("echo", new Responder(function(result:*):void {
+				trace("echo result: " + result);
+				callInvokeClient();
+			}, onStatus), "hello");
+		}
+
+		private function callInvokeClient():void {
+			connection.call("invokeClient", new Responder(function(result:*):void {
+				trace("invokeClient result: " + result);
+				connection.close();
+				trace("connected: " + connection.connected);
+				connectRejected();
+			}, onStatus), "greet", "from server", 42);
+		}
+
+		private function connectRejected():void {
+			var rejected:NetConnection = new NetConnection();
+			rejected.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void {
+				onNetStatus(event);
+				if (event.info.code == "NetConnection.Connect.Closed") {
+					connectUnreachable();
+				}
+			});
+			rejected.connect("rtmp://localhost/reject");
+		}
+
+		private function connectUnreachable():void {
+			var unreachable:NetConnection = new NetConnection();
+			unreachable.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+			unreachable.connect("rtmp://localhost:1936/test");
+		}
+
+		private function onStatus(status:Object):void {
+			trace("status: " + status.code + " (" + status.level + ")");
+		}
+
+		private function onNetStatus(event:NetStatusEvent):void {
+			var description:String = event.info.description ? ": " + event.info.description : "";
+			trace("netStatus: " + event.info.code + " (" + event.info.level + ")" + description);
+		}
+	}
+}
@@ -0,0 +1,107 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.NetStatusEvent;
+	import flash.net.NetConnection;
+	import flash.net.NetStream;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		private var ns:NetStream;
+		private var frame:int = 0;
+		private var phase:int = 0;
+
+		public function Test() {
+			var nc:NetConnection = new NetConnection();
+			nc.connect(null);
+
+			ns = new NetStream(nc);
+			ns.client = {
+				onMarker: function(name:String):void {
+					trace("onMarker " + name);
+				}
+			};
+			ns.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+
+			try {
+				ns.appendBytesAction("rewind");
+			} catch (e:Error) {
+				trace("invalid action: " + e.errorID);
+			}
+
+			// Data generation mode starts with an FLV header.
+			ns.play(null);
+			var bytes:ByteArray = header();
+			bytes.writeBytes(tag(0, "a"));
+			ns.appendBytes(bytes);
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frame++;
+			if (frame == 3) {
+				trace("appendBytes");
+				ns.appendBytes(tag(300, "b"));
+			}
+		}
+
+		private function onNetStatus(event:NetStatusEvent):void {
+			trace(event.info.code + " (" + event.info.level + ")");
+			if (phase == 0 && event.info.code == "NetStream.Buffer.Empty") {
+				phase = 1;
+				// Continue from another position in the same stream.
+				trace("resetSeek");
+				ns.appendBytesAction("resetSeek");
+				ns.appendBytes(tag(5000, "c"));
+				trace("endSequence");
+				ns.appendBytesAction("endSequence");
+			} else if (phase == 1 && event.info.code == "NetStream.Play.Stop") {
+				phase = 2;
+				// Start over with a new stream.
+				trace("resetBegin");
+				ns.appendBytesAction("resetBegin");
+				var bytes:ByteArray = header();
+				bytes.writeBytes(tag(0, "d"));
+				ns.appendBytes(bytes);
+				trace("endSequence");
+				ns.appendBytesAction("endSequence");
+			}
+		}
+
+		private static function header():ByteArray {
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeUTFBytes("FLV");
+			bytes.writeByte(1);
+			bytes.writeByte(0);
+			bytes.writeUnsignedInt(9);
+			bytes.writeUnsignedInt(0);
+			return bytes;
+		}
+
+		private static function writeUI24(bytes:ByteArray, value:uint):void {
+			bytes.writeByte(value >> 16);
+			bytes.writeByte(value >> 8);
+			bytes.writeByte(value);
+		}
+
+		// A script data tag that calls `onMarker(name)` on the client.
+		private static function tag(timestamp:uint, name:String):ByteArray {
+			var body:ByteArray = new ByteArray();
+			body.writeByte(2);
+			body.writeUTF("onMarker");
+			body.writeByte(2);
+			body.writeUTF(name);
+
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeByte(18);
+			writeUI24(bytes, body.length);
+			writeUI24(bytes, timestamp);
+			bytes.writeByte(timestamp >> 24);
+			writeUI24(bytes, 0);
+			bytes.writeBytes(body);
+			bytes.writeUnsignedInt(11 + body.length);
+			return bytes;
+		}
+	}
+}
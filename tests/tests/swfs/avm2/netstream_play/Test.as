@@ -0,0 +1,35 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.NetStatusEvent;
+    import flash.media.Video;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var nc:NetConnection = new NetConnection();
+            nc.connect(null);
+
+            var ns:NetStream = new NetStream(nc);
+            ns.client = {
+                onMetaData: function(info:Object):void {
+                    trace("onMetaData: " + info.width + "x" + info.height + ", duration " + info.duration);
+                }
+            };
+            ns.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+                trace(e.info.code + " (" + e.info.level + "), time " + ns.time);
+                if (e.info.code == "NetStream.Play.Stop") {
+                    trace("bytesLoaded: " + ns.bytesLoaded + " of " + ns.bytesTotal);
+                }
+            });
+
+            var video:Video = new Video(16, 16);
+            video.attachNetStream(ns);
+            addChild(video);
+
+            // The FLV has five frames of Screen Video and five packets of PCM
+            // audio, 200ms apart.
+            ns.play("test.flv");
+        }
+    }
+}
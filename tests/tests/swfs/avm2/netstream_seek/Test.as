@@ -0,0 +1,52 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.NetStatusEvent;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+
+    public class Test extends MovieClip {
+        private var ns:NetStream;
+        private var frame:int = 0;
+
+        public function Test() {
+            var nc:NetConnection = new NetConnection();
+            nc.connect(null);
+
+            ns = new NetStream(nc);
+            ns.client = {
+                onMetaData: function(info:Object):void {
+                    trace("onMetaData: duration " + info.duration + ", " + info.keyframes.times.length + " keyframes");
+                },
+                onCuePoint: function(info:Object):void {
+                    trace("onCuePoint: " + info.name + " at " + info.time);
+                }
+            };
+            ns.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+                trace(e.info.code + " (" + e.info.level + "), time " + ns.time);
+            });
+            ns.play("test.flv");
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frame++;
+            if (frame == 1) {
+                trace("time: " + ns.time);
+                trace("bufferLength: " + ns.bufferLength);
+                trace("seek(2)");
+                ns.seek(2);
+                trace("time: " + ns.time);
+            } else if (frame == 2) {
+                trace("time: " + ns.time);
+                trace("pause()");
+                ns.pause();
+            } else if (frame == 3) {
+                trace("time: " + ns.time);
+                trace("resume()");
+                ns.resume();
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+package {
+	import flash.display.Sprite;
+	import flash.system.Security;
+
+	public class Friendly extends Sprite {
+		public function Friendly() {
+			Security.allowDomain("www.example.com");
+			graphics.beginFill(0x00FF00);
+			graphics.drawRect(0, 0, 10, 10);
+			graphics.endFill();
+		}
+	}
+}
@@ -0,0 +1,79 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Loader;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.IOErrorEvent;
+	import flash.events.SecurityErrorEvent;
+	import flash.net.Socket;
+	import flash.net.URLRequest;
+	import flash.system.LoaderContext;
+	import flash.system.Security;
+
+	public class Test extends Sprite {
+		private var steps:Array;
+
+		public function Test() {
+			trace("sandboxType: " + Security.sandboxType);
+			steps = [
+				function():void { load("http://www.example.com/child.swf", false); },
+				function():void { load("http://other.example.org/child.swf", false); },
+				function():void { load("http://other.example.org/friendly.swf", false); },
+				function():void { load("http://cdn.example.com/child.swf", true); },
+				function():void { connect("other.example.org", 5000); },
+				function():void { connect("www.example.com", 5001); }
+			];
+			next();
+		}
+
+		private function next():void {
+			if (steps.length > 0) {
+				steps.shift()();
+			} else {
+				trace("Done");
+			}
+		}
+
+		private function load(url:String, checkPolicyFile:Boolean):void {
+			trace("// load " + url + (checkPolicyFile ? " with checkPolicyFile" : ""));
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("sameDomain: " + loader.contentLoaderInfo.sameDomain);
+				trace("childAllowsParent: " + loader.contentLoaderInfo.childAllowsParent);
+				trace("parentAllowsChild: " + loader.contentLoaderInfo.parentAllowsChild);
+				var bitmapData:BitmapData = new BitmapData(10, 10, false, 0);
+				try {
+					bitmapData.draw(loader);
+					trace("draw: allowed");
+				} catch (e:SecurityError) {
+					trace("draw: " + e.name + " " + e.errorID);
+				}
+				next();
+			});
+			loader.contentLoaderInfo.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+				trace("ioError");
+				next();
+			});
+			loader.load(new URLRequest(url), new LoaderContext(checkPolicyFile));
+		}
+
+		private function connect(host:String, port:int):void {
+			trace("// connect to " + host + ":" + port);
+			var socket:Socket = new Socket();
+			socket.addEventListener(Event.CONNECT, function(e:Event):void {
+				trace("connect");
+				socket.close();
+				next();
+			});
+			socket.addEventListener(SecurityErrorEvent.SECURITY_ERROR, function(e:SecurityErrorEvent):void {
+				trace(e.type + " " + e.errorID);
+				next();
+			});
+			socket.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+				trace(e.type + " " + e.errorID);
+				next();
+			});
+			socket.connect(host, port);
+		}
+	}
+}
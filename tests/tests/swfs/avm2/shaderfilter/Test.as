@@ -0,0 +1,56 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shader;
+	import flash.display.Sprite;
+	import flash.filters.ShaderFilter;
+	import flash.geom.Point;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		// A shader that outputs `amount - src` for the color channels of its input.
+		static var INVERT:Array = [
+				165, 1, 0, 0, 0, 164, 6, 0, 73, 110, 118, 101, 114, 116, 161, 1,
+				2, 0, 0, 12, 95, 79, 117, 116, 67, 111, 111, 114, 100, 0, 163, 0,
+				4, 115, 114, 99, 0, 161, 2, 4, 1, 0, 15, 100, 115, 116, 0, 161,
+				1, 1, 3, 0, 8, 97, 109, 111, 117, 110, 116, 0, 162, 1, 100, 101,
+				102, 97, 117, 108, 116, 86, 97, 108, 117, 101, 0, 63, 128, 0, 0, 48,
+				1, 0, 241, 0, 0, 16, 0, 29, 2, 0, 226, 3, 0, 0, 0, 2,
+				2, 0, 226, 1, 0, 24, 0, 29, 1, 0, 226, 2, 0, 24, 0
+		];
+
+		public function Test() {
+			var shader:Shader = new Shader(bytes(INVERT));
+			var filter:ShaderFilter = new ShaderFilter(shader);
+			trace("shader: " + (filter.shader == shader));
+
+			var source:BitmapData = new BitmapData(2, 1, true, 0);
+			source.setPixel32(0, 0, 0xFFFF8000);
+			source.setPixel32(1, 0, 0xFF0000FF);
+			var result:BitmapData = new BitmapData(2, 1, true, 0);
+			result.applyFilter(source, source.rect, new Point(0, 0), filter);
+			trace("applyFilter: " + result.getPixel32(0, 0).toString(16) + " " + result.getPixel32(1, 0).toString(16));
+			trace("generateFilterRect: " + source.generateFilterRect(source.rect, filter));
+			filter.leftExtension = 2;
+			filter.topExtension = 1;
+			trace("generateFilterRect with extensions: " + source.generateFilterRect(source.rect, filter));
+
+			shader.data.amount.value = [0.5];
+			result.applyFilter(source, source.rect, new Point(0, 0), new ShaderFilter(shader));
+			trace("amount 0.5: " + result.getPixel32(0, 0).toString(16) + " " + result.getPixel32(1, 0).toString(16));
+
+			var sprite:Sprite = new Sprite();
+			sprite.filters = [filter];
+			trace("filters: " + sprite.filters.length + " " + (sprite.filters[0] is ShaderFilter));
+		}
+
+		static function bytes(values:Array):ByteArray {
+			var bytes:ByteArray = new ByteArray();
+			for each (var value:int in values) {
+				bytes.writeByte(value);
+			}
+			bytes.position = 0;
+			return bytes;
+		}
+	}
+}
@@ -0,0 +1,77 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shader;
+	import flash.display.ShaderJob;
+	import flash.events.ShaderEvent;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		// A shader that outputs `amount - src` for the color channels of its input.
+		static var INVERT:Array = [
+				165, 1, 0, 0, 0, 164, 6, 0, 73, 110, 118, 101, 114, 116, 161, 1,
+				2, 0, 0, 12, 95, 79, 117, 116, 67, 111, 111, 114, 100, 0, 163, 0,
+				4, 115, 114, 99, 0, 161, 2, 4, 1, 0, 15, 100, 115, 116, 0, 161,
+				1, 1, 3, 0, 8, 97, 109, 111, 117, 110, 116, 0, 162, 1, 100, 101,
+				102, 97, 117, 108, 116, 86, 97, 108, 117, 101, 0, 63, 128, 0, 0, 48,
+				1, 0, 241, 0, 0, 16, 0, 29, 2, 0, 226, 3, 0, 0, 0, 2,
+				2, 0, 226, 1, 0, 24, 0, 29, 1, 0, 226, 2, 0, 24, 0
+		];
+
+		public function Test() {
+			var shader:Shader = new Shader(bytes(INVERT));
+			trace("name: " + shader.data.name);
+			trace("amount: " + shader.data.amount.value);
+
+			var source:BitmapData = new BitmapData(2, 1, true, 0);
+			source.setPixel32(0, 0, 0xFFFF8000);
+			source.setPixel32(1, 0, 0x80FF0000);
+			shader.data.src.input = source;
+			var target:BitmapData = new BitmapData(2, 1, true, 0);
+			var job:ShaderJob = new ShaderJob(shader, target);
+			job.start(true);
+			trace("progress: " + job.progress);
+			trace("BitmapData: " + target.getPixel32(0, 0).toString(16) + " " + target.getPixel32(1, 0).toString(16));
+
+			var input:Vector.<Number> = Vector.<Number>([0.25, 0.5, 0.75, 1, 0, 0, 0, 0.5]);
+			shader.data.src.input = input;
+			shader.data.src.width = 2;
+			shader.data.src.height = 1;
+			shader.data.amount.value = [0.5];
+			var numbers:Vector.<Number> = new Vector.<Number>();
+			new ShaderJob(shader, numbers, 2, 1).start(true);
+			trace("Vector: " + numbers);
+
+			var byteArray:ByteArray = new ByteArray();
+			new ShaderJob(shader, byteArray, 2, 1).start(true);
+			byteArray.position = 0;
+			var floats:Array = [];
+			while (byteArray.bytesAvailable > 0) {
+				floats.push(byteArray.readFloat());
+			}
+			trace("ByteArray: " + floats);
+
+			try {
+				new ShaderJob(shader, null).start(true);
+			} catch (e:Error) {
+				trace("null target: " + e.errorID);
+			}
+
+			var async:ShaderJob = new ShaderJob(shader, new Vector.<Number>(), 2, 1);
+			async.addEventListener(ShaderEvent.COMPLETE, function(e:ShaderEvent):void {
+				trace("complete: " + e.vector);
+			});
+			async.start();
+			trace("started async job");
+		}
+
+		static function bytes(values:Array):ByteArray {
+			var bytes:ByteArray = new ByteArray();
+			for each (var value:int in values) {
+				bytes.writeByte(value);
+			}
+			bytes.position = 0;
+			return bytes;
+		}
+	}
+}
@@ -0,0 +1,19 @@
+package {
+    import flash.display.MovieClip;
+    import flash.net.SharedObject;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var so:SharedObject = SharedObject.getRemote("game", "rtmp://localhost/app");
+            trace("same name and URI: " + (so === SharedObject.getRemote("game", "rtmp://localhost/app")));
+            trace("other name: " + (so === SharedObject.getRemote("level", "rtmp://localhost/app")));
+            trace("other URI: " + (so === SharedObject.getRemote("game", "rtmp://localhost/other")));
+
+            var persistent:SharedObject = SharedObject.getRemote("game", "rtmp://localhost/app", true);
+            trace("persistent: " + (so === persistent));
+            trace("persistent again: " + (persistent === SharedObject.getRemote("game", "rtmp://localhost/app", true)));
+
+            trace("local: " + (so === SharedObject.getLocal("game")));
+        }
+    }
+}
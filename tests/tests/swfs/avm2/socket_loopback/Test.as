@@ -0,0 +1,59 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.DataEvent;
+    import flash.events.Event;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
+    import flash.net.Socket;
+    import flash.net.XMLSocket;
+
+    public class Test extends MovieClip {
+        private var socket:Socket = new Socket();
+        private var xmlSocket:XMLSocket = new XMLSocket();
+        private var unreachable:Socket = new Socket();
+
+        public function Test() {
+            socket.addEventListener(Event.CONNECT, function(e:Event):void {
+                trace("socket connect, connected: " + socket.connected);
+                socket.writeUTFBytes("hello");
+                trace("socket bytesPending: " + socket.bytesPending);
+                socket.flush();
+                trace("socket bytesPending after flush: " + socket.bytesPending);
+            });
+            socket.addEventListener(ProgressEvent.SOCKET_DATA, function(e:ProgressEvent):void {
+                trace("socket socketData, bytesLoaded: " + e.bytesLoaded + ", bytesAvailable: " + socket.bytesAvailable);
+                trace("socket read: " + socket.readUTFBytes(socket.bytesAvailable));
+            });
+            socket.addEventListener(Event.CLOSE, function(e:Event):void {
+                trace("socket close, connected: " + socket.connected);
+            });
+            socket.connect("localhost", 8001);
+            trace("socket connecting, connected: " + socket.connected);
+
+            xmlSocket.addEventListener(Event.CONNECT, function(e:Event):void {
+                trace("xmlSocket connect, connected: " + xmlSocket.connected);
+            });
+            xmlSocket.addEventListener(DataEvent.DATA, function(e:DataEvent):void {
+                trace("xmlSocket data: " + e.data);
+                if (e.data == "<hello/>") {
+                    xmlSocket.send("<ping/>");
+                } else {
+                    xmlSocket.close();
+                    trace("xmlSocket closed, connected: " + xmlSocket.connected);
+                }
+            });
+            xmlSocket.addEventListener(Event.CLOSE, function(e:Event):void {
+                trace("xmlSocket close (should not happen)");
+            });
+            xmlSocket.connect("localhost", 8002);
+
+            unreachable.addEventListener(Event.CONNECT, function(e:Event):void {
+                trace("unreachable connect (should not happen)");
+            });
+            unreachable.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace("unreachable ioError: " + e.text + ", connected: " + unreachable.connected);
+            });
+            unreachable.connect("localhost", 8003);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+package {
+	import flash.media.Sound;
+
+	public class AdpcmStereoSound extends Sound {
+	}
+}
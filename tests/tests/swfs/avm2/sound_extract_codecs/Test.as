@@ -0,0 +1,64 @@
+package {
+	import flash.display.Sprite;
+	import flash.media.Sound;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		public function Test() {
+			trace("/// ADPCM, 44.1KHz mono");
+			var adpcm:Sound = new AdpcmSound();
+			var output:ByteArray = new ByteArray();
+			trace("extracted: " + adpcm.extract(output, 5, 0));
+			trace("extracted: " + adpcm.extract(output, 5));
+			output.position = 0;
+			while (output.bytesAvailable > 0) {
+				trace(output.readFloat() + ", " + output.readFloat());
+			}
+			trace("extracted past the end: " + adpcm.extract(new ByteArray(), 100, 1000));
+			checkSound(adpcm);
+
+			trace("/// ADPCM, 22KHz stereo");
+			checkSound(new AdpcmStereoSound());
+
+			trace("/// MP3, 44.1KHz mono");
+			checkSound(new Mp3Sound());
+
+			trace("/// Nellymoser, 22KHz mono");
+			checkSound(new NellymoserSound());
+		}
+
+		private function checkSound(sound:Sound):void {
+			var all:ByteArray = new ByteArray();
+			var total:Number = sound.extract(all, 1000000, 0);
+			trace("length: " + sound.length);
+			trace("extracted: " + total);
+			trace("bytes: " + all.length);
+
+			var peak:Number = 0;
+			var stereo:Boolean = false;
+			all.position = 0;
+			while (all.bytesAvailable > 0) {
+				var left:Number = all.readFloat();
+				var right:Number = all.readFloat();
+				peak = Math.max(peak, Math.abs(left), Math.abs(right));
+				stereo = stereo || left != right;
+			}
+			trace("audible: " + (peak > 0.01));
+			trace("in range: " + (peak <= 1));
+			trace("stereo: " + stereo);
+
+			// Extracting from the middle returns the same samples as extracting everything.
+			var part:ByteArray = new ByteArray();
+			var position:int = total / 3;
+			var count:Number = sound.extract(part, 1000, position);
+			trace("extracted from " + position + ": " + count);
+			var same:Boolean = true;
+			all.position = position * 8;
+			part.position = 0;
+			while (part.bytesAvailable > 0) {
+				same = same && part.readFloat() == all.readFloat();
+			}
+			trace("same samples: " + same);
+		}
+	}
+}
@@ -0,0 +1,42 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.IOErrorEvent;
+	import flash.events.ProgressEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+	import flash.media.SoundLoaderContext;
+	import flash.net.URLRequest;
+
+	public class Test extends Sprite {
+		private var channel:SoundChannel = null;
+
+		public function Test() {
+			var sound:Sound = new Sound();
+			sound.addEventListener(Event.OPEN, function(e:Event):void {
+				trace("open: bytesLoaded = " + sound.bytesLoaded + ", bytesTotal = " + sound.bytesTotal + ", isBuffering = " + sound.isBuffering);
+			});
+			sound.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+				trace("progress: " + e.bytesLoaded + " / " + e.bytesTotal + ", bytesLoaded = " + sound.bytesLoaded + ", bytesTotal = " + sound.bytesTotal + ", isBuffering = " + sound.isBuffering);
+				if (!sound.isBuffering && channel == null) {
+					channel = sound.play();
+					trace("play while loading: " + channel);
+				}
+			});
+			sound.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("complete: bytesLoaded = " + sound.bytesLoaded + ", bytesTotal = " + sound.bytesTotal + ", isBuffering = " + sound.isBuffering);
+				trace("play after loading: " + sound.play());
+			});
+			sound.load(new URLRequest("noise.mp3"), new SoundLoaderContext(500));
+
+			var missing:Sound = new Sound();
+			missing.addEventListener(Event.OPEN, function(e:Event):void {
+				trace("missing open: isBuffering = " + missing.isBuffering);
+			});
+			missing.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+				trace("missing ioError: " + e.text + ", isBuffering = " + missing.isBuffering);
+			});
+			missing.load(new URLRequest("missing.mp3"));
+		}
+	}
+}
@@ -0,0 +1,64 @@
+package {
+	import flash.display.Sprite;
+	import flash.media.Sound;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var values:Array = [0, 0.5, -0.25, 0.1, -0.7, 1 / 3, 0.999, -1, 0.000123, 0.3];
+			var input:ByteArray = new ByteArray();
+			for each (var value:Number in values) {
+				input.writeFloat(value);
+			}
+
+			trace("/// Stereo");
+			input.position = 0;
+			var stereo:Sound = new Sound();
+			stereo.loadPCMFromByteArray(input, values.length / 2, "float", true, 44100);
+			var output:ByteArray = new ByteArray();
+			trace("extracted: " + stereo.extract(output, 100));
+			trace("bytes: " + output.length);
+			input.position = 0;
+			output.position = 0;
+			var exact:Boolean = true;
+			while (input.bytesAvailable > 0) {
+				var expected:Number = input.readFloat();
+				var actual:Number = output.readFloat();
+				if (expected != actual) {
+					trace("mismatch: expected " + expected + ", got " + actual);
+					exact = false;
+				}
+			}
+			trace("exact: " + exact);
+			output.position = 8;
+			trace("second frame: " + output.readFloat() + ", " + output.readFloat());
+
+			trace("/// Mono");
+			input.position = 0;
+			var mono:Sound = new Sound();
+			mono.loadPCMFromByteArray(input, 4, "float", false, 44100);
+			output = new ByteArray();
+			trace("extracted: " + mono.extract(output, 100));
+			input.position = 0;
+			output.position = 0;
+			for (var i:int = 0; i < 4; i++) {
+				expected = input.readFloat();
+				var left:Number = output.readFloat();
+				var right:Number = output.readFloat();
+				trace("frame " + i + ": " + (left == expected) + ", " + (right == expected));
+			}
+
+			trace("/// Out of range");
+			input = new ByteArray();
+			input.writeFloat(1.5);
+			input.writeFloat(-2);
+			input.position = 0;
+			var loud:Sound = new Sound();
+			loud.loadPCMFromByteArray(input, 1);
+			output = new ByteArray();
+			trace("extracted: " + loud.extract(output, 100));
+			output.position = 0;
+			trace("frame: " + output.readFloat() + ", " + output.readFloat());
+		}
+	}
+}
@@ -0,0 +1,56 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+
+	public class Test extends MovieClip {
+		private var events:int = 0;
+
+		public function Test() {
+			var sound:Sound = new Sound();
+			sound.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+			trace("before play");
+			var channel:SoundChannel = sound.play();
+			trace("after play, events: " + events);
+			channel.addEventListener(Event.SOUND_COMPLETE, onComplete);
+		}
+
+		private function onSampleData(event:SampleDataEvent):void {
+			events++;
+			trace("sampleData " + events + ", position: " + event.position + ", data length: " + event.data.length);
+			// Three full requests, then too few samples to continue.
+			if (events <= 3) {
+				for (var i:int = 0; i < 2048; i++) {
+					var sample:Number = Math.sin(i / 2048 * Math.PI * 2) * 0.25;
+					event.data.writeFloat(sample);
+					event.data.writeFloat(sample);
+				}
+			}
+		}
+
+		private function onComplete(event:Event):void {
+			trace("soundComplete, events: " + events);
+
+			var sound:Sound = new Sound();
+			sound.addEventListener(SampleDataEvent.SAMPLE_DATA, onOffsetSampleData);
+			trace("play from 1000ms");
+			var channel:SoundChannel = sound.play(1000);
+			trace("after play");
+			channel.addEventListener(Event.SOUND_COMPLETE, onOffsetComplete);
+		}
+
+		private function onOffsetSampleData(event:SampleDataEvent):void {
+			trace("sampleData, position: " + event.position);
+			for (var i:int = 0; i < 100; i++) {
+				event.data.writeFloat(0);
+				event.data.writeFloat(0);
+			}
+		}
+
+		private function onOffsetComplete(event:Event):void {
+			trace("soundComplete");
+		}
+	}
+}
@@ -0,0 +1,112 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.text.engine.ContentElement;
+	import flash.text.engine.ElementFormat;
+	import flash.text.engine.GraphicElement;
+	import flash.text.engine.GroupElement;
+	import flash.text.engine.TextBlock;
+	import flash.text.engine.TextElement;
+	import flash.text.engine.TextLine;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			testGraphics();
+			testInsufficientWidth();
+			testText();
+		}
+
+		static function graphic(width:Number, height:Number):GraphicElement {
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawRect(0, 0, width, height);
+			shape.graphics.endFill();
+			return new GraphicElement(shape, width, height, new ElementFormat());
+		}
+
+		static function describe(name:String, line:TextLine):void {
+			trace("// " + name);
+			trace("textBlockBeginIndex: " + line.textBlockBeginIndex);
+			trace("rawTextLength: " + line.rawTextLength);
+			trace("atomCount: " + line.atomCount);
+			trace("textWidth: " + line.textWidth);
+			trace("ascent: " + line.ascent);
+			trace("descent: " + line.descent);
+			trace("textHeight: " + line.textHeight);
+			trace("validity: " + line.validity);
+		}
+
+		static function testGraphics():void {
+			var elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+			elements.push(graphic(30, 20));
+			elements.push(graphic(40, 10));
+			elements.push(graphic(50, 30));
+			var block:TextBlock = new TextBlock(new GroupElement(elements));
+
+			var first:TextLine = block.createTextLine(null, 75);
+			trace("textLineCreationResult: " + block.textLineCreationResult);
+			describe("first graphic line", first);
+			trace("hasGraphicElement: " + first.hasGraphicElement);
+			trace("getAtomBounds(1): " + first.getAtomBounds(1));
+			trace("numChildren: " + first.numChildren);
+			trace("graphic position: " + first.getAtomGraphic(0).x + " " + first.getAtomGraphic(0).y);
+			trace("getAtomGraphic(1) is a child: " + (first.getAtomGraphic(1).parent == first));
+
+			var second:TextLine = block.createTextLine(first, 75);
+			describe("second graphic line", second);
+			trace("firstLine: " + (block.firstLine == first));
+			trace("lastLine: " + (block.lastLine == second));
+			trace("nextLine: " + (first.nextLine == second));
+			trace("previousLine: " + (second.previousLine == first));
+			trace("textBlock: " + (second.textBlock == block));
+
+			trace("after the last line: " + block.createTextLine(second, 75));
+			trace("textLineCreationResult: " + block.textLineCreationResult);
+		}
+
+		static function testInsufficientWidth():void {
+			var block:TextBlock = new TextBlock(graphic(30, 20));
+			trace("// insufficient width");
+			trace("createTextLine: " + block.createTextLine(null, 10));
+			trace("textLineCreationResult: " + block.textLineCreationResult);
+			var line:TextLine = block.createTextLine(null, 10, 0, true);
+			trace("textLineCreationResult with fitSomething: " + block.textLineCreationResult);
+			trace("textWidth: " + line.textWidth);
+		}
+
+		static function testText():void {
+			var block:TextBlock = new TextBlock(new TextElement("Hello world", new ElementFormat(null, 20)));
+			var line:TextLine = block.createTextLine();
+			trace("// text line");
+			trace("textBlockBeginIndex: " + line.textBlockBeginIndex);
+			trace("rawTextLength: " + line.rawTextLength);
+			trace("atomCount: " + line.atomCount);
+			trace("textWidth is positive: " + (line.textWidth > 0));
+			trace("ascent is larger than descent: " + (line.ascent > line.descent));
+			trace("textHeight: " + (line.textHeight == line.ascent + line.descent));
+			trace("getAtomTextBlockBeginIndex(6): " + line.getAtomTextBlockBeginIndex(6));
+			trace("getAtomTextBlockEndIndex(6): " + line.getAtomTextBlockEndIndex(6));
+			trace("getAtomIndexAtCharIndex(4): " + line.getAtomIndexAtCharIndex(4));
+			trace("getAtomWordBoundaryOnLeft(6): " + line.getAtomWordBoundaryOnLeft(6));
+			trace("getAtomWordBoundaryOnLeft(2): " + line.getAtomWordBoundaryOnLeft(2));
+			var atom:Number = line.getAtomBounds(6).x;
+			trace("atoms are laid out left to right: " + (atom > line.getAtomBounds(5).x));
+			trace("getAtomBounds(0).y: " + (line.getAtomBounds(0).y == -line.ascent));
+
+			var first:TextLine = block.createTextLine(null, atom + 1);
+			trace("// broken text lines");
+			trace("first rawTextLength: " + first.rawTextLength);
+			trace("first textWidth: " + (first.textWidth == atom));
+			var second:TextLine = block.createTextLine(first);
+			trace("second textBlockBeginIndex: " + second.textBlockBeginIndex);
+			trace("second rawTextLength: " + second.rawTextLength);
+			trace("the old line was released: " + (line.validity) + " " + (line.textBlock == null));
+
+			try {
+				line.getAtomBounds(20);
+			} catch (e:RangeError) {
+				trace("getAtomBounds(20): " + e.errorID);
+			}
+		}
+	}
+}
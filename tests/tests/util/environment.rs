@@ -37,3 +37,11 @@ fn build_wgpu_descriptors() -> Option<Arc<Descriptors>> {
 }
 
 pub static WGPU: Lazy<Option<Arc<Descriptors>>> = Lazy::new(build_wgpu_descriptors);
+
+/// Whether tests should render with the software renderer instead of wgpu.
+///
+/// This is chosen by setting the `RUFFLE_TEST_RENDERER` environment variable
+/// to `software`, and allows running renderer tests on machines without a GPU.
+pub static USE_SOFTWARE_RENDERER: Lazy<bool> = Lazy::new(|| {
+    std::env::var("RUFFLE_TEST_RENDERER").map_or(false, |renderer| renderer == "software")
+});
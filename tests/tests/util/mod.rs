@@ -2,9 +2,12 @@
 // https://doc.rust-lang.org/book/ch11-03-test-organization.html
 
 pub mod environment;
+pub mod navigator;
 pub mod options;
+pub mod remoting;
 pub mod runner;
 pub mod test;
+pub mod ui;
 
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
 /// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
@@ -0,0 +1,407 @@
+use crate::util::options::{SecuritySandbox, SocketEvent, SocketPeer};
+use crate::util::remoting;
+use anyhow::{anyhow, Result};
+use ruffle_core::backend::navigator::{
+    ConnectionState, NavigationMethod, NavigatorBackend, NullExecutor, NullNavigatorBackend,
+    OwnedFuture, Request, Response, ResponseBody, SocketAction, StreamingResponse,
+};
+use ruffle_core::indexmap::IndexMap;
+use ruffle_core::loader::Error;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration;
+use url::{Origin, Url};
+
+/// A navigator backend that loads files relative to the test directory, and
+/// connects sockets to the scripted peers in the test options.
+///
+/// In the security sandbox, requests are resolved against the URL of the
+/// movie instead, and served from the directory of the matching server.
+/// Requests to the remoting gateway in the test options are answered by a
+/// mock gateway.
+///
+/// If the test runs a loopback RTMP server, `rtmp://localhost` connects to it
+/// over a real socket.
+///
+/// Streamed responses are split into chunks of `CHUNK_SIZE` bytes, as if they
+/// were being downloaded.
+pub struct TestNavigatorBackend {
+    inner: NullNavigatorBackend,
+    peers: SocketPeers,
+    sandbox: Option<(Url, Vec<(Origin, PathBuf)>)>,
+    remoting_gateway: Option<String>,
+    rtmp_server: Option<SocketAddr>,
+}
+
+impl TestNavigatorBackend {
+    const CHUNK_SIZE: usize = 1000;
+
+    /// The port that `rtmp://` URLs connect to by default.
+    const RTMP_PORT: u16 = 1935;
+
+    pub fn new(
+        base_path: &Path,
+        executor: &NullExecutor,
+        peers: SocketPeers,
+        security_sandbox: Option<&SecuritySandbox>,
+        remoting_gateway: Option<String>,
+        rtmp_server: Option<SocketAddr>,
+    ) -> Result<Self> {
+        let sandbox = security_sandbox
+            .map(|sandbox| -> Result<_> {
+                let servers = sandbox
+                    .servers
+                    .iter()
+                    .map(|(server, path)| -> Result<_> {
+                        Ok((Url::parse(server)?.origin(), base_path.join(path)))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((Url::parse(&sandbox.url)?, servers))
+            })
+            .transpose()?;
+        Ok(Self {
+            inner: NullNavigatorBackend::with_base_path(base_path, executor)?,
+            peers,
+            sandbox,
+            remoting_gateway,
+            rtmp_server,
+        })
+    }
+}
+
+impl NavigatorBackend for TestNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        target: String,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, target, vars_method)
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<Response, Error> {
+        if self.remoting_gateway.as_deref() == Some(request.url()) {
+            let url = request.url().to_string();
+            let body = match request.body() {
+                Some((body, _)) => remoting::respond(body),
+                None => Err(rtmp::Error::invalid_data("Missing remoting request")),
+            };
+            return Box::pin(async move {
+                let body = body.map_err(|e| Error::FetchError(e.to_string()))?;
+                Ok(Response { url, body })
+            });
+        }
+
+        let Some((movie_url, servers)) = &self.sandbox else {
+            return self.inner.fetch(request);
+        };
+
+        let url = movie_url.join(request.url());
+        let servers = servers.clone();
+        Box::pin(async move {
+            let url = url.map_err(|e| Error::FetchError(e.to_string()))?;
+            let origin = url.origin();
+            let (_, directory) = servers
+                .iter()
+                .find(|(server, _)| *server == origin)
+                .ok_or_else(|| Error::FetchError(format!("No server for {url}")))?;
+            let path = directory.join(url.path().trim_start_matches('/'));
+            let body = std::fs::read(path).map_err(|e| Error::FetchError(e.to_string()))?;
+            Ok(Response {
+                url: url.to_string(),
+                body,
+            })
+        })
+    }
+
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, Error> {
+        let fetch = self.fetch(request);
+        Box::pin(async move {
+            let response = fetch.await?;
+            let chunks = response
+                .body
+                .chunks(Self::CHUNK_SIZE)
+                .map(|chunk| chunk.to_vec())
+                .collect();
+            Ok(StreamingResponse {
+                url: response.url,
+                expected_length: Some(response.body.len() as u64),
+                body: Box::new(ChunkedBody(chunks)),
+            })
+        })
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        _timeout: Duration,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        match self.rtmp_server {
+            Some(address) if host == "localhost" && port == Self::RTMP_PORT => {
+                connect_loopback(address, receiver, sender)
+            }
+            _ => self.peers.connect(host, port, receiver, sender),
+        }
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+}
+
+/// Connect to a server on this machine, passing data between the socket and
+/// the channels on background threads.
+fn connect_loopback(
+    address: SocketAddr,
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<SocketAction>,
+) {
+    thread::spawn(move || {
+        let stream = match TcpStream::connect(address) {
+            Ok(stream) => stream,
+            Err(_) => {
+                let _ = sender.send(SocketAction::Connect(ConnectionState::Failed));
+                return;
+            }
+        };
+        let _ = sender.send(SocketAction::Connect(ConnectionState::Connected));
+
+        let mut reader = match stream.try_clone() {
+            Ok(reader) => reader,
+            Err(_) => {
+                let _ = sender.send(SocketAction::Close);
+                return;
+            }
+        };
+        thread::spawn(move || {
+            let mut buffer = vec![0; 4096];
+            loop {
+                match reader.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(len) => {
+                        if sender
+                            .send(SocketAction::Data(buffer[..len].to_vec()))
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                }
+            }
+            let _ = sender.send(SocketAction::Close);
+        });
+
+        let mut writer = stream;
+        for data in receiver {
+            if writer.write_all(&data).is_err() {
+                break;
+            }
+        }
+        let _ = writer.shutdown(Shutdown::Both);
+    });
+}
+
+/// A response body that is returned in the given chunks.
+struct ChunkedBody(VecDeque<Vec<u8>>);
+
+impl ResponseBody for ChunkedBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        let chunk = self.0.pop_front();
+        Box::pin(async move { Ok(chunk) })
+    }
+}
+
+/// The servers that a test can connect to, which follow the script in the
+/// test options instead of using the network.
+///
+/// Each peer accepts a single connection. Connecting to any other address
+/// fails, as if the server could not be reached.
+#[derive(Clone, Default)]
+pub struct SocketPeers(Rc<RefCell<SocketPeersData>>);
+
+#[derive(Default)]
+struct SocketPeersData {
+    /// Peers that have not been connected to yet.
+    waiting: Vec<SocketPeer>,
+
+    /// Peers that the test is connected to.
+    connections: Vec<PeerConnection>,
+
+    /// Every way in which the test strayed from the scripts.
+    errors: Vec<String>,
+}
+
+/// A connection between the test and a scripted peer.
+struct PeerConnection {
+    address: String,
+    steps: VecDeque<SocketEvent>,
+    close: bool,
+
+    /// Data sent by the test that has not been matched by the script yet.
+    received: Vec<u8>,
+
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<SocketAction>,
+}
+
+impl SocketPeers {
+    pub fn new(peers: Vec<SocketPeer>) -> Self {
+        Self(Rc::new(RefCell::new(SocketPeersData {
+            waiting: peers,
+            ..Default::default()
+        })))
+    }
+
+    fn connect(
+        &self,
+        host: String,
+        port: u16,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        let mut data = self.0.borrow_mut();
+        let Some(index) = data
+            .waiting
+            .iter()
+            .position(|peer| peer.host == host && peer.port == port)
+        else {
+            let _ = sender.send(SocketAction::Connect(ConnectionState::Failed));
+            return;
+        };
+
+        let peer = data.waiting.remove(index);
+        let _ = sender.send(SocketAction::Connect(ConnectionState::Connected));
+        data.connections.push(PeerConnection {
+            address: format!("{host}:{port}"),
+            steps: peer.script.into(),
+            close: peer.close,
+            received: Vec::new(),
+            receiver,
+            sender,
+        });
+    }
+
+    /// Let every peer read what the test sent, and follow its script as far
+    /// as it can.
+    pub fn tick(&self) {
+        let data = &mut *self.0.borrow_mut();
+        let errors = &mut data.errors;
+        data.connections
+            .retain_mut(|connection| match connection.tick() {
+                Ok(is_open) => is_open,
+                Err(error) => {
+                    errors.push(error);
+                    false
+                }
+            });
+    }
+
+    /// Check that every peer was connected to and finished its script.
+    pub fn finish(&self) -> Result<()> {
+        let data = self.0.borrow();
+        let mut errors = data.errors.clone();
+        for peer in &data.waiting {
+            errors.push(format!(
+                "{}:{}: the test never connected",
+                peer.host, peer.port
+            ));
+        }
+        for connection in &data.connections {
+            if !connection.steps.is_empty() {
+                errors.push(format!(
+                    "{}: the script was not finished, next step was {:?}",
+                    connection.address, connection.steps[0]
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!("Socket script failed:\n{}", errors.join("\n")))
+        }
+    }
+}
+
+impl PeerConnection {
+    /// Returns whether the connection is still open.
+    fn tick(&mut self) -> Result<bool, String> {
+        let mut client_closed = false;
+        loop {
+            match self.receiver.try_recv() {
+                Ok(data) => self.received.extend_from_slice(&data),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    client_closed = true;
+                    break;
+                }
+            }
+        }
+
+        while let Some(step) = self.steps.front() {
+            match step {
+                SocketEvent::Send(text) => {
+                    let _ = self
+                        .sender
+                        .send(SocketAction::Data(text.as_bytes().to_vec()));
+                }
+                SocketEvent::Receive(expected) => {
+                    let expected = expected.as_bytes();
+                    if self.received.len() < expected.len() {
+                        break;
+                    }
+                    let actual: Vec<u8> = self.received.drain(..expected.len()).collect();
+                    if actual != expected {
+                        return Err(format!(
+                            "{}: expected to receive {:?}, but received {:?}",
+                            self.address,
+                            String::from_utf8_lossy(expected),
+                            String::from_utf8_lossy(&actual)
+                        ));
+                    }
+                }
+            }
+            self.steps.pop_front();
+        }
+
+        if !self.received.is_empty() && (self.steps.is_empty() || client_closed) {
+            return Err(format!(
+                "{}: received unexpected data {:?}",
+                self.address,
+                String::from_utf8_lossy(&self.received)
+            ));
+        }
+
+        if client_closed {
+            if !self.steps.is_empty() {
+                return Err(format!(
+                    "{}: the test closed the connection before the script was finished",
+                    self.address
+                ));
+            }
+            return Ok(false);
+        }
+
+        if self.steps.is_empty() && self.close {
+            let _ = self.sender.send(SocketAction::Close);
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::util::environment::WGPU;
+use crate::util::environment::{USE_SOFTWARE_RENDERER, WGPU};
 use crate::util::runner::TestAudioBackend;
 use anyhow::{anyhow, Result};
 use approx::assert_relative_eq;
@@ -8,6 +8,7 @@ use ruffle_core::{PlayerBuilder, ViewportDimensions};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::wgpu;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -22,6 +23,14 @@ pub struct TestOptions {
     pub ignore: bool,
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
+    pub file_dialogs: Vec<FileDialogAnswer>,
+    pub microphone: Option<PathBuf>,
+    pub camera: Option<PathBuf>,
+    pub sockets: Vec<SocketPeer>,
+    pub security_sandbox: Option<SecuritySandbox>,
+    pub remoting_gateway: Option<String>,
+    pub rtmp_server: bool,
+    pub local_connection_peer: Option<LocalConnectionPeer>,
 }
 
 impl Default for TestOptions {
@@ -34,6 +43,14 @@ impl Default for TestOptions {
             ignore: false,
             approximations: None,
             player_options: PlayerOptions::default(),
+            file_dialogs: Vec::new(),
+            microphone: None,
+            camera: None,
+            sockets: Vec::new(),
+            security_sandbox: None,
+            remoting_gateway: None,
+            rtmp_server: false,
+            local_connection_peer: None,
         }
     }
 }
@@ -48,6 +65,68 @@ impl TestOptions {
     }
 }
 
+/// The files that the test chooses in a file dialog, relative to the test
+/// directory. Choosing no files cancels the dialog.
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileDialogAnswer {
+    pub files: Vec<PathBuf>,
+}
+
+/// A server that the test can connect to with a socket, which follows a
+/// script instead of using the network.
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SocketPeer {
+    pub host: String,
+    pub port: u16,
+    #[serde(default)]
+    pub script: Vec<SocketEvent>,
+
+    /// Whether the peer closes the connection once its script is done.
+    /// Otherwise, the test is expected to close it.
+    #[serde(default)]
+    pub close: bool,
+}
+
+/// A step in the script of a `SocketPeer`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketEvent {
+    /// Wait for the test to send exactly this text.
+    Receive(String),
+
+    /// Send this text to the test.
+    Send(String),
+}
+
+/// Runs the test as a remote movie in the security sandbox, as if it was
+/// loaded from `url`.
+///
+/// Absolute URLs are served from the directory of the server with the same
+/// origin, relative to the test directory. Loading from any other origin
+/// fails, as if the server could not be reached.
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecuritySandbox {
+    pub url: String,
+    #[serde(default)]
+    pub servers: HashMap<String, PathBuf>,
+}
+
+/// A second movie that runs alongside the test, and can talk to it with
+/// `LocalConnection`.
+///
+/// The peer runs the same number of frames as the test, right after it, and
+/// its trace output goes to the same place. It is loaded from `url` if one is
+/// given, so that it can be in a different domain than the test.
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocalConnectionPeer {
+    pub path: PathBuf,
+    pub url: Option<String>,
+}
+
 #[derive(Deserialize, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct Approximations {
@@ -116,22 +195,25 @@ impl PlayerOptions {
         };
 
         if let Some(render_options) = &self.with_renderer {
+            use ruffle_render_software::SoftwareRenderBackend;
             use ruffle_render_wgpu::backend::WgpuRenderBackend;
             use ruffle_render_wgpu::target::TextureTarget;
 
-            if let Some(descriptors) = WGPU.clone() {
+            if *USE_SOFTWARE_RENDERER {
+                player_builder = player_builder
+                    .with_quality(render_options.quality())
+                    .with_renderer(SoftwareRenderBackend::new(ViewportDimensions {
+                        width,
+                        height,
+                        scale_factor: 1.0,
+                    }));
+            } else if let Some(descriptors) = WGPU.clone() {
                 if render_options.is_supported(&descriptors.adapter) {
                     let target = TextureTarget::new(&descriptors.device, (width, height))
                         .map_err(|e| anyhow!(e.to_string()))?;
 
                     player_builder = player_builder
-                        .with_quality(match render_options.sample_count {
-                            16 => StageQuality::High16x16,
-                            8 => StageQuality::High8x8,
-                            4 => StageQuality::High,
-                            2 => StageQuality::Medium,
-                            _ => StageQuality::Low,
-                        })
+                        .with_quality(render_options.quality())
                         .with_renderer(
                             WgpuRenderBackend::new(descriptors, target)
                                 .map_err(|e| anyhow!(e.to_string()))?,
@@ -157,7 +239,7 @@ impl PlayerOptions {
         if let Some(render) = &self.with_renderer {
             // If we don't actually want to check the renderer (ie we're just listing potential tests),
             // don't spend the cost to create it
-            if check_renderer && !render.optional {
+            if check_renderer && !render.optional && !*USE_SOFTWARE_RENDERER {
                 if let Some(wgpu) = WGPU.as_deref() {
                     if !render.is_supported(&wgpu.adapter) {
                         return false;
@@ -190,7 +272,7 @@ impl ImageComparison {
         actual_image: image::RgbaImage,
         expected_image: image::RgbaImage,
         test_path: &Path,
-        adapter_info: ruffle_render_wgpu::wgpu::AdapterInfo,
+        renderer_name: &str,
     ) -> Result<()> {
         use anyhow::Context;
 
@@ -227,7 +309,7 @@ impl ImageComparison {
             .unwrap();
 
         if outliers > self.max_outliers {
-            let suffix = format!("{}-{:?}", std::env::consts::OS, adapter_info.backend);
+            let suffix = format!("{}-{}", std::env::consts::OS, renderer_name);
 
             image::RgbaImage::from_raw(
                 expected_image.width(),
@@ -274,6 +356,16 @@ impl Default for RenderOptions {
 }
 
 impl RenderOptions {
+    pub fn quality(&self) -> StageQuality {
+        match self.sample_count {
+            16 => StageQuality::High16x16,
+            8 => StageQuality::High8x8,
+            4 => StageQuality::High,
+            2 => StageQuality::Medium,
+            _ => StageQuality::Low,
+        }
+    }
+
     pub fn is_supported(&self, adapter: &wgpu::Adapter) -> bool {
         let info = adapter.get_info();
         // 5140 & 140 is WARP, https://learn.microsoft.com/en-us/windows/win32/direct3ddxgi/d3d10-graphics-programming-guide-dxgi#new-info-about-enumerating-adapters-for-windows-8
@@ -0,0 +1,91 @@
+use flash_lso::types::{Element, Value};
+use rtmp::remoting::{Message, Packet};
+use std::rc::Rc;
+
+/// Answer a Flash Remoting request, as a gateway with these services would:
+///
+/// - `test.echo(...)` returns its arguments as an array.
+/// - `test.add(a, b)` returns the sum of two numbers.
+/// - `test.fail(description)` fails with a `Test.Failed` status.
+///
+/// Calls to any other service fail with a `Server.ResourceNotFound` status.
+pub fn respond(request: &[u8]) -> rtmp::Result<Vec<u8>> {
+    let request = Packet::parse(request)?;
+    let messages = request
+        .messages
+        .into_iter()
+        .map(|message| {
+            let arguments = match &message.contents {
+                Value::StrictArray(arguments) => arguments.clone(),
+                _ => Vec::new(),
+            };
+            let result = match message.target_uri.as_str() {
+                "test.echo" => Ok(Value::StrictArray(
+                    arguments
+                        .iter()
+                        .map(|argument| Rc::new(amf0(argument)))
+                        .collect(),
+                )),
+                "test.add" => Ok(Value::Number(arguments.iter().map(number).sum())),
+                "test.fail" => Err(status(
+                    "Test.Failed",
+                    arguments
+                        .first()
+                        .map_or(Value::Undefined, |argument| amf0(argument)),
+                )),
+                target => Err(status(
+                    "Server.ResourceNotFound",
+                    Value::String(format!("No such service: {target}")),
+                )),
+            };
+            let (callback, contents) = match result {
+                Ok(contents) => ("onResult", contents),
+                Err(contents) => ("onStatus", contents),
+            };
+            Message {
+                target_uri: format!("{}/{callback}", message.response_uri),
+                response_uri: "null".to_string(),
+                contents,
+            }
+        })
+        .collect();
+
+    Packet {
+        version: request.version,
+        headers: Vec::new(),
+        messages,
+    }
+    .to_bytes()
+}
+
+/// The status object of a failed call.
+fn status(code: &str, description: Value) -> Value {
+    Value::Object(
+        vec![
+            Element::new("code", Value::String(code.to_string())),
+            Element::new("level", Value::String("error".to_string())),
+            Element::new("description", description),
+        ],
+        None,
+    )
+}
+
+/// Convert an argument to a value that can be sent back to either kind of
+/// connection.
+///
+/// AMF3 connections wrap each argument in an AMF3 value, and may send
+/// integers, which AMF0 does not have.
+fn amf0(value: &Value) -> Value {
+    match value {
+        Value::AMF3(value) => amf0(value),
+        Value::Integer(value) => Value::Number((*value).into()),
+        value => value.clone(),
+    }
+}
+
+fn number(value: &Rc<Value>) -> f64 {
+    match amf0(value) {
+        Value::Number(value) => value,
+        _ => f64::NAN,
+    }
+}
@@ -1,17 +1,22 @@
+use crate::util::navigator::{SocketPeers, TestNavigatorBackend};
 use crate::util::test::Test;
+use crate::util::ui::TestUiBackend;
 use anyhow::{anyhow, Result};
 use ruffle_core::backend::audio::{
     swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
     SoundTransform,
 };
+use ruffle_core::backend::camera::SequenceCameraBackend;
+use ruffle_core::backend::local_connection::LocalConnectionBroker;
 use ruffle_core::backend::log::LogBackend;
+use ruffle_core::backend::microphone::WavMicrophoneBackend;
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
 use ruffle_core::events::KeyCode;
 use ruffle_core::events::MouseButton as RuffleMouseButton;
 use ruffle_core::impl_audio_mixer_backend;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::{Player, PlayerBuilder, PlayerEvent};
+use ruffle_core::{Player, PlayerBuilder, PlayerEvent, SandboxType};
 use ruffle_input_format::{AutomatedEvent, InputInjector, MouseButton as InputMouseButton};
 use std::cell::RefCell;
 use std::path::Path;
@@ -77,14 +82,37 @@ pub fn run_swf(
 ) -> Result<String> {
     let base_path = Path::new(&test.output_path).parent().unwrap();
     let mut executor = NullExecutor::new();
-    let movie = SwfMovie::from_path(&test.swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+    let mut movie =
+        SwfMovie::from_path(&test.swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
     let frame_time = 1000.0 / movie.frame_rate().to_f64();
     let frame_time_duration = Duration::from_millis(frame_time as u64);
     let trace_output = Rc::new(RefCell::new(String::new()));
+    let socket_peers = SocketPeers::new(test.options.sockets.clone());
+    let local_connection_broker = LocalConnectionBroker::new();
+    let rtmp_server = if test.options.rtmp_server {
+        let server = rtmp::Server::bind("127.0.0.1:0")?;
+        let address = server.local_addr()?;
+        server.spawn();
+        Some(address)
+    } else {
+        None
+    };
 
-    let builder = PlayerBuilder::new()
+    let mut builder = PlayerBuilder::new()
         .with_log(TestLogBackend::new(trace_output.clone()))
-        .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor)?)
+        .with_navigator(TestNavigatorBackend::new(
+            base_path,
+            &executor,
+            socket_peers.clone(),
+            test.options.security_sandbox.as_ref(),
+            test.options.remoting_gateway.clone(),
+            rtmp_server,
+        )?)
+        .with_ui(TestUiBackend::new(
+            base_path,
+            test.options.file_dialogs.clone(),
+        ))
+        .with_local_connection_broker(local_connection_broker.clone())
         .with_max_execution_duration(Duration::from_secs(300))
         .with_viewport_dimensions(
             movie.width().to_pixels() as u32,
@@ -92,6 +120,40 @@ pub fn run_swf(
             1.0,
         );
 
+    // The microphone plays back a WAV file relative to the test directory.
+    if let Some(microphone) = &test.options.microphone {
+        let data = std::fs::read(base_path.join(microphone))?;
+        builder = builder.with_microphone(WavMicrophoneBackend::new("Test Microphone", &data)?);
+    }
+
+    // The camera plays back a `.y4m` file, or a directory of images at 15 fps.
+    if let Some(camera) = &test.options.camera {
+        let path = base_path.join(camera);
+        let camera = if path.is_dir() {
+            let mut paths = std::fs::read_dir(&path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?;
+            paths.sort();
+            let images = paths
+                .iter()
+                .map(std::fs::read)
+                .collect::<Result<Vec<_>, _>>()?;
+            SequenceCameraBackend::from_images("Test Camera", images, 15)?
+        } else {
+            SequenceCameraBackend::from_y4m("Test Camera", &std::fs::read(path)?)?
+        };
+        builder = builder.with_camera(camera);
+    }
+
+    // Sandboxed tests run as a remote movie, loading their files from the
+    // configured servers.
+    if let Some(security_sandbox) = &test.options.security_sandbox {
+        movie.set_url(security_sandbox.url.clone());
+        builder = builder
+            .with_security_sandbox(true)
+            .with_sandbox_type(SandboxType::Remote);
+    }
+
     // Test player options may override anything set above
     let player = test
         .options
@@ -100,6 +162,26 @@ pub fn run_swf(
         .with_movie(movie)
         .build();
 
+    let peer = test
+        .options
+        .local_connection_peer
+        .as_ref()
+        .map(|peer| -> Result<_> {
+            let mut movie = SwfMovie::from_path(base_path.join(&peer.path), None)
+                .map_err(|e| anyhow!(e.to_string()))?;
+            if let Some(url) = &peer.url {
+                movie.set_url(url.clone());
+            }
+            Ok(PlayerBuilder::new()
+                .with_log(TestLogBackend::new(trace_output.clone()))
+                .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor)?)
+                .with_local_connection_broker(local_connection_broker.clone())
+                .with_max_execution_duration(Duration::from_secs(300))
+                .with_movie(movie)
+                .build())
+        })
+        .transpose()?;
+
     before_start(player.clone())?;
 
     for _ in 0..test.options.num_frames {
@@ -130,6 +212,31 @@ pub fn run_swf(
         player.lock().unwrap().update_timers(frame_time);
         executor.run();
 
+        // Media streams play at the same fixed rate as timers.
+        player.lock().unwrap().update_streams(frame_time);
+
+        // Scripted socket peers answer what was sent during this frame, and
+        // the player then handles their answers.
+        socket_peers.tick();
+        player.lock().unwrap().update_sockets();
+        player.lock().unwrap().update_net_connections(frame_time);
+
+        // The peer runs its frame after the test, and then both handle the
+        // `LocalConnection` messages that were sent to them.
+        if let Some(peer) = &peer {
+            while !peer
+                .lock()
+                .unwrap()
+                .preload(&mut ExecutionLimit::exhausted())
+            {}
+            peer.lock().unwrap().run_frame();
+            peer.lock().unwrap().update_timers(frame_time);
+        }
+        player.lock().unwrap().update_local_connections();
+        if let Some(peer) = &peer {
+            peer.lock().unwrap().update_local_connections();
+        }
+
         injector.next(|evt, _btns_down| {
             player.lock().unwrap().handle_event(match evt {
                 AutomatedEvent::MouseDown { pos, btn } => PlayerEvent::MouseDown {
@@ -166,19 +273,28 @@ pub fn run_swf(
     // FIXME: Determine how we want to compare against on on-disk image
     #[cfg(feature = "imgtests")]
     if let Some(image_comparison) = &test.options.image_comparison {
-        if crate::util::environment::WGPU.is_some() {
+        use crate::util::environment::{USE_SOFTWARE_RENDERER, WGPU};
+
+        if *USE_SOFTWARE_RENDERER || WGPU.is_some() {
             use anyhow::Context;
+            use ruffle_render_software::SoftwareRenderBackend;
             use ruffle_render_wgpu::backend::WgpuRenderBackend;
             use ruffle_render_wgpu::target::TextureTarget;
 
             let mut player_lock = player.lock().unwrap();
             player_lock.render();
-            let renderer = player_lock
-                .renderer_mut()
-                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                .unwrap();
-
-            let actual_image = renderer.capture_frame().expect("Failed to capture image");
+            let renderer = player_lock.renderer_mut();
+            let (actual_image, renderer_name) = if let Some(renderer) =
+                renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+            {
+                (
+                    renderer.capture_frame().expect("Failed to capture image"),
+                    format!("{:?}", renderer.descriptors().adapter.get_info().backend),
+                )
+            } else {
+                let renderer = renderer.downcast_mut::<SoftwareRenderBackend>().unwrap();
+                (renderer.capture_frame(), "Software".to_string())
+            };
 
             let expected_image_path = base_path.join("expected.png");
             if expected_image_path.is_file() {
@@ -186,12 +302,7 @@ pub fn run_swf(
                     .context("Failed to open expected image")?
                     .into_rgba8();
 
-                image_comparison.test(
-                    actual_image,
-                    expected_image,
-                    base_path,
-                    renderer.descriptors().adapter.get_info(),
-                )?;
+                image_comparison.test(actual_image, expected_image, base_path, &renderer_name)?;
             } else {
                 actual_image.save(expected_image_path)?;
             }
@@ -201,6 +312,7 @@ pub fn run_swf(
     before_end(player)?;
 
     executor.run();
+    socket_peers.finish()?;
 
     let trace = trace_output.borrow().clone();
     Ok(trace)
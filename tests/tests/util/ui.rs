@@ -0,0 +1,147 @@
+use crate::util::options::FileDialogAnswer;
+use chrono::{DateTime, Utc};
+use ruffle_core::backend::ui::{
+    FileDialogFuture, FileDialogResult, FileFilter, FullscreenError, MouseCursor, UiBackend,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Files written by the test, which are kept in memory so that tests never
+/// modify their own directory.
+type SavedFiles = Rc<RefCell<HashMap<PathBuf, Vec<u8>>>>;
+
+/// A UI backend that answers file dialogs from the list in the test options.
+pub struct TestUiBackend {
+    base_path: PathBuf,
+    answers: VecDeque<FileDialogAnswer>,
+    saved_files: SavedFiles,
+}
+
+impl TestUiBackend {
+    pub fn new(base_path: &Path, answers: Vec<FileDialogAnswer>) -> Self {
+        Self {
+            base_path: base_path.to_owned(),
+            answers: answers.into(),
+            saved_files: Default::default(),
+        }
+    }
+
+    /// Answers the next file dialog, which is cancelled if the list of
+    /// answers has run out.
+    fn next_answer(&mut self) -> Vec<TestFile> {
+        let answer = self.answers.pop_front().unwrap_or_default();
+        answer
+            .files
+            .into_iter()
+            .map(|file| TestFile {
+                path: self.base_path.join(file),
+                saved_files: self.saved_files.clone(),
+            })
+            .collect()
+    }
+}
+
+impl UiBackend for TestUiBackend {
+    fn mouse_visible(&self) -> bool {
+        true
+    }
+
+    fn set_mouse_visible(&mut self, _visible: bool) {}
+
+    fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+
+    fn set_clipboard_content(&mut self, _content: String) {}
+
+    fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
+        Ok(())
+    }
+
+    fn display_unsupported_message(&self) {}
+
+    fn display_root_movie_download_failed_message(&self) {}
+
+    fn message(&self, _message: &str) {}
+
+    fn open_virtual_keyboard(&self) {}
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Option<FileDialogFuture> {
+        let files = self.next_answer();
+        assert!(
+            multiple || files.len() <= 1,
+            "Test chose several files in a single file dialog"
+        );
+        for file in &files {
+            let name = file.file_name();
+            assert!(
+                filters.is_empty() || filters.iter().any(|filter| filter.matches(&name)),
+                "Test chose {name}, which isn't allowed by the dialog's filters"
+            );
+        }
+
+        let files: Vec<Box<dyn FileDialogResult>> = files
+            .into_iter()
+            .map(|file| Box::new(file) as Box<dyn FileDialogResult>)
+            .collect();
+        Some(Box::pin(async move { files }))
+    }
+
+    fn display_file_save_dialog(&mut self, _file_name: String) -> Option<FileDialogFuture> {
+        let files = self.next_answer();
+        assert!(files.len() <= 1, "Test chose several files to save to");
+
+        let files: Vec<Box<dyn FileDialogResult>> = files
+            .into_iter()
+            .map(|file| Box::new(file) as Box<dyn FileDialogResult>)
+            .collect();
+        Some(Box::pin(async move { files }))
+    }
+}
+
+/// A file chosen by a test, which reads what the test saved to it or else
+/// the file in the test directory.
+struct TestFile {
+    path: PathBuf,
+    saved_files: SavedFiles,
+}
+
+impl FileDialogResult for TestFile {
+    fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn size(&self) -> Option<u64> {
+        self.contents().map(|contents| contents.len() as u64)
+    }
+
+    // Timestamps are left out to keep test output the same on every machine.
+    fn creation_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn modification_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn contents(&self) -> Option<Vec<u8>> {
+        if let Some(contents) = self.saved_files.borrow().get(&self.path) {
+            return Some(contents.clone());
+        }
+        std::fs::read(&self.path).ok()
+    }
+
+    fn write(&self, data: &[u8]) -> bool {
+        self.saved_files
+            .borrow_mut()
+            .insert(self.path.clone(), data.to_vec());
+        true
+    }
+}
@@ -9,6 +9,7 @@ mod ui;
 
 use generational_arena::{Arena, Index};
 use js_sys::{Array, Error as JsError, Function, Object, Promise, Uint8Array};
+use ruffle_core::backend::local_connection::LocalConnectionBroker;
 use ruffle_core::compatibility_rules::CompatibilityRules;
 use ruffle_core::config::Letterbox;
 use ruffle_core::context::UpdateContext;
@@ -50,6 +51,9 @@ thread_local! {
     static INSTANCES: RefCell<Arena<RefCell<RuffleInstance>>> = RefCell::new(Arena::new());
 
     static CURRENT_CONTEXT: RefCell<Option<*mut UpdateContext<'static, 'static>>> = RefCell::new(None);
+
+    /// Routes `LocalConnection` messages between all instances on the page.
+    static LOCAL_CONNECTION_BROKER: LocalConnectionBroker = LocalConnectionBroker::new();
 }
 
 type AnimationHandler = Closure<dyn FnMut(f64)>;
@@ -554,6 +558,7 @@ impl Ruffle {
             .with_log(log_adapter::WebLogBackend::new(trace_observer.clone()))
             .with_ui(ui::WebUiBackend::new(js_player.clone(), &canvas))
             .with_video(SoftwareVideoBackend::new())
+            .with_local_connection_broker(LOCAL_CONNECTION_BROKER.with(Clone::clone))
             .with_letterbox(config.letterbox)
             .with_max_execution_duration(config.max_execution_duration)
             .with_warn_on_unsupported_content(config.warn_on_unsupported_content)
@@ -1,12 +1,15 @@
 //! Navigator backend for web
 use js_sys::{Array, ArrayBuffer, Uint8Array};
 use ruffle_core::backend::navigator::{
-    NavigationMethod, NavigatorBackend, OwnedFuture, Request, Response,
+    ConnectionState, NavigationMethod, NavigatorBackend, OwnedFuture, Request, Response,
+    SocketAction,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use std::borrow::Cow;
+use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing_subscriber::layer::Layered;
 use tracing_subscriber::Registry;
 use tracing_wasm::WASMLayer;
@@ -243,6 +246,19 @@ impl NavigatorBackend for WebNavigatorBackend {
         })
     }
 
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        _timeout: Duration,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        // Browsers cannot open raw TCP connections.
+        tracing::warn!("Cannot connect to {host}:{port}: sockets are not supported on the web");
+        let _ = sender.send(SocketAction::Connect(ConnectionState::Failed));
+    }
+
     fn pre_process_url(&self, mut url: Url) -> Url {
         if self.upgrade_to_https && url.scheme() == "http" && url.set_scheme("https").is_err() {
             tracing::error!("Url::set_scheme failed on: {}", url);
@@ -1,8 +1,14 @@
 use super::JavascriptPlayer;
-use ruffle_core::backend::ui::{FullscreenError, MouseCursor, UiBackend};
+use chrono::{DateTime, TimeZone, Utc};
+use js_sys::{Array, Promise, Uint8Array};
+use ruffle_core::backend::ui::{
+    FileDialogFuture, FileDialogResult, FileFilter, FullscreenError, MouseCursor, UiBackend,
+};
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
-use web_sys::HtmlCanvasElement;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, HtmlAnchorElement, HtmlCanvasElement, HtmlInputElement, Url};
 
 /// An implementation of `UiBackend` utilizing `web_sys` bindings to input APIs.
 pub struct WebUiBackend {
@@ -40,6 +46,95 @@ impl WebUiBackend {
     }
 }
 
+/// A file chosen with an `<input type="file">` element.
+///
+/// Browsers only let us read such files asynchronously, so the contents are
+/// read before the dialog result is returned.
+struct WebOpenedFile {
+    name: String,
+    last_modified: f64,
+    contents: Vec<u8>,
+}
+
+impl FileDialogResult for WebOpenedFile {
+    fn file_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn size(&self) -> Option<u64> {
+        Some(self.contents.len() as u64)
+    }
+
+    fn creation_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn modification_time(&self) -> Option<DateTime<Utc>> {
+        Utc.timestamp_millis_opt(self.last_modified as i64).single()
+    }
+
+    fn contents(&self) -> Option<Vec<u8>> {
+        Some(self.contents.clone())
+    }
+
+    fn write(&self, _data: &[u8]) -> bool {
+        false
+    }
+}
+
+/// A file that is saved by letting the browser download it.
+struct WebSavedFile {
+    name: String,
+}
+
+impl FileDialogResult for WebSavedFile {
+    fn file_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn size(&self) -> Option<u64> {
+        None
+    }
+
+    fn creation_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn modification_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn contents(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn write(&self, data: &[u8]) -> bool {
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return false;
+        };
+        let parts = Array::of1(&Uint8Array::from(data));
+        let Ok(blob) = Blob::new_with_u8_array_sequence(&parts) else {
+            return false;
+        };
+        let Ok(url) = Url::create_object_url_with_blob(&blob) else {
+            return false;
+        };
+
+        let anchor: HtmlAnchorElement = match document
+            .create_element("a")
+            .map(|element| element.dyn_into())
+        {
+            Ok(Ok(anchor)) => anchor,
+            _ => return false,
+        };
+        anchor.set_href(&url);
+        anchor.set_download(&self.name);
+        anchor.click();
+        Url::revoke_object_url(&url).warn_on_error();
+        true
+    }
+}
+
 impl UiBackend for WebUiBackend {
     fn mouse_visible(&self) -> bool {
         self.cursor_visible
@@ -86,4 +181,66 @@ impl UiBackend for WebUiBackend {
     fn open_virtual_keyboard(&self) {
         self.js_player.open_virtual_keyboard()
     }
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Option<FileDialogFuture> {
+        let document = web_sys::window()?.document()?;
+        let input: HtmlInputElement = document.create_element("input").ok()?.dyn_into().ok()?;
+        input.set_type("file");
+        input.set_multiple(multiple);
+        let accept: Vec<_> = filters
+            .iter()
+            .flat_map(|filter| &filter.extensions)
+            .filter(|extension| *extension != "*")
+            .map(|extension| format!(".{extension}"))
+            .collect();
+        input.set_accept(&accept.join(","));
+
+        // The input fires `change` once files are chosen, and `cancel` if the
+        // dialog is closed without choosing any.
+        let closed = Promise::new(&mut |resolve, _reject| {
+            input
+                .add_event_listener_with_callback("change", &resolve)
+                .warn_on_error();
+            input
+                .add_event_listener_with_callback("cancel", &resolve)
+                .warn_on_error();
+        });
+        input.click();
+
+        Some(Box::pin(async move {
+            let _ = JsFuture::from(closed).await;
+
+            let mut results: Vec<Box<dyn FileDialogResult>> = Vec::new();
+            let Some(files) = input.files() else {
+                return results;
+            };
+            for i in 0..files.length() {
+                let Some(file) = files.get(i) else {
+                    continue;
+                };
+                let Ok(buffer) = JsFuture::from(file.array_buffer()).await else {
+                    tracing::warn!("Couldn't read chosen file {}", file.name());
+                    continue;
+                };
+                results.push(Box::new(WebOpenedFile {
+                    name: file.name(),
+                    last_modified: file.last_modified(),
+                    contents: Uint8Array::new(&buffer).to_vec(),
+                }));
+            }
+            results
+        }))
+    }
+
+    fn display_file_save_dialog(&mut self, file_name: String) -> Option<FileDialogFuture> {
+        // Browsers don't let us ask where to save a file, so the file is
+        // downloaded under the suggested name instead.
+        Some(Box::pin(async move {
+            vec![Box::new(WebSavedFile { name: file_name }) as Box<dyn FileDialogResult>]
+        }))
+    }
 }